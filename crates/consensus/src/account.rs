@@ -1,3 +1,5 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
 use alloy_primitives::{keccak256, B256, U256};
 use alloy_rlp::{RlpDecodable, RlpEncodable};
 
@@ -5,6 +7,7 @@ use alloy_rlp::{RlpDecodable, RlpEncodable};
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, RlpDecodable, RlpEncodable)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Account {
     /// The account's nonce.
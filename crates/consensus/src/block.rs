@@ -0,0 +1,197 @@
+use crate::{Header, Request, Sealable, Sealed, TxEnvelope};
+use alloy_eips::{
+    eip4895::Withdrawal,
+    eip7685::{Decodable7685, Encodable7685},
+};
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::{Decodable, Encodable};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A [`Block`] with its memoized hash.
+pub type SealedBlock<T = TxEnvelope> = Sealed<Block<T>>;
+
+/// An Ethereum block: a [`Header`] and the [`BlockBody`] it commits to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Block<T = TxEnvelope> {
+    /// The block header.
+    pub header: Header,
+    /// The block body.
+    pub body: BlockBody<T>,
+}
+
+impl<T> Block<T> {
+    /// Creates a new block from its header and body.
+    pub const fn new(header: Header, body: BlockBody<T>) -> Self {
+        Self { header, body }
+    }
+
+    /// Calculates the hash of the block header.
+    pub fn hash_slow(&self) -> B256 {
+        self.header.hash_slow()
+    }
+}
+
+impl<T> Sealable for Block<T> {
+    fn hash(&self) -> B256 {
+        self.hash_slow()
+    }
+}
+
+impl<T: Encodable> Block<T> {
+    /// Returns the [EIP-7685] requests of the block, each encoded as a byte string.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    fn encoded_requests(&self) -> Option<Vec<Bytes>> {
+        let requests = self.body.requests.as_ref()?;
+        Some(requests.iter().map(|request| request.encoded_7685().into()).collect())
+    }
+
+    fn payload_length(&self, requests: Option<&[Bytes]>) -> usize {
+        self.header.length()
+            + alloy_rlp::list_length(&self.body.transactions)
+            + alloy_rlp::list_length(&self.body.ommers)
+            + self.body.withdrawals.as_deref().map_or(0, alloy_rlp::list_length)
+            + requests.map_or(0, alloy_rlp::list_length::<Bytes, Bytes>)
+    }
+}
+
+/// Encodes the block as in the `eth` wire protocol and `debug_getRawBlock`: the list of the
+/// header, the transactions, the ommers, and the withdrawals and requests if any.
+impl<T: Encodable> Encodable for Block<T> {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        let requests = self.encoded_requests();
+        let payload_length = self.payload_length(requests.as_deref());
+        alloy_rlp::Header { list: true, payload_length }.encode(out);
+        self.header.encode(out);
+        alloy_rlp::encode_list(&self.body.transactions, out);
+        alloy_rlp::encode_list(&self.body.ommers, out);
+        if let Some(withdrawals) = &self.body.withdrawals {
+            alloy_rlp::encode_list(withdrawals, out);
+        }
+        if let Some(requests) = &requests {
+            alloy_rlp::encode_list::<Bytes, Bytes>(requests, out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length(self.encoded_requests().as_deref());
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl<T: Decodable> Decodable for Block<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let rlp_header = alloy_rlp::Header::decode(buf)?;
+        if !rlp_header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        if buf.len() < rlp_header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+        let (mut payload, rest) = buf.split_at(rlp_header.payload_length);
+
+        let header = Header::decode(&mut payload)?;
+        let transactions = Vec::decode(&mut payload)?;
+        let ommers = Vec::decode(&mut payload)?;
+        let withdrawals = (!payload.is_empty()).then(|| Vec::decode(&mut payload)).transpose()?;
+        let requests = (!payload.is_empty())
+            .then(|| {
+                Vec::<Bytes>::decode(&mut payload)?
+                    .iter()
+                    .map(|request| {
+                        Request::decode_7685(&mut request.as_ref())
+                            .map_err(|_| alloy_rlp::Error::Custom("invalid request"))
+                    })
+                    .collect()
+            })
+            .transpose()?;
+        if !payload.is_empty() {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: rlp_header.payload_length,
+                got: rlp_header.payload_length - payload.len(),
+            });
+        }
+
+        *buf = rest;
+        Ok(Self { header, body: BlockBody { transactions, ommers, withdrawals, requests } })
+    }
+}
+
+impl<T> Default for Block<T> {
+    fn default() -> Self {
+        Self { header: Header::default(), body: BlockBody::default() }
+    }
+}
+
+/// The body of an Ethereum [`Block`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BlockBody<T = TxEnvelope> {
+    /// The transactions of the block.
+    pub transactions: Vec<T>,
+    /// The ommer headers of the block, always empty after the merge.
+    pub ommers: Vec<Header>,
+    /// The [EIP-4895] withdrawals of the block, after Shanghai.
+    ///
+    /// [EIP-4895]: https://eips.ethereum.org/EIPS/eip-4895
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// The [EIP-7685] requests of the block, after Prague.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub requests: Option<Vec<Request>>,
+}
+
+impl<T> Default for BlockBody<T> {
+    fn default() -> Self {
+        Self { transactions: Vec::new(), ommers: Vec::new(), withdrawals: None, requests: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Signed, TxEip1559, TxLegacy};
+    use alloy_primitives::{Address, Parity, Signature};
+
+    #[test]
+    fn block_rlp_roundtrip() {
+        let signature = Signature::test_signature();
+        let transactions = vec![
+            TxEnvelope::Legacy(Signed::new_unchecked(
+                TxLegacy::default(),
+                signature.with_parity(Parity::NonEip155(false)),
+                B256::ZERO,
+            )),
+            TxEnvelope::Eip1559(Signed::new_unchecked(
+                TxEip1559 { chain_id: 1, nonce: 1, ..Default::default() },
+                signature,
+                B256::ZERO,
+            )),
+        ];
+        let withdrawal =
+            Withdrawal { index: 1, validator_index: 2, address: Address::ZERO, amount: 3 };
+        let header = Header { number: 1, base_fee_per_gas: Some(7), ..Default::default() };
+
+        for withdrawals in [None, Some(vec![withdrawal])] {
+            let block = Block::new(
+                header.clone(),
+                BlockBody { transactions: transactions.clone(), withdrawals, ..Default::default() },
+            );
+            let encoded = alloy_rlp::encode(&block);
+            assert_eq!(encoded.len(), block.length());
+
+            let decoded = Block::<TxEnvelope>::decode(&mut encoded.as_slice()).unwrap();
+            // The hashes of the transactions are recomputed on decoding.
+            assert_eq!(decoded.header, block.header);
+            assert_eq!(decoded.body.withdrawals, block.body.withdrawals);
+            assert_eq!(decoded.body.transactions.len(), 2);
+            assert_eq!(alloy_rlp::encode(&decoded), encoded);
+        }
+    }
+}
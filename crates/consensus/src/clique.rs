@@ -0,0 +1,210 @@
+//! Helpers for the [Clique] proof-of-authority consensus, which stores the signature of the block
+//! and the list of authorized signers in the `extra_data` of the [`Header`].
+//!
+//! The `extra_data` of a Clique header is made of:
+//! - a vanity of [`EXTRA_VANITY`] bytes, free for the signer to use,
+//! - on checkpoint blocks only, the addresses of the authorized signers, concatenated,
+//! - the seal, a signature of [`EXTRA_SEAL`] bytes of the [`seal_hash`] of the header.
+//!
+//! [Clique]: https://eips.ethereum.org/EIPS/eip-225
+
+use crate::Header;
+use alloy_primitives::{Address, SignatureError, B256};
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The number of bytes of the vanity at the start of the `extra_data`.
+pub const EXTRA_VANITY: usize = 32;
+
+/// The number of bytes of the seal at the end of the `extra_data`.
+pub const EXTRA_SEAL: usize = 65;
+
+/// The default number of blocks between checkpoints.
+pub const DEFAULT_EPOCH: u64 = 30_000;
+
+/// An error reading the Clique fields of a [`Header`].
+#[derive(Debug)]
+pub enum CliqueError {
+    /// The `extra_data` is too short to hold the vanity and the seal.
+    MissingExtraData {
+        /// The length of the `extra_data`.
+        len: usize,
+    },
+    /// The signers of a checkpoint are not a whole number of addresses.
+    InvalidSignerList {
+        /// The length of the signer list.
+        len: usize,
+    },
+    /// The seal is not a valid signature.
+    InvalidSeal(SignatureError),
+}
+
+impl fmt::Display for CliqueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingExtraData { len } => write!(
+                f,
+                "extra data of {len} bytes is too short for the {EXTRA_VANITY} bytes vanity and \
+                 the {EXTRA_SEAL} bytes seal"
+            ),
+            Self::InvalidSignerList { len } => {
+                write!(f, "signer list of {len} bytes is not a list of addresses")
+            }
+            Self::InvalidSeal(err) => write!(f, "invalid seal: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CliqueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidSeal(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<SignatureError> for CliqueError {
+    fn from(err: SignatureError) -> Self {
+        Self::InvalidSeal(err)
+    }
+}
+
+/// Returns whether the block with the given number is a checkpoint, which lists the signers, for
+/// the given epoch length.
+pub const fn is_checkpoint(number: u64, epoch: u64) -> bool {
+    epoch != 0 && number % epoch == 0
+}
+
+/// The parts of the `extra_data` of a Clique header.
+struct ExtraData<'a> {
+    vanity: &'a [u8],
+    signers: &'a [u8],
+    seal: &'a [u8],
+}
+
+impl<'a> ExtraData<'a> {
+    /// Splits the `extra_data` of the header into its vanity, signer list and seal.
+    fn split(header: &'a Header) -> Result<Self, CliqueError> {
+        let extra_data = &header.extra_data[..];
+        if extra_data.len() < EXTRA_VANITY + EXTRA_SEAL {
+            return Err(CliqueError::MissingExtraData { len: extra_data.len() });
+        }
+        let (vanity, rest) = extra_data.split_at(EXTRA_VANITY);
+        let (signers, seal) = rest.split_at(rest.len() - EXTRA_SEAL);
+        Ok(Self { vanity, signers, seal })
+    }
+}
+
+/// Returns the vanity of the header.
+pub fn vanity(header: &Header) -> Result<&[u8], CliqueError> {
+    ExtraData::split(header).map(|extra_data| extra_data.vanity)
+}
+
+/// Returns the seal of the header, the signature of its [`seal_hash`] by the block signer.
+pub fn seal(header: &Header) -> Result<&[u8], CliqueError> {
+    ExtraData::split(header).map(|extra_data| extra_data.seal)
+}
+
+/// Returns the hash signed by the block signer: the hash of the header without the seal.
+pub fn seal_hash(header: &Header) -> Result<B256, CliqueError> {
+    let len = header.extra_data.len();
+    if len < EXTRA_VANITY + EXTRA_SEAL {
+        return Err(CliqueError::MissingExtraData { len });
+    }
+    let unsealed =
+        Header { extra_data: header.extra_data.slice(..len - EXTRA_SEAL), ..header.clone() };
+    Ok(unsealed.hash_slow())
+}
+
+/// Recovers the signer of the block from the seal of the header.
+///
+/// The block signer is not the `beneficiary` of the header, which Clique uses for votes.
+#[cfg(feature = "k256")]
+pub fn recover_signer(header: &Header) -> Result<Address, CliqueError> {
+    let seal = seal(header)?;
+    let signature =
+        alloy_primitives::Signature::from_bytes_and_parity(&seal[..64], seal[64] as u64)?;
+    Ok(signature.recover_address_from_prehash(&seal_hash(header)?)?)
+}
+
+/// Returns the authorized signers listed in a checkpoint header, in the order of the list.
+///
+/// Headers of other blocks list no signers.
+pub fn checkpoint_signers(header: &Header) -> Result<Vec<Address>, CliqueError> {
+    let signers = ExtraData::split(header)?.signers;
+    if signers.len() % Address::len_bytes() != 0 {
+        return Err(CliqueError::InvalidSignerList { len: signers.len() });
+    }
+    Ok(signers.chunks_exact(Address::len_bytes()).map(Address::from_slice).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, Bytes};
+    use k256::ecdsa::SigningKey;
+
+    fn address(key: &SigningKey) -> Address {
+        let point = key.verifying_key().to_encoded_point(false);
+        Address::from_slice(&keccak256(&point.as_bytes()[1..])[12..])
+    }
+
+    /// Returns a header with the given signers, sealed by `key`.
+    fn sealed_header(key: &SigningKey, signers: &[Address]) -> Header {
+        let mut extra_data = vec![7; EXTRA_VANITY];
+        signers.iter().for_each(|signer| extra_data.extend_from_slice(signer.as_slice()));
+        extra_data.extend_from_slice(&[0; EXTRA_SEAL]);
+        let mut header =
+            Header { number: 30_000, extra_data: Bytes::from(extra_data), ..Default::default() };
+
+        let (signature, recovery_id) =
+            key.sign_prehash_recoverable(seal_hash(&header).unwrap().as_slice()).unwrap();
+        let mut extra_data = header.extra_data.to_vec();
+        let seal = extra_data.len() - EXTRA_SEAL;
+        extra_data[seal..seal + 64].copy_from_slice(&signature.to_bytes());
+        extra_data[seal + 64] = recovery_id.to_byte();
+        header.extra_data = extra_data.into();
+        header
+    }
+
+    #[test]
+    fn sealed_checkpoint() {
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let signers = [Address::with_last_byte(1), address(&key)];
+        let header = sealed_header(&key, &signers);
+
+        assert!(is_checkpoint(header.number, DEFAULT_EPOCH));
+        assert_eq!(vanity(&header).unwrap(), &[7; EXTRA_VANITY]);
+        assert_eq!(seal(&header).unwrap().len(), EXTRA_SEAL);
+        assert_eq!(checkpoint_signers(&header).unwrap(), signers);
+
+        #[cfg(feature = "k256")]
+        {
+            assert_eq!(recover_signer(&header).unwrap(), address(&key));
+
+            // The seal covers the rest of the header.
+            let mut tampered = header;
+            tampered.gas_limit += 1;
+            assert_ne!(recover_signer(&tampered).unwrap(), address(&key));
+        }
+    }
+
+    #[test]
+    fn invalid_extra_data() {
+        let header = Header { extra_data: Bytes::from(vec![0; 96]), ..Default::default() };
+        assert!(matches!(vanity(&header), Err(CliqueError::MissingExtraData { len: 96 })));
+        assert!(matches!(seal_hash(&header), Err(CliqueError::MissingExtraData { len: 96 })));
+
+        let header = Header { extra_data: Bytes::from(vec![0; 97]), ..Default::default() };
+        assert!(checkpoint_signers(&header).unwrap().is_empty());
+        let header = Header { extra_data: Bytes::from(vec![0; 97 + 21]), ..Default::default() };
+        assert!(matches!(
+            checkpoint_signers(&header),
+            Err(CliqueError::InvalidSignerList { len: 21 })
+        ));
+    }
+}
@@ -1,5 +1,5 @@
 //! Ethereum protocol-related constants
-use alloy_primitives::{b256, B256};
+use alloy_primitives::{b256, uint, B256, U256};
 
 /// The first four bytes of the call data for a function call specifies the function to be called.
 pub const SELECTOR_LEN: usize = 4;
@@ -16,6 +16,13 @@ pub const FINNEY_TO_WEI: u128 = (GWEI_TO_WEI as u128) * 1_000_000;
 /// Multiplier for converting ether to wei.
 pub const ETH_TO_WEI: u128 = FINNEY_TO_WEI * 1000;
 
+/// Half of the order of the secp256k1 curve. Since Homestead ([EIP-2]), transaction signatures with
+/// an `s` value greater than this are invalid.
+///
+/// [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+pub const SECP256K1N_HALF: U256 =
+    uint!(0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0_U256);
+
 /// Multiplier for converting mgas to gas.
 pub const MGAS_TO_GAS: u64 = 1_000_000u64;
 
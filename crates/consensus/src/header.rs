@@ -1,7 +1,10 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
 use crate::Sealable;
 use alloy_eips::{
     eip1559::{calc_next_block_base_fee, BaseFeeParams},
-    eip4844::{calc_blob_gasprice, calc_excess_blob_gas},
+    eip4844::{calc_blob_gasprice, calc_excess_blob_gas, BlobParams},
+    InMemorySize,
 };
 use alloy_primitives::{b256, keccak256, Address, BlockNumber, Bloom, Bytes, B256, B64, U256};
 use alloy_rlp::{
@@ -22,6 +25,7 @@ pub const EMPTY_ROOT_HASH: B256 =
 
 /// Ethereum Block header
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Header {
@@ -52,6 +56,7 @@ pub struct Header {
     pub logs_bloom: Bloom,
     /// A scalar value corresponding to the difficulty level of this block. This can be calculated
     /// from the previous block’s difficulty level and the timestamp; formally Hd.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
     pub difficulty: U256,
     /// A scalar value equal to the number of ancestor blocks. The genesis block has a number of
     /// zero; formally Hi.
@@ -219,6 +224,13 @@ impl Header {
         self.excess_blob_gas.map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for _this_ block with the given blob parameters of its fork.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None
+    pub fn blob_fee_with_params(&self, params: BlobParams) -> Option<u128> {
+        self.excess_blob_gas.map(|excess| params.calc_blob_fee(excess))
+    }
+
     /// Returns the blob fee for the next block according to the EIP-4844 spec.
     ///
     /// Returns `None` if `excess_blob_gas` is None.
@@ -228,6 +240,16 @@ impl Header {
         self.next_block_excess_blob_gas().map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for the next block with the given blob parameters of its fork.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None.
+    ///
+    /// See also [Self::next_block_excess_blob_gas_with_params]
+    pub fn next_block_blob_fee_with_params(&self, params: BlobParams) -> Option<u128> {
+        self.next_block_excess_blob_gas_with_params(params)
+            .map(|excess| params.calc_blob_fee(excess))
+    }
+
     /// Calculate base fee for next block according to the EIP-1559 spec.
     ///
     /// Returns a `None` if no base fee is set, no EIP-1559 support
@@ -248,6 +270,13 @@ impl Header {
         Some(calc_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 
+    /// Calculate excess blob gas for the next block with the given blob parameters of its fork.
+    ///
+    /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
+    pub fn next_block_excess_blob_gas_with_params(&self, params: BlobParams) -> Option<u128> {
+        Some(params.next_block_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+    }
+
     /// Calculate a heuristic for the in-memory size of the [Header].
     #[inline]
     pub fn size(&self) -> usize {
@@ -467,7 +496,7 @@ impl Decodable for Header {
             if buf.first().map(|b| *b == EMPTY_LIST_CODE).unwrap_or_default() {
                 buf.advance(1)
             } else {
-                this.base_fee_per_gas = Some(U256::decode(buf)?.to::<u128>());
+                this.base_fee_per_gas = Some(u128::decode(buf)?);
             }
         }
 
@@ -485,7 +514,7 @@ impl Decodable for Header {
             if buf.first().map(|b| *b == EMPTY_LIST_CODE).unwrap_or_default() {
                 buf.advance(1)
             } else {
-                this.blob_gas_used = Some(U256::decode(buf)?.to::<u128>());
+                this.blob_gas_used = Some(u128::decode(buf)?);
             }
         }
 
@@ -493,7 +522,7 @@ impl Decodable for Header {
             if buf.first().map(|b| *b == EMPTY_LIST_CODE).unwrap_or_default() {
                 buf.advance(1)
             } else {
-                this.excess_blob_gas = Some(U256::decode(buf)?.to::<u128>());
+                this.excess_blob_gas = Some(u128::decode(buf)?);
             }
         }
 
@@ -512,7 +541,7 @@ impl Decodable for Header {
         //    post-London, so this is technically not valid. However, a tool like proptest would
         //    generate a block like this.
         if started_len - buf.len() < rlp_head.payload_length {
-            this.parent_beacon_block_root = Some(B256::decode(buf)?);
+            this.requests_root = Some(B256::decode(buf)?);
         }
 
         let consumed = started_len - buf.len();
@@ -526,10 +555,42 @@ impl Decodable for Header {
     }
 }
 
+impl InMemorySize for Header {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.extra_data.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn blob_fee_with_params() {
+        let header = Header {
+            excess_blob_gas: Some(10_000_000),
+            blob_gas_used: Some(7 * 131072),
+            ..Default::default()
+        };
+        assert_eq!(header.blob_fee_with_params(BlobParams::CANCUN), header.blob_fee());
+        assert_eq!(
+            header.next_block_blob_fee_with_params(BlobParams::CANCUN),
+            header.next_block_blob_fee()
+        );
+
+        // The Prague target of 6 blobs absorbs more of the blob gas used.
+        let prague = BlobParams::PRAGUE;
+        assert_eq!(
+            header.next_block_excess_blob_gas_with_params(prague),
+            Some(10_000_000 + 131072)
+        );
+        assert!(
+            header.next_block_blob_fee_with_params(prague).unwrap()
+                <= header.next_block_blob_fee().unwrap()
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn header_serde() {
@@ -546,4 +607,36 @@ mod tests {
         let decoded: Header = serde_json::from_str(&json).unwrap();
         assert_eq!(decoded, header);
     }
+
+    #[test]
+    fn header_rlp_roundtrip_requests_root() {
+        let header = Header {
+            base_fee_per_gas: Some(1),
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::with_last_byte(1)),
+            requests_root: Some(B256::with_last_byte(2)),
+            ..Default::default()
+        };
+
+        let encoded = alloy_rlp::encode(&header);
+        assert_eq!(encoded.len(), header.length());
+        assert_eq!(Header::decode(&mut &encoded[..]).unwrap(), header);
+    }
+
+    #[test]
+    fn header_rlp_rejects_overflowing_base_fee() {
+        let header = Header { base_fee_per_gas: Some(1), ..Default::default() };
+        let mut encoded = alloy_rlp::encode(&header);
+
+        // Replace the base fee, the last field, with a 17-byte integer.
+        encoded.pop();
+        encoded.push(0x80 + 17);
+        encoded.extend_from_slice(&[0xff; 17]);
+        let payload_length = (encoded.len() - 3) as u16;
+        encoded[1..3].copy_from_slice(&payload_length.to_be_bytes());
+
+        assert!(Header::decode(&mut &encoded[..]).is_err());
+    }
 }
@@ -10,17 +10,36 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+// Required by the `Arbitrary` derives of `alloy-primitives`, enabled by the `proptest` feature.
+#[cfg(all(feature = "proptest", not(feature = "arbitrary")))]
+use arbitrary as _;
+
+// Only used to generate validly signed transactions with the `proptest` feature.
+#[cfg(all(feature = "k256", not(feature = "proptest")))]
+use k256 as _;
+
 mod account;
 pub use account::Account;
 
 pub mod constants;
 
+mod block;
+pub use block::{Block, BlockBody, SealedBlock};
+
+pub mod clique;
+
 mod header;
 pub use header::{Header, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
 
+mod payload;
+pub use payload::{BuiltPayload, PayloadBuilder};
+
+pub mod proofs;
+
 mod receipt;
 pub use receipt::{
-    AnyReceiptEnvelope, Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom, TxReceipt,
+    AnyReceiptEnvelope, DepositReceipt, Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom,
+    TxReceipt,
 };
 
 mod request;
@@ -30,10 +49,13 @@ pub mod transaction;
 #[cfg(feature = "kzg")]
 pub use transaction::BlobTransactionValidationError;
 pub use transaction::{
-    SignableTransaction, Transaction, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant,
-    TxEip4844WithSidecar, TxEnvelope, TxLegacy, TxType, TypedTransaction,
+    SignableTransaction, Transaction, TxDeposit, TxEip1559, TxEip2930, TxEnvelope, TxLegacy,
+    TxType, TypedTransaction,
 };
+#[cfg(feature = "eip4844")]
+pub use transaction::{TxEip4844, TxEip4844Variant, TxEip4844WithSidecar};
 
+#[cfg(feature = "eip4844")]
 pub use alloy_eips::eip4844::{
     builder::{SidecarBuilder, SidecarCoder, SimpleCoder},
     utils, Blob, BlobTransactionSidecar, Bytes48,
@@ -42,8 +64,16 @@ pub use alloy_eips::eip4844::{
 #[cfg(feature = "kzg")]
 pub use alloy_eips::eip4844::env_settings::EnvKzgSettings;
 
+#[cfg(feature = "serde-bincode-compat")]
+pub mod serde_bincode_compat;
+
 mod sealed;
 pub use sealed::{Sealable, Sealed};
 
 mod signed;
-pub use signed::Signed;
+pub use signed::{RecoveryPolicy, Signed};
+
+#[cfg(feature = "eip4844")]
+mod validation;
+#[cfg(feature = "eip4844")]
+pub use validation::{validate_blob_gas, BlobGasError};
@@ -0,0 +1,304 @@
+use crate::{
+    proofs::{
+        calculate_receipts_root, calculate_requests_root, calculate_transactions_root,
+        calculate_withdrawals_root,
+    },
+    Block, BlockBody, Eip658Value, Header, Receipt, ReceiptEnvelope, Request, TxEnvelope, TxType,
+    EMPTY_OMMER_ROOT_HASH,
+};
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::{Bloom, B256};
+
+#[cfg(feature = "eip4844")]
+use crate::{BlobTransactionSidecar, Signed, TxEip4844Variant};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// A builder assembling a [`Block`] from a header template and the contents of its body, as a
+/// block builder would assemble an execution payload.
+///
+/// All the roots of the header committing to the body, the logs bloom, and the gas totals are
+/// computed from the body. The state root cannot be, and is taken from the template with the
+/// other fields, e.g. the parent hash, fee recipient, or base fee.
+///
+/// Blob sidecars are split from the transactions they are sent with, as blocks only commit to the
+/// blob versioned hashes.
+///
+/// ```
+/// use alloy_consensus::{Header, PayloadBuilder, EMPTY_ROOT_HASH};
+/// use alloy_eips::eip4895::Withdrawal;
+///
+/// let template = Header {
+///     number: 1,
+///     gas_limit: 30_000_000,
+///     base_fee_per_gas: Some(7),
+///     withdrawals_root: Some(EMPTY_ROOT_HASH),
+///     ..Default::default()
+/// };
+/// let payload = PayloadBuilder::new(template)
+///     .withdrawals(vec![Withdrawal {
+///         index: 0,
+///         validator_index: 1,
+///         amount: 32,
+///         ..Default::default()
+///     }])
+///     .build();
+/// assert_ne!(payload.block.header.withdrawals_root, Some(EMPTY_ROOT_HASH));
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "this builder does nothing unless built"]
+pub struct PayloadBuilder {
+    header: Header,
+    transactions: Vec<TxEnvelope>,
+    receipts: Vec<ReceiptEnvelope>,
+    withdrawals: Option<Vec<Withdrawal>>,
+    requests: Option<Vec<Request>>,
+    #[cfg(feature = "eip4844")]
+    sidecars: Vec<BlobTransactionSidecar>,
+}
+
+impl PayloadBuilder {
+    /// Creates a new builder of an empty block, from a header template.
+    ///
+    /// Withdrawals and requests default to none, or to an empty list if the template has a
+    /// withdrawals root or a requests root, respectively.
+    pub fn new(template: Header) -> Self {
+        Self {
+            transactions: Vec::new(),
+            receipts: Vec::new(),
+            withdrawals: template.withdrawals_root.map(|_| Vec::new()),
+            requests: template.requests_root.map(|_| Vec::new()),
+            #[cfg(feature = "eip4844")]
+            sidecars: Vec::new(),
+            header: template,
+        }
+    }
+
+    /// Appends a transaction to the block, assuming it succeeds without logs and uses all its gas.
+    pub fn transaction(self, tx: impl Into<TxEnvelope>) -> Self {
+        let tx = tx.into();
+        let gas_used = self.gas_used() + gas_limit(&tx);
+        let receipt = Receipt {
+            status: Eip658Value::Eip658(true),
+            cumulative_gas_used: gas_used,
+            logs: vec![],
+        };
+        self.transaction_with_receipt(tx, receipt)
+    }
+
+    /// Appends a transaction to the block, with the receipt of its execution after the previous
+    /// transactions, including the cumulative gas used.
+    pub fn transaction_with_receipt(mut self, tx: impl Into<TxEnvelope>, receipt: Receipt) -> Self {
+        let tx = self.split_sidecar(tx.into());
+        let receipt = receipt.with_bloom();
+        self.receipts.push(match tx.tx_type() {
+            TxType::Legacy => ReceiptEnvelope::Legacy(receipt),
+            TxType::Eip2930 => ReceiptEnvelope::Eip2930(receipt),
+            TxType::Eip1559 => ReceiptEnvelope::Eip1559(receipt),
+            TxType::Eip4844 => ReceiptEnvelope::Eip4844(receipt),
+        });
+        self.transactions.push(tx);
+        self
+    }
+
+    /// Sets the [EIP-4895] withdrawals of the block.
+    ///
+    /// [EIP-4895]: https://eips.ethereum.org/EIPS/eip-4895
+    pub fn withdrawals(mut self, withdrawals: Vec<Withdrawal>) -> Self {
+        self.withdrawals = Some(withdrawals);
+        self
+    }
+
+    /// Sets the [EIP-7685] requests of the block.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    pub fn requests(mut self, requests: Vec<Request>) -> Self {
+        self.requests = Some(requests);
+        self
+    }
+
+    /// Assembles the block, computing the header fields committing to its body.
+    ///
+    /// The blob gas used is only set if the template has an excess blob gas, or the block has blob
+    /// transactions.
+    pub fn build(self) -> BuiltPayload {
+        let gas_used = self.gas_used();
+        let mut header = self.header;
+        header.ommers_hash = EMPTY_OMMER_ROOT_HASH;
+        header.transactions_root = calculate_transactions_root(&self.transactions);
+        header.receipts_root = calculate_receipts_root(&self.receipts);
+        header.logs_bloom = self.receipts.iter().fold(Bloom::ZERO, |mut bloom, receipt| {
+            bloom |= *receipt.logs_bloom();
+            bloom
+        });
+        header.gas_used = gas_used;
+        header.withdrawals_root = self.withdrawals.as_deref().map(calculate_withdrawals_root);
+        header.requests_root = self.requests.as_deref().map(calculate_requests_root);
+
+        let blob_gas_used: u128 = self.transactions.iter().map(blob_gas).sum();
+        if header.excess_blob_gas.is_some() || blob_gas_used > 0 {
+            header.blob_gas_used = Some(blob_gas_used);
+        }
+
+        BuiltPayload {
+            block: Block::new(
+                header,
+                BlockBody {
+                    transactions: self.transactions,
+                    ommers: Vec::new(),
+                    withdrawals: self.withdrawals,
+                    requests: self.requests,
+                },
+            ),
+            receipts: self.receipts,
+            #[cfg(feature = "eip4844")]
+            sidecars: self.sidecars,
+        }
+    }
+
+    /// Returns the gas used by the transactions appended so far.
+    fn gas_used(&self) -> u128 {
+        self.receipts.last().map_or(0, |receipt| receipt.cumulative_gas_used())
+    }
+
+    /// Moves the blob sidecar of the transaction, if any, to the blobs bundle.
+    fn split_sidecar(&mut self, tx: TxEnvelope) -> TxEnvelope {
+        #[cfg(feature = "eip4844")]
+        if let TxEnvelope::Eip4844(signed) = tx {
+            let (tx, signature, hash) = signed.into_parts();
+            let tx = match tx {
+                TxEip4844Variant::TxEip4844WithSidecar(tx) => {
+                    let (tx, sidecar) = tx.into_parts();
+                    self.sidecars.push(sidecar);
+                    TxEip4844Variant::TxEip4844(tx)
+                }
+                tx => tx,
+            };
+            return TxEnvelope::Eip4844(Signed::new_unchecked(tx, signature, hash));
+        }
+        tx
+    }
+}
+
+/// A block assembled by a [`PayloadBuilder`], with the receipts and blob sidecars of its
+/// transactions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuiltPayload {
+    /// The assembled block.
+    pub block: Block,
+    /// The receipts of the transactions of the block.
+    pub receipts: Vec<ReceiptEnvelope>,
+    /// The sidecars of the blob transactions of the block, in order.
+    #[cfg(feature = "eip4844")]
+    pub sidecars: Vec<BlobTransactionSidecar>,
+}
+
+impl BuiltPayload {
+    /// Calculates the hash of the block.
+    pub fn hash_slow(&self) -> B256 {
+        self.block.hash_slow()
+    }
+}
+
+/// Returns the gas limit of the transaction.
+const fn gas_limit(tx: &TxEnvelope) -> u128 {
+    match tx {
+        TxEnvelope::Legacy(tx) => tx.tx().gas_limit,
+        TxEnvelope::Eip2930(tx) => tx.tx().gas_limit,
+        TxEnvelope::Eip1559(tx) => tx.tx().gas_limit,
+        #[cfg(feature = "eip4844")]
+        TxEnvelope::Eip4844(tx) => tx.tx().tx().gas_limit,
+    }
+}
+
+/// Returns the blob gas used by the transaction.
+#[cfg_attr(not(feature = "eip4844"), allow(clippy::missing_const_for_fn))]
+fn blob_gas(tx: &TxEnvelope) -> u128 {
+    match tx {
+        #[cfg(feature = "eip4844")]
+        TxEnvelope::Eip4844(tx) => tx.tx().tx().blob_gas(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::{b256, bytes, hex};
+
+    // <https://github.com/alloy-rs/alloy/blob/main/crates/rpc-types-engine/src/payload.rs>, pulled
+    // from hive tests.
+    #[test]
+    fn builds_hive_payload() {
+        let template = Header {
+            parent_hash: b256!("67ead97eb79b47a1638659942384143f36ed44275d4182799875ab5a87324055"),
+            state_root: b256!("76a03cbcb7adce07fd284c61e4fa31e5e786175cefac54a29e46ec8efa28ea41"),
+            mix_hash: b256!("028111cb7d25918386a69656b3d17b2febe95fd0f11572c1a55c14f99fdfe3df"),
+            number: 1,
+            gas_limit: 0x2fefd8,
+            timestamp: 0x1235,
+            extra_data: bytes!("8b726574682f76302e312e30"),
+            base_fee_per_gas: Some(0x342770c0),
+            ..Default::default()
+        };
+        let tx = TxEnvelope::decode_2718(&mut &hex!("f865808506fc23ac00830124f8940000000000000000000000000000000000000316018032a044b25a8b9b247d01586b3d59c71728ff49c9b84928d9e7fa3377ead3b5570b5da03ceac696601ff7ee6f5fe8864e2998db9babdf5eeba1a0cd5b4d44b3fcbd181b")[..]).unwrap();
+        let receipt = Receipt {
+            status: Eip658Value::Eip658(true),
+            cumulative_gas_used: 0xa860,
+            logs: vec![],
+        };
+        let payload = PayloadBuilder::new(template).transaction_with_receipt(tx, receipt).build();
+
+        let header = &payload.block.header;
+        assert_eq!(
+            header.receipts_root,
+            b256!("4e3c608a9f2e129fccb91a1dae7472e78013b8e654bccc8d224ce3d63ae17006")
+        );
+        assert_eq!(header.gas_used, 0xa860);
+        assert_eq!(header.withdrawals_root, None);
+        assert_eq!(header.blob_gas_used, None);
+        assert_eq!(
+            payload.hash_slow(),
+            b256!("a6f40ed042e61e88e76125dede8fff8026751ea14454b68fb534cea99f2b2a77")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "eip4844")]
+    fn splits_blob_sidecars() {
+        use crate::{TxEip4844, TxEip4844WithSidecar};
+        use alloy_primitives::{address, Signature, U256};
+
+        let tx = TxEip4844 {
+            chain_id: 1,
+            gas_limit: 21_000,
+            max_fee_per_gas: 10,
+            to: address!("000000000000000000000000000000000000dEaD"),
+            blob_versioned_hashes: vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+            ..Default::default()
+        };
+        let sidecar = BlobTransactionSidecar::default();
+        let tx = TxEip4844WithSidecar::from_tx_and_sidecar(tx, sidecar.clone());
+        let signature = Signature::from_rs_and_parity(U256::from(1), U256::from(1), false).unwrap();
+        let tx = Signed::new_unchecked(tx, signature, B256::with_last_byte(3));
+
+        let template = Header { excess_blob_gas: Some(0), ..Default::default() };
+        let payload = PayloadBuilder::new(template).transaction(tx.clone()).transaction(tx).build();
+
+        assert_eq!(payload.sidecars, [sidecar.clone(), sidecar]);
+        let header = &payload.block.header;
+        assert_eq!(header.gas_used, 42_000);
+        assert_eq!(header.blob_gas_used, Some(4 * 131_072));
+        assert!(payload.block.body.transactions.iter().all(|tx| matches!(
+            tx,
+            TxEnvelope::Eip4844(tx) if matches!(tx.tx(), TxEip4844Variant::TxEip4844(_))
+        )));
+        assert_eq!(
+            header.transactions_root,
+            calculate_transactions_root(&payload.block.body.transactions)
+        );
+        assert_eq!(payload.receipts[1].cumulative_gas_used(), 42_000);
+    }
+}
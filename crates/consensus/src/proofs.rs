@@ -0,0 +1,270 @@
+//! Merkle Patricia trie roots of the block body, committed to in the [`Header`], and proofs of
+//! inclusion of their items.
+//!
+//! The tries are built with the [`HashBuilder`] of `alloy-trie`, and proofs are checked with its
+//! [`verify_proof`].
+
+use crate::{Header, Request};
+use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawal, eip7685::Encodable7685};
+use alloy_primitives::{keccak256, Bytes, B256};
+use alloy_rlp::Encodable;
+use alloy_trie::{proof::ProofRetainer, HashBuilder};
+
+pub use alloy_trie::{
+    proof::{verify_proof, ProofVerificationError},
+    Nibbles,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Calculates the root of the trie of the given items, keyed by the RLP encoding of their index,
+/// and encoded with `encode`.
+pub fn ordered_trie_root_with_encoder<T, F>(items: &[T], encode: F) -> B256
+where
+    F: FnMut(&T, &mut Vec<u8>),
+{
+    let mut hash_builder = HashBuilder::default();
+    for (key, value) in ordered_leaves(items, encode) {
+        hash_builder.add_leaf(key, &value);
+    }
+    hash_builder.root()
+}
+
+/// Calculates the root of the trie of the given items, keyed by the RLP encoding of their index,
+/// and RLP encoded.
+pub fn ordered_trie_root<T: Encodable>(items: &[T]) -> B256 {
+    ordered_trie_root_with_encoder(items, |item, out| item.encode(out))
+}
+
+/// Calculates the transactions root of a block, from its [EIP-2718] encoded transactions.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub fn calculate_transactions_root<T: Encodable2718>(transactions: &[T]) -> B256 {
+    ordered_trie_root_with_encoder(transactions, |tx, out| tx.encode_2718(out))
+}
+
+/// Calculates the receipts root of a block, from its [EIP-2718] encoded receipts.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub fn calculate_receipts_root<T: Encodable2718>(receipts: &[T]) -> B256 {
+    ordered_trie_root_with_encoder(receipts, |receipt, out| receipt.encode_2718(out))
+}
+
+/// Generates the proof of the item at `index` in the trie of [`ordered_trie_root_with_encoder`],
+/// or `None` if `index` is out of bounds.
+///
+/// The proof is the RLP encoding of the nodes on the path from the root to the item, as returned
+/// by `eth_getProof`, and is checked with [`verify_proof`] against the key
+/// `Nibbles::unpack(alloy_rlp::encode(index))`.
+pub fn ordered_trie_proof_with_encoder<T, F>(
+    items: &[T],
+    index: usize,
+    encode: F,
+) -> Option<Vec<Bytes>>
+where
+    F: FnMut(&T, &mut Vec<u8>),
+{
+    if index >= items.len() {
+        return None;
+    }
+    let target = Nibbles::unpack(alloy_rlp::encode(index));
+    let mut hash_builder =
+        HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![target]));
+    for (key, value) in ordered_leaves(items, encode) {
+        hash_builder.add_leaf(key, &value);
+    }
+    hash_builder.root();
+    // The retained nodes are keyed by their path, so they are sorted from the root to the item.
+    Some(hash_builder.take_proofs().into_values().collect())
+}
+
+/// Generates the proof of the receipt at `index` of a block, verifiable against the receipts
+/// root of its header with [`verify_receipt_proof`], or `None` if `index` is out of bounds.
+///
+/// Such proofs show that a transaction emitted an event on a chain to a verifier that only
+/// trusts the headers of the chain, e.g. a cross-chain messaging contract.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_consensus::{
+///     proofs::{calculate_receipts_root, generate_receipt_proof, verify_receipt_proof},
+///     Eip658Value, Receipt, ReceiptEnvelope,
+/// };
+///
+/// let receipts: Vec<ReceiptEnvelope> = (1..=3)
+///     .map(|gas| {
+///         let receipt = Receipt {
+///             status: Eip658Value::Eip658(true),
+///             cumulative_gas_used: gas * 21_000,
+///             logs: vec![],
+///         };
+///         ReceiptEnvelope::Eip1559(receipt.with_bloom())
+///     })
+///     .collect();
+/// let receipts_root = calculate_receipts_root(&receipts);
+///
+/// let proof = generate_receipt_proof(&receipts, 1).unwrap();
+/// assert!(verify_receipt_proof(receipts_root, 1, &receipts[1], &proof).is_ok());
+/// ```
+pub fn generate_receipt_proof<T: Encodable2718>(
+    receipts: &[T],
+    index: usize,
+) -> Option<Vec<Bytes>> {
+    ordered_trie_proof_with_encoder(receipts, index, |receipt, out| receipt.encode_2718(out))
+}
+
+/// Verifies a proof of [`generate_receipt_proof`] that `receipt` is the receipt at `index` of the
+/// block with the given receipts root.
+#[allow(clippy::result_large_err)]
+pub fn verify_receipt_proof<T: Encodable2718>(
+    receipts_root: B256,
+    index: usize,
+    receipt: &T,
+    proof: &[Bytes],
+) -> Result<(), ProofVerificationError> {
+    let key = Nibbles::unpack(alloy_rlp::encode(index));
+    verify_proof(receipts_root, key, Some(receipt.encoded_2718()), proof)
+}
+
+/// Calculates the [EIP-4895] withdrawals root of a block.
+///
+/// [EIP-4895]: https://eips.ethereum.org/EIPS/eip-4895
+pub fn calculate_withdrawals_root(withdrawals: &[Withdrawal]) -> B256 {
+    ordered_trie_root(withdrawals)
+}
+
+/// Calculates the [EIP-7685] requests root of a block.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+pub fn calculate_requests_root(requests: &[Request]) -> B256 {
+    ordered_trie_root_with_encoder(requests, |request, out| request.encode_7685(out))
+}
+
+/// Calculates the ommers hash of a block.
+pub fn calculate_ommers_root(ommers: &[Header]) -> B256 {
+    let mut out = Vec::new();
+    alloy_rlp::encode_list(ommers, &mut out);
+    keccak256(out)
+}
+
+/// Returns the leaves of the trie of the given items, keyed by the RLP encoding of their index,
+/// in the key order expected by the [`HashBuilder`].
+fn ordered_leaves<T, F>(items: &[T], mut encode: F) -> Vec<(Nibbles, Vec<u8>)>
+where
+    F: FnMut(&T, &mut Vec<u8>),
+{
+    let mut leaves: Vec<_> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let mut value = Vec::new();
+            encode(item, &mut value);
+            (Nibbles::unpack(alloy_rlp::encode(index)), value)
+        })
+        .collect();
+    leaves.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    leaves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Eip658Value, Receipt, ReceiptEnvelope, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::{b256, hex, Address, Log};
+
+    // <https://etherscan.io/block/14764013>, whose EIP-2718 encoded receipts are those of the
+    // Portal Network test assets.
+    #[test]
+    fn mainnet_receipt_proofs() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/mainnet_14764013_receipts.rlp");
+        let data = std::fs::read_to_string(path).unwrap();
+        let receipts: Vec<ReceiptEnvelope> = data
+            .lines()
+            .map(|line| ReceiptEnvelope::decode_2718(&mut hex::decode(line).unwrap().as_slice()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(receipts.len(), 19);
+        assert_eq!(receipts[18].cumulative_gas_used(), 1_314_225);
+
+        let root = calculate_receipts_root(&receipts);
+        assert_eq!(root, b256!("168a3827607627e781941dc777737fc4b6beb69a8b139240b881992b35b854ea"));
+        for (index, receipt) in receipts.iter().enumerate() {
+            let proof = generate_receipt_proof(&receipts, index).unwrap();
+            assert_eq!(verify_receipt_proof(root, index, receipt, &proof), Ok(()));
+            let other = &receipts[(index + 1) % receipts.len()];
+            assert!(verify_receipt_proof(root, index, other, &proof).is_err());
+        }
+    }
+
+    #[test]
+    fn receipt_proofs() {
+        let receipts: Vec<_> = (0..300)
+            .map(|index| {
+                let receipt = Receipt {
+                    status: Eip658Value::Eip658(index % 3 != 0),
+                    cumulative_gas_used: 21_000 * (index + 1),
+                    logs: vec![Log::new_unchecked(
+                        Address::with_last_byte(index as u8),
+                        vec![B256::with_last_byte(index as u8)],
+                        Bytes::from(vec![index as u8; 40]),
+                    )],
+                };
+                ReceiptEnvelope::Eip1559(receipt.with_bloom())
+            })
+            .collect();
+        let root = calculate_receipts_root(&receipts);
+
+        for index in [0, 1, 127, 128, 255, 299] {
+            let proof = generate_receipt_proof(&receipts, index).unwrap();
+            assert_eq!(verify_receipt_proof(root, index, &receipts[index], &proof), Ok(()));
+            let other = &receipts[(index + 1) % receipts.len()];
+            assert!(verify_receipt_proof(root, index, other, &proof).is_err());
+        }
+        assert_eq!(generate_receipt_proof(&receipts, 300), None);
+
+        let proof = generate_receipt_proof(&receipts, 42).unwrap();
+        let receipt = &receipts[42];
+        assert!(matches!(
+            verify_receipt_proof(B256::ZERO, 42, receipt, &proof),
+            Err(ProofVerificationError::ValueMismatch { .. })
+        ));
+        assert!(verify_receipt_proof(root, 42, receipt, &proof[..proof.len() - 1]).is_err());
+        let mut tampered = proof.clone();
+        tampered[1] = Bytes::from_static(&[0xc0]);
+        assert!(verify_receipt_proof(root, 42, receipt, &tampered).is_err());
+        let mut extended = proof.clone();
+        extended.push(Bytes::from_static(&[0xc0]));
+        assert!(verify_receipt_proof(root, 42, receipt, &extended).is_err());
+        assert!(verify_receipt_proof(root, 43, receipt, &proof).is_err());
+    }
+
+    #[test]
+    fn ordered_roots() {
+        assert_eq!(calculate_ommers_root(&[]), EMPTY_OMMER_ROOT_HASH);
+        assert_eq!(calculate_withdrawals_root(&[]), EMPTY_ROOT_HASH);
+
+        // The RLP encoding of index 0 sorts after the ones of 1..=127.
+        let withdrawals: Vec<_> = (0..200)
+            .map(|index| Withdrawal {
+                index,
+                validator_index: index * 3,
+                address: Address::with_last_byte(index as u8),
+                amount: 32_000_000_000 + index,
+            })
+            .collect();
+        // Roots computed with `alloy_trie::HashBuilder`.
+        assert_eq!(
+            calculate_withdrawals_root(&withdrawals),
+            b256!("40c70f1f041019016216b2a213e5134c8e8edc485f59c90bc115425f77fd3418")
+        );
+        let mut reversed = withdrawals;
+        reversed.reverse();
+        assert_eq!(
+            calculate_withdrawals_root(&reversed),
+            b256!("6ccd139b26d8cf3df61dd81ec38e9d733471f5d51ee5655a8f215a50d2bb1195")
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{Eip658Value, ReceiptWithBloom, TxReceipt};
-use alloy_eips::eip2718::{Decodable2718, Eip2718Result, Encodable2718};
+use alloy_eips::{
+    eip2718::{Decodable2718, Eip2718Result, Encodable2718, Typed2718},
+    InMemorySize,
+};
 use alloy_primitives::{bytes::BufMut, Bloom, Log};
 use alloy_rlp::{Decodable, Encodable};
 
@@ -108,14 +111,13 @@ impl<T> TxReceipt<T> for AnyReceiptEnvelope<T> {
     }
 }
 
-impl Encodable2718 for AnyReceiptEnvelope {
-    fn type_flag(&self) -> Option<u8> {
-        match self.r#type {
-            0 => None,
-            ty => Some(ty),
-        }
+impl Typed2718 for AnyReceiptEnvelope {
+    fn ty(&self) -> u8 {
+        self.r#type
     }
+}
 
+impl Encodable2718 for AnyReceiptEnvelope {
     fn encode_2718_len(&self) -> usize {
         self.inner.length() + !self.is_legacy() as usize
     }
@@ -139,3 +141,10 @@ impl Decodable2718 for AnyReceiptEnvelope {
         Self::typed_decode(0, buf)
     }
 }
+
+impl<T: InMemorySize> InMemorySize for AnyReceiptEnvelope<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.inner.heap_size()
+    }
+}
@@ -0,0 +1,238 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
+use crate::{
+    receipt::{Eip658Value, TxReceipt},
+    transaction::DEPOSIT_TX_TYPE_ID,
+    ReceiptWithBloom,
+};
+use alloy_eips::{
+    eip2718::{Decodable2718, Eip2718Error, Eip2718Result, Encodable2718, Typed2718},
+    InMemorySize,
+};
+use alloy_primitives::{Bloom, Log};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+
+/// The receipt of a [`TxDeposit`](crate::TxDeposit) transaction.
+///
+/// On top of the fields of regular receipts, deposit receipts store the nonce of the sender at
+/// the time of the deposit since Regolith, and the version of the receipt since Canyon.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[doc(alias = "DepositTransactionReceipt", alias = "DepositTxReceipt")]
+pub struct DepositReceipt<T = Log> {
+    /// The receipt, with its bloom filter.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub inner: ReceiptWithBloom<T>,
+    /// The nonce of the sender before the deposit was executed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::quantity::opt"
+        )
+    )]
+    pub deposit_nonce: Option<u64>,
+    /// The version of the deposit receipt, set to 1 since Canyon.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::quantity::opt"
+        )
+    )]
+    pub deposit_receipt_version: Option<u64>,
+}
+
+impl<T> DepositReceipt<T> {
+    /// Creates a new [`DepositReceipt`].
+    pub const fn new(
+        inner: ReceiptWithBloom<T>,
+        deposit_nonce: Option<u64>,
+        deposit_receipt_version: Option<u64>,
+    ) -> Self {
+        Self { inner, deposit_nonce, deposit_receipt_version }
+    }
+}
+
+impl<T: Encodable> DepositReceipt<T> {
+    fn payload_len(&self) -> usize {
+        self.inner.receipt.status.length()
+            + self.inner.receipt.cumulative_gas_used.length()
+            + self.inner.logs_bloom.length()
+            + self.inner.receipt.logs.length()
+            + self.deposit_nonce.map_or(0, |nonce| nonce.length())
+            + self.deposit_receipt_version.map_or(0, |version| version.length())
+    }
+}
+
+impl<T> TxReceipt<T> for DepositReceipt<T> {
+    fn status_or_post_state(&self) -> &Eip658Value {
+        self.inner.status_or_post_state()
+    }
+
+    fn status(&self) -> bool {
+        self.inner.status()
+    }
+
+    fn bloom(&self) -> Bloom {
+        self.inner.logs_bloom
+    }
+
+    fn bloom_cheap(&self) -> Option<Bloom> {
+        Some(self.inner.logs_bloom)
+    }
+
+    fn cumulative_gas_used(&self) -> u128 {
+        self.inner.receipt.cumulative_gas_used
+    }
+
+    fn logs(&self) -> &[T] {
+        &self.inner.receipt.logs
+    }
+}
+
+impl<T: Encodable> Encodable for DepositReceipt<T> {
+    /// Encodes the receipt fields, followed by the deposit nonce and receipt version if set.
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_len() }.encode(out);
+        self.inner.receipt.status.encode(out);
+        self.inner.receipt.cumulative_gas_used.encode(out);
+        self.inner.logs_bloom.encode(out);
+        self.inner.receipt.logs.encode(out);
+        if let Some(nonce) = self.deposit_nonce {
+            nonce.encode(out);
+        }
+        if let Some(version) = self.deposit_receipt_version {
+            version.encode(out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_len();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+}
+
+impl<T: Decodable> Decodable for DepositReceipt<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let started_len = buf.len();
+
+        let status = Decodable::decode(buf)?;
+        let cumulative_gas_used = Decodable::decode(buf)?;
+        let logs_bloom = Decodable::decode(buf)?;
+        let logs = Decodable::decode(buf)?;
+
+        // The deposit fields are only present after Regolith and Canyon respectively.
+        let decode_optional = |buf: &mut &[u8]| {
+            let consumed = started_len - buf.len();
+            (consumed < header.payload_length).then(|| u64::decode(buf)).transpose()
+        };
+        let deposit_nonce = decode_optional(buf)?;
+        let deposit_receipt_version = decode_optional(buf)?;
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            });
+        }
+
+        let receipt = crate::Receipt { status, cumulative_gas_used, logs };
+        Ok(Self {
+            inner: ReceiptWithBloom::new(receipt, logs_bloom),
+            deposit_nonce,
+            deposit_receipt_version,
+        })
+    }
+}
+
+impl Typed2718 for DepositReceipt {
+    fn ty(&self) -> u8 {
+        DEPOSIT_TX_TYPE_ID
+    }
+}
+
+impl Encodable2718 for DepositReceipt {
+    fn encode_2718_len(&self) -> usize {
+        self.length() + 1
+    }
+
+    fn encode_2718(&self, out: &mut dyn BufMut) {
+        out.put_u8(DEPOSIT_TX_TYPE_ID);
+        self.encode(out);
+    }
+}
+
+impl Decodable2718 for DepositReceipt {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
+        if ty != DEPOSIT_TX_TYPE_ID {
+            return Err(Eip2718Error::UnexpectedType(ty));
+        }
+        Ok(Decodable::decode(buf)?)
+    }
+
+    fn fallback_decode(_buf: &mut &[u8]) -> Eip2718Result<Self> {
+        Err(Eip2718Error::UnexpectedType(0))
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for DepositReceipt<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.inner.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Receipt;
+    use alloy_primitives::{address, LogData, B256};
+
+    fn receipt(deposit_nonce: Option<u64>, deposit_receipt_version: Option<u64>) -> DepositReceipt {
+        let log = Log {
+            address: address!("4200000000000000000000000000000000000015"),
+            data: LogData::new_unchecked(vec![B256::with_last_byte(1)], vec![0xab].into()),
+        };
+        let receipt = Receipt { status: true.into(), cumulative_gas_used: 46_913, logs: vec![log] };
+        DepositReceipt::new(receipt.with_bloom(), deposit_nonce, deposit_receipt_version)
+    }
+
+    #[test]
+    fn encode_decode_deposit_receipt() {
+        for receipt in [receipt(None, None), receipt(Some(7), None), receipt(Some(7), Some(1))] {
+            let encoded = receipt.encoded_2718();
+            assert_eq!(encoded[0], DEPOSIT_TX_TYPE_ID);
+            assert_eq!(encoded.len(), receipt.encode_2718_len());
+            assert_eq!(DepositReceipt::decode_2718(&mut &encoded[..]).unwrap(), receipt);
+        }
+
+        // Pre-Regolith deposit receipts are encoded like regular receipts.
+        let mut regular = Vec::new();
+        receipt(None, None).inner.encode(&mut regular);
+        let mut deposit = Vec::new();
+        receipt(None, None).encode(&mut deposit);
+        assert_eq!(regular, deposit);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_deposit_receipt() {
+        let receipt = receipt(Some(7), Some(1));
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["depositNonce"], "0x7");
+        assert_eq!(json["depositReceiptVersion"], "0x1");
+        assert_eq!(json["status"], "0x1");
+        assert_eq!(serde_json::from_value::<DepositReceipt>(json).unwrap(), receipt);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{Receipt, ReceiptWithBloom, TxReceipt, TxType};
-use alloy_eips::eip2718::{Decodable2718, Eip2718Error, Eip2718Result, Encodable2718};
+use alloy_eips::{
+    eip2718::{Decodable2718, Eip2718Error, Eip2718Result, Encodable2718, Typed2718},
+    InMemorySize,
+};
 use alloy_primitives::{Bloom, Log};
 use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable};
 
@@ -76,6 +79,33 @@ impl<T> ReceiptEnvelope<T> {
         &self.as_receipt_with_bloom().unwrap().logs_bloom
     }
 
+    /// Converts the logs of the receipt with `f`, keeping its type and bloom filter.
+    ///
+    /// See [`Receipt::map_logs`].
+    pub fn map_logs<U>(self, f: impl FnMut(T) -> U) -> ReceiptEnvelope<U> {
+        match self {
+            Self::Legacy(r) => ReceiptEnvelope::Legacy(r.map_logs(f)),
+            Self::Eip2930(r) => ReceiptEnvelope::Eip2930(r.map_logs(f)),
+            Self::Eip1559(r) => ReceiptEnvelope::Eip1559(r.map_logs(f)),
+            Self::Eip4844(r) => ReceiptEnvelope::Eip4844(r.map_logs(f)),
+        }
+    }
+
+    /// Converts the logs of the receipt with the fallible `f`, keeping its type and bloom filter.
+    ///
+    /// See [`Receipt::try_map_logs`].
+    pub fn try_map_logs<U, E>(
+        self,
+        f: impl FnMut(T) -> Result<U, E>,
+    ) -> Result<ReceiptEnvelope<U>, E> {
+        Ok(match self {
+            Self::Legacy(r) => ReceiptEnvelope::Legacy(r.try_map_logs(f)?),
+            Self::Eip2930(r) => ReceiptEnvelope::Eip2930(r.try_map_logs(f)?),
+            Self::Eip1559(r) => ReceiptEnvelope::Eip1559(r.try_map_logs(f)?),
+            Self::Eip4844(r) => ReceiptEnvelope::Eip4844(r.try_map_logs(f)?),
+        })
+    }
+
     /// Return the inner receipt with bloom. Currently this is infallible,
     /// however, future receipt types may be added.
     pub const fn as_receipt_with_bloom(&self) -> Option<&ReceiptWithBloom<T>> {
@@ -161,16 +191,13 @@ impl Decodable for ReceiptEnvelope {
     }
 }
 
-impl Encodable2718 for ReceiptEnvelope {
-    fn type_flag(&self) -> Option<u8> {
-        match self {
-            Self::Legacy(_) => None,
-            Self::Eip2930(_) => Some(TxType::Eip2930 as u8),
-            Self::Eip1559(_) => Some(TxType::Eip1559 as u8),
-            Self::Eip4844(_) => Some(TxType::Eip4844 as u8),
-        }
+impl Typed2718 for ReceiptEnvelope {
+    fn ty(&self) -> u8 {
+        self.tx_type().into()
     }
+}
 
+impl Encodable2718 for ReceiptEnvelope {
     fn encode_2718_len(&self) -> usize {
         self.inner_length() + !self.is_legacy() as usize
     }
@@ -200,6 +227,27 @@ impl Decodable2718 for ReceiptEnvelope {
     }
 }
 
+#[cfg(any(test, feature = "proptest"))]
+impl<T> proptest::arbitrary::Arbitrary for ReceiptEnvelope<T>
+where
+    T: proptest::arbitrary::Arbitrary + 'static,
+{
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        use proptest::{arbitrary::any, prop_oneof, strategy::Strategy};
+
+        prop_oneof![
+            any::<ReceiptWithBloom<T>>().prop_map(Self::Legacy),
+            any::<ReceiptWithBloom<T>>().prop_map(Self::Eip2930),
+            any::<ReceiptWithBloom<T>>().prop_map(Self::Eip1559),
+            any::<ReceiptWithBloom<T>>().prop_map(Self::Eip4844),
+        ]
+        .boxed()
+    }
+}
+
 #[cfg(any(test, feature = "arbitrary"))]
 impl<'a, T> arbitrary::Arbitrary<'a> for ReceiptEnvelope<T>
 where
@@ -217,3 +265,10 @@ where
         }
     }
 }
+
+impl<T: InMemorySize> InMemorySize for ReceiptEnvelope<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.as_receipt_with_bloom().map_or(0, ReceiptWithBloom::heap_size)
+    }
+}
@@ -3,6 +3,9 @@ use alloy_primitives::{Bloom, Log};
 mod any;
 pub use any::AnyReceiptEnvelope;
 
+mod deposit;
+pub use deposit::DepositReceipt;
+
 mod envelope;
 pub use envelope::ReceiptEnvelope;
 
@@ -1,4 +1,7 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
 use crate::receipt::{Eip658Value, TxReceipt};
+use alloy_eips::InMemorySize;
 use alloy_primitives::{Bloom, Log};
 use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable};
 use core::borrow::Borrow;
@@ -7,19 +10,23 @@ use core::borrow::Borrow;
 use alloc::vec::Vec;
 
 /// Receipt containing result of transaction execution.
+///
+/// In JSON, the status is serialized as `status` for [EIP-658] receipts and as `root` for
+/// pre-[EIP-658] receipts, which carry the post-transaction state root instead.
+///
+/// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[doc(alias = "TransactionReceipt", alias = "TxReceipt")]
 pub struct Receipt<T = Log> {
     /// If transaction is executed successfully.
     ///
-    /// This is the `statusCode`
-    #[cfg_attr(feature = "serde", serde(alias = "root"))]
+    /// This is the `statusCode`, or the post-transaction state root before [EIP-658].
+    ///
+    /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
     pub status: Eip658Value,
     /// Gas used
-    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
     pub cumulative_gas_used: u128,
     /// Log send from contracts.
     pub logs: Vec<T>,
@@ -53,6 +60,71 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Receipt<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Helper<T> {
+            #[serde(default)]
+            status: Option<Eip658Value>,
+            #[serde(default)]
+            root: Option<alloy_primitives::Bytes>,
+            #[serde(with = "alloy_serde::quantity")]
+            cumulative_gas_used: u128,
+            logs: Vec<T>,
+        }
+
+        let Helper { status, root, cumulative_gas_used, logs } = Helper::deserialize(deserializer)?;
+
+        // The status code takes precedence, as some clients return an empty `root` alongside it.
+        let status = status
+            .or_else(|| {
+                root.and_then(|root| alloy_primitives::B256::try_from(&root[..]).ok())
+                    .map(Into::into)
+            })
+            .ok_or_else(|| serde::de::Error::missing_field("status"))?;
+
+        Ok(Self { status, cumulative_gas_used, logs })
+    }
+}
+
+impl<T> Receipt<T> {
+    /// Converts the logs of the receipt with `f`, e.g. into decoded logs.
+    pub fn map_logs<U>(self, f: impl FnMut(T) -> U) -> Receipt<U> {
+        let Self { status, cumulative_gas_used, logs } = self;
+        Receipt { status, cumulative_gas_used, logs: logs.into_iter().map(f).collect() }
+    }
+
+    /// Converts the logs of the receipt with the fallible `f`, returning the first error.
+    pub fn try_map_logs<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Receipt<U>, E> {
+        let Self { status, cumulative_gas_used, logs } = self;
+        Ok(Receipt {
+            status,
+            cumulative_gas_used,
+            logs: logs.into_iter().map(f).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Calculates the bloom filter of the logs, accrued into the bloom by `accrue`.
+    ///
+    /// This can be used for logs that do not borrow a [`Log`], e.g. by calling
+    /// [`Bloom::accrue_raw_log`] with the address and topics of each log.
+    pub fn bloom_with(&self, mut accrue: impl FnMut(&mut Bloom, &T)) -> Bloom {
+        let mut bloom = Bloom::ZERO;
+        for log in &self.logs {
+            accrue(&mut bloom, log);
+        }
+        bloom
+    }
+}
+
 impl<T> Receipt<T>
 where
     T: Borrow<Log>,
@@ -109,6 +181,7 @@ impl<T> From<ReceiptWithBloom<T>> for Receipt<T> {
 ///
 /// [`Sealed`]: crate::sealed::Sealed
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[doc(alias = "TransactionReceiptWithBloom", alias = "TxReceiptWithBloom")]
@@ -190,6 +263,31 @@ impl<T> ReceiptWithBloom<T> {
         (self.receipt, self.logs_bloom)
     }
 
+    /// Converts the logs of the receipt with `f`, keeping the bloom filter.
+    ///
+    /// See [`Receipt::map_logs`].
+    pub fn map_logs<U>(self, f: impl FnMut(T) -> U) -> ReceiptWithBloom<U> {
+        ReceiptWithBloom { receipt: self.receipt.map_logs(f), logs_bloom: self.logs_bloom }
+    }
+
+    /// Converts the logs of the receipt with the fallible `f`, keeping the bloom filter.
+    ///
+    /// See [`Receipt::try_map_logs`].
+    pub fn try_map_logs<U, E>(
+        self,
+        f: impl FnMut(T) -> Result<U, E>,
+    ) -> Result<ReceiptWithBloom<U>, E> {
+        Ok(ReceiptWithBloom { receipt: self.receipt.try_map_logs(f)?, logs_bloom: self.logs_bloom })
+    }
+
+    /// Recalculates the bloom filter from the logs, e.g. after they were modified.
+    pub fn recompute_bloom(&mut self)
+    where
+        T: Borrow<Log>,
+    {
+        self.logs_bloom = self.receipt.bloom_slow();
+    }
+
     /// Decodes the receipt payload
     fn decode_receipt(buf: &mut &[u8]) -> alloy_rlp::Result<Self>
     where
@@ -252,6 +350,20 @@ where
     }
 }
 
+impl<T: InMemorySize> InMemorySize for Receipt<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.logs.heap_size()
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for ReceiptWithBloom<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.receipt.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[cfg(feature = "serde")]
@@ -277,5 +389,88 @@ mod test {
             json,
             r#"{"root":"0x0000000000000000000000000000000000000000000000000000000000000000","cumulativeGasUsed":"0x0","logs":[]}"#
         );
+        assert_eq!(serde_json::from_str::<super::Receipt<()>>(&json).unwrap(), receipt);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_root_and_status() {
+        use super::{Eip658Value, Receipt};
+
+        let root = "0x96a8e009d2b88b1483e6941e6812e32263b05683fac202abc622a3e31aed1957";
+        let receipt: Receipt<()> = serde_json::from_str(&format!(
+            r#"{{"root":"{root}","cumulativeGasUsed":"0x5208","logs":[]}}"#
+        ))
+        .unwrap();
+        assert_eq!(receipt.status, Eip658Value::PostState(root.parse().unwrap()));
+
+        // An empty or null root is ignored in favor of the status code.
+        for root in [r#""0x""#, "null"] {
+            let receipt: Receipt<()> = serde_json::from_str(&format!(
+                r#"{{"status":"0x0","root":{root},"cumulativeGasUsed":"0x5208","logs":[]}}"#
+            ))
+            .unwrap();
+            assert_eq!(receipt.status, Eip658Value::Eip658(false));
+        }
+
+        let err = serde_json::from_str::<Receipt<()>>(r#"{"cumulativeGasUsed":"0x0","logs":[]}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("missing field `status`"));
+    }
+
+    #[test]
+    fn map_logs() {
+        use super::{Eip658Value, Receipt, ReceiptWithBloom};
+        use alloy_primitives::{Address, Log, LogData, B256};
+
+        /// A decoded event, with its value in the second topic.
+        #[derive(Debug, PartialEq)]
+        struct Transfer(u8);
+
+        impl From<&Transfer> for LogData {
+            fn from(transfer: &Transfer) -> Self {
+                Self::new_unchecked(
+                    vec![B256::with_last_byte(1), B256::with_last_byte(transfer.0)],
+                    Default::default(),
+                )
+            }
+        }
+
+        let logs = (2..4)
+            .map(|value| Log {
+                address: Address::with_last_byte(value),
+                data: (&Transfer(value)).into(),
+            })
+            .collect();
+        let receipt = Receipt { status: Eip658Value::Eip658(true), cumulative_gas_used: 1, logs };
+        let mut with_bloom = receipt.clone().with_bloom();
+
+        let decode = |log: Log| match log.topics() {
+            [_, value] => Ok(Log { address: log.address, data: Transfer(value[31]) }),
+            _ => Err("unexpected topics"),
+        };
+        let decoded = with_bloom.clone().try_map_logs(decode).unwrap();
+        assert_eq!(
+            decoded.receipt.logs[1],
+            Log { address: Address::with_last_byte(3), data: Transfer(3) }
+        );
+        assert_eq!(
+            decoded.receipt.bloom_with(|bloom, log| bloom.accrue_log(&log.reserialize())),
+            with_bloom.logs_bloom
+        );
+        assert_eq!(decoded.receipt.map_logs(|log| log.reserialize()), receipt);
+
+        let err = receipt
+            .clone()
+            .map_logs(|log| Log { data: LogData::empty(), ..log })
+            .try_map_logs(decode);
+        assert_eq!(err.unwrap_err(), "unexpected topics");
+
+        with_bloom.receipt.logs.pop();
+        with_bloom.recompute_bloom();
+        assert_eq!(
+            with_bloom,
+            ReceiptWithBloom::from(Receipt { logs: receipt.logs[..1].to_vec(), ..receipt })
+        );
     }
 }
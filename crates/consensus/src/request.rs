@@ -5,6 +5,7 @@ use alloy_eips::{
     eip7002::WithdrawalRequest,
     eip7251::ConsolidationRequest,
     eip7685::{Decodable7685, Eip7685Error, Encodable7685},
+    InMemorySize,
 };
 use alloy_rlp::{Decodable, Encodable};
 
@@ -13,10 +14,8 @@ use alloy_rlp::{Decodable, Encodable};
 /// See also [EIP-7685](https://eips.ethereum.org/EIPS/eip-7685).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
-#[cfg_attr(
-    any(test, feature = "arbitrary"),
-    derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
-)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(untagged))]
 pub enum Request {
@@ -115,3 +114,14 @@ impl Decodable7685 for Request {
         })
     }
 }
+
+impl InMemorySize for Request {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::DepositRequest(req) => req.heap_size(),
+            Self::WithdrawalRequest(req) => req.heap_size(),
+            Self::ConsolidationRequest(req) => req.heap_size(),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use alloy_primitives::{Address, BlockNumber, Bloom, Bytes, B256, B64, U256};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+/// Bincode compatible representation of a [`Header`](crate::Header).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Header<'a> {
+    parent_hash: B256,
+    ommers_hash: B256,
+    beneficiary: Address,
+    state_root: B256,
+    transactions_root: B256,
+    receipts_root: B256,
+    withdrawals_root: Option<B256>,
+    logs_bloom: Bloom,
+    difficulty: U256,
+    number: BlockNumber,
+    gas_limit: u128,
+    gas_used: u128,
+    timestamp: u64,
+    mix_hash: B256,
+    nonce: B64,
+    base_fee_per_gas: Option<u128>,
+    blob_gas_used: Option<u128>,
+    excess_blob_gas: Option<u128>,
+    parent_beacon_block_root: Option<B256>,
+    requests_root: Option<B256>,
+    extra_data: Cow<'a, Bytes>,
+}
+
+impl<'a> From<&'a crate::Header> for Header<'a> {
+    fn from(value: &'a crate::Header) -> Self {
+        Self {
+            parent_hash: value.parent_hash,
+            ommers_hash: value.ommers_hash,
+            beneficiary: value.beneficiary,
+            state_root: value.state_root,
+            transactions_root: value.transactions_root,
+            receipts_root: value.receipts_root,
+            withdrawals_root: value.withdrawals_root,
+            logs_bloom: value.logs_bloom,
+            difficulty: value.difficulty,
+            number: value.number,
+            gas_limit: value.gas_limit,
+            gas_used: value.gas_used,
+            timestamp: value.timestamp,
+            mix_hash: value.mix_hash,
+            nonce: value.nonce,
+            base_fee_per_gas: value.base_fee_per_gas,
+            blob_gas_used: value.blob_gas_used,
+            excess_blob_gas: value.excess_blob_gas,
+            parent_beacon_block_root: value.parent_beacon_block_root,
+            requests_root: value.requests_root,
+            extra_data: Cow::Borrowed(&value.extra_data),
+        }
+    }
+}
+
+impl From<Header<'_>> for crate::Header {
+    fn from(value: Header<'_>) -> Self {
+        Self {
+            parent_hash: value.parent_hash,
+            ommers_hash: value.ommers_hash,
+            beneficiary: value.beneficiary,
+            state_root: value.state_root,
+            transactions_root: value.transactions_root,
+            receipts_root: value.receipts_root,
+            withdrawals_root: value.withdrawals_root,
+            logs_bloom: value.logs_bloom,
+            difficulty: value.difficulty,
+            number: value.number,
+            gas_limit: value.gas_limit,
+            gas_used: value.gas_used,
+            timestamp: value.timestamp,
+            mix_hash: value.mix_hash,
+            nonce: value.nonce,
+            base_fee_per_gas: value.base_fee_per_gas,
+            blob_gas_used: value.blob_gas_used,
+            excess_blob_gas: value.excess_blob_gas,
+            parent_beacon_block_root: value.parent_beacon_block_root,
+            requests_root: value.requests_root,
+            extra_data: value.extra_data.into_owned(),
+        }
+    }
+}
+
+impl_serde_as!(Header => crate::Header);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Data {
+        #[serde_as(as = "Header<'_>")]
+        header: crate::Header,
+    }
+
+    #[test]
+    fn roundtrip_header() {
+        let data = Data {
+            header: crate::Header {
+                number: 1,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(7),
+                extra_data: vec![0xab].into(),
+                ..Default::default()
+            },
+        };
+
+        // Absent fields are kept, so that the header can be decoded in order.
+        let json = serde_json::to_value(&data).unwrap();
+        assert!(json["header"]["withdrawals_root"].is_null());
+        assert_eq!(serde_json::from_value::<Data>(json).unwrap(), data);
+
+        let encoded = bincode::serialize(&data).unwrap();
+        assert_eq!(bincode::deserialize::<Data>(&encoded).unwrap(), data);
+    }
+}
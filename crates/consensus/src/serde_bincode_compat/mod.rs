@@ -0,0 +1,70 @@
+//! Bincode-compatible serde implementations for consensus types.
+//!
+//! The serde implementations of the consensus types follow the JSON-RPC format, relying on
+//! flattened fields, skipped fields and self-describing enums that non-self-describing formats
+//! like bincode do not support. The types of this module mirror them with a plain
+//! representation, and are used with [`serde_with`] to persist consensus types with such
+//! formats:
+//!
+//! ```
+//! use alloy_consensus::{serde_bincode_compat, Header, TxEnvelope};
+//! use serde::{Deserialize, Serialize};
+//! use serde_with::serde_as;
+//!
+//! #[serde_as]
+//! #[derive(Serialize, Deserialize)]
+//! struct Data {
+//!     #[serde_as(as = "serde_bincode_compat::Header<'_>")]
+//!     header: Header,
+//!     #[serde_as(as = "Vec<serde_bincode_compat::TxEnvelope<'_>>")]
+//!     transactions: Vec<TxEnvelope>,
+//! }
+//! ```
+
+use core::fmt::Debug;
+use serde::{Deserialize, Serialize};
+
+/// Implements [`serde_with::SerializeAs`] and [`serde_with::DeserializeAs`] for the bincode
+/// compatible representations of consensus types, and links them with [`SerdeBincodeCompat`].
+macro_rules! impl_serde_as {
+    ($($ty:ident => $target:ty),* $(,)?) => {$(
+        impl serde_with::SerializeAs<$target> for $ty<'_> {
+            fn serialize_as<S>(source: &$target, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serde::Serialize::serialize(&$ty::from(source), serializer)
+            }
+        }
+
+        impl<'de> serde_with::DeserializeAs<'de, $target> for $ty<'de> {
+            fn deserialize_as<D>(deserializer: D) -> Result<$target, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                <$ty<'de> as serde::Deserialize<'de>>::deserialize(deserializer).map(Into::into)
+            }
+        }
+
+        impl $crate::serde_bincode_compat::SerdeBincodeCompat for $target {
+            type BincodeRepr<'a> = $ty<'a>;
+        }
+    )*};
+}
+
+mod header;
+pub use header::Header;
+
+mod receipt;
+pub use receipt::{DepositReceipt, Log, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+
+mod transaction;
+pub use transaction::{Signed, TxDeposit, TxEip1559, TxEip2930, TxEnvelope, TxLegacy};
+#[cfg(feature = "eip4844")]
+pub use transaction::{TxEip4844, TxEip4844Variant, TxEip4844WithSidecar};
+
+/// A type with a bincode compatible serde representation.
+pub trait SerdeBincodeCompat: Sized + 'static {
+    /// The bincode compatible representation, borrowing from the type when serializing.
+    type BincodeRepr<'a>: Debug + Serialize + Deserialize<'a> + From<&'a Self> + Into<Self>;
+}
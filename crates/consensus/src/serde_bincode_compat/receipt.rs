@@ -0,0 +1,220 @@
+use alloy_primitives::{Address, Bloom, Bytes, B256};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, vec::Vec};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+/// Bincode compatible representation of a [`Log`](alloy_primitives::Log).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Log<'a> {
+    address: Address,
+    topics: Cow<'a, [B256]>,
+    data: Cow<'a, Bytes>,
+}
+
+impl<'a> From<&'a alloy_primitives::Log> for Log<'a> {
+    fn from(value: &'a alloy_primitives::Log) -> Self {
+        Self {
+            address: value.address,
+            topics: Cow::Borrowed(value.data.topics()),
+            data: Cow::Borrowed(&value.data.data),
+        }
+    }
+}
+
+impl From<Log<'_>> for alloy_primitives::Log {
+    fn from(value: Log<'_>) -> Self {
+        Self::new_unchecked(value.address, value.topics.into_owned(), value.data.into_owned())
+    }
+}
+
+/// Bincode compatible representation of an [`Eip658Value`](crate::Eip658Value).
+#[derive(Debug, Serialize, Deserialize)]
+enum Eip658Value {
+    Eip658(bool),
+    PostState(B256),
+}
+
+impl From<crate::Eip658Value> for Eip658Value {
+    fn from(value: crate::Eip658Value) -> Self {
+        match value {
+            crate::Eip658Value::Eip658(status) => Self::Eip658(status),
+            crate::Eip658Value::PostState(state) => Self::PostState(state),
+        }
+    }
+}
+
+impl From<Eip658Value> for crate::Eip658Value {
+    fn from(value: Eip658Value) -> Self {
+        match value {
+            Eip658Value::Eip658(status) => Self::Eip658(status),
+            Eip658Value::PostState(state) => Self::PostState(state),
+        }
+    }
+}
+
+/// Bincode compatible representation of a [`Receipt`](crate::Receipt).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Receipt<'a> {
+    status: Eip658Value,
+    cumulative_gas_used: u128,
+    logs: Vec<Log<'a>>,
+}
+
+impl<'a> From<&'a crate::Receipt> for Receipt<'a> {
+    fn from(value: &'a crate::Receipt) -> Self {
+        Self {
+            status: value.status.into(),
+            cumulative_gas_used: value.cumulative_gas_used,
+            logs: value.logs.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Receipt<'_>> for crate::Receipt {
+    fn from(value: Receipt<'_>) -> Self {
+        Self {
+            status: value.status.into(),
+            cumulative_gas_used: value.cumulative_gas_used,
+            logs: value.logs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Bincode compatible representation of a [`ReceiptWithBloom`](crate::ReceiptWithBloom).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReceiptWithBloom<'a> {
+    receipt: Receipt<'a>,
+    logs_bloom: Bloom,
+}
+
+impl<'a> From<&'a crate::ReceiptWithBloom> for ReceiptWithBloom<'a> {
+    fn from(value: &'a crate::ReceiptWithBloom) -> Self {
+        Self { receipt: (&value.receipt).into(), logs_bloom: value.logs_bloom }
+    }
+}
+
+impl From<ReceiptWithBloom<'_>> for crate::ReceiptWithBloom {
+    fn from(value: ReceiptWithBloom<'_>) -> Self {
+        Self { receipt: value.receipt.into(), logs_bloom: value.logs_bloom }
+    }
+}
+
+/// Bincode compatible representation of a [`ReceiptEnvelope`](crate::ReceiptEnvelope).
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ReceiptEnvelope<'a> {
+    /// Receipt of a legacy transaction.
+    Legacy(ReceiptWithBloom<'a>),
+    /// Receipt of an EIP-2930 transaction.
+    Eip2930(ReceiptWithBloom<'a>),
+    /// Receipt of an EIP-1559 transaction.
+    Eip1559(ReceiptWithBloom<'a>),
+    /// Receipt of an EIP-4844 transaction.
+    Eip4844(ReceiptWithBloom<'a>),
+}
+
+impl<'a> From<&'a crate::ReceiptEnvelope> for ReceiptEnvelope<'a> {
+    fn from(value: &'a crate::ReceiptEnvelope) -> Self {
+        match value {
+            crate::ReceiptEnvelope::Legacy(receipt) => Self::Legacy(receipt.into()),
+            crate::ReceiptEnvelope::Eip2930(receipt) => Self::Eip2930(receipt.into()),
+            crate::ReceiptEnvelope::Eip1559(receipt) => Self::Eip1559(receipt.into()),
+            crate::ReceiptEnvelope::Eip4844(receipt) => Self::Eip4844(receipt.into()),
+        }
+    }
+}
+
+impl From<ReceiptEnvelope<'_>> for crate::ReceiptEnvelope {
+    fn from(value: ReceiptEnvelope<'_>) -> Self {
+        match value {
+            ReceiptEnvelope::Legacy(receipt) => Self::Legacy(receipt.into()),
+            ReceiptEnvelope::Eip2930(receipt) => Self::Eip2930(receipt.into()),
+            ReceiptEnvelope::Eip1559(receipt) => Self::Eip1559(receipt.into()),
+            ReceiptEnvelope::Eip4844(receipt) => Self::Eip4844(receipt.into()),
+        }
+    }
+}
+
+/// Bincode compatible representation of a [`DepositReceipt`](crate::DepositReceipt).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepositReceipt<'a> {
+    inner: ReceiptWithBloom<'a>,
+    deposit_nonce: Option<u64>,
+    deposit_receipt_version: Option<u64>,
+}
+
+impl<'a> From<&'a crate::DepositReceipt> for DepositReceipt<'a> {
+    fn from(value: &'a crate::DepositReceipt) -> Self {
+        Self {
+            inner: (&value.inner).into(),
+            deposit_nonce: value.deposit_nonce,
+            deposit_receipt_version: value.deposit_receipt_version,
+        }
+    }
+}
+
+impl From<DepositReceipt<'_>> for crate::DepositReceipt {
+    fn from(value: DepositReceipt<'_>) -> Self {
+        Self {
+            inner: value.inner.into(),
+            deposit_nonce: value.deposit_nonce,
+            deposit_receipt_version: value.deposit_receipt_version,
+        }
+    }
+}
+
+impl_serde_as!(
+    Log => alloy_primitives::Log,
+    Receipt => crate::Receipt,
+    ReceiptWithBloom => crate::ReceiptWithBloom,
+    ReceiptEnvelope => crate::ReceiptEnvelope,
+    DepositReceipt => crate::DepositReceipt,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::LogData;
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Data {
+        #[serde_as(as = "Vec<ReceiptEnvelope<'_>>")]
+        receipts: Vec<crate::ReceiptEnvelope>,
+        #[serde_as(as = "DepositReceipt<'_>")]
+        deposit: crate::DepositReceipt,
+    }
+
+    #[test]
+    fn roundtrip_receipts() {
+        let log = alloy_primitives::Log {
+            address: Address::with_last_byte(0x15),
+            data: LogData::new_unchecked(vec![B256::with_last_byte(1)], vec![0xab].into()),
+        };
+        let receipt = crate::Receipt {
+            status: crate::Eip658Value::PostState(B256::with_last_byte(2)),
+            cumulative_gas_used: 46_913,
+            logs: vec![log],
+        }
+        .with_bloom();
+        let data = Data {
+            receipts: vec![
+                crate::ReceiptEnvelope::Legacy(receipt.clone()),
+                crate::ReceiptEnvelope::Eip4844(receipt.clone()),
+            ],
+            deposit: crate::DepositReceipt::new(receipt, None, Some(1)),
+        };
+
+        // Absent deposit fields are not skipped, and the receipt is not flattened.
+        let json = serde_json::to_value(&data).unwrap();
+        assert!(json["deposit"]["deposit_nonce"].is_null());
+        assert!(json["deposit"]["inner"].is_object());
+        assert_eq!(serde_json::from_value::<Data>(json).unwrap(), data);
+
+        let encoded = bincode::serialize(&data).unwrap();
+        assert_eq!(bincode::deserialize::<Data>(&encoded).unwrap(), data);
+    }
+}
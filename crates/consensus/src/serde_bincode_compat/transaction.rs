@@ -0,0 +1,464 @@
+use super::SerdeBincodeCompat;
+use crate::SignableTransaction;
+use alloy_eips::eip2930::AccessList;
+#[cfg(feature = "eip4844")]
+use alloy_eips::eip4844::BlobTransactionSidecar;
+use alloy_primitives::{Address, Bytes, ChainId, Signature, TxKind, B256, U256};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+/// Bincode compatible representation of a [`TxLegacy`](crate::TxLegacy).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TxLegacy<'a> {
+    chain_id: Option<ChainId>,
+    nonce: u64,
+    gas_price: u128,
+    gas_limit: u128,
+    to: TxKind,
+    value: U256,
+    input: Cow<'a, Bytes>,
+}
+
+impl<'a> From<&'a crate::TxLegacy> for TxLegacy<'a> {
+    fn from(value: &'a crate::TxLegacy) -> Self {
+        Self {
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_price: value.gas_price,
+            gas_limit: value.gas_limit,
+            to: value.to,
+            value: value.value,
+            input: Cow::Borrowed(&value.input),
+        }
+    }
+}
+
+impl From<TxLegacy<'_>> for crate::TxLegacy {
+    fn from(value: TxLegacy<'_>) -> Self {
+        Self {
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_price: value.gas_price,
+            gas_limit: value.gas_limit,
+            to: value.to,
+            value: value.value,
+            input: value.input.into_owned(),
+        }
+    }
+}
+
+/// Bincode compatible representation of a [`TxEip2930`](crate::TxEip2930).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TxEip2930<'a> {
+    chain_id: ChainId,
+    nonce: u64,
+    gas_price: u128,
+    gas_limit: u128,
+    to: TxKind,
+    value: U256,
+    access_list: Cow<'a, AccessList>,
+    input: Cow<'a, Bytes>,
+}
+
+impl<'a> From<&'a crate::TxEip2930> for TxEip2930<'a> {
+    fn from(value: &'a crate::TxEip2930) -> Self {
+        Self {
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_price: value.gas_price,
+            gas_limit: value.gas_limit,
+            to: value.to,
+            value: value.value,
+            access_list: Cow::Borrowed(&value.access_list),
+            input: Cow::Borrowed(&value.input),
+        }
+    }
+}
+
+impl From<TxEip2930<'_>> for crate::TxEip2930 {
+    fn from(value: TxEip2930<'_>) -> Self {
+        Self {
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_price: value.gas_price,
+            gas_limit: value.gas_limit,
+            to: value.to,
+            value: value.value,
+            access_list: value.access_list.into_owned(),
+            input: value.input.into_owned(),
+        }
+    }
+}
+
+/// Bincode compatible representation of a [`TxEip1559`](crate::TxEip1559).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TxEip1559<'a> {
+    chain_id: ChainId,
+    nonce: u64,
+    gas_limit: u128,
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+    to: TxKind,
+    value: U256,
+    access_list: Cow<'a, AccessList>,
+    input: Cow<'a, Bytes>,
+}
+
+impl<'a> From<&'a crate::TxEip1559> for TxEip1559<'a> {
+    fn from(value: &'a crate::TxEip1559) -> Self {
+        Self {
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_limit: value.gas_limit,
+            max_fee_per_gas: value.max_fee_per_gas,
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas,
+            to: value.to,
+            value: value.value,
+            access_list: Cow::Borrowed(&value.access_list),
+            input: Cow::Borrowed(&value.input),
+        }
+    }
+}
+
+impl From<TxEip1559<'_>> for crate::TxEip1559 {
+    fn from(value: TxEip1559<'_>) -> Self {
+        Self {
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_limit: value.gas_limit,
+            max_fee_per_gas: value.max_fee_per_gas,
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas,
+            to: value.to,
+            value: value.value,
+            access_list: value.access_list.into_owned(),
+            input: value.input.into_owned(),
+        }
+    }
+}
+
+#[cfg(feature = "eip4844")]
+/// Bincode compatible representation of a [`TxEip4844`](crate::TxEip4844).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TxEip4844<'a> {
+    chain_id: ChainId,
+    nonce: u64,
+    gas_limit: u128,
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+    to: Address,
+    value: U256,
+    access_list: Cow<'a, AccessList>,
+    blob_versioned_hashes: Cow<'a, [B256]>,
+    max_fee_per_blob_gas: u128,
+    input: Cow<'a, Bytes>,
+}
+
+#[cfg(feature = "eip4844")]
+impl<'a> From<&'a crate::TxEip4844> for TxEip4844<'a> {
+    fn from(value: &'a crate::TxEip4844) -> Self {
+        Self {
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_limit: value.gas_limit,
+            max_fee_per_gas: value.max_fee_per_gas,
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas,
+            to: value.to,
+            value: value.value,
+            access_list: Cow::Borrowed(&value.access_list),
+            blob_versioned_hashes: Cow::Borrowed(&value.blob_versioned_hashes),
+            max_fee_per_blob_gas: value.max_fee_per_blob_gas,
+            input: Cow::Borrowed(&value.input),
+        }
+    }
+}
+
+#[cfg(feature = "eip4844")]
+impl From<TxEip4844<'_>> for crate::TxEip4844 {
+    fn from(value: TxEip4844<'_>) -> Self {
+        Self {
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_limit: value.gas_limit,
+            max_fee_per_gas: value.max_fee_per_gas,
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas,
+            to: value.to,
+            value: value.value,
+            access_list: value.access_list.into_owned(),
+            blob_versioned_hashes: value.blob_versioned_hashes.into_owned(),
+            max_fee_per_blob_gas: value.max_fee_per_blob_gas,
+            input: value.input.into_owned(),
+        }
+    }
+}
+
+#[cfg(feature = "eip4844")]
+/// Bincode compatible representation of a [`TxEip4844WithSidecar`](crate::TxEip4844WithSidecar).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TxEip4844WithSidecar<'a> {
+    tx: TxEip4844<'a>,
+    sidecar: Cow<'a, BlobTransactionSidecar>,
+}
+
+#[cfg(feature = "eip4844")]
+impl<'a> From<&'a crate::TxEip4844WithSidecar> for TxEip4844WithSidecar<'a> {
+    fn from(value: &'a crate::TxEip4844WithSidecar) -> Self {
+        Self { tx: (&value.tx).into(), sidecar: Cow::Borrowed(&value.sidecar) }
+    }
+}
+
+#[cfg(feature = "eip4844")]
+impl From<TxEip4844WithSidecar<'_>> for crate::TxEip4844WithSidecar {
+    fn from(value: TxEip4844WithSidecar<'_>) -> Self {
+        Self { tx: value.tx.into(), sidecar: value.sidecar.into_owned() }
+    }
+}
+
+#[cfg(feature = "eip4844")]
+/// Bincode compatible representation of a [`TxEip4844Variant`](crate::TxEip4844Variant).
+#[derive(Debug, Serialize, Deserialize)]
+pub enum TxEip4844Variant<'a> {
+    /// A standalone transaction.
+    TxEip4844(TxEip4844<'a>),
+    /// A transaction with a sidecar.
+    TxEip4844WithSidecar(TxEip4844WithSidecar<'a>),
+}
+
+#[cfg(feature = "eip4844")]
+impl<'a> From<&'a crate::TxEip4844Variant> for TxEip4844Variant<'a> {
+    fn from(value: &'a crate::TxEip4844Variant) -> Self {
+        match value {
+            crate::TxEip4844Variant::TxEip4844(tx) => Self::TxEip4844(tx.into()),
+            crate::TxEip4844Variant::TxEip4844WithSidecar(tx) => {
+                Self::TxEip4844WithSidecar(tx.into())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "eip4844")]
+impl From<TxEip4844Variant<'_>> for crate::TxEip4844Variant {
+    fn from(value: TxEip4844Variant<'_>) -> Self {
+        match value {
+            TxEip4844Variant::TxEip4844(tx) => Self::TxEip4844(tx.into()),
+            TxEip4844Variant::TxEip4844WithSidecar(tx) => Self::TxEip4844WithSidecar(tx.into()),
+        }
+    }
+}
+
+/// Bincode compatible representation of a [`TxDeposit`](crate::TxDeposit).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TxDeposit<'a> {
+    source_hash: B256,
+    from: Address,
+    to: TxKind,
+    mint: Option<u128>,
+    value: U256,
+    gas_limit: u128,
+    is_system_transaction: bool,
+    input: Cow<'a, Bytes>,
+}
+
+impl<'a> From<&'a crate::TxDeposit> for TxDeposit<'a> {
+    fn from(value: &'a crate::TxDeposit) -> Self {
+        Self {
+            source_hash: value.source_hash,
+            from: value.from,
+            to: value.to,
+            mint: value.mint,
+            value: value.value,
+            gas_limit: value.gas_limit,
+            is_system_transaction: value.is_system_transaction,
+            input: Cow::Borrowed(&value.input),
+        }
+    }
+}
+
+impl From<TxDeposit<'_>> for crate::TxDeposit {
+    fn from(value: TxDeposit<'_>) -> Self {
+        Self {
+            source_hash: value.source_hash,
+            from: value.from,
+            to: value.to,
+            mint: value.mint,
+            value: value.value,
+            gas_limit: value.gas_limit,
+            is_system_transaction: value.is_system_transaction,
+            input: value.input.into_owned(),
+        }
+    }
+}
+
+/// Bincode compatible representation of a [`Signed`](crate::Signed) transaction.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T::BincodeRepr<'a>: Serialize",
+    deserialize = "T::BincodeRepr<'a>: Deserialize<'de>"
+))]
+pub struct Signed<'a, T: SerdeBincodeCompat> {
+    tx: T::BincodeRepr<'a>,
+    signature: Signature,
+    hash: B256,
+}
+
+impl<'a, T: SerdeBincodeCompat> From<&'a crate::Signed<T>> for Signed<'a, T> {
+    fn from(value: &'a crate::Signed<T>) -> Self {
+        Self { tx: value.tx().into(), signature: *value.signature(), hash: *value.hash() }
+    }
+}
+
+impl<T: SerdeBincodeCompat + SignableTransaction<Signature>> From<Signed<'_, T>>
+    for crate::Signed<T>
+{
+    fn from(value: Signed<'_, T>) -> Self {
+        Self::new_unchecked(value.tx.into(), value.signature, value.hash)
+    }
+}
+
+impl<T: SerdeBincodeCompat> serde_with::SerializeAs<crate::Signed<T>> for Signed<'_, T> {
+    fn serialize_as<S>(source: &crate::Signed<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Signed::<'_, T>::from(source).serialize(serializer)
+    }
+}
+
+impl<'de, T: SerdeBincodeCompat + SignableTransaction<Signature>>
+    serde_with::DeserializeAs<'de, crate::Signed<T>> for Signed<'de, T>
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<crate::Signed<T>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Signed::<'de, T>::deserialize(deserializer).map(Into::into)
+    }
+}
+
+/// Bincode compatible representation of a [`TxEnvelope`](crate::TxEnvelope).
+#[derive(Debug, Serialize, Deserialize)]
+pub enum TxEnvelope<'a> {
+    /// A legacy transaction.
+    Legacy(Signed<'a, crate::TxLegacy>),
+    /// An EIP-2930 transaction.
+    Eip2930(Signed<'a, crate::TxEip2930>),
+    /// An EIP-1559 transaction.
+    Eip1559(Signed<'a, crate::TxEip1559>),
+    /// An EIP-4844 transaction.
+    #[cfg(feature = "eip4844")]
+    Eip4844(Signed<'a, crate::TxEip4844Variant>),
+}
+
+impl<'a> From<&'a crate::TxEnvelope> for TxEnvelope<'a> {
+    fn from(value: &'a crate::TxEnvelope) -> Self {
+        match value {
+            crate::TxEnvelope::Legacy(tx) => Self::Legacy(tx.into()),
+            crate::TxEnvelope::Eip2930(tx) => Self::Eip2930(tx.into()),
+            crate::TxEnvelope::Eip1559(tx) => Self::Eip1559(tx.into()),
+            #[cfg(feature = "eip4844")]
+            crate::TxEnvelope::Eip4844(tx) => Self::Eip4844(tx.into()),
+        }
+    }
+}
+
+impl From<TxEnvelope<'_>> for crate::TxEnvelope {
+    fn from(value: TxEnvelope<'_>) -> Self {
+        match value {
+            TxEnvelope::Legacy(tx) => Self::Legacy(tx.into()),
+            TxEnvelope::Eip2930(tx) => Self::Eip2930(tx.into()),
+            TxEnvelope::Eip1559(tx) => Self::Eip1559(tx.into()),
+            #[cfg(feature = "eip4844")]
+            TxEnvelope::Eip4844(tx) => Self::Eip4844(tx.into()),
+        }
+    }
+}
+
+impl_serde_as!(
+    TxLegacy => crate::TxLegacy,
+    TxEip2930 => crate::TxEip2930,
+    TxEip1559 => crate::TxEip1559,
+    TxDeposit => crate::TxDeposit,
+    TxEnvelope => crate::TxEnvelope,
+);
+
+#[cfg(feature = "eip4844")]
+impl_serde_as!(
+    TxEip4844 => crate::TxEip4844,
+    TxEip4844WithSidecar => crate::TxEip4844WithSidecar,
+    TxEip4844Variant => crate::TxEip4844Variant,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, hex};
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Data {
+        #[serde_as(as = "Vec<TxEnvelope<'_>>")]
+        transactions: Vec<crate::TxEnvelope>,
+        #[serde_as(as = "TxDeposit<'_>")]
+        deposit: crate::TxDeposit,
+    }
+
+    fn signature() -> Signature {
+        Signature::from_rs_and_parity(
+            b256!("840cfc572845f5786e702984c2a582528cad4b49b2a10b9db1be7fca90058565").into(),
+            b256!("25e7109ceb98168d95b09b18bbf6b685130e0562f233877d492b94eee0c5b6d1").into(),
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn roundtrip_transactions() {
+        let to = address!("6069a6c32cf691f5982febae4faf8a6f3ab2f0f6");
+        let legacy = crate::TxLegacy {
+            chain_id: Some(1),
+            nonce: 1,
+            gas_price: 2,
+            gas_limit: 21_000,
+            to: to.into(),
+            ..Default::default()
+        };
+        let eip1559 = crate::TxEip1559 {
+            chain_id: 1,
+            nonce: 2,
+            gas_limit: 44_386,
+            to: TxKind::Create,
+            input: hex!("a22cb465").into(),
+            ..Default::default()
+        };
+        let eip4844 = crate::TxEip4844 {
+            to,
+            blob_versioned_hashes: vec![B256::with_last_byte(1)],
+            ..Default::default()
+        };
+        let data = Data {
+            transactions: vec![
+                legacy.into_signed(signature()).into(),
+                eip1559.into_signed(signature()).into(),
+                crate::TxEip4844Variant::from(eip4844).into_signed(signature()).into(),
+            ],
+            deposit: crate::TxDeposit {
+                from: Address::with_last_byte(1),
+                to: to.into(),
+                mint: Some(1),
+                ..Default::default()
+            },
+        };
+
+        // The representation does not skip empty fields, so it can be decoded in order.
+        let json = serde_json::to_value(&data).unwrap();
+        assert!(json["deposit"]["input"].is_string());
+        assert_eq!(serde_json::from_value::<Data>(json).unwrap(), data);
+
+        let encoded = bincode::serialize(&data).unwrap();
+        assert_eq!(bincode::deserialize::<Data>(&encoded).unwrap(), data);
+    }
+}
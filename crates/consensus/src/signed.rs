@@ -1,4 +1,5 @@
 use crate::transaction::SignableTransaction;
+use alloy_eips::{eip2718::Typed2718, InMemorySize};
 use alloy_primitives::{Signature, B256};
 
 /// A transaction with a signature and hash seal.
@@ -55,13 +56,108 @@ impl<T: SignableTransaction<Sig>, Sig> Signed<T, Sig> {
     }
 }
 
+/// Generates transactions with random signatures, which are unlikely to be recoverable.
+///
+/// See [`Signed::arbitrary_validly_signed`] for transactions with a recoverable signer.
+#[cfg(any(test, feature = "proptest"))]
+impl<T> proptest::arbitrary::Arbitrary for Signed<T, Signature>
+where
+    T: SignableTransaction<Signature> + proptest::arbitrary::Arbitrary + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        use proptest::{arbitrary::any, strategy::Strategy};
+
+        (proptest::arbitrary::any_with::<T>(params), any::<Signature>())
+            .prop_map(|(tx, signature)| tx.into_signed(signature))
+            .boxed()
+    }
+}
+
+#[cfg(all(any(test, feature = "proptest"), feature = "k256"))]
+impl<T> Signed<T, Signature>
+where
+    T: SignableTransaction<Signature> + proptest::arbitrary::Arbitrary + 'static,
+{
+    /// Returns a strategy generating transactions signed by random keys, along with the address of
+    /// their signer.
+    pub fn arbitrary_validly_signed(
+    ) -> impl proptest::strategy::Strategy<Value = (Self, alloy_primitives::Address)> {
+        use alloy_primitives::Address;
+        use k256::ecdsa::SigningKey;
+        use proptest::{arbitrary::any, strategy::Strategy};
+
+        let key = any::<B256>().prop_filter_map("invalid secret key", |secret| {
+            SigningKey::from_slice(&secret[..]).ok()
+        });
+        (any::<T>(), key).prop_map(|(tx, key)| {
+            let (signature, recovery_id) = key
+                .sign_prehash_recoverable(tx.signature_hash().as_slice())
+                .expect("valid prehash");
+            (tx.into_signed((signature, recovery_id).into()), Address::from_private_key(&key))
+        })
+    }
+}
+
+/// The policy applied to the `s` value of signatures when recovering the signer of a transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RecoveryPolicy {
+    /// Reject signatures with an `s` value greater than [`SECP256K1N_HALF`], as required since
+    /// Homestead ([EIP-2]).
+    ///
+    /// [`SECP256K1N_HALF`]: crate::constants::SECP256K1N_HALF
+    /// [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+    Strict,
+    /// Accept any `s` value, as found in the signatures of pre-Homestead transactions. This is the
+    /// policy of [`Signed::recover_signer`].
+    #[default]
+    Lenient,
+}
+
 #[cfg(feature = "k256")]
 impl<T: SignableTransaction<Signature>> Signed<T, Signature> {
-    /// Recover the signer of the transaction
+    /// Recover the signer of the transaction.
+    ///
+    /// This applies the default [`RecoveryPolicy::Lenient`], accepting the high `s` values of
+    /// pre-Homestead signatures. Use [`recover_signer_with_policy`] with
+    /// [`RecoveryPolicy::Strict`] to reject them.
+    ///
+    /// [`recover_signer_with_policy`]: Self::recover_signer_with_policy
     pub fn recover_signer(
         &self,
     ) -> Result<alloy_primitives::Address, alloy_primitives::SignatureError> {
+        self.recover_signer_with_policy(RecoveryPolicy::default())
+    }
+
+    /// Recover the signer of the transaction, applying the given [`RecoveryPolicy`] to the `s`
+    /// value of the signature.
+    pub fn recover_signer_with_policy(
+        &self,
+        policy: RecoveryPolicy,
+    ) -> Result<alloy_primitives::Address, alloy_primitives::SignatureError> {
+        if policy == RecoveryPolicy::Strict
+            && self.signature.s() > crate::constants::SECP256K1N_HALF
+        {
+            return Err(alloy_primitives::SignatureError::FromBytes(
+                "invalid signature: s value is greater than secp256k1n/2",
+            ));
+        }
         let sighash = self.tx.signature_hash();
         self.signature.recover_address_from_prehash(&sighash)
     }
 }
+
+impl<T: Typed2718, Sig> Typed2718 for Signed<T, Sig> {
+    fn ty(&self) -> u8 {
+        self.tx.ty()
+    }
+}
+
+impl<T: InMemorySize, Sig: InMemorySize> InMemorySize for Signed<T, Sig> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.tx.heap_size() + self.signature.heap_size()
+    }
+}
@@ -0,0 +1,299 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
+use crate::Transaction;
+use alloy_eips::{
+    eip2718::{Decodable2718, Eip2718Error, Eip2718Result, Encodable2718, Typed2718},
+    InMemorySize,
+};
+use alloy_primitives::{keccak256, Address, Bytes, ChainId, TxKind, B256, U256};
+use alloy_rlp::{Buf, BufMut, Decodable, Encodable, Header, EMPTY_STRING_CODE};
+use core::mem;
+
+/// The [EIP-2718] type of [`TxDeposit`] transactions.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub const DEPOSIT_TX_TYPE_ID: u8 = 0x7E;
+
+/// A deposit transaction of OP stack chains, derived from L1 and executed without a signature.
+///
+/// See the [Optimism specs](https://specs.optimism.io/protocol/deposits.html#the-deposited-transaction-type).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[doc(alias = "DepositTransaction", alias = "TransactionDeposit", alias = "DepositTx")]
+pub struct TxDeposit {
+    /// Hash that uniquely identifies the source of the deposit.
+    pub source_hash: B256,
+    /// The address of the sender account.
+    pub from: Address,
+    /// The address of the recipient account, or the null (zero-length) address if the deposited
+    /// transaction is a contract creation.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "TxKind::is_create"))]
+    pub to: TxKind,
+    /// The ETH value to mint on L2.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::quantity::opt"
+        )
+    )]
+    pub mint: Option<u128>,
+    /// The ETH value to send to the recipient account.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub value: U256,
+    /// The gas limit for the L2 transaction.
+    #[cfg_attr(feature = "serde", serde(rename = "gas", with = "alloy_serde::quantity"))]
+    pub gas_limit: u128,
+    /// Field indicating if this transaction is exempt from the L2 gas limit.
+    #[cfg_attr(feature = "serde", serde(rename = "isSystemTx"))]
+    pub is_system_transaction: bool,
+    /// Input has two uses depending if transaction is Create or Call (if `to` field is None or
+    /// Some).
+    pub input: Bytes,
+}
+
+impl TxDeposit {
+    /// Decodes the inner [TxDeposit] fields from RLP bytes.
+    ///
+    /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
+    /// RLP fields in the following order:
+    ///
+    /// - `source_hash`
+    /// - `from`
+    /// - `to`
+    /// - `mint`
+    /// - `value`
+    /// - `gas_limit`
+    /// - `is_system_transaction`
+    /// - `input`
+    pub(crate) fn decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self {
+            source_hash: Decodable::decode(buf)?,
+            from: Decodable::decode(buf)?,
+            to: Decodable::decode(buf)?,
+            mint: if *buf.first().ok_or(alloy_rlp::Error::InputTooShort)? == EMPTY_STRING_CODE {
+                buf.advance(1);
+                None
+            } else {
+                Some(Decodable::decode(buf)?)
+            },
+            value: Decodable::decode(buf)?,
+            gas_limit: Decodable::decode(buf)?,
+            is_system_transaction: Decodable::decode(buf)?,
+            input: Decodable::decode(buf)?,
+        })
+    }
+
+    /// Outputs the length of the transaction's fields, without a RLP header.
+    #[doc(hidden)]
+    pub fn fields_len(&self) -> usize {
+        let mut len = 0;
+        len += self.source_hash.length();
+        len += self.from.length();
+        len += self.to.length();
+        len += self.mint.map_or(1, |mint| mint.length());
+        len += self.value.length();
+        len += self.gas_limit.length();
+        len += self.is_system_transaction.length();
+        len += self.input.0.length();
+        len
+    }
+
+    /// Encodes only the transaction's fields into the desired buffer, without a RLP header.
+    pub(crate) fn encode_fields(&self, out: &mut dyn BufMut) {
+        self.source_hash.encode(out);
+        self.from.encode(out);
+        self.to.encode(out);
+        match self.mint {
+            Some(mint) => mint.encode(out),
+            None => out.put_u8(EMPTY_STRING_CODE),
+        }
+        self.value.encode(out);
+        self.gas_limit.encode(out);
+        self.is_system_transaction.encode(out);
+        self.input.0.encode(out);
+    }
+
+    /// Returns the hash of the transaction, i.e. the hash of its [EIP-2718] encoding.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    pub fn tx_hash(&self) -> B256 {
+        keccak256(self.encoded_2718())
+    }
+
+    /// Calculates a heuristic for the in-memory size of the [TxDeposit] transaction.
+    #[inline]
+    pub fn size(&self) -> usize {
+        mem::size_of::<B256>() + // source_hash
+        mem::size_of::<Address>() + // from
+        self.to.size() + // to
+        mem::size_of::<Option<u128>>() + // mint
+        mem::size_of::<U256>() + // value
+        mem::size_of::<u128>() + // gas_limit
+        mem::size_of::<bool>() + // is_system_transaction
+        self.input.len() // input
+    }
+}
+
+impl Transaction for TxDeposit {
+    fn chain_id(&self) -> Option<ChainId> {
+        None
+    }
+
+    /// Deposit transactions have no nonce, the nonce of the sender is stored in the receipt.
+    fn nonce(&self) -> u64 {
+        0
+    }
+
+    fn gas_limit(&self) -> u128 {
+        self.gas_limit
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        None
+    }
+
+    fn to(&self) -> TxKind {
+        self.to
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn input(&self) -> &[u8] {
+        &self.input
+    }
+}
+
+impl Encodable for TxDeposit {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.encode_fields(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+}
+
+impl Decodable for TxDeposit {
+    fn decode(data: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(data)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let remaining_len = data.len();
+
+        if header.payload_length > remaining_len {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+
+        let tx = Self::decode_fields(data)?;
+        let consumed = remaining_len - data.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            });
+        }
+        Ok(tx)
+    }
+}
+
+impl Typed2718 for TxDeposit {
+    fn ty(&self) -> u8 {
+        DEPOSIT_TX_TYPE_ID
+    }
+}
+
+impl Encodable2718 for TxDeposit {
+    fn encode_2718_len(&self) -> usize {
+        self.length() + 1
+    }
+
+    fn encode_2718(&self, out: &mut dyn BufMut) {
+        out.put_u8(DEPOSIT_TX_TYPE_ID);
+        self.encode(out);
+    }
+}
+
+impl Decodable2718 for TxDeposit {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
+        if ty != DEPOSIT_TX_TYPE_ID {
+            return Err(Eip2718Error::UnexpectedType(ty));
+        }
+        Ok(Decodable::decode(buf)?)
+    }
+
+    fn fallback_decode(_buf: &mut &[u8]) -> Eip2718Result<Self> {
+        Err(Eip2718Error::UnexpectedType(0))
+    }
+}
+
+impl InMemorySize for TxDeposit {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.input.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, hex};
+
+    fn tx(mint: Option<u128>) -> TxDeposit {
+        TxDeposit {
+            source_hash: b256!("d6d29f89ab7df6d2fe8ac69f9d2cb1f1a8c0c0a6d0d2f1c5fa4b4a8e0bf2b7e1"),
+            from: address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001"),
+            to: address!("4200000000000000000000000000000000000015").into(),
+            mint,
+            value: U256::ZERO,
+            gas_limit: 1_000_000,
+            is_system_transaction: false,
+            input: hex!("440a5e20").into(),
+        }
+    }
+
+    #[test]
+    fn encode_decode_deposit() {
+        for tx in [tx(None), tx(Some(1_000_000_000_000_000_000))] {
+            let encoded = tx.encoded_2718();
+            assert_eq!(encoded[0], DEPOSIT_TX_TYPE_ID);
+            assert_eq!(encoded.len(), tx.encode_2718_len());
+
+            let decoded = TxDeposit::decode_2718(&mut &encoded[..]).unwrap();
+            assert_eq!(decoded, tx);
+            assert_eq!(decoded.tx_hash(), keccak256(&encoded));
+        }
+
+        // A zero mint is encoded as the empty string, like an absent one.
+        assert_eq!(tx(None).encoded_2718(), tx(Some(0)).encoded_2718());
+    }
+
+    #[test]
+    fn decode_rejects_other_types() {
+        let mut encoded = tx(None).encoded_2718();
+        encoded[0] = 0x02;
+        assert!(matches!(
+            TxDeposit::decode_2718(&mut &encoded[..]),
+            Err(Eip2718Error::UnexpectedType(0x02))
+        ));
+        assert!(TxDeposit::decode_2718(&mut &encoded[1..]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_deposit() {
+        let json = r#"{"sourceHash":"0xd6d29f89ab7df6d2fe8ac69f9d2cb1f1a8c0c0a6d0d2f1c5fa4b4a8e0bf2b7e1","from":"0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001","to":"0x4200000000000000000000000000000000000015","mint":"0x2a","value":"0x0","gas":"0xf4240","isSystemTx":false,"input":"0x440a5e20"}"#;
+        let tx: TxDeposit = serde_json::from_str(json).unwrap();
+        assert_eq!(tx, self::tx(Some(42)));
+        assert_eq!(serde_json::to_string(&tx).unwrap(), json);
+    }
+}
@@ -1,5 +1,7 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
 use crate::{SignableTransaction, Signed, Transaction, TxType};
-use alloy_eips::eip2930::AccessList;
+use alloy_eips::{eip2718::Typed2718, eip2930::AccessList, InMemorySize};
 use alloy_primitives::{keccak256, Bytes, ChainId, Signature, TxKind, U256};
 use alloy_rlp::{BufMut, Decodable, Encodable, Header};
 use core::mem;
@@ -9,6 +11,7 @@ use alloc::vec::Vec;
 
 /// A transaction with a priority fee ([EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)).
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[doc(alias = "Eip1559Transaction", alias = "TransactionEip1559", alias = "Eip1559Tx")]
@@ -56,6 +59,7 @@ pub struct TxEip1559 {
     /// be transferred to the message call’s recipient or,
     /// in the case of contract creation, as an endowment
     /// to the newly created account; formally Tv.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
     pub value: U256,
     /// The accessList specifies a list of addresses and storage keys;
     /// these addresses and storage keys are added into the `accessed_addresses`
@@ -73,15 +77,15 @@ pub struct TxEip1559 {
 
 impl TxEip1559 {
     /// Returns the effective gas price for the given `base_fee`.
-    pub const fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+    pub const fn effective_gas_price(&self, base_fee: Option<u128>) -> u128 {
         match base_fee {
             None => self.max_fee_per_gas,
             Some(base_fee) => {
                 // if the tip is greater than the max priority fee per gas, set it to the max
                 // priority fee per gas + base fee
-                let tip = self.max_fee_per_gas.saturating_sub(base_fee as u128);
+                let tip = self.max_fee_per_gas.saturating_sub(base_fee);
                 if tip > self.max_priority_fee_per_gas {
-                    self.max_priority_fee_per_gas + base_fee as u128
+                    self.max_priority_fee_per_gas + base_fee
                 } else {
                     // otherwise return the max fee per gas
                     self.max_fee_per_gas
@@ -90,6 +94,23 @@ impl TxEip1559 {
         }
     }
 
+    /// Returns the priority fee per gas paid to the block producer for the given `base_fee`, or
+    /// `None` if the max fee per gas is lower than the base fee.
+    pub const fn effective_tip_per_gas(&self, base_fee: u128) -> Option<u128> {
+        match self.max_fee_per_gas.checked_sub(base_fee) {
+            Some(tip) if tip > self.max_priority_fee_per_gas => Some(self.max_priority_fee_per_gas),
+            tip => tip,
+        }
+    }
+
+    /// Returns the maximum amount of wei the transaction can cost:
+    /// `gas_limit * max_fee_per_gas + value`.
+    pub fn max_cost(&self) -> U256 {
+        U256::from(self.gas_limit)
+            .saturating_mul(U256::from(self.max_fee_per_gas))
+            .saturating_add(self.value)
+    }
+
     /// Decodes the inner [TxEip1559] fields from RLP bytes.
     ///
     /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
@@ -260,6 +281,12 @@ impl TxEip1559 {
     }
 }
 
+impl Typed2718 for TxEip1559 {
+    fn ty(&self) -> u8 {
+        self.tx_type().into()
+    }
+}
+
 impl Transaction for TxEip1559 {
     fn chain_id(&self) -> Option<ChainId> {
         Some(self.chain_id)
@@ -305,14 +332,15 @@ impl SignableTransaction<Signature> for TxEip1559 {
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self> {
+        // Drop any v chain id value to ensure the signature format is correct at the time of
+        // combination for an EIP-1559 transaction. V should indicate the y-parity of the
+        // signature, and the hash must commit to the encoding with that parity.
+        let signature = signature.with_parity_bool();
+
         let mut buf = Vec::with_capacity(self.encoded_len_with_signature(&signature, false));
         self.encode_with_signature(&signature, &mut buf, false);
         let hash = keccak256(&buf);
-
-        // Drop any v chain id value to ensure the signature format is correct at the time of
-        // combination for an EIP-1559 transaction. V should indicate the y-parity of the
-        // signature.
-        Signed::new_unchecked(self, signature.with_parity_bool(), hash)
+        Signed::new_unchecked(self, signature, hash)
     }
 }
 
@@ -382,6 +410,30 @@ mod tests {
         assert_eq!(signed_tx.recover_signer().unwrap(), signer, "Recovering signer should pass.");
     }
 
+    #[test]
+    fn fees_and_max_cost() {
+        let tx = TxEip1559 {
+            max_priority_fee_per_gas: 2,
+            max_fee_per_gas: 10,
+            gas_limit: 21_000,
+            value: U256::from(1),
+            ..Default::default()
+        };
+
+        assert_eq!(tx.effective_gas_price(None), 10);
+        assert_eq!(tx.effective_gas_price(Some(5)), 7);
+        assert_eq!(tx.effective_tip_per_gas(5), Some(2));
+        assert_eq!(tx.effective_tip_per_gas(10), Some(0));
+        assert_eq!(tx.effective_tip_per_gas(11), None);
+        assert_eq!(tx.max_cost(), U256::from(21_000 * 10 + 1));
+
+        // Base fees are not truncated to 64 bits.
+        let base_fee = u64::MAX as u128 + 1;
+        let tx = TxEip1559 { max_priority_fee_per_gas: 2, max_fee_per_gas: base_fee + 5, ..tx };
+        assert_eq!(tx.effective_gas_price(Some(base_fee)), base_fee + 2);
+        assert_eq!(tx.effective_tip_per_gas(base_fee), Some(2));
+    }
+
     #[test]
     fn encode_decode_eip1559() {
         let hash: B256 = b256!("0ec0b6a2df4d87424e5f6ad2a654e27aaeb7dac20ae9e8385cc09087ad532ee0");
@@ -412,3 +464,10 @@ mod tests {
         assert_eq!(*decoded.hash(), hash);
     }
 }
+
+impl InMemorySize for TxEip1559 {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.access_list.heap_size() + self.input.heap_size()
+    }
+}
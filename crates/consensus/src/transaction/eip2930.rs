@@ -1,5 +1,7 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
 use crate::{SignableTransaction, Signed, Transaction, TxType};
-use alloy_eips::eip2930::AccessList;
+use alloy_eips::{eip2718::Typed2718, eip2930::AccessList, InMemorySize};
 use alloy_primitives::{keccak256, Bytes, ChainId, Signature, TxKind, U256};
 use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable, Header};
 use core::mem;
@@ -9,6 +11,7 @@ use alloc::vec::Vec;
 
 /// Transaction with an [`AccessList`] ([EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)).
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[doc(alias = "Eip2930Transaction", alias = "TransactionEip2930", alias = "Eip2930Tx")]
@@ -43,6 +46,7 @@ pub struct TxEip2930 {
     /// be transferred to the message call’s recipient or,
     /// in the case of contract creation, as an endowment
     /// to the newly created account; formally Tv.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
     pub value: U256,
     /// The accessList specifies a list of addresses and storage keys;
     /// these addresses and storage keys are added into the `accessed_addresses`
@@ -59,6 +63,24 @@ pub struct TxEip2930 {
 }
 
 impl TxEip2930 {
+    /// Returns the effective gas price, which is the gas price regardless of the base fee.
+    pub const fn effective_gas_price(&self, _base_fee: Option<u128>) -> u128 {
+        self.gas_price
+    }
+
+    /// Returns the priority fee per gas paid to the block producer for the given `base_fee`, or
+    /// `None` if the gas price is lower than the base fee.
+    pub const fn effective_tip_per_gas(&self, base_fee: u128) -> Option<u128> {
+        self.gas_price.checked_sub(base_fee)
+    }
+
+    /// Returns the maximum amount of wei the transaction can cost: `gas_limit * gas_price + value`.
+    pub fn max_cost(&self) -> U256 {
+        U256::from(self.gas_limit)
+            .saturating_mul(U256::from(self.gas_price))
+            .saturating_add(self.value)
+    }
+
     /// Calculates a heuristic for the in-memory size of the [TxEip2930] transaction.
     #[inline]
     pub fn size(&self) -> usize {
@@ -224,6 +246,12 @@ impl TxEip2930 {
     }
 }
 
+impl Typed2718 for TxEip2930 {
+    fn ty(&self) -> u8 {
+        self.tx_type().into()
+    }
+}
+
 impl Transaction for TxEip2930 {
     fn chain_id(&self) -> Option<ChainId> {
         Some(self.chain_id)
@@ -272,14 +300,15 @@ impl SignableTransaction<Signature> for TxEip2930 {
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self> {
+        // Drop any v chain id value to ensure the signature format is correct at the time of
+        // combination for an EIP-2930 transaction. V should indicate the y-parity of the
+        // signature, and the hash must commit to the encoding with that parity.
+        let signature = signature.with_parity_bool();
+
         let mut buf = Vec::with_capacity(self.encoded_len_with_signature(&signature, false));
         self.encode_with_signature(&signature, &mut buf, false);
         let hash = keccak256(&buf);
-
-        // Drop any v chain id value to ensure the signature format is correct at the time of
-        // combination for an EIP-2930 transaction. V should indicate the y-parity of the
-        // signature.
-        Signed::new_unchecked(self, signature.with_parity_bool(), hash)
+        Signed::new_unchecked(self, signature, hash)
     }
 }
 
@@ -308,6 +337,13 @@ impl Decodable for TxEip2930 {
     }
 }
 
+impl InMemorySize for TxEip2930 {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.access_list.heap_size() + self.input.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::TxEip2930;
@@ -1,6 +1,9 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
 use crate::{SignableTransaction, Signed, Transaction, TxType};
+use alloy_eips::InMemorySize;
 
-use alloy_eips::{eip2930::AccessList, eip4844::DATA_GAS_PER_BLOB};
+use alloy_eips::{eip2718::Typed2718, eip2930::AccessList, eip4844::DATA_GAS_PER_BLOB};
 use alloy_primitives::{keccak256, Address, Bytes, ChainId, Signature, TxKind, B256, U256};
 use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable, Header};
 use core::mem;
@@ -75,6 +78,22 @@ impl From<(TxEip4844, BlobTransactionSidecar)> for TxEip4844Variant {
     }
 }
 
+#[cfg(any(test, feature = "proptest"))]
+impl proptest::arbitrary::Arbitrary for TxEip4844Variant {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        use proptest::{arbitrary::any, prop_oneof, strategy::Strategy};
+
+        prop_oneof![
+            any::<TxEip4844>().prop_map(Self::TxEip4844),
+            any::<TxEip4844WithSidecar>().prop_map(Self::TxEip4844WithSidecar),
+        ]
+        .boxed()
+    }
+}
+
 impl TxEip4844Variant {
     /// Verifies that the transaction's blob data, commitments, and proofs are all valid.
     ///
@@ -191,6 +210,12 @@ impl TxEip4844Variant {
     }
 }
 
+impl Typed2718 for TxEip4844Variant {
+    fn ty(&self) -> u8 {
+        self.tx_type().into()
+    }
+}
+
 impl Transaction for TxEip4844Variant {
     fn chain_id(&self) -> Option<ChainId> {
         match self {
@@ -268,16 +293,17 @@ impl SignableTransaction<Signature> for TxEip4844Variant {
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self> {
+        // Drop any v chain id value to ensure the signature format is correct at the time of
+        // combination for an EIP-4844 transaction. V should indicate the y-parity of the
+        // signature, and the hash must commit to the encoding with that parity.
+        let signature = signature.with_parity_bool();
+
         let payload_length = 1 + self.fields_len() + signature.rlp_vrs_len();
         let mut buf = Vec::with_capacity(payload_length);
         // we use the inner tx to encode the fields
         self.tx().encode_with_signature(&signature, &mut buf, false);
         let hash = keccak256(&buf);
-
-        // Drop any v chain id value to ensure the signature format is correct at the time of
-        // combination for an EIP-4844 transaction. V should indicate the y-parity of the
-        // signature.
-        Signed::new_unchecked(self, signature.with_parity_bool(), hash)
+        Signed::new_unchecked(self, signature, hash)
     }
 }
 
@@ -285,6 +311,7 @@ impl SignableTransaction<Signature> for TxEip4844Variant {
 ///
 /// A transaction with blob hashes and max blob fee. It does not have the Blob sidecar.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[doc(alias = "Eip4844Transaction", alias = "TransactionEip4844", alias = "Eip4844Tx")]
@@ -330,6 +357,7 @@ pub struct TxEip4844 {
     /// be transferred to the message call’s recipient or,
     /// in the case of contract creation, as an endowment
     /// to the newly created account; formally Tv.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
     pub value: U256,
     /// The accessList specifies a list of addresses and storage keys;
     /// these addresses and storage keys are added into the `accessed_addresses`
@@ -339,6 +367,12 @@ pub struct TxEip4844 {
     pub access_list: AccessList,
 
     /// It contains a vector of fixed size hash(32 bytes)
+    #[cfg_attr(
+        any(test, feature = "proptest"),
+        proptest(
+            strategy = "proptest::collection::vec(proptest::arbitrary::any::<B256>(), 1..=alloy_eips::eip4844::BlobParams::CANCUN.max_blobs_per_block())"
+        )
+    )]
     pub blob_versioned_hashes: Vec<B256>,
 
     /// Max fee per data gas
@@ -357,15 +391,15 @@ pub struct TxEip4844 {
 
 impl TxEip4844 {
     /// Returns the effective gas price for the given `base_fee`.
-    pub const fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+    pub const fn effective_gas_price(&self, base_fee: Option<u128>) -> u128 {
         match base_fee {
             None => self.max_fee_per_gas,
             Some(base_fee) => {
                 // if the tip is greater than the max priority fee per gas, set it to the max
                 // priority fee per gas + base fee
-                let tip = self.max_fee_per_gas.saturating_sub(base_fee as u128);
+                let tip = self.max_fee_per_gas.saturating_sub(base_fee);
                 if tip > self.max_priority_fee_per_gas {
-                    self.max_priority_fee_per_gas + base_fee as u128
+                    self.max_priority_fee_per_gas + base_fee
                 } else {
                     // otherwise return the max fee per gas
                     self.max_fee_per_gas
@@ -374,11 +408,34 @@ impl TxEip4844 {
         }
     }
 
+    /// Returns the priority fee per gas paid to the block producer for the given `base_fee`, or
+    /// `None` if the max fee per gas is lower than the base fee.
+    pub const fn effective_tip_per_gas(&self, base_fee: u128) -> Option<u128> {
+        match self.max_fee_per_gas.checked_sub(base_fee) {
+            Some(tip) if tip > self.max_priority_fee_per_gas => Some(self.max_priority_fee_per_gas),
+            tip => tip,
+        }
+    }
+
+    /// Returns the maximum amount of wei the transaction can cost:
+    /// `gas_limit * max_fee_per_gas + value + blob_gas * max_fee_per_blob_gas`.
+    pub fn max_cost(&self) -> U256 {
+        U256::from(self.gas_limit)
+            .saturating_mul(U256::from(self.max_fee_per_gas))
+            .saturating_add(self.value)
+            .saturating_add(self.max_blob_fee())
+    }
+
+    /// Returns the maximum amount of wei the transaction can pay for blob gas:
+    /// `blob_gas * max_fee_per_blob_gas`.
+    pub fn max_blob_fee(&self) -> U256 {
+        U256::from(self.blob_gas()).saturating_mul(U256::from(self.max_fee_per_blob_gas))
+    }
+
     /// Returns the total gas for all blobs in this transaction.
     #[inline]
-    pub fn blob_gas(&self) -> u64 {
-        // SAFETY: we don't expect u64::MAX / DATA_GAS_PER_BLOB hashes in a single transaction
-        self.blob_versioned_hashes.len() as u64 * DATA_GAS_PER_BLOB
+    pub fn blob_gas(&self) -> u128 {
+        self.blob_versioned_hashes.len() as u128 * DATA_GAS_PER_BLOB as u128
     }
 
     /// Verifies that the given blob data, commitments, and proofs are all valid for this
@@ -617,14 +674,21 @@ impl SignableTransaction<Signature> for TxEip4844 {
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self> {
+        // Drop any v chain id value to ensure the signature format is correct at the time of
+        // combination for an EIP-4844 transaction. V should indicate the y-parity of the
+        // signature, and the hash must commit to the encoding with that parity.
+        let signature = signature.with_parity_bool();
+
         let mut buf = Vec::with_capacity(self.encoded_len_with_signature(&signature, false));
         self.encode_with_signature(&signature, &mut buf, false);
         let hash = keccak256(&buf);
+        Signed::new_unchecked(self, signature, hash)
+    }
+}
 
-        // Drop any v chain id value to ensure the signature format is correct at the time of
-        // combination for an EIP-4844 transaction. V should indicate the y-parity of the
-        // signature.
-        Signed::new_unchecked(self, signature.with_parity_bool(), hash)
+impl Typed2718 for TxEip4844 {
+    fn ty(&self) -> u8 {
+        self.tx_type().into()
     }
 }
 
@@ -713,6 +777,25 @@ pub struct TxEip4844WithSidecar {
     pub sidecar: BlobTransactionSidecar,
 }
 
+/// Generates transactions whose blob versioned hashes are the ones of the commitments of their
+/// sidecar.
+#[cfg(any(test, feature = "proptest"))]
+impl proptest::arbitrary::Arbitrary for TxEip4844WithSidecar {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        use proptest::{arbitrary::any, strategy::Strategy};
+
+        any::<(TxEip4844, BlobTransactionSidecar)>()
+            .prop_map(|(mut tx, sidecar)| {
+                tx.blob_versioned_hashes = sidecar.versioned_hashes().collect();
+                Self { tx, sidecar }
+            })
+            .boxed()
+    }
+}
+
 impl TxEip4844WithSidecar {
     /// Constructs a new [TxEip4844WithSidecar] from a [TxEip4844] and a [BlobTransactionSidecar].
     #[doc(alias = "from_transaction_and_sidecar")]
@@ -847,6 +930,11 @@ impl SignableTransaction<Signature> for TxEip4844WithSidecar {
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self, Signature> {
+        // Drop any v chain id value to ensure the signature format is correct at the time of
+        // combination for an EIP-4844 transaction. V should indicate the y-parity of the
+        // signature, and the hash must commit to the encoding with that parity.
+        let signature = signature.with_parity_bool();
+
         let mut buf = Vec::with_capacity(self.tx.encoded_len_with_signature(&signature, false));
         // The sidecar is NOT included in the signed payload, only the transaction fields and the
         // type byte. Include the type byte.
@@ -855,11 +943,13 @@ impl SignableTransaction<Signature> for TxEip4844WithSidecar {
         // encode a header.
         self.tx.encode_with_signature(&signature, &mut buf, false);
         let hash = keccak256(&buf);
+        Signed::new_unchecked(self, signature, hash)
+    }
+}
 
-        // Drop any v chain id value to ensure the signature format is correct at the time of
-        // combination for an EIP-4844 transaction. V should indicate the y-parity of the
-        // signature.
-        Signed::new_unchecked(self, signature.with_parity_bool(), hash)
+impl Typed2718 for TxEip4844WithSidecar {
+    fn ty(&self) -> u8 {
+        self.tx_type().into()
     }
 }
 
@@ -893,11 +983,37 @@ impl Transaction for TxEip4844WithSidecar {
     }
 }
 
+impl InMemorySize for TxEip4844Variant {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::TxEip4844(tx) => tx.heap_size(),
+            Self::TxEip4844WithSidecar(tx) => tx.heap_size(),
+        }
+    }
+}
+
+impl InMemorySize for TxEip4844 {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.access_list.heap_size()
+            + self.blob_versioned_hashes.heap_size()
+            + self.input.heap_size()
+    }
+}
+
+impl InMemorySize for TxEip4844WithSidecar {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.tx.heap_size() + self.sidecar.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{BlobTransactionSidecar, TxEip4844, TxEip4844WithSidecar};
     use crate::{transaction::eip4844::TxEip4844Variant, SignableTransaction, TxEnvelope};
-    use alloy_eips::eip2930::AccessList;
+    use alloy_eips::{eip2930::AccessList, InMemorySize};
     use alloy_primitives::{address, b256, bytes, Signature, U256};
     use alloy_rlp::{Decodable, Encodable};
 
@@ -994,4 +1110,44 @@ mod tests {
             b256!("93fc9daaa0726c3292a2e939df60f7e773c6a6a726a61ce43f4a217c64d85e87")
         );
     }
+
+    #[test]
+    fn fees_and_max_cost() {
+        let tx = TxEip4844 {
+            max_priority_fee_per_gas: 2,
+            max_fee_per_gas: 10,
+            gas_limit: 21_000,
+            value: U256::from(1),
+            blob_versioned_hashes: vec![Default::default(); 2],
+            max_fee_per_blob_gas: 3,
+            ..Default::default()
+        };
+
+        assert_eq!(tx.effective_gas_price(Some(5)), 7);
+        assert_eq!(tx.effective_gas_price(Some(9)), 10);
+        assert_eq!(tx.effective_tip_per_gas(5), Some(2));
+        assert_eq!(tx.effective_tip_per_gas(9), Some(1));
+        assert_eq!(tx.effective_tip_per_gas(11), None);
+        assert_eq!(tx.max_blob_fee(), U256::from(2 * 131_072 * 3));
+        assert_eq!(tx.max_cost(), U256::from(21_000 * 10 + 1 + 2 * 131_072 * 3));
+    }
+
+    #[test]
+    fn heap_size_includes_sidecar() {
+        let tx = TxEip4844 {
+            blob_versioned_hashes: vec![Default::default()],
+            input: bytes!("deadbeef"),
+            ..Default::default()
+        };
+        assert_eq!(tx.heap_size(), 32 + 4);
+
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![[2; 131072].into()],
+            commitments: vec![[3; 48].into()],
+            proofs: vec![[4; 48].into()],
+        };
+        let tx = TxEip4844WithSidecar { tx, sidecar };
+        assert_eq!(tx.heap_size(), tx.tx.heap_size() + 131072 + 48 + 48);
+        assert_eq!(TxEip4844Variant::from(tx.clone()).heap_size(), tx.heap_size());
+    }
 }
@@ -1,10 +1,14 @@
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
+use alloy_eips::InMemorySize;
 use core::fmt;
 
 use crate::{Signed, TxEip1559, TxEip2930, TxLegacy};
-use alloy_eips::eip2718::{Decodable2718, Eip2718Error, Eip2718Result, Encodable2718};
-use alloy_primitives::B256;
-use alloy_rlp::{Decodable, Encodable, Header};
+use alloy_eips::eip2718::{Decodable2718, Eip2718Error, Eip2718Result, Encodable2718, Typed2718};
+use alloy_primitives::{B256, U256};
+use alloy_rlp::{Decodable, Encodable, Header, EMPTY_LIST_CODE, EMPTY_STRING_CODE};
 
+#[cfg(feature = "eip4844")]
 use crate::transaction::eip4844::{TxEip4844, TxEip4844Variant, TxEip4844WithSidecar};
 
 /// Ethereum `TransactionType` flags as specified in EIPs [2718], [1559], and
@@ -16,6 +20,7 @@ use crate::transaction::eip4844::{TxEip4844, TxEip4844Variant, TxEip4844WithSide
 /// [4844]: https://eips.ethereum.org/EIPS/eip-4844
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[doc(alias = "TransactionType")]
 pub enum TxType {
     /// Legacy transaction type.
@@ -34,6 +39,12 @@ impl From<TxType> for u8 {
     }
 }
 
+impl Typed2718 for TxType {
+    fn ty(&self) -> u8 {
+        (*self).into()
+    }
+}
+
 impl fmt::Display for TxType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -92,6 +103,7 @@ pub enum TxEnvelope {
     /// A [`TxEip1559`] tagged with type 2.
     #[cfg_attr(feature = "serde", serde(rename = "0x2", alias = "0x02"))]
     Eip1559(Signed<TxEip1559>),
+    #[cfg(feature = "eip4844")]
     /// A TxEip4844 tagged with type 3.
     /// An EIP-4844 transaction has two network representations:
     /// 1 - The transaction itself, which is a regular RLP-encoded transaction and used to retrieve
@@ -121,12 +133,14 @@ impl From<Signed<TxEip1559>> for TxEnvelope {
     }
 }
 
+#[cfg(feature = "eip4844")]
 impl From<Signed<TxEip4844Variant>> for TxEnvelope {
     fn from(v: Signed<TxEip4844Variant>) -> Self {
         Self::Eip4844(v)
     }
 }
 
+#[cfg(feature = "eip4844")]
 impl From<Signed<TxEip4844>> for TxEnvelope {
     fn from(v: Signed<TxEip4844>) -> Self {
         let (tx, signature, hash) = v.into_parts();
@@ -134,6 +148,7 @@ impl From<Signed<TxEip4844>> for TxEnvelope {
     }
 }
 
+#[cfg(feature = "eip4844")]
 impl From<Signed<TxEip4844WithSidecar>> for TxEnvelope {
     fn from(v: Signed<TxEip4844WithSidecar>) -> Self {
         let (tx, signature, hash) = v.into_parts();
@@ -141,6 +156,58 @@ impl From<Signed<TxEip4844WithSidecar>> for TxEnvelope {
     }
 }
 
+#[cfg(any(test, feature = "proptest"))]
+impl proptest::arbitrary::Arbitrary for TxEnvelope {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        use proptest::{arbitrary::any, strategy::Strategy};
+
+        #[allow(unused_mut)]
+        let mut variants = vec![
+            any::<Signed<TxLegacy>>().prop_map(Self::Legacy).boxed(),
+            any::<Signed<TxEip2930>>().prop_map(Self::Eip2930).boxed(),
+            any::<Signed<TxEip1559>>().prop_map(Self::Eip1559).boxed(),
+        ];
+        #[cfg(feature = "eip4844")]
+        variants.push(any::<Signed<TxEip4844Variant>>().prop_map(Self::Eip4844).boxed());
+        proptest::strategy::Union::new(variants).boxed()
+    }
+}
+
+#[cfg(all(any(test, feature = "proptest"), feature = "k256"))]
+impl TxEnvelope {
+    /// Returns a strategy generating transactions signed by random keys, along with the address of
+    /// their signer.
+    ///
+    /// See [`Signed::arbitrary_validly_signed`].
+    pub fn arbitrary_validly_signed(
+    ) -> impl proptest::strategy::Strategy<Value = (Self, alloy_primitives::Address)> {
+        use proptest::strategy::Strategy;
+
+        #[allow(unused_mut)]
+        let mut variants = vec![
+            Signed::<TxLegacy>::arbitrary_validly_signed()
+                .prop_map(|(tx, signer)| (Self::Legacy(tx), signer))
+                .boxed(),
+            Signed::<TxEip2930>::arbitrary_validly_signed()
+                .prop_map(|(tx, signer)| (Self::Eip2930(tx), signer))
+                .boxed(),
+            Signed::<TxEip1559>::arbitrary_validly_signed()
+                .prop_map(|(tx, signer)| (Self::Eip1559(tx), signer))
+                .boxed(),
+        ];
+        #[cfg(feature = "eip4844")]
+        variants.push(
+            Signed::<TxEip4844Variant>::arbitrary_validly_signed()
+                .prop_map(|(tx, signer)| (Self::Eip4844(tx), signer))
+                .boxed(),
+        );
+        proptest::strategy::Union::new(variants)
+    }
+}
+
 impl TxEnvelope {
     /// Returns true if the transaction is a legacy transaction.
     #[inline]
@@ -160,6 +227,7 @@ impl TxEnvelope {
         matches!(self, Self::Eip1559(_))
     }
 
+    #[cfg(feature = "eip4844")]
     /// Returns true if the transaction is an EIP-4844 transaction.
     #[inline]
     pub const fn is_eip4844(&self) -> bool {
@@ -190,24 +258,76 @@ impl TxEnvelope {
         }
     }
 
+    #[cfg(feature = "eip4844")]
     /// Returns the [`TxEip4844`] variant if the transaction is an EIP-4844 transaction.
     pub const fn as_eip4844(&self) -> Option<&Signed<TxEip4844Variant>> {
         match self {
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => Some(tx),
             _ => None,
         }
     }
 
     /// Recover the signer of the transaction.
+    ///
+    /// See [`Signed::recover_signer`].
     #[cfg(feature = "k256")]
     pub fn recover_signer(
         &self,
+    ) -> Result<alloy_primitives::Address, alloy_primitives::SignatureError> {
+        self.recover_signer_with_policy(crate::RecoveryPolicy::default())
+    }
+
+    /// Recover the signer of the transaction, applying the given [`RecoveryPolicy`] to the `s`
+    /// value of the signature.
+    ///
+    /// [`RecoveryPolicy`]: crate::RecoveryPolicy
+    #[cfg(feature = "k256")]
+    pub fn recover_signer_with_policy(
+        &self,
+        policy: crate::RecoveryPolicy,
     ) -> Result<alloy_primitives::Address, alloy_primitives::SignatureError> {
         match self {
-            Self::Legacy(tx) => tx.recover_signer(),
-            Self::Eip2930(tx) => tx.recover_signer(),
-            Self::Eip1559(tx) => tx.recover_signer(),
-            Self::Eip4844(tx) => tx.recover_signer(),
+            Self::Legacy(tx) => tx.recover_signer_with_policy(policy),
+            Self::Eip2930(tx) => tx.recover_signer_with_policy(policy),
+            Self::Eip1559(tx) => tx.recover_signer_with_policy(policy),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.recover_signer_with_policy(policy),
+        }
+    }
+
+    /// Returns the effective gas price for the given `base_fee`.
+    pub const fn effective_gas_price(&self, base_fee: Option<u128>) -> u128 {
+        match self {
+            Self::Legacy(tx) => tx.tx().effective_gas_price(base_fee),
+            Self::Eip2930(tx) => tx.tx().effective_gas_price(base_fee),
+            Self::Eip1559(tx) => tx.tx().effective_gas_price(base_fee),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.tx().tx().effective_gas_price(base_fee),
+        }
+    }
+
+    /// Returns the priority fee per gas paid to the block producer for the given `base_fee`, or
+    /// `None` if the transaction does not pay the base fee.
+    pub const fn effective_tip_per_gas(&self, base_fee: u128) -> Option<u128> {
+        match self {
+            Self::Legacy(tx) => tx.tx().effective_tip_per_gas(base_fee),
+            Self::Eip2930(tx) => tx.tx().effective_tip_per_gas(base_fee),
+            Self::Eip1559(tx) => tx.tx().effective_tip_per_gas(base_fee),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.tx().tx().effective_tip_per_gas(base_fee),
+        }
+    }
+
+    /// Returns the maximum amount of wei the transaction can cost, including its value and blob
+    /// fees.
+    pub fn max_cost(&self) -> U256 {
+        match self {
+            Self::Legacy(tx) => tx.tx().max_cost(),
+            Self::Eip2930(tx) => tx.tx().max_cost(),
+            Self::Eip1559(tx) => tx.tx().max_cost(),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.tx().tx().max_cost(),
         }
     }
 
@@ -217,6 +337,7 @@ impl TxEnvelope {
             Self::Legacy(tx) => tx.signature_hash(),
             Self::Eip2930(tx) => tx.signature_hash(),
             Self::Eip1559(tx) => tx.signature_hash(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.signature_hash(),
         }
     }
@@ -228,6 +349,7 @@ impl TxEnvelope {
             Self::Legacy(tx) => tx.hash(),
             Self::Eip2930(tx) => tx.hash(),
             Self::Eip1559(tx) => tx.hash(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.hash(),
         }
     }
@@ -239,6 +361,7 @@ impl TxEnvelope {
             Self::Legacy(_) => TxType::Legacy,
             Self::Eip2930(_) => TxType::Eip2930,
             Self::Eip1559(_) => TxType::Eip1559,
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(_) => TxType::Eip4844,
         }
     }
@@ -246,7 +369,7 @@ impl TxEnvelope {
     /// Return the length of the inner txn, __without a type byte__.
     pub fn inner_length(&self) -> usize {
         match self {
-            Self::Legacy(t) => t.tx().fields_len() + t.signature().rlp_vrs_len(),
+            Self::Legacy(t) => t.tx().encoded_len_with_signature(t.signature()),
             Self::Eip2930(t) => {
                 let payload_length = t.tx().fields_len() + t.signature().rlp_vrs_len();
                 Header { list: true, payload_length }.length() + payload_length
@@ -255,6 +378,7 @@ impl TxEnvelope {
                 let payload_length = t.tx().fields_len() + t.signature().rlp_vrs_len();
                 Header { list: true, payload_length }.length() + payload_length
             }
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(t) => match t.tx() {
                 TxEip4844Variant::TxEip4844(tx) => {
                     let payload_length = tx.fields_len() + t.signature().rlp_vrs_len();
@@ -320,26 +444,32 @@ impl Decodable2718 for TxEnvelope {
         match ty.try_into().map_err(|_| alloy_rlp::Error::Custom("unexpected tx type"))? {
             TxType::Eip2930 => Ok(TxEip2930::decode_signed_fields(buf)?.into()),
             TxType::Eip1559 => Ok(TxEip1559::decode_signed_fields(buf)?.into()),
+            #[cfg(feature = "eip4844")]
             TxType::Eip4844 => Ok(TxEip4844Variant::decode_signed_fields(buf)?.into()),
+            #[cfg(not(feature = "eip4844"))]
+            TxType::Eip4844 => Err(Eip2718Error::UnexpectedType(ty)),
             TxType::Legacy => Err(Eip2718Error::UnexpectedType(0)),
         }
     }
 
     fn fallback_decode(buf: &mut &[u8]) -> Eip2718Result<Self> {
-        Ok(TxLegacy::decode_signed_fields(buf)?.into())
+        match buf.first() {
+            // A typed transaction wrapped in an RLP string, as framed in the devp2p messages.
+            Some(&byte) if (EMPTY_STRING_CODE..EMPTY_LIST_CODE).contains(&byte) => {
+                Self::network_decode(buf)
+            }
+            _ => Ok(TxLegacy::decode_signed_fields(buf)?.into()),
+        }
     }
 }
 
-impl Encodable2718 for TxEnvelope {
-    fn type_flag(&self) -> Option<u8> {
-        match self {
-            Self::Legacy(_) => None,
-            Self::Eip2930(_) => Some(TxType::Eip2930.into()),
-            Self::Eip1559(_) => Some(TxType::Eip1559.into()),
-            Self::Eip4844(_) => Some(TxType::Eip4844.into()),
-        }
+impl Typed2718 for TxEnvelope {
+    fn ty(&self) -> u8 {
+        self.tx_type().into()
     }
+}
 
+impl Encodable2718 for TxEnvelope {
     fn encode_2718_len(&self) -> usize {
         self.inner_length() + !self.is_legacy() as usize
     }
@@ -354,6 +484,7 @@ impl Encodable2718 for TxEnvelope {
             Self::Eip1559(tx) => {
                 tx.tx().encode_with_signature(tx.signature(), out, false);
             }
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => {
                 tx.tx().encode_with_signature(tx.signature(), out, false);
             }
@@ -361,6 +492,19 @@ impl Encodable2718 for TxEnvelope {
     }
 }
 
+impl InMemorySize for TxEnvelope {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::Legacy(tx) => tx.heap_size(),
+            Self::Eip2930(tx) => tx.heap_size(),
+            Self::Eip1559(tx) => tx.heap_size(),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.heap_size(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -369,7 +513,7 @@ mod tests {
     use alloy_primitives::{hex, Address, Signature, U256};
     #[allow(unused_imports)]
     use alloy_primitives::{Bytes, TxKind};
-    use std::{fs, path::PathBuf, vec};
+    use std::vec;
 
     #[cfg(not(feature = "std"))]
     use std::vec::Vec;
@@ -420,7 +564,7 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "k256")]
+    #[cfg(all(feature = "k256", feature = "eip4844"))]
     // Test vector from https://sepolia.etherscan.io/tx/0x9a22ccb0029bc8b0ddd073be1a1d923b7ae2b2ea52100bae0db4424f9107e9c0
     // Blobscan: https://sepolia.blobscan.com/tx/0x9a22ccb0029bc8b0ddd073be1a1d923b7ae2b2ea52100bae0db4424f9107e9c0
     fn test_decode_live_4844_tx() {
@@ -460,6 +604,18 @@ mod tests {
         assert_eq!(from, address!("A83C816D4f9b2783761a22BA6FADB0eB0606D7B2"));
     }
 
+    #[test]
+    #[cfg(not(feature = "eip4844"))]
+    fn test_decode_4844_tx_without_eip4844() {
+        // https://sepolia.etherscan.io/getRawTx?tx=0x9a22ccb0029bc8b0ddd073be1a1d923b7ae2b2ea52100bae0db4424f9107e9c0
+        let raw_tx = hex::decode("0x03f9011d83aa36a7820fa28477359400852e90edd0008252089411e9ca82a3a762b4b5bd264d4173a242e7a770648080c08504a817c800f8a5a0012ec3d6f66766bedb002a190126b3549fce0047de0d4c25cffce0dc1c57921aa00152d8e24762ff22b1cfd9f8c0683786a7ca63ba49973818b3d1e9512cd2cec4a0013b98c6c83e066d5b14af2b85199e3d4fc7d1e778dd53130d180f5077e2d1c7a001148b495d6e859114e670ca54fb6e2657f0cbae5b08063605093a4b3dc9f8f1a0011ac212f13c5dff2b2c6b600a79635103d6f580a4221079951181b25c7e654901a0c8de4cced43169f9aa3d36506363b2d2c44f6c49fc1fd91ea114c86f3757077ea01e11fdd0d1934eda0492606ee0bb80a7bf8f35cc5f86ec60fe5031ba48bfd544").unwrap();
+        assert!(matches!(
+            TxEnvelope::decode_2718(&mut raw_tx.as_slice()),
+            Err(Eip2718Error::UnexpectedType(3))
+        ));
+        assert!(TxEnvelope::decode(&mut raw_tx.as_slice()).is_err());
+    }
+
     fn test_encode_decode_roundtrip<T: SignableTransaction<Signature>>(tx: T)
     where
         Signed<T>: Into<TxEnvelope>,
@@ -531,7 +687,81 @@ mod tests {
     }
 
     #[test]
+    fn test_decode_2718_consumes_typed_envelope() {
+        let tx: TxEnvelope = TxEip1559::default().into_signed(Signature::test_signature()).into();
+        let mut encoded = tx.encoded_2718();
+        encoded.extend_from_slice(&[0xde, 0xad]);
+
+        let mut buf = &encoded[..];
+        assert_eq!(TxEnvelope::decode_2718(&mut buf).unwrap(), tx);
+        assert_eq!(buf, [0xde, 0xad]);
+    }
+
+    #[test]
+    fn test_decode_network_framed_transactions() {
+        let signature = Signature::test_signature();
+        let legacy = TxEnvelope::Legacy(
+            TxLegacy {
+                chain_id: Some(1),
+                nonce: 2,
+                gas_price: 3,
+                gas_limit: 4,
+                to: Address::left_padding_from(&[5]).into(),
+                value: U256::from(6_u64),
+                input: vec![7].into(),
+            }
+            .into_signed(signature),
+        );
+        let eip1559 = TxEnvelope::Eip1559(
+            TxEip1559 {
+                chain_id: 1u64,
+                nonce: 2,
+                max_fee_per_gas: 3,
+                max_priority_fee_per_gas: 4,
+                gas_limit: 5,
+                to: Address::left_padding_from(&[6]).into(),
+                value: U256::from(7_u64),
+                input: vec![8].into(),
+                access_list: Default::default(),
+            }
+            .into_signed(signature),
+        );
+
+        // A typed transaction framed in an RLP string is accepted by the EIP-2718 decoder.
+        let mut framed = Vec::new();
+        eip1559.network_encode(&mut framed);
+        assert_eq!(TxEnvelope::decode_2718(&mut framed.as_slice()).unwrap(), eip1559);
+        assert_eq!(TxEnvelope::fallback_decode(&mut framed.as_slice()).unwrap(), eip1559);
+
+        // A `Transactions` message with a malformed item in the middle.
+        let mut items = Vec::new();
+        legacy.network_encode(&mut items);
+        Header { list: false, payload_length: 2 }.encode(&mut items);
+        items.extend_from_slice(&[0x02, 0xc0]);
+        eip1559.network_encode(&mut items);
+        let mut message = Vec::new();
+        Header { list: true, payload_length: items.len() }.encode(&mut message);
+        message.extend_from_slice(&items);
+        message.push(0xff);
+
+        let buf = &mut message.as_slice();
+        let decoded = TxEnvelope::network_decode_list(buf).unwrap();
+        assert_eq!(buf, &[0xff]);
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].as_ref().unwrap(), &legacy);
+        assert!(decoded[1].is_err());
+        assert_eq!(decoded[2].as_ref().unwrap(), &eip1559);
+
+        // The list framing itself must be valid.
+        assert!(TxEnvelope::network_decode_list(&mut &message[..message.len() - 2]).is_err());
+        assert!(TxEnvelope::network_decode_list(&mut framed.as_slice()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "eip4844")]
     fn decode_encode_known_rpc_transaction() {
+        use std::{fs, path::PathBuf};
+
         // test data pulled from hive test that sends blob transactions
         let network_data_path =
             PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata/rpc_blob_transaction.rlp");
@@ -610,7 +840,7 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "serde")]
+    #[cfg(all(feature = "serde", feature = "eip4844"))]
     fn test_serde_roundtrip_eip4844() {
         let tx = TxEip4844Variant::TxEip4844(TxEip4844 {
             chain_id: 1,
@@ -651,4 +881,22 @@ mod tests {
         });
         test_serde_roundtrip(tx);
     }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn proptest_2718_roundtrip(tx in proptest::arbitrary::any::<TxEnvelope>()) {
+            let mut encoded = Vec::new();
+            tx.encode_2718(&mut encoded);
+            proptest::prop_assert_eq!(encoded.len(), tx.encode_2718_len());
+            proptest::prop_assert_eq!(TxEnvelope::decode_2718(&mut &encoded[..]).unwrap(), tx);
+        }
+
+        #[test]
+        #[cfg(feature = "k256")]
+        fn proptest_recover_valid_signer((tx, signer) in TxEnvelope::arbitrary_validly_signed()) {
+            proptest::prop_assert_eq!(tx.recover_signer().unwrap(), signer);
+        }
+    }
 }
@@ -1,5 +1,8 @@
-use crate::{SignableTransaction, Signed, Transaction};
-use alloy_primitives::{keccak256, Bytes, ChainId, Signature, TxKind, U256};
+#![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
+
+use crate::{SignableTransaction, Signed, Transaction, TxType};
+use alloy_eips::{eip2718::Typed2718, InMemorySize};
+use alloy_primitives::{keccak256, Bytes, ChainId, Parity, Signature, TxKind, U256};
 use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable, Header, Result};
 use core::mem;
 
@@ -8,6 +11,7 @@ use alloc::vec::Vec;
 
 /// Legacy transaction.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[doc(alias = "LegacyTransaction", alias = "TransactionLegacy", alias = "LegacyTx")]
@@ -21,6 +25,11 @@ pub struct TxLegacy {
             skip_serializing_if = "Option::is_none",
         )
     )]
+    // The chain ID is encoded in `v`, which must not overflow.
+    #[cfg_attr(
+        any(test, feature = "proptest"),
+        proptest(strategy = "proptest::option::of(0..=u64::from(u32::MAX))")
+    )]
     pub chain_id: Option<ChainId>,
     /// A scalar value equal to the number of transactions sent by the sender; formally Tn.
     #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
@@ -49,6 +58,7 @@ pub struct TxLegacy {
     /// be transferred to the message call’s recipient or,
     /// in the case of contract creation, as an endowment
     /// to the newly created account; formally Tv.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
     pub value: U256,
     /// Input has two uses depending if transaction is Create or Call (if `to` field is None or
     /// Some). pub init: An unlimited size byte array specifying the
@@ -62,6 +72,24 @@ impl TxLegacy {
     /// The EIP-2718 transaction type.
     pub const TX_TYPE: isize = 0;
 
+    /// Returns the effective gas price, which is the gas price regardless of the base fee.
+    pub const fn effective_gas_price(&self, _base_fee: Option<u128>) -> u128 {
+        self.gas_price
+    }
+
+    /// Returns the priority fee per gas paid to the block producer for the given `base_fee`, or
+    /// `None` if the gas price is lower than the base fee.
+    pub const fn effective_tip_per_gas(&self, base_fee: u128) -> Option<u128> {
+        self.gas_price.checked_sub(base_fee)
+    }
+
+    /// Returns the maximum amount of wei the transaction can cost: `gas_limit * gas_price + value`.
+    pub fn max_cost(&self) -> U256 {
+        U256::from(self.gas_limit)
+            .saturating_mul(U256::from(self.gas_price))
+            .saturating_add(self.value)
+    }
+
     /// Calculates a heuristic for the in-memory size of the [TxLegacy] transaction.
     #[inline]
     pub fn size(&self) -> usize {
@@ -197,6 +225,12 @@ impl TxLegacy {
     }
 }
 
+impl Typed2718 for TxLegacy {
+    fn ty(&self) -> u8 {
+        TxType::Legacy.into()
+    }
+}
+
 impl Transaction for TxLegacy {
     fn chain_id(&self) -> Option<ChainId> {
         self.chain_id
@@ -250,6 +284,11 @@ impl SignableTransaction<Signature> for TxLegacy {
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self> {
+        // Legacy signatures encode the chain ID in `v` since EIP-155, and `v` is 27 or 28 before.
+        let signature = self.chain_id.map_or_else(
+            || signature.with_parity(Parity::NonEip155(signature.v().y_parity())),
+            |chain_id| signature.with_chain_id(chain_id),
+        );
         let mut buf = Vec::with_capacity(self.encoded_len_with_signature(&signature));
         self.encode_with_signature_fields(&signature, &mut buf);
         let hash = keccak256(&buf);
@@ -348,4 +387,61 @@ mod tests {
         assert_eq!(tx.tx().chain_id, Some(1), "Expected same chain id");
         assert_eq!(expected, recovered, "Expected same signer");
     }
+
+    #[test]
+    // Test vector from https://etherscan.io/tx/0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060
+    fn pre_eip155_legacy() {
+        let raw_tx = hex!("f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a");
+        let signer = address!("a1e4380a3b1f749673e270229993ee55f35663b4");
+
+        let tx = TxLegacy::decode_signed_fields(&mut raw_tx.as_slice()).unwrap();
+        assert_eq!(tx.tx().chain_id, None);
+        assert_eq!(
+            *tx.hash(),
+            b256!("5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060")
+        );
+        assert_eq!(tx.recover_signer().unwrap(), signer);
+
+        // Signing without a chain ID yields a `v` of 27 or 28.
+        let (tx, signature, hash) = tx.into_parts();
+        let resigned = tx.into_signed(signature.with_parity_bool());
+        assert_eq!(*resigned.hash(), hash);
+        let mut buf = Vec::new();
+        resigned.tx().encode_with_signature_fields(resigned.signature(), &mut buf);
+        assert_eq!(buf, raw_tx);
+    }
+
+    #[test]
+    fn recover_high_s_signature() {
+        let raw_tx = hex!("f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a");
+        let (tx, signature, _) =
+            TxLegacy::decode_signed_fields(&mut raw_tx.as_slice()).unwrap().into_parts();
+
+        // The same signature with the complementary `s` value, as accepted before Homestead.
+        let n = crate::constants::SECP256K1N_HALF * U256::from(2) + U256::from(1);
+        let high_s = Signature::from_rs_and_parity(
+            signature.r(),
+            n - signature.s(),
+            signature.v().inverted(),
+        )
+        .unwrap();
+        let signed = tx.into_signed(high_s);
+
+        assert!(signed.recover_signer_with_policy(crate::RecoveryPolicy::Strict).is_err());
+        assert_eq!(
+            signed.recover_signer().unwrap(),
+            address!("a1e4380a3b1f749673e270229993ee55f35663b4")
+        );
+        assert_eq!(
+            signed.recover_signer_with_policy(crate::RecoveryPolicy::Lenient).unwrap(),
+            address!("a1e4380a3b1f749673e270229993ee55f35663b4")
+        );
+    }
+}
+
+impl InMemorySize for TxLegacy {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.input.heap_size()
+    }
 }
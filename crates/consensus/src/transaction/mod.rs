@@ -7,6 +7,9 @@ use core::any;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
+mod deposit;
+pub use deposit::{TxDeposit, DEPOSIT_TX_TYPE_ID};
+
 mod eip1559;
 pub use eip1559::TxEip1559;
 
@@ -14,14 +17,17 @@ mod eip2930;
 pub use eip2930::TxEip2930;
 
 /// [EIP-4844] constants, helpers, and types.
+#[cfg(feature = "eip4844")]
 pub mod eip4844;
 
+#[cfg(feature = "eip4844")]
 pub use alloy_eips::eip4844::{
     builder::{SidecarBuilder, SidecarCoder, SimpleCoder},
     utils as eip4844_utils, Blob, BlobTransactionSidecar, Bytes48,
 };
 #[cfg(feature = "kzg")]
 pub use eip4844::BlobTransactionValidationError;
+#[cfg(feature = "eip4844")]
 pub use eip4844::{TxEip4844, TxEip4844Variant, TxEip4844WithSidecar};
 
 mod envelope;
@@ -1,8 +1,8 @@
-use crate::{
-    transaction::eip4844::{TxEip4844, TxEip4844Variant, TxEip4844WithSidecar},
-    Transaction, TxEip1559, TxEip2930, TxEnvelope, TxLegacy, TxType,
-};
-use alloy_primitives::{ChainId, TxKind};
+#[cfg(feature = "eip4844")]
+use crate::transaction::eip4844::{TxEip4844, TxEip4844Variant, TxEip4844WithSidecar};
+use crate::{Transaction, TxEip1559, TxEip2930, TxEnvelope, TxLegacy, TxType};
+use alloy_eips::{eip2718::Typed2718, InMemorySize};
+use alloy_primitives::{ChainId, TxKind, U256};
 
 /// The TypedTransaction enum represents all Ethereum transaction request types.
 ///
@@ -25,6 +25,7 @@ pub enum TypedTransaction {
     /// EIP-1559 transaction
     #[cfg_attr(feature = "serde", serde(rename = "0x02", alias = "0x2"))]
     Eip1559(TxEip1559),
+    #[cfg(feature = "eip4844")]
     /// EIP-4844 transaction
     #[cfg_attr(feature = "serde", serde(rename = "0x03", alias = "0x3"))]
     Eip4844(TxEip4844Variant),
@@ -48,18 +49,21 @@ impl From<TxEip1559> for TypedTransaction {
     }
 }
 
+#[cfg(feature = "eip4844")]
 impl From<TxEip4844Variant> for TypedTransaction {
     fn from(tx: TxEip4844Variant) -> Self {
         Self::Eip4844(tx)
     }
 }
 
+#[cfg(feature = "eip4844")]
 impl From<TxEip4844> for TypedTransaction {
     fn from(tx: TxEip4844) -> Self {
         Self::Eip4844(tx.into())
     }
 }
 
+#[cfg(feature = "eip4844")]
 impl From<TxEip4844WithSidecar> for TypedTransaction {
     fn from(tx: TxEip4844WithSidecar) -> Self {
         Self::Eip4844(tx.into())
@@ -72,11 +76,32 @@ impl From<TxEnvelope> for TypedTransaction {
             TxEnvelope::Legacy(tx) => Self::Legacy(tx.strip_signature()),
             TxEnvelope::Eip2930(tx) => Self::Eip2930(tx.strip_signature()),
             TxEnvelope::Eip1559(tx) => Self::Eip1559(tx.strip_signature()),
+            #[cfg(feature = "eip4844")]
             TxEnvelope::Eip4844(tx) => Self::Eip4844(tx.strip_signature()),
         }
     }
 }
 
+#[cfg(any(test, feature = "proptest"))]
+impl proptest::arbitrary::Arbitrary for TypedTransaction {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        use proptest::{arbitrary::any, strategy::Strategy};
+
+        #[allow(unused_mut)]
+        let mut variants = vec![
+            any::<TxLegacy>().prop_map(Self::Legacy).boxed(),
+            any::<TxEip2930>().prop_map(Self::Eip2930).boxed(),
+            any::<TxEip1559>().prop_map(Self::Eip1559).boxed(),
+        ];
+        #[cfg(feature = "eip4844")]
+        variants.push(any::<TxEip4844Variant>().prop_map(Self::Eip4844).boxed());
+        proptest::strategy::Union::new(variants).boxed()
+    }
+}
+
 impl TypedTransaction {
     /// Return the [`TxType`] of the inner txn.
     #[doc(alias = "transaction_type")]
@@ -85,6 +110,7 @@ impl TypedTransaction {
             Self::Legacy(_) => TxType::Legacy,
             Self::Eip2930(_) => TxType::Eip2930,
             Self::Eip1559(_) => TxType::Eip1559,
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(_) => TxType::Eip4844,
         }
     }
@@ -112,6 +138,47 @@ impl TypedTransaction {
             _ => None,
         }
     }
+
+    /// Returns the effective gas price for the given `base_fee`.
+    pub const fn effective_gas_price(&self, base_fee: Option<u128>) -> u128 {
+        match self {
+            Self::Legacy(tx) => tx.effective_gas_price(base_fee),
+            Self::Eip2930(tx) => tx.effective_gas_price(base_fee),
+            Self::Eip1559(tx) => tx.effective_gas_price(base_fee),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.tx().effective_gas_price(base_fee),
+        }
+    }
+
+    /// Returns the priority fee per gas paid to the block producer for the given `base_fee`, or
+    /// `None` if the transaction does not pay the base fee.
+    pub const fn effective_tip_per_gas(&self, base_fee: u128) -> Option<u128> {
+        match self {
+            Self::Legacy(tx) => tx.effective_tip_per_gas(base_fee),
+            Self::Eip2930(tx) => tx.effective_tip_per_gas(base_fee),
+            Self::Eip1559(tx) => tx.effective_tip_per_gas(base_fee),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.tx().effective_tip_per_gas(base_fee),
+        }
+    }
+
+    /// Returns the maximum amount of wei the transaction can cost, including its value and blob
+    /// fees.
+    pub fn max_cost(&self) -> U256 {
+        match self {
+            Self::Legacy(tx) => tx.max_cost(),
+            Self::Eip2930(tx) => tx.max_cost(),
+            Self::Eip1559(tx) => tx.max_cost(),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.tx().max_cost(),
+        }
+    }
+}
+
+impl Typed2718 for TypedTransaction {
+    fn ty(&self) -> u8 {
+        self.tx_type().into()
+    }
 }
 
 impl Transaction for TypedTransaction {
@@ -120,6 +187,7 @@ impl Transaction for TypedTransaction {
             Self::Legacy(tx) => tx.chain_id(),
             Self::Eip2930(tx) => tx.chain_id(),
             Self::Eip1559(tx) => tx.chain_id(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.chain_id(),
         }
     }
@@ -129,6 +197,7 @@ impl Transaction for TypedTransaction {
             Self::Legacy(tx) => tx.nonce(),
             Self::Eip2930(tx) => tx.nonce(),
             Self::Eip1559(tx) => tx.nonce(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.nonce(),
         }
     }
@@ -138,6 +207,7 @@ impl Transaction for TypedTransaction {
             Self::Legacy(tx) => tx.gas_limit(),
             Self::Eip2930(tx) => tx.gas_limit(),
             Self::Eip1559(tx) => tx.gas_limit(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.gas_limit(),
         }
     }
@@ -147,6 +217,7 @@ impl Transaction for TypedTransaction {
             Self::Legacy(tx) => tx.gas_price(),
             Self::Eip2930(tx) => tx.gas_price(),
             Self::Eip1559(tx) => tx.gas_price(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.gas_price(),
         }
     }
@@ -156,6 +227,7 @@ impl Transaction for TypedTransaction {
             Self::Legacy(tx) => tx.to(),
             Self::Eip2930(tx) => tx.to(),
             Self::Eip1559(tx) => tx.to(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.to(),
         }
     }
@@ -165,6 +237,7 @@ impl Transaction for TypedTransaction {
             Self::Legacy(tx) => tx.value(),
             Self::Eip2930(tx) => tx.value(),
             Self::Eip1559(tx) => tx.value(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.value(),
         }
     }
@@ -174,6 +247,7 @@ impl Transaction for TypedTransaction {
             Self::Legacy(tx) => tx.input(),
             Self::Eip2930(tx) => tx.input(),
             Self::Eip1559(tx) => tx.input(),
+            #[cfg(feature = "eip4844")]
             Self::Eip4844(tx) => tx.input(),
         }
     }
@@ -192,3 +266,16 @@ impl<T: From<TxEnvelope>> From<TxEnvelope> for alloy_serde::WithOtherFields<T> {
         Self::new(value.into())
     }
 }
+
+impl InMemorySize for TypedTransaction {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::Legacy(tx) => tx.heap_size(),
+            Self::Eip2930(tx) => tx.heap_size(),
+            Self::Eip1559(tx) => tx.heap_size(),
+            #[cfg(feature = "eip4844")]
+            Self::Eip4844(tx) => tx.heap_size(),
+        }
+    }
+}
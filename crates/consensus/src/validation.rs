@@ -0,0 +1,176 @@
+//! Block validation helpers.
+
+use crate::{Block, Header};
+use alloy_eips::eip4844::{BlobParams, DATA_GAS_PER_BLOB};
+use core::fmt;
+
+/// An error of [`validate_blob_gas`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlobGasError {
+    /// The header of the block has no `blob_gas_used` or `excess_blob_gas`.
+    MissingBlobGasFields,
+    /// The `blob_gas_used` of the header is not the blob gas of the transactions.
+    BlobGasUsedMismatch {
+        /// The blob gas of the transactions.
+        expected: u128,
+        /// The `blob_gas_used` of the header.
+        got: u128,
+    },
+    /// The block has more blobs than allowed.
+    TooManyBlobs {
+        /// The number of blobs of the block.
+        count: u64,
+        /// The maximum number of blobs per block.
+        max: u64,
+    },
+    /// The `excess_blob_gas` of the header does not follow from the parent.
+    ExcessBlobGasMismatch {
+        /// The excess blob gas computed from the parent.
+        expected: u128,
+        /// The `excess_blob_gas` of the header.
+        got: u128,
+    },
+}
+
+impl fmt::Display for BlobGasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingBlobGasFields => {
+                f.write_str("header is missing `blob_gas_used` or `excess_blob_gas`")
+            }
+            Self::BlobGasUsedMismatch { expected, got } => {
+                write!(f, "blob gas used mismatch: expected {expected}, got {got}")
+            }
+            Self::TooManyBlobs { count, max } => {
+                write!(f, "too many blobs: {count} exceeds the maximum of {max}")
+            }
+            Self::ExcessBlobGasMismatch { expected, got } => {
+                write!(f, "excess blob gas mismatch: expected {expected}, got {got}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlobGasError {}
+
+/// Validates the blob gas accounting of a block, introduced by [EIP-4844], against its parent
+/// header:
+/// - `blob_gas_used` is the blob gas of the transactions of the block,
+/// - the block has at most [`BlobParams::max_blob_count`] blobs,
+/// - `excess_blob_gas` follows from the parent, against [`BlobParams::target_blob_count`].
+///
+/// `params` are the blob parameters of the fork of the block, e.g. [`BlobParams::PRAGUE`]. A
+/// parent from before Cancun counts as having no blob gas used nor excess.
+///
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+pub fn validate_blob_gas(
+    parent: &Header,
+    block: &Block,
+    params: BlobParams,
+) -> Result<(), BlobGasError> {
+    let header = &block.header;
+    let (Some(blob_gas_used), Some(excess_blob_gas)) =
+        (header.blob_gas_used, header.excess_blob_gas)
+    else {
+        return Err(BlobGasError::MissingBlobGasFields);
+    };
+
+    let count = block
+        .body
+        .transactions
+        .iter()
+        .filter_map(|tx| tx.as_eip4844())
+        .map(|tx| tx.tx().tx().blob_versioned_hashes.len() as u64)
+        .sum::<u64>();
+    let expected = count as u128 * DATA_GAS_PER_BLOB as u128;
+    if blob_gas_used != expected {
+        return Err(BlobGasError::BlobGasUsedMismatch { expected, got: blob_gas_used });
+    }
+    if count > params.max_blob_count {
+        return Err(BlobGasError::TooManyBlobs { count, max: params.max_blob_count });
+    }
+
+    let expected = params.next_block_excess_blob_gas(
+        parent.excess_blob_gas.unwrap_or_default(),
+        parent.blob_gas_used.unwrap_or_default(),
+    );
+    if excess_blob_gas != expected {
+        return Err(BlobGasError::ExcessBlobGasMismatch { expected, got: excess_blob_gas });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockBody, Signed, TxEip4844, TxEnvelope};
+    use alloy_primitives::{Signature, B256};
+
+    fn blob_tx(blobs: usize) -> TxEnvelope {
+        let tx = TxEip4844 { blob_versioned_hashes: vec![B256::ZERO; blobs], ..Default::default() };
+        let signature = Signature::test_signature();
+        TxEnvelope::Eip4844(Signed::new_unchecked(tx.into(), signature, B256::ZERO))
+    }
+
+    fn block(blobs: &[usize], blob_gas_used: u128, excess_blob_gas: u128) -> Block {
+        let header = Header {
+            blob_gas_used: Some(blob_gas_used),
+            excess_blob_gas: Some(excess_blob_gas),
+            ..Default::default()
+        };
+        let transactions = blobs.iter().map(|&blobs| blob_tx(blobs)).collect();
+        Block::new(header, BlockBody { transactions, ..Default::default() })
+    }
+
+    const BLOB: u128 = DATA_GAS_PER_BLOB as u128;
+
+    #[test]
+    fn validates_blob_gas() {
+        let parent = Header {
+            blob_gas_used: Some(5 * BLOB),
+            excess_blob_gas: Some(BLOB),
+            ..Default::default()
+        };
+        let cancun = BlobParams::CANCUN;
+        validate_blob_gas(&parent, &block(&[2, 3], 5 * BLOB, 3 * BLOB), cancun).unwrap();
+
+        assert_eq!(
+            validate_blob_gas(&parent, &block(&[2, 3], 4 * BLOB, 3 * BLOB), cancun),
+            Err(BlobGasError::BlobGasUsedMismatch { expected: 5 * BLOB, got: 4 * BLOB })
+        );
+        assert_eq!(
+            validate_blob_gas(&parent, &block(&[2, 3], 5 * BLOB, 2 * BLOB), cancun),
+            Err(BlobGasError::ExcessBlobGasMismatch { expected: 3 * BLOB, got: 2 * BLOB })
+        );
+        assert_eq!(
+            validate_blob_gas(&parent, &block(&[4, 3], 7 * BLOB, 3 * BLOB), cancun),
+            Err(BlobGasError::TooManyBlobs { count: 7, max: 6 })
+        );
+        assert_eq!(
+            validate_blob_gas(&parent, &Block::default(), cancun),
+            Err(BlobGasError::MissingBlobGasFields)
+        );
+    }
+
+    #[test]
+    fn validates_prague_blob_gas() {
+        let parent = Header {
+            blob_gas_used: Some(5 * BLOB),
+            excess_blob_gas: Some(BLOB),
+            ..Default::default()
+        };
+        // Prague raises the maximum to 9 blobs and the target to 6.
+        let prague = BlobParams::PRAGUE;
+        validate_blob_gas(&parent, &block(&[4, 3], 7 * BLOB, 0), prague).unwrap();
+        assert_eq!(
+            validate_blob_gas(&parent, &block(&[9, 1], 10 * BLOB, 0), prague),
+            Err(BlobGasError::TooManyBlobs { count: 10, max: 9 })
+        );
+    }
+
+    #[test]
+    fn pre_cancun_parent() {
+        validate_blob_gas(&Header::default(), &block(&[1], BLOB, 0), BlobParams::CANCUN).unwrap();
+    }
+}
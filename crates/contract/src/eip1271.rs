@@ -0,0 +1,305 @@
+//! Signature verification for both EOAs and smart contract wallets.
+//!
+//! Recovering the signer of an ECDSA signature only works for externally owned accounts. Smart
+//! contract wallets instead implement [EIP-1271] `isValidSignature`, and wallets that are not yet
+//! deployed wrap their signatures following [EIP-6492], so that they can be verified by deploying
+//! the wallet in a simulated call. [`verify_signature`] handles all three cases.
+//! [`verify_siwe_message`] applies it to Sign-In with Ethereum messages.
+//!
+//! [EIP-1271]: https://eips.ethereum.org/EIPS/eip-1271
+//! [EIP-6492]: https://eips.ethereum.org/EIPS/eip-6492
+
+use crate::{Error, Result, SolCallBuilder};
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{b256, Address, Bytes, FixedBytes, Signature, B256};
+use alloy_provider::Provider;
+use alloy_signer::siwe::SiweMessage;
+use alloy_sol_types::{sol, SolCall, SolValue};
+use alloy_transport::{RpcError, Transport};
+
+/// The value returned by `isValidSignature` for a valid signature.
+pub const EIP1271_MAGIC_VALUE: FixedBytes<4> = FixedBytes([0x16, 0x26, 0xba, 0x7e]);
+
+/// The suffix of EIP-6492 wrapped signatures.
+pub const EIP6492_MAGIC_SUFFIX: B256 =
+    b256!("6492649264926492649264926492649264926492649264926492649264926492");
+
+sol! {
+    /// The EIP-1271 contract signature validation interface.
+    #[derive(Debug, PartialEq, Eq)]
+    #[allow(missing_docs)]
+    interface IERC1271 {
+        function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4 magicValue);
+    }
+}
+
+/// An EIP-6492 signature of a counterfactual (not yet deployed) smart contract wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Eip6492Signature {
+    /// The factory deploying the wallet.
+    pub factory: Address,
+    /// The calldata of the factory call deploying the wallet.
+    pub factory_calldata: Bytes,
+    /// The wallet signature, to be verified with EIP-1271 once deployed.
+    pub signature: Bytes,
+}
+
+impl Eip6492Signature {
+    /// Decodes a wrapped signature, returning `None` if it does not have the EIP-6492 suffix or
+    /// is malformed.
+    pub fn decode(signature: &[u8]) -> Option<Self> {
+        let wrapped = signature.strip_suffix(EIP6492_MAGIC_SUFFIX.as_slice())?;
+        let (factory, factory_calldata, signature) =
+            <(Address, Bytes, Bytes)>::abi_decode_params(wrapped, true).ok()?;
+        Some(Self { factory, factory_calldata, signature })
+    }
+
+    /// Returns the wrapped signature: `abi.encode(factory, factoryCalldata, signature) ++
+    /// magicSuffix`.
+    pub fn encode(&self) -> Bytes {
+        let mut encoded = (self.factory, self.factory_calldata.clone(), self.signature.clone())
+            .abi_encode_params();
+        encoded.extend_from_slice(EIP6492_MAGIC_SUFFIX.as_slice());
+        encoded.into()
+    }
+
+    /// Returns the init code of a contract that deploys the wallet through the factory, calls
+    /// `isValidSignature(hash, signature)` on it, and returns the 32 bytes result, or nothing if
+    /// the call failed.
+    ///
+    /// The result of an `eth_call` creating this contract is the `isValidSignature` return value.
+    pub fn validator_code(&self, wallet: Address, hash: B256) -> Option<Bytes> {
+        let is_valid =
+            IERC1271::isValidSignatureCall { hash, signature: self.signature.clone() }.abi_encode();
+        validator_code(self.factory, &self.factory_calldata, wallet, &is_valid)
+    }
+}
+
+/// Verifies that `signature` is a valid signature of `hash` by `signer`.
+///
+/// The signature is checked, in order:
+/// - as an EIP-6492 signature, if it has the magic suffix, by simulating the wallet deployment and
+///   calling `isValidSignature` in a single `eth_call`;
+/// - as an ECDSA signature, by recovering the signer;
+/// - as an EIP-1271 signature, by calling `isValidSignature`, if `signer` has code.
+///
+/// Returns `false` for invalid signatures, and an error only if the RPC calls fail.
+pub async fn verify_signature<T, P, N>(
+    provider: &P,
+    signer: Address,
+    hash: B256,
+    signature: &[u8],
+) -> Result<bool>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    if let Some(wrapped) = Eip6492Signature::decode(signature) {
+        if provider.get_code_at(signer).await?.is_empty() {
+            let Some(code) = wrapped.validator_code(signer, hash) else { return Ok(false) };
+            let tx = N::TransactionRequest::default().with_deploy_code(code);
+            let output = provider.call(&tx).await?;
+            return Ok(is_magic_value(&output));
+        }
+        return is_valid_signature(provider, signer, hash, wrapped.signature).await;
+    }
+
+    if let Ok(ecdsa) = Signature::try_from(signature) {
+        if ecdsa.recover_address_from_prehash(&hash).is_ok_and(|recovered| recovered == signer) {
+            return Ok(true);
+        }
+    }
+
+    if provider.get_code_at(signer).await?.is_empty() {
+        return Ok(false);
+    }
+    is_valid_signature(provider, signer, hash, Bytes::copy_from_slice(signature)).await
+}
+
+/// Verifies that `signature` is a signature of the [EIP-4361] Sign-In with Ethereum `message` by
+/// its address, which may be a smart contract wallet, see [`verify_signature`].
+///
+/// Only the signature is checked: the domain, nonce and validity period of the message must be
+/// checked with [`SiweMessage::validate`] first.
+///
+/// [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+pub async fn verify_siwe_message<T, P, N>(
+    provider: &P,
+    message: &SiweMessage,
+    signature: &[u8],
+) -> Result<bool>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    verify_signature(provider, message.address, message.hash(), signature).await
+}
+
+/// Calls EIP-1271 `isValidSignature` on `wallet`, treating reverts as invalid signatures.
+async fn is_valid_signature<T, P, N>(
+    provider: &P,
+    wallet: Address,
+    hash: B256,
+    signature: Bytes,
+) -> Result<bool>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let call = IERC1271::isValidSignatureCall { hash, signature };
+    let call = SolCallBuilder::new_sol(provider, &wallet, &call);
+    match call.call_raw().await {
+        Ok(output) => Ok(is_magic_value(&output)),
+        Err(Error::TransportError(RpcError::ErrorResp(_))) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns `true` if `output` is the ABI-encoded [`EIP1271_MAGIC_VALUE`].
+fn is_magic_value(output: &[u8]) -> bool {
+    output.len() == 32 && output[..4] == EIP1271_MAGIC_VALUE[..]
+}
+
+/// Builds the init code of the EIP-6492 validator, see [`Eip6492Signature::validator_code`].
+///
+/// The code copies `factory_calldata ++ is_valid` from its own tail into memory, calls the
+/// factory (ignoring failures, as the wallet may already be deployed), then static calls the
+/// wallet and returns the result.
+fn validator_code(
+    factory: Address,
+    factory_calldata: &[u8],
+    wallet: Address,
+    is_valid: &[u8],
+) -> Option<Bytes> {
+    const CODE_LEN: u16 = 95;
+    const FAIL_DEST: u16 = 89;
+
+    let lf = u16::try_from(factory_calldata.len()).ok()?;
+    let lv = u16::try_from(is_valid.len()).ok()?;
+    let data_len = lf.checked_add(lv)?;
+    let ret = data_len.checked_add(31)? / 32 * 32;
+
+    let mut code = Vec::with_capacity(CODE_LEN as usize + data_len as usize);
+    let push2 = |code: &mut Vec<u8>, value: u16| {
+        code.push(0x61);
+        code.extend_from_slice(&value.to_be_bytes());
+    };
+
+    // CODECOPY(0, CODE_LEN, data_len)
+    push2(&mut code, data_len);
+    push2(&mut code, CODE_LEN);
+    code.extend_from_slice(&[0x60, 0x00, 0x39]);
+
+    // POP(CALL(gas, factory, 0, 0, lf, 0, 0))
+    code.extend_from_slice(&[0x60, 0x00, 0x60, 0x00]);
+    push2(&mut code, lf);
+    code.extend_from_slice(&[0x60, 0x00, 0x60, 0x00, 0x73]);
+    code.extend_from_slice(factory.as_slice());
+    code.extend_from_slice(&[0x5a, 0xf1, 0x50]);
+
+    // STATICCALL(gas, wallet, lf, lv, ret, 32)
+    code.extend_from_slice(&[0x60, 0x20]);
+    push2(&mut code, ret);
+    push2(&mut code, lv);
+    push2(&mut code, lf);
+    code.push(0x73);
+    code.extend_from_slice(wallet.as_slice());
+    code.extend_from_slice(&[0x5a, 0xfa]);
+
+    // JUMPI(FAIL_DEST, ISZERO(success))
+    code.push(0x15);
+    push2(&mut code, FAIL_DEST);
+    code.push(0x57);
+
+    // RETURN(ret, 32)
+    code.extend_from_slice(&[0x60, 0x20]);
+    push2(&mut code, ret);
+    code.push(0xf3);
+
+    // FAIL_DEST: RETURN(0, 0)
+    debug_assert_eq!(code.len(), FAIL_DEST as usize);
+    code.extend_from_slice(&[0x5b, 0x60, 0x00, 0x60, 0x00, 0xf3]);
+    debug_assert_eq!(code.len(), CODE_LEN as usize);
+
+    code.extend_from_slice(factory_calldata);
+    code.extend_from_slice(is_valid);
+    Some(code.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{bytes, keccak256};
+    use alloy_provider::ProviderBuilder;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[test]
+    fn eip6492_roundtrip() {
+        let wrapped = Eip6492Signature {
+            factory: Address::with_last_byte(1),
+            factory_calldata: bytes!("deadbeef"),
+            signature: bytes!("c0ffee"),
+        };
+        let encoded = wrapped.encode();
+        assert!(encoded.ends_with(EIP6492_MAGIC_SUFFIX.as_slice()));
+        assert_eq!(Eip6492Signature::decode(&encoded), Some(wrapped));
+        assert_eq!(Eip6492Signature::decode(&encoded[..encoded.len() - 1]), None);
+    }
+
+    #[test]
+    fn validator_layout() {
+        let wrapped = Eip6492Signature {
+            factory: Address::with_last_byte(1),
+            factory_calldata: bytes!("deadbeef"),
+            signature: Bytes::from(vec![0xab; 65]),
+        };
+        let wallet = Address::with_last_byte(2);
+        let code = wrapped.validator_code(wallet, B256::ZERO).unwrap();
+        let is_valid = IERC1271::isValidSignatureCall {
+            hash: B256::ZERO,
+            signature: wrapped.signature.clone(),
+        }
+        .abi_encode();
+
+        assert_eq!(code.len(), 95 + 4 + is_valid.len());
+        assert_eq!(code[89], 0x5b);
+        assert_eq!(&code[95..99], &wrapped.factory_calldata[..]);
+        assert_eq!(&code[99..], &is_valid[..]);
+        assert_eq!(&code[21..41], wrapped.factory.as_slice());
+        assert_eq!(&code[56..76], wallet.as_slice());
+    }
+
+    #[tokio::test]
+    async fn verifies_ecdsa_without_rpc() {
+        // Never contacted: ECDSA signatures are verified locally.
+        let provider = ProviderBuilder::new().on_http("http://localhost:1".parse().unwrap());
+        let signer = PrivateKeySigner::random();
+        let hash = keccak256("hello");
+        let signature = signer.sign_hash_sync(&hash).unwrap();
+
+        assert!(verify_signature(&provider, signer.address(), hash, &signature.as_bytes())
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn verifies_siwe_message() {
+        let provider = ProviderBuilder::new().on_http("http://localhost:1".parse().unwrap());
+        let signer = PrivateKeySigner::random();
+        let message = SiweMessage::new(
+            "example.com",
+            signer.address(),
+            "https://example.com/login",
+            1,
+            "abcdefgh",
+            alloy_signer::siwe::Timestamp::now(),
+        );
+        let signature = message.sign_sync(&signer).unwrap();
+
+        assert!(verify_siwe_message(&provider, &message, &signature.as_bytes()).await.unwrap());
+    }
+}
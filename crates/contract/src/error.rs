@@ -1,5 +1,5 @@
 use alloy_dyn_abi::Error as AbiError;
-use alloy_primitives::Selector;
+use alloy_primitives::{Selector, B256};
 use alloy_transport::TransportError;
 use thiserror::Error;
 
@@ -27,6 +27,17 @@ pub enum Error {
     /// An error occurred interacting with a contract over RPC.
     #[error(transparent)]
     TransportError(#[from] TransportError),
+    /// An error occurred signing a message or typed data.
+    #[error(transparent)]
+    SignerError(#[from] alloy_signer::Error),
+    /// The locally computed EIP-712 domain separator does not match the one of the contract.
+    #[error("EIP-712 domain separator mismatch: expected {expected}, computed {computed}")]
+    DomainSeparatorMismatch {
+        /// The domain separator returned by the contract.
+        expected: B256,
+        /// The locally computed domain separator.
+        computed: B256,
+    },
 }
 
 impl From<alloy_sol_types::Error> for Error {
@@ -1,9 +1,10 @@
-use std::{future::IntoFuture, marker::PhantomData};
+use std::{future::IntoFuture, marker::PhantomData, sync::Arc};
 
 use alloy_dyn_abi::{DynSolValue, FunctionExt};
 use alloy_json_abi::Function;
 use alloy_network::Network;
 use alloy_primitives::Bytes;
+use alloy_provider::Executor;
 use alloy_rpc_types_eth::{state::StateOverride, BlockId};
 use alloy_sol_types::SolCall;
 use alloy_transport::Transport;
@@ -88,6 +89,12 @@ where
         self.inner = self.inner.block(block);
         self
     }
+
+    /// Set the [`Executor`] executing this call, instead of the node through `eth_call`.
+    pub fn executor(mut self, executor: Arc<dyn Executor<N>>) -> Self {
+        self.inner = self.inner.executor(executor);
+        self
+    }
 }
 
 impl<'req, 'state, T, N> From<alloy_provider::EthCall<'req, 'state, T, N, Bytes>>
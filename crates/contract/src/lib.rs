@@ -30,6 +30,27 @@ pub use instance::*;
 mod call;
 pub use call::*;
 
+mod trace;
+pub use trace::*;
+
+pub mod eip1271;
+
+pub mod permit;
+
+pub mod safe;
+
+pub mod storage;
+
+pub mod tokens;
+
+#[cfg(feature = "signatures")]
+mod signatures;
+#[cfg(feature = "signatures")]
+pub use signatures::{
+    SignatureClient, SignatureDatabase, SignatureError, SignatureResult, FOUR_BYTE_API_URL,
+    OPENCHAIN_API_URL,
+};
+
 // Not public API.
 // NOTE: please avoid changing the API of this module due to its use in the `sol!` macro.
 #[doc(hidden)]
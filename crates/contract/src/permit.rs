@@ -0,0 +1,525 @@
+//! [EIP-2612] and Uniswap [Permit2] signing helpers.
+//!
+//! Both flows boil down to signing an [EIP-712] struct and submitting the signature on-chain, but
+//! are easy to get wrong: the EIP-2612 domain `version` differs between tokens, and Permit2 has
+//! no `version` at all and uses different nonce schemes for its allowance and signature transfer
+//! variants.
+//!
+//! [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+//! [Permit2]: https://github.com/Uniswap/permit2
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+
+use crate::{tokens::IERC20, Error, Result, SolCallBuilder};
+use alloy_network::Network;
+use alloy_primitives::{address, Address, Bytes, Signature, U256};
+use alloy_provider::Provider;
+use alloy_signer::Signer;
+use alloy_sol_types::{sol, Eip712Domain, SolCall, SolStruct};
+use alloy_transport::Transport;
+use std::borrow::Cow;
+
+/// The canonical Permit2 contract address, identical on all chains.
+pub const PERMIT2_ADDRESS: Address = address!("000000000022D473030F116dDEE9F6B43aC78BA3");
+
+sol! {
+    /// The EIP-2612 `Permit` struct.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Permit {
+        /// The token owner.
+        address owner;
+        /// The approved spender.
+        address spender;
+        /// The approved amount.
+        uint256 value;
+        /// The owner's current permit nonce.
+        uint256 nonce;
+        /// The timestamp after which the permit is invalid.
+        uint256 deadline;
+    }
+
+    /// The EIP-2612 permit extension, with the EIP-5267 domain getter.
+    #[derive(Debug, PartialEq, Eq)]
+    #[allow(missing_docs)]
+    interface IERC20Permit {
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+        function nonces(address owner) external view returns (uint256 nonce);
+        function DOMAIN_SEPARATOR() external view returns (bytes32 separator);
+        function version() external view returns (string version);
+        function eip712Domain() external view returns (bytes1 fields, string name, string version, uint256 chainId, address verifyingContract, bytes32 salt, uint256[] extensions);
+    }
+}
+
+sol! {
+    /// The token and amount of a Permit2 signature transfer.
+    #[derive(Debug, PartialEq, Eq)]
+    struct TokenPermissions {
+        /// The token to transfer.
+        address token;
+        /// The maximum amount that can be transferred.
+        uint256 amount;
+    }
+
+    /// A Permit2 signature transfer of a single token.
+    ///
+    /// `spender` is not part of the `permitTransferFrom` arguments, but must match the caller.
+    #[derive(Debug, PartialEq, Eq)]
+    struct PermitTransferFrom {
+        /// The permitted token and amount.
+        TokenPermissions permitted;
+        /// The address allowed to submit the permit.
+        address spender;
+        /// The unordered nonce of the permit.
+        uint256 nonce;
+        /// The timestamp after which the permit is invalid.
+        uint256 deadline;
+    }
+
+    /// A Permit2 signature transfer of multiple tokens.
+    #[derive(Debug, PartialEq, Eq)]
+    struct PermitBatchTransferFrom {
+        /// The permitted tokens and amounts.
+        TokenPermissions[] permitted;
+        /// The address allowed to submit the permit.
+        address spender;
+        /// The unordered nonce of the permit.
+        uint256 nonce;
+        /// The timestamp after which the permit is invalid.
+        uint256 deadline;
+    }
+
+    /// The recipient and amount of a Permit2 signature transfer.
+    #[derive(Debug, PartialEq, Eq)]
+    struct SignatureTransferDetails {
+        /// The recipient of the tokens.
+        address to;
+        /// The amount to transfer, at most the permitted amount.
+        uint256 requestedAmount;
+    }
+
+    /// The token, amount, expiration and nonce of a Permit2 allowance.
+    #[derive(Debug, PartialEq, Eq)]
+    struct PermitDetails {
+        /// The token to approve.
+        address token;
+        /// The approved amount.
+        uint160 amount;
+        /// The timestamp at which the allowance expires.
+        uint48 expiration;
+        /// The owner's current allowance nonce for this token and spender.
+        uint48 nonce;
+    }
+
+    /// A Permit2 allowance for a single token.
+    #[derive(Debug, PartialEq, Eq)]
+    struct PermitSingle {
+        /// The allowance details.
+        PermitDetails details;
+        /// The approved spender.
+        address spender;
+        /// The timestamp after which the signature is invalid.
+        uint256 sigDeadline;
+    }
+
+    /// A Permit2 allowance for multiple tokens.
+    #[derive(Debug, PartialEq, Eq)]
+    struct PermitBatch {
+        /// The allowance details, one per token.
+        PermitDetails[] details;
+        /// The approved spender.
+        address spender;
+        /// The timestamp after which the signature is invalid.
+        uint256 sigDeadline;
+    }
+
+    /// The Permit2 contract interface.
+    #[derive(Debug, PartialEq, Eq)]
+    #[allow(missing_docs)]
+    interface IPermit2 {
+        /// The [`PermitTransferFrom`](super::PermitTransferFrom) passed to `permitTransferFrom`,
+        /// without the signed `spender`, which is the caller.
+        struct PermitTransferFrom {
+            TokenPermissions permitted;
+            uint256 nonce;
+            uint256 deadline;
+        }
+
+        /// The [`PermitBatchTransferFrom`](super::PermitBatchTransferFrom) passed to
+        /// `permitTransferFrom`, without the signed `spender`, which is the caller.
+        struct PermitBatchTransferFrom {
+            TokenPermissions[] permitted;
+            uint256 nonce;
+            uint256 deadline;
+        }
+
+        function permit(address owner, PermitSingle permitSingle, bytes signature) external;
+        function permit(address owner, PermitBatch permitBatch, bytes signature) external;
+        function permitTransferFrom(PermitTransferFrom permit, SignatureTransferDetails transferDetails, address owner, bytes signature) external;
+        function permitTransferFrom(PermitBatchTransferFrom permit, SignatureTransferDetails[] transferDetails, address owner, bytes signature) external;
+        function allowance(address user, address token, address spender) external view returns (uint160 amount, uint48 expiration, uint48 nonce);
+        function nonceBitmap(address owner, uint256 wordPos) external view returns (uint256 bitmap);
+        function DOMAIN_SEPARATOR() external view returns (bytes32 separator);
+    }
+}
+
+/// A signed permit, ready to be submitted on-chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedPermit<P> {
+    /// The address of the signer.
+    pub owner: Address,
+    /// The signed permit.
+    pub permit: P,
+    /// The signature over the EIP-712 hash of the permit.
+    pub signature: Signature,
+}
+
+impl<P> SignedPermit<P> {
+    /// Returns the 65 bytes `r || s || v` encoding of the signature, with `v` being 27 or 28, as
+    /// expected by Permit2.
+    pub fn signature_bytes(&self) -> Bytes {
+        let mut bytes = self.signature.as_bytes();
+        bytes[64] = 27 + self.signature.v().y_parity_byte();
+        Bytes::copy_from_slice(&bytes)
+    }
+}
+
+impl SignedPermit<Permit> {
+    /// Returns the calldata of the EIP-2612 `permit` call, to be sent to the token contract.
+    pub fn calldata(&self) -> Bytes {
+        IERC20Permit::permitCall {
+            owner: self.permit.owner,
+            spender: self.permit.spender,
+            value: self.permit.value,
+            deadline: self.permit.deadline,
+            v: 27 + self.signature.v().y_parity_byte(),
+            r: self.signature.r().into(),
+            s: self.signature.s().into(),
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+impl SignedPermit<PermitSingle> {
+    /// Returns the calldata of the Permit2 `permit` call, to be sent to [`PERMIT2_ADDRESS`].
+    pub fn calldata(&self) -> Bytes {
+        IPermit2::permit_0Call {
+            owner: self.owner,
+            permitSingle: self.permit.clone(),
+            signature: self.signature_bytes(),
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+impl SignedPermit<PermitBatch> {
+    /// Returns the calldata of the Permit2 batch `permit` call, to be sent to
+    /// [`PERMIT2_ADDRESS`].
+    pub fn calldata(&self) -> Bytes {
+        IPermit2::permit_1Call {
+            owner: self.owner,
+            permitBatch: self.permit.clone(),
+            signature: self.signature_bytes(),
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+impl SignedPermit<PermitTransferFrom> {
+    /// Returns the calldata of the Permit2 `permitTransferFrom` call, to be sent to
+    /// [`PERMIT2_ADDRESS`] by the permit's spender.
+    pub fn calldata(&self, transfer: SignatureTransferDetails) -> Bytes {
+        IPermit2::permitTransferFrom_0Call {
+            permit: IPermit2::PermitTransferFrom {
+                permitted: self.permit.permitted.clone(),
+                nonce: self.permit.nonce,
+                deadline: self.permit.deadline,
+            },
+            transferDetails: transfer,
+            owner: self.owner,
+            signature: self.signature_bytes(),
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+impl SignedPermit<PermitBatchTransferFrom> {
+    /// Returns the calldata of the Permit2 batch `permitTransferFrom` call, to be sent to
+    /// [`PERMIT2_ADDRESS`] by the permit's spender.
+    ///
+    /// `transfers` must have the same length as the permitted tokens.
+    pub fn calldata(&self, transfers: Vec<SignatureTransferDetails>) -> Bytes {
+        IPermit2::permitTransferFrom_1Call {
+            permit: IPermit2::PermitBatchTransferFrom {
+                permitted: self.permit.permitted.clone(),
+                nonce: self.permit.nonce,
+                deadline: self.permit.deadline,
+            },
+            transferDetails: transfers,
+            owner: self.owner,
+            signature: self.signature_bytes(),
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+/// Returns the EIP-712 domain of an EIP-2612 token.
+pub fn erc2612_domain(
+    name: impl Into<Cow<'static, str>>,
+    version: impl Into<Cow<'static, str>>,
+    chain_id: u64,
+    token: Address,
+) -> Eip712Domain {
+    Eip712Domain::new(
+        Some(name.into()),
+        Some(version.into()),
+        Some(U256::from(chain_id)),
+        Some(token),
+        None,
+    )
+}
+
+/// Returns the EIP-712 domain of the Permit2 contract at [`PERMIT2_ADDRESS`].
+///
+/// Note that the Permit2 domain has no `version`.
+pub fn permit2_domain(chain_id: u64) -> Eip712Domain {
+    Eip712Domain::new(
+        Some(Cow::Borrowed("Permit2")),
+        None,
+        Some(U256::from(chain_id)),
+        Some(PERMIT2_ADDRESS),
+        None,
+    )
+}
+
+/// Fetches the EIP-712 domain of an EIP-2612 token.
+///
+/// The domain is read from the EIP-5267 `eip712Domain()` getter if the token implements it.
+/// Otherwise it is built from `name()`, `version()` (defaulting to `"1"`) and the provider's chain
+/// id, and checked against the token's `DOMAIN_SEPARATOR()`.
+pub async fn fetch_erc2612_domain<T, P, N>(provider: &P, token: Address) -> Result<Eip712Domain>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let call = SolCallBuilder::new_sol(provider, &token, &IERC20Permit::eip712DomainCall {});
+    if let Ok(domain) = call.call().await {
+        let fields = domain.fields[0];
+        return Ok(Eip712Domain::new(
+            (fields & 0x01 != 0).then_some(Cow::Owned(domain.name)),
+            (fields & 0x02 != 0).then_some(Cow::Owned(domain.version)),
+            (fields & 0x04 != 0).then_some(domain.chainId),
+            (fields & 0x08 != 0).then_some(domain.verifyingContract),
+            (fields & 0x10 != 0).then_some(domain.salt),
+        ));
+    }
+
+    let name = SolCallBuilder::new_sol(provider, &token, &IERC20::nameCall {});
+    let version = SolCallBuilder::new_sol(provider, &token, &IERC20Permit::versionCall {});
+    let separator =
+        SolCallBuilder::new_sol(provider, &token, &IERC20Permit::DOMAIN_SEPARATORCall {});
+
+    let name = name.call().await?.name;
+    let version = version.call().await.map(|v| v.version).unwrap_or_else(|_| "1".to_string());
+    let chain_id = provider.get_chain_id().await?;
+    let domain = erc2612_domain(name, version, chain_id, token);
+
+    let expected = separator.call().await?.separator;
+    let computed = domain.separator();
+    if expected != computed {
+        return Err(Error::DomainSeparatorMismatch { expected, computed });
+    }
+    Ok(domain)
+}
+
+/// Signs an EIP-2612 permit with an already known domain.
+pub async fn sign_erc2612_permit<S>(
+    signer: &S,
+    domain: &Eip712Domain,
+    permit: Permit,
+) -> Result<SignedPermit<Permit>>
+where
+    S: Signer + Send + Sync,
+{
+    let signature = signer.sign_typed_data(&permit, domain).await?;
+    Ok(SignedPermit { owner: permit.owner, permit, signature })
+}
+
+/// Builds and signs an EIP-2612 permit allowing `spender` to spend `value` of the signer's
+/// `token` until `deadline`.
+///
+/// The token domain and the signer's current nonce are fetched from the chain.
+pub async fn erc2612_permit<T, P, N, S>(
+    provider: &P,
+    signer: &S,
+    token: Address,
+    spender: Address,
+    value: U256,
+    deadline: U256,
+) -> Result<SignedPermit<Permit>>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+    S: Signer + Send + Sync,
+{
+    let owner = signer.address();
+    let domain = fetch_erc2612_domain(provider, token).await?;
+    let nonce = SolCallBuilder::new_sol(provider, &token, &IERC20Permit::noncesCall { owner })
+        .call()
+        .await?
+        .nonce;
+    let permit = Permit { owner, spender, value, nonce, deadline };
+    sign_erc2612_permit(signer, &domain, permit).await
+}
+
+/// Signs a Permit2 struct ([`PermitSingle`], [`PermitBatch`], [`PermitTransferFrom`] or
+/// [`PermitBatchTransferFrom`]) for the given chain.
+///
+/// Allowance permits ([`PermitSingle`], [`PermitBatch`]) use the sequential nonce returned by
+/// [`permit2_allowance`], while signature transfers use unordered nonces, tracked in
+/// `nonceBitmap`.
+pub async fn sign_permit2<S, P>(signer: &S, chain_id: u64, permit: P) -> Result<SignedPermit<P>>
+where
+    S: Signer + Send + Sync,
+    P: SolStruct + Send + Sync,
+{
+    let signature = signer.sign_typed_data(&permit, &permit2_domain(chain_id)).await?;
+    Ok(SignedPermit { owner: signer.address(), permit, signature })
+}
+
+/// Returns the Permit2 allowance of `spender` over `owner`'s `token`, including the nonce to use
+/// in the next allowance permit.
+pub async fn permit2_allowance<T, P, N>(
+    provider: &P,
+    owner: Address,
+    token: Address,
+    spender: Address,
+) -> Result<IPermit2::allowanceReturn>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let call = IPermit2::allowanceCall { user: owner, token, spender };
+    SolCallBuilder::new_sol(provider, &PERMIT2_ADDRESS, &call).call().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{hex, keccak256};
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[test]
+    fn type_hashes() {
+        assert_eq!(
+            Permit::eip712_encode_type(),
+            "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+        );
+        assert_eq!(
+            PermitTransferFrom::eip712_encode_type(),
+            "PermitTransferFrom(TokenPermissions permitted,address spender,uint256 nonce,uint256 deadline)TokenPermissions(address token,uint256 amount)"
+        );
+        assert_eq!(
+            PermitSingle::eip712_encode_type(),
+            "PermitSingle(PermitDetails details,address spender,uint256 sigDeadline)PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)"
+        );
+        assert_eq!(
+            PermitBatch::eip712_type_hash(&PermitBatch {
+                details: vec![],
+                spender: Address::ZERO,
+                sigDeadline: U256::ZERO
+            }),
+            keccak256("PermitBatch(PermitDetails[] details,address spender,uint256 sigDeadline)PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)")
+        );
+    }
+
+    #[test]
+    fn permit2_domain_has_no_version() {
+        let domain = permit2_domain(1);
+        assert!(domain.version.is_none());
+        assert_eq!(
+            domain.encode_type(),
+            "EIP712Domain(string name,uint256 chainId,address verifyingContract)"
+        );
+    }
+
+    #[tokio::test]
+    async fn sign_and_encode() {
+        let signer = PrivateKeySigner::random();
+        let token = Address::with_last_byte(1);
+        let spender = Address::with_last_byte(2);
+
+        let domain = erc2612_domain("Token", "1", 1, token);
+        let permit = Permit {
+            owner: signer.address(),
+            spender,
+            value: U256::from(100),
+            nonce: U256::ZERO,
+            deadline: U256::MAX,
+        };
+        let signed = sign_erc2612_permit(&signer, &domain, permit).await.unwrap();
+        let hash = signed.permit.eip712_signing_hash(&domain);
+        assert_eq!(signed.signature.recover_address_from_prehash(&hash).unwrap(), signer.address());
+
+        let call = IERC20Permit::permitCall::abi_decode(&signed.calldata(), true).unwrap();
+        assert_eq!(call.owner, signer.address());
+        assert!(call.v == 27 || call.v == 28);
+
+        let permit = PermitSingle {
+            details: PermitDetails {
+                token,
+                amount: U256::from(100),
+                expiration: u64::MAX >> 16,
+                nonce: 0,
+            },
+            spender,
+            sigDeadline: U256::MAX,
+        };
+        let signed = sign_permit2(&signer, 1, permit).await.unwrap();
+        let call = IPermit2::permit_0Call::abi_decode(&signed.calldata(), true).unwrap();
+        assert_eq!(call.owner, signer.address());
+        assert_eq!(call.signature, signed.signature_bytes());
+        assert_eq!(
+            Signature::try_from(&call.signature[..]).unwrap().as_bytes(),
+            signed.signature.as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn permit_transfer_from_calldata() {
+        // The selectors of the Permit2 deployment, whose permits have no `spender`.
+        assert_eq!(IPermit2::permitTransferFrom_0Call::SELECTOR, hex!("30f28b7a"));
+        assert_eq!(IPermit2::permitTransferFrom_1Call::SELECTOR, hex!("edd9444b"));
+
+        let signer = PrivateKeySigner::random();
+        let permitted =
+            TokenPermissions { token: Address::with_last_byte(1), amount: U256::from(5) };
+        let permit = PermitTransferFrom {
+            permitted: permitted.clone(),
+            spender: Address::with_last_byte(2),
+            nonce: U256::from(7),
+            deadline: U256::MAX,
+        };
+        let signed = sign_permit2(&signer, 1, permit).await.unwrap();
+        let transfer = SignatureTransferDetails {
+            to: Address::with_last_byte(3),
+            requestedAmount: U256::from(5),
+        };
+        let calldata = signed.calldata(transfer.clone());
+        assert_eq!(calldata[..4], hex!("30f28b7a"));
+
+        let call = IPermit2::permitTransferFrom_0Call::abi_decode(&calldata, true).unwrap();
+        assert_eq!(
+            call.permit,
+            IPermit2::PermitTransferFrom { permitted, nonce: U256::from(7), deadline: U256::MAX }
+        );
+        assert_eq!(call.transferDetails, transfer);
+        assert_eq!(call.owner, signer.address());
+    }
+}
@@ -0,0 +1,231 @@
+use super::{SafeSignature, SafeTransaction};
+use alloy_primitives::{hex, Address, Bytes, B256};
+use serde::{Deserialize, Serialize};
+
+/// The Safe Transaction Service URL for Ethereum mainnet.
+pub const SAFE_MAINNET_SERVICE_URL: &str = "https://safe-transaction-mainnet.safe.global";
+
+/// Safe Transaction Service result type.
+pub type SafeApiResult<T, E = SafeApiError> = core::result::Result<T, E>;
+
+/// Error when interacting with the Safe Transaction Service.
+#[derive(Debug, thiserror::Error)]
+pub enum SafeApiError {
+    /// An error occurred sending the request.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The response could not be deserialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The service returned an error.
+    #[error("safe transaction service error ({status}): {message}")]
+    Api {
+        /// The HTTP status code.
+        status: u16,
+        /// The response body.
+        message: String,
+    },
+    /// The transaction has no signature from the given owner.
+    #[error("missing signature of {0}")]
+    MissingSignature(Address),
+}
+
+/// The state of a Safe, as returned by the Safe Transaction Service.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SafeInfo {
+    /// The Safe address.
+    pub address: Address,
+    /// The current on-chain nonce.
+    pub nonce: u64,
+    /// The number of required signatures.
+    pub threshold: u64,
+    /// The Safe owners.
+    pub owners: Vec<Address>,
+    /// The Safe version, e.g. `1.3.0`.
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// An owner confirmation of a Safe transaction, as returned by the Safe Transaction Service.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeConfirmation {
+    /// The confirming owner.
+    pub owner: Address,
+    /// The encoded signature.
+    pub signature: Bytes,
+    /// The signature type, e.g. `EOA` or `ETH_SIGN`.
+    #[serde(default)]
+    pub signature_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MultisigTransaction {
+    confirmations: Vec<SafeConfirmation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProposeRequest<'a> {
+    to: Address,
+    value: String,
+    data: Option<String>,
+    operation: u8,
+    safe_tx_gas: String,
+    base_gas: String,
+    gas_price: String,
+    gas_token: Address,
+    refund_receiver: Address,
+    nonce: String,
+    contract_transaction_hash: B256,
+    sender: Address,
+    signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    origin: Option<&'a str>,
+}
+
+/// A client for the [Safe Transaction Service](https://docs.safe.global/core-api/transaction-service-overview),
+/// used to propose transactions and collect owner confirmations off-chain.
+#[derive(Clone, Debug)]
+pub struct SafeTransactionService {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl SafeTransactionService {
+    /// Creates a new client for the service at `url`, e.g. [`SAFE_MAINNET_SERVICE_URL`].
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_client(reqwest::Client::new(), url)
+    }
+
+    /// Creates a new client using the given HTTP client.
+    pub fn with_client(client: reqwest::Client, url: impl Into<String>) -> Self {
+        Self { client, url: url.into().trim_end_matches('/').to_string() }
+    }
+
+    /// Returns the service URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the state of a Safe.
+    pub async fn safe_info(&self, safe: Address) -> SafeApiResult<SafeInfo> {
+        let response = self.client.get(format!("{}/api/v1/safes/{safe}/", self.url)).send().await?;
+        Ok(serde_json::from_str(&Self::text(response).await?)?)
+    }
+
+    /// Proposes a transaction to the other owners, with the signature of `sender`.
+    ///
+    /// `sender` must be an owner of the Safe and must have signed `transaction`.
+    pub async fn propose(
+        &self,
+        transaction: &SafeTransaction,
+        sender: Address,
+        origin: Option<&str>,
+    ) -> SafeApiResult<()> {
+        let signature = transaction
+            .signatures
+            .get(&sender)
+            .ok_or(SafeApiError::MissingSignature(sender))?
+            .encode(sender);
+        let tx = &transaction.tx;
+        let request = ProposeRequest {
+            to: tx.to,
+            value: tx.value.to_string(),
+            data: (!tx.data.is_empty()).then(|| hex::encode_prefixed(&tx.data)),
+            operation: tx.operation,
+            safe_tx_gas: tx.safeTxGas.to_string(),
+            base_gas: tx.baseGas.to_string(),
+            gas_price: tx.gasPrice.to_string(),
+            gas_token: tx.gasToken,
+            refund_receiver: tx.refundReceiver,
+            nonce: tx.nonce.to_string(),
+            contract_transaction_hash: transaction.hash(),
+            sender,
+            signature: hex::encode_prefixed(signature),
+            origin,
+        };
+        let url = format!("{}/api/v1/safes/{}/multisig-transactions/", self.url, transaction.safe);
+        Self::text(self.client.post(url).json(&request).send().await?).await?;
+        Ok(())
+    }
+
+    /// Adds the signature of `owner` to a proposed transaction.
+    pub async fn confirm(
+        &self,
+        safe_tx_hash: B256,
+        owner: Address,
+        signature: &SafeSignature,
+    ) -> SafeApiResult<()> {
+        let url =
+            format!("{}/api/v1/multisig-transactions/{safe_tx_hash}/confirmations/", self.url);
+        let body =
+            serde_json::json!({ "signature": hex::encode_prefixed(signature.encode(owner)) });
+        Self::text(self.client.post(url).json(&body).send().await?).await?;
+        Ok(())
+    }
+
+    /// Returns the confirmations of a proposed transaction.
+    pub async fn confirmations(&self, safe_tx_hash: B256) -> SafeApiResult<Vec<SafeConfirmation>> {
+        let url = format!("{}/api/v1/multisig-transactions/{safe_tx_hash}/", self.url);
+        let response = self.client.get(url).send().await?;
+        let tx: MultisigTransaction = serde_json::from_str(&Self::text(response).await?)?;
+        Ok(tx.confirmations)
+    }
+
+    async fn text(response: reqwest::Response) -> SafeApiResult<String> {
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            return Err(SafeApiError::Api { status: status.as_u16(), message: text });
+        }
+        Ok(text)
+    }
+}
+
+impl SafeTransaction {
+    /// Adds the signatures of confirmations fetched from the Safe Transaction Service.
+    ///
+    /// Confirmations whose signature cannot be decoded, or does not belong to the confirming
+    /// owner, are skipped. Returns the number of added signatures.
+    pub fn add_confirmations(&mut self, confirmations: &[SafeConfirmation]) -> usize {
+        let hash = self.hash();
+        let mut added = 0;
+        for confirmation in confirmations {
+            match SafeSignature::decode(&confirmation.signature, hash) {
+                Some((owner, signature)) if owner == confirmation.owner => {
+                    self.signatures.insert(owner, signature);
+                    added += 1;
+                }
+                _ => {}
+            }
+        }
+        added
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safe::SafeTx;
+    use alloy_primitives::U256;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[tokio::test]
+    async fn add_confirmations() {
+        let tx = SafeTx::call(Address::with_last_byte(1), U256::ZERO, Bytes::new(), U256::ZERO);
+        let mut transaction = SafeTransaction::new(Address::with_last_byte(0x5a), Some(1), tx);
+        let signer = PrivateKeySigner::random();
+        let signature = transaction.clone().sign(&signer).await.unwrap();
+
+        let json = format!(
+            r#"{{"confirmations":[{{"owner":"{}","submissionDate":"2024-01-01T00:00:00Z","signature":"{}","signatureType":"EOA"}},{{"owner":"{}","signature":"0x00","signatureType":"EOA"}}]}}"#,
+            signer.address(),
+            hex::encode_prefixed(signature.encode(signer.address())),
+            Address::with_last_byte(2),
+        );
+        let tx: MultisigTransaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(transaction.add_confirmations(&tx.confirmations), 1);
+        assert_eq!(transaction.signatures.get(&signer.address()), Some(&signature));
+    }
+}
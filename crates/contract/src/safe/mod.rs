@@ -0,0 +1,319 @@
+//! [Safe](https://safe.global) multisig transaction building and signing.
+//!
+//! A Safe transaction is an [EIP-712] `SafeTx` struct, signed by a threshold of the Safe's owners
+//! and executed through `execTransaction` with the owners' signatures concatenated in ascending
+//! owner order. [`SafeTransaction`] takes care of hashing, collecting and encoding the signatures,
+//! and of building the final calldata.
+//!
+//! With the `safe-api` feature, transactions can also be proposed to and confirmed through the
+//! Safe Transaction Service.
+//!
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+
+use crate::{Result, SolCallBuilder};
+use alloy_network::Network;
+use alloy_primitives::{Address, Bytes, Signature, B256, U256};
+use alloy_provider::Provider;
+use alloy_signer::Signer;
+use alloy_sol_types::{sol, Eip712Domain, SolCall, SolStruct};
+use alloy_transport::Transport;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "safe-api")]
+mod api;
+#[cfg(feature = "safe-api")]
+pub use api::{
+    SafeApiError, SafeApiResult, SafeConfirmation, SafeInfo, SafeTransactionService,
+    SAFE_MAINNET_SERVICE_URL,
+};
+
+sol! {
+    /// The EIP-712 struct signed by Safe owners.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct SafeTx {
+        /// The destination of the transaction.
+        address to;
+        /// The ether value of the transaction.
+        uint256 value;
+        /// The calldata of the transaction.
+        bytes data;
+        /// The operation type, see [`SafeOperation`].
+        uint8 operation;
+        /// The gas to forward to the transaction.
+        uint256 safeTxGas;
+        /// The gas costs independent of the transaction execution, used for refunds.
+        uint256 baseGas;
+        /// The gas price used for the refund.
+        uint256 gasPrice;
+        /// The token used for the refund, or the zero address for ether.
+        address gasToken;
+        /// The refund receiver, or the zero address for `tx.origin`.
+        address refundReceiver;
+        /// The Safe nonce.
+        uint256 nonce;
+    }
+
+    /// The Safe contract interface.
+    #[derive(Debug, PartialEq, Eq)]
+    #[allow(missing_docs)]
+    interface ISafe {
+        function execTransaction(address to, uint256 value, bytes data, uint8 operation, uint256 safeTxGas, uint256 baseGas, uint256 gasPrice, address gasToken, address refundReceiver, bytes signatures) external payable returns (bool success);
+        function approveHash(bytes32 hashToApprove) external;
+        function nonce() external view returns (uint256 nonce);
+        function getThreshold() external view returns (uint256 threshold);
+        function getOwners() external view returns (address[] owners);
+        function VERSION() external view returns (string version);
+    }
+}
+
+/// The operation type of a [`SafeTx`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SafeOperation {
+    /// A regular call.
+    #[default]
+    Call = 0,
+    /// A delegate call, e.g. to `MultiSendCallOnly`.
+    DelegateCall = 1,
+}
+
+impl From<SafeOperation> for u8 {
+    fn from(operation: SafeOperation) -> Self {
+        operation as Self
+    }
+}
+
+impl SafeTx {
+    /// Creates a new Safe transaction calling `to` with `value` and `data`, without gas refund.
+    pub fn call(to: Address, value: U256, data: Bytes, nonce: U256) -> Self {
+        Self { to, value, data, nonce, ..Default::default() }
+    }
+
+    /// Sets the operation type.
+    pub fn with_operation(mut self, operation: SafeOperation) -> Self {
+        self.operation = operation.into();
+        self
+    }
+}
+
+/// Returns the EIP-712 domain of a Safe.
+///
+/// Safes from version 1.3.0 include the chain id in their domain; pass `None` for older Safes.
+pub fn safe_domain(chain_id: Option<u64>, safe: Address) -> Eip712Domain {
+    Eip712Domain::new(None, None, chain_id.map(U256::from), Some(safe), None)
+}
+
+/// An owner signature over a `safeTxHash`.
+///
+/// Contract (EIP-1271) signatures are not supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SafeSignature {
+    /// An ECDSA signature over the EIP-712 hash.
+    Eip712(Signature),
+    /// An ECDSA signature over the EIP-191 prefixed hash, as produced by `eth_sign`.
+    EthSign(Signature),
+    /// A hash approved on-chain with `approveHash`, or implicitly by the owner executing the
+    /// transaction.
+    ApprovedHash,
+}
+
+impl SafeSignature {
+    /// Returns the 65 bytes encoding of the signature of `owner`.
+    pub fn encode(&self, owner: Address) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        match self {
+            Self::Eip712(signature) | Self::EthSign(signature) => {
+                bytes[..32].copy_from_slice(&signature.r().to_be_bytes::<32>());
+                bytes[32..64].copy_from_slice(&signature.s().to_be_bytes::<32>());
+                let v = 27 + signature.v().y_parity_byte();
+                bytes[64] = if matches!(self, Self::EthSign(_)) { v + 4 } else { v };
+            }
+            Self::ApprovedHash => {
+                bytes[12..32].copy_from_slice(owner.as_slice());
+                bytes[64] = 1;
+            }
+        }
+        bytes
+    }
+
+    /// Decodes a 65 bytes signature, returning it with the owner it belongs to.
+    ///
+    /// The owner of ECDSA signatures is recovered from `safe_tx_hash`. Returns `None` for
+    /// malformed and contract signatures.
+    pub fn decode(bytes: &[u8], safe_tx_hash: B256) -> Option<(Address, Self)> {
+        if bytes.len() != 65 {
+            return None;
+        }
+        let r = U256::from_be_slice(&bytes[..32]);
+        let s = U256::from_be_slice(&bytes[32..64]);
+        match bytes[64] {
+            0 => None,
+            1 => Some((Address::from_slice(&bytes[12..32]), Self::ApprovedHash)),
+            v @ 31..=32 => {
+                let signature = Signature::from_rs_and_parity(r, s, v - 31 != 0).ok()?;
+                let owner = signature.recover_address_from_msg(safe_tx_hash).ok()?;
+                Some((owner, Self::EthSign(signature)))
+            }
+            v @ 27..=28 => {
+                let signature = Signature::from_rs_and_parity(r, s, v - 27 != 0).ok()?;
+                let owner = signature.recover_address_from_prehash(&safe_tx_hash).ok()?;
+                Some((owner, Self::Eip712(signature)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A Safe transaction and the owner signatures collected for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SafeTransaction {
+    /// The Safe address.
+    pub safe: Address,
+    /// The chain id, or `None` for Safes older than 1.3.0.
+    pub chain_id: Option<u64>,
+    /// The transaction.
+    pub tx: SafeTx,
+    /// The collected signatures, by owner.
+    pub signatures: BTreeMap<Address, SafeSignature>,
+}
+
+impl SafeTransaction {
+    /// Creates a new Safe transaction with no signatures.
+    pub const fn new(safe: Address, chain_id: Option<u64>, tx: SafeTx) -> Self {
+        Self { safe, chain_id, tx, signatures: BTreeMap::new() }
+    }
+
+    /// Returns the EIP-712 domain of the Safe.
+    pub fn domain(&self) -> Eip712Domain {
+        safe_domain(self.chain_id, self.safe)
+    }
+
+    /// Returns the `safeTxHash`, i.e. the EIP-712 signing hash of the transaction.
+    pub fn hash(&self) -> B256 {
+        self.tx.eip712_signing_hash(&self.domain())
+    }
+
+    /// Signs the transaction hash with `signer` and adds the signature.
+    pub async fn sign<S>(&mut self, signer: &S) -> Result<SafeSignature>
+    where
+        S: Signer + Send + Sync,
+    {
+        let signature = SafeSignature::Eip712(signer.sign_hash(&self.hash()).await?);
+        self.signatures.insert(signer.address(), signature);
+        Ok(signature)
+    }
+
+    /// Adds the signature of `owner`, replacing any previous one.
+    pub fn add_signature(&mut self, owner: Address, signature: SafeSignature) {
+        self.signatures.insert(owner, signature);
+    }
+
+    /// Adds an [`SafeSignature::ApprovedHash`] signature for `owner`.
+    ///
+    /// This is required when the executing owner does not otherwise sign the transaction.
+    pub fn add_approval(&mut self, owner: Address) {
+        self.signatures.insert(owner, SafeSignature::ApprovedHash);
+    }
+
+    /// Returns the concatenated signatures, sorted by owner address as required by the Safe.
+    pub fn encoded_signatures(&self) -> Bytes {
+        self.signatures.iter().flat_map(|(owner, signature)| signature.encode(*owner)).collect()
+    }
+
+    /// Returns the `execTransaction` calldata, to be sent to the Safe.
+    pub fn exec_calldata(&self) -> Bytes {
+        let tx = &self.tx;
+        ISafe::execTransactionCall {
+            to: tx.to,
+            value: tx.value,
+            data: tx.data.clone(),
+            operation: tx.operation,
+            safeTxGas: tx.safeTxGas,
+            baseGas: tx.baseGas,
+            gasPrice: tx.gasPrice,
+            gasToken: tx.gasToken,
+            refundReceiver: tx.refundReceiver,
+            signatures: self.encoded_signatures(),
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+/// Returns the current nonce of a Safe, to be used for its next transaction.
+pub async fn safe_nonce<T, P, N>(provider: &P, safe: Address) -> Result<U256>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    Ok(SolCallBuilder::new_sol(provider, &safe, &ISafe::nonceCall {}).call().await?.nonce)
+}
+
+/// Returns the owners and signature threshold of a Safe.
+pub async fn safe_owners<T, P, N>(provider: &P, safe: Address) -> Result<(Vec<Address>, U256)>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let owners = SolCallBuilder::new_sol(provider, &safe, &ISafe::getOwnersCall {});
+    let threshold = SolCallBuilder::new_sol(provider, &safe, &ISafe::getThresholdCall {});
+    Ok((owners.call().await?.owners, threshold.call().await?.threshold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[test]
+    fn type_hash() {
+        assert_eq!(
+            SafeTx::eip712_encode_type(),
+            "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)"
+        );
+        assert_eq!(
+            safe_domain(Some(1), Address::ZERO).encode_type(),
+            "EIP712Domain(uint256 chainId,address verifyingContract)"
+        );
+        assert_eq!(
+            safe_domain(None, Address::ZERO).encode_type(),
+            "EIP712Domain(address verifyingContract)"
+        );
+    }
+
+    #[tokio::test]
+    async fn sign_and_encode() {
+        let safe = Address::with_last_byte(0x5a);
+        let tx = SafeTx::call(Address::with_last_byte(1), U256::from(1), Bytes::new(), U256::ZERO);
+        let mut transaction = SafeTransaction::new(safe, Some(1), tx);
+
+        let a = PrivateKeySigner::random();
+        let b = PrivateKeySigner::random();
+        transaction.sign(&a).await.unwrap();
+        transaction.sign(&b).await.unwrap();
+        let executor = Address::with_last_byte(2);
+        transaction.add_approval(executor);
+
+        let encoded = transaction.encoded_signatures();
+        assert_eq!(encoded.len(), 3 * 65);
+
+        let hash = transaction.hash();
+        let decoded: Vec<_> =
+            encoded.chunks(65).map(|chunk| SafeSignature::decode(chunk, hash).unwrap().0).collect();
+        let mut owners = vec![a.address(), b.address(), executor];
+        owners.sort();
+        assert_eq!(decoded, owners);
+
+        let eth_sign = SafeSignature::EthSign(a.sign_message(hash.as_slice()).await.unwrap());
+        let (owner, decoded) = SafeSignature::decode(&eth_sign.encode(a.address()), hash).unwrap();
+        assert_eq!(owner, a.address());
+        assert_eq!(decoded.encode(owner), eth_sign.encode(owner));
+
+        let call =
+            ISafe::execTransactionCall::abi_decode(&transaction.exec_calldata(), true).unwrap();
+        assert_eq!(call.signatures, encoded);
+        assert_eq!(call.value, U256::from(1));
+    }
+}
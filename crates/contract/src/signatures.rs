@@ -0,0 +1,457 @@
+use crate::TraceDecoder;
+use alloy_json_abi::{Error as SolError, Event, Function};
+use alloy_primitives::{hex, Selector, B256};
+use alloy_rpc_types_trace::geth::CallFrame;
+use lru::LruCache;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeSet, HashMap},
+    num::NonZeroUsize,
+    sync::Mutex,
+};
+
+/// The default OpenChain signature database lookup endpoint.
+pub const OPENCHAIN_API_URL: &str = "https://api.openchain.xyz/signature-database/v1/lookup";
+
+/// The default 4byte.directory API endpoint.
+pub const FOUR_BYTE_API_URL: &str = "https://www.4byte.directory/api/v1";
+
+/// The default number of cached lookups.
+// `Option::unwrap` is not const on our MSRV.
+const DEFAULT_CACHE_SIZE: NonZeroUsize = match NonZeroUsize::new(4096) {
+    Some(size) => size,
+    None => panic!("cache size must be non-zero"),
+};
+
+/// The selector of the builtin `Error(string)` revert.
+const REVERT_SELECTOR: Selector = Selector::new([0x08, 0xc3, 0x79, 0xa0]);
+
+/// The selector of the builtin `Panic(uint256)` revert.
+const PANIC_SELECTOR: Selector = Selector::new([0x4e, 0x48, 0x7b, 0x71]);
+
+/// Signature database lookup result type.
+pub type SignatureResult<T, E = SignatureError> = core::result::Result<T, E>;
+
+/// Error when looking up signatures in a signature database.
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    /// An error occurred sending the request.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The response could not be deserialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The signature database returned an error.
+    #[error("signature database error: {0}")]
+    Api(String),
+}
+
+/// A public signature database that can be queried for unknown selectors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignatureDatabase {
+    /// The [OpenChain](https://openchain.xyz/signatures) signature database, with the given
+    /// lookup URL.
+    OpenChain(String),
+    /// The [4byte.directory](https://www.4byte.directory) signature database, with the given API
+    /// URL.
+    FourByte(String),
+}
+
+impl Default for SignatureDatabase {
+    fn default() -> Self {
+        Self::OpenChain(OPENCHAIN_API_URL.to_string())
+    }
+}
+
+/// The kind of signature being looked up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum SignatureKey {
+    Function(Selector),
+    Event(B256),
+}
+
+/// A client for public signature databases, used to label selectors and events for which no ABI
+/// is available.
+///
+/// Lookups, including misses, are cached in a local LRU cache.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_contract::SignatureClient;
+/// use alloy_primitives::hex;
+///
+/// let client = SignatureClient::new();
+/// let transfer = client.identify_function(hex!("a9059cbb").into()).await?;
+/// assert_eq!(transfer.unwrap().signature(), "transfer(address,uint256)");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SignatureClient {
+    client: reqwest::Client,
+    database: SignatureDatabase,
+    cache: Mutex<LruCache<SignatureKey, Vec<String>>>,
+}
+
+impl Default for SignatureClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignatureClient {
+    /// Creates a new client using the OpenChain signature database.
+    pub fn new() -> Self {
+        Self::with_database(SignatureDatabase::default())
+    }
+
+    /// Creates a new client using the given signature database.
+    pub fn with_database(database: SignatureDatabase) -> Self {
+        Self::with_client(reqwest::Client::new(), database)
+    }
+
+    /// Creates a new client using the given HTTP client and signature database.
+    pub fn with_client(client: reqwest::Client, database: SignatureDatabase) -> Self {
+        Self { client, database, cache: Mutex::new(LruCache::new(DEFAULT_CACHE_SIZE)) }
+    }
+
+    /// Sets the number of lookups to keep in the cache.
+    pub fn with_cache_size(self, size: NonZeroUsize) -> Self {
+        Self { cache: Mutex::new(LruCache::new(size)), ..self }
+    }
+
+    /// Returns the signature database used by this client.
+    pub const fn database(&self) -> &SignatureDatabase {
+        &self.database
+    }
+
+    /// Looks up the text signatures of the given function or error selectors.
+    ///
+    /// The result contains the candidate signatures for each selector, in the same order. An empty
+    /// list means that the selector is not known to the database.
+    pub async fn lookup_functions(
+        &self,
+        selectors: &[Selector],
+    ) -> SignatureResult<Vec<Vec<String>>> {
+        self.lookup(selectors.iter().copied().map(SignatureKey::Function).collect()).await
+    }
+
+    /// Looks up the text signatures of the given event topics.
+    ///
+    /// The result contains the candidate signatures for each topic, in the same order. An empty
+    /// list means that the event is not known to the database.
+    pub async fn lookup_events(&self, topics: &[B256]) -> SignatureResult<Vec<Vec<String>>> {
+        self.lookup(topics.iter().copied().map(SignatureKey::Event).collect()).await
+    }
+
+    /// Identifies the function with the given selector.
+    pub async fn identify_function(&self, selector: Selector) -> SignatureResult<Option<Function>> {
+        let signatures = self.lookup_functions(&[selector]).await?;
+        Ok(signatures.iter().flatten().find_map(|sig| Function::parse(sig).ok()))
+    }
+
+    /// Identifies the custom error with the given selector.
+    pub async fn identify_error(&self, selector: Selector) -> SignatureResult<Option<SolError>> {
+        let signatures = self.lookup_functions(&[selector]).await?;
+        Ok(signatures.iter().flatten().find_map(|sig| SolError::parse(sig).ok()))
+    }
+
+    /// Identifies the event with the given topic.
+    pub async fn identify_event(&self, topic: B256) -> SignatureResult<Option<Event>> {
+        let signatures = self.lookup_events(&[topic]).await?;
+        Ok(signatures.iter().flatten().find_map(|sig| Event::parse(sig).ok()))
+    }
+
+    async fn lookup(&self, keys: Vec<SignatureKey>) -> SignatureResult<Vec<Vec<String>>> {
+        let mut results = HashMap::with_capacity(keys.len());
+        {
+            let mut cache = self.cache.lock().unwrap();
+            for key in &keys {
+                if let Some(signatures) = cache.get(key) {
+                    results.insert(*key, signatures.clone());
+                }
+            }
+        }
+
+        let missing = keys
+            .iter()
+            .filter(|key| !results.contains_key(*key))
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let mut found = match &self.database {
+                SignatureDatabase::OpenChain(url) => self.lookup_openchain(url, &missing).await?,
+                SignatureDatabase::FourByte(url) => self.lookup_four_byte(url, &missing).await?,
+            };
+            let mut cache = self.cache.lock().unwrap();
+            for key in missing {
+                let signatures = found.remove(&key).unwrap_or_default();
+                cache.put(key, signatures.clone());
+                results.insert(key, signatures);
+            }
+        }
+
+        Ok(keys.iter().map(|key| results[key].clone()).collect())
+    }
+
+    async fn lookup_openchain(
+        &self,
+        url: &str,
+        keys: &[SignatureKey],
+    ) -> SignatureResult<HashMap<SignatureKey, Vec<String>>> {
+        let join = |f: fn(&SignatureKey) -> Option<String>| {
+            keys.iter().filter_map(f).collect::<Vec<_>>().join(",")
+        };
+        let functions = join(|key| match key {
+            SignatureKey::Function(selector) => Some(selector.to_string()),
+            SignatureKey::Event(_) => None,
+        });
+        let events = join(|key| match key {
+            SignatureKey::Event(topic) => Some(topic.to_string()),
+            SignatureKey::Function(_) => None,
+        });
+
+        let body = self
+            .client
+            .get(url)
+            .query(&[("function", functions), ("event", events), ("filter", "true".into())])
+            .send()
+            .await?
+            .text()
+            .await?;
+        parse_openchain_response(&body)
+    }
+
+    async fn lookup_four_byte(
+        &self,
+        url: &str,
+        keys: &[SignatureKey],
+    ) -> SignatureResult<HashMap<SignatureKey, Vec<String>>> {
+        let mut found = HashMap::with_capacity(keys.len());
+        for key in keys {
+            let (endpoint, hex_signature) = match key {
+                SignatureKey::Function(selector) => ("signatures", selector.to_string()),
+                SignatureKey::Event(topic) => ("event-signatures", topic.to_string()),
+            };
+            let body = self
+                .client
+                .get(format!("{}/{endpoint}/", url.trim_end_matches('/')))
+                .query(&[("hex_signature", hex_signature)])
+                .send()
+                .await?
+                .text()
+                .await?;
+            found.insert(*key, parse_four_byte_response(&body)?);
+        }
+        Ok(found)
+    }
+}
+
+impl TraceDecoder {
+    /// Looks up the given function selectors and event topics in the signature database, and adds
+    /// all identified functions, errors and events to the decoder.
+    ///
+    /// Selectors and topics that are already known to the decoder are skipped.
+    pub async fn identify(
+        &mut self,
+        client: &SignatureClient,
+        selectors: impl IntoIterator<Item = Selector>,
+        topics: impl IntoIterator<Item = B256>,
+    ) -> SignatureResult<()> {
+        let selectors = selectors
+            .into_iter()
+            .filter(|selector| {
+                self.functions(selector).is_empty()
+                    && self.errors(selector).is_empty()
+                    && *selector != REVERT_SELECTOR
+                    && *selector != PANIC_SELECTOR
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let topics = topics
+            .into_iter()
+            .filter(|topic| self.events(topic).is_empty())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        if !selectors.is_empty() {
+            for signature in client.lookup_functions(&selectors).await?.iter().flatten() {
+                // The same namespace is shared by functions and custom errors.
+                if let Ok(function) = Function::parse(signature) {
+                    self.add_function(function);
+                }
+                if let Ok(error) = SolError::parse(signature) {
+                    self.add_error(error);
+                }
+            }
+        }
+        if !topics.is_empty() {
+            for signature in client.lookup_events(&topics).await?.iter().flatten() {
+                // Signature databases don't record which parameters are indexed, this is inferred
+                // from the number of topics when decoding.
+                if let Ok(event) = Event::parse(signature) {
+                    self.add_event(event);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Identifies all unknown call, revert and event selectors in the given `callTracer` frame
+    /// using the signature database.
+    ///
+    /// See [`identify`](Self::identify) for more details.
+    pub async fn identify_call_frame(
+        &mut self,
+        client: &SignatureClient,
+        frame: &CallFrame,
+    ) -> SignatureResult<()> {
+        let (mut selectors, mut topics) = (Vec::new(), Vec::new());
+        collect_selectors(frame, &mut selectors, &mut topics);
+        self.identify(client, selectors, topics).await
+    }
+}
+
+fn collect_selectors(frame: &CallFrame, selectors: &mut Vec<Selector>, topics: &mut Vec<B256>) {
+    if !frame.typ.starts_with("CREATE") && frame.input.len() >= 4 {
+        selectors.push(Selector::from_slice(&frame.input[..4]));
+    }
+    if let Some(output) = frame.output.as_ref().filter(|_| frame.error.is_some()) {
+        if output.len() >= 4 {
+            selectors.push(Selector::from_slice(&output[..4]));
+        }
+    }
+    topics.extend(frame.logs.iter().filter_map(|log| log.topics.as_ref()?.first().copied()));
+    for call in &frame.calls {
+        collect_selectors(call, selectors, topics);
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenChainResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    result: OpenChainResult,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenChainResult {
+    #[serde(default)]
+    function: HashMap<String, Option<Vec<OpenChainSignature>>>,
+    #[serde(default)]
+    event: HashMap<String, Option<Vec<OpenChainSignature>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenChainSignature {
+    name: String,
+    #[serde(default)]
+    filtered: bool,
+}
+
+fn parse_openchain_response(body: &str) -> SignatureResult<HashMap<SignatureKey, Vec<String>>> {
+    let response: OpenChainResponse = serde_json::from_str(body)?;
+    if !response.ok {
+        return Err(SignatureError::Api(response.error.unwrap_or_default()));
+    }
+
+    let names = |signatures: Option<Vec<OpenChainSignature>>| {
+        signatures
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|sig| !sig.filtered)
+            .map(|sig| sig.name)
+            .collect::<Vec<_>>()
+    };
+    let mut found = HashMap::new();
+    for (selector, signatures) in response.result.function {
+        if let Ok(selector) = hex::decode_to_array(&selector) {
+            found.insert(SignatureKey::Function(selector.into()), names(signatures));
+        }
+    }
+    for (topic, signatures) in response.result.event {
+        if let Ok(topic) = hex::decode_to_array(&topic) {
+            found.insert(SignatureKey::Event(topic.into()), names(signatures));
+        }
+    }
+    Ok(found)
+}
+
+#[derive(Debug, Deserialize)]
+struct FourByteResponse {
+    results: Vec<FourByteSignature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FourByteSignature {
+    id: u64,
+    text_signature: String,
+}
+
+fn parse_four_byte_response(body: &str) -> SignatureResult<Vec<String>> {
+    let mut response: FourByteResponse = serde_json::from_str(body)?;
+    // Prefer the oldest submission, which is the most likely to be the canonical one.
+    response.results.sort_by_key(|sig| sig.id);
+    Ok(response.results.into_iter().map(|sig| sig.text_signature).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, hex};
+
+    #[test]
+    fn parses_openchain_response() {
+        let body = r#"{"ok":true,"result":{"event":{"0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef":[{"name":"Transfer(address,address,uint256)","filtered":false}]},"function":{"0xa9059cbb":[{"name":"transfer(address,uint256)","filtered":false},{"name":"many_msg_babbage(bytes1)","filtered":true}],"0xdeadbeef":null}}}"#;
+        let found = parse_openchain_response(body).unwrap();
+        assert_eq!(
+            found[&SignatureKey::Function(hex!("a9059cbb").into())],
+            vec!["transfer(address,uint256)".to_string()]
+        );
+        assert!(found[&SignatureKey::Function(hex!("deadbeef").into())].is_empty());
+        assert_eq!(
+            found[&SignatureKey::Event(b256!(
+                "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            ))],
+            vec!["Transfer(address,address,uint256)".to_string()]
+        );
+
+        let err = parse_openchain_response(r#"{"ok":false,"error":"invalid hash"}"#).unwrap_err();
+        assert!(matches!(err, SignatureError::Api(msg) if msg == "invalid hash"));
+    }
+
+    #[test]
+    fn parses_four_byte_response() {
+        let body = r#"{"count":2,"next":null,"previous":null,"results":[{"id":313067,"created_at":"2021-10-20T01:42:19.958712Z","text_signature":"join_tg_invmru_haha_fd06787(address,bool)","hex_signature":"0xa9059cbb","bytes_signature":"©\u0005\u009c»"},{"id":145,"created_at":"2016-07-09T03:58:28.234977Z","text_signature":"transfer(address,uint256)","hex_signature":"0xa9059cbb","bytes_signature":"©\u0005\u009c»"}]}"#;
+        let found = parse_four_byte_response(body).unwrap();
+        assert_eq!(found[0], "transfer(address,uint256)");
+    }
+
+    #[tokio::test]
+    async fn uses_cache() {
+        let client = SignatureClient::with_database(SignatureDatabase::OpenChain(
+            "http://localhost:1".to_string(),
+        ));
+        let selector: Selector = hex!("a9059cbb").into();
+        client
+            .cache
+            .lock()
+            .unwrap()
+            .put(SignatureKey::Function(selector), vec!["transfer(address,uint256)".to_string()]);
+
+        let function = client.identify_function(selector).await.unwrap().unwrap();
+        assert_eq!(function.signature(), "transfer(address,uint256)");
+
+        let mut decoder = TraceDecoder::new();
+        decoder.identify(&client, [selector], []).await.unwrap();
+        assert_eq!(decoder.functions(&selector).len(), 1);
+    }
+}
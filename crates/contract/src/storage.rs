@@ -0,0 +1,313 @@
+//! Solidity storage layout utilities.
+//!
+//! Computes the storage slots of state variables following the
+//! [Solidity storage layout] rules, for use with `eth_getStorageAt`, `eth_getProof` or state
+//! overrides.
+//!
+//! # Examples
+//!
+//! Computing the slot of `allowance[owner][spender]` for a token whose `allowance` mapping is
+//! declared at slot 1:
+//!
+//! ```
+//! use alloy_contract::storage::StorageSlot;
+//! use alloy_primitives::{address, U256};
+//!
+//! let owner = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+//! let spender = address!("000000000022D473030F116dDEE9F6B43aC78BA3");
+//! let slot = StorageSlot::new(U256::from(1)).mapping(&owner).mapping(&spender);
+//! println!("allowance slot: {slot}");
+//! ```
+//!
+//! [Solidity storage layout]: https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html
+
+use alloy_primitives::{keccak256, B256, U256};
+use alloy_sol_types::{SolType, SolValue};
+use std::fmt;
+
+/// The size of a storage slot, in bytes.
+pub const SLOT_SIZE: usize = 32;
+
+/// Returns the slot of `mapping[key]`, for a mapping declared at `slot`.
+///
+/// Value type keys are padded to 32 bytes as in ABI encoding, while `string` and `bytes` keys are
+/// hashed unpadded, as `keccak256(h(key) . slot)`.
+pub fn mapping_slot<K: SolValue>(key: &K, slot: U256) -> U256 {
+    let mut buf =
+        if <K::SolType as SolType>::DYNAMIC { key.abi_encode_packed() } else { key.abi_encode() };
+    buf.extend_from_slice(&slot.to_be_bytes::<SLOT_SIZE>());
+    keccak256(buf).into()
+}
+
+/// Returns the first slot of the elements of a dynamic array (or of a long `string`/`bytes`
+/// value) declared at `slot`.
+///
+/// The array length itself is stored at `slot`.
+pub fn dynamic_array_slot(slot: U256) -> U256 {
+    keccak256(slot.to_be_bytes::<SLOT_SIZE>()).into()
+}
+
+/// Returns the location of `array[index]` for a dynamic array declared at `slot`, whose elements
+/// are `element_size` bytes wide.
+///
+/// Elements of up to 16 bytes are packed several per slot; larger elements, such as structs, take
+/// `element_size.div_ceil(32)` slots each.
+///
+/// # Panics
+///
+/// Panics if `element_size` is zero, see [`StorageLocation::element`].
+pub fn array_element_location(slot: U256, index: usize, element_size: usize) -> StorageLocation {
+    StorageLocation::new(dynamic_array_slot(slot), 0).element(index, element_size)
+}
+
+/// Returns the ERC-7201 namespaced storage slot for the given namespace id.
+///
+/// This is `keccak256(keccak256(id) - 1) & ~0xff`, see [ERC-7201].
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+pub fn erc7201_slot(id: &str) -> U256 {
+    let inner = U256::from_be_bytes(keccak256(id).0) - U256::from(1);
+    let outer = U256::from_be_bytes(keccak256(inner.to_be_bytes::<SLOT_SIZE>()).0);
+    outer & !U256::from(0xff)
+}
+
+/// Computes the byte-level locations of the members of a struct, or of consecutive state
+/// variables, from their sizes in bytes, starting at `slot`.
+///
+/// Members are packed into the same slot, right to left, as long as they fit. Members of 32
+/// bytes or more (structs, arrays, mappings) always start a new slot, and so does the member
+/// following them.
+pub fn packed_layout(slot: U256, sizes: &[usize]) -> Vec<StorageLocation> {
+    let mut layout = Vec::with_capacity(sizes.len());
+    let mut current = StorageLocation::new(slot, 0);
+    for &size in sizes {
+        if current.offset != 0 && (size >= SLOT_SIZE || current.offset as usize + size > SLOT_SIZE)
+        {
+            current = StorageLocation::new(current.slot + U256::from(1), 0);
+        }
+        layout.push(current);
+
+        if size >= SLOT_SIZE {
+            let slots = size.div_ceil(SLOT_SIZE);
+            current = StorageLocation::new(current.slot + U256::from(slots), 0);
+        } else {
+            current.offset += size as u8;
+        }
+    }
+    layout
+}
+
+/// A storage slot, with builder methods to navigate nested mappings, arrays and structs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StorageSlot(pub U256);
+
+impl StorageSlot {
+    /// Creates a new storage slot.
+    pub const fn new(slot: U256) -> Self {
+        Self(slot)
+    }
+
+    /// Creates the ERC-7201 namespaced root slot for the given namespace id.
+    ///
+    /// See [`erc7201_slot`].
+    pub fn erc7201(id: &str) -> Self {
+        Self(erc7201_slot(id))
+    }
+
+    /// Returns the slot of `self[key]`, where `self` is a mapping.
+    ///
+    /// See [`mapping_slot`].
+    pub fn mapping<K: SolValue>(self, key: &K) -> Self {
+        Self(mapping_slot(key, self.0))
+    }
+
+    /// Returns the first slot of `self[index]`, where `self` is a dynamic array whose elements
+    /// take `slots_per_element` slots each.
+    ///
+    /// Use [`array_element_location`] for arrays of packed elements smaller than a slot.
+    pub fn array_element(self, index: usize, slots_per_element: usize) -> Self {
+        Self(dynamic_array_slot(self.0) + U256::from(index) * U256::from(slots_per_element))
+    }
+
+    /// Returns the slot `offset` slots after this one, e.g. the slot of a struct member.
+    pub fn field(self, offset: usize) -> Self {
+        Self(self.0 + U256::from(offset))
+    }
+
+    /// Returns the slot as a [`U256`], for use with `eth_getStorageAt`.
+    pub const fn to_u256(self) -> U256 {
+        self.0
+    }
+
+    /// Returns the slot as a [`B256`], for use as a state override key.
+    pub fn to_b256(self) -> B256 {
+        self.0.into()
+    }
+}
+
+impl From<U256> for StorageSlot {
+    fn from(slot: U256) -> Self {
+        Self(slot)
+    }
+}
+
+impl From<B256> for StorageSlot {
+    fn from(slot: B256) -> Self {
+        Self(slot.into())
+    }
+}
+
+impl From<StorageSlot> for U256 {
+    fn from(slot: StorageSlot) -> Self {
+        slot.0
+    }
+}
+
+impl From<StorageSlot> for B256 {
+    fn from(slot: StorageSlot) -> Self {
+        slot.0.into()
+    }
+}
+
+impl fmt::Display for StorageSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+/// The location of a value in storage: its slot, and its byte offset within that slot, counted
+/// from the least significant (rightmost) byte.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StorageLocation {
+    /// The storage slot.
+    pub slot: U256,
+    /// The byte offset within the slot, from the right.
+    pub offset: u8,
+}
+
+impl StorageLocation {
+    /// Creates a new storage location.
+    pub const fn new(slot: U256, offset: u8) -> Self {
+        Self { slot, offset }
+    }
+
+    /// Returns the location of the `index`-th element of `element_size` bytes in a sequence
+    /// starting at this location's slot.
+    ///
+    /// This is the layout of both dynamic array elements and static arrays.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element_size` is zero, as no Solidity type has a zero-sized storage encoding.
+    pub fn element(self, index: usize, element_size: usize) -> Self {
+        assert!(element_size > 0, "storage array elements cannot be zero-sized");
+        if element_size > SLOT_SIZE / 2 {
+            let slots = element_size.div_ceil(SLOT_SIZE);
+            return Self::new(self.slot + U256::from(index) * U256::from(slots), 0);
+        }
+        let per_slot = SLOT_SIZE / element_size;
+        let slot = self.slot + U256::from(index / per_slot);
+        Self::new(slot, ((index % per_slot) * element_size) as u8)
+    }
+
+    /// Extracts the `size` bytes value at this location from the full slot value.
+    pub fn extract(&self, slot_value: U256, size: usize) -> U256 {
+        let value = slot_value >> (self.offset as usize * 8);
+        if size >= SLOT_SIZE {
+            value
+        } else {
+            value & ((U256::from(1) << (size * 8)) - U256::from(1))
+        }
+    }
+
+    /// Writes the `size` bytes `value` at this location into the full slot value, leaving the
+    /// other bytes untouched.
+    pub fn insert(&self, slot_value: U256, value: U256, size: usize) -> U256 {
+        if size >= SLOT_SIZE {
+            return value;
+        }
+        let shift = self.offset as usize * 8;
+        let mask = ((U256::from(1) << (size * 8)) - U256::from(1)) << shift;
+        (slot_value & !mask) | ((value << shift) & mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, uint, Address};
+
+    #[test]
+    fn mapping_slots() {
+        let owner = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let mut buf = owner.into_word().to_vec();
+        buf.extend_from_slice(&U256::from(3).to_be_bytes::<32>());
+        assert_eq!(mapping_slot(&owner, U256::from(3)), U256::from_be_bytes(keccak256(buf).0));
+
+        let mut buf = b"key".to_vec();
+        buf.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
+        assert_eq!(
+            mapping_slot(&"key".to_string(), U256::ZERO),
+            U256::from_be_bytes(keccak256(buf).0)
+        );
+
+        let spender = Address::with_last_byte(1);
+        let nested = StorageSlot::new(U256::from(1)).mapping(&owner).mapping(&spender);
+        assert_eq!(nested.0, mapping_slot(&spender, mapping_slot(&owner, U256::from(1))));
+    }
+
+    #[test]
+    fn erc7201() {
+        // From the ERC-7201 specification.
+        assert_eq!(
+            StorageSlot::erc7201("example.main").to_b256(),
+            b256!("183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500")
+        );
+    }
+
+    #[test]
+    fn arrays() {
+        let base = dynamic_array_slot(U256::from(2));
+        assert_eq!(base, U256::from_be_bytes(keccak256(U256::from(2).to_be_bytes::<32>()).0));
+        assert_eq!(StorageSlot::new(U256::from(2)).array_element(3, 2).0, base + U256::from(6));
+
+        // uint64[]: four elements per slot
+        assert_eq!(
+            array_element_location(U256::from(2), 5, 8),
+            StorageLocation::new(base + U256::from(1), 8)
+        );
+        // uint128[3] at slot 0
+        let loc = StorageLocation::default().element(2, 16);
+        assert_eq!(loc, StorageLocation::new(U256::from(1), 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "storage array elements cannot be zero-sized")]
+    fn zero_sized_elements() {
+        StorageLocation::default().element(1, 0);
+    }
+
+    #[test]
+    fn packing() {
+        // struct { uint128 a; uint128 b; uint256 c; address d; bool e; uint96 f; }
+        let layout = packed_layout(U256::ZERO, &[16, 16, 32, 20, 1, 12]);
+        assert_eq!(
+            layout,
+            vec![
+                StorageLocation::new(U256::ZERO, 0),
+                StorageLocation::new(U256::ZERO, 16),
+                StorageLocation::new(U256::from(1), 0),
+                StorageLocation::new(U256::from(2), 0),
+                StorageLocation::new(U256::from(2), 20),
+                StorageLocation::new(U256::from(3), 0),
+            ]
+        );
+
+        let value = uint!(0x0000000000000000000000000000002a0000000000000000000000000000000b_U256);
+        assert_eq!(layout[0].extract(value, 16), U256::from(11));
+        assert_eq!(layout[1].extract(value, 16), U256::from(42));
+        let updated = layout[1].insert(value, U256::from(7), 16);
+        assert_eq!(layout[1].extract(updated, 16), U256::from(7));
+        assert_eq!(layout[0].extract(updated, 16), U256::from(11));
+    }
+}
@@ -0,0 +1,272 @@
+//! Helpers for interacting with ERC-20, ERC-721 and ERC-1155 tokens.
+//!
+//! The interfaces are also exported, for use with [`CallBuilder`](crate::CallBuilder) and log
+//! decoding.
+
+use crate::{Result, SolCallBuilder};
+use alloy_network::Network;
+use alloy_primitives::{Address, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::Filter;
+use alloy_sol_types::{sol, sol_data, SolEvent, SolType};
+use alloy_transport::Transport;
+use std::future::IntoFuture;
+
+sol! {
+    /// The ERC-20 token interface, including the optional metadata extension.
+    #[derive(Debug, PartialEq, Eq)]
+    #[allow(missing_docs)]
+    interface IERC20 {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+        event Approval(address indexed owner, address indexed spender, uint256 value);
+
+        function name() external view returns (string name);
+        function symbol() external view returns (string symbol);
+        function decimals() external view returns (uint8 decimals);
+        function totalSupply() external view returns (uint256 totalSupply);
+        function balanceOf(address owner) external view returns (uint256 balance);
+        function allowance(address owner, address spender) external view returns (uint256 allowance);
+        function transfer(address to, uint256 amount) external returns (bool success);
+        function approve(address spender, uint256 amount) external returns (bool success);
+        function transferFrom(address from, address to, uint256 amount) external returns (bool success);
+    }
+}
+
+sol! {
+    /// The ERC-721 non-fungible token interface.
+    #[derive(Debug, PartialEq, Eq)]
+    #[allow(missing_docs)]
+    interface IERC721 {
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+        event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId);
+        event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+
+        function balanceOf(address owner) external view returns (uint256 balance);
+        function ownerOf(uint256 tokenId) external view returns (address owner);
+        function tokenURI(uint256 tokenId) external view returns (string uri);
+        function getApproved(uint256 tokenId) external view returns (address operator);
+        function isApprovedForAll(address owner, address operator) external view returns (bool approved);
+        function safeTransferFrom(address from, address to, uint256 tokenId) external;
+        function transferFrom(address from, address to, uint256 tokenId) external;
+    }
+}
+
+sol! {
+    /// The ERC-1155 multi-token interface.
+    #[derive(Debug, PartialEq, Eq)]
+    #[allow(missing_docs)]
+    interface IERC1155 {
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+        event ApprovalForAll(address indexed account, address indexed operator, bool approved);
+
+        function balanceOf(address account, uint256 id) external view returns (uint256 balance);
+        function balanceOfBatch(address[] accounts, uint256[] ids) external view returns (uint256[] balances);
+        function isApprovedForAll(address account, address operator) external view returns (bool approved);
+        function uri(uint256 id) external view returns (string uri);
+        function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes data) external;
+    }
+}
+
+/// The metadata of an ERC-20 token.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Erc20Metadata {
+    /// The token name.
+    pub name: String,
+    /// The token symbol.
+    pub symbol: String,
+    /// The number of decimals of the token.
+    pub decimals: u8,
+}
+
+/// Returns the ERC-20 balance of `owner`.
+pub async fn erc20_balance_of<T, P, N>(provider: &P, token: Address, owner: Address) -> Result<U256>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let call = IERC20::balanceOfCall { owner };
+    Ok(SolCallBuilder::new_sol(provider, &token, &call).call().await?.balance)
+}
+
+/// Returns the amount of `owner`'s ERC-20 tokens that `spender` is allowed to spend.
+pub async fn erc20_allowance<T, P, N>(
+    provider: &P,
+    token: Address,
+    owner: Address,
+    spender: Address,
+) -> Result<U256>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let call = IERC20::allowanceCall { owner, spender };
+    Ok(SolCallBuilder::new_sol(provider, &token, &call).call().await?.allowance)
+}
+
+/// Returns the name, symbol and decimals of an ERC-20 token.
+///
+/// Tokens that predate the metadata extension and return `bytes32` for `name` and `symbol` are
+/// supported; their values are decoded as null-padded UTF-8.
+pub async fn erc20_metadata<T, P, N>(provider: &P, token: Address) -> Result<Erc20Metadata>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let name = SolCallBuilder::new_sol(provider, &token, &IERC20::nameCall {});
+    let symbol = SolCallBuilder::new_sol(provider, &token, &IERC20::symbolCall {});
+    let decimals = SolCallBuilder::new_sol(provider, &token, &IERC20::decimalsCall {});
+
+    let (name, symbol, decimals) = futures::try_join!(
+        name.call_raw().into_future(),
+        symbol.call_raw().into_future(),
+        decimals.call().into_future()
+    )?;
+
+    Ok(Erc20Metadata {
+        name: decode_string(&name)?,
+        symbol: decode_string(&symbol)?,
+        decimals: decimals.decimals,
+    })
+}
+
+/// Returns the owner of an ERC-721 token.
+pub async fn erc721_owner_of<T, P, N>(provider: &P, token: Address, id: U256) -> Result<Address>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let call = IERC721::ownerOfCall { tokenId: id };
+    Ok(SolCallBuilder::new_sol(provider, &token, &call).call().await?.owner)
+}
+
+/// Returns the ERC-1155 balances of each `(accounts[i], ids[i])` pair.
+pub async fn erc1155_balance_of_batch<T, P, N>(
+    provider: &P,
+    token: Address,
+    accounts: Vec<Address>,
+    ids: Vec<U256>,
+) -> Result<Vec<U256>>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    let call = IERC1155::balanceOfBatchCall { accounts, ids };
+    Ok(SolCallBuilder::new_sol(provider, &token, &call).call().await?.balances)
+}
+
+/// Returns a filter for the ERC-20 `Transfer` events of `token`, optionally restricted to a
+/// sender and/or recipient.
+pub fn erc20_transfer_filter(token: Address, from: Option<Address>, to: Option<Address>) -> Filter {
+    with_address_topics(
+        Filter::new().address(token).event_signature(IERC20::Transfer::SIGNATURE_HASH),
+        from,
+        to,
+    )
+}
+
+/// Returns a filter for the ERC-721 `Transfer` events of `token`, optionally restricted to a
+/// sender, a recipient and/or a token id.
+///
+/// Note that ERC-20 and ERC-721 `Transfer` events share the same signature, and only differ in
+/// whether the amount or id is indexed.
+pub fn erc721_transfer_filter(
+    token: Address,
+    from: Option<Address>,
+    to: Option<Address>,
+    id: Option<U256>,
+) -> Filter {
+    let mut filter = with_address_topics(
+        Filter::new().address(token).event_signature(IERC721::Transfer::SIGNATURE_HASH),
+        from,
+        to,
+    );
+    if let Some(id) = id {
+        filter = filter.topic3(B256::from(id));
+    }
+    filter
+}
+
+/// Returns a filter for both the ERC-1155 `TransferSingle` and `TransferBatch` events of
+/// `token`, optionally restricted to a sender and/or recipient.
+pub fn erc1155_transfer_filter(
+    token: Address,
+    from: Option<Address>,
+    to: Option<Address>,
+) -> Filter {
+    let mut filter = Filter::new().address(token).event_signature(vec![
+        IERC1155::TransferSingle::SIGNATURE_HASH,
+        IERC1155::TransferBatch::SIGNATURE_HASH,
+    ]);
+    if let Some(from) = from {
+        filter = filter.topic2(from.into_word());
+    }
+    if let Some(to) = to {
+        filter = filter.topic3(to.into_word());
+    }
+    filter
+}
+
+fn with_address_topics(mut filter: Filter, from: Option<Address>, to: Option<Address>) -> Filter {
+    if let Some(from) = from {
+        filter = filter.topic1(from.into_word());
+    }
+    if let Some(to) = to {
+        filter = filter.topic2(to.into_word());
+    }
+    filter
+}
+
+/// Decodes a `string` return value, falling back to a null-padded `bytes32`.
+fn decode_string(data: &[u8]) -> Result<String> {
+    if let Ok((value,)) = <(sol_data::String,)>::abi_decode_params(data, true) {
+        return Ok(value);
+    }
+    let value = sol_data::FixedBytes::<32>::abi_decode(data, true)?;
+    let len = value.iter().position(|&b| b == 0).unwrap_or(value.len());
+    Ok(String::from_utf8_lossy(&value[..len]).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, bytes};
+    use alloy_sol_types::SolValue;
+
+    #[test]
+    fn decode_metadata_strings() {
+        let data = ("Wrapped Ether".to_string(),).abi_encode_params();
+        assert_eq!(decode_string(&data).unwrap(), "Wrapped Ether");
+
+        // MKR `symbol()`
+        let data = b256!("4d4b520000000000000000000000000000000000000000000000000000000000");
+        assert_eq!(decode_string(&data[..]).unwrap(), "MKR");
+
+        let data = bytes!("deadbeef");
+        assert!(decode_string(&data).is_err());
+    }
+
+    #[test]
+    fn transfer_filters() {
+        let token = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let from = Address::with_last_byte(1);
+
+        let filter = erc20_transfer_filter(token, Some(from), None);
+        assert_eq!(filter.topics[0], IERC20::Transfer::SIGNATURE_HASH.into());
+        assert_eq!(filter.topics[1], from.into_word().into());
+        assert!(filter.topics[2].is_empty());
+
+        let filter = erc721_transfer_filter(token, None, None, Some(U256::from(7)));
+        assert!(filter.topics[1].is_empty());
+        assert_eq!(filter.topics[3], B256::from(U256::from(7)).into());
+
+        let filter = erc1155_transfer_filter(token, None, Some(from));
+        assert_eq!(filter.topics[0].iter().count(), 2);
+        assert_eq!(filter.topics[3], from.into_word().into());
+    }
+}
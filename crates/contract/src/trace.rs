@@ -0,0 +1,672 @@
+use alloy_dyn_abi::{DynSolValue, EventExt, FunctionExt, JsonAbiExt};
+use alloy_json_abi::{Error as SolError, Event, Function, JsonAbi};
+use alloy_primitives::{hex, Address, Bytes, Selector, B256, U256};
+use alloy_rpc_types_trace::{
+    geth::{CallFrame, CallLogFrame, GethTrace},
+    parity::{Action, TraceOutput, TransactionTrace},
+};
+use std::{collections::HashMap, fmt};
+
+/// Decodes call frames, logs and revert data of transaction traces using a set of contract ABIs.
+///
+/// Functions, events and errors are indexed by their selector, so a single decoder can be shared
+/// for all the contracts touched by a trace. Addresses can additionally be given a human-readable
+/// label, which is used instead of the raw address when rendering a call tree.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_contract::TraceDecoder;
+/// use alloy_json_abi::JsonAbi;
+///
+/// let abi = JsonAbi::parse(["function transfer(address to, uint256 amount) returns (bool)"])?;
+/// let decoder = TraceDecoder::new().with_abi(&abi);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TraceDecoder {
+    labels: HashMap<Address, String>,
+    functions: HashMap<Selector, Vec<Function>>,
+    events: HashMap<B256, Vec<Event>>,
+    errors: HashMap<Selector, Vec<SolError>>,
+}
+
+impl TraceDecoder {
+    /// Creates a new, empty decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all functions, events and errors of the given ABI to the decoder.
+    pub fn with_abi(mut self, abi: &JsonAbi) -> Self {
+        self.add_abi(abi);
+        self
+    }
+
+    /// Adds the ABI of a known contract and labels its address.
+    pub fn with_contract(
+        mut self,
+        address: Address,
+        label: impl Into<String>,
+        abi: &JsonAbi,
+    ) -> Self {
+        self.add_abi(abi);
+        self.add_label(address, label);
+        self
+    }
+
+    /// Labels the given address.
+    pub fn with_label(mut self, address: Address, label: impl Into<String>) -> Self {
+        self.add_label(address, label);
+        self
+    }
+
+    /// Adds all functions, events and errors of the given ABI to the decoder.
+    pub fn add_abi(&mut self, abi: &JsonAbi) {
+        abi.functions().cloned().for_each(|function| self.add_function(function));
+        abi.events().cloned().for_each(|event| self.add_event(event));
+        abi.errors().cloned().for_each(|error| self.add_error(error));
+    }
+
+    /// Labels the given address.
+    pub fn add_label(&mut self, address: Address, label: impl Into<String>) {
+        self.labels.insert(address, label.into());
+    }
+
+    /// Adds a single function to the decoder.
+    ///
+    /// Functions with the same signature as an already known function are ignored.
+    pub fn add_function(&mut self, function: Function) {
+        let functions = self.functions.entry(function.selector()).or_default();
+        if !functions.iter().any(|f| f.signature() == function.signature()) {
+            functions.push(function);
+        }
+    }
+
+    /// Adds a single event to the decoder.
+    ///
+    /// Anonymous events cannot be identified by their topics and are ignored.
+    pub fn add_event(&mut self, event: Event) {
+        if event.anonymous {
+            return;
+        }
+        let events = self.events.entry(event.selector()).or_default();
+        if !events.iter().any(|e| e.full_signature() == event.full_signature()) {
+            events.push(event);
+        }
+    }
+
+    /// Adds a single custom error to the decoder.
+    pub fn add_error(&mut self, error: SolError) {
+        let errors = self.errors.entry(error.selector()).or_default();
+        if !errors.iter().any(|e| e.signature() == error.signature()) {
+            errors.push(error);
+        }
+    }
+
+    /// Returns the label of the given address, if any.
+    pub fn label(&self, address: &Address) -> Option<&str> {
+        self.labels.get(address).map(String::as_str)
+    }
+
+    /// Returns the known functions with the given selector.
+    pub fn functions(&self, selector: &Selector) -> &[Function] {
+        self.functions.get(selector).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the known events with the given signature hash.
+    pub fn events(&self, selector: &B256) -> &[Event] {
+        self.events.get(selector).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the known custom errors with the given selector.
+    pub fn errors(&self, selector: &Selector) -> &[SolError] {
+        self.errors.get(selector).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Decodes calldata, and the call's return data if given.
+    ///
+    /// Returns `None` if no known function matches the calldata.
+    pub fn decode_call(&self, input: &[u8], output: Option<&[u8]>) -> Option<DecodedCall> {
+        let (selector, data) = split_selector(input)?;
+        self.functions(&selector).iter().find_map(|function| {
+            let inputs = function.abi_decode_input(data, false).ok()?;
+            let outputs = output
+                .filter(|output| output.is_empty() == function.outputs.is_empty())
+                .and_then(|output| function.abi_decode_output(output, false).ok())
+                .map(|values| {
+                    named_params(function.outputs.iter().map(|p| p.name.as_str()), values)
+                });
+            Some(DecodedCall {
+                name: function.name.clone(),
+                signature: function.signature(),
+                inputs: named_params(function.inputs.iter().map(|p| p.name.as_str()), inputs),
+                outputs,
+            })
+        })
+    }
+
+    /// Decodes a log from its topics and data.
+    ///
+    /// Returns `None` if no known event matches the log.
+    pub fn decode_log(&self, topics: &[B256], data: &[u8]) -> Option<DecodedLog> {
+        self.events(topics.first()?).iter().find_map(|event| {
+            decode_event(event, topics, data).or_else(|| {
+                // Events without any indexed parameters may come from signature databases, which
+                // don't record indexing: assume the leading parameters are the indexed ones.
+                let indexed = topics.len() - 1;
+                if indexed == 0 || event.inputs.iter().any(|param| param.indexed) {
+                    return None;
+                }
+                let mut event = event.clone();
+                event.inputs.iter_mut().take(indexed).for_each(|param| param.indexed = true);
+                decode_event(&event, topics, data)
+            })
+        })
+    }
+
+    /// Decodes revert data into a human-readable reason.
+    ///
+    /// Known custom errors are tried first, before falling back to the builtin `Error(string)`
+    /// and `Panic(uint256)` errors.
+    pub fn decode_revert(&self, data: &[u8]) -> Option<String> {
+        if let Some((selector, params)) = split_selector(data) {
+            let custom = self.errors(&selector).iter().find_map(|error| {
+                let values = error.abi_decode_input(params, false).ok()?;
+                let params = named_params(error.inputs.iter().map(|p| p.name.as_str()), values);
+                Some(format!("{}({})", error.name, DisplayParams(&params)))
+            });
+            if custom.is_some() {
+                return custom;
+            }
+        }
+        alloy_sol_types::decode_revert_reason(data)
+    }
+
+    /// Decodes a `callTracer` frame and all of its children.
+    pub fn decode_call_frame(&self, frame: &CallFrame) -> DecodedCallFrame {
+        let is_create = frame.typ.starts_with("CREATE");
+        let call = if is_create {
+            None
+        } else {
+            self.decode_call(
+                &frame.input,
+                frame.output.as_ref().map(|output| &output[..]).filter(|_| frame.error.is_none()),
+            )
+        };
+        let revert = frame
+            .error
+            .as_ref()
+            .and(frame.output.as_deref())
+            .and_then(|output| self.decode_revert(output))
+            .or_else(|| frame.revert_reason.clone());
+
+        DecodedCallFrame {
+            typ: frame.typ.clone(),
+            from: frame.from,
+            to: frame.to,
+            label: frame.to.and_then(|to| self.label(&to)).map(ToString::to_string),
+            value: frame.value,
+            gas_used: frame.gas_used,
+            input: frame.input.clone(),
+            output: frame.output.clone(),
+            call,
+            error: frame.error.clone(),
+            revert,
+            logs: frame.logs.iter().map(|log| self.decode_call_log(log)).collect(),
+            calls: frame.calls.iter().map(|call| self.decode_call_frame(call)).collect(),
+        }
+    }
+
+    /// Decodes a [`GethTrace`], if it was produced by the `callTracer`.
+    pub fn decode_geth_trace(&self, trace: &GethTrace) -> Option<DecodedCallFrame> {
+        match trace {
+            GethTrace::CallTracer(frame) => Some(self.decode_call_frame(frame)),
+            _ => None,
+        }
+    }
+
+    /// Decodes a single parity-style trace.
+    pub fn decode_parity_trace(&self, trace: &TransactionTrace) -> DecodedTransactionTrace {
+        let (label, call, revert) = match (&trace.action, &trace.result) {
+            (Action::Call(action), result) => {
+                let output = match result {
+                    Some(TraceOutput::Call(out)) => Some(&out.output[..]),
+                    _ => None,
+                };
+                let call =
+                    self.decode_call(&action.input, output.filter(|_| trace.error.is_none()));
+                let revert = trace.error.as_ref().and(output).and_then(|o| self.decode_revert(o));
+                (self.label(&action.to).map(ToString::to_string), call, revert)
+            }
+            (Action::Selfdestruct(action), _) => {
+                (self.label(&action.address).map(ToString::to_string), None, None)
+            }
+            _ => (None, None, None),
+        };
+        DecodedTransactionTrace { trace: trace.clone(), label, call, revert }
+    }
+
+    /// Decodes a list of parity-style traces, e.g. the output of `trace_transaction`.
+    pub fn decode_parity_traces<'a, I>(&self, traces: I) -> Vec<DecodedTransactionTrace>
+    where
+        I: IntoIterator<Item = &'a TransactionTrace>,
+    {
+        traces.into_iter().map(|trace| self.decode_parity_trace(trace)).collect()
+    }
+
+    fn decode_call_log(&self, log: &CallLogFrame) -> DecodedCallLog {
+        let topics = log.topics.clone().unwrap_or_default();
+        let data = log.data.clone().unwrap_or_default();
+        let decoded = self.decode_log(&topics, &data);
+        DecodedCallLog { address: log.address, topics, data, decoded }
+    }
+}
+
+/// A decoded function parameter, event parameter or return value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedParam {
+    /// The name of the parameter, empty if unnamed.
+    pub name: String,
+    /// The decoded value.
+    pub value: DynSolValue,
+}
+
+impl fmt::Display for DecodedParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.name.is_empty() {
+            write!(f, "{}: ", self.name)?;
+        }
+        fmt_value(&self.value, f)
+    }
+}
+
+/// A decoded function call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedCall {
+    /// The function name.
+    pub name: String,
+    /// The function signature, e.g. `transfer(address,uint256)`.
+    pub signature: String,
+    /// The decoded arguments.
+    pub inputs: Vec<DecodedParam>,
+    /// The decoded return values, if the call returned successfully.
+    pub outputs: Option<Vec<DecodedParam>>,
+}
+
+impl fmt::Display for DecodedCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.name, DisplayParams(&self.inputs))
+    }
+}
+
+/// A decoded event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedLog {
+    /// The event name.
+    pub name: String,
+    /// The event signature, e.g. `Transfer(address,address,uint256)`.
+    pub signature: String,
+    /// The decoded parameters, in declaration order.
+    pub params: Vec<DecodedParam>,
+}
+
+impl fmt::Display for DecodedLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.name, DisplayParams(&self.params))
+    }
+}
+
+/// A log emitted in a [`DecodedCallFrame`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedCallLog {
+    /// The address of the contract that emitted the log.
+    pub address: Option<Address>,
+    /// The raw topics of the log.
+    pub topics: Vec<B256>,
+    /// The raw data of the log.
+    pub data: Bytes,
+    /// The decoded event, if it matched a known event.
+    pub decoded: Option<DecodedLog>,
+}
+
+impl fmt::Display for DecodedCallLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.decoded {
+            Some(decoded) => write!(f, "emit {decoded}"),
+            None => {
+                f.write_str("emit unknown(topics: [")?;
+                for (i, topic) in self.topics.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{topic}")?;
+                }
+                write!(f, "], data: {})", self.data)
+            }
+        }
+    }
+}
+
+/// A `callTracer` frame annotated with decoded calls, logs and revert reasons.
+///
+/// The [`Display`](fmt::Display) implementation renders the frame as a human-readable call tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedCallFrame {
+    /// The type of the call, e.g. `CALL` or `CREATE`.
+    pub typ: String,
+    /// The address that initiated the call.
+    pub from: Address,
+    /// The address of the called contract.
+    pub to: Option<Address>,
+    /// The label of the called contract, if known.
+    pub label: Option<String>,
+    /// Value transferred.
+    pub value: Option<U256>,
+    /// How much gas was used by the call.
+    pub gas_used: u128,
+    /// The raw calldata.
+    pub input: Bytes,
+    /// The raw output of the call, if any.
+    pub output: Option<Bytes>,
+    /// The decoded call, if it matched a known function.
+    pub call: Option<DecodedCall>,
+    /// Error message, if any.
+    pub error: Option<String>,
+    /// The decoded revert reason, if the call reverted.
+    pub revert: Option<String>,
+    /// Logs emitted by this call.
+    pub logs: Vec<DecodedCallLog>,
+    /// Decoded child calls.
+    pub calls: Vec<Self>,
+}
+
+impl DecodedCallFrame {
+    fn fmt_tree(&self, f: &mut fmt::Formatter<'_>, prefix: &str) -> fmt::Result {
+        write!(f, "[{}] ", self.gas_used)?;
+        match (&self.label, self.to) {
+            (Some(label), _) => f.write_str(label)?,
+            (None, Some(to)) => write!(f, "{to}")?,
+            (None, None) => f.write_str("<unknown>")?,
+        }
+        f.write_str("::")?;
+        match &self.call {
+            Some(call) => write!(f, "{call}")?,
+            None if self.typ.starts_with("CREATE") => f.write_str("new")?,
+            None if self.input.is_empty() => f.write_str("fallback()")?,
+            None => write!(f, "{}", self.input)?,
+        }
+        if let Some(value) = self.value.filter(|value| !value.is_zero()) {
+            write!(f, " {{value: {value}}}")?;
+        }
+        if self.typ != "CALL" {
+            write!(f, " [{}]", self.typ.to_lowercase())?;
+        }
+        writeln!(f)?;
+
+        let children = self.logs.len() + self.calls.len() + 1;
+        let mut index = 0;
+        let mut next = |f: &mut fmt::Formatter<'_>| {
+            index += 1;
+            let last = index == children;
+            f.write_str(prefix)?;
+            f.write_str(if last { "└─ " } else { "├─ " })?;
+            Ok::<_, fmt::Error>(format!("{prefix}{}", if last { "   " } else { "│  " }))
+        };
+        for log in &self.logs {
+            next(f)?;
+            writeln!(f, "{log}")?;
+        }
+        for call in &self.calls {
+            let child_prefix = next(f)?;
+            call.fmt_tree(f, &child_prefix)?;
+        }
+        next(f)?;
+        match (&self.error, &self.revert) {
+            (Some(_), Some(revert)) => writeln!(f, "← [Revert] {revert}"),
+            (Some(error), None) => writeln!(f, "← [{error}]"),
+            (None, _) => match self.call.as_ref().and_then(|call| call.outputs.as_ref()) {
+                Some(outputs) => writeln!(f, "← ({})", DisplayParams(outputs)),
+                None => match &self.output {
+                    Some(output) if !output.is_empty() => writeln!(f, "← {output}"),
+                    _ => writeln!(f, "← [Stop]"),
+                },
+            },
+        }
+    }
+}
+
+impl fmt::Display for DecodedCallFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_tree(f, "")
+    }
+}
+
+/// A parity-style trace annotated with the decoded call and revert reason.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedTransactionTrace {
+    /// The raw trace.
+    pub trace: TransactionTrace,
+    /// The label of the called contract, if known.
+    pub label: Option<String>,
+    /// The decoded call, if it matched a known function.
+    pub call: Option<DecodedCall>,
+    /// The decoded revert reason, if the call reverted.
+    pub revert: Option<String>,
+}
+
+impl fmt::Display for DecodedTransactionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for _ in 0..self.trace.trace_address.len() {
+            f.write_str("  ")?;
+        }
+        let target = self.label.clone();
+        match &self.trace.action {
+            Action::Call(action) => {
+                write!(f, "{}::", target.unwrap_or_else(|| action.to.to_string()))?;
+                match &self.call {
+                    Some(call) => write!(f, "{call}")?,
+                    None if action.input.is_empty() => f.write_str("fallback()")?,
+                    None => write!(f, "{}", action.input)?,
+                }
+                if !action.value.is_zero() {
+                    write!(f, " {{value: {}}}", action.value)?;
+                }
+            }
+            Action::Create(action) => write!(f, "new {{from: {}}}", action.from)?,
+            Action::Selfdestruct(action) => write!(
+                f,
+                "{}::selfdestruct {{refund: {}}}",
+                target.unwrap_or_else(|| action.address.to_string()),
+                action.refund_address
+            )?,
+            Action::Reward(action) => write!(f, "reward {{author: {}}}", action.author)?,
+        }
+        if let Some(revert) = &self.revert {
+            write!(f, " ← [Revert] {revert}")?;
+        } else if let Some(error) = &self.trace.error {
+            write!(f, " ← [{error}]")?;
+        } else if let Some(outputs) = self.call.as_ref().and_then(|call| call.outputs.as_ref()) {
+            write!(f, " ← ({})", DisplayParams(outputs))?;
+        }
+        Ok(())
+    }
+}
+
+struct DisplayParams<'a>(&'a [DecodedParam]);
+
+impl fmt::Display for DisplayParams<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, param) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{param}")?;
+        }
+        Ok(())
+    }
+}
+
+fn fmt_value(value: &DynSolValue, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let fmt_seq = |f: &mut fmt::Formatter<'_>, values: &[DynSolValue], open, close| {
+        f.write_str(open)?;
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            fmt_value(value, f)?;
+        }
+        f.write_str(close)
+    };
+    match value {
+        DynSolValue::Bool(b) => write!(f, "{b}"),
+        DynSolValue::Int(i, _) => write!(f, "{i}"),
+        DynSolValue::Uint(u, _) => write!(f, "{u}"),
+        DynSolValue::FixedBytes(word, size) => f.write_str(&hex::encode_prefixed(&word[..*size])),
+        DynSolValue::Address(address) => write!(f, "{address}"),
+        DynSolValue::Function(function) => write!(f, "{function}"),
+        DynSolValue::Bytes(bytes) => f.write_str(&hex::encode_prefixed(bytes)),
+        DynSolValue::String(s) => write!(f, "{s:?}"),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            fmt_seq(f, values, "[", "]")
+        }
+        DynSolValue::Tuple(values) => fmt_seq(f, values, "(", ")"),
+        #[allow(unreachable_patterns)]
+        _ => write!(f, "{value:?}"),
+    }
+}
+
+fn decode_event(event: &Event, topics: &[B256], data: &[u8]) -> Option<DecodedLog> {
+    let decoded = event.decode_log_parts(topics.iter().copied(), data, false).ok()?;
+    let (mut indexed, mut body) = (decoded.indexed.into_iter(), decoded.body.into_iter());
+    let params = event
+        .inputs
+        .iter()
+        .map(|param| {
+            let value = if param.indexed { indexed.next() } else { body.next() }?;
+            Some(DecodedParam { name: param.name.clone(), value })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(DecodedLog { name: event.name.clone(), signature: event.signature(), params })
+}
+
+fn named_params<'a>(
+    names: impl Iterator<Item = &'a str>,
+    values: Vec<DynSolValue>,
+) -> Vec<DecodedParam> {
+    names.zip(values).map(|(name, value)| DecodedParam { name: name.to_string(), value }).collect()
+}
+
+fn split_selector(data: &[u8]) -> Option<(Selector, &[u8])> {
+    let (selector, rest) = (data.get(..4)?, &data[4..]);
+    Some((Selector::from_slice(selector), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, keccak256};
+
+    fn decoder() -> TraceDecoder {
+        let abi = JsonAbi::parse([
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "error InsufficientBalance(uint256 available, uint256 required)",
+        ])
+        .unwrap();
+        TraceDecoder::new().with_contract(
+            address!("00000000000000000000000000000000000000aa"),
+            "Token",
+            &abi,
+        )
+    }
+
+    fn transfer_input(to: Address, amount: u64) -> Bytes {
+        let function = Function::parse("transfer(address,uint256)").unwrap();
+        function
+            .abi_encode_input(&[to.into(), DynSolValue::Uint(U256::from(amount), 256)])
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn decodes_call_frame_tree() {
+        let decoder = decoder();
+        let sender = address!("00000000000000000000000000000000000000bb");
+        let receiver = address!("00000000000000000000000000000000000000cc");
+        let log = CallLogFrame {
+            address: Some(address!("00000000000000000000000000000000000000aa")),
+            topics: Some(vec![
+                keccak256("Transfer(address,address,uint256)"),
+                sender.into_word(),
+                receiver.into_word(),
+            ]),
+            data: Some(U256::from(5).to_be_bytes::<32>().to_vec().into()),
+        };
+        let frame = CallFrame {
+            from: sender,
+            to: Some(address!("00000000000000000000000000000000000000aa")),
+            input: transfer_input(receiver, 5),
+            output: Some(DynSolValue::Bool(true).abi_encode().into()),
+            gas_used: 21000,
+            logs: vec![log],
+            typ: "CALL".to_string(),
+            ..Default::default()
+        };
+
+        let decoded = decoder.decode_call_frame(&frame);
+        let call = decoded.call.as_ref().unwrap();
+        assert_eq!(call.signature, "transfer(address,uint256)");
+        assert_eq!(call.inputs[1].value, DynSolValue::Uint(U256::from(5), 256));
+        assert_eq!(call.outputs.as_ref().unwrap()[0].value, DynSolValue::Bool(true));
+        assert_eq!(decoded.logs[0].decoded.as_ref().unwrap().name, "Transfer");
+
+        let tree = decoded.to_string();
+        assert_eq!(
+            tree,
+            format!(
+                "[21000] Token::transfer(to: {receiver}, amount: 5)\n\
+                 ├─ emit Transfer(from: {sender}, to: {receiver}, value: 5)\n\
+                 └─ ← (true)\n"
+            )
+        );
+    }
+
+    #[test]
+    fn decodes_reverts() {
+        let decoder = decoder();
+        let error = SolError::parse("InsufficientBalance(uint256,uint256)").unwrap();
+        let data = error
+            .abi_encode_input(&[
+                DynSolValue::Uint(U256::from(1), 256),
+                DynSolValue::Uint(U256::from(2), 256),
+            ])
+            .unwrap();
+        assert_eq!(
+            decoder.decode_revert(&data).unwrap(),
+            "InsufficientBalance(available: 1, required: 2)"
+        );
+
+        let data = alloy_sol_types::Revert::from("nope");
+        let data = alloy_sol_types::SolError::abi_encode(&data);
+        assert_eq!(decoder.decode_revert(&data).unwrap(), "revert: nope");
+    }
+
+    #[test]
+    fn decodes_parity_trace() {
+        let decoder = decoder();
+        let receiver = address!("00000000000000000000000000000000000000cc");
+        let trace = TransactionTrace {
+            action: Action::Call(alloy_rpc_types_trace::parity::CallAction {
+                to: address!("00000000000000000000000000000000000000aa"),
+                input: transfer_input(receiver, 7),
+                ..Default::default()
+            }),
+            trace_address: vec![0],
+            ..Default::default()
+        };
+        let decoded = decoder.decode_parity_traces([&trace]);
+        assert_eq!(decoded[0].label.as_deref(), Some("Token"));
+        assert_eq!(decoded[0].to_string(), format!("  Token::transfer(to: {receiver}, amount: 7)"));
+    }
+}
@@ -15,6 +15,11 @@ use core::{
 // https://eips.ethereum.org/EIPS/eip-2718#transactiontype-only-goes-up-to-0x7f
 const TX_TYPE_BYTE_MAX: u8 = 0x7f;
 
+/// The type of the legacy variant of [EIP-2718] envelopes, which is encoded without a type flag.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub const LEGACY_TX_TYPE_ID: u8 = 0;
+
 /// [EIP-2718] decoding errors.
 ///
 /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
@@ -48,6 +53,34 @@ impl From<alloy_rlp::Error> for Eip2718Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Eip2718Error {}
 
+/// A type differentiated by an [EIP-2718] transaction type, such as a transaction, a receipt, or an
+/// envelope over them.
+///
+/// ## Implementing
+///
+/// Implement this trait for the transaction types of your network, and the envelopes over them, so
+/// that they can be used with [`Encodable2718`] and the alloy envelope machinery. Custom types,
+/// e.g. deposit transactions, should use a type in the range `0x01..=0x7f` that is not already
+/// taken by Ethereum.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub trait Typed2718 {
+    /// Returns the [EIP-2718] type of the value, [`LEGACY_TX_TYPE_ID`] for the legacy variant.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    fn ty(&self) -> u8;
+
+    /// True if the value is of the given type.
+    fn is_type(&self, ty: u8) -> bool {
+        self.ty() == ty
+    }
+
+    /// True if the value is the legacy variant.
+    fn is_legacy(&self) -> bool {
+        self.ty() == LEGACY_TX_TYPE_ID
+    }
+}
+
 /// Decoding trait for [EIP-2718] envelopes. These envelopes wrap a transaction
 /// or a receipt with a type flag.
 ///
@@ -88,19 +121,37 @@ pub trait Decodable2718: Sized {
     /// decoder.
     fn fallback_decode(buf: &mut &[u8]) -> Eip2718Result<Self>;
 
-    /// Encode the transaction according to [EIP-2718] rules. First a 1-byte
+    /// Decode the transaction according to [EIP-2718] rules. First a 1-byte
     /// type flag in the range 0x0-0x7f, then the body of the transaction.
     ///
+    /// This is the canonical encoding, used by `eth_sendRawTransaction` and
+    /// the block tries.
+    ///
     /// [EIP-2718] inner encodings are unspecified, and produce an opaque
     /// bytestring.
     ///
     /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
     fn decode_2718(buf: &mut &[u8]) -> Eip2718Result<Self> {
         Self::extract_type_byte(buf)
-            .map(|ty| Self::typed_decode(ty, &mut &buf[1..]))
+            .map(|ty| {
+                buf.advance(1);
+                Self::typed_decode(ty, buf)
+            })
             .unwrap_or_else(|| Self::fallback_decode(buf))
     }
 
+    /// Decode an [EIP-2718] envelope in the canonical format, checking that
+    /// the whole buffer is consumed.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    fn decode_2718_exact(mut buf: &[u8]) -> Eip2718Result<Self> {
+        let decoded = Self::decode_2718(&mut buf)?;
+        if !buf.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength.into());
+        }
+        Ok(decoded)
+    }
+
     /// Decode an [EIP-2718] transaction in the network format. The network
     /// format is used ONLY by the Ethereum p2p protocol. Do not call this
     /// method unless you are building a p2p protocol client.
@@ -141,6 +192,47 @@ pub trait Decodable2718: Sized {
 
         Ok(tx)
     }
+
+    /// Decode an RLP list of [EIP-2718] envelopes in the network format, such as the payload of
+    /// the devp2p `Transactions` and `PooledTransactions` messages.
+    ///
+    /// Each item is decoded on its own, and a malformed item does not prevent the other items from
+    /// being decoded: the result of every item is returned, in order. An error is only returned
+    /// if the list framing itself is malformed.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    fn network_decode_list(buf: &mut &[u8]) -> Eip2718Result<Vec<Eip2718Result<Self>>> {
+        let h = Header::decode(buf)?;
+        if !h.list {
+            return Err(alloy_rlp::Error::UnexpectedString.into());
+        }
+        if buf.len() < h.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort.into());
+        }
+        let (mut payload, rest) = buf.split_at(h.payload_length);
+
+        let mut items = Vec::new();
+        while !payload.is_empty() {
+            let mut item_payload = payload;
+            let item_header = Header::decode(&mut item_payload)?;
+            let item_len = payload.len() - item_payload.len() + item_header.payload_length;
+            if payload.len() < item_len {
+                return Err(alloy_rlp::Error::InputTooShort.into());
+            }
+            let (mut item, tail) = payload.split_at(item_len);
+            items.push(Self::network_decode(&mut item).and_then(|decoded| {
+                if item.is_empty() {
+                    Ok(decoded)
+                } else {
+                    Err(alloy_rlp::Error::UnexpectedLength.into())
+                }
+            }));
+            payload = tail;
+        }
+
+        *buf = rest;
+        Ok(items)
+    }
 }
 
 /// Encoding trait for [EIP-2718] envelopes. These envelopes wrap a transaction
@@ -155,19 +247,20 @@ pub trait Decodable2718: Sized {
 ///
 /// Implement this trait when you need to make custom TransactionEnvelope
 /// and ReceiptEnvelope types for your network. These types should be enums
-/// over the accepted transaction types.
+/// over the accepted transaction types. The type flag is taken from the
+/// [`Typed2718`] implementation.
 ///
 /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
-pub trait Encodable2718: Sized + Send + Sync + 'static {
+pub trait Encodable2718: Typed2718 + Sized + Send + Sync + 'static {
     /// Return the type flag (if any).
     ///
-    /// This should return `None` for the default (legacy) variant of the
+    /// This returns `None` for the default (legacy) variant of the
     /// envelope.
-    fn type_flag(&self) -> Option<u8>;
-
-    /// True if the envelope is the legacy variant.
-    fn is_legacy(&self) -> bool {
-        matches!(self.type_flag(), None | Some(0))
+    fn type_flag(&self) -> Option<u8> {
+        match self.ty() {
+            LEGACY_TX_TYPE_ID => None,
+            ty => Some(ty),
+        }
     }
 
     /// The length of the 2718 encoded envelope. This is the length of the type
@@ -177,6 +270,9 @@ pub trait Encodable2718: Sized + Send + Sync + 'static {
     /// Encode the transaction according to [EIP-2718] rules. First a 1-byte
     /// type flag in the range 0x0-0x7f, then the body of the transaction.
     ///
+    /// This is the canonical encoding, used by `eth_sendRawTransaction` and
+    /// the block tries.
+    ///
     /// [EIP-2718] inner encodings are unspecified, and produce an opaque
     /// bytestring.
     ///
@@ -222,13 +318,142 @@ pub trait Encodable2718: Sized + Send + Sync + 'static {
 
         self.encode_2718(out);
     }
+
+    /// The length of the envelope in the network format. This is the length of
+    /// the 2718 encoding, plus the RLP string header of non-legacy envelopes.
+    fn network_len(&self) -> usize {
+        let len = self.encode_2718_len();
+        if self.is_legacy() {
+            len
+        } else {
+            Header { list: false, payload_length: len }.length() + len
+        }
+    }
+
+    /// Encode in the network format, and return the encoding.
+    ///
+    /// This is a convenience method for encoding into a vec, and returning the
+    /// vec.
+    fn network_encoded(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.network_len());
+        self.network_encode(&mut out);
+        out
+    }
 }
 
 /// An [EIP-2718] envelope, blanket implemented for types that impl
-/// [`Encodable2718`] and [`Decodable2718`]. This envelope is a wrapper around
+/// [`Typed2718`], [`Encodable2718`] and [`Decodable2718`]. This envelope is a wrapper around
 /// a transaction, or a receipt, or any other type that is differentiated by an
 /// EIP-2718 transaction type.
 ///
 /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
 pub trait Eip2718Envelope: Decodable2718 + Encodable2718 {}
 impl<T> Eip2718Envelope for T where T: Decodable2718 + Encodable2718 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::{Decodable, Encodable};
+
+    const DEPOSIT_TX_TYPE_ID: u8 = 0x7e;
+
+    /// An envelope of a custom network, with a deposit transaction type.
+    #[derive(Debug, PartialEq, Eq)]
+    enum CustomEnvelope {
+        Legacy(u64),
+        Deposit(u64),
+    }
+
+    impl CustomEnvelope {
+        const fn value(&self) -> u64 {
+            match self {
+                Self::Legacy(value) | Self::Deposit(value) => *value,
+            }
+        }
+
+        fn encode_fields(&self, out: &mut dyn BufMut) {
+            Header { list: true, payload_length: self.value().length() }.encode(out);
+            self.value().encode(out);
+        }
+
+        fn decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<u64> {
+            let header = Header::decode(buf)?;
+            if !header.list {
+                return Err(alloy_rlp::Error::UnexpectedString);
+            }
+            u64::decode(buf)
+        }
+    }
+
+    impl Typed2718 for CustomEnvelope {
+        fn ty(&self) -> u8 {
+            match self {
+                Self::Legacy(_) => LEGACY_TX_TYPE_ID,
+                Self::Deposit(_) => DEPOSIT_TX_TYPE_ID,
+            }
+        }
+    }
+
+    impl Encodable2718 for CustomEnvelope {
+        fn encode_2718_len(&self) -> usize {
+            let payload_length = self.value().length();
+            Header { list: true, payload_length }.length()
+                + payload_length
+                + !self.is_legacy() as usize
+        }
+
+        fn encode_2718(&self, out: &mut dyn BufMut) {
+            if let Some(ty) = self.type_flag() {
+                out.put_u8(ty);
+            }
+            self.encode_fields(out);
+        }
+    }
+
+    impl Decodable2718 for CustomEnvelope {
+        fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
+            match ty {
+                DEPOSIT_TX_TYPE_ID => Ok(Self::Deposit(Self::decode_fields(buf)?)),
+                ty => Err(Eip2718Error::UnexpectedType(ty)),
+            }
+        }
+
+        fn fallback_decode(buf: &mut &[u8]) -> Eip2718Result<Self> {
+            Ok(Self::Legacy(Self::decode_fields(buf)?))
+        }
+    }
+
+    #[test]
+    fn custom_envelope_roundtrip() {
+        let legacy = CustomEnvelope::Legacy(1);
+        assert!(legacy.is_legacy());
+        assert_eq!(legacy.type_flag(), None);
+        assert_eq!(legacy.encoded_2718(), [0xc1, 0x01]);
+        assert_eq!(legacy.network_encoded(), legacy.encoded_2718());
+
+        let deposit = CustomEnvelope::Deposit(1);
+        assert!(deposit.is_type(DEPOSIT_TX_TYPE_ID));
+        assert_eq!(deposit.type_flag(), Some(DEPOSIT_TX_TYPE_ID));
+        assert_eq!(deposit.encoded_2718(), [0x7e, 0xc1, 0x01]);
+        assert_eq!(deposit.network_encoded(), [0x83, 0x7e, 0xc1, 0x01]);
+
+        for envelope in [legacy, deposit] {
+            let canonical = envelope.encoded_2718();
+            assert_eq!(canonical.len(), envelope.encode_2718_len());
+            assert_eq!(CustomEnvelope::decode_2718_exact(&canonical).unwrap(), envelope);
+
+            let network = envelope.network_encoded();
+            assert_eq!(network.len(), envelope.network_len());
+            assert_eq!(CustomEnvelope::network_decode(&mut network.as_slice()).unwrap(), envelope);
+        }
+    }
+
+    #[test]
+    fn decode_2718_exact_rejects_trailing_bytes() {
+        assert!(CustomEnvelope::decode_2718_exact(&[0x7e, 0xc1, 0x01, 0x00]).is_err());
+        assert!(matches!(
+            CustomEnvelope::decode_2718_exact(&[0x02, 0xc1, 0x01]),
+            Err(Eip2718Error::UnexpectedType(0x02))
+        ));
+    }
+}
@@ -14,10 +14,8 @@ use core::{mem, ops::Deref};
 /// A list of addresses and storage keys that the transaction plans to access.
 /// Accesses outside the list are possible, but become more expensive.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, RlpDecodable, RlpEncodable)]
-#[cfg_attr(
-    any(test, feature = "arbitrary"),
-    derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
-)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct AccessListItem {
@@ -25,7 +23,7 @@ pub struct AccessListItem {
     pub address: Address,
     /// Keys of storage that would be loaded at the start of execution
     #[cfg_attr(
-        any(test, feature = "arbitrary"),
+        any(test, feature = "proptest"),
         proptest(
             strategy = "proptest::collection::vec(proptest::arbitrary::any::<B256>(), 0..=20)"
         )
@@ -45,14 +43,12 @@ impl AccessListItem {
 
 /// AccessList as defined in EIP-2930
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, RlpDecodableWrapper, RlpEncodableWrapper)]
-#[cfg_attr(
-    any(test, feature = "arbitrary"),
-    derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
-)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AccessList(
     #[cfg_attr(
-        any(test, feature = "arbitrary"),
+        any(test, feature = "proptest"),
         proptest(
             strategy = "proptest::collection::vec(proptest::arbitrary::any::<AccessListItem>(), 0..=20)"
         )
@@ -163,7 +159,8 @@ pub struct AccessListWithGasUsed {
     /// List with accounts accessed during transaction.
     pub access_list: AccessList,
     /// Estimated gas used with access list.
-    pub gas_used: U256,
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub gas_used: u128,
 }
 
 #[cfg(all(test, feature = "serde"))]
@@ -204,7 +201,7 @@ mod tests {
                 AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
                 AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
             ]),
-            gas_used: U256::from(100),
+            gas_used: 100,
         };
         let json = serde_json::to_string(&list).unwrap();
         let list2 = serde_json::from_str::<AccessListWithGasUsed>(&json).unwrap();
@@ -2,10 +2,57 @@
 //!
 //! See also [EIP-2935](https://eips.ethereum.org/EIPS/eip-2935): Serve historical block hashes from state.
 
-use alloy_primitives::{address, bytes, Address, Bytes};
+use alloy_primitives::{address, bytes, Address, BlockNumber, Bytes, U256};
 
 /// The address for the EIP-2935 history storage contract.
 pub const HISTORY_STORAGE_ADDRESS: Address = address!("0aae40965e6800cd9b1f4b05ff21581047e3f91e");
 
 /// The code for the EIP-2935 history storage contract.
 pub static HISTORY_STORAGE_CODE: Bytes = bytes!("3373fffffffffffffffffffffffffffffffffffffffe1460575767ffffffffffffffff5f3511605357600143035f3511604b575f35612000014311604b57611fff5f3516545f5260205ff35b5f5f5260205ff35b5f5ffd5b5f35611fff60014303165500");
+
+/// The number of recent block hashes served by the EIP-2935 history storage contract, which is
+/// also the length of its ring buffer.
+pub const HISTORY_SERVE_WINDOW: u64 = 8192;
+
+/// The number of recent block hashes served by the `BLOCKHASH` opcode.
+pub const BLOCKHASH_SERVE_WINDOW: u64 = 256;
+
+/// Returns the storage slot of the EIP-2935 history storage contract holding the hash of the block
+/// with the given number.
+///
+/// The slot is only guaranteed to hold the hash of this block if the block is within the
+/// [`HISTORY_SERVE_WINDOW`], see [`is_served`].
+pub const fn block_hash_slot(number: BlockNumber) -> U256 {
+    U256::from_limbs([number % HISTORY_SERVE_WINDOW, 0, 0, 0])
+}
+
+/// Returns `true` if the hash of the block with the given number is served by the EIP-2935
+/// history storage contract in the state of the block with the number `current`.
+///
+/// This is the case for the [`HISTORY_SERVE_WINDOW`] blocks preceding `current`, provided these
+/// blocks were produced after the contract was activated.
+pub const fn is_served(current: BlockNumber, number: BlockNumber) -> bool {
+    number < current && current - number <= HISTORY_SERVE_WINDOW
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_buffer_slots() {
+        assert_eq!(block_hash_slot(0), U256::ZERO);
+        assert_eq!(block_hash_slot(8191), U256::from(8191));
+        assert_eq!(block_hash_slot(8192), U256::ZERO);
+        assert_eq!(block_hash_slot(20_000_123), U256::from(20_000_123 % 8192));
+    }
+
+    #[test]
+    fn serve_window() {
+        assert!(is_served(100, 99));
+        assert!(is_served(10_000, 10_000 - HISTORY_SERVE_WINDOW));
+        assert!(!is_served(10_000, 10_000 - HISTORY_SERVE_WINDOW - 1));
+        assert!(!is_served(100, 100));
+        assert!(!is_served(100, 101));
+    }
+}
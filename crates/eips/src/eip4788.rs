@@ -2,7 +2,7 @@
 //!
 //! [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
 
-use alloy_primitives::{address, bytes, Address, Bytes};
+use alloy_primitives::{address, bytes, Address, Bytes, U256};
 
 /// The caller to be used when calling the EIP-4788 beacon roots contract at the beginning of the
 /// block.
@@ -13,3 +13,44 @@ pub const BEACON_ROOTS_ADDRESS: Address = address!("000F3df6D732807Ef1319fB7B8bB
 
 /// The code for the EIP-4788 beacon roots contract.
 pub static BEACON_ROOTS_CODE: Bytes = bytes!("3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f35801560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b62001fff42064281555f359062001fff015500");
+
+/// The length of the ring buffers storing the timestamps and the parent beacon block roots in the
+/// EIP-4788 beacon roots contract.
+pub const HISTORY_BUFFER_LENGTH: u64 = 8191;
+
+/// Returns the storage slot of the EIP-4788 beacon roots contract holding the timestamp of the
+/// block stored at the ring buffer index of the given timestamp.
+///
+/// The parent beacon block root stored at [`beacon_root_slot`] belongs to the given timestamp
+/// only if this slot holds the same timestamp, otherwise it was overwritten by a later block.
+pub const fn timestamp_slot(timestamp: u64) -> U256 {
+    U256::from_limbs([timestamp % HISTORY_BUFFER_LENGTH, 0, 0, 0])
+}
+
+/// Returns the storage slot of the EIP-4788 beacon roots contract holding the parent beacon block
+/// root of the block with the given timestamp.
+pub const fn beacon_root_slot(timestamp: u64) -> U256 {
+    U256::from_limbs([timestamp % HISTORY_BUFFER_LENGTH + HISTORY_BUFFER_LENGTH, 0, 0, 0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_buffer_slots() {
+        assert_eq!(timestamp_slot(0), U256::ZERO);
+        assert_eq!(beacon_root_slot(0), U256::from(HISTORY_BUFFER_LENGTH));
+
+        let timestamp = 1_710_338_135;
+        assert_eq!(timestamp_slot(timestamp), U256::from(timestamp % 8191));
+        assert_eq!(beacon_root_slot(timestamp), U256::from(timestamp % 8191 + 8191));
+
+        // Timestamps a whole buffer apart share the same slots.
+        assert_eq!(timestamp_slot(timestamp), timestamp_slot(timestamp + HISTORY_BUFFER_LENGTH));
+        assert_eq!(
+            beacon_root_slot(timestamp),
+            beacon_root_slot(timestamp + HISTORY_BUFFER_LENGTH)
+        );
+    }
+}
@@ -5,9 +5,7 @@ use c_kzg::{KzgCommitment, KzgProof};
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-use crate::eip4844::{
-    utils::WholeFe, BYTES_PER_BLOB, FIELD_ELEMENTS_PER_BLOB, MAX_BLOBS_PER_BLOCK,
-};
+use crate::eip4844::{utils::WholeFe, BlobParams, BYTES_PER_BLOB, FIELD_ELEMENTS_PER_BLOB};
 
 #[cfg(feature = "kzg")]
 use crate::eip4844::env_settings::EnvKzgSettings;
@@ -185,17 +183,35 @@ pub trait SidecarCoder {
 /// - The first byte of every 32-byte word.
 /// - The right padding on the header word containing the data length.
 /// - Any right padding on the last word for each piece of data.
+///
+/// When decoding, data longer than the blobs of a block can hold is rejected. The bound follows
+/// the [`BlobParams`] of the coder, which default to Cancun's.
 #[derive(Clone, Copy, Debug, Default)]
 #[non_exhaustive]
-pub struct SimpleCoder;
+pub struct SimpleCoder {
+    blob_params: BlobParams,
+}
 
 impl SimpleCoder {
+    /// Creates a coder bounded by the maximum number of blobs per block of `blob_params`.
+    pub const fn with_blob_params(blob_params: BlobParams) -> Self {
+        Self { blob_params }
+    }
+
+    /// Returns the blob parameters bounding the size of decoded data.
+    pub const fn blob_params(&self) -> BlobParams {
+        self.blob_params
+    }
+
     /// Decode an some bytes from an iterator of valid FEs.
     ///
     /// Returns `Ok(Some(data))` if there is some data.
     /// Returns `Ok(None)` if there is no data (length prefix is 0).
     /// Returns `Err(())` if there is an error.
-    fn decode_one<'a>(mut fes: impl Iterator<Item = WholeFe<'a>>) -> Result<Option<Vec<u8>>, ()> {
+    fn decode_one<'a>(
+        &self,
+        mut fes: impl Iterator<Item = WholeFe<'a>>,
+    ) -> Result<Option<Vec<u8>>, ()> {
         let first = fes.next().ok_or(())?;
         let mut num_bytes = u64::from_be_bytes(first.as_ref()[1..9].try_into().unwrap()) as usize;
 
@@ -204,8 +220,8 @@ impl SimpleCoder {
             return Ok(None);
         }
 
-        // if there are too many bytes
-        if num_bytes > BYTES_PER_BLOB * MAX_BLOBS_PER_BLOCK {
+        // if there are more bytes than fit in the blobs of a block
+        if num_bytes > BYTES_PER_BLOB * self.blob_params.max_blobs_per_block() {
             return Err(());
         }
 
@@ -250,7 +266,7 @@ impl SidecarCoder for SimpleCoder {
 
         let mut res = Vec::new();
         loop {
-            match Self::decode_one(&mut fes) {
+            match self.decode_one(&mut fes) {
                 Ok(Some(data)) => res.push(data),
                 Ok(None) => break,
                 Err(()) => return None,
@@ -429,9 +445,9 @@ mod tests {
         let mut builder = PartialSidecar::new();
         let data = &[vec![1u8; 32], vec![2u8; 372], vec![3u8; 17], vec![4u8; 5]];
 
-        data.iter().for_each(|data| SimpleCoder.code(&mut builder, data.as_slice()));
+        data.iter().for_each(|data| SimpleCoder::default().code(&mut builder, data.as_slice()));
 
-        let decoded = SimpleCoder.decode_all(builder.blobs()).unwrap();
+        let decoded = SimpleCoder::default().decode_all(builder.blobs()).unwrap();
         assert_eq!(decoded, data);
     }
 
@@ -448,11 +464,23 @@ mod tests {
 
         let mut builder = data.iter().collect::<SidecarBuilder<SimpleCoder>>();
 
-        let expected_fe = data.iter().map(|d| SimpleCoder.required_fe(d)).sum::<usize>();
+        let expected_fe = data.iter().map(|d| SimpleCoder::default().required_fe(d)).sum::<usize>();
         assert_eq!(builder.len(), expected_fe * 32);
 
         // consume 2 more
         builder.ingest(b"hello");
         assert_eq!(builder.len(), expected_fe * 32 + 64);
     }
+
+    #[test]
+    fn decoding_is_bounded_by_blob_params() {
+        // A single piece of zeroes, one byte longer than the blobs of a Cancun block.
+        let len = BYTES_PER_BLOB * BlobParams::CANCUN.max_blobs_per_block() + 1;
+        let mut blobs = vec![Blob::new([0u8; BYTES_PER_BLOB]); 7];
+        blobs[0][1..9].copy_from_slice(&(len as u64).to_be_bytes());
+
+        assert_eq!(SimpleCoder::default().decode_all(&blobs), None);
+        let decoded = SimpleCoder::with_blob_params(BlobParams::PRAGUE).decode_all(&blobs).unwrap();
+        assert_eq!(decoded, [vec![0u8; len]]);
+    }
 }
@@ -1,9 +1,16 @@
-use crate::eip4844::trusted_setup_points::{G1_POINTS, G2_POINTS};
+use crate::eip4844::trusted_setup_points::{
+    BYTES_PER_G1_POINT, BYTES_PER_G2_POINT, G1_POINTS, G2_POINTS,
+};
 use alloc::sync::Arc;
 use c_kzg::KzgSettings;
 use core::hash::{Hash, Hasher};
 
 /// KZG settings.
+///
+/// The default mainnet trusted setup is loaded lazily, once, and shared by all the
+/// [`EnvKzgSettings::Default`] values. Other trusted setups can be loaded from a file, from the
+/// contents of a file or from the raw points, or an already loaded [`KzgSettings`] can be wrapped,
+/// in which case the settings are shared by the clones of the [`EnvKzgSettings::Custom`] value.
 #[derive(Clone, Debug, Default, Eq)]
 pub enum EnvKzgSettings {
     /// Default mainnet trusted setup.
@@ -34,7 +41,110 @@ impl Hash for EnvKzgSettings {
     }
 }
 
+impl From<KzgSettings> for EnvKzgSettings {
+    fn from(settings: KzgSettings) -> Self {
+        Self::Custom(Arc::new(settings))
+    }
+}
+
+impl From<Arc<KzgSettings>> for EnvKzgSettings {
+    fn from(settings: Arc<KzgSettings>) -> Self {
+        Self::Custom(settings)
+    }
+}
+
+/// The settings loaded from trusted setup files, by canonical path, with the modification time
+/// and the length of the file they were loaded from.
+#[cfg(feature = "std")]
+type TrustedSetupCache = std::collections::HashMap<
+    std::path::PathBuf,
+    ((Option<std::time::SystemTime>, u64), Arc<KzgSettings>),
+>;
+
+/// Locks the cache of [`EnvKzgSettings::load_trusted_setup_file`].
+#[cfg(feature = "std")]
+fn trusted_setup_cache() -> std::sync::MutexGuard<'static, TrustedSetupCache> {
+    static CACHE: std::sync::OnceLock<std::sync::Mutex<TrustedSetupCache>> =
+        std::sync::OnceLock::new();
+    CACHE.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner())
+}
+
 impl EnvKzgSettings {
+    /// Returns `true` if these are the default mainnet settings.
+    #[inline]
+    pub const fn is_default(&self) -> bool {
+        matches!(self, Self::Default)
+    }
+
+    /// Loads the settings of a trusted setup from its G1 and G2 points, e.g. embedded in the
+    /// binary.
+    pub fn load_trusted_setup(
+        g1_points: &[[u8; BYTES_PER_G1_POINT]],
+        g2_points: &[[u8; BYTES_PER_G2_POINT]],
+    ) -> Result<Self, c_kzg::Error> {
+        KzgSettings::load_trusted_setup(g1_points, g2_points).map(Into::into)
+    }
+
+    /// Parses the settings of a trusted setup from the contents of a trusted setup file.
+    ///
+    /// See [`load_trusted_setup_file`](Self::load_trusted_setup_file) for the format.
+    pub fn parse_trusted_setup(contents: &str) -> Result<Self, c_kzg::Error> {
+        KzgSettings::parse_kzg_trusted_setup(contents).map(Into::into)
+    }
+
+    /// Loads the settings of a trusted setup from a file.
+    ///
+    /// The file contains the number of G1 points, the number of G2 points, and then each G1 and G2
+    /// point hex-encoded, one per line.
+    ///
+    /// The loaded settings are cached by canonical path: loading the same file again returns
+    /// settings shared with the first load, without reading the file again. A cached entry is
+    /// invalidated when the modification time or the length of the file changes, and the whole
+    /// cache can be cleared with [`clear_trusted_setup_cache`](Self::clear_trusted_setup_cache).
+    ///
+    /// The cache is not locked while the file is read, so concurrent first loads of a file may
+    /// each read it; they still return the same settings.
+    #[cfg(feature = "std")]
+    pub fn load_trusted_setup_file(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, c_kzg::Error> {
+        let io_error = |err: std::io::Error| {
+            c_kzg::Error::InvalidTrustedSetup(format!("failed to read the trusted setup: {err}"))
+        };
+
+        let path = std::fs::canonicalize(path).map_err(io_error)?;
+        let metadata = std::fs::metadata(&path).map_err(io_error)?;
+        let stamp = (metadata.modified().ok(), metadata.len());
+        let cached = |cache: &TrustedSetupCache| {
+            cache
+                .get(&path)
+                .filter(|(cached, _)| stamp.0.is_some() && *cached == stamp)
+                .map(|(_, settings)| Self::Custom(settings.clone()))
+        };
+        if let Some(settings) = cached(&trusted_setup_cache()) {
+            return Ok(settings);
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(io_error)?;
+        let settings = Arc::new(KzgSettings::parse_kzg_trusted_setup(&contents)?);
+        let mut cache = trusted_setup_cache();
+        // Another thread may have loaded the same file in the meantime.
+        if let Some(settings) = cached(&cache) {
+            return Ok(settings);
+        }
+        cache.insert(path, (stamp, settings.clone()));
+        Ok(Self::Custom(settings))
+    }
+
+    /// Clears the cache of [`load_trusted_setup_file`](Self::load_trusted_setup_file), so that
+    /// the next loads read the files again.
+    ///
+    /// Settings already returned are not affected.
+    #[cfg(feature = "std")]
+    pub fn clear_trusted_setup_cache() {
+        trusted_setup_cache().clear();
+    }
+
     /// Returns the KZG settings.
     ///
     /// This will initialize the default settings if it is not already loaded.
@@ -64,3 +174,74 @@ impl EnvKzgSettings {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip4844::Blob;
+    use alloy_primitives::hex;
+    use core::fmt::Write;
+
+    fn trusted_setup_contents() -> String {
+        let mut contents = format!("{}\n{}\n", G1_POINTS.0.len(), G2_POINTS.0.len());
+        for point in G1_POINTS.0.iter().map(hex::encode).chain(G2_POINTS.0.iter().map(hex::encode))
+        {
+            writeln!(contents, "{point}").unwrap();
+        }
+        contents
+    }
+
+    fn commitment(settings: &EnvKzgSettings) -> [u8; 48] {
+        let mut blob = Blob::repeat_byte(0);
+        blob[31] = 1;
+        let blob = c_kzg::Blob::new(blob.0);
+        c_kzg::KzgCommitment::blob_to_kzg_commitment(&blob, settings.get())
+            .unwrap()
+            .to_bytes()
+            .into_inner()
+    }
+
+    #[test]
+    fn load_custom_trusted_setups() {
+        let default = EnvKzgSettings::default();
+        assert!(default.is_default());
+        let expected = commitment(&default);
+
+        let embedded = EnvKzgSettings::load_trusted_setup(&G1_POINTS.0, &G2_POINTS.0).unwrap();
+        assert!(!embedded.is_default());
+        assert_eq!(embedded, embedded.clone());
+        assert_ne!(embedded, default);
+        assert_eq!(commitment(&embedded), expected);
+
+        let contents = trusted_setup_contents();
+        let parsed = EnvKzgSettings::parse_trusted_setup(&contents).unwrap();
+        assert_eq!(commitment(&parsed), expected);
+        assert!(EnvKzgSettings::parse_trusted_setup("4096\n65\n").is_err());
+
+        let path = std::env::temp_dir()
+            .join(format!("alloy-eips-trusted-setup-{}.txt", std::process::id()));
+        std::fs::write(&path, &contents).unwrap();
+        let loaded = EnvKzgSettings::load_trusted_setup_file(&path).unwrap();
+        // Cached: both loads share the same settings.
+        assert_eq!(EnvKzgSettings::load_trusted_setup_file(&path).unwrap(), loaded);
+        // The cache is keyed by canonical path.
+        let dir = path.parent().unwrap();
+        let relative = dir.join(".").join(path.file_name().unwrap());
+        assert_eq!(EnvKzgSettings::load_trusted_setup_file(relative).unwrap(), loaded);
+        assert_eq!(commitment(&loaded), expected);
+
+        // Modifying the file invalidates the cached settings.
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        let modified = file.metadata().unwrap().modified().unwrap();
+        file.set_modified(modified + std::time::Duration::from_secs(1)).unwrap();
+        let reloaded = EnvKzgSettings::load_trusted_setup_file(&path).unwrap();
+        assert_ne!(reloaded, loaded);
+        assert_eq!(commitment(&reloaded), expected);
+
+        EnvKzgSettings::clear_trusted_setup_cache();
+        assert_ne!(EnvKzgSettings::load_trusted_setup_file(&path).unwrap(), reloaded);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(EnvKzgSettings::load_trusted_setup_file(path.with_extension("missing")).is_err());
+    }
+}
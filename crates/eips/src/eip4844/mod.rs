@@ -58,7 +58,9 @@ pub const MAX_DATA_GAS_PER_BLOCK: u64 = 786_432u64; // 0xC0000 = 6 * 0x20000
 /// Target data gas for data blobs in a single block.
 pub const TARGET_DATA_GAS_PER_BLOCK: u64 = 393_216u64; // 0x60000 = 3 * 0x20000
 
-/// Maximum number of data blobs in a single block.
+/// Maximum number of data blobs in a single block, in Cancun.
+///
+/// See [`BlobParams`] for the later forks.
 pub const MAX_BLOBS_PER_BLOCK: usize = (MAX_DATA_GAS_PER_BLOCK / DATA_GAS_PER_BLOB) as usize; // 786432 / 131072  = 6
 
 /// Target number of data blobs in a single block.
@@ -70,6 +72,94 @@ pub const BLOB_GASPRICE_UPDATE_FRACTION: u128 = 3_338_477u128; // 3338477
 /// Minimum gas price for a data blob
 pub const BLOB_TX_MIN_BLOB_GASPRICE: u128 = 1u128;
 
+/// The blob parameters of a fork: the target and maximum number of blobs per block, and the
+/// update fraction of the blob fee.
+///
+/// These change across forks, e.g. with [EIP-7691] in Prague, and may differ on other chains. They
+/// are set per fork by the blob schedule of the chain configuration, see [EIP-7840], whose entries
+/// this serializes as.
+///
+/// [EIP-7691]: https://eips.ethereum.org/EIPS/eip-7691
+/// [EIP-7840]: https://eips.ethereum.org/EIPS/eip-7840
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlobParams {
+    /// The target number of blobs per block.
+    #[cfg_attr(feature = "serde", serde(rename = "target"))]
+    pub target_blob_count: u64,
+    /// The maximum number of blobs per block.
+    #[cfg_attr(feature = "serde", serde(rename = "max"))]
+    pub max_blob_count: u64,
+    /// The update fraction of the blob fee, which determines its maximum rate of change.
+    #[cfg_attr(feature = "serde", serde(rename = "baseFeeUpdateFraction"))]
+    pub update_fraction: u128,
+}
+
+impl BlobParams {
+    /// The blob parameters of Cancun.
+    pub const CANCUN: Self = Self {
+        target_blob_count: TARGET_BLOBS_PER_BLOCK,
+        max_blob_count: MAX_BLOBS_PER_BLOCK as u64,
+        update_fraction: BLOB_GASPRICE_UPDATE_FRACTION,
+    };
+
+    /// The blob parameters of Prague, raised by [EIP-7691].
+    ///
+    /// [EIP-7691]: https://eips.ethereum.org/EIPS/eip-7691
+    pub const PRAGUE: Self =
+        Self { target_blob_count: 6, max_blob_count: 9, update_fraction: 5_007_716 };
+
+    /// The blob parameters of the latest fork, used as bounds where the fork is not known.
+    pub const LATEST: Self = Self::PRAGUE;
+
+    /// Returns the maximum number of blobs per block, as a `usize`.
+    #[inline]
+    pub const fn max_blobs_per_block(&self) -> usize {
+        self.max_blob_count as usize
+    }
+
+    /// Returns the target blob gas per block.
+    #[inline]
+    pub const fn target_blob_gas_per_block(&self) -> u64 {
+        self.target_blob_count * DATA_GAS_PER_BLOB
+    }
+
+    /// Returns the maximum blob gas per block.
+    #[inline]
+    pub const fn max_blob_gas_per_block(&self) -> u64 {
+        self.max_blob_count * DATA_GAS_PER_BLOB
+    }
+
+    /// Calculates the `excess_blob_gas` of a block from the `excess_blob_gas` and
+    /// `blob_gas_used` of its parent, against the target of these parameters.
+    ///
+    /// See also [`calc_excess_blob_gas`], which uses the Cancun parameters.
+    #[inline]
+    pub const fn next_block_excess_blob_gas(
+        &self,
+        parent_excess_blob_gas: u128,
+        parent_blob_gas_used: u128,
+    ) -> u128 {
+        (parent_excess_blob_gas + parent_blob_gas_used)
+            .saturating_sub(self.target_blob_gas_per_block() as u128)
+    }
+
+    /// Calculates the blob gas price from the excess blob gas of a block, with the update
+    /// fraction of these parameters.
+    ///
+    /// See also [`calc_blob_gasprice`], which uses the Cancun parameters.
+    #[inline]
+    pub fn calc_blob_fee(&self, excess_blob_gas: u128) -> u128 {
+        fake_exponential(BLOB_TX_MIN_BLOB_GASPRICE, excess_blob_gas, self.update_fraction)
+    }
+}
+
+impl Default for BlobParams {
+    fn default() -> Self {
+        Self::CANCUN
+    }
+}
+
 /// Commitment version of a KZG commitment
 pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
 
@@ -102,7 +192,10 @@ pub fn kzg_to_versioned_hash(commitment: &[u8]) -> B256 {
     B256::new(res.into())
 }
 
-/// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used` and `excess_blob_gas`.
+/// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used` and `excess_blob_gas`,
+/// with the Cancun target.
+///
+/// See [`BlobParams::next_block_excess_blob_gas`] for the later forks.
 ///
 /// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
 /// (`calc_excess_blob_gas`).
@@ -115,7 +208,10 @@ pub const fn calc_excess_blob_gas(
         .saturating_sub(TARGET_DATA_GAS_PER_BLOCK as u128)
 }
 
-/// Calculates the blob gas price from the header's excess blob gas field.
+/// Calculates the blob gas price from the header's excess blob gas field, with the Cancun update
+/// fraction.
+///
+/// See [`BlobParams::calc_blob_fee`] for the later forks.
 ///
 /// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
 /// (`get_blob_gasprice`).
@@ -190,9 +286,42 @@ mod tests {
         ] {
             let actual = calc_excess_blob_gas(excess as u128, (blobs * DATA_GAS_PER_BLOB) as u128);
             assert_eq!(actual, expected as u128, "test: {t:?}");
+            let actual = BlobParams::CANCUN
+                .next_block_excess_blob_gas(excess as u128, (blobs * DATA_GAS_PER_BLOB) as u128);
+            assert_eq!(actual, expected as u128, "test: {t:?}");
+        }
+    }
+
+    #[test]
+    fn cancun_blob_fee() {
+        for excess in [0, 1, 10_000_000, 100_000_000] {
+            assert_eq!(BlobParams::CANCUN.calc_blob_fee(excess), calc_blob_gasprice(excess));
         }
     }
 
+    #[test]
+    fn prague_blob_params() {
+        let params = BlobParams::PRAGUE;
+        assert_eq!(params.max_blob_gas_per_block(), 9 * DATA_GAS_PER_BLOB);
+        // A full Cancun block is at the Prague target, so the excess does not grow.
+        assert_eq!(params.next_block_excess_blob_gas(0, MAX_DATA_GAS_PER_BLOCK as u128), 0);
+        assert_eq!(
+            params.next_block_excess_blob_gas(0, params.max_blob_gas_per_block() as u128),
+            3 * DATA_GAS_PER_BLOB as u128
+        );
+        // The fee rises slower than with the Cancun update fraction.
+        let excess = 10_000_000;
+        assert!(params.calc_blob_fee(excess) < BlobParams::CANCUN.calc_blob_fee(excess));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_blob_params() {
+        let json = r#"{"target":6,"max":9,"baseFeeUpdateFraction":5007716}"#;
+        assert_eq!(serde_json::from_str::<BlobParams>(json).unwrap(), BlobParams::PRAGUE);
+        assert_eq!(serde_json::to_string(&BlobParams::PRAGUE).unwrap(), json);
+    }
+
     // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L60
     #[test]
     fn test_calc_blob_fee() {
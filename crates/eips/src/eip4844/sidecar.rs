@@ -8,8 +8,8 @@ use crate::eip4844::{
 use alloy_primitives::{bytes::BufMut, B256};
 use alloy_rlp::{Decodable, Encodable};
 
-#[cfg(any(test, feature = "arbitrary"))]
-use crate::eip4844::MAX_BLOBS_PER_BLOCK;
+#[cfg(any(test, feature = "arbitrary", feature = "proptest"))]
+use crate::eip4844::BlobParams;
 
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
@@ -20,7 +20,6 @@ use alloc::vec::Vec;
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[repr(C)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(any(test, feature = "arbitrary"), derive(proptest_derive::Arbitrary))]
 #[doc(alias = "BlobTxSidecar")]
 pub struct BlobTransactionSidecar {
     /// The blob data.
@@ -38,7 +37,7 @@ pub struct BlobTransactionSidecar {
 #[cfg(any(test, feature = "arbitrary"))]
 impl<'a> arbitrary::Arbitrary<'a> for BlobTransactionSidecar {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let num_blobs = u.int_in_range(1..=MAX_BLOBS_PER_BLOCK)?;
+        let num_blobs = u.int_in_range(1..=BlobParams::default().max_blobs_per_block())?;
         let mut blobs = Vec::with_capacity(num_blobs);
         for _ in 0..num_blobs {
             blobs.push(Blob::arbitrary(u)?);
@@ -55,6 +54,46 @@ impl<'a> arbitrary::Arbitrary<'a> for BlobTransactionSidecar {
     }
 }
 
+/// Generates sidecars of 1 to [`BlobParams::max_blob_count`] blobs, with as many commitments and
+/// proofs. The parameters default to the Cancun ones.
+///
+/// The blobs are canonical, i.e. each of their field elements is lower than the BLS modulus, and
+/// are expanded from random seeds to keep the generation cheap. The commitments and proofs are
+/// random.
+#[cfg(any(test, feature = "proptest"))]
+impl proptest::arbitrary::Arbitrary for BlobTransactionSidecar {
+    type Parameters = BlobParams;
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        use crate::eip4844::FIELD_ELEMENT_BYTES;
+        use alloy_primitives::keccak256;
+        use proptest::{arbitrary::any, collection::vec, strategy::Strategy};
+
+        let blob = any::<B256>().prop_map(|seed| {
+            let mut blob = Blob::ZERO;
+            for (i, element) in blob.chunks_exact_mut(FIELD_ELEMENT_BYTES as usize).enumerate() {
+                element.copy_from_slice(
+                    keccak256([seed.as_slice(), &i.to_be_bytes()].concat()).as_slice(),
+                );
+                // Clearing the most significant byte keeps the field element below the modulus.
+                element[0] = 0;
+            }
+            blob
+        });
+        (1..=params.max_blobs_per_block())
+            .prop_flat_map(move |num_blobs| {
+                (
+                    vec(blob.clone(), num_blobs),
+                    vec(any::<Bytes48>(), num_blobs),
+                    vec(any::<Bytes48>(), num_blobs),
+                )
+            })
+            .prop_map(|(blobs, commitments, proofs)| Self { blobs, commitments, proofs })
+            .boxed()
+    }
+}
+
 impl BlobTransactionSidecar {
     /// Constructs a new [BlobTransactionSidecar] from a set of blobs, commitments, and proofs.
     pub const fn new(blobs: Vec<Blob>, commitments: Vec<Bytes48>, proofs: Vec<Bytes48>) -> Self {
@@ -210,6 +249,13 @@ impl Decodable for BlobTransactionSidecar {
     }
 }
 
+impl crate::InMemorySize for BlobTransactionSidecar {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.blobs.heap_size() + self.commitments.heap_size() + self.proofs.heap_size()
+    }
+}
+
 // Helper function to deserialize boxed blobs
 #[cfg(all(debug_assertions, feature = "serde"))]
 fn deserialize_blobs<'de, D>(deserializer: D) -> Result<Vec<Blob>, D::Error>
@@ -317,6 +363,21 @@ mod tests {
         assert_eq!(blob, deserialized);
     }
 
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(8))]
+
+        #[test]
+        fn proptest_sidecar_lengths(sidecar in proptest::arbitrary::any::<BlobTransactionSidecar>()) {
+            proptest::prop_assert!((1..=BlobParams::CANCUN.max_blobs_per_block()).contains(&sidecar.blobs.len()));
+            proptest::prop_assert_eq!(sidecar.commitments.len(), sidecar.blobs.len());
+            proptest::prop_assert_eq!(sidecar.proofs.len(), sidecar.blobs.len());
+
+            let mut encoded = Vec::new();
+            sidecar.encode(&mut encoded);
+            proptest::prop_assert_eq!(BlobTransactionSidecar::decode(&mut &encoded[..]).unwrap(), sidecar);
+        }
+    }
+
     #[test]
     fn test_arbitrary_blob() {
         let mut unstructured = arbitrary::Unstructured::new(b"unstructured blob");
@@ -4,18 +4,65 @@
 
 #![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
 
+use alloc::{collections::BTreeMap, vec::Vec};
 use alloy_primitives::{Address, U256};
 use alloy_rlp::{RlpDecodable, RlpEncodable};
 
 /// Multiplier for converting gwei to wei.
 pub const GWEI_TO_WEI: u64 = 1_000_000_000;
 
+/// Converts an amount in gwei to wei.
+#[inline]
+pub fn gwei_to_wei(gwei: u64) -> U256 {
+    U256::from(gwei) * U256::from(GWEI_TO_WEI)
+}
+
+/// Converts an amount in wei to gwei.
+///
+/// Returns `None` if the amount is not a whole number of gwei, or does not fit in a `u64` of gwei.
+#[inline]
+pub fn wei_to_gwei(wei: U256) -> Option<u64> {
+    let (gwei, rem) = wei.div_rem(U256::from(GWEI_TO_WEI));
+    if rem.is_zero() {
+        gwei.try_into().ok()
+    } else {
+        None
+    }
+}
+
+/// Groups the withdrawals by validator index, keeping their order within each group.
+pub fn group_by_validator<'a>(
+    withdrawals: impl IntoIterator<Item = &'a Withdrawal>,
+) -> BTreeMap<u64, Vec<Withdrawal>> {
+    let mut groups = BTreeMap::<_, Vec<_>>::new();
+    for withdrawal in withdrawals {
+        groups.entry(withdrawal.validator_index).or_default().push(*withdrawal);
+    }
+    groups
+}
+
+/// Sums the amounts of the withdrawals by recipient address, in wei.
+///
+/// The sums are in wei, and cannot overflow, unlike sums of the `u64` amounts in gwei.
+pub fn sum_by_address<'a>(
+    withdrawals: impl IntoIterator<Item = &'a Withdrawal>,
+) -> BTreeMap<Address, U256> {
+    let mut sums = BTreeMap::<_, U256>::new();
+    for withdrawal in withdrawals {
+        *sums.entry(withdrawal.address).or_default() += withdrawal.amount_wei();
+    }
+    sums
+}
+
+/// Sums the amounts of the withdrawals, in wei.
+pub fn total_amount_wei<'a>(withdrawals: impl IntoIterator<Item = &'a Withdrawal>) -> U256 {
+    withdrawals.into_iter().map(Withdrawal::amount_wei).sum()
+}
+
 /// Withdrawal represents a validator withdrawal from the consensus layer.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
-#[cfg_attr(
-    any(test, feature = "arbitrary"),
-    derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
-)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "ssz", derive(ssz_derive::Encode, ssz_derive::Decode))]
 pub struct Withdrawal {
@@ -38,15 +85,47 @@ pub struct Withdrawal {
 impl Withdrawal {
     /// Return the withdrawal amount in wei.
     pub fn amount_wei(&self) -> U256 {
-        U256::from(self.amount) * U256::from(GWEI_TO_WEI)
+        gwei_to_wei(self.amount)
     }
 }
 
-#[cfg(all(test, feature = "serde"))]
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn gwei_wei_conversion() {
+        assert_eq!(gwei_to_wei(u64::MAX), U256::from(u64::MAX) * U256::from(GWEI_TO_WEI));
+        assert_eq!(wei_to_gwei(gwei_to_wei(u64::MAX)), Some(u64::MAX));
+        assert_eq!(wei_to_gwei(gwei_to_wei(u64::MAX) + U256::from(GWEI_TO_WEI)), None);
+        assert_eq!(wei_to_gwei(U256::from(1)), None);
+        assert_eq!(wei_to_gwei(U256::ZERO), Some(0));
+    }
+
+    #[test]
+    fn group_and_sum_withdrawals() {
+        let withdrawal = |index, validator_index, address: u8, amount| Withdrawal {
+            index,
+            validator_index,
+            address: Address::with_last_byte(address),
+            amount,
+        };
+        let withdrawals =
+            [withdrawal(0, 7, 1, u64::MAX), withdrawal(1, 8, 2, 5), withdrawal(2, 7, 1, u64::MAX)];
+
+        let groups = group_by_validator(&withdrawals);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&7], [withdrawals[0], withdrawals[2]]);
+        assert_eq!(groups[&8], [withdrawals[1]]);
+
+        let sums = sum_by_address(&withdrawals);
+        assert_eq!(sums[&Address::with_last_byte(1)], gwei_to_wei(u64::MAX) * U256::from(2));
+        assert_eq!(sums[&Address::with_last_byte(2)], gwei_to_wei(5));
+        assert_eq!(total_amount_wei(&withdrawals), sums.values().sum::<U256>());
+    }
+
     // <https://github.com/paradigmxyz/reth/issues/1614>
+    #[cfg(feature = "serde")]
     #[test]
     fn test_withdrawal_serde_roundtrip() {
         let input = r#"[{"index":"0x0","validatorIndex":"0x0","address":"0x0000000000000000000000000000000000001000","amount":"0x1"},{"index":"0x1","validatorIndex":"0x1","address":"0x0000000000000000000000000000000000001001","amount":"0x1"},{"index":"0x2","validatorIndex":"0x2","address":"0x0000000000000000000000000000000000001002","amount":"0x1"},{"index":"0x3","validatorIndex":"0x3","address":"0x0000000000000000000000000000000000001003","amount":"0x1"},{"index":"0x4","validatorIndex":"0x4","address":"0x0000000000000000000000000000000000001004","amount":"0x1"},{"index":"0x5","validatorIndex":"0x5","address":"0x0000000000000000000000000000000000001005","amount":"0x1"},{"index":"0x6","validatorIndex":"0x6","address":"0x0000000000000000000000000000000000001006","amount":"0x1"},{"index":"0x7","validatorIndex":"0x7","address":"0x0000000000000000000000000000000000001007","amount":"0x1"},{"index":"0x8","validatorIndex":"0x8","address":"0x0000000000000000000000000000000000001008","amount":"0x1"},{"index":"0x9","validatorIndex":"0x9","address":"0x0000000000000000000000000000000000001009","amount":"0x1"},{"index":"0xa","validatorIndex":"0xa","address":"0x000000000000000000000000000000000000100a","amount":"0x1"},{"index":"0xb","validatorIndex":"0xb","address":"0x000000000000000000000000000000000000100b","amount":"0x1"},{"index":"0xc","validatorIndex":"0xc","address":"0x000000000000000000000000000000000000100c","amount":"0x1"},{"index":"0xd","validatorIndex":"0xd","address":"0x000000000000000000000000000000000000100d","amount":"0x1"},{"index":"0xe","validatorIndex":"0xe","address":"0x000000000000000000000000000000000000100e","amount":"0x1"},{"index":"0xf","validatorIndex":"0xf","address":"0x000000000000000000000000000000000000100f","amount":"0x1"}]"#;
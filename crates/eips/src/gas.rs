@@ -0,0 +1,91 @@
+//! Conversions of gas amounts and fees between integer types.
+//!
+//! The consensus and RPC types represent amounts of gas, such as gas limits and used gas, and
+//! fees per gas as [`u128`]. Some external formats use other widths, e.g. [`u64`] for the gas of
+//! execution payloads, or [`U256`] for their base fee. [`GasCast`] converts between them without
+//! silently truncating values, and [`fee`] computes fees without overflowing.
+
+use alloy_primitives::{
+    ruint::{UintTryFrom, UintTryTo},
+    U256,
+};
+
+/// Checked and saturating conversions of gas amounts and fees to other integer types.
+///
+/// This is implemented for all the integer types, primitive or not, that can be converted to a
+/// [`U256`].
+///
+/// # Examples
+///
+/// ```
+/// use alloy_eips::gas::GasCast;
+/// use alloy_primitives::U256;
+///
+/// assert_eq!(30_000_000u128.checked_cast::<u64>(), Some(30_000_000));
+/// assert_eq!(U256::MAX.checked_cast::<u128>(), None);
+/// assert_eq!(U256::MAX.saturating_cast::<u128>(), u128::MAX);
+/// ```
+pub trait GasCast: Sized {
+    /// Converts the value to `T`, returning `None` if it does not fit.
+    fn checked_cast<T>(self) -> Option<T>
+    where
+        U256: UintTryTo<T>;
+
+    /// Converts the value to `T`, saturating at the bounds of `T`.
+    fn saturating_cast<T>(self) -> T
+    where
+        U256: UintTryTo<T>;
+}
+
+impl<V> GasCast for V
+where
+    U256: UintTryFrom<V>,
+{
+    #[inline]
+    fn checked_cast<T>(self) -> Option<T>
+    where
+        U256: UintTryTo<T>,
+    {
+        U256::uint_try_from(self).ok()?.uint_try_to().ok()
+    }
+
+    #[inline]
+    fn saturating_cast<T>(self) -> T
+    where
+        U256: UintTryTo<T>,
+    {
+        U256::saturating_from(self).saturating_to()
+    }
+}
+
+/// Returns the fee paid for the given amount of gas at the given price per gas.
+///
+/// Unlike multiplying [`u128`]s, this cannot overflow.
+#[inline]
+pub fn fee(gas: u128, price_per_gas: u128) -> U256 {
+    U256::from(gas) * U256::from(price_per_gas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{U128, U64};
+
+    #[test]
+    fn casts() {
+        assert_eq!(21_000u64.checked_cast::<u128>(), Some(21_000));
+        assert_eq!(U64::from(21_000).checked_cast::<u128>(), Some(21_000));
+        assert_eq!(u128::MAX.checked_cast::<u64>(), None);
+        assert_eq!(u128::MAX.saturating_cast::<u64>(), u64::MAX);
+        assert_eq!(u128::MAX.saturating_cast::<U64>(), U64::MAX);
+        assert_eq!(U256::from(7).checked_cast::<U128>(), Some(U128::from(7)));
+        assert_eq!((-1i64).checked_cast::<u64>(), None);
+        assert_eq!((-1i64).saturating_cast::<u64>(), 0);
+    }
+
+    #[test]
+    fn fee_does_not_overflow() {
+        assert_eq!(fee(21_000, 10), U256::from(210_000));
+        assert_eq!(fee(u128::MAX, 2), U256::from(u128::MAX) * U256::from(2));
+    }
+}
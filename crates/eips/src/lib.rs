@@ -11,6 +11,10 @@
 #[macro_use]
 extern crate alloc;
 
+// Required by the `Arbitrary` derives of `alloy-primitives`, enabled by the `proptest` feature.
+#[cfg(all(feature = "proptest", not(feature = "arbitrary")))]
+use arbitrary as _;
+
 pub mod eip1559;
 pub use eip1559::calc_next_block_base_fee;
 
@@ -42,3 +46,9 @@ pub mod eip7251;
 pub mod eip7685;
 
 pub mod eip7702;
+
+pub mod gas;
+pub use gas::GasCast;
+
+pub mod size;
+pub use size::InMemorySize;
@@ -0,0 +1,184 @@
+//! In-memory size accounting.
+//!
+//! [`InMemorySize`] reports the memory used by a value, including the heap allocations it owns,
+//! recursively. This lets memory-limited caches and pools budget their entries precisely, instead
+//! of relying on the size heuristics of each type.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{
+    eip2930::{AccessList, AccessListItem},
+    eip4895::Withdrawal,
+    eip6110::DepositRequest,
+    eip7002::WithdrawalRequest,
+    eip7251::ConsolidationRequest,
+};
+use alloy_primitives::{Address, Bloom, Bytes, FixedBytes, Log, LogData, Signature, TxKind, U256};
+use core::mem;
+
+/// The memory used by a value, including the heap allocations it owns.
+///
+/// Implementations only need to report the heap allocations with [`heap_size`], the inline size
+/// of the value being accounted for by [`in_memory_size`]. Collections account for their
+/// capacity, and the heap allocations of their elements.
+///
+/// Shared allocations, such as the buffer of [`Bytes`], are accounted for by each value referring
+/// to them.
+///
+/// [`heap_size`]: InMemorySize::heap_size
+/// [`in_memory_size`]: InMemorySize::in_memory_size
+pub trait InMemorySize {
+    /// Returns the number of bytes allocated on the heap by the value, and by the values it owns.
+    fn heap_size(&self) -> usize;
+
+    /// Returns the total number of bytes used by the value: its inline size, plus its heap
+    /// allocations.
+    #[inline]
+    fn in_memory_size(&self) -> usize
+    where
+        Self: Sized,
+    {
+        mem::size_of::<Self>() + self.heap_size()
+    }
+}
+
+macro_rules! impl_no_heap {
+    ($($t:ty),* $(,)?) => {$(
+        impl InMemorySize for $t {
+            #[inline]
+            fn heap_size(&self) -> usize {
+                0
+            }
+        }
+    )*};
+}
+
+impl_no_heap!(
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    Address,
+    Bloom,
+    U256,
+    TxKind,
+    Signature,
+    Withdrawal,
+    DepositRequest,
+    WithdrawalRequest,
+    ConsolidationRequest,
+);
+
+impl<const N: usize> InMemorySize for FixedBytes<N> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl InMemorySize for Bytes {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl InMemorySize for String {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for Option<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, T::heap_size)
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for Box<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        mem::size_of::<T>() + T::heap_size(self)
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for Vec<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl InMemorySize for LogData {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        mem::size_of_val(self.topics()) + self.data.heap_size()
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for Log<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.data.heap_size()
+    }
+}
+
+impl InMemorySize for AccessListItem {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.storage_keys.heap_size()
+    }
+}
+
+impl InMemorySize for AccessList {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn heap_size_is_recursive() {
+        let item = || {
+            let mut storage_keys = Vec::with_capacity(4);
+            storage_keys.push(B256::ZERO);
+            AccessListItem { address: Address::ZERO, storage_keys }
+        };
+        assert_eq!(item().heap_size(), 4 * 32);
+
+        let list = AccessList(vec![item(), item()]);
+        assert_eq!(list.heap_size(), 2 * mem::size_of::<AccessListItem>() + 2 * 4 * 32);
+        assert_eq!(list.in_memory_size(), mem::size_of::<AccessList>() + list.heap_size());
+
+        assert_eq!(Some(Bytes::from_static(&[1, 2, 3])).heap_size(), 3);
+        assert_eq!(None::<Bytes>.heap_size(), 0);
+        assert_eq!(Box::new(U256::ZERO).heap_size(), 32);
+    }
+
+    #[test]
+    fn log_heap_size() {
+        let log = Log::new_unchecked(
+            Address::ZERO,
+            vec![B256::ZERO, B256::ZERO],
+            Bytes::from_static(&[0; 10]),
+        );
+        assert_eq!(log.heap_size(), 2 * 32 + 10);
+    }
+}
@@ -11,7 +11,8 @@
 
 extern crate alloc;
 
-use alloc::collections::BTreeMap;
+use alloc::{collections::BTreeMap, string::String};
+use alloy_eips::eip4844::BlobParams;
 use alloy_primitives::{Address, Bytes, B256, U256};
 use alloy_serde::{storage::deserialize_storage_map, ttd::deserialize_json_ttd_opt, OtherFields};
 use serde::{Deserialize, Serialize};
@@ -426,6 +427,14 @@ pub struct ChainConfig {
     /// The deposit contract address
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub deposit_contract_address: Option<Address>,
+
+    /// The blob parameters of each fork, keyed by the lowercase fork name, see [EIP-7840].
+    ///
+    /// Forks missing from the schedule use their mainnet parameters.
+    ///
+    /// [EIP-7840]: https://eips.ethereum.org/EIPS/eip-7840
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub blob_schedule: BTreeMap<String, BlobParams>,
 }
 
 impl ChainConfig {
@@ -508,6 +517,21 @@ impl ChainConfig {
             && self.is_active_at_timestamp(self.cancun_time, timestamp)
     }
 
+    /// Returns the blob parameters of the fork active at the specified timestamp, or `None` before
+    /// Cancun.
+    ///
+    /// The parameters are read from the [blob schedule](Self::blob_schedule), falling back to the
+    /// mainnet parameters of the fork.
+    pub fn blob_params_at_timestamp(&self, timestamp: u64) -> Option<BlobParams> {
+        [
+            ("prague", self.prague_time, BlobParams::PRAGUE),
+            ("cancun", self.cancun_time, BlobParams::CANCUN),
+        ]
+        .into_iter()
+        .find(|(_, time, _)| self.is_active_at_timestamp(*time, timestamp))
+        .map(|(fork, _, params)| self.blob_schedule.get(fork).copied().unwrap_or(params))
+    }
+
     // Private function handling the comparison logic for block numbers
     fn is_active_at_block(&self, config_block: Option<u64>, block: u64) -> bool {
         config_block.map_or(false, |cb| cb <= block)
@@ -551,6 +575,7 @@ impl Default for ChainConfig {
             parlia: None,
             extra_fields: Default::default(),
             deposit_contract_address: None,
+            blob_schedule: Default::default(),
         }
     }
 }
@@ -1489,6 +1514,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn blob_params_at_timestamp() {
+        let config: ChainConfig = serde_json::from_str(
+            r#"{
+                "chainId": 1337,
+                "cancunTime": 10,
+                "pragueTime": 20,
+                "blobSchedule": {
+                    "prague": { "target": 8, "max": 12, "baseFeeUpdateFraction": 7000000 }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.blob_params_at_timestamp(9), None);
+        assert_eq!(config.blob_params_at_timestamp(10), Some(BlobParams::CANCUN));
+        assert_eq!(
+            config.blob_params_at_timestamp(20),
+            Some(BlobParams {
+                target_blob_count: 8,
+                max_blob_count: 12,
+                update_fraction: 7_000_000
+            })
+        );
+
+        let config =
+            ChainConfig { cancun_time: Some(0), prague_time: Some(0), ..Default::default() };
+        assert_eq!(config.blob_params_at_timestamp(0), Some(BlobParams::PRAGUE));
+    }
+
     #[test]
     fn parse_dump_genesis_mainnet() {
         let mainnet = include_str!("../dumpgenesis/mainnet.json");
@@ -3,6 +3,11 @@ use std::fmt::Display;
 
 /// A JSON-RPC 2.0 ID object. This may be a number, a string, or null.
 ///
+/// Negative integers are deserialized as strings holding their decimal
+/// representation. Floating-point numbers are deserialized as numbers if they
+/// are non-negative integers exactly representable as an [`f64`], i.e. up to
+/// 2^53, and rejected otherwise, as they cannot be echoed back unchanged.
+///
 /// ### Ordering
 ///
 /// This type implements [`PartialOrd`], [`Ord`], [`PartialEq`], and [`Eq`] so
@@ -63,7 +68,7 @@ impl<'de> Deserialize<'de> for Id {
             type Value = Id;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                write!(formatter, "a string, a number, or null")
+                write!(formatter, "a string, an integer, or null")
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
@@ -73,6 +78,27 @@ impl<'de> Deserialize<'de> for Id {
                 Ok(Id::Number(v))
             }
 
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(u64::try_from(v).map_or_else(|_| Id::String(v.to_string()), Id::Number))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                /// The largest integer up to which all integers are exactly representable.
+                const MAX_EXACT_INTEGER: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+
+                if v.fract() == 0.0 && (0.0..=MAX_EXACT_INTEGER).contains(&v) {
+                    Ok(Id::Number(v as u64))
+                } else {
+                    Err(E::invalid_value(serde::de::Unexpected::Float(v), &self))
+                }
+            }
+
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
@@ -155,6 +181,56 @@ impl Id {
             _ => None,
         }
     }
+
+    /// Returns the same ID in its other representation: a number as a decimal
+    /// string, or a decimal string as a number.
+    ///
+    /// Returns `None` for null and for strings that are not decimal numbers.
+    pub fn alternate(&self) -> Option<Self> {
+        match self {
+            Self::Number(n) => Some(Self::String(n.to_string())),
+            Self::String(s) => s.parse().ok().map(Self::Number),
+            Self::None => None,
+        }
+    }
+}
+
+/// How the IDs of responses are matched with the IDs of requests.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum IdMode {
+    /// Response IDs must be equal to the IDs of their requests.
+    #[default]
+    Strict,
+    /// Response IDs may also be echoed in the other representation, e.g.
+    /// `"1"` for `1`, see [`Id::alternate`].
+    ///
+    /// In batches, responses whose ID matches no pending request, e.g. because
+    /// the server reused an ID, are matched with the request at the same
+    /// position.
+    Lenient,
+}
+
+impl IdMode {
+    /// Returns `true` if the mode is [`IdMode::Lenient`].
+    pub const fn is_lenient(self) -> bool {
+        matches!(self, Self::Lenient)
+    }
+
+    /// Returns `true` if a response with the ID `response` answers a request
+    /// with the ID `request`.
+    pub fn matches(self, request: &Id, response: &Id) -> bool {
+        request == response || (self.is_lenient() && response.alternate().as_ref() == Some(request))
+    }
+
+    /// Returns the ID to look up if no request has the ID of a response, if
+    /// any.
+    pub fn fallback(self, response: &Id) -> Option<Id> {
+        if self.is_lenient() {
+            response.alternate()
+        } else {
+            None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -181,4 +257,46 @@ mod test {
             assert_eq!(deserialized, case);
         }
     }
+
+    #[test]
+    fn it_deserializes_out_of_range_numbers() {
+        let cases = [
+            (r#"{"id":-1}"#, Id::String("-1".into())),
+            (r#"{"id":2.0}"#, Id::Number(2)),
+            (r#"{"id":9007199254740992.0}"#, Id::Number(1 << 53)),
+        ];
+        for (json, id) in cases {
+            assert_eq!(serde_json::from_str::<TestCase>(json).unwrap().id, id);
+        }
+    }
+
+    #[test]
+    fn it_rejects_lossy_floats() {
+        for json in [
+            r#"{"id":1.5}"#,
+            r#"{"id":-2.0}"#,
+            r#"{"id":1e300}"#,
+            r#"{"id":18446744073709551616}"#,
+            r#"{"id":9007199254740994.0}"#,
+        ] {
+            let err = serde_json::from_str::<TestCase>(json).unwrap_err();
+            assert!(err.to_string().contains("invalid value: floating point"), "{json}: {err}");
+        }
+    }
+
+    #[test]
+    fn it_matches_ids() {
+        let (number, string) = (Id::Number(1), Id::String("1".into()));
+        assert_eq!(number.alternate(), Some(string.clone()));
+        assert_eq!(string.alternate(), Some(number.clone()));
+        assert_eq!(Id::String("a".into()).alternate(), None);
+
+        assert!(IdMode::Strict.matches(&number, &number));
+        assert!(!IdMode::Strict.matches(&number, &string));
+        assert!(IdMode::Lenient.matches(&number, &string));
+        assert!(IdMode::Lenient.matches(&string, &number));
+        assert!(!IdMode::Lenient.matches(&Id::Number(2), &string));
+        assert_eq!(IdMode::Strict.fallback(&string), None);
+        assert_eq!(IdMode::Lenient.fallback(&string), Some(number));
+    }
 }
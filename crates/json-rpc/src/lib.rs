@@ -80,13 +80,13 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
 mod common;
-pub use common::Id;
+pub use common::{Id, IdMode};
 
 mod error;
 pub use error::RpcError;
 
 mod notification;
-pub use notification::{EthNotification, PubSubItem};
+pub use notification::{EthNotification, PubSubItem, ServerNotification};
 
 mod packet;
 pub use packet::{BorrowedResponsePacket, RequestPacket, ResponsePacket};
@@ -15,15 +15,39 @@ pub struct EthNotification<T = Box<serde_json::value::RawValue>> {
     pub result: T,
 }
 
+/// A JSON-RPC notification sent by the server with a method other than
+/// `eth_subscription`.
+///
+/// This includes the subscription notifications of other namespaces, e.g.
+/// `parity_subscription`, which are not routed to subscriptions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServerNotification {
+    /// The notification method.
+    pub method: String,
+    /// The notification parameters, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Box<serde_json::value::RawValue>>,
+}
+
 /// An item received over an Ethereum pubsub transport. Ethereum pubsub uses a
 /// non-standard JSON-RPC notification format. An item received over a pubsub
-/// transport may be a JSON-RPC response or an Ethereum-style notification.
+/// transport may be a JSON-RPC response, an Ethereum-style notification, or
+/// another server-initiated notification.
+///
+/// Items are classified as follows:
+/// - an item with an `id` is a [`Response`], even if it also has a `method`. Server-to-client
+///   requests are not supported, and fail to deserialize as they have no `result` or `error`.
+/// - an item without an `id` whose `method` is `eth_subscription`, or that has no `method`, is an
+///   [`EthNotification`].
+/// - any other item without an `id` is a [`ServerNotification`], whatever its method and params.
 #[derive(Clone, Debug)]
 pub enum PubSubItem {
     /// A [`Response`] to a JSON-RPC request.
     Response(Response),
     /// An Ethereum-style notification.
     Notification(EthNotification),
+    /// A notification with a method other than `eth_subscription`.
+    ServerNotification(ServerNotification),
 }
 
 impl From<Response> for PubSubItem {
@@ -38,6 +62,12 @@ impl From<EthNotification> for PubSubItem {
     }
 }
 
+impl From<ServerNotification> for PubSubItem {
+    fn from(notification: ServerNotification) -> Self {
+        Self::ServerNotification(notification)
+    }
+}
+
 impl<'de> Deserialize<'de> for PubSubItem {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -58,8 +88,9 @@ impl<'de> Deserialize<'de> for PubSubItem {
             {
                 let mut id = None;
                 let mut result = None;
-                let mut params = None;
+                let mut params: Option<Box<serde_json::value::RawValue>> = None;
                 let mut error = None;
+                let mut method: Option<String> = None;
 
                 // Drain the map into the appropriate fields.
                 while let Ok(Some(key)) = map.next_key() {
@@ -88,6 +119,12 @@ impl<'de> Deserialize<'de> for PubSubItem {
                             }
                             error = Some(map.next_value()?);
                         }
+                        "method" => {
+                            if method.is_some() {
+                                return Err(serde::de::Error::duplicate_field("method"));
+                            }
+                            method = Some(map.next_value()?);
+                        }
                         // Discard unknown fields.
                         _ => {
                             let _ = map.next_value::<serde_json::Value>()?;
@@ -114,9 +151,18 @@ impl<'de> Deserialize<'de> for PubSubItem {
                             "unexpected `error` field in subscription notification",
                         ));
                     }
-                    params
-                        .map(PubSubItem::Notification)
-                        .ok_or_else(|| serde::de::Error::missing_field("params"))
+                    match method {
+                        Some(method) if method != "eth_subscription" => {
+                            Ok(ServerNotification { method, params }.into())
+                        }
+                        _ => {
+                            let params =
+                                params.ok_or_else(|| serde::de::Error::missing_field("params"))?;
+                            serde_json::from_str(params.get())
+                                .map(PubSubItem::Notification)
+                                .map_err(serde::de::Error::custom)
+                        }
+                    }
                 }
             }
         }
@@ -128,7 +174,7 @@ impl<'de> Deserialize<'de> for PubSubItem {
 #[cfg(test)]
 mod test {
 
-    use crate::{EthNotification, PubSubItem};
+    use crate::{EthNotification, PubSubItem, ServerNotification};
 
     #[test]
     fn deserializer_test() {
@@ -140,10 +186,68 @@ mod test {
 
         match deser {
             PubSubItem::Notification(EthNotification { subscription, result }) => {
-                assert_eq!(subscription, "0xcd0c3e8af590364c09d0fa6a1210faf5".parse().unwrap());
+                assert_eq!(
+                    subscription,
+                    "0xcd0c3e8af590364c09d0fa6a1210faf5".parse::<alloy_primitives::U256>().unwrap()
+                );
                 assert_eq!(result.get(), r#"{"difficulty": "0xd9263f42a87", "uncles": []}"#);
             }
             _ => panic!("unexpected deserialization result"),
         }
     }
+
+    #[test]
+    fn deserializes_server_notifications() {
+        let notification = r#"{"jsonrpc":"2.0","method":"parity_newPending","params":["0x01"]}"#;
+        let deser = serde_json::from_str::<PubSubItem>(notification).unwrap();
+        match deser {
+            PubSubItem::ServerNotification(ServerNotification { method, params }) => {
+                assert_eq!(method, "parity_newPending");
+                assert_eq!(params.unwrap().get(), r#"["0x01"]"#);
+            }
+            _ => panic!("unexpected deserialization result"),
+        }
+
+        let notification = r#"{"jsonrpc":"2.0","method":"node_shutdown"}"#;
+        let deser = serde_json::from_str::<PubSubItem>(notification).unwrap();
+        assert!(matches!(deser, PubSubItem::ServerNotification(n) if n.params.is_none()));
+
+        // Notifications without a method are still subscription notifications.
+        let notification = r#"{"params":{"subscription":"0x1","result":null}}"#;
+        let deser = serde_json::from_str::<PubSubItem>(notification).unwrap();
+        assert!(matches!(deser, PubSubItem::Notification(_)));
+    }
+
+    #[test]
+    fn classifies_by_method() {
+        // Subscription notifications of other namespaces are server notifications.
+        let notification = r#"{"jsonrpc":"2.0","method":"parity_subscription","params":{"subscription":"0x1","result":null}}"#;
+        let deser = serde_json::from_str::<PubSubItem>(notification).unwrap();
+        assert!(
+            matches!(deser, PubSubItem::ServerNotification(n) if n.method == "parity_subscription")
+        );
+
+        // The params of `eth_subscription` notifications must be subscription params.
+        let notification = r#"{"jsonrpc":"2.0","method":"eth_subscription","params":["0x1"]}"#;
+        assert!(serde_json::from_str::<PubSubItem>(notification).is_err());
+        let notification = r#"{"jsonrpc":"2.0","method":"eth_subscription"}"#;
+        assert!(serde_json::from_str::<PubSubItem>(notification).is_err());
+
+        // Notifications cannot carry an error, whatever their method.
+        let notification =
+            r#"{"jsonrpc":"2.0","method":"node_shutdown","error":{"code":1,"message":"x"}}"#;
+        assert!(serde_json::from_str::<PubSubItem>(notification).is_err());
+    }
+
+    #[test]
+    fn classifies_by_id() {
+        // An item with an ID is a response, even with a method.
+        let response = r#"{"jsonrpc":"2.0","id":1,"method":"eth_subscription","result":"0x1"}"#;
+        let deser = serde_json::from_str::<PubSubItem>(response).unwrap();
+        assert!(matches!(deser, PubSubItem::Response(r) if r.id == crate::Id::Number(1)));
+
+        // Server-to-client requests are not supported.
+        let request = r#"{"jsonrpc":"2.0","id":1,"method":"client_ping","params":[]}"#;
+        assert!(serde_json::from_str::<PubSubItem>(request).is_err());
+    }
 }
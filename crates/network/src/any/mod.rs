@@ -84,6 +84,10 @@ impl ReceiptResponse for AnyTransactionReceipt {
     fn status(&self) -> bool {
         self.inner.inner.status()
     }
+
+    fn status_or_post_state(&self) -> alloy_consensus::Eip658Value {
+        self.inner.status_or_post_state()
+    }
 }
 
 impl TransactionResponse for WithOtherFields<Transaction> {
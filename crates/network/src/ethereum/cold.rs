@@ -0,0 +1,459 @@
+//! Bundles of transactions moved between an online machine and an air-gapped one, for cold
+//! signing.
+
+use crate::{Ethereum, NetworkWallet};
+use alloy_consensus::{
+    BlobTransactionSidecar, Transaction, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant,
+    TxEip4844WithSidecar, TxEnvelope, TxLegacy, TypedTransaction,
+};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_primitives::{keccak256, Address, Bytes, ChainId, TxHash};
+use alloy_rpc_types_eth::TransactionRequest;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+
+/// The version of the format of the bundles.
+pub const BUNDLE_VERSION: u8 = 1;
+
+/// Error thrown by an [`UnsignedTransactionBundle`] or a [`SignedTransactionBundle`].
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    /// The transaction is not fully filled.
+    #[error("transaction is missing the keys {0:?}")]
+    IncompleteTransaction(Vec<&'static str>),
+    /// The transaction has no chain ID.
+    #[error("transaction has no chain ID")]
+    MissingChainId,
+    /// The chain ID of the transaction is not the chain ID of the bundle.
+    #[error("transaction chain ID {tx} does not match the bundle chain ID {bundle}")]
+    ChainIdMismatch {
+        /// The chain ID of the bundle.
+        bundle: ChainId,
+        /// The chain ID of the transaction.
+        tx: ChainId,
+    },
+    /// The wallet has no signer for the sender of the transaction.
+    #[error("no signer for {0}")]
+    MissingSigner(Address),
+    /// The signed transaction does not match its hash.
+    #[error("signed transaction does not match its hash {0}")]
+    HashMismatch(TxHash),
+    /// The bundle has an unsupported version.
+    #[error("unsupported bundle version {0}, expected {BUNDLE_VERSION}")]
+    UnsupportedVersion(u8),
+    /// Signing failed.
+    #[error(transparent)]
+    Signer(#[from] alloy_signer::Error),
+    /// The signed transaction is not a valid EIP-2718 envelope.
+    #[error(transparent)]
+    Eip2718(#[from] alloy_eips::eip2718::Eip2718Error),
+    /// The JSON of the bundle is invalid.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The bundle failed to serialize to CBOR, or its CBOR is invalid.
+    #[error("CBOR error: {0}")]
+    Cbor(String),
+}
+
+/// Serialization of the bundles to JSON and CBOR, checking their version when deserialized.
+trait BundleFormat: Serialize + DeserializeOwned {
+    /// Checks the bundle once deserialized.
+    fn check(self) -> Result<Self, BundleError>;
+
+    fn to_json(&self) -> Result<String, BundleError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    fn from_json(json: &str) -> Result<Self, BundleError> {
+        serde_json::from_str::<Self>(json)?.check()
+    }
+
+    fn to_cbor(&self) -> Result<Vec<u8>, BundleError> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes)
+            .map_err(|err| BundleError::Cbor(err.to_string()))?;
+        Ok(bytes)
+    }
+
+    fn from_cbor(bytes: &[u8]) -> Result<Self, BundleError> {
+        ciborium::from_reader::<Self, _>(bytes)
+            .map_err(|err| BundleError::Cbor(err.to_string()))?
+            .check()
+    }
+}
+
+/// A fully filled unsigned transaction, with the context to sign it on an air-gapped machine.
+///
+/// The online side fills the transaction, e.g. with a provider without a wallet, and exports the
+/// bundle as [JSON](Self::to_json) or [CBOR](Self::to_cbor), e.g. through a file or a QR code.
+/// The air-gapped side imports the bundle, [signs](Self::sign) it, and exports the resulting
+/// [`SignedTransactionBundle`], which the online side imports and broadcasts with
+/// `eth_sendRawTransaction`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(request: alloy_rpc_types_eth::TransactionRequest, signer: alloy_signer_local::PrivateKeySigner) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_network::{EthereumWallet, SignedTransactionBundle, UnsignedTransactionBundle};
+///
+/// // Online: export the filled transaction.
+/// let cbor = UnsignedTransactionBundle::from_request(request)?.to_cbor()?;
+///
+/// // Air-gapped: sign it.
+/// let bundle = UnsignedTransactionBundle::from_cbor(&cbor)?;
+/// let signed = bundle.sign(&EthereumWallet::from(signer)).await?.to_cbor()?;
+///
+/// // Online: broadcast it.
+/// let signed = SignedTransactionBundle::from_cbor(&signed)?;
+/// println!("broadcasting {}: {}", signed.hash, signed.raw);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransactionBundle {
+    /// The version of the format of the bundle, [`BUNDLE_VERSION`].
+    pub version: u8,
+    /// The chain ID of the transaction.
+    pub chain_id: ChainId,
+    /// The sender of the transaction, whose key signs it.
+    pub from: Address,
+    /// The unsigned transaction.
+    #[serde(with = "binary_transaction")]
+    pub transaction: TypedTransaction,
+}
+
+/// An unsigned transaction in binary formats, such as CBOR.
+///
+/// The internally tagged [`TypedTransaction`] can only be deserialized from human-readable
+/// formats, as serde buffers its fields in a human-readable representation to find the tag.
+#[derive(Serialize, Deserialize)]
+enum BinaryTransaction<'a> {
+    Legacy(Cow<'a, TxLegacy>),
+    Eip2930(Cow<'a, TxEip2930>),
+    Eip1559(Cow<'a, TxEip1559>),
+    Eip4844(Cow<'a, TxEip4844>),
+    Eip4844WithSidecar(Cow<'a, TxEip4844>, Cow<'a, BlobTransactionSidecar>),
+}
+
+/// (De)serializes a [`TypedTransaction`] as itself in human-readable formats, and as a
+/// [`BinaryTransaction`] in the others.
+mod binary_transaction {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(
+        tx: &TypedTransaction,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return tx.serialize(serializer);
+        }
+        match tx {
+            TypedTransaction::Legacy(tx) => BinaryTransaction::Legacy(Cow::Borrowed(tx)),
+            TypedTransaction::Eip2930(tx) => BinaryTransaction::Eip2930(Cow::Borrowed(tx)),
+            TypedTransaction::Eip1559(tx) => BinaryTransaction::Eip1559(Cow::Borrowed(tx)),
+            TypedTransaction::Eip4844(TxEip4844Variant::TxEip4844(tx)) => {
+                BinaryTransaction::Eip4844(Cow::Borrowed(tx))
+            }
+            TypedTransaction::Eip4844(TxEip4844Variant::TxEip4844WithSidecar(tx)) => {
+                BinaryTransaction::Eip4844WithSidecar(
+                    Cow::Borrowed(&tx.tx),
+                    Cow::Borrowed(&tx.sidecar),
+                )
+            }
+        }
+        .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TypedTransaction, D::Error> {
+        if deserializer.is_human_readable() {
+            return TypedTransaction::deserialize(deserializer);
+        }
+        Ok(match BinaryTransaction::deserialize(deserializer)? {
+            BinaryTransaction::Legacy(tx) => tx.into_owned().into(),
+            BinaryTransaction::Eip2930(tx) => tx.into_owned().into(),
+            BinaryTransaction::Eip1559(tx) => tx.into_owned().into(),
+            BinaryTransaction::Eip4844(tx) => {
+                TypedTransaction::Eip4844(TxEip4844Variant::TxEip4844(tx.into_owned()))
+            }
+            BinaryTransaction::Eip4844WithSidecar(tx, sidecar) => {
+                let tx = TxEip4844WithSidecar::from_tx_and_sidecar(
+                    tx.into_owned(),
+                    sidecar.into_owned(),
+                );
+                TypedTransaction::Eip4844(TxEip4844Variant::TxEip4844WithSidecar(tx))
+            }
+        })
+    }
+}
+
+impl BundleFormat for UnsignedTransactionBundle {
+    fn check(self) -> Result<Self, BundleError> {
+        let bundle = match self.version {
+            BUNDLE_VERSION => self,
+            version => return Err(BundleError::UnsupportedVersion(version)),
+        };
+        match bundle.transaction.chain_id() {
+            Some(tx) if tx == bundle.chain_id => Ok(bundle),
+            Some(tx) => Err(BundleError::ChainIdMismatch { bundle: bundle.chain_id, tx }),
+            None => Err(BundleError::MissingChainId),
+        }
+    }
+}
+
+impl UnsignedTransactionBundle {
+    /// Creates a bundle of the transaction sent by `from`, which must have a chain ID.
+    pub fn new(from: Address, transaction: TypedTransaction) -> Result<Self, BundleError> {
+        let chain_id = transaction.chain_id().ok_or(BundleError::MissingChainId)?;
+        Ok(Self { version: BUNDLE_VERSION, chain_id, from, transaction })
+    }
+
+    /// Creates a bundle of a fully filled transaction request, with its sender and chain ID.
+    pub fn from_request(request: TransactionRequest) -> Result<Self, BundleError> {
+        let from = request.from.ok_or(BundleError::IncompleteTransaction(vec!["from"]))?;
+        if request.chain_id.is_none() {
+            return Err(BundleError::MissingChainId);
+        }
+        request
+            .missing_keys()
+            .map_err(|(_, missing)| BundleError::IncompleteTransaction(missing))?;
+        let transaction =
+            request.build_typed_tx().map_err(|_| BundleError::IncompleteTransaction(Vec::new()))?;
+        Self::new(from, transaction)
+    }
+
+    /// Serializes the bundle to JSON.
+    pub fn to_json(&self) -> Result<String, BundleError> {
+        BundleFormat::to_json(self)
+    }
+
+    /// Deserializes a bundle from JSON, checking its version and chain ID.
+    pub fn from_json(json: &str) -> Result<Self, BundleError> {
+        BundleFormat::from_json(json)
+    }
+
+    /// Serializes the bundle to CBOR, with its binary fields as byte strings.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, BundleError> {
+        BundleFormat::to_cbor(self)
+    }
+
+    /// Deserializes a bundle from CBOR, checking its version and chain ID.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, BundleError> {
+        BundleFormat::from_cbor(bytes)
+    }
+
+    /// Signs the transaction with the signer of the sender in the wallet.
+    pub async fn sign<W>(self, wallet: &W) -> Result<SignedTransactionBundle, BundleError>
+    where
+        W: NetworkWallet<Ethereum>,
+    {
+        let Self { chain_id, from, transaction, .. } = self.check()?;
+        if !wallet.has_signer_for(&from) {
+            return Err(BundleError::MissingSigner(from));
+        }
+        let envelope: TxEnvelope = wallet.sign_transaction_from(from, transaction).await?;
+        Ok(SignedTransactionBundle::new(chain_id, from, &envelope))
+    }
+}
+
+/// A signed transaction, exported from an air-gapped machine to be broadcast, see
+/// [`UnsignedTransactionBundle`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedTransactionBundle {
+    /// The version of the format of the bundle, [`BUNDLE_VERSION`].
+    pub version: u8,
+    /// The chain ID of the transaction.
+    pub chain_id: ChainId,
+    /// The sender of the transaction.
+    pub from: Address,
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The EIP-2718 encoding of the signed transaction, to broadcast with
+    /// `eth_sendRawTransaction`.
+    pub raw: Bytes,
+}
+
+impl BundleFormat for SignedTransactionBundle {
+    fn check(self) -> Result<Self, BundleError> {
+        let bundle = match self.version {
+            BUNDLE_VERSION => self,
+            version => return Err(BundleError::UnsupportedVersion(version)),
+        };
+        if keccak256(&bundle.raw) != bundle.hash {
+            return Err(BundleError::HashMismatch(bundle.hash));
+        }
+        Ok(bundle)
+    }
+}
+
+impl SignedTransactionBundle {
+    /// Creates a bundle of the signed transaction sent by `from`.
+    pub fn new(chain_id: ChainId, from: Address, envelope: &TxEnvelope) -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            chain_id,
+            from,
+            hash: *envelope.tx_hash(),
+            raw: envelope.encoded_2718().into(),
+        }
+    }
+
+    /// Decodes the signed transaction.
+    pub fn envelope(&self) -> Result<TxEnvelope, BundleError> {
+        Ok(TxEnvelope::decode_2718(&mut self.raw.as_ref())?)
+    }
+
+    /// Serializes the bundle to JSON.
+    pub fn to_json(&self) -> Result<String, BundleError> {
+        BundleFormat::to_json(self)
+    }
+
+    /// Deserializes a bundle from JSON, checking its version and hash.
+    pub fn from_json(json: &str) -> Result<Self, BundleError> {
+        BundleFormat::from_json(json)
+    }
+
+    /// Serializes the bundle to CBOR, with its binary fields as byte strings.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, BundleError> {
+        BundleFormat::to_cbor(self)
+    }
+
+    /// Deserializes a bundle from CBOR, checking its version and hash.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, BundleError> {
+        BundleFormat::from_cbor(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EthereumWallet, TransactionBuilder, TxSigner};
+    use alloy_consensus::SignableTransaction;
+    use alloy_primitives::{Signature, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    /// Signs with a local key, as `PrivateKeySigner` implements `TxSigner` of another build of
+    /// this crate in tests.
+    struct LocalTxSigner(PrivateKeySigner);
+
+    #[async_trait::async_trait]
+    impl TxSigner<Signature> for LocalTxSigner {
+        fn address(&self) -> Address {
+            self.0.address()
+        }
+
+        async fn sign_transaction(
+            &self,
+            tx: &mut dyn SignableTransaction<Signature>,
+        ) -> alloy_signer::Result<Signature> {
+            self.0.sign_hash_sync(&tx.signature_hash())
+        }
+    }
+
+    fn request(from: Address) -> TransactionRequest {
+        TransactionRequest::default()
+            .from(from)
+            .to(Address::with_last_byte(1))
+            .value(U256::from(100))
+            .nonce(7)
+            .gas_limit(21_000)
+            .max_fee_per_gas(20_000_000_000)
+            .max_priority_fee_per_gas(1_000_000_000)
+            .with_chain_id(1)
+    }
+
+    #[tokio::test]
+    async fn cold_signing() {
+        let signer = PrivateKeySigner::random();
+        let bundle = UnsignedTransactionBundle::from_request(request(signer.address())).unwrap();
+        assert_eq!(bundle.chain_id, 1);
+        assert_eq!(
+            UnsignedTransactionBundle::from_json(&bundle.to_json().unwrap()).unwrap(),
+            bundle
+        );
+        let imported = UnsignedTransactionBundle::from_cbor(&bundle.to_cbor().unwrap()).unwrap();
+        assert_eq!(imported, bundle);
+
+        let wallet = EthereumWallet::from(LocalTxSigner(signer.clone()));
+        let signed = imported.sign(&wallet).await.unwrap();
+        assert_eq!(SignedTransactionBundle::from_json(&signed.to_json().unwrap()).unwrap(), signed);
+        let cbor = signed.to_cbor().unwrap();
+        let value: ciborium::Value = ciborium::from_reader(&cbor[..]).unwrap();
+        let raw = value.as_map().unwrap().iter().find(|(key, _)| key.as_text() == Some("raw"));
+        assert_eq!(raw.unwrap().1.as_bytes(), Some(&signed.raw.to_vec()));
+        let signed = SignedTransactionBundle::from_cbor(&cbor).unwrap();
+        let TxEnvelope::Eip1559(tx) = signed.envelope().unwrap() else { unreachable!() };
+        assert_eq!(tx.hash(), &signed.hash);
+        assert_eq!(tx.tx().nonce, 7);
+        let signature_hash = tx.tx().signature_hash();
+        assert_eq!(
+            tx.signature().recover_address_from_prehash(&signature_hash).unwrap(),
+            signer.address()
+        );
+
+        let other = EthereumWallet::from(LocalTxSigner(PrivateKeySigner::random()));
+        assert!(matches!(
+            bundle.sign(&other).await,
+            Err(BundleError::MissingSigner(from)) if from == signer.address()
+        ));
+    }
+
+    #[test]
+    fn cbor_transaction_types() {
+        let legacy =
+            TxLegacy { chain_id: Some(1), input: Bytes::from_static(&[1]), ..Default::default() };
+        let blob = TxEip4844 { chain_id: 1, input: Bytes::from_static(&[2]), ..Default::default() };
+        let sidecar = BlobTransactionSidecar::default();
+        let transactions: [TypedTransaction; 3] = [
+            legacy.into(),
+            TypedTransaction::Eip4844(blob.clone().into()),
+            TypedTransaction::Eip4844(
+                TxEip4844WithSidecar::from_tx_and_sidecar(blob, sidecar).into(),
+            ),
+        ];
+        for transaction in transactions {
+            let bundle = UnsignedTransactionBundle::new(Address::ZERO, transaction).unwrap();
+            let cbor = bundle.to_cbor().unwrap();
+            assert_eq!(UnsignedTransactionBundle::from_cbor(&cbor).unwrap(), bundle);
+        }
+    }
+
+    #[test]
+    fn invalid_bundles() {
+        let from = Address::with_last_byte(2);
+        let mut incomplete = request(from);
+        incomplete.nonce = None;
+        assert!(matches!(
+            UnsignedTransactionBundle::from_request(incomplete),
+            Err(BundleError::IncompleteTransaction(missing)) if missing == ["nonce"]
+        ));
+
+        let mut bundle = UnsignedTransactionBundle::from_request(request(from)).unwrap();
+        bundle.chain_id = 5;
+        assert!(matches!(
+            UnsignedTransactionBundle::from_json(&bundle.to_json().unwrap()),
+            Err(BundleError::ChainIdMismatch { bundle: 5, tx: 1 })
+        ));
+        bundle.version = 2;
+        assert!(matches!(
+            UnsignedTransactionBundle::from_cbor(&bundle.to_cbor().unwrap()),
+            Err(BundleError::UnsupportedVersion(2))
+        ));
+
+        let signed = SignedTransactionBundle {
+            version: BUNDLE_VERSION,
+            chain_id: 1,
+            from,
+            hash: TxHash::ZERO,
+            raw: Bytes::from_static(&[1, 2, 3]),
+        };
+        assert!(matches!(
+            SignedTransactionBundle::from_json(&signed.to_json().unwrap()),
+            Err(BundleError::HashMismatch(_))
+        ));
+    }
+}
@@ -3,6 +3,9 @@ use alloy_primitives::Bytes;
 
 mod builder;
 
+mod cold;
+pub use cold::{BundleError, SignedTransactionBundle, UnsignedTransactionBundle, BUNDLE_VERSION};
+
 mod wallet;
 pub use wallet::EthereumWallet;
 
@@ -40,6 +43,10 @@ impl ReceiptResponse for alloy_rpc_types_eth::TransactionReceipt {
     fn status(&self) -> bool {
         self.inner.status()
     }
+
+    fn status_or_post_state(&self) -> alloy_consensus::Eip658Value {
+        self.status_or_post_state()
+    }
 }
 
 impl TransactionResponse for alloy_rpc_types_eth::Transaction {
@@ -6,7 +6,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-use alloy_consensus::TxReceipt;
+use alloy_consensus::{Eip658Value, TxReceipt};
 use alloy_eips::eip2718::{Eip2718Envelope, Eip2718Error};
 use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, TxHash, U256};
@@ -19,7 +19,10 @@ pub use transaction::{
 };
 
 mod ethereum;
-pub use ethereum::{Ethereum, EthereumWallet};
+pub use ethereum::{
+    BundleError, Ethereum, EthereumWallet, SignedTransactionBundle, UnsignedTransactionBundle,
+    BUNDLE_VERSION,
+};
 
 mod any;
 pub use any::AnyNetwork;
@@ -44,11 +47,20 @@ pub trait ReceiptResponse {
     /// transaction. The transaction status is not knowable from the receipt
     /// for transactions before [EIP-658].
     ///
-    /// This can be handled using [`TxReceipt::status_or_post_state`].
+    /// This can be handled using [`ReceiptResponse::status_or_post_state`].
     ///
     /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
-    /// [`TxReceipt::status_or_post_state`]: alloy_consensus::TxReceipt::status_or_post_state
     fn status(&self) -> bool;
+
+    /// Status code of the transaction, or its post-transaction state root for pre-[EIP-658]
+    /// receipts.
+    ///
+    /// Defaults to the [`ReceiptResponse::status`] of the transaction.
+    ///
+    /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+    fn status_or_post_state(&self) -> Eip658Value {
+        Eip658Value::Eip658(self.status())
+    }
 }
 
 /// Transaction Response
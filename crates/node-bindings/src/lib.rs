@@ -12,7 +12,10 @@ extern crate tracing;
 use alloy_primitives::U256;
 
 pub mod anvil;
-pub use anvil::{Anvil, AnvilInstance};
+pub use anvil::{Anvil, AnvilError, AnvilInstance};
+
+pub mod pool;
+pub use pool::{AnvilLease, AnvilPool};
 
 pub mod geth;
 pub use geth::{Geth, GethInstance};
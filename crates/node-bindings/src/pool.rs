@@ -0,0 +1,263 @@
+//! A pool of reusable Anvil instances.
+
+use crate::{Anvil, AnvilError, AnvilInstance};
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    ops::Deref,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+/// A pool of [`AnvilInstance`]s shared by the tests of a suite.
+///
+/// Spawning a node is by far the slowest part of most tests: the pool keeps up to `size`
+/// instances alive and leases them out instead. Instances are reset with `anvil_reset` when a
+/// lease is dropped, so every lease starts from a fresh chain.
+///
+/// Instances listen on ports picked by the OS, so pools can be used by concurrent tests.
+///
+/// # Example
+///
+/// ```no_run
+/// use alloy_node_bindings::{Anvil, AnvilPool};
+/// use std::sync::OnceLock;
+///
+/// fn pool() -> &'static AnvilPool {
+///     static POOL: OnceLock<AnvilPool> = OnceLock::new();
+///     POOL.get_or_init(|| AnvilPool::new(Anvil::new(), 4))
+/// }
+///
+/// let anvil = pool().lease().unwrap();
+/// println!("Anvil running at `{}`", anvil.endpoint());
+/// // Dropping the lease resets the instance and returns it to the pool.
+/// drop(anvil);
+/// ```
+#[derive(Debug)]
+pub struct AnvilPool {
+    anvil: Anvil,
+    size: usize,
+    state: Mutex<PoolState>,
+    available: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    idle: Vec<AnvilInstance>,
+    /// The number of spawned instances, either idle, leased or being spawned.
+    spawned: usize,
+}
+
+impl AnvilPool {
+    /// Creates a pool of at most `size` instances launched with the `anvil` config.
+    ///
+    /// Instances are spawned on demand, see [`prespawn`](Self::prespawn). The port configured in
+    /// `anvil` is ignored.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero.
+    pub fn new(anvil: Anvil, size: usize) -> Self {
+        assert!(size > 0, "anvil pool size must be greater than zero");
+        Self {
+            anvil: anvil.port(0u16),
+            size,
+            state: Mutex::new(PoolState::default()),
+            available: Condvar::new(),
+        }
+    }
+
+    /// Returns the maximum number of instances of the pool.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of idle instances, ready to be leased.
+    pub fn idle(&self) -> usize {
+        self.state().idle.len()
+    }
+
+    /// Spawns instances until the pool is full.
+    pub fn prespawn(&self) -> Result<(), AnvilError> {
+        loop {
+            {
+                let mut state = self.state();
+                if state.spawned == self.size {
+                    return Ok(());
+                }
+                state.spawned += 1;
+            }
+            let instance = self.spawn()?;
+            self.state().idle.push(instance);
+            self.available.notify_one();
+        }
+    }
+
+    /// Leases an instance, spawning it if there are no idle instances and the pool is not full.
+    ///
+    /// Blocks until an instance is returned to the pool otherwise.
+    pub fn lease(&self) -> Result<AnvilLease<'_>, AnvilError> {
+        let mut state = self.state();
+        loop {
+            if let Some(instance) = state.idle.pop() {
+                return Ok(AnvilLease { pool: self, instance: Some(instance) });
+            }
+            if state.spawned < self.size {
+                state.spawned += 1;
+                drop(state);
+                return Ok(AnvilLease { pool: self, instance: Some(self.spawn()?) });
+            }
+            state = self.available.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Leases an instance if one is idle or the pool is not full, without blocking.
+    pub fn try_lease(&self) -> Result<Option<AnvilLease<'_>>, AnvilError> {
+        let mut state = self.state();
+        if let Some(instance) = state.idle.pop() {
+            return Ok(Some(AnvilLease { pool: self, instance: Some(instance) }));
+        }
+        if state.spawned == self.size {
+            return Ok(None);
+        }
+        state.spawned += 1;
+        drop(state);
+        Ok(Some(AnvilLease { pool: self, instance: Some(self.spawn()?) }))
+    }
+
+    /// Spawns a new instance, whose slot has already been reserved.
+    fn spawn(&self) -> Result<AnvilInstance, AnvilError> {
+        self.anvil.clone().try_spawn().inspect_err(|_| self.release_slot())
+    }
+
+    /// Frees the slot of an instance that failed to spawn or reset.
+    fn release_slot(&self) {
+        self.state().spawned -= 1;
+        self.available.notify_one();
+    }
+
+    /// Resets a returned instance and makes it available again.
+    fn release(&self, instance: AnvilInstance) {
+        match reset(instance.port()) {
+            Ok(()) => {
+                self.state().idle.push(instance);
+                self.available.notify_one();
+            }
+            Err(err) => {
+                debug!(target: "anvil", %err, "dropping anvil instance that failed to reset");
+                drop(instance);
+                self.release_slot();
+            }
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// An [`AnvilInstance`] leased from an [`AnvilPool`].
+///
+/// The instance is reset and returned to the pool when the lease is dropped.
+#[derive(Debug)]
+pub struct AnvilLease<'a> {
+    pool: &'a AnvilPool,
+    instance: Option<AnvilInstance>,
+}
+
+impl AnvilLease<'_> {
+    /// Takes the instance out of the pool, which will spawn a new one in its place if needed.
+    pub fn detach(mut self) -> AnvilInstance {
+        let instance = self.instance.take().expect("instance is only taken on drop");
+        self.pool.release_slot();
+        instance
+    }
+}
+
+impl Deref for AnvilLease<'_> {
+    type Target = AnvilInstance;
+
+    fn deref(&self) -> &Self::Target {
+        self.instance.as_ref().expect("instance is only taken on drop")
+    }
+}
+
+impl Drop for AnvilLease<'_> {
+    fn drop(&mut self) {
+        if let Some(instance) = self.instance.take() {
+            self.pool.release(instance);
+        }
+    }
+}
+
+/// Calls `anvil_reset` on the instance listening on `port`.
+fn reset(port: u16) -> Result<(), AnvilError> {
+    let response = request(port, "anvil_reset", serde_json::json!([]))?;
+    response.get("error").map_or(Ok(()), |error| Err(AnvilError::ResetError(error.to_string())))
+}
+
+/// Sends a JSON-RPC request over a plain HTTP/1.1 connection, returning the response object.
+fn request(
+    port: u16,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, AnvilError> {
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })
+        .to_string();
+    let io_err = |err: std::io::Error| AnvilError::ResetError(err.to_string());
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).map_err(io_err)?;
+    write!(
+        stream,
+        "POST / HTTP/1.1\r\nHost: localhost:{port}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .map_err(io_err)?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(io_err)?;
+    let (_, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| AnvilError::ResetError("malformed HTTP response".into()))?;
+    serde_json::from_str(body).map_err(|err| AnvilError::ResetError(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_number(port: u16) -> serde_json::Value {
+        request(port, "eth_blockNumber", serde_json::json!([])).unwrap()["result"].clone()
+    }
+
+    #[test]
+    fn reuses_and_resets_instances() {
+        let pool = AnvilPool::new(Anvil::new(), 1);
+        let lease = pool.lease().unwrap();
+        let port = lease.port();
+        request(port, "evm_mine", serde_json::json!([])).unwrap();
+        assert_eq!(block_number(port), "0x1");
+        assert!(pool.try_lease().unwrap().is_none());
+        drop(lease);
+
+        assert_eq!(pool.idle(), 1);
+        let lease = pool.lease().unwrap();
+        assert_eq!(lease.port(), port);
+        assert_eq!(block_number(port), "0x0");
+    }
+
+    #[test]
+    fn prespawns_instances() {
+        let pool = AnvilPool::new(Anvil::new(), 2);
+        pool.prespawn().unwrap();
+        assert_eq!(pool.idle(), 2);
+
+        let (a, b) = (pool.lease().unwrap(), pool.lease().unwrap());
+        assert_ne!(a.port(), b.port());
+        let detached = a.detach();
+        let c = pool.try_lease().unwrap().unwrap();
+        assert_ne!(c.port(), detached.port());
+        assert!(pool.try_lease().unwrap().is_none());
+        drop(b);
+    }
+}
@@ -0,0 +1,692 @@
+//! A crash-safe transaction broker.
+//!
+//! The [`TxBroker`] takes transaction intents, and drives them to a terminal state: it assigns
+//! their nonces, fills in their gas and fees, signs and broadcasts them, monitors them until they
+//! are included, and bumps their fees when they are stuck.
+//!
+//! Every intent is persisted to a [`BrokerStore`] before any of its transactions is broadcast, so
+//! that a restarted broker resumes where the previous one stopped: it reloads the in-flight
+//! intents, rebroadcasts their transactions, and keeps monitoring them.
+
+use crate::Provider;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Ethereum, Network, NetworkWallet, ReceiptResponse, TransactionBuilder};
+use alloy_primitives::{keccak256, Address, Bytes, TxHash};
+use alloy_transport::{Transport, TransportError};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// The identifier of an intent, assigned by the broker in increasing order.
+pub type IntentId = u64;
+
+/// A transaction intent, and its state, as persisted by a [`BrokerStore`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "", rename_all = "camelCase")]
+pub struct BrokerRecord<N: Network = Ethereum> {
+    /// The identifier of the intent.
+    pub id: IntentId,
+    /// The transaction request, as filled once the intent is submitted.
+    pub request: N::TransactionRequest,
+    /// The state of the intent.
+    pub state: IntentState,
+}
+
+/// The state of a transaction intent.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum IntentState {
+    /// The intent is waiting to be submitted.
+    Queued,
+    /// A transaction was signed for the intent, and is waiting to be included.
+    #[serde(rename_all = "camelCase")]
+    Pending {
+        /// The nonce assigned to the intent.
+        nonce: u64,
+        /// The transactions signed for the intent, the last one having the highest fees.
+        attempts: Vec<Attempt>,
+    },
+    /// One of the transactions of the intent was included.
+    #[serde(rename_all = "camelCase")]
+    Confirmed {
+        /// The hash of the included transaction.
+        tx_hash: TxHash,
+        /// Whether the transaction succeeded, according to its receipt.
+        success: bool,
+    },
+    /// The intent failed, and will not be retried.
+    Failed {
+        /// The reason of the failure.
+        error: String,
+    },
+}
+
+impl IntentState {
+    /// Returns `true` if the intent reached a terminal state.
+    pub const fn is_terminal(&self) -> bool {
+        matches!(self, Self::Confirmed { .. } | Self::Failed { .. })
+    }
+}
+
+/// A signed transaction of an intent.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attempt {
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+    /// The [EIP-2718] encoding of the transaction.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    pub raw: Bytes,
+    /// The block number when the transaction was signed.
+    pub signed_at_block: u64,
+    /// Whether the node accepted the transaction. Not persisted, so that transactions are
+    /// rebroadcast after a restart.
+    #[serde(skip)]
+    pub accepted: bool,
+}
+
+/// Persistent storage for the intents of a [`TxBroker`].
+pub trait BrokerStore<N: Network = Ethereum>: Send + Sync {
+    /// Loads all the records.
+    fn load(&self) -> io::Result<Vec<BrokerRecord<N>>>;
+
+    /// Saves a record, replacing the previous record of the intent.
+    ///
+    /// The record must be durably stored when this returns, as transactions are broadcast right
+    /// after they are saved.
+    fn save(&self, record: &BrokerRecord<N>) -> io::Result<()>;
+}
+
+/// A [`BrokerStore`] saving each record to a JSON file in a directory.
+///
+/// Records are replaced atomically, by writing them to a temporary file first.
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Opens the store in the given directory, creating it if it does not exist.
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the directory of the store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl<N: Network> BrokerStore<N> for FileStore {
+    fn load(&self) -> io::Result<Vec<BrokerRecord<N>>> {
+        let mut records = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                records.push(serde_json::from_slice(&fs::read(path)?)?);
+            }
+        }
+        Ok(records)
+    }
+
+    fn save(&self, record: &BrokerRecord<N>) -> io::Result<()> {
+        let tmp = self.dir.join(format!("{}.tmp", record.id));
+        let file = fs::File::create(&tmp)?;
+        serde_json::to_writer(&file, record)?;
+        file.sync_all()?;
+        fs::rename(tmp, self.dir.join(format!("{}.json", record.id)))
+    }
+}
+
+/// A [`BrokerStore`] keeping records in memory. Records do not survive restarts.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    records: Mutex<BTreeMap<IntentId, serde_json::Value>>,
+}
+
+impl<N: Network> BrokerStore<N> for MemoryStore {
+    fn load(&self) -> io::Result<Vec<BrokerRecord<N>>> {
+        let records = self.records.lock().unwrap();
+        records.values().map(|record| Ok(serde_json::from_value(record.clone())?)).collect()
+    }
+
+    fn save(&self, record: &BrokerRecord<N>) -> io::Result<()> {
+        let value = serde_json::to_value(record)?;
+        self.records.lock().unwrap().insert(record.id, value);
+        Ok(())
+    }
+}
+
+/// The configuration of a [`TxBroker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BrokerConfig {
+    bump_after_blocks: u64,
+    bump_percent: u64,
+    max_bumps: usize,
+}
+
+impl Default for BrokerConfig {
+    fn default() -> Self {
+        Self { bump_after_blocks: 3, bump_percent: 20, max_bumps: 5 }
+    }
+}
+
+impl BrokerConfig {
+    /// Sets the number of blocks after which the fees of a pending transaction are bumped.
+    /// Defaults to 3.
+    pub const fn with_bump_after_blocks(mut self, blocks: u64) -> Self {
+        self.bump_after_blocks = blocks;
+        self
+    }
+
+    /// Sets the percentage by which fees are bumped. Nodes usually require at least 10% to
+    /// replace a transaction. Defaults to 20.
+    pub const fn with_bump_percent(mut self, percent: u64) -> Self {
+        self.bump_percent = percent;
+        self
+    }
+
+    /// Sets the maximum number of times the fees of an intent are bumped. Defaults to 5.
+    pub const fn with_max_bumps(mut self, max_bumps: usize) -> Self {
+        self.max_bumps = max_bumps;
+        self
+    }
+}
+
+/// An error of a [`TxBroker`].
+#[derive(Debug)]
+pub enum BrokerError {
+    /// The store failed.
+    Store(io::Error),
+    /// A request to the node failed.
+    Transport(TransportError),
+    /// Signing a transaction failed.
+    Signing(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl fmt::Display for BrokerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Store(err) => write!(f, "broker store error: {err}"),
+            Self::Transport(err) => write!(f, "broker transport error: {err}"),
+            Self::Signing(err) => write!(f, "broker signing error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for BrokerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Store(err) => Some(err),
+            Self::Transport(err) => Some(err),
+            Self::Signing(err) => Some(&**err),
+        }
+    }
+}
+
+impl From<io::Error> for BrokerError {
+    fn from(err: io::Error) -> Self {
+        Self::Store(err)
+    }
+}
+
+impl From<TransportError> for BrokerError {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+/// Drives transaction intents to a terminal state, surviving restarts.
+///
+/// Intents are [enqueued](Self::enqueue) and processed on every [`poll`](Self::poll), which should
+/// be called regularly, e.g. on every new block:
+/// - Queued intents are assigned the next nonce of the sender, filled in with their gas limit and
+///   fees if missing, signed, saved, and broadcast, in the order they were enqueued. Intents
+///   rejected by the node fail, and release their nonce.
+/// - Pending intents are confirmed once one of their transactions is included. Their fees are
+///   bumped, and the replacement broadcast, when none was included for a number of blocks. They
+///   fail if their nonce is used by another transaction.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(wallet: alloy_network::EthereumWallet) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_network::TransactionBuilder;
+/// use alloy_primitives::{Address, U256};
+/// use alloy_provider::{
+///     broker::{BrokerConfig, FileStore, TxBroker},
+///     ProviderBuilder,
+/// };
+/// use alloy_rpc_types_eth::TransactionRequest;
+///
+/// let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse()?);
+/// let store = FileStore::open("broker")?;
+/// let mut broker = TxBroker::new(provider, wallet, store, BrokerConfig::default())?;
+///
+/// let id = broker.enqueue(
+///     TransactionRequest::default().with_to(Address::ZERO).with_value(U256::from(1)),
+/// )?;
+/// while !broker.state(id).unwrap().is_terminal() {
+///     broker.poll().await?;
+///     tokio::time::sleep(std::time::Duration::from_secs(12)).await;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TxBroker<P, W, S, T, N: Network = Ethereum> {
+    provider: P,
+    wallet: W,
+    store: S,
+    config: BrokerConfig,
+    from: Address,
+    records: BTreeMap<IntentId, BrokerRecord<N>>,
+    next_nonce: Option<u64>,
+    _pd: PhantomData<fn() -> T>,
+}
+
+impl<P, W, S, T, N> TxBroker<P, W, S, T, N>
+where
+    P: Provider<T, N>,
+    W: NetworkWallet<N>,
+    S: BrokerStore<N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a broker sending transactions from the default signer of the wallet, and reloads
+    /// the intents saved in the store.
+    pub fn new(
+        provider: P,
+        wallet: W,
+        store: S,
+        config: BrokerConfig,
+    ) -> Result<Self, BrokerError> {
+        let records = store.load()?.into_iter().map(|record| (record.id, record)).collect();
+        let from = wallet.default_signer_address();
+        Ok(Self {
+            provider,
+            wallet,
+            store,
+            config,
+            from,
+            records,
+            next_nonce: None,
+            _pd: PhantomData,
+        })
+    }
+
+    /// Returns the address transactions are sent from.
+    pub const fn from(&self) -> Address {
+        self.from
+    }
+
+    /// Returns the provider.
+    pub const fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Returns the state of an intent.
+    pub fn state(&self, id: IntentId) -> Option<&IntentState> {
+        self.records.get(&id).map(|record| &record.state)
+    }
+
+    /// Returns the records of all the intents.
+    pub fn records(&self) -> impl Iterator<Item = &BrokerRecord<N>> {
+        self.records.values()
+    }
+
+    /// Enqueues an intent, returning its identifier once it is saved.
+    ///
+    /// The sender, nonce, and chain ID of the request are always set by the broker. Its gas limit
+    /// and fees are only estimated if missing.
+    pub fn enqueue(&mut self, request: N::TransactionRequest) -> Result<IntentId, BrokerError> {
+        let id = self.records.keys().next_back().map_or(0, |id| id + 1);
+        let record = BrokerRecord { id, request, state: IntentState::Queued };
+        self.store.save(&record)?;
+        self.records.insert(id, record);
+        Ok(id)
+    }
+
+    /// Processes the intents once: monitors, bumps, and rebroadcasts the pending ones, then
+    /// submits the queued ones.
+    pub async fn poll(&mut self) -> Result<(), BrokerError> {
+        let block = self.provider.get_block_number().await?;
+        let pending = self.ids(|state| matches!(state, IntentState::Pending { .. }));
+        if !pending.is_empty() {
+            let nonce = self.provider.get_transaction_count(self.from).await?;
+            for id in pending {
+                self.monitor(id, block, nonce).await?;
+            }
+        }
+
+        for id in self.ids(|state| matches!(state, IntentState::Queued)) {
+            self.submit(id, block).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the identifiers of the intents in the given state, in order.
+    fn ids(&self, state: fn(&IntentState) -> bool) -> Vec<IntentId> {
+        self.records
+            .values()
+            .filter(|record| state(&record.state))
+            .map(|record| record.id)
+            .collect()
+    }
+
+    /// Returns the next nonce to assign.
+    async fn next_nonce(&mut self) -> Result<u64, BrokerError> {
+        if let Some(nonce) = self.next_nonce {
+            return Ok(nonce);
+        }
+        let chain = self.provider.get_transaction_count(self.from).pending().await?;
+        let assigned = self
+            .records
+            .values()
+            .filter_map(|record| match record.state {
+                IntentState::Pending { nonce, .. } => Some(nonce + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+        Ok(chain.max(assigned))
+    }
+
+    /// Signs a transaction for the given request.
+    async fn sign(
+        &self,
+        request: N::TransactionRequest,
+        block: u64,
+    ) -> Result<Attempt, BrokerError> {
+        let envelope = self
+            .wallet
+            .sign_request(request)
+            .await
+            .map_err(|err| BrokerError::Signing(Box::new(err)))?;
+        let raw = Bytes::from(envelope.encoded_2718());
+        Ok(Attempt { tx_hash: keccak256(&raw), raw, signed_at_block: block, accepted: false })
+    }
+
+    /// Saves the record of an intent with the given state.
+    fn update(&mut self, id: IntentId, state: IntentState) -> Result<(), BrokerError> {
+        let record = self.records.get_mut(&id).expect("known intent");
+        record.state = state;
+        self.store.save(record)?;
+        Ok(())
+    }
+
+    /// Submits a queued intent.
+    async fn submit(&mut self, id: IntentId, block: u64) -> Result<(), BrokerError> {
+        let nonce = self.next_nonce().await?;
+        let mut request = self.records[&id].request.clone();
+        request.set_from(self.from);
+        request.set_nonce(nonce);
+        request.set_chain_id(self.provider.get_chain_id().await?);
+        if request.gas_limit().is_none() {
+            match self.provider.estimate_gas(&request).await {
+                Ok(gas_limit) => request.set_gas_limit(gas_limit),
+                Err(err) => return self.update(id, IntentState::Failed { error: err.to_string() }),
+            }
+        }
+        if request.gas_price().is_none() && request.max_fee_per_gas().is_none() {
+            let fees = self.provider.estimate_eip1559_fees(None).await?;
+            request.set_max_fee_per_gas(fees.max_fee_per_gas);
+            request.set_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        }
+
+        let attempt = self.sign(request.clone(), block).await?;
+        let raw = attempt.raw.clone();
+        self.records.get_mut(&id).expect("known intent").request = request;
+        self.update(id, IntentState::Pending { nonce, attempts: vec![attempt] })?;
+        self.next_nonce = Some(nonce + 1);
+
+        match self.broadcast(&raw).await {
+            Err(RpcError::ErrorResp(err)) => {
+                // The transaction was rejected, so the nonce can be reused.
+                self.next_nonce = Some(nonce);
+                self.update(id, IntentState::Failed { error: err.to_string() })
+            }
+            result => {
+                self.set_accepted(id, result.is_ok());
+                Ok(())
+            }
+        }
+    }
+
+    /// Monitors a pending intent, given the current block, and the nonce of the sender.
+    async fn monitor(&mut self, id: IntentId, block: u64, nonce: u64) -> Result<(), BrokerError> {
+        let IntentState::Pending { nonce: assigned, attempts } = &self.records[&id].state else {
+            return Ok(());
+        };
+        let (assigned, attempts) = (*assigned, attempts.clone());
+
+        for attempt in attempts.iter().rev() {
+            if let Some(receipt) = self.provider.get_transaction_receipt(attempt.tx_hash).await? {
+                let state =
+                    IntentState::Confirmed { tx_hash: attempt.tx_hash, success: receipt.status() };
+                return self.update(id, state);
+            }
+        }
+        if nonce > assigned {
+            let error = format!("nonce {assigned} was used by another transaction");
+            return self.update(id, IntentState::Failed { error });
+        }
+
+        let last = attempts.last().expect("pending intents have an attempt");
+        let bumps = attempts.len() - 1;
+        if block >= last.signed_at_block + self.config.bump_after_blocks
+            && bumps < self.config.max_bumps
+        {
+            let request = self.bump(id);
+            let attempt = self.sign(request, block).await?;
+            let raw = attempt.raw.clone();
+            let mut attempts = attempts;
+            attempts.push(attempt);
+            self.update(id, IntentState::Pending { nonce: assigned, attempts })?;
+            let result = self.broadcast(&raw).await;
+            self.set_accepted(id, result.is_ok());
+        } else if !last.accepted {
+            let result = self.broadcast(&last.raw.clone()).await;
+            // The node already knowing the transaction is reported as an error.
+            self.set_accepted(id, !matches!(result, Err(RpcError::Transport(_))));
+        }
+        Ok(())
+    }
+
+    /// Bumps the fees of the request of an intent, returning the new request.
+    fn bump(&mut self, id: IntentId) -> N::TransactionRequest {
+        let percent = self.config.bump_percent as u128;
+        let bump = |fee: u128| fee + (fee * percent).div_ceil(100).max(1);
+
+        let request = &mut self.records.get_mut(&id).expect("known intent").request;
+        if let Some(max_fee) = request.max_fee_per_gas() {
+            request.set_max_fee_per_gas(bump(max_fee));
+            let priority_fee = request.max_priority_fee_per_gas().unwrap_or_default();
+            request.set_max_priority_fee_per_gas(bump(priority_fee));
+        } else if let Some(gas_price) = request.gas_price() {
+            request.set_gas_price(bump(gas_price));
+        }
+        request.clone()
+    }
+
+    /// Marks the last attempt of an intent as accepted by the node, or not.
+    fn set_accepted(&mut self, id: IntentId, accepted: bool) {
+        let record = self.records.get_mut(&id).expect("known intent");
+        if let IntentState::Pending { attempts, .. } = &mut record.state {
+            if let Some(attempt) = attempts.last_mut() {
+                attempt.accepted = accepted;
+            }
+        }
+    }
+
+    /// Broadcasts a raw transaction.
+    async fn broadcast(&self, raw: &Bytes) -> Result<TxHash, TransportError> {
+        let result =
+            self.provider.send_raw_transaction(raw).await.map(|pending| *pending.tx_hash());
+        if let Err(err) = &result {
+            debug!(tx_hash = %keccak256(raw), %err, "failed to broadcast transaction");
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{rpc_error, MockTransport},
+        RootProvider,
+    };
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_network::EthereumWallet;
+    use alloy_primitives::{U256, U64};
+    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_signer_local::PrivateKeySigner;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+
+    /// A node including the transactions it is told to.
+    #[derive(Debug, Default)]
+    struct Node {
+        block: u64,
+        /// The nonce of the sender.
+        nonce: u64,
+        /// The transactions received, with their max fee per gas.
+        received: Vec<(TxHash, u128)>,
+        included: Vec<TxHash>,
+        /// Whether to reject transactions.
+        reject: bool,
+    }
+
+    fn provider(node: Arc<Mutex<Node>>) -> RootProvider<MockTransport> {
+        let transport = MockTransport::new(move |method, params| {
+            let mut node = node.lock().unwrap();
+            match method {
+                "eth_blockNumber" => Ok(json!(U64::from(node.block))),
+                "eth_chainId" => Ok(json!("0x1")),
+                "eth_getTransactionCount" => Ok(json!(U64::from(node.nonce))),
+                "eth_estimateGas" => Ok(json!("0x5208")),
+                "eth_sendRawTransaction" if node.reject => {
+                    Err(rpc_error(-32000, "insufficient funds"))
+                }
+                "eth_sendRawTransaction" => {
+                    let raw: Bytes = serde_json::from_value(params[0].clone()).unwrap();
+                    let tx = alloy_consensus::TxEnvelope::decode_2718(&mut &raw[..]).unwrap();
+                    let tx_hash = *tx.tx_hash();
+                    let request = <TransactionRequest as From<_>>::from(tx);
+                    node.received.push((tx_hash, request.max_fee_per_gas.unwrap()));
+                    Ok(json!(tx_hash))
+                }
+                "eth_getTransactionReceipt" => {
+                    let tx_hash: TxHash = serde_json::from_value(params[0].clone()).unwrap();
+                    Ok(if node.included.contains(&tx_hash) {
+                        receipt(tx_hash)
+                    } else {
+                        Value::Null
+                    })
+                }
+                method => unreachable!("{method}"),
+            }
+        });
+        transport.provider()
+    }
+
+    fn receipt(tx_hash: TxHash) -> Value {
+        json!({
+            "transactionHash": tx_hash,
+            "transactionIndex": "0x0",
+            "blockHash": TxHash::ZERO,
+            "blockNumber": "0x1",
+            "from": Address::ZERO,
+            "to": Address::ZERO,
+            "contractAddress": null,
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x64",
+            "logs": [],
+            "logsBloom": alloy_primitives::Bloom::ZERO,
+            "status": "0x1",
+            "type": "0x2",
+        })
+    }
+
+    fn request() -> TransactionRequest {
+        TransactionRequest::default()
+            .with_to(Address::ZERO)
+            .with_value(U256::from(1))
+            .with_max_fee_per_gas(100)
+            .with_max_priority_fee_per_gas(10)
+    }
+
+    #[tokio::test]
+    async fn drives_intents_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let node = Arc::new(Mutex::new(Node::default()));
+        let wallet = EthereumWallet::from(PrivateKeySigner::random());
+        let config = BrokerConfig::default().with_bump_after_blocks(2);
+        let open = || {
+            let store = FileStore::open(dir.path()).unwrap();
+            TxBroker::new(provider(node.clone()), wallet.clone(), store, config).unwrap()
+        };
+
+        let mut first = open();
+        let a = first.enqueue(request()).unwrap();
+        let b = first.enqueue(request()).unwrap();
+        first.poll().await.unwrap();
+        let nonces = [a, b].map(|id| match first.state(id).unwrap() {
+            IntentState::Pending { nonce, .. } => *nonce,
+            state => panic!("{state:?}"),
+        });
+        assert_eq!(nonces, [0, 1]);
+        assert_eq!(node.lock().unwrap().received.len(), 2);
+
+        // A restarted broker rebroadcasts the pending transactions.
+        drop(first);
+        let mut broker = open();
+        broker.poll().await.unwrap();
+        assert_eq!(node.lock().unwrap().received.len(), 4);
+
+        // Stuck transactions are bumped.
+        node.lock().unwrap().block = 2;
+        broker.poll().await.unwrap();
+        let (bumped, max_fee) = *node.lock().unwrap().received.last().unwrap();
+        assert_eq!(max_fee, 120);
+
+        // The bumped transaction of `b` is included, while `a` is replaced by another transaction.
+        {
+            let mut node = node.lock().unwrap();
+            node.included.push(bumped);
+            node.nonce = 2;
+        }
+        broker.poll().await.unwrap();
+        assert_eq!(
+            broker.state(b).unwrap(),
+            &IntentState::Confirmed { tx_hash: bumped, success: true }
+        );
+        assert!(matches!(broker.state(a).unwrap(), IntentState::Failed { .. }));
+
+        // Rejected transactions fail, and release their nonce.
+        node.lock().unwrap().reject = true;
+        let c = broker.enqueue(request()).unwrap();
+        broker.poll().await.unwrap();
+        assert!(matches!(broker.state(c).unwrap(), IntentState::Failed { .. }));
+        node.lock().unwrap().reject = false;
+        let d = broker.enqueue(request()).unwrap();
+        broker.poll().await.unwrap();
+        assert!(matches!(broker.state(d).unwrap(), IntentState::Pending { nonce: 2, .. }));
+
+        // The state survives restarts.
+        let restarted = open();
+        assert_eq!(restarted.records().count(), 4);
+        assert_eq!(restarted.state(b), broker.state(b));
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
     fillers::{
-        ChainIdFiller, FillerControlFlow, GasFiller, JoinFill, NonceFiller, RecommendedFiller,
-        TxFiller, WalletFiller,
+        ChainIdFiller, CoordinatedNonceFiller, FillerControlFlow, GasFiller, JoinFill,
+        NonceCoordinator, NonceFiller, Policy, PolicyFiller, RecommendedFiller, TxFiller,
+        WalletFiller,
     },
     provider::SendableTx,
-    Provider, RootProvider,
+    DynProvider, Provider, ProviderConfig, RootProvider,
 };
 use alloy_chains::NamedChain;
 use alloy_network::{Ethereum, Network};
@@ -122,6 +123,39 @@ impl ProviderBuilder<Identity, Identity, Ethereum> {
     }
 }
 
+impl<N: Network> ProviderBuilder<Identity, Identity, N> {
+    /// Builds a provider from a [`ProviderConfig`], e.g. read from a configuration file.
+    ///
+    /// Connects to the endpoint, checks that it serves the configured chain, and erases the type
+    /// of the resulting stack, which varies with the configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_provider::{DynProvider, Provider, ProviderBuilder, ProviderConfig};
+    ///
+    /// let config: ProviderConfig = serde_json::from_str(&std::fs::read_to_string("provider.json")?)?;
+    /// let provider: DynProvider = ProviderBuilder::from_config(&config).await?;
+    /// let block_number = provider.get_block_number().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_config(config: &ProviderConfig) -> TransportResult<DynProvider<N>> {
+        let client = config.client().await?;
+        let fillers = config.fillers;
+        let chain_id = config.chain.map(|chain| chain.id);
+        let builder = Self::default()
+            .filler(fillers.gas.then(GasFiller::default))
+            .filler(fillers.nonce.then(NonceFiller::default))
+            .filler(fillers.chain_id.then(|| ChainIdFiller::new(chain_id)));
+        Ok(match config.broadcast_layer().await? {
+            Some(broadcast) => builder.layer(broadcast).on_client(client).erased(),
+            None => builder.on_client(client).erased(),
+        })
+    }
+}
+
 impl<N> Default for ProviderBuilder<Identity, Identity, N> {
     fn default() -> Self {
         Self { layer: Identity, filler: Identity, network: PhantomData }
@@ -132,14 +166,16 @@ impl<L, N> ProviderBuilder<L, Identity, N> {
     /// Add preconfigured set of layers handling gas estimation, nonce
     /// management, and chain-id fetching.
     pub fn with_recommended_fillers(self) -> ProviderBuilder<L, RecommendedFiller, N> {
-        self.filler(GasFiller).filler(NonceFiller::default()).filler(ChainIdFiller::default())
+        self.filler(GasFiller::default())
+            .filler(NonceFiller::default())
+            .filler(ChainIdFiller::default())
     }
 
     /// Add gas estimation to the stack being built.
     ///
     /// See [`GasFiller`]
     pub fn with_gas_estimation(self) -> ProviderBuilder<L, JoinFill<Identity, GasFiller>, N> {
-        self.filler(GasFiller)
+        self.filler(GasFiller::default())
     }
 
     /// Add nonce management to the stack being built.
@@ -149,6 +185,16 @@ impl<L, N> ProviderBuilder<L, Identity, N> {
         self.filler(NonceFiller::default())
     }
 
+    /// Add nonce management coordinated with other processes to the stack being built.
+    ///
+    /// See [`CoordinatedNonceFiller`]
+    pub fn with_coordinated_nonce_management(
+        self,
+        coordinator: impl NonceCoordinator + 'static,
+    ) -> ProviderBuilder<L, JoinFill<Identity, CoordinatedNonceFiller>, N> {
+        self.filler(CoordinatedNonceFiller::new(coordinator))
+    }
+
     /// Add a chain ID filler to the stack being built. The filler will attempt
     /// to fetch the chain ID from the provider using
     /// [`Provider::get_chain_id`]. the first time a transaction is prepared,
@@ -206,6 +252,18 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         self.filler(WalletFiller::new(wallet))
     }
 
+    /// Add a wallet layer to the stack being built, enforcing the given policy before signing
+    /// transactions.
+    ///
+    /// See [`PolicyFiller`].
+    pub fn wallet_with_policy<W>(
+        self,
+        wallet: W,
+        policy: Policy,
+    ) -> ProviderBuilder<L, JoinFill<F, PolicyFiller<WalletFiller<W>>>, N> {
+        self.filler(PolicyFiller::new(WalletFiller::new(wallet), policy))
+    }
+
     /// Change the network.
     ///
     /// By default, the network is `Ethereum`. This method must be called to configure a different
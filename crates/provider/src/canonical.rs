@@ -0,0 +1,357 @@
+//! Canonical chain tracking and reorg detection.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+use alloy_rpc_client::WeakClient;
+use alloy_rpc_types_eth::Block;
+use alloy_transport::{utils::Spawnable, Transport};
+use futures::{Stream, StreamExt};
+use std::collections::VecDeque;
+use tokio::{
+    select,
+    sync::{broadcast, watch},
+};
+
+/// The default number of blocks tracked by a [`CanonicalChainTracker`], bounding the depth of
+/// the reorgs it can attribute to a common ancestor.
+pub(crate) const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// The capacity of the event channel of a [`CanonicalChainTracker`].
+const EVENT_CHANNEL_SIZE: usize = 64;
+
+/// A change of the canonical chain, broadcast by a [`CanonicalChainTracker`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainEvent {
+    /// The chain was extended by a block, on top of the previous head.
+    Extended(BlockNumHash),
+    /// The chain was reorged: the `old` blocks were replaced by the `new` ones, both ordered by
+    /// increasing number.
+    Reorged {
+        /// The blocks that are no longer canonical.
+        old: Vec<BlockNumHash>,
+        /// The blocks that replaced them, up to the new head.
+        new: Vec<BlockNumHash>,
+        /// The number of blocks that are no longer canonical.
+        depth: u64,
+    },
+}
+
+/// A handle to a task tracking the canonical chain, returned by
+/// [`Provider::canonical_chain_tracker`](crate::Provider::canonical_chain_tracker).
+///
+/// The task follows the new heads of the node, and walks back the parents of each new head
+/// until it reaches a block it tracks. A new head whose parent hash disagrees with the tracked
+/// block of the same number is a reorg, reported with the replaced blocks. Heads skipping
+/// blocks are filled in, so each new canonical block is reported once.
+///
+/// The handle can be cloned to share one tracker between components, each
+/// [subscribing](Self::subscribe) to the events. The task stops once all the handles are
+/// dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::ChainEvent;
+///
+/// let tracker = provider.canonical_chain_tracker().await?;
+/// let mut events = tracker.subscribe();
+/// while let Ok(event) = events.recv().await {
+///     match event {
+///         ChainEvent::Extended(block) => println!("new block {}", block.number),
+///         ChainEvent::Reorged { depth, new, .. } => {
+///             println!("reorg of depth {depth}, new head {:?}", new.last())
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CanonicalChainTracker {
+    events: broadcast::Sender<ChainEvent>,
+    head: watch::Receiver<Option<BlockNumHash>>,
+}
+
+impl CanonicalChainTracker {
+    /// Subscribes to the events of the tracker, from now on.
+    ///
+    /// Receivers lagging more than 64 events behind miss the oldest ones, see
+    /// [`broadcast::Receiver::recv`].
+    pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns the current head of the tracked chain, or `None` if no head was received yet.
+    pub fn head(&self) -> Option<BlockNumHash> {
+        *self.head.borrow()
+    }
+}
+
+/// A tracked block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ChainBlock {
+    number: u64,
+    hash: B256,
+    parent_hash: B256,
+}
+
+impl ChainBlock {
+    fn from_block(block: &Block) -> Option<Self> {
+        let header = &block.header;
+        Some(Self { number: header.number?, hash: header.hash?, parent_hash: header.parent_hash })
+    }
+
+    const fn num_hash(&self) -> BlockNumHash {
+        BlockNumHash::new(self.number, self.hash)
+    }
+}
+
+/// The last blocks of the canonical chain, ordered by increasing number.
+#[derive(Debug)]
+struct CanonicalChain {
+    blocks: VecDeque<ChainBlock>,
+    max_depth: usize,
+}
+
+impl CanonicalChain {
+    fn new(max_depth: usize) -> Self {
+        Self { blocks: VecDeque::new(), max_depth: max_depth.max(1) }
+    }
+
+    fn contains(&self, hash: B256) -> bool {
+        self.blocks.iter().any(|block| block.hash == hash)
+    }
+
+    /// Returns whether `block` connects to the tracked chain, or goes below it.
+    fn connects(&self, block: &ChainBlock) -> bool {
+        let Some(first) = self.blocks.front() else { return true };
+        if block.number <= first.number {
+            return true;
+        }
+        let index = (block.number - 1 - first.number) as usize;
+        self.blocks.get(index).is_some_and(|parent| parent.hash == block.parent_hash)
+    }
+
+    /// Applies `segment`, a chain of blocks connecting to the tracked chain, and returns the
+    /// resulting events.
+    fn apply(&mut self, segment: Vec<ChainBlock>) -> Vec<ChainEvent> {
+        let Some(first) = segment.first() else { return Vec::new() };
+        let was_empty = self.blocks.is_empty();
+        let keep = self.blocks.iter().take_while(|block| block.number < first.number).count();
+        let old = self.blocks.split_off(keep);
+        let new = segment.iter().map(ChainBlock::num_hash).collect::<Vec<_>>();
+        self.blocks.extend(segment);
+        let excess = self.blocks.len().saturating_sub(self.max_depth);
+        self.blocks.drain(..excess);
+
+        if was_empty {
+            Vec::new()
+        } else if old.is_empty() {
+            new.into_iter().map(ChainEvent::Extended).collect()
+        } else {
+            let depth = old.len() as u64;
+            let old = old.iter().map(ChainBlock::num_hash).collect();
+            vec![ChainEvent::Reorged { old, new, depth }]
+        }
+    }
+
+    fn head(&self) -> Option<BlockNumHash> {
+        self.blocks.back().map(ChainBlock::num_hash)
+    }
+}
+
+/// The task behind a [`CanonicalChainTracker`].
+pub(crate) struct ChainTracker<T, S> {
+    client: WeakClient<T>,
+    heads: S,
+    chain: CanonicalChain,
+}
+
+impl<T: Transport + Clone, S: Stream<Item = Block> + Unpin + 'static> ChainTracker<T, S> {
+    /// Walks back from `head` to `chain`, returning the blocks from the first one connecting to
+    /// it, or `None` if a parent could not be fetched.
+    async fn segment(
+        client: &WeakClient<T>,
+        chain: &CanonicalChain,
+        head: ChainBlock,
+    ) -> Option<Vec<ChainBlock>> {
+        let mut segment = VecDeque::from([head]);
+        while !chain.connects(&segment[0]) {
+            let client = client.upgrade()?;
+            let parent = client
+                .request::<_, Option<Block>>("eth_getBlockByHash", (segment[0].parent_hash, false))
+                .await;
+            match parent.ok().flatten().as_ref().and_then(ChainBlock::from_block) {
+                Some(parent) => segment.push_front(parent),
+                None => {
+                    debug!(hash = %segment[0].parent_hash, "failed to fetch parent block");
+                    return None;
+                }
+            }
+        }
+        Some(segment.into())
+    }
+
+    async fn into_future(
+        mut self,
+        events: broadcast::Sender<ChainEvent>,
+        head: watch::Sender<Option<BlockNumHash>>,
+    ) {
+        loop {
+            let block = select! {
+                block = self.heads.next() => match block {
+                    Some(block) => block,
+                    None => break,
+                },
+                _ = head.closed() => break,
+            };
+            let Some(block) = ChainBlock::from_block(&block) else { continue };
+            if self.chain.contains(block.hash) {
+                continue;
+            }
+            let Some(segment) = Self::segment(&self.client, &self.chain, block).await else {
+                continue;
+            };
+            for event in self.chain.apply(segment) {
+                trace!(?event, "canonical chain event");
+                let _ = events.send(event);
+            }
+            head.send_replace(self.chain.head());
+        }
+        debug!("canonical chain tracker stopped");
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: Transport + Clone, S: Stream<Item = Block> + Unpin + 'static> ChainTracker<T, S> {
+    /// Spawns the tracker task, returning its handle.
+    pub(crate) fn spawn(
+        client: WeakClient<T>,
+        heads: S,
+        max_depth: usize,
+    ) -> CanonicalChainTracker {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_SIZE);
+        let (head, head_rx) = watch::channel(None);
+        let tracker = Self { client, heads, chain: CanonicalChain::new(max_depth) };
+        tracker.into_future(events.clone(), head).spawn_task();
+        CanonicalChainTracker { events, head: head_rx }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Transport + Clone, S: Stream<Item = Block> + Unpin + Send + 'static> ChainTracker<T, S> {
+    /// Spawns the tracker task, returning its handle.
+    pub(crate) fn spawn(
+        client: WeakClient<T>,
+        heads: S,
+        max_depth: usize,
+    ) -> CanonicalChainTracker {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_SIZE);
+        let (head, head_rx) = watch::channel(None);
+        let tracker = Self { client, heads, chain: CanonicalChain::new(max_depth) };
+        tracker.into_future(events.clone(), head).spawn_task();
+        CanonicalChainTracker { events, head: head_rx }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Provider, RootProvider};
+    use alloy_rpc_types_eth::Header;
+    use serde_json::json;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// Returns the hash of block `number` of `fork`, which branches off fork 0 after block 10.
+    fn hash(number: u64, fork: u8) -> B256 {
+        let fork = if number <= 10 { 0 } else { fork };
+        let mut hash = B256::with_last_byte(fork);
+        hash[..8].copy_from_slice(&number.to_be_bytes());
+        hash
+    }
+
+    fn block(hash: B256) -> Block {
+        let number = u64::from_be_bytes(hash[..8].try_into().unwrap());
+        let header = Header {
+            number: Some(number),
+            hash: Some(hash),
+            parent_hash: self::hash(number - 1, hash[31]),
+            ..Default::default()
+        };
+        Block { header, ..Default::default() }
+    }
+
+    fn num_hash(number: u64, fork: u8) -> BlockNumHash {
+        BlockNumHash::new(number, hash(number, fork))
+    }
+
+    #[test]
+    fn applies_segments() {
+        let mut chain = CanonicalChain::new(3);
+        let segment = |range: std::ops::RangeInclusive<u64>, fork| {
+            range.map(|n| ChainBlock::from_block(&block(hash(n, fork))).unwrap()).collect()
+        };
+        assert_eq!(chain.apply(segment(9..=10, 0)), []);
+        assert_eq!(
+            chain.apply(segment(11..=12, 0)),
+            [ChainEvent::Extended(num_hash(11, 0)), ChainEvent::Extended(num_hash(12, 0))]
+        );
+        assert_eq!(chain.blocks.len(), 3);
+
+        let reorged = ChainBlock::from_block(&block(hash(12, 1))).unwrap();
+        assert!(!chain.connects(&reorged));
+        assert!(chain.connects(&ChainBlock::from_block(&block(hash(11, 1))).unwrap()));
+        assert_eq!(
+            chain.apply(segment(11..=12, 1)),
+            [ChainEvent::Reorged {
+                old: vec![num_hash(11, 0), num_hash(12, 0)],
+                new: vec![num_hash(11, 1), num_hash(12, 1)],
+                depth: 2,
+            }]
+        );
+        assert_eq!(chain.head(), Some(num_hash(12, 1)));
+    }
+
+    #[tokio::test]
+    async fn tracks_reorgs() {
+        let latest = Arc::new(Mutex::new(hash(10, 0)));
+        let node_latest = latest.clone();
+        let transport = MockTransport::new(move |method, params| {
+            let hash = match method {
+                "eth_getBlockByNumber" => *node_latest.lock().unwrap(),
+                "eth_getBlockByHash" => serde_json::from_value(params[0].clone()).unwrap(),
+                method => unreachable!("{method}"),
+            };
+            Ok(json!(block(hash)))
+        });
+        let client = transport.client().with_poll_interval(Duration::from_millis(10));
+        let provider = RootProvider::<_>::new(client);
+
+        let tracker = provider.canonical_chain_tracker().await.unwrap();
+        let mut events = tracker.subscribe();
+        while tracker.head().is_none() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(tracker.head(), Some(num_hash(10, 0)));
+
+        // The head skips block 11, which is filled in.
+        *latest.lock().unwrap() = hash(12, 0);
+        assert_eq!(events.recv().await.unwrap(), ChainEvent::Extended(num_hash(11, 0)));
+        assert_eq!(events.recv().await.unwrap(), ChainEvent::Extended(num_hash(12, 0)));
+
+        *latest.lock().unwrap() = hash(13, 1);
+        assert_eq!(
+            events.recv().await.unwrap(),
+            ChainEvent::Reorged {
+                old: vec![num_hash(11, 0), num_hash(12, 0)],
+                new: vec![num_hash(11, 1), num_hash(12, 1), num_hash(13, 1)],
+                depth: 2,
+            }
+        );
+        assert_eq!(tracker.head(), Some(num_hash(13, 1)));
+    }
+}
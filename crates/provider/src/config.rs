@@ -0,0 +1,317 @@
+//! Serializable provider configuration.
+
+use crate::layers::{BroadcastLayer, DedupLayer, ValidationLayer};
+use alloy_chains::NamedChain;
+use alloy_primitives::{ChainId, U64};
+use alloy_rpc_client::{BuiltInConnectionString, RpcClient};
+use alloy_transport::{
+    BoxTransport, BoxTransportConnect, Transport, TransportErrorKind, TransportResult,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+use tower::Layer;
+
+/// The configuration of a provider stack, built with
+/// [`ProviderBuilder::from_config`](crate::ProviderBuilder::from_config).
+///
+/// This lets services describe their provider in a configuration file, e.g. in TOML:
+///
+/// ```toml
+/// endpoint = { url = "wss://eth.example.com", transport = "ws" }
+/// broadcast = [{ url = "https://rpc.flashbots.net" }]
+/// layers = [{ type = "dedup" }]
+/// fillers = { nonce = false }
+/// chain = { id = 1 }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderConfig {
+    /// The endpoint requests are sent to.
+    pub endpoint: EndpointConfig,
+    /// The endpoints raw transactions are also broadcast to. See [`BroadcastLayer`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub broadcast: Vec<EndpointConfig>,
+    /// The layers wrapping the transport of the endpoint, from the innermost to the outermost.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<LayerConfig>,
+    /// The transaction fillers of the provider.
+    #[serde(default)]
+    pub fillers: FillerConfig,
+    /// The chain the endpoint is expected to serve.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainConfig>,
+    /// The poll interval of the client, in milliseconds.
+    ///
+    /// Defaults to the poll interval for the [chain](Self::chain), if known, or the default
+    /// interval of the client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll_interval_ms: Option<u64>,
+}
+
+impl ProviderConfig {
+    /// Creates a new configuration for the given endpoint, with the recommended fillers.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            endpoint: EndpointConfig::new(url),
+            broadcast: Vec::new(),
+            layers: Vec::new(),
+            fillers: FillerConfig::default(),
+            chain: None,
+            poll_interval_ms: None,
+        }
+    }
+
+    /// Connects to the endpoint, applying the layers, and checks the chain it serves.
+    pub(crate) async fn client(&self) -> TransportResult<RpcClient<BoxTransport>> {
+        let (transport, is_local) = self.endpoint.connect().await?;
+        let transport =
+            self.layers.iter().fold(transport, |transport, layer| layer.apply(transport));
+        let client = RpcClient::new(transport, is_local);
+
+        if let Some(chain) = &self.chain {
+            let chain_id = client.request::<_, U64>("eth_chainId", ()).await?.to::<ChainId>();
+            if chain_id != chain.id {
+                return Err(TransportErrorKind::custom_str(&format!(
+                    "expected chain ID {}, endpoint serves {chain_id}",
+                    chain.id
+                )));
+            }
+        }
+
+        let chain_interval = || {
+            let chain = NamedChain::try_from(self.chain.as_ref()?.id).ok()?;
+            // Same as the `ChainLayer`.
+            Some(chain.average_blocktime_hint()?.mul_f32(0.6)).filter(|_| !is_local)
+        };
+        if let Some(interval) =
+            self.poll_interval_ms.map(Duration::from_millis).or_else(chain_interval)
+        {
+            client.set_poll_interval(interval);
+        }
+        Ok(client)
+    }
+
+    /// Connects to the broadcast endpoints, returning `None` if there are none.
+    pub(crate) async fn broadcast_layer(&self) -> TransportResult<Option<BroadcastLayer>> {
+        if self.broadcast.is_empty() {
+            return Ok(None);
+        }
+        let mut endpoints = Vec::with_capacity(self.broadcast.len());
+        for endpoint in &self.broadcast {
+            let (transport, is_local) = endpoint.connect().await?;
+            endpoints.push(RpcClient::new(transport, is_local));
+        }
+        Ok(Some(BroadcastLayer::new(endpoints)))
+    }
+}
+
+/// The configuration of an endpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EndpointConfig {
+    /// The URL of the endpoint, or the path of its IPC socket.
+    pub url: String,
+    /// The kind of transport to connect to the endpoint with.
+    #[serde(default)]
+    pub transport: TransportKind,
+}
+
+impl EndpointConfig {
+    /// Creates a new configuration for the given endpoint, with the transport guessed from its
+    /// URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), transport: TransportKind::Auto }
+    }
+
+    /// Connects to the endpoint, returning its boxed transport and whether it is local.
+    async fn connect(&self) -> TransportResult<(BoxTransport, bool)> {
+        let connect: BuiltInConnectionString = match self.transport {
+            TransportKind::Auto => self.url.parse()?,
+            #[cfg(any(feature = "reqwest", feature = "hyper"))]
+            TransportKind::Http => BuiltInConnectionString::try_as_http(&self.url)?,
+            #[cfg(feature = "ws")]
+            TransportKind::Ws => BuiltInConnectionString::try_as_ws(&self.url)?,
+            #[cfg(feature = "ipc")]
+            TransportKind::Ipc => BuiltInConnectionString::try_as_ipc(&self.url)?,
+            #[allow(unreachable_patterns)]
+            kind => {
+                return Err(TransportErrorKind::custom_str(&format!(
+                    "the {kind} transport is not enabled"
+                )))
+            }
+        };
+        let is_local = connect.is_local();
+        let transport = match connect {
+            // The built-in connection only uses `reqwest` when the `hyper` feature is disabled.
+            #[cfg(feature = "reqwest")]
+            BuiltInConnectionString::Http(url) => {
+                alloy_transport_http::Http::<reqwest::Client>::new(url).boxed()
+            }
+            connect => connect.connect_boxed().await?,
+        };
+        Ok((transport, is_local))
+    }
+}
+
+/// The kind of transport of an [`EndpointConfig`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    /// Guess the transport from the URL, like
+    /// [`ProviderBuilder::on_builtin`](crate::ProviderBuilder::on_builtin).
+    #[default]
+    Auto,
+    /// HTTP, requiring the `reqwest` or `hyper` feature.
+    Http,
+    /// WebSocket, requiring the `ws` feature.
+    Ws,
+    /// IPC, requiring the `ipc` feature.
+    Ipc,
+}
+
+impl fmt::Display for TransportKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Http => "HTTP",
+            Self::Ws => "WebSocket",
+            Self::Ipc => "IPC",
+        })
+    }
+}
+
+/// The configuration of a layer wrapping the transport of a [`ProviderConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum LayerConfig {
+    /// A [`DedupLayer`] with the default filter.
+    Dedup,
+    /// A [`ValidationLayer`] with the default schemas, logging divergences.
+    Validation,
+}
+
+impl LayerConfig {
+    /// Wraps `transport` in the layer.
+    fn apply(self, transport: BoxTransport) -> BoxTransport {
+        match self {
+            Self::Dedup => DedupLayer::new().layer(transport).boxed(),
+            Self::Validation => ValidationLayer::new().layer(transport).boxed(),
+        }
+    }
+}
+
+/// The configuration of the transaction fillers of a [`ProviderConfig`].
+///
+/// All fillers are enabled by default, like
+/// [`ProviderBuilder::with_recommended_fillers`](crate::ProviderBuilder::with_recommended_fillers).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FillerConfig {
+    /// Whether to fill the gas limit and fees with the [`GasFiller`](crate::fillers::GasFiller).
+    pub gas: bool,
+    /// Whether to fill nonces with the [`NonceFiller`](crate::fillers::NonceFiller).
+    pub nonce: bool,
+    /// Whether to fill the chain ID with the [`ChainIdFiller`](crate::fillers::ChainIdFiller),
+    /// with the ID of the [chain](ProviderConfig::chain) if set, or fetched from the endpoint.
+    pub chain_id: bool,
+}
+
+impl Default for FillerConfig {
+    fn default() -> Self {
+        Self { gas: true, nonce: true, chain_id: true }
+    }
+}
+
+/// The chain expected to be served by the endpoint of a [`ProviderConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainConfig {
+    /// The chain ID, checked against the one returned by the endpoint when building the provider.
+    pub id: ChainId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DynProvider, ProviderBuilder};
+    use alloy_network::Ethereum;
+    use serde_json::json;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    #[test]
+    fn deserializes_config() {
+        let config: ProviderConfig = serde_json::from_value(json!({
+            "endpoint": { "url": "http://localhost:8545", "transport": "http" },
+            "layers": [{ "type": "dedup" }, { "type": "validation" }],
+            "fillers": { "nonce": false },
+            "chain": { "id": 31337 },
+        }))
+        .unwrap();
+        assert_eq!(
+            config,
+            ProviderConfig {
+                endpoint: EndpointConfig {
+                    url: "http://localhost:8545".to_string(),
+                    transport: TransportKind::Http,
+                },
+                layers: vec![LayerConfig::Dedup, LayerConfig::Validation],
+                fillers: FillerConfig { gas: true, nonce: false, chain_id: true },
+                chain: Some(ChainConfig { id: 31337 }),
+                ..ProviderConfig::new("")
+            }
+        );
+        let roundtrip = serde_json::to_value(&config).unwrap();
+        assert_eq!(serde_json::from_value::<ProviderConfig>(roundtrip).unwrap(), config);
+
+        assert!(serde_json::from_value::<ProviderConfig>(json!({
+            "endpoint": { "url": "http://localhost:8545" },
+            "layer": [],
+        }))
+        .is_err());
+    }
+
+    /// Serves `eth_chainId` requests over HTTP, returning the URL of the server.
+    async fn serve_chain_id(chain_id: u64) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 4096];
+                let len = stream.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..len]);
+                let body: serde_json::Value =
+                    serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+                let body =
+                    json!({ "jsonrpc": "2.0", "id": body["id"], "result": U64::from(chain_id) })
+                        .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn builds_from_config() {
+        let url = serve_chain_id(1).await;
+        let mut config = ProviderConfig::new(url);
+        config.layers = vec![LayerConfig::Dedup];
+        config.chain = Some(ChainConfig { id: 1 });
+        config.poll_interval_ms = Some(50);
+        let provider: DynProvider = ProviderBuilder::from_config(&config).await.unwrap();
+        assert_eq!(provider.client().poll_interval(), Duration::from_millis(50));
+        assert_eq!(provider.get_chain_id().await.unwrap(), 1);
+
+        config.chain = Some(ChainConfig { id: 10 });
+        let result = ProviderBuilder::<_, _, Ethereum>::from_config(&config).await;
+        let err = result.err().unwrap();
+        assert!(err.to_string().contains("expected chain ID 10"), "{err}");
+    }
+}
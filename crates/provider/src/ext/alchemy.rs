@@ -0,0 +1,357 @@
+//! This module extends the Ethereum JSON-RPC provider with the Alchemy enhanced APIs.
+//!
+//! Only Alchemy is covered for now: the enhanced APIs of other providers, such as Infura, are
+//! left to follow-up work.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::Address;
+use alloy_rpc_types_alchemy::{
+    AssetTransfer, AssetTransferCategory, AssetTransfersRequest, AssetTransfersResponse,
+    TokenBalances, TokenBalancesSpec, TokenMetadata,
+};
+use alloy_rpc_types_trace::{
+    filter::{TraceFilter, TraceFilterMode},
+    parity::LocalizedTransactionTrace,
+};
+use alloy_transport::{Transport, TransportResult};
+
+/// Alchemy enhanced API interface, for the indexed transfer and token methods served by Alchemy
+/// nodes.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait AlchemyApi<N, T>: Send + Sync {
+    /// Returns a page of the asset transfers matching the request.
+    async fn alchemy_get_asset_transfers(
+        &self,
+        request: &AssetTransfersRequest,
+    ) -> TransportResult<AssetTransfersResponse>;
+
+    /// Returns all the asset transfers matching the request, following the page keys until the
+    /// last page.
+    async fn alchemy_get_all_asset_transfers(
+        &self,
+        request: AssetTransfersRequest,
+    ) -> TransportResult<Vec<AssetTransfer>>;
+
+    /// Returns all the transfers of the given categories sent or received by `address`, ordered
+    /// by block number.
+    ///
+    /// The Alchemy API filters on the sender and the recipient together, so this fetches both
+    /// directions and merges them.
+    async fn alchemy_get_address_transfers(
+        &self,
+        address: Address,
+        categories: Vec<AssetTransferCategory>,
+    ) -> TransportResult<Vec<AssetTransfer>>;
+
+    /// Returns the balances of the given tokens held by `address`.
+    async fn alchemy_get_token_balances(
+        &self,
+        address: Address,
+        tokens: TokenBalancesSpec,
+    ) -> TransportResult<TokenBalances>;
+
+    /// Returns the metadata of the token at `contract`.
+    async fn alchemy_get_token_metadata(&self, contract: Address)
+        -> TransportResult<TokenMetadata>;
+
+    /// Returns the traces of the calls sent by `address` between `from_block` and `to_block`,
+    /// inclusive, with the `trace_filter` method of the Alchemy Trace API.
+    async fn alchemy_get_traces_from(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>>;
+
+    /// Returns the traces of the calls received by `address` between `from_block` and
+    /// `to_block`, inclusive, with the `trace_filter` method of the Alchemy Trace API.
+    async fn alchemy_get_traces_to(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>>;
+
+    /// Returns the traces of the calls sent or received by `address` between `from_block` and
+    /// `to_block`, inclusive, with the `trace_filter` method of the Alchemy Trace API.
+    async fn alchemy_get_address_traces(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> AlchemyApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn alchemy_get_asset_transfers(
+        &self,
+        request: &AssetTransfersRequest,
+    ) -> TransportResult<AssetTransfersResponse> {
+        self.client().request("alchemy_getAssetTransfers", (request,)).await
+    }
+
+    async fn alchemy_get_all_asset_transfers(
+        &self,
+        mut request: AssetTransfersRequest,
+    ) -> TransportResult<Vec<AssetTransfer>> {
+        let mut transfers = Vec::new();
+        loop {
+            let page = self.alchemy_get_asset_transfers(&request).await?;
+            transfers.extend(page.transfers);
+            match page.page_key {
+                Some(page_key) => request.page_key = Some(page_key),
+                None => return Ok(transfers),
+            }
+        }
+    }
+
+    async fn alchemy_get_address_transfers(
+        &self,
+        address: Address,
+        categories: Vec<AssetTransferCategory>,
+    ) -> TransportResult<Vec<AssetTransfer>> {
+        let request = AssetTransfersRequest::new(categories);
+        let mut transfers =
+            self.alchemy_get_all_asset_transfers(request.clone().from_address(address)).await?;
+        let received = self.alchemy_get_all_asset_transfers(request.to_address(address)).await?;
+        // Transfers to self are returned in both directions.
+        let received = received
+            .into_iter()
+            .filter(|transfer| {
+                transfer.from != address
+                    || !transfers.iter().any(|sent| sent.unique_id == transfer.unique_id)
+            })
+            .collect::<Vec<_>>();
+        transfers.extend(received);
+        transfers.sort_by_key(|transfer| transfer.block_num);
+        Ok(transfers)
+    }
+
+    async fn alchemy_get_token_balances(
+        &self,
+        address: Address,
+        tokens: TokenBalancesSpec,
+    ) -> TransportResult<TokenBalances> {
+        self.client().request("alchemy_getTokenBalances", (address, tokens)).await
+    }
+
+    async fn alchemy_get_token_metadata(
+        &self,
+        contract: Address,
+    ) -> TransportResult<TokenMetadata> {
+        self.client().request("alchemy_getTokenMetadata", (contract,)).await
+    }
+
+    async fn alchemy_get_traces_from(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
+        let filter = TraceFilter::builder()
+            .from_block(from_block)
+            .to_block(to_block)
+            .from_address(address)
+            .build();
+        self.client().request("trace_filter", (filter,)).await
+    }
+
+    async fn alchemy_get_traces_to(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
+        let filter = TraceFilter::builder()
+            .from_block(from_block)
+            .to_block(to_block)
+            .to_address(address)
+            .build();
+        self.client().request("trace_filter", (filter,)).await
+    }
+
+    async fn alchemy_get_address_traces(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
+        let filter = TraceFilter::builder()
+            .from_block(from_block)
+            .to_block(to_block)
+            .from_address(address)
+            .to_address(address)
+            .mode(TraceFilterMode::Union)
+            .build();
+        self.client().request("trace_filter", (filter,)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use alloy_primitives::{B256, U256};
+    use serde_json::{json, Value};
+
+    const OWNER: Address = Address::with_last_byte(1);
+    const OTHER: Address = Address::with_last_byte(2);
+    const TOKEN: Address = Address::with_last_byte(3);
+
+    fn transfer(block: u64, from: Address, to: Address, id: &str) -> Value {
+        json!({
+            "category": "external",
+            "blockNum": format!("{block:#x}"),
+            "from": from,
+            "to": to,
+            "value": 1.0,
+            "asset": "ETH",
+            "uniqueId": id,
+            "hash": B256::with_last_byte(block as u8),
+            "rawContract": { "value": "0xde0b6b3a7640000", "address": null, "decimal": "0x12" }
+        })
+    }
+
+    /// A transport answering the Alchemy methods for an address with a few transfers, paging the
+    /// received ones.
+    fn provider() -> impl Provider<MockTransport> {
+        let transport = MockTransport::new(|method, params| {
+            let result = match method {
+                "alchemy_getAssetTransfers" => {
+                    let filter = &params[0];
+                    assert_eq!(filter["category"], json!(["external"]));
+                    if filter["fromAddress"] == json!(OWNER) {
+                        json!({ "transfers": [
+                            transfer(3, OWNER, OTHER, "0x3:external"),
+                            transfer(5, OWNER, OWNER, "0x5:external"),
+                        ] })
+                    } else if filter["pageKey"].is_null() {
+                        assert_eq!(filter["toAddress"], json!(OWNER));
+                        json!({
+                            "transfers": [transfer(1, OTHER, OWNER, "0x1:external")],
+                            "pageKey": "next"
+                        })
+                    } else {
+                        assert_eq!(filter["pageKey"], "next");
+                        json!({ "transfers": [
+                            transfer(4, OTHER, OWNER, "0x4:external"),
+                            transfer(5, OWNER, OWNER, "0x5:external"),
+                        ] })
+                    }
+                }
+                "alchemy_getTokenBalances" => {
+                    assert_eq!(params, json!([OWNER, [TOKEN]]));
+                    json!({
+                        "address": OWNER,
+                        "tokenBalances": [{ "contractAddress": TOKEN, "tokenBalance": "0x2a" }]
+                    })
+                }
+                "alchemy_getTokenMetadata" => {
+                    assert_eq!(params, json!([TOKEN]));
+                    json!({ "name": "Token", "symbol": "TKN", "decimals": 18, "logo": null })
+                }
+                "trace_filter" => {
+                    let filter: TraceFilter = serde_json::from_value(params[0].clone()).unwrap();
+                    assert_eq!((filter.from_block, filter.to_block), (Some(1), Some(5)));
+                    assert_eq!(filter.mode, TraceFilterMode::Union);
+                    // One trace per direction the filter matches.
+                    let traces = [(&filter.from_address, OTHER), (&filter.to_address, OWNER)]
+                        .into_iter()
+                        .filter(|(addresses, _)| addresses.contains(&OWNER))
+                        .map(|(_, to)| trace(to))
+                        .collect::<Vec<_>>();
+                    json!(traces)
+                }
+                method => panic!("unexpected method {method}"),
+            };
+            Ok(result)
+        });
+        transport.provider()
+    }
+
+    fn trace(to: Address) -> Value {
+        let from = if to == OWNER { OTHER } else { OWNER };
+        json!({
+            "action": {
+                "callType": "call",
+                "from": from,
+                "gas": "0x0",
+                "input": "0x",
+                "to": to,
+                "value": "0x1"
+            },
+            "result": { "gasUsed": "0x0", "output": "0x" },
+            "subtraces": 0,
+            "traceAddress": [],
+            "type": "call",
+            "blockNumber": 2,
+            "transactionPosition": 0
+        })
+    }
+
+    #[tokio::test]
+    async fn traces_by_address() {
+        use alloy_rpc_types_trace::parity::Action;
+
+        let provider = provider();
+        let recipients = |traces: Vec<LocalizedTransactionTrace>| {
+            traces
+                .into_iter()
+                .map(|trace| match trace.trace.action {
+                    Action::Call(call) => call.to,
+                    action => unreachable!("{action:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+        let sent = provider.alchemy_get_traces_from(OWNER, 1, 5).await.unwrap();
+        assert_eq!(recipients(sent), [OTHER]);
+        let received = provider.alchemy_get_traces_to(OWNER, 1, 5).await.unwrap();
+        assert_eq!(recipients(received), [OWNER]);
+        let all = provider.alchemy_get_address_traces(OWNER, 1, 5).await.unwrap();
+        assert_eq!(recipients(all), [OTHER, OWNER]);
+    }
+
+    #[tokio::test]
+    async fn follows_page_keys() {
+        let provider = provider();
+        let request =
+            AssetTransfersRequest::new([AssetTransferCategory::External]).to_address(OWNER);
+        let transfers = provider.alchemy_get_all_asset_transfers(request).await.unwrap();
+        let blocks = transfers.iter().map(|t| t.block_num).collect::<Vec<_>>();
+        assert_eq!(blocks, [1, 4, 5]);
+        assert_eq!(transfers[0].raw_contract.decimal, Some(18));
+    }
+
+    #[tokio::test]
+    async fn merges_address_transfers() {
+        let provider = provider();
+        let transfers = provider
+            .alchemy_get_address_transfers(OWNER, vec![AssetTransferCategory::External])
+            .await
+            .unwrap();
+        let ids = transfers.iter().map(|t| t.unique_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, ["0x1:external", "0x3:external", "0x4:external", "0x5:external"]);
+    }
+
+    #[tokio::test]
+    async fn reads_tokens() {
+        let provider = provider();
+        let balances = provider
+            .alchemy_get_token_balances(OWNER, TokenBalancesSpec::from(vec![TOKEN]))
+            .await
+            .unwrap();
+        assert_eq!(balances.non_zero().collect::<Vec<_>>(), [(TOKEN, U256::from(42))]);
+
+        let metadata = provider.alchemy_get_token_metadata(TOKEN).await.unwrap();
+        assert_eq!(metadata.symbol.as_deref(), Some("TKN"));
+        assert_eq!(metadata.decimals, Some(18));
+    }
+}
@@ -0,0 +1,187 @@
+//! This module extends the Ethereum JSON-RPC provider with helpers for the Arbitrum precompiles.
+use crate::Provider;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{address, Address, Bytes};
+use alloy_rpc_types_eth::arbitrum::{
+    ArbitrumGasComponents, ArbitrumL1GasEstimate, ArbitrumPricesInWei,
+};
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+
+/// The address of the `NodeInterface` virtual contract, only available through `eth_call` and
+/// `eth_estimateGas`.
+pub const NODE_INTERFACE_ADDRESS: Address = address!("00000000000000000000000000000000000000C8");
+
+/// The address of the `ArbGasInfo` precompile.
+pub const ARB_GAS_INFO_ADDRESS: Address = address!("000000000000000000000000000000000000006C");
+
+sol! {
+    function gasEstimateL1Component(address to, bool contractCreation, bytes calldata data)
+        external
+        payable
+        returns (uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate);
+
+    function gasEstimateComponents(address to, bool contractCreation, bytes calldata data)
+        external
+        payable
+        returns (uint64 gasEstimate, uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate);
+
+    function getPricesInWei() external view returns (uint256, uint256, uint256, uint256, uint256, uint256);
+}
+
+/// Arbitrum gas estimation helpers, calling the `NodeInterface` and `ArbGasInfo` precompiles.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait ArbitrumApi<N: Network, T>: Send + Sync {
+    /// Estimates the gas needed to pay for posting `tx` to L1, with
+    /// `NodeInterface.gasEstimateL1Component`.
+    async fn arb_estimate_l1_component(
+        &self,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<ArbitrumL1GasEstimate>;
+
+    /// Estimates the total gas of `tx` along with its L1 component, with
+    /// `NodeInterface.gasEstimateComponents`.
+    async fn arb_estimate_gas_components(
+        &self,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<ArbitrumGasComponents>;
+
+    /// Returns the current gas prices, with `ArbGasInfo.getPricesInWei`.
+    async fn arb_prices_in_wei(&self) -> TransportResult<ArbitrumPricesInWei>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> ArbitrumApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn arb_estimate_l1_component(
+        &self,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<ArbitrumL1GasEstimate> {
+        let (to, contract_creation, data) = node_interface_args::<N>(tx);
+        let call = gasEstimateL1ComponentCall { to, contractCreation: contract_creation, data };
+        let ret = call_precompile(self, tx.clone(), NODE_INTERFACE_ADDRESS, &call).await?;
+        Ok(ArbitrumL1GasEstimate {
+            gas_estimate_for_l1: ret.gasEstimateForL1,
+            base_fee: ret.baseFee,
+            l1_base_fee_estimate: ret.l1BaseFeeEstimate,
+        })
+    }
+
+    async fn arb_estimate_gas_components(
+        &self,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<ArbitrumGasComponents> {
+        let (to, contract_creation, data) = node_interface_args::<N>(tx);
+        let call = gasEstimateComponentsCall { to, contractCreation: contract_creation, data };
+        let ret = call_precompile(self, tx.clone(), NODE_INTERFACE_ADDRESS, &call).await?;
+        Ok(ArbitrumGasComponents {
+            gas_estimate: ret.gasEstimate,
+            gas_estimate_for_l1: ret.gasEstimateForL1,
+            base_fee: ret.baseFee,
+            l1_base_fee_estimate: ret.l1BaseFeeEstimate,
+        })
+    }
+
+    async fn arb_prices_in_wei(&self) -> TransportResult<ArbitrumPricesInWei> {
+        let tx = N::TransactionRequest::default();
+        let ret = call_precompile(self, tx, ARB_GAS_INFO_ADDRESS, &getPricesInWeiCall {}).await?;
+        Ok(ArbitrumPricesInWei {
+            per_l2_tx: ret._0,
+            per_l1_calldata_byte: ret._1,
+            per_storage_allocation: ret._2,
+            per_arb_gas_base: ret._3,
+            per_arb_gas_congestion: ret._4,
+            per_arb_gas_total: ret._5,
+        })
+    }
+}
+
+/// Returns the `(to, contractCreation, data)` arguments of the `NodeInterface` estimation calls.
+fn node_interface_args<N: Network>(tx: &N::TransactionRequest) -> (Address, bool, Bytes) {
+    let to = tx.to();
+    (to.unwrap_or_default(), to.is_none(), tx.input().cloned().unwrap_or_default())
+}
+
+/// Calls `precompile` with `call`, keeping the sender and value of `tx`.
+async fn call_precompile<P, C, N, T>(
+    provider: &P,
+    mut tx: N::TransactionRequest,
+    precompile: Address,
+    call: &C,
+) -> TransportResult<C::Return>
+where
+    P: Provider<T, N> + ?Sized,
+    C: SolCall,
+    N: Network,
+    T: Transport + Clone,
+{
+    tx.set_to(precompile);
+    tx.set_input(call.abi_encode());
+    let output = provider.call(&tx).await?;
+    C::abi_decode_returns(&output, true).map_err(TransportErrorKind::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use alloy_primitives::{hex, TxKind, U256};
+    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_sol_types::SolValue;
+    use serde_json::json;
+
+    /// A transport answering `eth_call`s to the precompiles, checking the calldata.
+    fn provider() -> impl Provider<MockTransport> {
+        let transport = MockTransport::new(|method, params| {
+            assert_eq!(method, "eth_call");
+            let tx: TransactionRequest = serde_json::from_value(params[0].clone()).unwrap();
+            let input = tx.input.input().unwrap();
+
+            let output = match tx.to {
+                Some(TxKind::Call(NODE_INTERFACE_ADDRESS)) => {
+                    assert_eq!(tx.from, Some(Address::with_last_byte(1)));
+                    assert_eq!(tx.value, Some(U256::from(5)));
+                    let call = gasEstimateComponentsCall::abi_decode(input, true).unwrap();
+                    assert_eq!(call.to, Address::with_last_byte(2));
+                    assert!(!call.contractCreation);
+                    assert_eq!(call.data, Bytes::from_static(&[0xab]));
+                    (100_000u64, 30_000u64, U256::from(10), U256::from(20)).abi_encode_sequence()
+                }
+                Some(TxKind::Call(ARB_GAS_INFO_ADDRESS)) => {
+                    assert_eq!(input[..], getPricesInWeiCall::SELECTOR);
+                    (1..=6u8).flat_map(|i| U256::from(i).to_be_bytes::<32>()).collect()
+                }
+                to => panic!("unexpected call to {to:?}"),
+            };
+            Ok(json!(hex::encode_prefixed(output)))
+        });
+        transport.provider()
+    }
+
+    #[tokio::test]
+    async fn estimates_gas_components() {
+        let tx = TransactionRequest::default()
+            .from(Address::with_last_byte(1))
+            .to(Address::with_last_byte(2))
+            .value(U256::from(5))
+            .input(Bytes::from_static(&[0xab]).into());
+        let components = provider().arb_estimate_gas_components(&tx).await.unwrap();
+        assert_eq!(components.gas_estimate, 100_000);
+        assert_eq!(components.gas_estimate_for_l1, 30_000);
+        assert_eq!(components.gas_estimate_for_l2(), 70_000);
+        assert_eq!(components.l1_base_fee_estimate, U256::from(20));
+    }
+
+    #[tokio::test]
+    async fn reads_prices_in_wei() {
+        let prices = provider().arb_prices_in_wei().await.unwrap();
+        assert_eq!(prices.per_l2_tx, U256::from(1));
+        assert_eq!(prices.per_arb_gas_total, U256::from(6));
+    }
+}
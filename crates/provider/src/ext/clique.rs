@@ -0,0 +1,163 @@
+//! This module extends the Ethereum JSON-RPC provider with the Clique namespace's RPC methods.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_clique::{CliqueSnapshot, CliqueStatus};
+use alloy_rpc_types_eth::BlockNumberOrTag;
+use alloy_transport::{Transport, TransportResult};
+use std::collections::BTreeMap;
+
+/// Clique namespace rpc interface that gives access to the proof-of-authority signer set and
+/// voting of geth nodes running the clique consensus engine.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait CliqueApi<N, T>: Send + Sync {
+    /// Returns the list of authorized signers at the given block.
+    async fn clique_get_signers(&self, block: BlockNumberOrTag) -> TransportResult<Vec<Address>>;
+
+    /// Returns the list of authorized signers at the block with the given hash.
+    async fn clique_get_signers_at_hash(&self, hash: B256) -> TransportResult<Vec<Address>>;
+
+    /// Returns the state snapshot of the signer voting at the given block.
+    async fn clique_get_snapshot(&self, block: BlockNumberOrTag)
+        -> TransportResult<CliqueSnapshot>;
+
+    /// Returns the state snapshot of the signer voting at the block with the given hash.
+    async fn clique_get_snapshot_at_hash(&self, hash: B256) -> TransportResult<CliqueSnapshot>;
+
+    /// Returns the current proposals the node is voting on, mapping each proposed address to
+    /// whether it is voted in or out.
+    async fn clique_proposals(&self) -> TransportResult<BTreeMap<Address, bool>>;
+
+    /// Adds a new authorization proposal that the signer will attempt to push through, voting to
+    /// add the given address as a signer if `authorize` is true, or to remove it otherwise.
+    async fn clique_propose(&self, address: Address, authorize: bool) -> TransportResult<()>;
+
+    /// Drops a currently running proposal, stopping the signer from casting further votes
+    /// (either for or against).
+    async fn clique_discard(&self, address: Address) -> TransportResult<()>;
+
+    /// Returns the sealing activity of the signers over the last blocks.
+    async fn clique_status(&self) -> TransportResult<CliqueStatus>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> CliqueApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn clique_get_signers(&self, block: BlockNumberOrTag) -> TransportResult<Vec<Address>> {
+        self.client().request("clique_getSigners", (block,)).await
+    }
+
+    async fn clique_get_signers_at_hash(&self, hash: B256) -> TransportResult<Vec<Address>> {
+        self.client().request("clique_getSignersAtHash", (hash,)).await
+    }
+
+    async fn clique_get_snapshot(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> TransportResult<CliqueSnapshot> {
+        self.client().request("clique_getSnapshot", (block,)).await
+    }
+
+    async fn clique_get_snapshot_at_hash(&self, hash: B256) -> TransportResult<CliqueSnapshot> {
+        self.client().request("clique_getSnapshotAtHash", (hash,)).await
+    }
+
+    async fn clique_proposals(&self) -> TransportResult<BTreeMap<Address, bool>> {
+        self.client().request("clique_proposals", ()).await
+    }
+
+    async fn clique_propose(&self, address: Address, authorize: bool) -> TransportResult<()> {
+        self.client().request("clique_propose", (address, authorize)).await
+    }
+
+    async fn clique_discard(&self, address: Address) -> TransportResult<()> {
+        self.client().request("clique_discard", (address,)).await
+    }
+
+    async fn clique_status(&self) -> TransportResult<CliqueStatus> {
+        self.client().request("clique_status", ()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use serde_json::{json, Value};
+
+    const SIGNER: Address = Address::with_last_byte(1);
+    const CANDIDATE: Address = Address::with_last_byte(2);
+
+    /// A transport answering the clique namespace like a single-signer network, checking the
+    /// params of the requests.
+    fn provider() -> impl Provider<MockTransport> {
+        let transport = MockTransport::new(|method, params| {
+            let result = match method {
+                "clique_getSigners" => {
+                    assert_eq!(params, json!(["latest"]));
+                    json!([SIGNER])
+                }
+                "clique_getSnapshotAtHash" => {
+                    assert_eq!(params, json!([B256::with_last_byte(3)]));
+                    json!({
+                        "number": 3,
+                        "hash": B256::with_last_byte(3),
+                        "signers": { SIGNER.to_string(): {} },
+                        "recents": { "3": SIGNER },
+                        "votes": [{ "signer": SIGNER, "block": 2, "address": CANDIDATE, "authorize": true }],
+                        "tally": { CANDIDATE.to_string(): { "authorize": true, "votes": 1 } }
+                    })
+                }
+                "clique_propose" => {
+                    assert_eq!(params, json!([CANDIDATE, true]));
+                    Value::Null
+                }
+                "clique_discard" => {
+                    assert_eq!(params, json!([CANDIDATE]));
+                    Value::Null
+                }
+                "clique_proposals" => json!({ CANDIDATE.to_string(): true }),
+                "clique_status" => json!({
+                    "inturnPercent": 100.0,
+                    "sealerActivity": { SIGNER.to_string(): 64 },
+                    "numBlocks": 64
+                }),
+                method => panic!("unexpected method {method}"),
+            };
+            Ok(result)
+        });
+        transport.provider()
+    }
+
+    #[tokio::test]
+    async fn reads_signers_and_snapshot() {
+        let provider = provider();
+        let signers = provider.clique_get_signers(BlockNumberOrTag::Latest).await.unwrap();
+        assert_eq!(signers, vec![SIGNER]);
+
+        let snapshot = provider.clique_get_snapshot_at_hash(B256::with_last_byte(3)).await.unwrap();
+        assert!(snapshot.is_signer(&SIGNER));
+        assert!(snapshot.is_recent_signer(&SIGNER));
+        assert_eq!(snapshot.votes[0].address, CANDIDATE);
+        assert_eq!(snapshot.tally[&CANDIDATE].votes, 1);
+    }
+
+    #[tokio::test]
+    async fn proposes_and_discards_signers() {
+        let provider = provider();
+        provider.clique_propose(CANDIDATE, true).await.unwrap();
+        assert!(provider.clique_proposals().await.unwrap()[&CANDIDATE]);
+        provider.clique_discard(CANDIDATE).await.unwrap();
+
+        let status = provider.clique_status().await.unwrap();
+        assert_eq!(status.inturn_percent, 100.0);
+        assert_eq!(status.sealer_activity[&SIGNER], 64);
+        assert_eq!(status.num_blocks, 64);
+    }
+}
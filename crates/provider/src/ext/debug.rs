@@ -1,12 +1,18 @@
 //! This module extends the Ethereum JSON-RPC provider with the Debug namespace's RPC methods.
-use crate::Provider;
+use crate::{ext::TraceCallBuilder, Provider};
+use alloy_consensus::Block as ConsensusBlock;
+use alloy_eips::BlockId;
 use alloy_network::Network;
 use alloy_primitives::{hex, Bytes, TxHash, B256};
 use alloy_rpc_types_eth::{Block, BlockNumberOrTag, TransactionRequest};
-use alloy_rpc_types_trace::geth::{
-    BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
+use alloy_rpc_types_trace::{
+    common::join_tx_hashes,
+    geth::{
+        AccountRangeDump, BlockTraceResult, DumpAccount, GethDebugTracingCallOptions,
+        GethDebugTracingOptions, GethTrace, StateDump, TraceResult,
+    },
 };
-use alloy_transport::{Transport, TransportResult};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
 
 /// Debug namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -49,6 +55,24 @@ pub trait DebugApi<N, T>: Send + Sync {
         trace_options: GethDebugTracingOptions,
     ) -> TransportResult<Vec<TraceResult>>;
 
+    /// Same as [`debug_trace_block`](Self::debug_trace_block), but RLP-encodes the given block,
+    /// and returns the trace result of each transaction along with its hash, in the order of the
+    /// block.
+    ///
+    /// Fails with a [`TraceJoinError`](alloy_rpc_types_trace::common::TraceJoinError) if the
+    /// trace results do not match the transactions of the block.
+    ///
+    /// A [`SealedBlock`](alloy_consensus::SealedBlock) dereferences to its block.
+    ///
+    /// # Note
+    ///
+    /// The parent of this block must be present, or it will fail.
+    async fn debug_trace_consensus_block(
+        &self,
+        block: &ConsensusBlock,
+        trace_options: GethDebugTracingOptions,
+    ) -> TransportResult<Vec<(TxHash, TraceResult)>>;
+
     /// Reruns the transaction specified by the hash and returns the trace.
     ///
     /// It will replay any prior transactions to achieve the same state the transaction was executed
@@ -113,6 +137,10 @@ pub trait DebugApi<N, T>: Send + Sync {
         trace_options: GethDebugTracingCallOptions,
     ) -> TransportResult<GethTrace>;
 
+    /// Returns a [`TraceCallBuilder`] to configure and send a `debug_traceCall` request for `tx`,
+    /// returning the typed output of the selected tracer.
+    fn debug_trace_call_builder(&self, tx: TransactionRequest) -> TraceCallBuilder<'_, T>;
+
     /// Same as `debug_trace_call` but it used to run and trace multiple transactions at once.
     ///
     /// [GethDebugTracingOptions] can be used to specify the trace options.
@@ -126,6 +154,43 @@ pub trait DebugApi<N, T>: Send + Sync {
         block: BlockNumberOrTag,
         trace_options: GethDebugTracingCallOptions,
     ) -> TransportResult<Vec<GethTrace>>;
+
+    /// Returns the whole world state at the given block, with `debug_dumpBlock`.
+    ///
+    /// The dump is returned in a single response, so this is only suitable for small states, e.g.
+    /// of dev nodes. Use [`debug_dump_block_to`](Self::debug_dump_block_to) for larger ones.
+    async fn debug_dump_block(&self, block: BlockNumberOrTag) -> TransportResult<StateDump>;
+
+    /// Returns a page of the world state at the given block, with `debug_accountRange`.
+    ///
+    /// Accounts are ordered by their hashed address, starting from `start`, and the next page
+    /// starts from [`AccountRangeDump::next`]. The code and storage of the accounts can be omitted.
+    /// Accounts whose address preimage is unknown to the node are only included with
+    /// `incompletes`.
+    async fn debug_account_range(
+        &self,
+        block: BlockId,
+        start: B256,
+        max_results: u64,
+        no_code: bool,
+        no_storage: bool,
+        incompletes: bool,
+    ) -> TransportResult<AccountRangeDump>;
+
+    /// Streams the whole world state at the given block to `sink`, one account at a time,
+    /// returning the state root.
+    ///
+    /// The state is requested in pages of `page_size` accounts with `debug_accountRange`, so that
+    /// it is never held in memory as a whole. The sink is called with the key of each account in
+    /// [`StateDump::accounts`], and the account, and stops the dump by returning an error.
+    async fn debug_dump_block_to<F>(
+        &self,
+        block: BlockId,
+        page_size: u64,
+        sink: F,
+    ) -> TransportResult<B256>
+    where
+        F: FnMut(String, DumpAccount) -> std::io::Result<()> + Send;
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -173,6 +238,16 @@ where
         self.client().request("debug_traceBlock", (rlp_block, trace_options)).await
     }
 
+    async fn debug_trace_consensus_block(
+        &self,
+        block: &ConsensusBlock,
+        trace_options: GethDebugTracingOptions,
+    ) -> TransportResult<Vec<(TxHash, TraceResult)>> {
+        let traces = self.debug_trace_block(&alloy_rlp::encode(block), trace_options).await?;
+        join_tx_hashes(traces, block.body.transactions.iter().map(|tx| *tx.tx_hash()))
+            .map_err(TransportErrorKind::custom)
+    }
+
     async fn debug_trace_transaction(
         &self,
         hash: TxHash,
@@ -206,6 +281,10 @@ where
         self.client().request("debug_traceCall", (tx, block, trace_options)).await
     }
 
+    fn debug_trace_call_builder(&self, tx: TransactionRequest) -> TraceCallBuilder<'_, T> {
+        TraceCallBuilder::new(self.client(), tx)
+    }
+
     async fn debug_trace_call_many(
         &self,
         txs: Vec<TransactionRequest>,
@@ -214,6 +293,50 @@ where
     ) -> TransportResult<Vec<GethTrace>> {
         self.client().request("debug_traceCallMany", (txs, block, trace_options)).await
     }
+
+    async fn debug_dump_block(&self, block: BlockNumberOrTag) -> TransportResult<StateDump> {
+        self.client().request("debug_dumpBlock", (block,)).await
+    }
+
+    async fn debug_account_range(
+        &self,
+        block: BlockId,
+        start: B256,
+        max_results: u64,
+        no_code: bool,
+        no_storage: bool,
+        incompletes: bool,
+    ) -> TransportResult<AccountRangeDump> {
+        self.client()
+            .request(
+                "debug_accountRange",
+                (block, start, max_results, no_code, no_storage, incompletes),
+            )
+            .await
+    }
+
+    async fn debug_dump_block_to<F>(
+        &self,
+        block: BlockId,
+        page_size: u64,
+        mut sink: F,
+    ) -> TransportResult<B256>
+    where
+        F: FnMut(String, DumpAccount) -> std::io::Result<()> + Send,
+    {
+        let mut start = B256::ZERO;
+        loop {
+            let page =
+                self.debug_account_range(block, start, page_size, false, false, true).await?;
+            for (key, account) in page.accounts {
+                sink(key, account).map_err(TransportErrorKind::custom)?;
+            }
+            match page.next {
+                Some(next) => start = B256::left_padding_from(&next),
+                None => return Ok(page.root),
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -324,4 +447,110 @@ mod test {
         let result = provider.debug_get_bad_blocks().await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn dump_block_in_pages() {
+        use crate::mock::MockTransport;
+        use serde_json::json;
+
+        let account = |balance: &str| {
+            json!({
+                "balance": balance,
+                "nonce": 0,
+                "root": B256::ZERO,
+                "codeHash": B256::ZERO,
+            })
+        };
+        let pages = [
+            json!({
+                "root": B256::with_last_byte(1),
+                "accounts": { "0x0000000000000000000000000000000000000001": account("1") },
+                // The base64 encoding of the hashed address 0x00..0a.
+                "next": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAo=",
+            }),
+            json!({
+                "root": B256::with_last_byte(1),
+                "accounts": { "0x0000000000000000000000000000000000000002": account("2") },
+            }),
+        ];
+        let transport = MockTransport::new(move |method, params| {
+            assert_eq!(method, "debug_accountRange");
+            assert_eq!(params[2], 1);
+            let page = match params[1].as_str().unwrap() {
+                "0x0000000000000000000000000000000000000000000000000000000000000000" => &pages[0],
+                "0x000000000000000000000000000000000000000000000000000000000000000a" => &pages[1],
+                start => unreachable!("{start}"),
+            };
+            Ok(page.clone())
+        });
+        let provider = transport.provider();
+
+        let mut accounts = Vec::new();
+        let root = provider
+            .debug_dump_block_to(BlockId::latest(), 1, |key, account| {
+                accounts.push((key, account.balance));
+                Ok(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(root, B256::with_last_byte(1));
+        assert_eq!(
+            accounts,
+            [
+                ("0x0000000000000000000000000000000000000001".to_string(), U256::from(1)),
+                ("0x0000000000000000000000000000000000000002".to_string(), U256::from(2))
+            ]
+        );
+
+        let err = provider
+            .debug_dump_block_to(BlockId::latest(), 1, |_, _| Err(std::io::ErrorKind::Other.into()))
+            .await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn trace_consensus_block() {
+        use crate::mock::MockTransport;
+        use alloy_consensus::{
+            BlockBody, Header, Sealable, SignableTransaction, TxEnvelope, TxLegacy,
+        };
+        use alloy_primitives::Signature;
+        use serde_json::json;
+
+        let transactions = (0..2)
+            .map(|nonce| {
+                let tx = TxLegacy { nonce, chain_id: Some(1), ..Default::default() };
+                TxEnvelope::Legacy(tx.into_signed(Signature::test_signature()))
+            })
+            .collect::<Vec<_>>();
+        let block = ConsensusBlock::new(
+            Header { number: 1, ..Default::default() },
+            BlockBody { transactions, ..Default::default() },
+        );
+        let rlp = hex::encode_prefixed(alloy_rlp::encode(&block));
+
+        let transport = MockTransport::new(move |method, params| {
+            assert_eq!(method, "debug_traceBlock");
+            assert_eq!(params[0], rlp);
+            let traces = json!([
+                { "result": { "gas": 1, "failed": false, "returnValue": "0x", "structLogs": [] } },
+                { "error": "execution reverted" },
+            ]);
+            Ok(traces)
+        });
+        let provider = transport.provider();
+
+        let sealed = block.clone().seal_slow();
+        let traces = provider
+            .debug_trace_consensus_block(&sealed, GethDebugTracingOptions::default())
+            .await
+            .unwrap();
+        let hashes = traces.iter().map(|(hash, _)| *hash).collect::<Vec<_>>();
+        assert_eq!(
+            hashes,
+            block.body.transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>()
+        );
+        assert!(matches!(traces[0].1, TraceResult::Success { .. }));
+        assert_eq!(traces[1].1.error().map(String::as_str), Some("execution reverted"));
+    }
 }
@@ -0,0 +1,268 @@
+use alloy_eips::BlockId;
+use alloy_json_rpc::RpcReturn;
+use alloy_rpc_client::ClientRef;
+use alloy_rpc_types_eth::{state::StateOverride, BlockOverrides, TransactionRequest};
+use alloy_rpc_types_trace::geth::{
+    mux::{MuxConfig, MuxFrame},
+    CallConfig, CallFrame, DefaultFrame, FourByteFrame, GethDebugBuiltInTracerType,
+    GethDebugTracerConfig, GethDebugTracerType, GethDebugTracingCallOptions,
+    GethDefaultTracingOptions, GethTrace, NoopFrame, PreStateConfig, PreStateFrame,
+};
+use alloy_transport::{RpcFut, Transport, TransportResult};
+use std::{future::IntoFuture, marker::PhantomData, time::Duration};
+
+/// A builder for `debug_traceCall` requests, returned by
+/// [`DebugApi::debug_trace_call_builder`](super::DebugApi::debug_trace_call_builder).
+///
+/// Selecting a tracer also selects the type of its output, which the response is deserialized
+/// into, instead of a [`GethTrace`] that may be of any tracer:
+///
+/// ```no_run
+/// # async fn example<P: alloy_provider::Provider>(provider: P) -> alloy_transport::TransportResult<()> {
+/// use alloy_provider::ext::DebugApi;
+/// use alloy_rpc_types_eth::TransactionRequest;
+/// use alloy_rpc_types_trace::geth::CallConfig;
+///
+/// let call_frame = provider
+///     .debug_trace_call_builder(TransactionRequest::default())
+///     .call_tracer(CallConfig::default().with_log())
+///     .await?;
+/// println!("{} subcalls", call_frame.calls.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "this builder does nothing unless sent or awaited"]
+pub struct TraceCallBuilder<'a, T, O = GethTrace> {
+    client: ClientRef<'a, T>,
+    tx: TransactionRequest,
+    block: BlockId,
+    options: GethDebugTracingCallOptions,
+    _output: PhantomData<fn() -> O>,
+}
+
+impl<'a, T> TraceCallBuilder<'a, T> {
+    /// Creates a new builder tracing `tx` at the latest block with the default struct logger,
+    /// returning the raw [`GethTrace`].
+    pub fn new(client: ClientRef<'a, T>, tx: TransactionRequest) -> Self {
+        Self {
+            client,
+            tx,
+            block: BlockId::default(),
+            options: GethDebugTracingCallOptions::default(),
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, O> TraceCallBuilder<'a, T, O> {
+    /// Sets the block to execute the call on top of.
+    pub const fn block(mut self, block: BlockId) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// Sets the state overrides to apply before executing the call.
+    pub fn state_overrides(mut self, overrides: StateOverride) -> Self {
+        self.options.state_overrides = Some(overrides);
+        self
+    }
+
+    /// Sets the block overrides to apply to the block the call is executed in.
+    pub fn block_overrides(mut self, overrides: BlockOverrides) -> Self {
+        self.options.block_overrides = Some(overrides);
+        self
+    }
+
+    /// Sets the tracing timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.tracing_options = self.options.tracing_options.with_timeout(timeout);
+        self
+    }
+
+    /// Returns the options the call will be traced with.
+    pub const fn options(&self) -> &GethDebugTracingCallOptions {
+        &self.options
+    }
+
+    /// Uses the default struct logger with the given options.
+    pub fn struct_logger(
+        self,
+        config: GethDefaultTracingOptions,
+    ) -> TraceCallBuilder<'a, T, DefaultFrame> {
+        let mut builder = self.tracer(None, GethDebugTracerConfig::default());
+        builder.options.tracing_options.config = config;
+        builder
+    }
+
+    /// Uses the `callTracer`.
+    pub fn call_tracer(self, config: CallConfig) -> TraceCallBuilder<'a, T, CallFrame> {
+        self.tracer(Some(GethDebugBuiltInTracerType::CallTracer), config.into())
+    }
+
+    /// Uses the `prestateTracer`.
+    pub fn prestate_tracer(self, config: PreStateConfig) -> TraceCallBuilder<'a, T, PreStateFrame> {
+        self.tracer(Some(GethDebugBuiltInTracerType::PreStateTracer), config.into())
+    }
+
+    /// Uses the `4byteTracer`.
+    pub fn four_byte_tracer(self) -> TraceCallBuilder<'a, T, FourByteFrame> {
+        self.tracer(
+            Some(GethDebugBuiltInTracerType::FourByteTracer),
+            GethDebugTracerConfig::default(),
+        )
+    }
+
+    /// Uses the `muxTracer`.
+    pub fn mux_tracer(self, config: MuxConfig) -> TraceCallBuilder<'a, T, MuxFrame> {
+        self.tracer(Some(GethDebugBuiltInTracerType::MuxTracer), config.into())
+    }
+
+    /// Uses the `noopTracer`.
+    pub fn noop_tracer(self) -> TraceCallBuilder<'a, T, NoopFrame> {
+        self.tracer(Some(GethDebugBuiltInTracerType::NoopTracer), GethDebugTracerConfig::default())
+    }
+
+    /// Uses a custom JavaScript tracer, returning its raw JSON output.
+    pub fn js_tracer(
+        mut self,
+        code: impl Into<String>,
+        config: serde_json::Value,
+    ) -> TraceCallBuilder<'a, T, serde_json::Value> {
+        self.options.tracing_options.tracer = Some(GethDebugTracerType::JsTracer(code.into()));
+        self.options.tracing_options.tracer_config = config.into();
+        self.with_output()
+    }
+
+    fn tracer<P>(
+        mut self,
+        tracer: Option<GethDebugBuiltInTracerType>,
+        config: GethDebugTracerConfig,
+    ) -> TraceCallBuilder<'a, T, P> {
+        self.options.tracing_options.tracer = tracer.map(Into::into);
+        self.options.tracing_options.tracer_config = config;
+        self.with_output()
+    }
+
+    fn with_output<P>(self) -> TraceCallBuilder<'a, T, P> {
+        TraceCallBuilder {
+            client: self.client,
+            tx: self.tx,
+            block: self.block,
+            options: self.options,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, O> TraceCallBuilder<'a, T, O>
+where
+    T: Transport + Clone,
+    O: RpcReturn,
+{
+    /// Sends the `debug_traceCall` request and returns the output of the selected tracer.
+    pub async fn send(self) -> TransportResult<O> {
+        self.client.request("debug_traceCall", (self.tx, self.block, self.options)).await
+    }
+}
+
+impl<'a, T, O> IntoFuture for TraceCallBuilder<'a, T, O>
+where
+    T: Transport + Clone,
+    O: RpcReturn,
+{
+    type Output = TransportResult<O>;
+    type IntoFuture = RpcFut<'a, O>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ext::DebugApi, mock::MockTransport, ProviderBuilder};
+    use alloy_primitives::{Address, U256};
+    use alloy_rpc_types_eth::state::AccountOverride;
+    use serde_json::json;
+
+    #[test]
+    fn builds_options() {
+        let provider = ProviderBuilder::new().on_http("http://localhost:1".parse().unwrap());
+        let account = AccountOverride { balance: Some(U256::MAX), ..Default::default() };
+        let overrides = StateOverride::from([(Address::ZERO, account)]);
+        let builder = provider
+            .debug_trace_call_builder(TransactionRequest::default())
+            .state_overrides(overrides)
+            .call_tracer(CallConfig::default().only_top_call());
+
+        assert_eq!(
+            serde_json::to_value(builder.options()).unwrap(),
+            json!({
+                "tracer": "callTracer",
+                "tracerConfig": { "onlyTopCall": true },
+                "stateOverrides": {
+                    "0x0000000000000000000000000000000000000000": {
+                        "balance": "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+                    }
+                }
+            })
+        );
+
+        let builder = builder.struct_logger(GethDefaultTracingOptions::default().disable_stack());
+        assert_eq!(
+            serde_json::to_value(builder.options()).unwrap(),
+            json!({
+                "disableStack": true,
+                "stateOverrides": {
+                    "0x0000000000000000000000000000000000000000": {
+                        "balance": "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+                    }
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn deserializes_selected_frame() {
+        let transport = MockTransport::new(|method, params| {
+            assert_eq!(method, "debug_traceCall");
+            Ok(match params[2]["tracer"].as_str().unwrap() {
+                "noopTracer" => json!({}),
+                "prestateTracer" => json!({ "0x0000000000000000000000000000000000000001": {} }),
+                "4byteTracer" => json!({ "0x27dc297e-128": 1 }),
+                tracer => unreachable!("{tracer}"),
+            })
+        });
+        let provider = transport.provider();
+        let builder = || provider.debug_trace_call_builder(TransactionRequest::default());
+
+        assert_eq!(builder().noop_tracer().await.unwrap(), NoopFrame::default());
+        let frame = builder().prestate_tracer(PreStateConfig::default()).await.unwrap();
+        let PreStateFrame::Default(mode) = frame else { panic!("{frame:?}") };
+        assert!(mode.0.contains_key(&Address::with_last_byte(1)));
+        let frame = builder().four_byte_tracer().await.unwrap();
+        assert_eq!(frame.0["0x27dc297e-128"], 1);
+    }
+
+    #[tokio::test]
+    async fn trace_call_with_call_tracer() {
+        let provider = ProviderBuilder::new().on_anvil_with_wallet();
+        let tx = TransactionRequest::default()
+            .from(Address::with_last_byte(1))
+            .to(Address::with_last_byte(2))
+            .value(U256::from(1));
+        let account = AccountOverride { balance: Some(U256::MAX), ..Default::default() };
+        let overrides = StateOverride::from([(Address::with_last_byte(1), account)]);
+
+        let frame = provider
+            .debug_trace_call_builder(tx)
+            .state_overrides(overrides)
+            .call_tracer(CallConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(frame.to, Some(Address::with_last_byte(2)));
+        assert_eq!(frame.value, Some(U256::from(1)));
+    }
+}
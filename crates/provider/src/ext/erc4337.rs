@@ -0,0 +1,475 @@
+//! This module extends the Ethereum JSON-RPC provider with the [ERC-4337] account abstraction
+//! methods of bundlers and paymaster services, and with bindings to the entry point contracts.
+//!
+//! [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+use crate::Provider;
+use alloy_network::{Ethereum, Network, TransactionBuilder};
+use alloy_primitives::{address, aliases::U192, Address, B256, U256, U64};
+use alloy_rpc_types_eth::erc4337::{
+    PackedUserOperation, PaymasterSponsorship, SendUserOperation, UserOperation,
+    UserOperationGasEstimation,
+};
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use serde_json::Value;
+
+/// The address of the v0.6 entry point, deployed at the same address on all chains.
+pub const ENTRY_POINT_V06_ADDRESS: Address = address!("5FF137D4b0FDCD49DcA30c7CF57E578a026d2789");
+
+/// The address of the v0.7 entry point, deployed at the same address on all chains.
+pub const ENTRY_POINT_V07_ADDRESS: Address = address!("0000000071727De22E5E9d8BAb0EdAC6f20da032");
+
+sol! {
+    /// The v0.6 entry point.
+    #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IEntryPointV06 {
+        /// A user operation, as passed to the entry point.
+        struct UserOperation {
+            address sender;
+            uint256 nonce;
+            bytes initCode;
+            bytes callData;
+            uint256 callGasLimit;
+            uint256 verificationGasLimit;
+            uint256 preVerificationGas;
+            uint256 maxFeePerGas;
+            uint256 maxPriorityFeePerGas;
+            bytes paymasterAndData;
+            bytes signature;
+        }
+
+        /// The result of validating a user operation.
+        struct ReturnInfo {
+            uint256 preOpGas;
+            uint256 prefund;
+            bool sigFailed;
+            uint48 validAfter;
+            uint48 validUntil;
+            bytes paymasterContext;
+        }
+
+        /// The stake of an entity taking part in a user operation.
+        struct StakeInfo {
+            uint256 stake;
+            uint256 unstakeDelaySec;
+        }
+
+        /// A user operation failed, with the reason prefixed by the failing entity code.
+        error FailedOp(uint256 opIndex, string reason);
+
+        /// Returned by `simulateValidation` when the user operation is valid.
+        error ValidationResult(
+            ReturnInfo returnInfo,
+            StakeInfo senderInfo,
+            StakeInfo factoryInfo,
+            StakeInfo paymasterInfo
+        );
+
+        /// Returned by `simulateHandleOp`.
+        error ExecutionResult(
+            uint256 preOpGas,
+            uint256 paid,
+            uint48 validAfter,
+            uint48 validUntil,
+            bool targetSuccess,
+            bytes targetResult
+        );
+
+        /// Returned by `getSenderAddress`.
+        error SenderAddressResult(address sender);
+
+        /// Executes a batch of user operations, paying the fees to the beneficiary.
+        function handleOps(UserOperation[] calldata ops, address payable beneficiary) external;
+
+        /// Returns the next nonce of the sender, for the given key.
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+
+        /// Returns the hash of a user operation, signed by the account.
+        function getUserOpHash(UserOperation calldata userOp) external view returns (bytes32);
+
+        /// Simulates the validation of a user operation, always reverting with the result.
+        function simulateValidation(UserOperation calldata userOp) external;
+
+        /// Returns the address of the account deployed by the init code, always reverting with it.
+        function getSenderAddress(bytes memory initCode) external;
+    }
+}
+
+sol! {
+    /// The v0.7 entry point.
+    #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IEntryPointV07 {
+        /// A user operation, as passed to the entry point, with its gas and fees packed.
+        struct PackedUserOperation {
+            address sender;
+            uint256 nonce;
+            bytes initCode;
+            bytes callData;
+            bytes32 accountGasLimits;
+            uint256 preVerificationGas;
+            bytes32 gasFees;
+            bytes paymasterAndData;
+            bytes signature;
+        }
+
+        /// A user operation failed, with the reason prefixed by the failing entity code.
+        error FailedOp(uint256 opIndex, string reason);
+
+        /// A user operation failed, with the revert data of the failing entity.
+        error FailedOpWithRevert(uint256 opIndex, string reason, bytes inner);
+
+        /// The post-operation of the paymaster reverted.
+        error PostOpReverted(bytes returnData);
+
+        /// The signature aggregator failed to validate the signatures.
+        error SignatureValidationFailed(address aggregator);
+
+        /// Returned by `getSenderAddress`.
+        error SenderAddressResult(address sender);
+
+        /// Executes a batch of user operations, paying the fees to the beneficiary.
+        function handleOps(PackedUserOperation[] calldata ops, address payable beneficiary) external;
+
+        /// Returns the next nonce of the sender, for the given key.
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+
+        /// Returns the hash of a user operation, signed by the account.
+        function getUserOpHash(PackedUserOperation calldata userOp) external view returns (bytes32);
+
+        /// Returns the address of the account deployed by the init code, always reverting with it.
+        function getSenderAddress(bytes memory initCode) external;
+    }
+}
+
+impl From<&UserOperation> for IEntryPointV06::UserOperation {
+    fn from(op: &UserOperation) -> Self {
+        Self {
+            sender: op.sender,
+            nonce: op.nonce,
+            initCode: op.init_code.clone(),
+            callData: op.call_data.clone(),
+            callGasLimit: op.call_gas_limit,
+            verificationGasLimit: op.verification_gas_limit,
+            preVerificationGas: op.pre_verification_gas,
+            maxFeePerGas: op.max_fee_per_gas,
+            maxPriorityFeePerGas: op.max_priority_fee_per_gas,
+            paymasterAndData: op.paymaster_and_data.clone(),
+            signature: op.signature.clone(),
+        }
+    }
+}
+
+impl From<&PackedUserOperation> for IEntryPointV07::PackedUserOperation {
+    fn from(op: &PackedUserOperation) -> Self {
+        Self {
+            sender: op.sender,
+            nonce: op.nonce,
+            initCode: op.init_code(),
+            callData: op.call_data.clone(),
+            accountGasLimits: pack_u128s(op.verification_gas_limit, op.call_gas_limit),
+            preVerificationGas: op.pre_verification_gas,
+            gasFees: pack_u128s(op.max_priority_fee_per_gas, op.max_fee_per_gas),
+            paymasterAndData: op.paymaster_and_data(),
+            signature: op.signature.clone(),
+        }
+    }
+}
+
+/// Packs two values in a word, truncated to their lower 128 bits, as the v0.7 entry point does.
+fn pack_u128s(high: U256, low: U256) -> B256 {
+    let mut word = B256::ZERO;
+    word[..16].copy_from_slice(&high.to_be_bytes::<32>()[16..]);
+    word[16..].copy_from_slice(&low.to_be_bytes::<32>()[16..]);
+    word
+}
+
+/// Entry point helpers, reading the nonces and hashes of user operations with `eth_call`.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait EntryPointApi<T, N = Ethereum>: Send + Sync {
+    /// Returns the next nonce of the sender for the given key, with `EntryPoint.getNonce`.
+    ///
+    /// The key lets accounts use several nonce sequences in parallel, the default one being `0`.
+    async fn entry_point_nonce(
+        &self,
+        entry_point: Address,
+        sender: Address,
+        key: U192,
+    ) -> TransportResult<U256>;
+
+    /// Returns the hash of a user operation, to be signed by the account, with
+    /// `EntryPoint.getUserOpHash`.
+    async fn user_operation_hash(
+        &self,
+        entry_point: Address,
+        user_op: &SendUserOperation,
+    ) -> TransportResult<B256>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> EntryPointApi<T, N> for P
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn entry_point_nonce(
+        &self,
+        entry_point: Address,
+        sender: Address,
+        key: U192,
+    ) -> TransportResult<U256> {
+        // Both versions share the same `getNonce` function.
+        let call = IEntryPointV07::getNonceCall { sender, key: U256::from(key) };
+        Ok(call_entry_point(self, entry_point, &call).await?.nonce)
+    }
+
+    async fn user_operation_hash(
+        &self,
+        entry_point: Address,
+        user_op: &SendUserOperation,
+    ) -> TransportResult<B256> {
+        match user_op {
+            SendUserOperation::EntryPointV06(op) => {
+                let call = IEntryPointV06::getUserOpHashCall { userOp: op.into() };
+                Ok(call_entry_point(self, entry_point, &call).await?._0)
+            }
+            SendUserOperation::EntryPointV07(op) => {
+                let call = IEntryPointV07::getUserOpHashCall { userOp: op.into() };
+                Ok(call_entry_point(self, entry_point, &call).await?._0)
+            }
+        }
+    }
+}
+
+/// Calls a view function of the entry point.
+async fn call_entry_point<P, C, T, N>(
+    provider: &P,
+    entry_point: Address,
+    call: &C,
+) -> TransportResult<C::Return>
+where
+    P: Provider<T, N> + ?Sized,
+    C: SolCall,
+    T: Transport + Clone,
+    N: Network,
+{
+    let tx = N::TransactionRequest::default().with_to(entry_point).with_input(call.abi_encode());
+    let output = provider.call(&tx).await?;
+    C::abi_decode_returns(&output, true).map_err(TransportErrorKind::custom)
+}
+
+/// Bundler rpc interface, to estimate and send user operations.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait BundlerApi<T, N = Ethereum>: Send + Sync {
+    /// Returns the entry points supported by the bundler, with `eth_supportedEntryPoints`.
+    async fn supported_entry_points(&self) -> TransportResult<Vec<Address>>;
+
+    /// Estimates the gas limits of a user operation, with `eth_estimateUserOperationGas`.
+    ///
+    /// The user operation must have a dummy signature of the same shape as its real signature,
+    /// for the account validation to be estimated correctly.
+    async fn estimate_user_operation_gas(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+    ) -> TransportResult<UserOperationGasEstimation>;
+
+    /// Sends a signed user operation to the bundler, with `eth_sendUserOperation`, returning its
+    /// hash.
+    async fn send_user_operation(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+    ) -> TransportResult<B256>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> BundlerApi<T, N> for P
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn supported_entry_points(&self) -> TransportResult<Vec<Address>> {
+        self.client().request("eth_supportedEntryPoints", ()).await
+    }
+
+    async fn estimate_user_operation_gas(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+    ) -> TransportResult<UserOperationGasEstimation> {
+        self.client().request("eth_estimateUserOperationGas", (user_op, entry_point)).await
+    }
+
+    async fn send_user_operation(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+    ) -> TransportResult<B256> {
+        self.client().request("eth_sendUserOperation", (user_op, entry_point)).await
+    }
+}
+
+/// Paymaster rpc interface, to have user operations sponsored by a paymaster service.
+///
+/// Supports both the `pm_sponsorUserOperation` method, implemented by most paymaster services, and
+/// the standard [ERC-7677] methods. The returned [`PaymasterSponsorship`] is applied to the user
+/// operation with [`SendUserOperation::apply_sponsorship`], before the operation is signed.
+///
+/// [ERC-7677]: https://eips.ethereum.org/EIPS/eip-7677
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait PaymasterApi<T, N = Ethereum>: Send + Sync {
+    /// Requests the sponsorship of a user operation, with `pm_sponsorUserOperation`.
+    ///
+    /// The context is specific to the paymaster service, e.g. a sponsorship policy ID.
+    async fn sponsor_user_operation(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+        context: Option<Value>,
+    ) -> TransportResult<PaymasterSponsorship>;
+
+    /// Returns stub paymaster data for gas estimation, with `pm_getPaymasterStubData`.
+    ///
+    /// See [ERC-7677](https://eips.ethereum.org/EIPS/eip-7677) for more details.
+    async fn get_paymaster_stub_data(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+        context: Option<Value>,
+    ) -> TransportResult<PaymasterSponsorship>;
+
+    /// Returns the final paymaster data of a user operation, with `pm_getPaymasterData`.
+    ///
+    /// The user operation must have its final gas limits, since the paymaster signature covers
+    /// them. See [ERC-7677](https://eips.ethereum.org/EIPS/eip-7677) for more details.
+    async fn get_paymaster_data(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+        context: Option<Value>,
+    ) -> TransportResult<PaymasterSponsorship>;
+
+    /// Requests the sponsorship of a user operation with `pm_sponsorUserOperation`, and applies it
+    /// to the operation.
+    async fn sponsor(
+        &self,
+        user_op: &mut SendUserOperation,
+        entry_point: Address,
+        context: Option<Value>,
+    ) -> TransportResult<()> {
+        let sponsorship = self.sponsor_user_operation(user_op, entry_point, context).await?;
+        user_op.apply_sponsorship(&sponsorship);
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> PaymasterApi<T, N> for P
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn sponsor_user_operation(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+        context: Option<Value>,
+    ) -> TransportResult<PaymasterSponsorship> {
+        match context {
+            Some(context) => {
+                self.client()
+                    .request("pm_sponsorUserOperation", (user_op, entry_point, context))
+                    .await
+            }
+            None => self.client().request("pm_sponsorUserOperation", (user_op, entry_point)).await,
+        }
+    }
+
+    async fn get_paymaster_stub_data(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+        context: Option<Value>,
+    ) -> TransportResult<PaymasterSponsorship> {
+        let chain_id = U64::from(self.get_chain_id().await?);
+        let context = context.unwrap_or_else(|| Value::Object(Default::default()));
+        self.client()
+            .request("pm_getPaymasterStubData", (user_op, entry_point, chain_id, context))
+            .await
+    }
+
+    async fn get_paymaster_data(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+        context: Option<Value>,
+    ) -> TransportResult<PaymasterSponsorship> {
+        let chain_id = U64::from(self.get_chain_id().await?);
+        let context = context.unwrap_or_else(|| Value::Object(Default::default()));
+        self.client()
+            .request("pm_getPaymasterData", (user_op, entry_point, chain_id, context))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, RootProvider};
+    use alloy_primitives::U256;
+    use alloy_rpc_types_eth::erc4337::PackedUserOperation;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn sponsors_user_operations() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let transport = MockTransport::new(move |method, params| {
+            let result = match method {
+                "eth_chainId" => json!("0x1"),
+                "pm_sponsorUserOperation" | "pm_getPaymasterData" => json!({
+                    "paymaster": Address::with_last_byte(1),
+                    "paymasterData": "0xabcd",
+                    "paymasterVerificationGasLimit": "0x1",
+                    "paymasterPostOpGasLimit": "0x2",
+                    "callGasLimit": "0x5208",
+                }),
+                method => unreachable!("{method}"),
+            };
+            recorded.lock().unwrap().push((method.to_string(), params));
+            Ok(result)
+        });
+        let provider = RootProvider::<_, Ethereum>::new(transport.client());
+
+        let entry_point = Address::with_last_byte(7);
+        let mut user_op = SendUserOperation::from(PackedUserOperation::default());
+        provider.sponsor(&mut user_op, entry_point, None).await.unwrap();
+        let SendUserOperation::EntryPointV07(op) = &user_op else { unreachable!() };
+        assert_eq!(op.paymaster, Some(Address::with_last_byte(1)));
+        assert_eq!(op.paymaster_post_op_gas_limit, Some(U256::from(2)));
+        assert_eq!(op.call_gas_limit, U256::from(21000));
+
+        let context = json!({ "sponsorshipPolicyId": "sp_test" });
+        provider.get_paymaster_data(&user_op, entry_point, Some(context.clone())).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].0, "pm_sponsorUserOperation");
+        assert_eq!(requests[0].1[1], json!(entry_point));
+        assert_eq!(requests[0].1.as_array().unwrap().len(), 2);
+        assert_eq!(requests[2].0, "pm_getPaymasterData");
+        assert_eq!(requests[2].1[2], json!("0x1"));
+        assert_eq!(requests[2].1[3], context);
+    }
+}
@@ -0,0 +1,370 @@
+//! A local view of the transaction pool of a node.
+use alloy_primitives::{Address, TxHash};
+use alloy_rpc_types_eth::Transaction;
+use alloy_rpc_types_txpool::TxpoolContent;
+use std::{
+    cmp::Reverse,
+    collections::{btree_map, hash_map::Entry, BTreeMap, BinaryHeap, HashMap},
+};
+
+/// The default maximum number of transactions in a [`MempoolView`].
+const DEFAULT_MAX_TRANSACTIONS: usize = 10_000;
+
+/// The minimum fee increase, in percent, for a transaction to replace another with the same sender
+/// and nonce, as in geth.
+const PRICE_BUMP: u128 = 10;
+
+/// An in-memory view of the transaction pool of a node, ordered by effective tip.
+///
+/// The view is filled with [`txpool_content`](super::TxPoolApi::txpool_content) snapshots with
+/// [`sync`](Self::sync), and kept up to date with pending transaction subscriptions with
+/// [`insert`](Self::insert), then [`mined`](Self::mined) and [`set_base_fee`](Self::set_base_fee)
+/// as blocks are included.
+///
+/// The transactions of each sender are kept as a nonce chain: only the transactions following the
+/// sender's next nonce without a gap are executable, and [`best`](Self::best) yields them in the
+/// order a block builder would include them. When the view is full, the last transaction of the
+/// sender paying the lowest tip is evicted.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::ext::{MempoolView, TxPoolApi};
+/// use futures::StreamExt;
+///
+/// let mut view = MempoolView::new(provider.get_gas_price().await?);
+/// view.sync(provider.txpool_content().await?);
+///
+/// let mut stream = provider.pending_transactions().await?;
+/// while let Some(tx) = stream.next().await {
+///     view.insert(tx);
+///     let gas: u128 = view.best().take(100).map(|tx| tx.gas).sum();
+///     println!("the next 100 transactions use {gas} gas");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MempoolView {
+    base_fee: u128,
+    max_transactions: usize,
+    /// The transactions of each sender, by nonce.
+    senders: HashMap<Address, BTreeMap<u64, Transaction>>,
+    /// The next nonce of each sender, when known.
+    nonces: HashMap<Address, u64>,
+    /// The sender and nonce of each transaction.
+    hashes: HashMap<TxHash, (Address, u64)>,
+}
+
+impl MempoolView {
+    /// Creates an empty view, ordering transactions with the given base fee.
+    pub fn new(base_fee: u128) -> Self {
+        Self {
+            base_fee,
+            max_transactions: DEFAULT_MAX_TRANSACTIONS,
+            senders: HashMap::new(),
+            nonces: HashMap::new(),
+            hashes: HashMap::new(),
+        }
+    }
+
+    /// Sets the maximum number of transactions in the view. Defaults to 10,000.
+    pub fn with_max_transactions(mut self, max_transactions: usize) -> Self {
+        self.max_transactions = max_transactions;
+        self.evict();
+        self
+    }
+
+    /// Returns the base fee transactions are ordered with.
+    pub const fn base_fee(&self) -> u128 {
+        self.base_fee
+    }
+
+    /// Sets the base fee transactions are ordered with, e.g. the base fee of the next block.
+    pub fn set_base_fee(&mut self, base_fee: u128) {
+        self.base_fee = base_fee;
+    }
+
+    /// Returns the number of transactions in the view.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if the view has no transactions.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Returns the transaction with the given hash, if any.
+    pub fn get(&self, hash: &TxHash) -> Option<&Transaction> {
+        let (sender, nonce) = self.hashes.get(hash)?;
+        self.senders.get(sender)?.get(nonce)
+    }
+
+    /// Returns the transactions of the given sender, by nonce.
+    pub fn sender(&self, sender: Address) -> impl Iterator<Item = &Transaction> + '_ {
+        self.senders.get(&sender).into_iter().flat_map(BTreeMap::values)
+    }
+
+    /// Returns the tip per gas the transaction pays with the current base fee, or `None` if it
+    /// cannot pay the base fee.
+    pub fn effective_tip(&self, tx: &Transaction) -> Option<u128> {
+        let (max_fee, max_priority_fee) = fees(tx);
+        max_fee.checked_sub(self.base_fee).map(|tip| tip.min(max_priority_fee))
+    }
+
+    /// Replaces the transactions of the view with the content of the node's transaction pool.
+    ///
+    /// The lowest pending nonce of each sender is taken as its next nonce.
+    pub fn sync(&mut self, content: TxpoolContent) {
+        self.senders.clear();
+        self.nonces.clear();
+        self.hashes.clear();
+        for (sender, txs) in content.pending {
+            if let Some(nonce) = txs.values().map(|tx| tx.nonce).min() {
+                self.nonces.insert(sender, nonce);
+            }
+            self.extend(txs.into_values());
+        }
+        self.extend(content.queued.into_values().flat_map(BTreeMap::into_values));
+    }
+
+    /// Inserts a pending transaction, returning `true` if it was added to the view.
+    ///
+    /// A transaction replaces the one with the same sender and nonce only if it raises both its
+    /// fee caps by 10%. Transactions that are already mined or below the next nonce of their
+    /// sender are ignored.
+    pub fn insert(&mut self, tx: Transaction) -> bool {
+        if tx.block_hash.is_some()
+            || self.nonces.get(&tx.from).is_some_and(|&next| tx.nonce < next)
+            || self.hashes.contains_key(&tx.hash)
+        {
+            return false;
+        }
+
+        let (sender, nonce, hash) = (tx.from, tx.nonce, tx.hash);
+        match self.senders.entry(sender).or_default().entry(nonce) {
+            btree_map::Entry::Occupied(mut entry) => {
+                if !is_replacement(entry.get(), &tx) {
+                    return false;
+                }
+                self.hashes.remove(&entry.get().hash);
+                entry.insert(tx);
+            }
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(tx);
+            }
+        }
+        self.hashes.insert(hash, (sender, nonce));
+        self.evict();
+        self.hashes.contains_key(&hash)
+    }
+
+    /// Removes the transaction with the given hash, e.g. when it was dropped by the node.
+    ///
+    /// The later transactions of the sender are kept, but are no longer executable.
+    pub fn remove(&mut self, hash: &TxHash) -> Option<Transaction> {
+        let (sender, nonce) = self.hashes.remove(hash)?;
+        let Entry::Occupied(mut txs) = self.senders.entry(sender) else { return None };
+        let tx = txs.get_mut().remove(&nonce);
+        if txs.get().is_empty() {
+            txs.remove();
+        }
+        tx
+    }
+
+    /// Records that the transaction of `sender` with the given nonce was mined, removing it and the
+    /// transactions of `sender` with lower nonces.
+    pub fn mined(&mut self, sender: Address, nonce: u64) {
+        let next = nonce.saturating_add(1);
+        let known = self.nonces.entry(sender).or_insert(next);
+        *known = next.max(*known);
+        let Entry::Occupied(mut txs) = self.senders.entry(sender) else { return };
+        let later = txs.get_mut().split_off(&next);
+        for tx in std::mem::replace(txs.get_mut(), later).into_values() {
+            self.hashes.remove(&tx.hash);
+        }
+        if txs.get().is_empty() {
+            txs.remove();
+        }
+    }
+
+    /// Returns the executable transactions in the order a block builder would include them: by
+    /// descending effective tip, following the nonce chain of each sender.
+    ///
+    /// Transactions that cannot pay the base fee are skipped, with the later transactions of their
+    /// sender.
+    pub fn best(&self) -> BestTransactions<'_> {
+        let mut heap = BinaryHeap::new();
+        for (sender, txs) in &self.senders {
+            let Some((&nonce, tx)) = txs.first_key_value() else { continue };
+            if self.nonces.get(sender).is_some_and(|&next| nonce != next) {
+                continue;
+            }
+            if let Some(tip) = self.effective_tip(tx) {
+                heap.push((tip, Reverse(*sender), nonce));
+            }
+        }
+        BestTransactions { view: self, heap }
+    }
+
+    /// Evicts transactions until the view is within its maximum size.
+    fn evict(&mut self) {
+        while self.len() > self.max_transactions {
+            // Evict from the end of the nonce chains, to keep them without gaps.
+            let worst = self
+                .senders
+                .values()
+                .filter_map(|txs| txs.last_key_value())
+                .min_by_key(|(_, tx)| (self.effective_tip(tx), Reverse(tx.from)))
+                .map(|(_, tx)| tx.hash);
+            match worst {
+                Some(hash) => self.remove(&hash),
+                None => break,
+            };
+        }
+    }
+}
+
+impl Extend<Transaction> for MempoolView {
+    fn extend<I: IntoIterator<Item = Transaction>>(&mut self, iter: I) {
+        for tx in iter {
+            self.insert(tx);
+        }
+    }
+}
+
+/// An iterator over the executable transactions of a [`MempoolView`], returned by
+/// [`MempoolView::best`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct BestTransactions<'a> {
+    view: &'a MempoolView,
+    /// The effective tip, sender and nonce of the next executable transaction of each sender.
+    heap: BinaryHeap<(u128, Reverse<Address>, u64)>,
+}
+
+impl<'a> Iterator for BestTransactions<'a> {
+    type Item = &'a Transaction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, Reverse(sender), nonce) = self.heap.pop()?;
+        let txs = &self.view.senders[&sender];
+        if let Some(next) = nonce.checked_add(1).and_then(|nonce| txs.get(&nonce)) {
+            if let Some(tip) = self.view.effective_tip(next) {
+                self.heap.push((tip, Reverse(sender), next.nonce));
+            }
+        }
+        txs.get(&nonce)
+    }
+}
+
+/// Returns the max fee and max priority fee per gas of the transaction.
+fn fees(tx: &Transaction) -> (u128, u128) {
+    match (tx.max_fee_per_gas, tx.gas_price) {
+        (Some(max_fee), _) => (max_fee, tx.max_priority_fee_per_gas.unwrap_or(max_fee)),
+        (None, gas_price) => {
+            let gas_price = gas_price.unwrap_or_default();
+            (gas_price, gas_price)
+        }
+    }
+}
+
+/// Returns `true` if `new` raises both fee caps of `old` by [`PRICE_BUMP`] percent.
+fn is_replacement(old: &Transaction, new: &Transaction) -> bool {
+    let bumped = |old: u128| old.saturating_mul(100 + PRICE_BUMP) / 100;
+    let ((old_max_fee, old_priority_fee), (new_max_fee, new_priority_fee)) = (fees(old), fees(new));
+    new_max_fee >= bumped(old_max_fee) && new_priority_fee >= bumped(old_priority_fee)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn tx(sender: u8, nonce: u64, max_fee: u128, max_priority_fee: u128) -> Transaction {
+        Transaction {
+            hash: B256::from(alloy_primitives::keccak256([sender, nonce as u8, max_fee as u8])),
+            from: Address::with_last_byte(sender),
+            nonce,
+            gas: 21_000,
+            max_fee_per_gas: Some(max_fee),
+            max_priority_fee_per_gas: Some(max_priority_fee),
+            ..Default::default()
+        }
+    }
+
+    fn best(view: &MempoolView) -> Vec<(u8, u64)> {
+        view.best().map(|tx| (tx.from[19], tx.nonce)).collect()
+    }
+
+    #[test]
+    fn orders_by_effective_tip_along_nonce_chains() {
+        let mut view = MempoolView::new(100);
+        view.extend([
+            tx(1, 0, 103, 3),
+            tx(1, 1, 200, 50),
+            tx(2, 0, 110, 20), // Pays a tip of 10.
+            tx(3, 5, 150, 5),
+            tx(4, 0, 99, 99), // Cannot pay the base fee.
+            tx(4, 1, 500, 100),
+        ]);
+        assert_eq!(best(&view), [(2, 0), (3, 5), (1, 0), (1, 1)]);
+
+        // A lower base fee lets sender 4 pay it.
+        view.set_base_fee(50);
+        assert_eq!(best(&view), [(4, 0), (4, 1), (2, 0), (3, 5), (1, 0), (1, 1)]);
+
+        view.mined(Address::with_last_byte(1), 0);
+        view.mined(Address::with_last_byte(3), 3);
+        assert_eq!(view.len(), 5);
+        // Sender 3 now has a nonce gap.
+        assert_eq!(best(&view), [(1, 1), (4, 0), (4, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn replaces_and_evicts() {
+        let mut view = MempoolView::new(0).with_max_transactions(3);
+        let original = tx(1, 0, 100, 10);
+        assert!(view.insert(original.clone()));
+        assert!(!view.insert(original.clone()));
+        // The fee caps must both be raised by 10%.
+        assert!(!view.insert(tx(1, 0, 200, 10)));
+        assert!(view.insert(tx(1, 0, 110, 11)));
+        assert_eq!(view.get(&original.hash), None);
+        assert_eq!(
+            view.sender(Address::with_last_byte(1)).next().unwrap().max_fee_per_gas,
+            Some(110)
+        );
+
+        assert!(view.insert(tx(1, 1, 100, 1)));
+        assert!(view.insert(tx(2, 0, 100, 5)));
+        // The last transaction of sender 1 pays the lowest tip.
+        assert!(view.insert(tx(3, 0, 100, 3)));
+        assert_eq!(best(&view), [(1, 0), (2, 0), (3, 0)]);
+        assert!(!view.insert(tx(4, 0, 100, 2)));
+        assert_eq!(view.len(), 3);
+    }
+
+    #[test]
+    fn syncs_txpool_content() {
+        let mut view = MempoolView::new(0);
+        view.insert(tx(9, 0, 100, 100));
+
+        let mut content = TxpoolContent::default();
+        for tx in [tx(1, 4, 100, 1), tx(1, 5, 100, 2)] {
+            content.pending.entry(tx.from).or_default().insert(tx.nonce.to_string(), tx);
+        }
+        let queued = tx(1, 7, 100, 3);
+        content.queued.entry(queued.from).or_default().insert(queued.nonce.to_string(), queued);
+        view.sync(content);
+
+        assert_eq!(view.len(), 3);
+        assert_eq!(best(&view), [(1, 4), (1, 5)]);
+        // Nonces below the pending ones were already mined.
+        assert!(!view.insert(tx(1, 3, 100, 100)));
+        assert!(view.insert(tx(1, 6, 100, 100)));
+        assert_eq!(best(&view), [(1, 4), (1, 5), (1, 6), (1, 7)]);
+    }
+}
@@ -0,0 +1,88 @@
+//! This module extends the Ethereum JSON-RPC provider with the Miner namespace's RPC methods.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::{Address, U128, U64};
+use alloy_transport::{Transport, TransportResult};
+
+/// Miner namespace rpc interface that gives access to the block production settings of the node.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait MinerApi<N, T>: Send + Sync {
+    /// Sets the minimal gas price, in wei, a transaction must pay to be included by the miner,
+    /// returning whether it was accepted.
+    async fn miner_set_gas_price(&self, gas_price: u128) -> TransportResult<bool>;
+
+    /// Sets the gas limit the miner will target when producing blocks, returning whether it was
+    /// accepted.
+    async fn miner_set_gas_limit(&self, gas_limit: u64) -> TransportResult<bool>;
+
+    /// Sets the address receiving the rewards of the mined blocks, returning whether it was
+    /// accepted.
+    async fn miner_set_etherbase(&self, address: Address) -> TransportResult<bool>;
+
+    /// Starts producing blocks.
+    async fn miner_start(&self) -> TransportResult<()>;
+
+    /// Stops producing blocks.
+    async fn miner_stop(&self) -> TransportResult<()>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> MinerApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn miner_set_gas_price(&self, gas_price: u128) -> TransportResult<bool> {
+        self.client().request("miner_setGasPrice", (U128::from(gas_price),)).await
+    }
+
+    async fn miner_set_gas_limit(&self, gas_limit: u64) -> TransportResult<bool> {
+        self.client().request("miner_setGasLimit", (U64::from(gas_limit),)).await
+    }
+
+    async fn miner_set_etherbase(&self, address: Address) -> TransportResult<bool> {
+        self.client().request("miner_setEtherbase", (address,)).await
+    }
+
+    async fn miner_start(&self) -> TransportResult<()> {
+        self.client().request("miner_start", ()).await
+    }
+
+    async fn miner_stop(&self) -> TransportResult<()> {
+        self.client().request("miner_stop", ()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn sets_mining_settings() {
+        let transport = MockTransport::new(|method, params| {
+            let result = match method {
+                "miner_setGasPrice" => json!(params == json!(["0x3b9aca00"])),
+                "miner_setGasLimit" => json!(params == json!(["0x1c9c380"])),
+                "miner_setEtherbase" => json!(params == json!([Address::with_last_byte(1)])),
+                "miner_start" | "miner_stop" => {
+                    assert!(params.is_null() || params == json!([]));
+                    Value::Null
+                }
+                method => panic!("unexpected method {method}"),
+            };
+            Ok(result)
+        });
+        let provider = transport.provider();
+
+        assert!(provider.miner_set_gas_price(1_000_000_000).await.unwrap());
+        assert!(provider.miner_set_gas_limit(30_000_000).await.unwrap());
+        assert!(provider.miner_set_etherbase(Address::with_last_byte(1)).await.unwrap());
+        provider.miner_stop().await.unwrap();
+        provider.miner_start().await.unwrap();
+    }
+}
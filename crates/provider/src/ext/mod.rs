@@ -5,20 +5,61 @@ mod admin;
 #[cfg(feature = "admin-api")]
 pub use admin::AdminApi;
 
+#[cfg(feature = "alchemy-api")]
+mod alchemy;
+#[cfg(feature = "alchemy-api")]
+pub use alchemy::AlchemyApi;
+
 #[cfg(feature = "anvil-api")]
 mod anvil;
 #[cfg(feature = "anvil-api")]
 pub use anvil::AnvilApi;
 
+#[cfg(feature = "arbitrum-api")]
+mod arbitrum;
+#[cfg(feature = "arbitrum-api")]
+pub use arbitrum::{ArbitrumApi, ARB_GAS_INFO_ADDRESS, NODE_INTERFACE_ADDRESS};
+
+#[cfg(feature = "clique-api")]
+mod clique;
+#[cfg(feature = "clique-api")]
+pub use clique::CliqueApi;
+
 #[cfg(feature = "engine-api")]
 mod engine;
 #[cfg(feature = "engine-api")]
 pub use engine::EngineApi;
 
+#[cfg(feature = "erc4337-api")]
+mod erc4337;
+#[cfg(feature = "erc4337-api")]
+pub use erc4337::{
+    BundlerApi, EntryPointApi, IEntryPointV06, IEntryPointV07, PaymasterApi,
+    ENTRY_POINT_V06_ADDRESS, ENTRY_POINT_V07_ADDRESS,
+};
+#[cfg(feature = "erc4337-api")]
+mod user_op;
+#[cfg(feature = "erc4337-api")]
+pub use user_op::UserOpBuilder;
+
 #[cfg(feature = "debug-api")]
 mod debug;
 #[cfg(feature = "debug-api")]
 pub use debug::DebugApi;
+#[cfg(feature = "debug-api")]
+mod debug_call;
+#[cfg(feature = "debug-api")]
+pub use debug_call::TraceCallBuilder;
+
+#[cfg(feature = "txpool-api")]
+mod mempool;
+#[cfg(feature = "txpool-api")]
+pub use mempool::{BestTransactions, MempoolView};
+
+#[cfg(feature = "miner-api")]
+mod miner;
+#[cfg(feature = "miner-api")]
+pub use miner::MinerApi;
 
 #[cfg(feature = "net-api")]
 mod net;
@@ -28,7 +69,7 @@ pub use net::NetApi;
 #[cfg(feature = "trace-api")]
 mod trace;
 #[cfg(feature = "trace-api")]
-pub use trace::{TraceApi, TraceCallList};
+pub use trace::{TraceApi, TraceCallList, TraceFilterStream};
 
 #[cfg(feature = "txpool-api")]
 mod txpool;
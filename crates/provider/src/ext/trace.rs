@@ -3,12 +3,18 @@ use crate::{Provider, RpcWithBlock};
 use alloy_eips::BlockNumberOrTag;
 use alloy_network::Network;
 use alloy_primitives::TxHash;
+use alloy_rpc_client::ClientRef;
 use alloy_rpc_types_eth::Index;
 use alloy_rpc_types_trace::{
     filter::TraceFilter,
     parity::{LocalizedTransactionTrace, TraceResults, TraceResultsWithTransactionHash, TraceType},
 };
-use alloy_transport::{Transport, TransportResult};
+use alloy_transport::{RpcFut, Transport, TransportResult};
+use futures::Stream;
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
 
 /// List of trace calls for use with [`TraceApi::trace_call_many`]
 pub type TraceCallList<'a, N> = &'a [(<N as Network>::TransactionRequest, Vec<TraceType>)];
@@ -76,6 +82,21 @@ where
         tracer: &TraceFilter,
     ) -> TransportResult<Vec<LocalizedTransactionTrace>>;
 
+    /// Returns a stream of the traces matching `filter`, fetched in pages of `page_size` traces
+    /// until exhaustion.
+    ///
+    /// The `after` and `count` fields of `filter` are the offset of the first trace and the total
+    /// number of traces to return, if set.
+    ///
+    /// # Note
+    ///
+    /// Not all nodes support this call.
+    fn trace_filter_paginated(
+        &self,
+        filter: TraceFilter,
+        page_size: u64,
+    ) -> TraceFilterStream<'_, T>;
+
     /// Trace all transactions in the given block.
     ///
     /// # Note
@@ -156,6 +177,14 @@ where
         self.client().request("trace_filter", (tracer,)).await
     }
 
+    fn trace_filter_paginated(
+        &self,
+        filter: TraceFilter,
+        page_size: u64,
+    ) -> TraceFilterStream<'_, T> {
+        TraceFilterStream::new(self.client(), filter, page_size)
+    }
+
     async fn trace_block(
         &self,
         block: BlockNumberOrTag,
@@ -180,6 +209,106 @@ where
     }
 }
 
+/// A stream of the traces matching a [`TraceFilter`], returned by
+/// [`TraceApi::trace_filter_paginated`].
+///
+/// Pages are requested with `trace_filter` one at a time, advancing the `after` offset, until a
+/// page has less traces than requested. The stream ends after the first error.
+#[must_use = "streams do nothing unless polled"]
+pub struct TraceFilterStream<'a, T> {
+    client: ClientRef<'a, T>,
+    filter: TraceFilter,
+    page_size: u64,
+    after: u64,
+    remaining: Option<u64>,
+    page: std::vec::IntoIter<LocalizedTransactionTrace>,
+    request: Option<RpcFut<'a, Vec<LocalizedTransactionTrace>>>,
+    done: bool,
+}
+
+impl<T> std::fmt::Debug for TraceFilterStream<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TraceFilterStream")
+            .field("filter", &self.filter)
+            .field("page_size", &self.page_size)
+            .field("after", &self.after)
+            .field("remaining", &self.remaining)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> TraceFilterStream<'a, T> {
+    /// Creates a new stream of the traces matching `filter`, in pages of `page_size` traces.
+    ///
+    /// # Panics
+    ///
+    /// If `page_size` is zero.
+    pub fn new(client: ClientRef<'a, T>, filter: TraceFilter, page_size: u64) -> Self {
+        assert!(page_size > 0, "page size must be greater than zero");
+        Self {
+            client,
+            after: filter.after.unwrap_or_default(),
+            remaining: filter.count,
+            filter,
+            page_size,
+            page: Vec::new().into_iter(),
+            request: None,
+            done: false,
+        }
+    }
+
+    /// Returns the number of traces to request in the next page.
+    fn next_count(&self) -> u64 {
+        self.remaining.map_or(self.page_size, |remaining| remaining.min(self.page_size))
+    }
+}
+
+impl<T: Transport + Clone> Stream for TraceFilterStream<'_, T> {
+    type Item = TransportResult<LocalizedTransactionTrace>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(trace) = this.page.next() {
+                return Poll::Ready(Some(Ok(trace)));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let count = this.next_count();
+            let request = match &mut this.request {
+                Some(request) => request,
+                None if count == 0 => {
+                    this.done = true;
+                    continue;
+                }
+                None => {
+                    let filter = this.filter.clone().after(this.after).count(count);
+                    this.request.insert(Box::pin(this.client.request("trace_filter", (filter,))))
+                }
+            };
+
+            let result = ready!(request.as_mut().poll(cx));
+            this.request = None;
+            match result {
+                Ok(traces) => {
+                    let len = traces.len() as u64;
+                    this.done = len < count;
+                    this.after += len;
+                    this.remaining = this.remaining.map(|remaining| remaining.saturating_sub(len));
+                    this.page = traces.into_iter();
+                }
+                Err(err) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::ProviderBuilder;
@@ -190,6 +319,59 @@ mod test {
         let _ = tracing_subscriber::fmt::try_init();
     }
 
+    #[tokio::test]
+    async fn test_trace_filter_pages() {
+        use crate::mock::MockTransport;
+        use futures::TryStreamExt;
+        use std::sync::{Arc, Mutex};
+
+        // Serves 25 traces, recording the requested pages.
+        let pages = Arc::new(Mutex::new(Vec::new()));
+        let recorded = pages.clone();
+        let transport = MockTransport::new(move |method, params| {
+            assert_eq!(method, "trace_filter");
+            let (filter,): (TraceFilter,) = serde_json::from_value(params).unwrap();
+            let (after, count) = (filter.after.unwrap(), filter.count.unwrap());
+            recorded.lock().unwrap().push((after, count));
+            let trace: LocalizedTransactionTrace = serde_json::from_value(serde_json::json!({
+                "action": { "callType": "call", "from": "0x0000000000000000000000000000000000000000",
+                    "gas": "0x0", "input": "0x", "to": "0x0000000000000000000000000000000000000000",
+                    "value": "0x0" },
+                "result": { "gasUsed": "0x0", "output": "0x" },
+                "subtraces": 0, "traceAddress": [], "type": "call"
+            }))
+            .unwrap();
+            let traces = vec![trace; 25u64.saturating_sub(after).min(count) as usize];
+            Ok(serde_json::json!(traces))
+        });
+        let provider = transport.provider();
+
+        let filter = TraceFilter::builder().after(5).build();
+        let traces: Vec<_> =
+            provider.trace_filter_paginated(filter, 10).try_collect().await.unwrap();
+        assert_eq!(traces.len(), 20);
+        assert_eq!(*pages.lock().unwrap(), [(5, 10), (15, 10), (25, 10)]);
+
+        pages.lock().unwrap().clear();
+        let filter = TraceFilter::builder().count(12).build();
+        let traces: Vec<_> =
+            provider.trace_filter_paginated(filter, 10).try_collect().await.unwrap();
+        assert_eq!(traces.len(), 12);
+        assert_eq!(*pages.lock().unwrap(), [(0, 10), (10, 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_trace_filter_paginated() {
+        use futures::TryStreamExt;
+
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let filter = TraceFilter::builder().from_block(0).build();
+        let traces: Vec<_> =
+            provider.trace_filter_paginated(filter, 10).try_collect().await.unwrap();
+        assert!(traces.is_empty());
+    }
+
     #[tokio::test]
     async fn test_trace_block() {
         init_tracing();
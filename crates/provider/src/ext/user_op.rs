@@ -0,0 +1,235 @@
+use super::{BundlerApi, EntryPointApi, PaymasterApi};
+use crate::Provider;
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{aliases::U192, Address, U256};
+use alloy_rpc_types_eth::erc4337::SendUserOperation;
+use alloy_transport::{Transport, TransportResult};
+use serde_json::Value;
+use std::marker::PhantomData;
+
+/// A builder filling in the nonce, fees, gas limits, and paymaster fields of a user operation,
+/// before it is signed.
+///
+/// The nonce and fees are read from the node, when they are not set. The gas limits are always
+/// estimated by the bundler, which is also used as the [ERC-7677] paymaster service when the
+/// operation is [sponsored](Self::sponsored).
+///
+/// The operation must have a dummy signature of the same shape as its real signature, for the
+/// account validation to be estimated correctly.
+///
+/// ```no_run
+/// # async fn example(account: alloy_primitives::Address) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::{
+///     ext::{BundlerApi, EntryPointApi, UserOpBuilder, ENTRY_POINT_V07_ADDRESS},
+///     ProviderBuilder,
+/// };
+/// use alloy_rpc_types_eth::erc4337::PackedUserOperation;
+///
+/// let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse()?);
+/// let bundler = ProviderBuilder::new().on_http("http://localhost:4337".parse()?);
+///
+/// let user_op = PackedUserOperation { sender: account, ..Default::default() };
+/// let user_op = UserOpBuilder::new(&provider, &bundler, ENTRY_POINT_V07_ADDRESS, user_op)
+///     .sponsored(None)
+///     .build()
+///     .await?;
+/// let hash = provider.user_operation_hash(ENTRY_POINT_V07_ADDRESS, &user_op).await?;
+/// // Sign the hash with the account's signer, and set the signature.
+/// bundler.send_user_operation(&user_op, ENTRY_POINT_V07_ADDRESS).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [ERC-7677]: https://eips.ethereum.org/EIPS/eip-7677
+#[derive(Debug)]
+#[must_use = "this builder does nothing unless built"]
+pub struct UserOpBuilder<'a, P, B, T, N = Ethereum> {
+    provider: &'a P,
+    bundler: &'a B,
+    entry_point: Address,
+    user_op: SendUserOperation,
+    nonce_key: U192,
+    sponsorship: Option<Option<Value>>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<'a, P, B, T, N> UserOpBuilder<'a, P, B, T, N>
+where
+    P: Provider<T, N>,
+    B: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new builder for a user operation sent to the given entry point, reading the
+    /// chain state from `provider`, and estimating gas with `bundler`.
+    pub fn new(
+        provider: &'a P,
+        bundler: &'a B,
+        entry_point: Address,
+        user_op: impl Into<SendUserOperation>,
+    ) -> Self {
+        Self {
+            provider,
+            bundler,
+            entry_point,
+            user_op: user_op.into(),
+            nonce_key: U192::ZERO,
+            sponsorship: None,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Sets the key of the nonce sequence to read the nonce from. Defaults to `0`.
+    pub const fn nonce_key(mut self, key: U192) -> Self {
+        self.nonce_key = key;
+        self
+    }
+
+    /// Has the operation sponsored by the paymaster service of the bundler, with the given
+    /// service-specific context, e.g. a sponsorship policy ID.
+    ///
+    /// Stub paymaster data is requested before estimating gas, and the final paymaster data
+    /// after, since the paymaster signature covers the gas limits.
+    pub fn sponsored(mut self, context: Option<Value>) -> Self {
+        self.sponsorship = Some(context);
+        self
+    }
+
+    /// Fills in the operation, returning it ready to be signed.
+    pub async fn build(self) -> TransportResult<SendUserOperation> {
+        let Self { provider, bundler, entry_point, mut user_op, nonce_key, sponsorship, .. } = self;
+
+        if user_op.nonce().is_zero() {
+            let nonce =
+                provider.entry_point_nonce(entry_point, user_op.sender(), nonce_key).await?;
+            user_op.set_nonce(nonce);
+        }
+        if user_op.max_fee_per_gas().is_zero() {
+            let fees = provider.estimate_eip1559_fees(None).await?;
+            user_op.set_fees(
+                U256::from(fees.max_fee_per_gas),
+                U256::from(fees.max_priority_fee_per_gas),
+            );
+        }
+
+        if let Some(context) = &sponsorship {
+            let stub =
+                bundler.get_paymaster_stub_data(&user_op, entry_point, context.clone()).await?;
+            user_op.apply_sponsorship(&stub);
+        }
+        let gas = bundler.estimate_user_operation_gas(&user_op, entry_point).await?;
+        user_op.apply_gas_estimation(&gas);
+        if let Some(context) = sponsorship {
+            let sponsorship = bundler.get_paymaster_data(&user_op, entry_point, context).await?;
+            user_op.apply_sponsorship(&sponsorship);
+        }
+        Ok(user_op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ext::{erc4337::IEntryPointV07, ENTRY_POINT_V07_ADDRESS},
+        mock::MockTransport,
+        RootProvider,
+    };
+
+    use alloy_primitives::{hex, B256};
+    use alloy_rpc_types_eth::{erc4337::PackedUserOperation, TransactionRequest};
+    use alloy_sol_types::{SolCall, SolValue};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    /// A node and bundler, recording the methods called.
+    fn endpoint(methods: Arc<Mutex<Vec<String>>>) -> RootProvider<MockTransport> {
+        let transport = MockTransport::new(move |method, params| {
+            let result = match method {
+                "eth_chainId" => json!("0x1"),
+                "eth_call" => {
+                    let tx: TransactionRequest = serde_json::from_value(params[0].clone()).unwrap();
+                    assert_eq!(tx.to, Some(ENTRY_POINT_V07_ADDRESS.into()));
+                    let input = tx.input.input().unwrap();
+                    let output = if input[..4] == IEntryPointV07::getNonceCall::SELECTOR {
+                        let call = IEntryPointV07::getNonceCall::abi_decode(input, true).unwrap();
+                        assert_eq!(call.sender, Address::with_last_byte(1));
+                        // The key is in the upper 192 bits of the nonce.
+                        ((call.key << 64usize) + U256::from(5)).abi_encode()
+                    } else {
+                        let call =
+                            IEntryPointV07::getUserOpHashCall::abi_decode(input, true).unwrap();
+                        assert_eq!(call.userOp.nonce, (U256::from(2) << 64usize) + U256::from(5));
+                        // The gas limits are packed, verification first.
+                        assert_eq!(U256::from_be_slice(&call.userOp.accountGasLimits[..16]), 200);
+                        assert_eq!(U256::from_be_slice(&call.userOp.accountGasLimits[16..]), 100);
+                        B256::with_last_byte(9).abi_encode()
+                    };
+                    json!(hex::encode_prefixed(output))
+                }
+                "pm_getPaymasterStubData" => json!({
+                    "paymaster": Address::with_last_byte(3),
+                    "paymasterData": "0x00",
+                    "paymasterPostOpGasLimit": "0x1",
+                }),
+                "eth_estimateUserOperationGas" => {
+                    assert_eq!(params[0]["paymasterData"], "0x00");
+                    json!({
+                        "preVerificationGas": "0x12c",
+                        "verificationGasLimit": "0xc8",
+                        "callGasLimit": "0x64",
+                        "paymasterVerificationGasLimit": "0x32",
+                    })
+                }
+                "pm_getPaymasterData" => {
+                    assert_eq!(params[0]["callGasLimit"], "0x64");
+                    json!({ "paymaster": Address::with_last_byte(3), "paymasterData": "0xabcd" })
+                }
+                method => unreachable!("{method}"),
+            };
+            methods.lock().unwrap().push(method.to_string());
+            Ok(result)
+        });
+        transport.provider()
+    }
+
+    #[tokio::test]
+    async fn builds_sponsored_user_operation() {
+        let (provider_methods, bundler_methods) = Default::default();
+        let provider = endpoint(Arc::clone(&provider_methods));
+        let bundler = endpoint(Arc::clone(&bundler_methods));
+
+        let user_op = PackedUserOperation {
+            sender: Address::with_last_byte(1),
+            max_fee_per_gas: U256::from(10),
+            ..Default::default()
+        };
+        let user_op = UserOpBuilder::new(&provider, &bundler, ENTRY_POINT_V07_ADDRESS, user_op)
+            .nonce_key(U192::from(2))
+            .sponsored(None)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(*provider_methods.lock().unwrap(), ["eth_call"]);
+        assert_eq!(
+            *bundler_methods.lock().unwrap(),
+            [
+                "eth_chainId",
+                "pm_getPaymasterStubData",
+                "eth_estimateUserOperationGas",
+                "eth_chainId",
+                "pm_getPaymasterData"
+            ]
+        );
+        let SendUserOperation::EntryPointV07(op) = &user_op else { unreachable!() };
+        assert_eq!(op.pre_verification_gas, U256::from(300));
+        assert_eq!(op.paymaster, Some(Address::with_last_byte(3)));
+        assert_eq!(op.paymaster_verification_gas_limit, Some(U256::from(50)));
+        assert_eq!(op.paymaster_post_op_gas_limit, Some(U256::from(1)));
+        assert_eq!(op.paymaster_data, Some(hex!("abcd").into()));
+
+        let hash = provider.user_operation_hash(ENTRY_POINT_V07_ADDRESS, &user_op).await.unwrap();
+        assert_eq!(hash, B256::with_last_byte(9));
+    }
+}
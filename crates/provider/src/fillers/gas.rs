@@ -6,9 +6,10 @@ use crate::{
     utils::Eip1559Estimation,
     Provider,
 };
+use alloy_eips::eip4844::BlobParams;
 use alloy_json_rpc::RpcError;
 use alloy_network::{Network, TransactionBuilder};
-use alloy_rpc_types_eth::BlockNumberOrTag;
+use alloy_rpc_types_eth::{BlockNumberOrTag, Header};
 use alloy_transport::{Transport, TransportResult};
 use futures::FutureExt;
 
@@ -45,6 +46,12 @@ pub enum GasFillable {
 /// - If the network does not support EIP-1559, it will fallback to the legacy tx and populate the
 ///   `gas_limit` and `gas_price` fields if unset.
 ///
+/// The blob fee is computed with the [`BlobParams`] of the fork of the latest block: Prague's if
+/// its header has an [EIP-7685] requests root, Cancun's otherwise. Chains with another blob
+/// schedule should set their parameters with [`GasFiller::with_blob_params`].
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+///
 /// # Example
 ///
 /// ```
@@ -62,9 +69,26 @@ pub enum GasFillable {
 /// # }
 /// ```
 #[derive(Clone, Copy, Debug, Default)]
-pub struct GasFiller;
+pub struct GasFiller {
+    blob_params: Option<BlobParams>,
+}
 
 impl GasFiller {
+    /// Creates a filler computing the blob fee with the given blob parameters of the active fork,
+    /// rather than the ones inferred from the latest block.
+    pub const fn with_blob_params(blob_params: BlobParams) -> Self {
+        Self { blob_params: Some(blob_params) }
+    }
+
+    /// Returns the blob parameters of the fork of the block following the given header.
+    fn blob_params(&self, header: &Header) -> BlobParams {
+        self.blob_params.unwrap_or(if header.requests_root.is_some() {
+            BlobParams::PRAGUE
+        } else {
+            BlobParams::CANCUN
+        })
+    }
+
     async fn prepare_legacy<P, T, N>(
         &self,
         provider: &P,
@@ -146,12 +170,13 @@ impl GasFiller {
         let max_fee_per_blob_gas_fut = tx.max_fee_per_blob_gas().map_or_else(
             || {
                 async {
-                    provider
+                    let header = provider
                         .get_block_by_number(BlockNumberOrTag::Latest, false)
                         .await?
                         .ok_or(RpcError::NullResp)?
-                        .header
-                        .next_block_blob_fee()
+                        .header;
+                    header
+                        .next_block_blob_fee_with_params(self.blob_params(&header))
                         .ok_or(RpcError::UnsupportedFeature("eip4844"))
                 }
                 .right_future()
@@ -300,10 +325,62 @@ mod tests {
         assert_eq!(receipt.gas_used, 0x5208);
     }
 
+    #[tokio::test]
+    async fn blob_fee_of_the_active_fork() {
+        use crate::mock::MockTransport;
+        use alloy_network::Ethereum;
+        use alloy_primitives::B256;
+        use alloy_rpc_types_eth::Block;
+
+        let block = |requests_root| {
+            let header = Header {
+                excess_blob_gas: Some(10_000_000),
+                blob_gas_used: Some(786_432),
+                requests_root,
+                ..Default::default()
+            };
+            serde_json::to_value(Block::<()> { header, ..Default::default() }).unwrap()
+        };
+        let tx = TransactionRequest {
+            gas: Some(21_000),
+            max_fee_per_gas: Some(2),
+            max_priority_fee_per_gas: Some(1),
+            ..Default::default()
+        };
+        let blob_fee = |filler: GasFiller, block: serde_json::Value| {
+            let provider = MockTransport::new(move |method, _| {
+                assert_eq!(method, "eth_getBlockByNumber");
+                Ok(block.clone())
+            })
+            .provider();
+            let tx = tx.clone();
+            async move {
+                match filler.prepare_4844::<_, _, Ethereum>(&provider, &tx).await.unwrap() {
+                    GasFillable::Eip4844 { max_fee_per_blob_gas, .. } => max_fee_per_blob_gas,
+                    fillable => unreachable!("{fillable:?}"),
+                }
+            }
+        };
+        let expected = |params: BlobParams| {
+            params.calc_blob_fee(params.next_block_excess_blob_gas(10_000_000, 786_432))
+        };
+
+        let cancun = blob_fee(GasFiller::default(), block(None)).await;
+        assert_eq!(cancun, expected(BlobParams::CANCUN));
+        let prague = blob_fee(GasFiller::default(), block(Some(B256::ZERO))).await;
+        assert_eq!(prague, expected(BlobParams::PRAGUE));
+        assert_ne!(cancun, prague);
+
+        let custom =
+            BlobParams { target_blob_count: 1, max_blob_count: 2, update_fraction: 2_000_000 };
+        let fee = blob_fee(GasFiller::with_blob_params(custom), block(Some(B256::ZERO))).await;
+        assert_eq!(fee, expected(custom));
+    }
+
     #[tokio::test]
     async fn non_eip1559_network() {
         let provider = ProviderBuilder::new()
-            .filler(crate::fillers::GasFiller)
+            .filler(crate::fillers::GasFiller::default())
             .filler(crate::fillers::NonceFiller::default())
             .filler(crate::fillers::ChainIdFiller::default())
             .on_anvil();
@@ -15,9 +15,36 @@ pub use wallet::WalletFiller;
 mod nonce;
 pub use nonce::NonceFiller;
 
+mod nonce_coordinator;
+pub use nonce_coordinator::{
+    CoordinatedNonceFiller, LocalNonceCoordinator, NonceCoordinator, NonceCoordinatorError,
+    NonceLease,
+};
+
+#[cfg(feature = "nonce-postgres")]
+mod nonce_postgres;
+#[cfg(feature = "nonce-postgres")]
+pub use nonce_postgres::PostgresNonceCoordinator;
+
+#[cfg(feature = "nonce-redis")]
+mod nonce_redis;
+#[cfg(feature = "nonce-redis")]
+pub use nonce_redis::RedisNonceCoordinator;
+
 mod gas;
 pub use gas::GasFiller;
 
+mod policy;
+pub use policy::{Policy, PolicyFiller, PolicyViolation};
+
+#[cfg(feature = "op-fee")]
+mod op_fee;
+#[cfg(feature = "op-fee")]
+pub use op_fee::{
+    flz_compress_len, OpFeeEstimate, OpGasEstimator, OpL1FeeFiller, OpL1FeeParams,
+    GAS_PRICE_ORACLE_ADDRESS,
+};
+
 mod join_fill;
 pub use join_fill::JoinFill;
 use tracing::error;
@@ -206,6 +233,48 @@ pub trait TxFiller<N: Network = Ethereum>: Clone + Send + Sync + std::fmt::Debug
     }
 }
 
+/// An optional filler, e.g. enabled by configuration. `None` fills nothing.
+impl<F, N> TxFiller<N> for Option<F>
+where
+    F: TxFiller<N>,
+    N: Network,
+{
+    type Fillable = F::Fillable;
+
+    fn status(&self, tx: &N::TransactionRequest) -> FillerControlFlow {
+        self.as_ref().map_or(FillerControlFlow::Finished, |filler| filler.status(tx))
+    }
+
+    fn fill_sync(&self, tx: &mut SendableTx<N>) {
+        if let Some(filler) = self {
+            filler.fill_sync(tx);
+        }
+    }
+
+    async fn prepare<P, T>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+    {
+        self.as_ref().expect("checked by 'ready()'").prepare(provider, tx).await
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        match self {
+            Some(filler) => filler.fill(fillable, tx).await,
+            None => Ok(tx),
+        }
+    }
+}
+
 /// A [`Provider`] that applies one or more [`TxFiller`]s.
 ///
 /// Fills arbitrary properties in a transaction request by composing multiple
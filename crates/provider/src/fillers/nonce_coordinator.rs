@@ -0,0 +1,429 @@
+use crate::{
+    fillers::{FillerControlFlow, TxFiller},
+    provider::SendableTx,
+    Provider,
+};
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::Address;
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A lease on the nonce of an address, held while the next nonce is chosen.
+///
+/// Leases expire after the TTL they are acquired with, so that a holder that crashed does not
+/// block the address forever.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonceLease {
+    /// The address the lease is on.
+    pub address: Address,
+    /// The fencing token of the lease, incremented by each acquisition.
+    ///
+    /// A release with the token of an earlier lease is rejected with
+    /// [`NonceCoordinatorError::LeaseLost`].
+    pub token: u64,
+    /// The next nonce recorded by the previous holder, `None` if none was recorded yet.
+    pub next_nonce: Option<u64>,
+    /// Whether the lease was taken over from a holder whose lease expired.
+    ///
+    /// The previous holder may have used nonces it did not record, so the recorded nonce is not
+    /// trusted in that case.
+    pub recovered: bool,
+}
+
+/// An error of a [`NonceCoordinator`].
+#[derive(Debug)]
+pub enum NonceCoordinatorError {
+    /// The lease expired and was taken over before it was released.
+    LeaseLost {
+        /// The address the lease was on.
+        address: Address,
+    },
+    /// The lease could not be acquired before the timeout.
+    Timeout {
+        /// The address the lease is on.
+        address: Address,
+        /// How long the lease was waited for.
+        waited: Duration,
+    },
+    /// The backend of the coordinator failed.
+    Backend(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl NonceCoordinatorError {
+    /// Creates a new [`NonceCoordinatorError::Backend`] error.
+    pub fn backend(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Backend(Box::new(err))
+    }
+}
+
+impl fmt::Display for NonceCoordinatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LeaseLost { address } => {
+                write!(f, "the nonce lease of {address} expired before it was released")
+            }
+            Self::Timeout { address, waited } => {
+                write!(f, "could not acquire the nonce lease of {address} within {waited:?}")
+            }
+            Self::Backend(err) => write!(f, "nonce coordinator backend error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for NonceCoordinatorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Backend(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// A store shared by the processes sending transactions from the same addresses, used to hand
+/// out nonces without collisions.
+///
+/// The store keeps, for each address, the next nonce to use and a lease held while it is read
+/// and incremented. Implementations must acquire and release leases atomically.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait NonceCoordinator: fmt::Debug + Send + Sync {
+    /// Acquires the lease on `address` for `ttl`, returning `None` if it is held by someone else.
+    ///
+    /// An expired lease is taken over, with [`NonceLease::recovered`] set.
+    async fn try_acquire(
+        &self,
+        address: Address,
+        ttl: Duration,
+    ) -> Result<Option<NonceLease>, NonceCoordinatorError>;
+
+    /// Releases the lease, recording `next_nonce` if given.
+    ///
+    /// Fails with [`NonceCoordinatorError::LeaseLost`] if the lease was taken over.
+    async fn release(
+        &self,
+        lease: &NonceLease,
+        next_nonce: Option<u64>,
+    ) -> Result<(), NonceCoordinatorError>;
+}
+
+/// A [`NonceCoordinator`] in the memory of the process.
+///
+/// This coordinates the fillers of the providers of a single process, and serves as a reference
+/// for the semantics of the shared implementations.
+#[derive(Debug, Default)]
+pub struct LocalNonceCoordinator {
+    entries: Mutex<HashMap<Address, LocalEntry>>,
+}
+
+#[derive(Debug, Default)]
+struct LocalEntry {
+    token: u64,
+    next_nonce: Option<u64>,
+    held_until: Option<Instant>,
+}
+
+impl LocalNonceCoordinator {
+    /// Creates a new, empty coordinator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NonceCoordinator for LocalNonceCoordinator {
+    async fn try_acquire(
+        &self,
+        address: Address,
+        ttl: Duration,
+    ) -> Result<Option<NonceLease>, NonceCoordinatorError> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.entry(address).or_default();
+        let recovered = match entry.held_until {
+            Some(until) if until > now => return Ok(None),
+            Some(_) => true,
+            None => false,
+        };
+        entry.token += 1;
+        entry.held_until = Some(now + ttl);
+        Ok(Some(NonceLease {
+            address,
+            token: entry.token,
+            next_nonce: entry.next_nonce,
+            recovered,
+        }))
+    }
+
+    async fn release(
+        &self,
+        lease: &NonceLease,
+        next_nonce: Option<u64>,
+    ) -> Result<(), NonceCoordinatorError> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get_mut(&lease.address) {
+            Some(entry) if entry.token == lease.token && entry.held_until.is_some() => {
+                entry.held_until = None;
+                entry.next_nonce = next_nonce.or(entry.next_nonce);
+                Ok(())
+            }
+            _ => Err(NonceCoordinatorError::LeaseLost { address: lease.address }),
+        }
+    }
+}
+
+/// A [`TxFiller`] that fills nonces handed out by a [`NonceCoordinator`], so that several
+/// processes can send transactions from the same address.
+///
+/// For each transaction, the filler acquires the lease of the sender, takes the next nonce
+/// recorded in the coordinator, records its successor and releases the lease. The nonce is read
+/// from the node instead when none is recorded or when the lease was taken over from a crashed
+/// holder, keeping the highest of both in the latter case.
+///
+/// As with [`NonceFiller`](super::NonceFiller), a nonce is consumed even if the transaction is
+/// then not sent.
+///
+/// # Example
+///
+/// ```
+/// # use alloy_network::{NetworkWallet, EthereumWallet, Ethereum};
+/// # use alloy_rpc_types_eth::TransactionRequest;
+/// # use alloy_provider::{fillers::LocalNonceCoordinator, ProviderBuilder, Provider};
+/// # async fn test<W: NetworkWallet<Ethereum> + Clone>(url: url::Url, wallet: W) -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new()
+///     .with_coordinated_nonce_management(LocalNonceCoordinator::new())
+///     .wallet(wallet)
+///     .on_http(url);
+///
+/// provider.send_transaction(TransactionRequest::default()).await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CoordinatedNonceFiller {
+    coordinator: Arc<dyn NonceCoordinator>,
+    lease_ttl: Duration,
+    retry_interval: Duration,
+    acquire_timeout: Duration,
+}
+
+impl CoordinatedNonceFiller {
+    /// Creates a new filler using the given coordinator.
+    pub fn new(coordinator: impl NonceCoordinator + 'static) -> Self {
+        Self::with_shared(Arc::new(coordinator))
+    }
+
+    /// Creates a new filler using a coordinator shared with other fillers.
+    pub fn with_shared(coordinator: Arc<dyn NonceCoordinator>) -> Self {
+        Self {
+            coordinator,
+            lease_ttl: Duration::from_secs(10),
+            retry_interval: Duration::from_millis(50),
+            acquire_timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Sets how long a lease is held before others can take it over, 10 seconds by default.
+    ///
+    /// This must cover the request of the nonce to the node.
+    pub const fn with_lease_ttl(mut self, ttl: Duration) -> Self {
+        self.lease_ttl = ttl;
+        self
+    }
+
+    /// Sets the interval between two attempts to acquire a held lease, 50 milliseconds by
+    /// default.
+    pub const fn with_retry_interval(mut self, interval: Duration) -> Self {
+        self.retry_interval = interval;
+        self
+    }
+
+    /// Sets how long to wait for a held lease before failing, 30 seconds by default.
+    pub const fn with_acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = timeout;
+        self
+    }
+
+    /// Returns the coordinator of the filler.
+    pub fn coordinator(&self) -> &Arc<dyn NonceCoordinator> {
+        &self.coordinator
+    }
+
+    /// Waits for the lease of `address`.
+    async fn acquire(&self, address: Address) -> Result<NonceLease, NonceCoordinatorError> {
+        let start = Instant::now();
+        loop {
+            if let Some(lease) = self.coordinator.try_acquire(address, self.lease_ttl).await? {
+                return Ok(lease);
+            }
+            let waited = start.elapsed();
+            if waited >= self.acquire_timeout {
+                return Err(NonceCoordinatorError::Timeout { address, waited });
+            }
+            tokio::time::sleep(self.retry_interval).await;
+        }
+    }
+
+    /// Get the next nonce for the given account.
+    async fn get_next_nonce<P, T, N>(&self, provider: &P, address: Address) -> TransportResult<u64>
+    where
+        P: Provider<T, N>,
+        N: Network,
+        T: Transport + Clone,
+    {
+        let lease = self.acquire(address).await.map_err(TransportErrorKind::custom)?;
+        let nonce = match lease.next_nonce {
+            Some(nonce) if !lease.recovered => Ok(nonce),
+            recorded => {
+                if lease.recovered {
+                    warn!(%address, "recovering the nonce lease of a stale holder");
+                }
+                provider
+                    .get_transaction_count(address)
+                    .pending()
+                    .await
+                    .map(|pending| recorded.map_or(pending, |recorded| recorded.max(pending)))
+            }
+        };
+        // Release the lease even if the node could not be reached, without recording anything.
+        let next_nonce = nonce.as_ref().ok().map(|nonce| nonce + 1);
+        self.coordinator.release(&lease, next_nonce).await.map_err(TransportErrorKind::custom)?;
+        nonce
+    }
+}
+
+impl<N: Network> TxFiller<N> for CoordinatedNonceFiller {
+    type Fillable = u64;
+
+    fn status(&self, tx: &<N as Network>::TransactionRequest) -> FillerControlFlow {
+        if tx.nonce().is_some() {
+            return FillerControlFlow::Finished;
+        }
+        if tx.from().is_none() {
+            return FillerControlFlow::missing("CoordinatedNonceManager", vec!["from"]);
+        }
+        FillerControlFlow::Ready
+    }
+
+    fn fill_sync(&self, _tx: &mut SendableTx<N>) {}
+
+    async fn prepare<P, T>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+    {
+        let from = tx.from().expect("checked by 'ready()'");
+        self.get_next_nonce(provider, from).await
+    }
+
+    async fn fill(
+        &self,
+        nonce: Self::Fillable,
+        mut tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        if let Some(builder) = tx.as_mut_builder() {
+            builder.set_nonce(nonce);
+        }
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, RootProvider};
+    use serde_json::json;
+
+    const SENDER: Address = Address::with_last_byte(1);
+
+    /// A provider whose pending transaction count of [`SENDER`] is `pending`.
+    fn provider(pending: u64) -> RootProvider<MockTransport> {
+        let transport = MockTransport::new(move |method, params| {
+            assert_eq!(method, "eth_getTransactionCount");
+            assert_eq!(params, json!([SENDER, "pending"]));
+            Ok(json!(format!("{pending:#x}")))
+        });
+        transport.provider()
+    }
+
+    #[tokio::test]
+    async fn local_leases_expire() {
+        let coordinator = LocalNonceCoordinator::new();
+        let ttl = Duration::from_millis(20);
+        let lease = coordinator.try_acquire(SENDER, ttl).await.unwrap().unwrap();
+        assert_eq!(lease.next_nonce, None);
+        assert!(!lease.recovered);
+        assert!(coordinator.try_acquire(SENDER, ttl).await.unwrap().is_none());
+        // Other addresses are independent.
+        assert!(coordinator.try_acquire(Address::ZERO, ttl).await.unwrap().is_some());
+
+        tokio::time::sleep(ttl * 2).await;
+        let recovered = coordinator.try_acquire(SENDER, ttl).await.unwrap().unwrap();
+        assert!(recovered.recovered);
+        assert!(recovered.token > lease.token);
+        // The stale holder cannot record its nonce anymore.
+        assert!(matches!(
+            coordinator.release(&lease, Some(1)).await,
+            Err(NonceCoordinatorError::LeaseLost { .. })
+        ));
+        coordinator.release(&recovered, Some(5)).await.unwrap();
+
+        let lease = coordinator.try_acquire(SENDER, ttl).await.unwrap().unwrap();
+        assert_eq!(lease.next_nonce, Some(5));
+        assert!(!lease.recovered);
+    }
+
+    #[tokio::test]
+    async fn replicas_share_nonces() {
+        let coordinator: Arc<dyn NonceCoordinator> = Arc::new(LocalNonceCoordinator::new());
+        let provider = provider(3);
+        let replicas = (0..4)
+            .map(|_| CoordinatedNonceFiller::with_shared(coordinator.clone()))
+            .collect::<Vec<_>>();
+        let mut nonces = futures::future::try_join_all(
+            replicas.iter().cycle().take(12).map(|filler| filler.get_next_nonce(&provider, SENDER)),
+        )
+        .await
+        .unwrap();
+        nonces.sort_unstable();
+        assert_eq!(nonces, (3..15).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn recovers_stale_leases() {
+        let coordinator = Arc::new(LocalNonceCoordinator::new());
+        let filler = CoordinatedNonceFiller::with_shared(coordinator.clone())
+            .with_lease_ttl(Duration::from_millis(20))
+            .with_retry_interval(Duration::from_millis(5));
+
+        assert_eq!(filler.get_next_nonce(&provider(3), SENDER).await.unwrap(), 3);
+        // A replica crashes holding the lease, after sending transactions up to nonce 7.
+        let stale = coordinator.try_acquire(SENDER, Duration::from_millis(20)).await.unwrap();
+        assert_eq!(stale.unwrap().next_nonce, Some(4));
+        assert_eq!(filler.get_next_nonce(&provider(8), SENDER).await.unwrap(), 8);
+        assert_eq!(filler.get_next_nonce(&provider(8), SENDER).await.unwrap(), 9);
+    }
+
+    #[tokio::test]
+    async fn times_out_on_held_leases() {
+        let coordinator = Arc::new(LocalNonceCoordinator::new());
+        let filler = CoordinatedNonceFiller::with_shared(coordinator.clone())
+            .with_retry_interval(Duration::from_millis(5))
+            .with_acquire_timeout(Duration::from_millis(20));
+        coordinator.try_acquire(SENDER, Duration::from_secs(60)).await.unwrap().unwrap();
+        let err = filler.get_next_nonce(&provider(0), SENDER).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with(&format!("could not acquire the nonce lease of {SENDER} within")));
+    }
+}
@@ -0,0 +1,134 @@
+use super::{NonceCoordinator, NonceCoordinatorError, NonceLease};
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use std::{fmt, sync::Arc, time::Duration};
+use tokio_postgres::Client;
+
+/// A [`NonceCoordinator`] storing the nonces and leases in a Postgres table.
+///
+/// Each address is a row of the table, keyed by a namespace and the address. Leases are acquired
+/// and released with single conditional statements, so they are handed out atomically across
+/// replicas. Lease expiry relies on the clock of the database server only.
+#[derive(Clone)]
+pub struct PostgresNonceCoordinator {
+    client: Arc<Client>,
+    table: String,
+    namespace: String,
+}
+
+impl fmt::Debug for PostgresNonceCoordinator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostgresNonceCoordinator")
+            .field("table", &self.table)
+            .field("namespace", &self.namespace)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PostgresNonceCoordinator {
+    /// The default name of the table.
+    pub const DEFAULT_TABLE: &'static str = "alloy_nonces";
+
+    /// Creates a new coordinator storing its rows in the [default
+    /// table](Self::DEFAULT_TABLE), under `namespace`.
+    ///
+    /// Replicas sending from the same addresses on the same chain must use the same namespace,
+    /// e.g. one including the chain ID.
+    pub fn new(client: Arc<Client>, namespace: impl Into<String>) -> Self {
+        Self { client, table: Self::DEFAULT_TABLE.to_string(), namespace: namespace.into() }
+    }
+
+    /// Sets the table the rows are stored in.
+    ///
+    /// The name is inserted as is in the statements, and must be trusted.
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    /// Creates the table if it does not exist.
+    pub async fn create_table(&self) -> Result<(), NonceCoordinatorError> {
+        let statement = format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                namespace TEXT NOT NULL,
+                address BYTEA NOT NULL,
+                next_nonce BIGINT,
+                token BIGINT NOT NULL,
+                held BOOLEAN NOT NULL,
+                recovered BOOLEAN NOT NULL,
+                expires_at TIMESTAMPTZ,
+                PRIMARY KEY (namespace, address)
+            )",
+            self.table
+        );
+        self.client.batch_execute(&statement).await.map_err(NonceCoordinatorError::backend)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NonceCoordinator for PostgresNonceCoordinator {
+    async fn try_acquire(
+        &self,
+        address: Address,
+        ttl: Duration,
+    ) -> Result<Option<NonceLease>, NonceCoordinatorError> {
+        // The update only applies to free or expired leases, the latter recorded as recovered.
+        let statement = format!(
+            "INSERT INTO {table} AS t
+                (namespace, address, next_nonce, token, held, recovered, expires_at)
+            VALUES ($1, $2, NULL, 1, TRUE, FALSE, now() + $3::float8 * interval '1 millisecond')
+            ON CONFLICT (namespace, address) DO UPDATE
+            SET token = t.token + 1, held = TRUE, recovered = t.held,
+                expires_at = EXCLUDED.expires_at
+            WHERE NOT t.held OR t.expires_at < now()
+            RETURNING token, next_nonce, recovered",
+            table = self.table
+        );
+        let row = self
+            .client
+            .query_opt(
+                &statement,
+                &[&self.namespace, &address.as_slice(), &(ttl.as_secs_f64() * 1000.0)],
+            )
+            .await
+            .map_err(NonceCoordinatorError::backend)?;
+        Ok(row.map(|row| NonceLease {
+            address,
+            token: row.get::<_, i64>(0) as u64,
+            next_nonce: row.get::<_, Option<i64>>(1).map(|nonce| nonce as u64),
+            recovered: row.get(2),
+        }))
+    }
+
+    async fn release(
+        &self,
+        lease: &NonceLease,
+        next_nonce: Option<u64>,
+    ) -> Result<(), NonceCoordinatorError> {
+        let statement = format!(
+            "UPDATE {} SET held = FALSE, expires_at = NULL,
+                next_nonce = COALESCE($4, next_nonce)
+            WHERE namespace = $1 AND address = $2 AND token = $3 AND held",
+            self.table
+        );
+        let updated = self
+            .client
+            .execute(
+                &statement,
+                &[
+                    &self.namespace,
+                    &lease.address.as_slice(),
+                    &(lease.token as i64),
+                    &next_nonce.map(|nonce| nonce as i64),
+                ],
+            )
+            .await
+            .map_err(NonceCoordinatorError::backend)?;
+        if updated == 1 {
+            Ok(())
+        } else {
+            Err(NonceCoordinatorError::LeaseLost { address: lease.address })
+        }
+    }
+}
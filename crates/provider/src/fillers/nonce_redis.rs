@@ -0,0 +1,120 @@
+use super::{NonceCoordinator, NonceCoordinatorError, NonceLease};
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use redis::{aio::ConnectionLike, Script};
+use std::{fmt, time::Duration};
+
+/// Acquires the lease of an address.
+///
+/// `KEYS[1]` is the state of the address and `KEYS[2]` its lease, which expires after `ARGV[1]`
+/// milliseconds. The state is left `held` by a lease that expired before it was released.
+const ACQUIRE: &str = r"
+if redis.call('EXISTS', KEYS[2]) == 1 then
+    return false
+end
+local token = redis.call('HINCRBY', KEYS[1], 'token', 1)
+local held = redis.call('HGET', KEYS[1], 'held') or '0'
+redis.call('HSET', KEYS[1], 'held', '1')
+redis.call('SET', KEYS[2], token, 'PX', ARGV[1])
+return {token, redis.call('HGET', KEYS[1], 'next') or '', held}
+";
+
+/// Releases the lease of an address if its token `ARGV[1]` is current, recording the next nonce
+/// `ARGV[2]` unless empty.
+const RELEASE: &str = r"
+if redis.call('HGET', KEYS[1], 'token') ~= ARGV[1] or redis.call('HGET', KEYS[1], 'held') ~= '1' then
+    return 0
+end
+if ARGV[2] ~= '' then
+    redis.call('HSET', KEYS[1], 'next', ARGV[2])
+end
+redis.call('HSET', KEYS[1], 'held', '0')
+redis.call('DEL', KEYS[2])
+return 1
+";
+
+/// A [`NonceCoordinator`] storing the nonces and leases in Redis.
+///
+/// The state of each address is stored in a hash at `<prefix>:<address>`, and its lease in a key
+/// at `<prefix>:<address>:lease` expiring with the lease. Both are updated with Lua scripts, so
+/// leases are handed out atomically across replicas. Lease expiry relies on the clock of the
+/// Redis server only.
+#[derive(Clone)]
+pub struct RedisNonceCoordinator<C> {
+    connection: C,
+    prefix: String,
+}
+
+impl<C> fmt::Debug for RedisNonceCoordinator<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisNonceCoordinator")
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> RedisNonceCoordinator<C> {
+    /// Creates a new coordinator storing its keys under `prefix`.
+    ///
+    /// Replicas sending from the same addresses on the same chain must use the same prefix, e.g.
+    /// one including the chain ID.
+    pub fn new(connection: C, prefix: impl Into<String>) -> Self {
+        Self { connection, prefix: prefix.into() }
+    }
+
+    fn keys(&self, address: Address) -> (String, String) {
+        let state = format!("{}:{address}", self.prefix);
+        let lease = format!("{state}:lease");
+        (state, lease)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C> NonceCoordinator for RedisNonceCoordinator<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    async fn try_acquire(
+        &self,
+        address: Address,
+        ttl: Duration,
+    ) -> Result<Option<NonceLease>, NonceCoordinatorError> {
+        let (state, lease) = self.keys(address);
+        let reply: Option<(u64, String, String)> = Script::new(ACQUIRE)
+            .key(state)
+            .key(lease)
+            .arg(ttl.as_millis().max(1) as u64)
+            .invoke_async(&mut self.connection.clone())
+            .await
+            .map_err(NonceCoordinatorError::backend)?;
+        let Some((token, next_nonce, held)) = reply else { return Ok(None) };
+        let next_nonce = if next_nonce.is_empty() {
+            None
+        } else {
+            Some(next_nonce.parse().map_err(NonceCoordinatorError::backend)?)
+        };
+        Ok(Some(NonceLease { address, token, next_nonce, recovered: held == "1" }))
+    }
+
+    async fn release(
+        &self,
+        lease: &NonceLease,
+        next_nonce: Option<u64>,
+    ) -> Result<(), NonceCoordinatorError> {
+        let (state, lease_key) = self.keys(lease.address);
+        let released: bool = Script::new(RELEASE)
+            .key(state)
+            .key(lease_key)
+            .arg(lease.token)
+            .arg(next_nonce.map(|nonce| nonce.to_string()).unwrap_or_default())
+            .invoke_async(&mut self.connection.clone())
+            .await
+            .map_err(NonceCoordinatorError::backend)?;
+        if released {
+            Ok(())
+        } else {
+            Err(NonceCoordinatorError::LeaseLost { address: lease.address })
+        }
+    }
+}
@@ -0,0 +1,648 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    fillers::{FillerControlFlow, TxFiller},
+    provider::SendableTx,
+    Provider,
+};
+use alloy_consensus::{SignableTransaction, TxEnvelope, TypedTransaction};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{address, b256, keccak256, Address, Signature, B256, U256};
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+
+/// The address of the `GasPriceOracle` predeploy on OP stack chains.
+pub const GAS_PRICE_ORACLE_ADDRESS: Address = address!("420000000000000000000000000000000000000F");
+
+/// The largest valid signature scalar, i.e. the secp256k1 curve order minus one.
+const MAX_SIGNATURE_SCALAR: B256 =
+    b256!("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+
+/// The minimum estimated size of a transaction in the Fjord L1 fee formula, scaled by 1e6.
+const FJORD_MIN_TX_SIZE_SCALED: i64 = 100 * 1_000_000;
+
+/// The intercept of the Fjord linear regression of the compressed transaction size.
+const FJORD_INTERCEPT: i64 = -42_585_600;
+
+/// The FastLZ size coefficient of the Fjord linear regression of the compressed transaction size.
+const FJORD_FASTLZ_COEF: i64 = 836_500;
+
+sol! {
+    function isEcotone() external view returns (bool);
+    function isFjord() external view returns (bool);
+    function l1BaseFee() external view returns (uint256);
+    function overhead() external view returns (uint256);
+    function scalar() external view returns (uint256);
+    function decimals() external view returns (uint256);
+    function blobBaseFee() external view returns (uint256);
+    function baseFeeScalar() external view returns (uint32);
+    function blobBaseFeeScalar() external view returns (uint32);
+}
+
+/// The parameters of the L1 data fee of OP stack chains, read from the `GasPriceOracle`
+/// predeploy.
+///
+/// The formula used depends on the active hardfork of the chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpL1FeeParams {
+    /// The Bedrock formula, charging the L1 calldata gas of the transaction plus a fixed overhead
+    /// at the L1 base fee.
+    Bedrock {
+        /// The L1 base fee.
+        l1_base_fee: U256,
+        /// The fixed L1 gas overhead per transaction.
+        overhead: U256,
+        /// The fee scalar, with `decimals` decimals.
+        scalar: U256,
+        /// The number of decimals of the fee scalar.
+        decimals: U256,
+    },
+    /// The Ecotone formula, charging the L1 calldata gas of the transaction at a weighted sum of
+    /// the L1 base fee and blob base fee.
+    Ecotone {
+        /// The L1 base fee.
+        l1_base_fee: U256,
+        /// The L1 base fee scalar, with 6 decimals.
+        base_fee_scalar: u32,
+        /// The L1 blob base fee.
+        blob_base_fee: U256,
+        /// The L1 blob base fee scalar, with 6 decimals.
+        blob_base_fee_scalar: u32,
+    },
+    /// The Fjord formula, same as Ecotone but charging an estimate of the compressed size of the
+    /// transaction instead of its calldata gas.
+    Fjord {
+        /// The L1 base fee.
+        l1_base_fee: U256,
+        /// The L1 base fee scalar, with 6 decimals.
+        base_fee_scalar: u32,
+        /// The L1 blob base fee.
+        blob_base_fee: U256,
+        /// The L1 blob base fee scalar, with 6 decimals.
+        blob_base_fee_scalar: u32,
+    },
+}
+
+impl OpL1FeeParams {
+    /// Returns the L1 data fee of the given EIP-2718 encoded signed transaction.
+    pub fn l1_fee(&self, encoded: &[u8]) -> U256 {
+        match *self {
+            Self::Bedrock { l1_base_fee, overhead, scalar, decimals } => {
+                let divisor = U256::from(10).pow(decimals);
+                (calldata_gas(encoded) + overhead) * l1_base_fee * scalar / divisor
+            }
+            Self::Ecotone { l1_base_fee, base_fee_scalar, blob_base_fee, blob_base_fee_scalar } => {
+                let fee_scaled =
+                    fee_scaled(l1_base_fee, base_fee_scalar, blob_base_fee, blob_base_fee_scalar);
+                calldata_gas(encoded) * fee_scaled / U256::from(16_000_000)
+            }
+            Self::Fjord { l1_base_fee, base_fee_scalar, blob_base_fee, blob_base_fee_scalar } => {
+                let fee_scaled =
+                    fee_scaled(l1_base_fee, base_fee_scalar, blob_base_fee, blob_base_fee_scalar);
+                let fastlz_size = flz_compress_len(encoded) as i64;
+                let estimated_size = (FJORD_INTERCEPT + FJORD_FASTLZ_COEF * fastlz_size)
+                    .max(FJORD_MIN_TX_SIZE_SCALED);
+                U256::from(estimated_size) * fee_scaled / U256::from(1_000_000_000_000u64)
+            }
+        }
+    }
+}
+
+/// Returns the L1 calldata gas of `data`, i.e. 4 gas per zero byte and 16 per non-zero byte.
+fn calldata_gas(data: &[u8]) -> U256 {
+    let zeros = data.iter().filter(|byte| **byte == 0).count() as u64;
+    U256::from(zeros * 4 + (data.len() as u64 - zeros) * 16)
+}
+
+/// Returns the weighted sum of the L1 base fee and blob base fee of the Ecotone and Fjord
+/// formulas, scaled by 16e6.
+fn fee_scaled(
+    l1_base_fee: U256,
+    base_fee_scalar: u32,
+    blob_base_fee: U256,
+    blob_base_fee_scalar: u32,
+) -> U256 {
+    U256::from(base_fee_scalar) * l1_base_fee * U256::from(16)
+        + U256::from(blob_base_fee_scalar) * blob_base_fee
+}
+
+/// Returns the length of `data` compressed with FastLZ, as computed by the Fjord L1 fee formula.
+///
+/// This is a port of `FlzCompressLen` from op-geth, which only counts the compressed bytes.
+pub fn flz_compress_len(data: &[u8]) -> u32 {
+    let mut n = 0u32;
+    let mut table = vec![0u32; 8192];
+
+    let u24 = |i: u32| {
+        let i = i as usize;
+        data[i] as u32 | (data[i + 1] as u32) << 8 | (data[i + 2] as u32) << 16
+    };
+    let cmp = |p: u32, q: u32, end: u32| {
+        let mut len = 0;
+        while len < end - q {
+            let (a, b) = ((p + len) as usize, (q + len) as usize);
+            len += 1;
+            if data[a] != data[b] {
+                break;
+            }
+        }
+        len
+    };
+    let literals = |n: &mut u32, r: u32| {
+        *n += 0x21 * (r / 0x20);
+        let r = r % 0x20;
+        if r != 0 {
+            *n += r + 1;
+        }
+    };
+    let matched = |n: &mut u32, l: u32| {
+        let l = l - 1;
+        *n += 3 * (l / 262);
+        *n += if l % 262 >= 6 { 3 } else { 2 };
+    };
+    let hash = |v: u32| (2_654_435_769u32.wrapping_mul(v) >> 19) & 0x1fff;
+
+    let ip_limit = (data.len() as u32).saturating_sub(13);
+    let mut anchor = 0;
+    let mut ip = 2;
+    while ip < ip_limit {
+        let mut r;
+        loop {
+            let s = u24(ip);
+            let h = hash(s) as usize;
+            r = table[h];
+            table[h] = ip;
+            let distance = ip.wrapping_sub(r);
+            if ip >= ip_limit {
+                break;
+            }
+            ip += 1;
+            if distance <= 0x1fff && s == u24(r) {
+                break;
+            }
+        }
+        if ip >= ip_limit {
+            break;
+        }
+        ip -= 1;
+        if ip > anchor {
+            literals(&mut n, ip - anchor);
+        }
+        let len = cmp(r + 3, ip + 3, ip_limit + 9);
+        matched(&mut n, len);
+        ip += len;
+        for _ in 0..2 {
+            table[hash(u24(ip)) as usize] = ip;
+            ip += 1;
+        }
+        anchor = ip;
+    }
+    literals(&mut n, data.len() as u32 - anchor);
+    n
+}
+
+/// Computes the L1 data fee of transactions on OP stack chains.
+///
+/// On OP stack chains, transactions pay an L1 data fee for posting them to L1 on top of the L2
+/// execution fee. This fee is not part of the gas used by the transaction, so it is not accounted
+/// for by [`Provider::estimate_gas`]. The estimator reads the fee parameters of the current
+/// hardfork from the `GasPriceOracle` predeploy, and computes the fee locally with the Bedrock,
+/// Ecotone or Fjord formula.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider, encoded: Vec<u8>) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::fillers::OpGasEstimator;
+///
+/// let params = OpGasEstimator::new().l1_fee_params(&provider).await?;
+/// let l1_fee = params.l1_fee(&encoded);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpGasEstimator {
+    oracle: Address,
+}
+
+impl Default for OpGasEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpGasEstimator {
+    /// Creates a new estimator reading the `GasPriceOracle` predeploy.
+    pub const fn new() -> Self {
+        Self { oracle: GAS_PRICE_ORACLE_ADDRESS }
+    }
+
+    /// Sets the address of the gas price oracle, for chains deploying it elsewhere.
+    pub const fn with_oracle(mut self, oracle: Address) -> Self {
+        self.oracle = oracle;
+        self
+    }
+
+    /// Returns the address of the gas price oracle.
+    pub const fn oracle(&self) -> Address {
+        self.oracle
+    }
+
+    /// Reads the L1 fee parameters of the current hardfork from the gas price oracle.
+    pub async fn l1_fee_params<P, T, N>(&self, provider: &P) -> TransportResult<OpL1FeeParams>
+    where
+        P: Provider<T, N> + ?Sized,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let (is_ecotone, is_fjord, l1_base_fee) = futures::try_join!(
+            self.read_flag(provider, &isEcotoneCall {}),
+            self.read_flag(provider, &isFjordCall {}),
+            self.read(provider, &l1BaseFeeCall {}),
+        )?;
+        let (is_ecotone, is_fjord) =
+            (is_ecotone.is_some_and(|ret| ret._0), is_fjord.is_some_and(|ret| ret._0));
+        let l1_base_fee = l1_base_fee._0;
+
+        if !is_ecotone {
+            let (overhead, scalar, decimals) = futures::try_join!(
+                self.read(provider, &overheadCall {}),
+                self.read(provider, &scalarCall {}),
+                self.read(provider, &decimalsCall {}),
+            )?;
+            return Ok(OpL1FeeParams::Bedrock {
+                l1_base_fee,
+                overhead: overhead._0,
+                scalar: scalar._0,
+                decimals: decimals._0,
+            });
+        }
+
+        let (base_fee_scalar, blob_base_fee, blob_base_fee_scalar) = futures::try_join!(
+            self.read(provider, &baseFeeScalarCall {}),
+            self.read(provider, &blobBaseFeeCall {}),
+            self.read(provider, &blobBaseFeeScalarCall {}),
+        )?;
+        let (base_fee_scalar, blob_base_fee, blob_base_fee_scalar) =
+            (base_fee_scalar._0, blob_base_fee._0, blob_base_fee_scalar._0);
+        Ok(if is_fjord {
+            OpL1FeeParams::Fjord {
+                l1_base_fee,
+                base_fee_scalar,
+                blob_base_fee,
+                blob_base_fee_scalar,
+            }
+        } else {
+            OpL1FeeParams::Ecotone {
+                l1_base_fee,
+                base_fee_scalar,
+                blob_base_fee,
+                blob_base_fee_scalar,
+            }
+        })
+    }
+
+    /// Returns the L1 data fee of the given EIP-2718 encoded signed transaction.
+    pub async fn estimate_l1_fee<P, T, N>(
+        &self,
+        provider: &P,
+        encoded: &[u8],
+    ) -> TransportResult<U256>
+    where
+        P: Provider<T, N> + ?Sized,
+        T: Transport + Clone,
+        N: Network,
+    {
+        Ok(self.l1_fee_params(provider).await?.l1_fee(encoded))
+    }
+
+    async fn read<P, C, T, N>(&self, provider: &P, call: &C) -> TransportResult<C::Return>
+    where
+        P: Provider<T, N> + ?Sized,
+        C: SolCall,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let tx =
+            N::TransactionRequest::default().with_to(self.oracle).with_input(call.abi_encode());
+        let output = provider.call(&tx).await?;
+        C::abi_decode_returns(&output, true).map_err(TransportErrorKind::custom)
+    }
+
+    /// Reads a hardfork flag of the oracle, returning `None` if the call reverts, as it does on
+    /// oracles predating the hardfork.
+    async fn read_flag<P, C, T, N>(
+        &self,
+        provider: &P,
+        call: &C,
+    ) -> TransportResult<Option<C::Return>>
+    where
+        P: Provider<T, N> + ?Sized,
+        C: SolCall,
+        T: Transport + Clone,
+        N: Network,
+    {
+        match self.read(provider, call).await {
+            Ok(ret) => Ok(Some(ret)),
+            Err(RpcError::ErrorResp(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// The fees of a transaction on an OP stack chain, computed by [`OpL1FeeFiller`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpFeeEstimate {
+    /// The maximum L2 execution fee, i.e. the gas limit times the maximum fee per gas.
+    pub l2_fee: U256,
+    /// The L1 data fee.
+    pub l1_fee: U256,
+}
+
+impl OpFeeEstimate {
+    /// Returns the maximum total fee of the transaction.
+    pub fn total(&self) -> U256 {
+        self.l2_fee + self.l1_fee
+    }
+}
+
+/// A [`TxFiller`] that computes the L1 data fee of transactions on OP stack chains with an
+/// [`OpGasEstimator`].
+///
+/// The fee is computed once all the other fields of the transaction are filled, assuming a
+/// signature of maximum size, and the estimate of the last accepted transaction can be read with
+/// [`last_estimate`](Self::last_estimate). If a budget is set, transactions whose maximum total
+/// fee exceeds it are rejected before being signed or sent.
+///
+/// Until the transaction can be built, the filler reports the fields it is still missing, so it
+/// waits for the fillers of the gas, nonce and chain ID fields regardless of the join order.
+///
+/// # Example
+///
+/// ```
+/// # use alloy_network::{NetworkWallet, EthereumWallet, Ethereum};
+/// # use alloy_primitives::U256;
+/// # use alloy_rpc_types_eth::TransactionRequest;
+/// # use alloy_provider::{fillers::OpL1FeeFiller, ProviderBuilder, RootProvider, Provider};
+/// # async fn test<W: NetworkWallet<Ethereum> + Clone>(url: url::Url, wallet: W) -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new()
+///     .with_recommended_fillers()
+///     .filler(OpL1FeeFiller::new().with_budget(U256::from(10u64.pow(15))))
+///     .wallet(wallet)
+///     .on_http(url);
+///
+/// provider.send_transaction(TransactionRequest::default()).await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OpL1FeeFiller {
+    estimator: OpGasEstimator,
+    budget: Option<U256>,
+    last: Arc<Mutex<Option<(B256, OpFeeEstimate)>>>,
+}
+
+impl OpL1FeeFiller {
+    /// Creates a new filler reading the `GasPriceOracle` predeploy, without a budget.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the estimator used to compute the L1 data fee.
+    pub const fn with_estimator(mut self, estimator: OpGasEstimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// Rejects transactions whose maximum total fee exceeds `budget`.
+    pub const fn with_budget(mut self, budget: U256) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Returns the estimate of the last transaction accepted by the filler.
+    pub fn last_estimate(&self) -> Option<OpFeeEstimate> {
+        self.last.lock().unwrap().map(|(_, estimate)| estimate)
+    }
+
+    /// Returns the encoding of `tx` signed with a placeholder signature, if it can be built.
+    fn encode<N>(tx: &N::TransactionRequest) -> Option<Vec<u8>>
+    where
+        N: Network<UnsignedTx = TypedTransaction, TxEnvelope = TxEnvelope>,
+    {
+        // The largest possible signature, so that the fee is not underestimated.
+        let scalar = U256::from_be_bytes(MAX_SIGNATURE_SCALAR.0);
+        let signature = Signature::from_rs_and_parity(scalar, scalar, false).ok()?;
+        let envelope: TxEnvelope = match tx.clone().build_unsigned().ok()? {
+            TypedTransaction::Legacy(tx) => tx.into_signed(signature).into(),
+            TypedTransaction::Eip2930(tx) => tx.into_signed(signature).into(),
+            TypedTransaction::Eip1559(tx) => tx.into_signed(signature).into(),
+            TypedTransaction::Eip4844(tx) => tx.into_signed(signature).into(),
+        };
+        Some(envelope.encoded_2718())
+    }
+}
+
+impl<N> TxFiller<N> for OpL1FeeFiller
+where
+    N: Network<UnsignedTx = TypedTransaction, TxEnvelope = TxEnvelope>,
+{
+    type Fillable = (B256, OpFeeEstimate);
+
+    fn status(&self, tx: &N::TransactionRequest) -> FillerControlFlow {
+        // Wait for the other fillers until the transaction can be built.
+        let Some(encoded) = Self::encode::<N>(tx) else {
+            let missing = tx.complete_preferred().err().unwrap_or_default();
+            return FillerControlFlow::missing("OpL1FeeFiller", missing);
+        };
+        match *self.last.lock().unwrap() {
+            Some((hash, _)) if hash == keccak256(encoded) => FillerControlFlow::Finished,
+            _ => FillerControlFlow::Ready,
+        }
+    }
+
+    fn fill_sync(&self, _tx: &mut SendableTx<N>) {}
+
+    async fn prepare<P, T>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+    {
+        let encoded = Self::encode::<N>(tx)
+            .ok_or_else(|| RpcError::local_usage_str("transaction cannot be built"))?;
+        let l1_fee = self.estimator.estimate_l1_fee(provider, &encoded).await?;
+        let max_fee_per_gas = tx.max_fee_per_gas().or_else(|| tx.gas_price()).unwrap_or_default();
+        let l2_fee = U256::from(tx.gas_limit().unwrap_or_default()) * U256::from(max_fee_per_gas);
+        Ok((keccak256(encoded), OpFeeEstimate { l2_fee, l1_fee }))
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        let (_, estimate) = fillable;
+        if let Some(budget) = self.budget.filter(|budget| estimate.total() > *budget) {
+            return Err(RpcError::local_usage_str(&format!(
+                "maximum total fee of {} (L1 data fee of {}) exceeds the budget of {budget}",
+                estimate.total(),
+                estimate.l1_fee,
+            )));
+        }
+        // Only remember accepted transactions, so that resending a rejected one is checked again.
+        *self.last.lock().unwrap() = Some(fillable);
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fillers::{FillProvider, JoinFill},
+        mock::MockTransport,
+        Identity, ProviderBuilder, RootProvider,
+    };
+    use alloy_network::Ethereum;
+    use alloy_primitives::{hex, TxKind};
+    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_sol_types::SolValue;
+    use serde_json::json;
+
+    const ECOTONE: OpL1FeeParams = OpL1FeeParams::Ecotone {
+        l1_base_fee: U256::from_limbs([1_000_000_000, 0, 0, 0]),
+        base_fee_scalar: 1368,
+        blob_base_fee: U256::from_limbs([1, 0, 0, 0]),
+        blob_base_fee_scalar: 810949,
+    };
+
+    #[test]
+    fn compresses_like_fastlz() {
+        assert_eq!(flz_compress_len(&[]), 0);
+        assert_eq!(flz_compress_len(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]), 11);
+        assert_eq!(flz_compress_len(&[0; 100]), 12);
+        let distinct: Vec<u8> = (0..=255).collect();
+        assert_eq!(flz_compress_len(&distinct), 256 + 256 / 32);
+    }
+
+    #[test]
+    fn computes_l1_fees() {
+        let bedrock = OpL1FeeParams::Bedrock {
+            l1_base_fee: U256::from(1_000_000_000),
+            overhead: U256::from(188),
+            scalar: U256::from(684_000),
+            decimals: U256::from(6),
+        };
+        assert_eq!(bedrock.l1_fee(&[0, 1, 2]), U256::from(153_216_000_000u64));
+
+        assert_eq!(ECOTONE.l1_fee(&[0, 1, 2]), U256::from(49_248_001));
+
+        let OpL1FeeParams::Ecotone {
+            l1_base_fee,
+            base_fee_scalar,
+            blob_base_fee,
+            blob_base_fee_scalar,
+        } = ECOTONE
+        else {
+            unreachable!()
+        };
+        let fjord = OpL1FeeParams::Fjord {
+            l1_base_fee,
+            base_fee_scalar,
+            blob_base_fee,
+            blob_base_fee_scalar,
+        };
+        // Small transactions are charged the minimum size of 100 bytes.
+        assert_eq!(fjord.l1_fee(&[0; 100]), U256::from(2_188_800_081u64));
+    }
+
+    /// A transport answering `eth_call`s to an Ecotone `GasPriceOracle`.
+    fn provider(
+        filler: OpL1FeeFiller,
+    ) -> FillProvider<
+        JoinFill<Identity, OpL1FeeFiller>,
+        RootProvider<MockTransport>,
+        MockTransport,
+        Ethereum,
+    > {
+        let transport = MockTransport::new(|method, params| {
+            assert_eq!(method, "eth_call");
+            let tx: TransactionRequest = serde_json::from_value(params[0].clone()).unwrap();
+            assert_eq!(tx.to, Some(TxKind::Call(GAS_PRICE_ORACLE_ADDRESS)));
+
+            let selector: [u8; 4] = tx.input.input().unwrap()[..4].try_into().unwrap();
+            let output = match selector {
+                isEcotoneCall::SELECTOR => true.abi_encode(),
+                isFjordCall::SELECTOR => false.abi_encode(),
+                l1BaseFeeCall::SELECTOR => U256::from(1_000_000_000).abi_encode(),
+                baseFeeScalarCall::SELECTOR => 1368u32.abi_encode(),
+                blobBaseFeeCall::SELECTOR => U256::from(1).abi_encode(),
+                blobBaseFeeScalarCall::SELECTOR => 810949u32.abi_encode(),
+                _ => panic!("unexpected call to {}", hex::encode(selector)),
+            };
+            Ok(json!(hex::encode_prefixed(output)))
+        });
+        ProviderBuilder::new().filler(filler).on_client(transport.client())
+    }
+
+    fn tx() -> TransactionRequest {
+        TransactionRequest::default()
+            .to(Address::with_last_byte(1))
+            .nonce(0)
+            .with_chain_id(10)
+            .gas_limit(21_000)
+            .max_fee_per_gas(1_000_000)
+            .max_priority_fee_per_gas(1)
+    }
+
+    #[tokio::test]
+    async fn reads_oracle_params() {
+        let params = OpGasEstimator::new().l1_fee_params(&provider(OpL1FeeFiller::new())).await;
+        assert_eq!(params.unwrap(), ECOTONE);
+    }
+
+    #[tokio::test]
+    async fn fills_l1_fee() {
+        let filler = OpL1FeeFiller::new();
+        let provider = provider(filler.clone());
+        assert_eq!(filler.last_estimate(), None);
+
+        // Incomplete transactions are left to the other fillers.
+        let incomplete = TransactionRequest::default().to(Address::with_last_byte(1));
+        let status = TxFiller::<Ethereum>::status(&filler, &incomplete);
+        assert!(status.as_missing().unwrap()[0].1.contains(&"nonce"), "{status:?}");
+
+        let tx = tx();
+        let encoded = OpL1FeeFiller::encode::<Ethereum>(&tx).unwrap();
+        provider.fill(tx.clone()).await.unwrap();
+        let estimate = filler.last_estimate().unwrap();
+        assert_eq!(estimate.l2_fee, U256::from(21_000_000_000u64));
+        assert_eq!(estimate.l1_fee, ECOTONE.l1_fee(&encoded));
+        assert!(TxFiller::<Ethereum>::status(&filler, &tx).is_finished());
+    }
+
+    #[tokio::test]
+    async fn enforces_budget() {
+        let provider = provider(OpL1FeeFiller::new().with_budget(U256::from(21_000_000_000u64)));
+        let err = provider.fill(tx()).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds the budget"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn rechecks_resent_rejected_tx() {
+        let filler = OpL1FeeFiller::new().with_budget(U256::from(21_000_000_000u64));
+        let provider = provider(filler.clone());
+        for _ in 0..2 {
+            let err = provider.fill(tx()).await.unwrap_err();
+            assert!(err.to_string().contains("exceeds the budget"), "{err}");
+        }
+        assert_eq!(filler.last_estimate(), None);
+        assert!(TxFiller::<Ethereum>::status(&filler, &tx()).is_ready());
+    }
+}
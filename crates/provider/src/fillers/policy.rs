@@ -0,0 +1,421 @@
+use crate::{
+    fillers::{FillerControlFlow, TxFiller},
+    provider::SendableTx,
+    Provider,
+};
+use alloy_json_rpc::RpcError;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{TxKind, U256};
+use alloy_transport::{Transport, TransportError, TransportErrorKind, TransportResult};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The window over which [`Policy::with_max_daily_value`] limits spending.
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The rules enforced by a [`PolicyFiller`].
+///
+/// The default policy allows every transaction.
+#[derive(Clone, Debug, Default)]
+pub struct Policy {
+    max_value: Option<U256>,
+    max_daily_value: Option<U256>,
+    allowed: Option<HashSet<TxKind>>,
+    denied: HashSet<TxKind>,
+    max_gas_price: Option<u128>,
+    simulate: bool,
+}
+
+impl Policy {
+    /// Limits the value sent by each transaction.
+    pub const fn with_max_value(mut self, max_value: U256) -> Self {
+        self.max_value = Some(max_value);
+        self
+    }
+
+    /// Limits the total value sent by transactions over the last 24 hours.
+    ///
+    /// The value of a transaction is counted once it is approved, whether it is then sent
+    /// successfully or not.
+    pub const fn with_max_daily_value(mut self, max_daily_value: U256) -> Self {
+        self.max_daily_value = Some(max_daily_value);
+        self
+    }
+
+    /// Only allows transactions to the given destinations. Contract creations are only allowed if
+    /// [`TxKind::Create`] is included.
+    ///
+    /// Can be called multiple times to extend the allowlist.
+    pub fn with_allowed(
+        mut self,
+        destinations: impl IntoIterator<Item = impl Into<TxKind>>,
+    ) -> Self {
+        self.allowed
+            .get_or_insert_with(Default::default)
+            .extend(destinations.into_iter().map(Into::into));
+        self
+    }
+
+    /// Denies transactions to the given destinations, even if they are allowed.
+    pub fn with_denied(
+        mut self,
+        destinations: impl IntoIterator<Item = impl Into<TxKind>>,
+    ) -> Self {
+        self.denied.extend(destinations.into_iter().map(Into::into));
+        self
+    }
+
+    /// Limits the gas price of legacy transactions, and the max fee per gas of EIP-1559
+    /// transactions.
+    pub const fn with_max_gas_price(mut self, max_gas_price: u128) -> Self {
+        self.max_gas_price = Some(max_gas_price);
+        self
+    }
+
+    /// Requires transactions to succeed when simulated with `eth_call` before they are sent.
+    pub const fn with_simulation(mut self) -> Self {
+        self.simulate = true;
+        self
+    }
+
+    /// Checks the rules that only depend on the transaction.
+    fn check<N: Network>(&self, tx: &N::TransactionRequest) -> Result<(), PolicyViolation> {
+        let value = tx.value().unwrap_or_default();
+        if let Some(max) = self.max_value {
+            if value > max {
+                return Err(PolicyViolation::ValueTooHigh { value, max });
+            }
+        }
+
+        let kind = tx.kind().unwrap_or(TxKind::Create);
+        let allowed = self.allowed.as_ref().map_or(true, |allowed| allowed.contains(&kind));
+        if !allowed || self.denied.contains(&kind) {
+            return Err(PolicyViolation::DestinationNotAllowed(kind));
+        }
+
+        if let Some(max) = self.max_gas_price {
+            let gas_price = tx.max_fee_per_gas().or_else(|| tx.gas_price()).unwrap_or_default();
+            if gas_price > max {
+                return Err(PolicyViolation::GasPriceTooHigh { gas_price, max });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A violation of a [`Policy`], returned by a [`PolicyFiller`] as a
+/// [`TransportErrorKind::Custom`] error.
+#[derive(Debug)]
+pub enum PolicyViolation {
+    /// The transaction sends more value than allowed per transaction.
+    ValueTooHigh {
+        /// The value sent by the transaction.
+        value: U256,
+        /// The maximum value allowed per transaction.
+        max: U256,
+    },
+    /// The transaction would exceed the value allowed over the last 24 hours.
+    DailyLimitExceeded {
+        /// The value sent by the transaction.
+        value: U256,
+        /// The value already sent over the last 24 hours.
+        spent: U256,
+        /// The maximum value allowed over 24 hours.
+        limit: U256,
+    },
+    /// The destination of the transaction is not allowed.
+    DestinationNotAllowed(TxKind),
+    /// The gas price of the transaction is higher than allowed.
+    GasPriceTooHigh {
+        /// The gas price, or max fee per gas, of the transaction.
+        gas_price: u128,
+        /// The maximum gas price allowed.
+        max: u128,
+    },
+    /// The transaction failed when simulated.
+    SimulationFailed(TransportError),
+}
+
+impl PolicyViolation {
+    /// Returns the policy violation that caused the given error, if any.
+    pub fn from_error(err: &TransportError) -> Option<&Self> {
+        match err {
+            RpcError::Transport(TransportErrorKind::Custom(err)) => err.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("policy violation: ")?;
+        match self {
+            Self::ValueTooHigh { value, max } => {
+                write!(f, "value {value} exceeds the maximum of {max} per transaction")
+            }
+            Self::DailyLimitExceeded { value, spent, limit } => {
+                write!(f, "value {value} exceeds the daily limit of {limit}, {spent} already spent")
+            }
+            Self::DestinationNotAllowed(TxKind::Call(to)) => {
+                write!(f, "destination {to} is not allowed")
+            }
+            Self::DestinationNotAllowed(TxKind::Create) => {
+                f.write_str("contract creation is not allowed")
+            }
+            Self::GasPriceTooHigh { gas_price, max } => {
+                write!(f, "gas price {gas_price} exceeds the maximum of {max}")
+            }
+            Self::SimulationFailed(err) => write!(f, "simulation failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PolicyViolation {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::SimulationFailed(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A [`TxFiller`] enforcing a [`Policy`] before the inner filler, usually a
+/// [`WalletFiller`], signs a transaction.
+///
+/// The policy is checked once the transaction is ready to be signed, after the other fillers
+/// filled it. Transactions violating the policy are rejected with a [`PolicyViolation`], and are
+/// never signed.
+///
+/// The spending over the last 24 hours is tracked by the filler, and shared by its clones.
+///
+/// # Example
+///
+/// ```
+/// # use alloy_network::{NetworkWallet, EthereumWallet, Ethereum};
+/// # use alloy_primitives::{address, U256};
+/// # use alloy_provider::{fillers::Policy, ProviderBuilder};
+/// # async fn test<W: NetworkWallet<Ethereum> + Clone>(url: url::Url, wallet: W) -> Result<(), Box<dyn std::error::Error>> {
+/// let policy = Policy::default()
+///     .with_max_daily_value(U256::from(10).pow(U256::from(18)))
+///     .with_allowed([address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")])
+///     .with_simulation();
+/// let provider = ProviderBuilder::new()
+///     .with_recommended_fillers()
+///     .wallet_with_policy(wallet, policy)
+///     .on_http(url);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`WalletFiller`]: crate::fillers::WalletFiller
+#[derive(Clone, Debug)]
+pub struct PolicyFiller<F> {
+    inner: F,
+    policy: Arc<Policy>,
+    /// The time and value of the transactions approved over the last 24 hours.
+    spent: Arc<Mutex<VecDeque<(Instant, U256)>>>,
+}
+
+impl<F> PolicyFiller<F> {
+    /// Creates a new filler enforcing the given policy before the inner filler.
+    pub fn new(inner: F, policy: Policy) -> Self {
+        Self { inner, policy: Arc::new(policy), spent: Default::default() }
+    }
+
+    /// Returns the enforced policy.
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// Returns the inner filler.
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner filler.
+    pub fn inner_mut(&mut self) -> &mut F {
+        &mut self.inner
+    }
+
+    /// Returns the value sent by the transactions approved over the last 24 hours.
+    pub fn spent(&self) -> U256 {
+        let mut spent = self.spent.lock().unwrap();
+        Self::expire(&mut spent);
+        spent.iter().map(|(_, value)| value).sum()
+    }
+
+    /// Checks the daily limit, recording the value of the transaction if it is approved.
+    fn spend(&self, value: U256) -> Result<(), PolicyViolation> {
+        let Some(limit) = self.policy.max_daily_value else { return Ok(()) };
+
+        let mut spent = self.spent.lock().unwrap();
+        Self::expire(&mut spent);
+        let total: U256 = spent.iter().map(|(_, value)| value).sum();
+        if total.saturating_add(value) > limit {
+            return Err(PolicyViolation::DailyLimitExceeded { value, spent: total, limit });
+        }
+        spent.push_back((Instant::now(), value));
+        Ok(())
+    }
+
+    fn expire(spent: &mut VecDeque<(Instant, U256)>) {
+        while spent.front().is_some_and(|(at, _)| at.elapsed() >= DAY) {
+            spent.pop_front();
+        }
+    }
+}
+
+impl<F, N> TxFiller<N> for PolicyFiller<F>
+where
+    F: TxFiller<N>,
+    N: Network,
+{
+    type Fillable = F::Fillable;
+
+    fn status(&self, tx: &N::TransactionRequest) -> FillerControlFlow {
+        self.inner.status(tx)
+    }
+
+    fn fill_sync(&self, tx: &mut SendableTx<N>) {
+        self.inner.fill_sync(tx);
+    }
+
+    async fn prepare<P, T>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+    {
+        self.policy.check::<N>(tx).map_err(TransportErrorKind::custom)?;
+        if self.policy.simulate {
+            if let Err(err) = provider.call(tx).await {
+                return Err(TransportErrorKind::custom(PolicyViolation::SimulationFailed(err)));
+            }
+        }
+        self.inner.prepare(provider, tx).await
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        if let Some(builder) = tx.as_builder() {
+            self.spend(builder.value().unwrap_or_default()).map_err(TransportErrorKind::custom)?;
+        }
+        self.inner.fill(fillable, tx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fillers::WalletFiller,
+        mock::{rpc_error, MockTransport},
+        ProviderBuilder,
+    };
+    use alloy_network::EthereumWallet;
+    use alloy_primitives::{address, Address, B256};
+    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_signer_local::PrivateKeySigner;
+    use serde_json::json;
+
+    const REVERTING: Address = address!("000000000000000000000000000000000000dead");
+
+    fn provider(policy: Policy) -> impl Provider<MockTransport> {
+        let transport = MockTransport::new(|method, params| match method {
+            "eth_call" => {
+                let to: Address = serde_json::from_value(params[0]["to"].clone()).unwrap();
+                if to == REVERTING {
+                    Err(rpc_error(3, "execution reverted"))
+                } else {
+                    Ok(json!("0x"))
+                }
+            }
+            "eth_sendRawTransaction" => Ok(json!(B256::ZERO)),
+            method => unreachable!("{method}"),
+        });
+        let wallet = EthereumWallet::from(PrivateKeySigner::random());
+        ProviderBuilder::new()
+            .filler(PolicyFiller::new(WalletFiller::new(wallet), policy))
+            .on_client(transport.client())
+    }
+
+    fn tx(to: Address, value: u64, gas_price: u128) -> TransactionRequest {
+        TransactionRequest::default()
+            .with_to(to)
+            .with_value(U256::from(value))
+            .with_nonce(0)
+            .with_chain_id(1)
+            .with_gas_limit(21_000)
+            .with_max_fee_per_gas(gas_price)
+            .with_max_priority_fee_per_gas(1)
+    }
+
+    async fn violation(
+        provider: &impl Provider<MockTransport>,
+        tx: TransactionRequest,
+    ) -> Option<String> {
+        let err = provider.send_transaction(tx).await.err()?;
+        Some(PolicyViolation::from_error(&err).unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn enforces_policy() {
+        let allowed = Address::with_last_byte(1);
+        let policy = Policy::default()
+            .with_max_value(U256::from(100))
+            .with_max_daily_value(U256::from(150))
+            .with_allowed([allowed, REVERTING])
+            .with_max_gas_price(1_000)
+            .with_simulation();
+        let provider = provider(policy);
+
+        assert_eq!(violation(&provider, tx(allowed, 100, 1_000)).await, None);
+        assert!(violation(&provider, tx(allowed, 101, 1_000))
+            .await
+            .unwrap()
+            .contains("maximum of 100"));
+        assert!(violation(&provider, tx(allowed, 51, 1_000))
+            .await
+            .unwrap()
+            .contains("daily limit"));
+        assert_eq!(violation(&provider, tx(allowed, 50, 1_000)).await, None);
+        assert!(violation(&provider, tx(Address::ZERO, 0, 1_000))
+            .await
+            .unwrap()
+            .contains("not allowed"));
+        assert!(violation(&provider, tx(allowed, 0, 1_001)).await.unwrap().contains("gas price"));
+        assert!(violation(&provider, tx(REVERTING, 0, 1_000))
+            .await
+            .unwrap()
+            .contains("simulation"));
+    }
+
+    #[test]
+    fn destinations() {
+        let to = Address::with_last_byte(1);
+        let check = |policy: &Policy, tx: TransactionRequest| {
+            policy.check::<alloy_network::Ethereum>(&tx).is_ok()
+        };
+        let create = TransactionRequest::default().into_create();
+
+        assert!(check(&Policy::default(), create.clone()));
+        assert!(!check(&Policy::default().with_allowed([to]), create.clone()));
+        assert!(check(&Policy::default().with_allowed([TxKind::Create]), create));
+        assert!(!check(
+            &Policy::default().with_denied([to]),
+            TransactionRequest::default().with_to(to)
+        ));
+        let policy = Policy::default().with_allowed([to]).with_denied([to]);
+        assert!(!check(&policy, TransactionRequest::default().with_to(to)));
+    }
+}
@@ -0,0 +1,285 @@
+//! Tracking of the finalized and safe blocks.
+
+use alloy_eips::{BlockHashOrNumber, BlockNumHash};
+use alloy_rpc_client::{PollerBuilder, WeakClient};
+use alloy_rpc_types_eth::{Block, BlockNumberOrTag};
+use alloy_transport::{utils::Spawnable, Transport, TransportErrorKind, TransportResult};
+use futures::{stream, Stream, StreamExt};
+use std::fmt;
+use tokio::{
+    select,
+    sync::{broadcast, watch},
+};
+
+/// The capacity of the event channel of a [`FinalityTracker`].
+const EVENT_CHANNEL_SIZE: usize = 16;
+
+/// An advance of the finality of the chain, broadcast by a [`FinalityTracker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FinalityEvent {
+    /// A new block was finalized.
+    Finalized(BlockNumHash),
+    /// A new block became safe.
+    Safe(BlockNumHash),
+}
+
+/// A handle to a task tracking the `finalized` and `safe` blocks, returned by
+/// [`Provider::finality_tracker`](crate::Provider::finality_tracker).
+///
+/// The task polls the `finalized` and `safe` block tags, and broadcasts an event each time one
+/// advances. The handle can be cloned to share one tracker between components, each
+/// [subscribing](Self::subscribe) to the events, or gating actions on
+/// [`is_finalized`](Self::is_finalized). The task stops once all the handles are dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider, deposit_block: u64) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::FinalityEvent;
+///
+/// let tracker = provider.finality_tracker();
+/// let mut events = tracker.subscribe();
+/// while !tracker.is_finalized(deposit_block).await? {
+///     events.recv().await?;
+/// }
+/// println!("deposit finalized");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FinalityTracker<T> {
+    client: WeakClient<T>,
+    events: broadcast::Sender<FinalityEvent>,
+    finalized: watch::Receiver<Option<BlockNumHash>>,
+    safe: watch::Receiver<Option<BlockNumHash>>,
+}
+
+impl<T> fmt::Debug for FinalityTracker<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FinalityTracker")
+            .field("finalized", &self.finalized())
+            .field("safe", &self.safe())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> FinalityTracker<T> {
+    /// Subscribes to the events of the tracker, from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<FinalityEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns the last finalized block, or `None` if it was not polled yet.
+    pub fn finalized(&self) -> Option<BlockNumHash> {
+        *self.finalized.borrow()
+    }
+
+    /// Returns the last safe block, or `None` if it was not polled yet.
+    pub fn safe(&self) -> Option<BlockNumHash> {
+        *self.safe.borrow()
+    }
+}
+
+impl<T: Transport + Clone> FinalityTracker<T> {
+    /// Returns whether the given block is finalized.
+    ///
+    /// A block number is compared to the last finalized block without any request. A block hash
+    /// is finalized if its block is canonical and not after the last finalized block, which
+    /// takes up to two requests.
+    pub async fn is_finalized(&self, block: impl Into<BlockHashOrNumber>) -> TransportResult<bool> {
+        self.is_at_or_before(block.into(), self.finalized()).await
+    }
+
+    /// Returns whether the given block is safe, see [`is_finalized`](Self::is_finalized).
+    pub async fn is_safe(&self, block: impl Into<BlockHashOrNumber>) -> TransportResult<bool> {
+        self.is_at_or_before(block.into(), self.safe()).await
+    }
+
+    async fn is_at_or_before(
+        &self,
+        block: BlockHashOrNumber,
+        tip: Option<BlockNumHash>,
+    ) -> TransportResult<bool> {
+        let Some(tip) = tip else { return Ok(false) };
+        let hash = match block {
+            BlockHashOrNumber::Number(number) => return Ok(number <= tip.number),
+            BlockHashOrNumber::Hash(hash) if hash == tip.hash => return Ok(true),
+            BlockHashOrNumber::Hash(hash) => hash,
+        };
+
+        let client = self.client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        let block: Option<Block> = client.request("eth_getBlockByHash", (hash, false)).await?;
+        let Some(number) = block.and_then(|block| block.header.number) else { return Ok(false) };
+        if number > tip.number {
+            return Ok(false);
+        }
+        let canonical: Option<Block> = client
+            .request("eth_getBlockByNumber", (BlockNumberOrTag::Number(number), false))
+            .await?;
+        Ok(canonical.and_then(|block| block.header.hash) == Some(hash))
+    }
+}
+
+/// The task behind a [`FinalityTracker`].
+pub(crate) struct FinalityPoller<T> {
+    client: WeakClient<T>,
+}
+
+impl<T: Transport + Clone> FinalityPoller<T> {
+    pub(crate) const fn new(client: WeakClient<T>) -> Self {
+        Self { client }
+    }
+
+    /// Returns a stream of the blocks of `tag`, polled with `eth_getBlockByNumber`.
+    fn poll(&self, tag: BlockNumberOrTag) -> impl Stream<Item = Option<Block>> + Unpin {
+        PollerBuilder::<T, _, Option<Block>>::new(
+            self.client.clone(),
+            "eth_getBlockByNumber",
+            (tag, false),
+        )
+        .into_stream()
+    }
+
+    fn channels(
+        &self,
+    ) -> (
+        FinalityTracker<T>,
+        broadcast::Sender<FinalityEvent>,
+        [watch::Sender<Option<BlockNumHash>>; 2],
+    ) {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_SIZE);
+        let (finalized, finalized_rx) = watch::channel(None);
+        let (safe, safe_rx) = watch::channel(None);
+        let tracker = FinalityTracker {
+            client: self.client.clone(),
+            events: events.clone(),
+            finalized: finalized_rx,
+            safe: safe_rx,
+        };
+        (tracker, events, [finalized, safe])
+    }
+
+    async fn into_future(
+        mut heads: impl Stream<Item = (usize, Option<Block>)> + Unpin,
+        events: broadcast::Sender<FinalityEvent>,
+        tips: [watch::Sender<Option<BlockNumHash>>; 2],
+    ) {
+        loop {
+            let (index, block) = select! {
+                head = heads.next() => match head {
+                    Some(head) => head,
+                    None => break,
+                },
+                _ = tips[0].closed() => break,
+            };
+            let Some(block) = block.as_ref().and_then(|block| {
+                Some(BlockNumHash::new(block.header.number?, block.header.hash?))
+            }) else {
+                continue;
+            };
+            // Finality only advances, a lower block is polled from a lagging node.
+            if tips[index].borrow().is_some_and(|tip| tip.number >= block.number) {
+                continue;
+            }
+            tips[index].send_replace(Some(block));
+            let event = if index == 0 {
+                FinalityEvent::Finalized(block)
+            } else {
+                FinalityEvent::Safe(block)
+            };
+            trace!(?event, "finality advanced");
+            let _ = events.send(event);
+        }
+        debug!("finality tracker stopped");
+    }
+
+    /// Spawns the poller task, returning its handle.
+    pub(crate) fn spawn(self) -> FinalityTracker<T> {
+        let (tracker, events, tips) = self.channels();
+        Self::into_future(self.heads(), events, tips).spawn_task();
+        tracker
+    }
+
+    fn heads(&self) -> impl Stream<Item = (usize, Option<Block>)> + Unpin {
+        let finalized = self.poll(BlockNumberOrTag::Finalized).map(|block| (0, block));
+        let safe = self.poll(BlockNumberOrTag::Safe).map(|block| (1, block));
+        stream::select(finalized, safe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Provider, RootProvider};
+    use alloy_primitives::B256;
+    use alloy_rpc_types_eth::Header;
+    use serde_json::json;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    fn block(number: u64) -> Block {
+        let header = Header {
+            number: Some(number),
+            hash: Some(B256::with_last_byte(number as u8)),
+            ..Default::default()
+        };
+        Block { header, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn tracks_finality() {
+        let finalized = Arc::new(Mutex::new(10u64));
+        let node_finalized = finalized.clone();
+        let transport = MockTransport::new(move |method, params| {
+            let finalized = *node_finalized.lock().unwrap();
+            let block = match (method, params[0].as_str().unwrap()) {
+                ("eth_getBlockByNumber", "finalized") => Some(block(finalized)),
+                ("eth_getBlockByNumber", "safe") => Some(block(finalized + 2)),
+                ("eth_getBlockByNumber", number) => {
+                    Some(block(u64::from_str_radix(&number[2..], 16).unwrap()))
+                }
+                ("eth_getBlockByHash", hash) => {
+                    let hash: B256 = hash.parse().unwrap();
+                    // A block off the canonical chain, with the number of block 5.
+                    Some(if hash == B256::repeat_byte(5) {
+                        Block { header: Header { hash: Some(hash), ..block(5).header }, ..block(5) }
+                    } else {
+                        block(hash[31] as u64)
+                    })
+                }
+                (method, _) => unreachable!("{method}"),
+            };
+            Ok(json!(block))
+        });
+        let client = transport.client().with_poll_interval(Duration::from_millis(10));
+        let provider = RootProvider::<_>::new(client);
+
+        let tracker = provider.finality_tracker();
+        let mut events = tracker.subscribe();
+        let mut received = vec![events.recv().await.unwrap(), events.recv().await.unwrap()];
+        received.sort_by_key(|event| matches!(event, FinalityEvent::Safe(_)));
+        let num_hash = |number| BlockNumHash::new(number, B256::with_last_byte(number as u8));
+        assert_eq!(
+            received,
+            [FinalityEvent::Finalized(num_hash(10)), FinalityEvent::Safe(num_hash(12))]
+        );
+
+        assert!(tracker.is_finalized(10).await.unwrap());
+        assert!(!tracker.is_finalized(11).await.unwrap());
+        assert!(tracker.is_safe(11).await.unwrap());
+        assert!(tracker.is_finalized(B256::with_last_byte(4)).await.unwrap());
+        assert!(!tracker.is_finalized(B256::with_last_byte(11)).await.unwrap());
+        assert!(!tracker.is_finalized(B256::repeat_byte(5)).await.unwrap());
+
+        *finalized.lock().unwrap() = 11;
+        let mut received = vec![events.recv().await.unwrap(), events.recv().await.unwrap()];
+        received.sort_by_key(|event| matches!(event, FinalityEvent::Safe(_)));
+        assert_eq!(
+            received,
+            [FinalityEvent::Finalized(num_hash(11)), FinalityEvent::Safe(num_hash(13))]
+        );
+        assert_eq!(tracker.finalized(), Some(num_hash(11)));
+    }
+}
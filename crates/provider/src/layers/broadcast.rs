@@ -0,0 +1,225 @@
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{hex, TxHash};
+use alloy_rpc_client::RpcClient;
+use alloy_transport::{
+    utils::Spawnable, BoxTransport, RpcFut, Transport, TransportError, TransportErrorKind,
+    TransportResult,
+};
+use futures::{stream::FuturesUnordered, StreamExt};
+use std::{fmt, marker::PhantomData, sync::Arc};
+
+use crate::{PendingTransactionBuilder, Provider, ProviderLayer, RootProvider};
+
+/// A layer that broadcasts raw transactions to additional endpoints, such as public RPCs and
+/// private relays, alongside the inner provider.
+///
+/// `eth_sendRawTransaction` is sent to the inner provider and every endpoint simultaneously.
+/// The call resolves as soon as one of them accepts the transaction, while the remaining requests
+/// keep running in the background. If every endpoint rejects it, the call fails with a
+/// [`BroadcastError`] holding all the errors.
+///
+/// All other requests only go through the inner provider.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(raw_tx: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::{layers::BroadcastLayer, Provider, ProviderBuilder};
+///
+/// let relays = BroadcastLayer::http([
+///     "https://rpc.flashbots.net".parse()?,
+///     "https://rpc.mevblocker.io".parse()?,
+/// ]);
+/// let provider =
+///     ProviderBuilder::new().layer(relays).on_http("https://eth.llamarpc.com".parse()?);
+/// let pending = provider.send_raw_transaction(raw_tx).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BroadcastLayer {
+    endpoints: Arc<[RpcClient<BoxTransport>]>,
+}
+
+impl BroadcastLayer {
+    /// Creates a new layer broadcasting to the given endpoints.
+    pub fn new(endpoints: impl IntoIterator<Item = RpcClient<BoxTransport>>) -> Self {
+        Self { endpoints: endpoints.into_iter().collect() }
+    }
+
+    /// Creates a new layer broadcasting to the given HTTP endpoints.
+    #[cfg(feature = "reqwest")]
+    pub fn http(urls: impl IntoIterator<Item = reqwest::Url>) -> Self {
+        Self::new(urls.into_iter().map(|url| RpcClient::new_http(url).boxed()))
+    }
+
+    /// Returns the endpoints transactions are broadcast to, besides the inner provider.
+    pub fn endpoints(&self) -> &[RpcClient<BoxTransport>] {
+        &self.endpoints
+    }
+}
+
+impl<P, T, N> ProviderLayer<P, T, N> for BroadcastLayer
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    type Provider = BroadcastProvider<P, T, N>;
+
+    fn layer(&self, inner: P) -> Self::Provider {
+        BroadcastProvider { inner, endpoints: self.endpoints.clone(), _pd: PhantomData }
+    }
+}
+
+/// A provider that broadcasts raw transactions to additional endpoints, created by a
+/// [`BroadcastLayer`].
+#[derive(Clone, Debug)]
+pub struct BroadcastProvider<P, T, N = Ethereum> {
+    inner: P,
+    endpoints: Arc<[RpcClient<BoxTransport>]>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> BroadcastProvider<P, T, N> {
+    /// Returns the endpoints transactions are broadcast to, besides the inner provider.
+    pub fn endpoints(&self) -> &[RpcClient<BoxTransport>] {
+        &self.endpoints
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> Provider<T, N> for BroadcastProvider<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<T, N> {
+        self.inner.root()
+    }
+
+    async fn send_raw_transaction(
+        &self,
+        encoded_tx: &[u8],
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        let rlp_hex = hex::encode_prefixed(encoded_tx);
+
+        let mut requests = FuturesUnordered::<RpcFut<'static, TxHash>>::new();
+        requests
+            .push(Box::pin(self.client().request("eth_sendRawTransaction", (rlp_hex.clone(),))));
+        for endpoint in self.endpoints.iter() {
+            requests.push(Box::pin(endpoint.request("eth_sendRawTransaction", (rlp_hex.clone(),))));
+        }
+
+        let mut errors = Vec::new();
+        while let Some(res) = requests.next().await {
+            match res {
+                Ok(tx_hash) => {
+                    if !requests.is_empty() {
+                        requests
+                            .for_each(|res| async move {
+                                if let Err(err) = res {
+                                    debug!(%err, "broadcast endpoint rejected transaction");
+                                }
+                            })
+                            .spawn_task();
+                    }
+                    return Ok(PendingTransactionBuilder::new(self.root(), tx_hash));
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        Err(TransportErrorKind::custom(BroadcastError { errors }))
+    }
+}
+
+/// The error returned when every endpoint of a [`BroadcastProvider`] rejected a transaction.
+#[derive(Debug)]
+pub struct BroadcastError {
+    /// The errors returned by the endpoints, in the order they were received.
+    pub errors: Vec<TransportError>,
+}
+
+impl fmt::Display for BroadcastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "all {} endpoints rejected the transaction", self.errors.len())?;
+        for (i, err) in self.errors.iter().enumerate() {
+            write!(f, "{} {err}", if i == 0 { ":" } else { ";" })?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BroadcastError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{rpc_error, MockTransport},
+        ProviderBuilder,
+    };
+    use alloy_primitives::b256;
+    use alloy_transport::RpcError;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    type Calls = Arc<Mutex<Vec<String>>>;
+
+    /// A transport answering every request with `result`, or with an error if `None`.
+    fn endpoint(calls: Calls, result: Option<TxHash>) -> MockTransport {
+        MockTransport::new(move |method, _params| {
+            calls.lock().unwrap().push(method.to_string());
+            result.map(|hash| json!(hash)).ok_or_else(|| rpc_error(-32000, "nonce too low"))
+        })
+    }
+
+    fn provider(
+        inner: Option<TxHash>,
+        endpoints: &[Option<TxHash>],
+    ) -> (impl Provider<MockTransport>, Calls) {
+        let calls = Calls::default();
+        let layer = BroadcastLayer::new(
+            endpoints.iter().map(|result| endpoint(calls.clone(), *result).client().boxed()),
+        );
+        let provider =
+            ProviderBuilder::new().layer(layer).on_client(endpoint(calls.clone(), inner).client());
+        (provider, calls)
+    }
+
+    const HASH: TxHash = b256!("2222222222222222222222222222222222222222222222222222222222222222");
+
+    #[tokio::test]
+    async fn resolves_on_first_success() {
+        let (provider, calls) = provider(None, &[None, Some(HASH), None]);
+        let pending = provider.send_raw_transaction(&[0x01]).await.unwrap();
+        assert_eq!(*pending.tx_hash(), HASH);
+
+        // The remaining requests are still delivered.
+        tokio::task::yield_now().await;
+        assert_eq!(calls.lock().unwrap().len(), 4);
+        assert!(calls.lock().unwrap().iter().all(|method| method == "eth_sendRawTransaction"));
+    }
+
+    #[tokio::test]
+    async fn aggregates_errors() {
+        let (provider, _) = provider(None, &[None, None]);
+        let err = provider.send_raw_transaction(&[0x01]).await.unwrap_err();
+        let RpcError::Transport(TransportErrorKind::Custom(err)) = err else {
+            panic!("unexpected error: {err}")
+        };
+        let err = err.downcast_ref::<BroadcastError>().unwrap();
+        assert_eq!(err.errors.len(), 3);
+        assert!(err.errors.iter().all(|err| err.as_error_resp().unwrap().code == -32000));
+    }
+
+    #[tokio::test]
+    async fn other_requests_use_inner_provider() {
+        let (provider, calls) = provider(Some(HASH), &[Some(HASH)]);
+        let _ = provider.get_block_number().await;
+        assert_eq!(*calls.lock().unwrap(), ["eth_blockNumber"]);
+    }
+}
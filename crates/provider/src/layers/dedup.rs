@@ -0,0 +1,244 @@
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_primitives::B256;
+use alloy_transport::{Transport, TransportError, TransportErrorKind, TransportFut};
+use futures::future::{FutureExt, WeakShared};
+use futures_utils_wasm::BoxFuture;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+/// The namespaces of the methods deduplicated by default.
+const READ_NAMESPACES: &[&str] = &["eth_", "net_", "web3_", "trace_"];
+
+/// The prefixes of the methods of the default namespaces that change the state of the node.
+const STATEFUL_METHODS: &[&str] = &[
+    "eth_send",
+    "eth_sign",
+    "eth_new",
+    "eth_uninstall",
+    "eth_getFilterChanges",
+    "eth_subscribe",
+    "eth_unsubscribe",
+];
+
+/// Returns whether identical concurrent requests of `method` can share a response.
+///
+/// This is the default filter of a [`DedupLayer`]: the methods of the `eth`, `net`, `web3` and
+/// `trace` namespaces, except those sending or signing transactions and managing filters and
+/// subscriptions.
+pub fn is_deduplicable(method: &str) -> bool {
+    READ_NAMESPACES.iter().any(|namespace| method.starts_with(namespace))
+        && !STATEFUL_METHODS.iter().any(|prefix| method.starts_with(prefix))
+}
+
+type ResponseFut = BoxFuture<'static, Result<ResponsePacket, Arc<TransportError>>>;
+
+type InFlight = Arc<Mutex<HashMap<(String, B256), WeakShared<ResponseFut>>>>;
+
+/// A layer deduplicating identical concurrent requests.
+///
+/// Added to a [`ClientBuilder`], the layer sends a single request upstream for all the requests
+/// with the same method and params that are in flight at the same time, and fans its response out
+/// to each of them with their own id. This collapses bursts of identical requests, e.g. many tasks
+/// asking for `eth_blockNumber` or `eth_chainId` at once. Responses are not cached: a request sent
+/// after the response of an identical one was received is sent upstream.
+///
+/// Only the methods accepted by the filter, [`is_deduplicable`] by default, are deduplicated.
+/// Batches and subscriptions are never deduplicated. An upstream call is cancelled once all the
+/// requests sharing it are dropped. Requests sharing a failed call fail with an error wrapping the
+/// original one, except the last request to receive it, which gets the original error.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::{layers::DedupLayer, Provider, ProviderBuilder};
+/// use alloy_rpc_client::ClientBuilder;
+///
+/// let client =
+///     ClientBuilder::default().layer(DedupLayer::new()).http("http://localhost:8545".parse()?);
+/// let provider = ProviderBuilder::new().on_client(client);
+/// let (a, b) = tokio::join!(provider.get_block_number(), provider.get_block_number());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`ClientBuilder`]: alloy_rpc_client::ClientBuilder
+#[derive(Clone, Copy, Debug)]
+pub struct DedupLayer {
+    filter: fn(&str) -> bool,
+}
+
+impl Default for DedupLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DedupLayer {
+    /// Creates a new layer deduplicating the methods accepted by [`is_deduplicable`].
+    pub const fn new() -> Self {
+        Self { filter: is_deduplicable }
+    }
+
+    /// Creates a new layer deduplicating the methods accepted by `filter`.
+    pub const fn with_filter(filter: fn(&str) -> bool) -> Self {
+        Self { filter }
+    }
+}
+
+impl<S> tower::Layer<S> for DedupLayer {
+    type Service = DedupService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DedupService { inner, filter: self.filter, in_flight: Default::default() }
+    }
+}
+
+/// A transport deduplicating identical concurrent requests, created by a [`DedupLayer`].
+pub struct DedupService<S> {
+    inner: S,
+    filter: fn(&str) -> bool,
+    in_flight: InFlight,
+}
+
+impl<S: fmt::Debug> fmt::Debug for DedupService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedupService")
+            .field("inner", &self.inner)
+            .field("in_flight", &self.in_flight.lock().unwrap().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Clone> Clone for DedupService<S> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), filter: self.filter, in_flight: self.in_flight.clone() }
+    }
+}
+
+impl<S> tower::Service<RequestPacket> for DedupService<S>
+where
+    S: Transport + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let RequestPacket::Single(single) = &request else { return self.inner.call(request) };
+        if single.is_subscription() || !(self.filter)(single.method()) {
+            return self.inner.call(request);
+        }
+        let id = single.id().clone();
+        let key = (single.method().to_owned(), single.params_hash());
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        let shared = match in_flight.get(&key).and_then(WeakShared::upgrade) {
+            Some(shared) => shared,
+            None => {
+                let response = self.inner.call(request);
+                let (map, entry) = (self.in_flight.clone(), key.clone());
+                let response: ResponseFut = Box::pin(async move {
+                    let response = response.await.map_err(Arc::new);
+                    map.lock().unwrap().remove(&entry);
+                    response
+                });
+                let shared = response.shared();
+                in_flight.insert(key, shared.downgrade().expect("not polled"));
+                shared
+            }
+        };
+        drop(in_flight);
+
+        Box::pin(async move {
+            match shared.await {
+                Ok(ResponsePacket::Single(mut response)) => {
+                    response.id = id;
+                    Ok(ResponsePacket::Single(response))
+                }
+                Ok(response) => Ok(response),
+                Err(err) => Err(Arc::try_unwrap(err)
+                    .unwrap_or_else(|err| TransportErrorKind::custom(SharedError(err)))),
+            }
+        })
+    }
+}
+
+/// The error of an upstream call shared by several requests.
+#[derive(Debug)]
+struct SharedError(Arc<TransportError>);
+
+impl fmt::Display for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for SharedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Provider, ProviderBuilder};
+    use alloy_primitives::Bytes;
+    use alloy_rpc_client::ClientBuilder;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn default_filter() {
+        assert!(is_deduplicable("eth_blockNumber"));
+        assert!(is_deduplicable("eth_getBalance"));
+        assert!(is_deduplicable("net_version"));
+        assert!(!is_deduplicable("eth_sendRawTransaction"));
+        assert!(!is_deduplicable("eth_newBlockFilter"));
+        assert!(!is_deduplicable("anvil_mine"));
+    }
+
+    #[tokio::test]
+    async fn dedups_concurrent_requests() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let transport = MockTransport::new(move |method, _params| {
+            let calls = {
+                let mut calls = recorded.lock().unwrap();
+                calls.push(method.to_owned());
+                calls.len()
+            };
+            Ok(match method {
+                "eth_blockNumber" => json!(format!("{calls:#x}")),
+                _ => json!(B256::with_last_byte(calls as u8)),
+            })
+        })
+        .with_latency(Duration::from_millis(20));
+        let client = ClientBuilder::default().layer(DedupLayer::new()).transport(transport, true);
+        let provider = ProviderBuilder::new().on_client(client);
+
+        let numbers = futures::future::join_all((0..10).map(|_| provider.get_block_number())).await;
+        assert!(numbers.into_iter().all(|number| number.unwrap() == 1));
+        assert_eq!(*calls.lock().unwrap(), ["eth_blockNumber"]);
+
+        // The response is not cached.
+        assert_eq!(provider.get_block_number().await.unwrap(), 2);
+
+        // Transactions are always sent.
+        let tx = Bytes::from_static(&[1]);
+        let (a, b) =
+            tokio::join!(provider.send_raw_transaction(&tx), provider.send_raw_transaction(&tx));
+        assert_ne!(a.unwrap().tx_hash(), b.unwrap().tx_hash());
+        assert_eq!(calls.lock().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{
+    provider::SendableTx, PendingTransactionBuilder, Provider, ProviderLayer, RootProvider,
+};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::TxHash;
+use alloy_transport::{Transport, TransportError, TransportFut, TransportResult};
+use futures_utils_wasm::impl_future;
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// Hooks called before requests are dispatched to the transport, and after their responses are
+/// received.
+///
+/// Applied by adding a [`HookLayer`] to a [`ClientBuilder`]. Both hooks do nothing by default.
+///
+/// [`ClientBuilder`]: alloy_rpc_client::ClientBuilder
+pub trait RequestHooks: fmt::Debug + Send + Sync + 'static {
+    /// Called before a request, or a batch of requests, is dispatched.
+    ///
+    /// Returning an error rejects the request without dispatching it.
+    fn before_request(&self, _request: &RequestPacket) -> TransportResult<()> {
+        Ok(())
+    }
+
+    /// Called with the response to a request, or the error the request failed with.
+    fn after_response(&self, _response: &TransportResult<ResponsePacket>) {}
+}
+
+/// Hooks called around sending transactions, with the filled transaction.
+///
+/// Applied by adding a [`HookLayer`] to a [`ProviderBuilder`]. Since fillers always run before
+/// other layers, the hooks see the transaction as it is sent: fully filled, and signed if the
+/// provider has a wallet. Raw transactions sent with [`Provider::send_raw_transaction`] are not
+/// hooked. Both hooks do nothing by default.
+///
+/// [`ProviderBuilder`]: crate::ProviderBuilder
+pub trait TransactionHooks<N: Network = Ethereum>: fmt::Debug + Send + Sync + 'static {
+    /// Called before a transaction is sent.
+    ///
+    /// Returning an error rejects the transaction without sending it.
+    fn before_send(&self, _tx: &SendableTx<N>) -> impl_future!(<Output = TransportResult<()>>) {
+        async { Ok(()) }
+    }
+
+    /// Called with the hash of a sent transaction, or the error sending it failed with.
+    fn after_send(&self, _result: Result<TxHash, &TransportError>) {}
+}
+
+/// A layer calling user-defined hooks around requests and transactions.
+///
+/// Added to a [`ClientBuilder`], the layer calls the [`RequestHooks`] around every request sent by
+/// the client. Added to a [`ProviderBuilder`], it calls the [`TransactionHooks`] around every
+/// transaction sent by the provider. This lets policies, such as allowlisting destinations or
+/// limiting spending, be implemented without writing a [`Provider`] or a filler.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_network::{Ethereum, TransactionBuilder};
+/// use alloy_primitives::Address;
+/// use alloy_provider::{
+///     layers::{HookLayer, TransactionHooks},
+///     ProviderBuilder, SendableTx,
+/// };
+/// use alloy_transport::{TransportErrorKind, TransportResult};
+///
+/// /// Only sends transactions to the given addresses.
+/// #[derive(Debug)]
+/// struct Allowlist(Vec<Address>);
+///
+/// impl TransactionHooks<Ethereum> for Allowlist {
+///     async fn before_send(&self, tx: &SendableTx<Ethereum>) -> TransportResult<()> {
+///         match tx.as_builder().and_then(|tx| tx.to()) {
+///             Some(to) if self.0.contains(&to) => Ok(()),
+///             _ => Err(TransportErrorKind::custom_str("destination not allowed")),
+///         }
+///     }
+/// }
+///
+/// let provider = ProviderBuilder::new()
+///     .with_recommended_fillers()
+///     .layer(HookLayer::new(Allowlist(vec![Address::ZERO])))
+///     .on_http("http://localhost:8545".parse()?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`ClientBuilder`]: alloy_rpc_client::ClientBuilder
+/// [`ProviderBuilder`]: crate::ProviderBuilder
+#[derive(Debug)]
+pub struct HookLayer<H> {
+    hooks: Arc<H>,
+}
+
+impl<H> Clone for HookLayer<H> {
+    fn clone(&self) -> Self {
+        Self { hooks: self.hooks.clone() }
+    }
+}
+
+impl<H> HookLayer<H> {
+    /// Creates a new layer calling the given hooks.
+    pub fn new(hooks: H) -> Self {
+        Self { hooks: Arc::new(hooks) }
+    }
+
+    /// Returns the hooks called by the layer.
+    pub fn hooks(&self) -> &H {
+        &self.hooks
+    }
+}
+
+impl<S, H: RequestHooks> tower::Layer<S> for HookLayer<H> {
+    type Service = HookService<S, H>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HookService { inner, hooks: self.hooks.clone() }
+    }
+}
+
+impl<P, T, N, H> ProviderLayer<P, T, N> for HookLayer<H>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    H: TransactionHooks<N>,
+{
+    type Provider = HookProvider<P, H, T, N>;
+
+    fn layer(&self, inner: P) -> Self::Provider {
+        HookProvider { inner, hooks: self.hooks.clone(), _pd: PhantomData }
+    }
+}
+
+/// A transport calling [`RequestHooks`], created by a [`HookLayer`].
+#[derive(Debug)]
+pub struct HookService<S, H> {
+    inner: S,
+    hooks: Arc<H>,
+}
+
+impl<S: Clone, H> Clone for HookService<S, H> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), hooks: self.hooks.clone() }
+    }
+}
+
+impl<S, H> tower::Service<RequestPacket> for HookService<S, H>
+where
+    S: Transport + Clone,
+    H: RequestHooks,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let hooks = self.hooks.clone();
+        if let Err(err) = hooks.before_request(&request) {
+            return Box::pin(async move { Err(err) });
+        }
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await;
+            hooks.after_response(&response);
+            response
+        })
+    }
+}
+
+/// A provider calling [`TransactionHooks`], created by a [`HookLayer`].
+#[derive(Debug)]
+pub struct HookProvider<P, H, T, N = Ethereum> {
+    inner: P,
+    hooks: Arc<H>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P: Clone, H, T, N> Clone for HookProvider<P, H, T, N> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), hooks: self.hooks.clone(), _pd: PhantomData }
+    }
+}
+
+impl<P, H, T, N> HookProvider<P, H, T, N> {
+    /// Returns the hooks called by the provider.
+    pub fn hooks(&self) -> &H {
+        &self.hooks
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, H, T, N> Provider<T, N> for HookProvider<P, H, T, N>
+where
+    P: Provider<T, N>,
+    H: TransactionHooks<N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<T, N> {
+        self.inner.root()
+    }
+
+    async fn send_transaction_internal(
+        &self,
+        tx: SendableTx<N>,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        self.hooks.before_send(&tx).await?;
+        let result = self.inner.send_transaction_internal(tx).await;
+        self.hooks.after_send(result.as_ref().map(|pending| *pending.tx_hash()));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, ProviderBuilder};
+    use alloy_network::TransactionBuilder;
+    use alloy_primitives::{Address, B256};
+    use alloy_rpc_client::ClientBuilder;
+    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_transport::TransportErrorKind;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Recorder {
+        fn push(&self, event: impl Into<String>) {
+            self.events.lock().unwrap().push(event.into());
+        }
+
+        fn take(&self) -> Vec<String> {
+            std::mem::take(&mut self.events.lock().unwrap())
+        }
+    }
+
+    impl RequestHooks for Recorder {
+        fn before_request(&self, request: &RequestPacket) -> TransportResult<()> {
+            let RequestPacket::Single(request) = request else { unreachable!() };
+            self.push(request.method());
+            Ok(())
+        }
+
+        fn after_response(&self, response: &TransportResult<ResponsePacket>) {
+            self.push(if response.is_ok() { "ok" } else { "err" });
+        }
+    }
+
+    impl TransactionHooks for Recorder {
+        async fn before_send(&self, tx: &SendableTx<Ethereum>) -> TransportResult<()> {
+            let to = tx.as_builder().unwrap().to().unwrap();
+            self.push(format!("before {to}"));
+            if to != Address::ZERO {
+                return Err(TransportErrorKind::custom_str("destination not allowed"));
+            }
+            Ok(())
+        }
+
+        fn after_send(&self, result: Result<TxHash, &TransportError>) {
+            self.push(format!("after {}", result.unwrap()));
+        }
+    }
+
+    #[tokio::test]
+    async fn hooks() {
+        let transport = MockTransport::new(|method, _params| {
+            assert_eq!(method, "eth_sendTransaction");
+            Ok(json!(B256::with_last_byte(1)))
+        });
+        let requests = HookLayer::new(Recorder::default());
+        let client = ClientBuilder::default().layer(requests.clone()).transport(transport, true);
+        let transactions = HookLayer::new(Recorder::default());
+        let provider = ProviderBuilder::new().layer(transactions.clone()).on_client(client);
+
+        let tx = TransactionRequest::default().with_to(Address::ZERO);
+        let pending = provider.send_transaction(tx).await.unwrap();
+        assert_eq!(*pending.tx_hash(), B256::with_last_byte(1));
+        assert_eq!(requests.hooks().take(), ["eth_sendTransaction", "ok"]);
+        assert_eq!(
+            transactions.hooks().take(),
+            [format!("before {}", Address::ZERO), format!("after {}", B256::with_last_byte(1))]
+        );
+
+        let tx = TransactionRequest::default().with_to(Address::with_last_byte(1));
+        assert!(provider.send_transaction(tx).await.is_err());
+        assert!(requests.hooks().take().is_empty());
+        assert_eq!(transactions.hooks().take(), [format!("before {}", Address::with_last_byte(1))]);
+    }
+}
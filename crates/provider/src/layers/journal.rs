@@ -0,0 +1,483 @@
+use crate::{
+    PendingTransactionBuilder, PendingTransactionConfig, Provider, ProviderLayer, RootProvider,
+};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Ethereum, Network, ReceiptResponse};
+use alloy_primitives::{keccak256, Bytes, TxHash};
+use alloy_transport::{utils::Spawnable, Transport, TransportErrorKind, TransportResult};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// An entry of a transaction journal, recording an event in the lifecycle of a signed
+/// transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+    /// The time of the event, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The event.
+    #[serde(flatten)]
+    pub event: JournalEvent,
+}
+
+/// An event in the lifecycle of a signed transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum JournalEvent {
+    /// The transaction is about to be sent. Recorded before sending, so that transactions can be
+    /// resubmitted after a crash.
+    Signed {
+        /// The [EIP-2718] encoding of the transaction.
+        ///
+        /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+        raw: Bytes,
+        /// The fields of the transaction, as filled, serialized as a transaction request.
+        request: Option<serde_json::Value>,
+    },
+    /// The node accepted the transaction.
+    Sent,
+    /// The node rejected the transaction with an error response.
+    ///
+    /// Transactions which failed to be sent for other reasons, such as a transport error, are not
+    /// recorded as rejected, as the node may have received them.
+    Rejected {
+        /// The error returned by the node.
+        error: String,
+    },
+    /// The transaction was included in a block.
+    Resolved {
+        /// Whether the transaction succeeded, according to its receipt.
+        success: bool,
+    },
+}
+
+impl JournalEntry {
+    /// Creates a new entry for an event happening now.
+    pub fn now(tx_hash: TxHash, event: JournalEvent) -> Self {
+        let timestamp =
+            SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        Self { tx_hash, timestamp, event }
+    }
+}
+
+/// Returns the raw transactions that were signed, but neither rejected nor resolved, in the order
+/// they were signed.
+///
+/// After a crash, these are the transactions that may need to be resubmitted.
+pub fn unresolved_transactions(entries: impl IntoIterator<Item = JournalEntry>) -> Vec<Bytes> {
+    let mut order = Vec::new();
+    let mut raw = HashMap::new();
+    for entry in entries {
+        match entry.event {
+            JournalEvent::Signed { raw: tx, .. } => {
+                if raw.insert(entry.tx_hash, tx).is_none() {
+                    order.push(entry.tx_hash);
+                }
+            }
+            JournalEvent::Sent => {}
+            JournalEvent::Rejected { .. } | JournalEvent::Resolved { .. } => {
+                raw.remove(&entry.tx_hash);
+            }
+        }
+    }
+    order.into_iter().filter_map(|tx_hash| raw.remove(&tx_hash)).collect()
+}
+
+/// A sink recording the entries of a transaction journal.
+///
+/// Implement this trait to store the journal in a database, or to forward it to an audit
+/// service. [`FileJournal`] and [`MemoryJournal`] are provided.
+///
+/// Entries are recorded on a blocking thread of the Tokio runtime, if any, so sinks may block on
+/// I/O.
+pub trait JournalSink: fmt::Debug + Send + Sync + 'static {
+    /// Records an entry.
+    ///
+    /// Failing to record a [`JournalEvent::Signed`] entry prevents the transaction from being
+    /// sent. Failures to record the other entries are only logged.
+    fn record(&self, entry: &JournalEntry) -> io::Result<()>;
+}
+
+/// A [`JournalSink`] appending entries to a file, as JSON lines.
+#[derive(Debug)]
+pub struct FileJournal {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileJournal {
+    /// Opens the journal at the given path, creating the file if it does not exist.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, file: Mutex::new(file) })
+    }
+
+    /// Returns the path of the journal.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the entries of the journal at the given path.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<JournalEntry>> {
+        BufReader::new(File::open(path)?)
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect()
+    }
+}
+
+impl JournalSink for FileJournal {
+    fn record(&self, entry: &JournalEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let mut file = self.file.lock().unwrap();
+        file.write_all(&line)?;
+        file.sync_data()
+    }
+}
+
+/// A [`JournalSink`] keeping entries in memory.
+#[derive(Debug, Default)]
+pub struct MemoryJournal {
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+impl MemoryJournal {
+    /// Returns the recorded entries.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+impl JournalSink for MemoryJournal {
+    fn record(&self, entry: &JournalEntry) -> io::Result<()> {
+        self.entries.lock().unwrap().push(entry.clone());
+        Ok(())
+    }
+}
+
+/// A layer recording every signed transaction sent by the provider to a [`JournalSink`].
+///
+/// Each transaction is recorded before being sent, with its raw bytes and its fields, then again
+/// once the node accepted or rejected it, and once it is included in a block, with the status of
+/// its receipt. This provides an audit trail of the transactions, and allows resubmitting the
+/// [unresolved transactions](unresolved_transactions) after a crash.
+///
+/// Transactions signed by a wallet filler, and raw transactions, are recorded. Transactions sent
+/// with `eth_sendTransaction`, to be signed by the node, are not.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(wallet: alloy_network::EthereumWallet) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_provider::{
+///     layers::{unresolved_transactions, FileJournal, JournalLayer},
+///     Provider, ProviderBuilder,
+/// };
+///
+/// let provider = ProviderBuilder::new()
+///     .with_recommended_fillers()
+///     .wallet(wallet)
+///     .layer(JournalLayer::new(FileJournal::open("transactions.jsonl")?))
+///     .on_http("http://localhost:8545".parse()?);
+///
+/// // Resubmit the transactions left pending by a previous run.
+/// for raw in unresolved_transactions(FileJournal::read("transactions.jsonl")?) {
+///     let _ = provider.send_raw_transaction(&raw).await;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct JournalLayer<S> {
+    sink: Arc<S>,
+    track_receipts: bool,
+}
+
+impl<S> Clone for JournalLayer<S> {
+    fn clone(&self) -> Self {
+        Self { sink: self.sink.clone(), track_receipts: self.track_receipts }
+    }
+}
+
+impl<S> JournalLayer<S> {
+    /// Creates a new layer recording transactions to the given sink.
+    pub fn new(sink: S) -> Self {
+        Self { sink: Arc::new(sink), track_receipts: true }
+    }
+
+    /// Sets whether to watch sent transactions, and record the status of their receipts. Enabled
+    /// by default.
+    pub const fn with_receipts(mut self, track_receipts: bool) -> Self {
+        self.track_receipts = track_receipts;
+        self
+    }
+
+    /// Returns the sink transactions are recorded to.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+}
+
+impl<P, T, N, S> ProviderLayer<P, T, N> for JournalLayer<S>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    S: JournalSink,
+{
+    type Provider = JournalProvider<P, S, T, N>;
+
+    fn layer(&self, inner: P) -> Self::Provider {
+        JournalProvider { inner, layer: self.clone(), _pd: PhantomData }
+    }
+}
+
+/// A provider recording signed transactions to a [`JournalSink`], created by a [`JournalLayer`].
+#[derive(Debug)]
+pub struct JournalProvider<P, S, T, N = Ethereum> {
+    inner: P,
+    layer: JournalLayer<S>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P: Clone, S, T, N> Clone for JournalProvider<P, S, T, N> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), layer: self.layer.clone(), _pd: PhantomData }
+    }
+}
+
+impl<P, S, T, N> JournalProvider<P, S, T, N> {
+    /// Returns the sink transactions are recorded to.
+    pub fn sink(&self) -> &S {
+        &self.layer.sink
+    }
+}
+
+/// Records an entry on a blocking thread, as sinks may block on I/O.
+async fn record_blocking<S: JournalSink>(sink: &Arc<S>, entry: JournalEntry) -> io::Result<()> {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        let sink = sink.clone();
+        return handle
+            .spawn_blocking(move || sink.record(&entry))
+            .await
+            .map_err(io::Error::other)?;
+    }
+    sink.record(&entry)
+}
+
+/// Records an entry, logging failures.
+async fn record<S: JournalSink>(sink: &Arc<S>, tx_hash: TxHash, event: JournalEvent) {
+    if let Err(err) = record_blocking(sink, JournalEntry::now(tx_hash, event)).await {
+        error!(%tx_hash, %err, "failed to record journal entry");
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, S, T, N> Provider<T, N> for JournalProvider<P, S, T, N>
+where
+    P: Provider<T, N>,
+    S: JournalSink,
+    T: Transport + Clone,
+    N: Network,
+{
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<T, N> {
+        self.inner.root()
+    }
+
+    async fn send_raw_transaction(
+        &self,
+        encoded_tx: &[u8],
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        let tx_hash = keccak256(encoded_tx);
+        let request = N::TxEnvelope::decode_2718(&mut &encoded_tx[..])
+            .ok()
+            .and_then(|tx| serde_json::to_value(N::TransactionRequest::from(tx)).ok());
+        let signed = JournalEvent::Signed { raw: Bytes::copy_from_slice(encoded_tx), request };
+        record_blocking(&self.layer.sink, JournalEntry::now(tx_hash, signed))
+            .await
+            .map_err(TransportErrorKind::custom)?;
+
+        let pending = match self.inner.send_raw_transaction(encoded_tx).await {
+            Ok(pending) => pending,
+            Err(err) => {
+                // Other errors leave the transaction unresolved, as it may have been sent.
+                if matches!(err, RpcError::ErrorResp(_)) {
+                    let rejected = JournalEvent::Rejected { error: err.to_string() };
+                    record(&self.layer.sink, tx_hash, rejected).await;
+                }
+                return Err(err);
+            }
+        };
+        record(&self.layer.sink, tx_hash, JournalEvent::Sent).await;
+
+        if self.layer.track_receipts {
+            let config = PendingTransactionConfig::new(*pending.tx_hash());
+            match self.root().watch_pending_transaction(config).await {
+                Ok(watcher) => {
+                    let sink = self.layer.sink.clone();
+                    let client = self.weak_client();
+                    async move {
+                        let receipt = async {
+                            let tx_hash = watcher.await?;
+                            let client =
+                                client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+                            client
+                                .request::<_, Option<N::ReceiptResponse>>(
+                                    "eth_getTransactionReceipt",
+                                    (tx_hash,),
+                                )
+                                .await
+                        };
+                        match receipt.await {
+                            Ok(Some(receipt)) => {
+                                let resolved = JournalEvent::Resolved { success: receipt.status() };
+                                record(&sink, tx_hash, resolved).await
+                            }
+                            Ok(None) => debug!(%tx_hash, "journaled transaction has no receipt"),
+                            Err(err) => {
+                                debug!(%tx_hash, %err, "failed to resolve journaled transaction")
+                            }
+                        }
+                    }
+                    .spawn_task();
+                }
+                Err(err) => debug!(%tx_hash, %err, "failed to watch journaled transaction"),
+            }
+        }
+
+        Ok(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mock::{rpc_error, MockTransport},
+        ProviderBuilder,
+    };
+    use alloy_network::{EthereumWallet, TransactionBuilder};
+    use alloy_primitives::{Address, U256};
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::TransactionRequest;
+    use alloy_signer_local::PrivateKeySigner;
+    use serde_json::json;
+
+    /// A transport accepting transactions with a nonce of 0, rejecting those with a nonce of 1,
+    /// and returning an invalid response to the others.
+    fn transport() -> MockTransport {
+        MockTransport::new(|method, params| {
+            assert_eq!(method, "eth_sendRawTransaction");
+            let (raw,): (Bytes,) = serde_json::from_value(params).unwrap();
+            let tx = alloy_consensus::TxEnvelope::decode_2718(&mut &raw[..]).unwrap();
+            let tx_hash = *tx.tx_hash();
+            match <TransactionRequest as From<_>>::from(tx).nonce {
+                Some(0) => Ok(json!(tx_hash)),
+                Some(1) => Err(rpc_error(-32000, "nonce too high")),
+                _ => Ok(json!("not a hash")),
+            }
+        })
+    }
+
+    fn tx(nonce: u64) -> TransactionRequest {
+        TransactionRequest::default()
+            .with_to(Address::ZERO)
+            .with_value(U256::from(1))
+            .with_nonce(nonce)
+            .with_chain_id(1)
+            .with_gas_limit(21_000)
+            .with_max_fee_per_gas(2)
+            .with_max_priority_fee_per_gas(1)
+    }
+
+    #[tokio::test]
+    async fn journals_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let layer = JournalLayer::new(FileJournal::open(&path).unwrap()).with_receipts(false);
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(PrivateKeySigner::random()))
+            .layer(layer)
+            .on_client(RpcClient::new(transport(), true));
+
+        let sent = *provider.send_transaction(tx(0)).await.unwrap().tx_hash();
+        provider.send_transaction(tx(1)).await.unwrap_err();
+        let err = provider.send_transaction(tx(2)).await.unwrap_err();
+        assert!(matches!(err, RpcError::DeserError { .. }));
+
+        let entries = FileJournal::read(&path).unwrap();
+        let events = entries.iter().map(|entry| &entry.event).collect::<Vec<_>>();
+        assert!(matches!(
+            events[..],
+            [
+                JournalEvent::Signed { .. },
+                JournalEvent::Sent,
+                JournalEvent::Signed { .. },
+                JournalEvent::Rejected { .. },
+                JournalEvent::Signed { .. },
+            ]
+        ));
+        assert_eq!(entries[0].tx_hash, sent);
+        assert_eq!(entries[1].tx_hash, sent);
+        let JournalEvent::Signed { request: Some(request), .. } = &entries[0].event else {
+            panic!("missing request")
+        };
+        assert_eq!(request["nonce"], "0x0");
+        assert_eq!(
+            serde_json::from_value::<Address>(request["to"].clone()).unwrap(),
+            Address::ZERO
+        );
+        let JournalEvent::Rejected { error } = &entries[3].event else { unreachable!() };
+        assert!(error.contains("nonce too high"));
+        // The sent transaction is not resolved without receipts, and the one whose response was
+        // invalid may have been sent.
+        let JournalEvent::Signed { raw, .. } = &entries[4].event else { unreachable!() };
+        let unresolved = unresolved_transactions(entries.clone());
+        assert_eq!(unresolved.len(), 2);
+        assert_eq!(unresolved[1], *raw);
+    }
+
+    #[test]
+    fn unresolved() {
+        let hash = |i| TxHash::with_last_byte(i);
+        let signed = |i| {
+            JournalEntry::now(
+                hash(i),
+                JournalEvent::Signed { raw: Bytes::from(vec![i]), request: None },
+            )
+        };
+        let entries = vec![
+            signed(1),
+            signed(2),
+            JournalEntry::now(hash(1), JournalEvent::Sent),
+            signed(3),
+            JournalEntry::now(hash(2), JournalEvent::Resolved { success: false }),
+            JournalEntry::now(hash(3), JournalEvent::Rejected { error: String::new() }),
+            signed(4),
+        ];
+        assert_eq!(unresolved_transactions(entries), [Bytes::from(vec![1]), Bytes::from(vec![4])]);
+
+        let entry = JournalEntry { tx_hash: hash(1), timestamp: 1, event: JournalEvent::Sent };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, format!(r#"{{"txHash":"{}","timestamp":1,"event":"sent"}}"#, hash(1)));
+        assert_eq!(serde_json::from_str::<JournalEntry>(&json).unwrap(), entry);
+    }
+}
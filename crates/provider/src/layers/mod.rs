@@ -1,11 +1,32 @@
 //! Useful layer implementations for the provider. Currently this
-//! module contains the `AnvilLayer`, `AnvilProvider` and `ChainLayer`
-//! types.
+//! module contains the `AnvilLayer`, `AnvilProvider`, `BroadcastLayer`,
+//! `BroadcastProvider`, `ChainLayer`, `DedupLayer`, `HookLayer`, `JournalLayer` and
+//! `ValidationLayer` types.
 
 #[cfg(any(test, feature = "anvil-node"))]
 mod anvil;
 #[cfg(any(test, feature = "anvil-node"))]
 pub use anvil::{AnvilLayer, AnvilProvider};
 
+mod broadcast;
+pub use broadcast::{BroadcastError, BroadcastLayer, BroadcastProvider};
+
 mod chain;
 pub use chain::ChainLayer;
+
+mod dedup;
+pub use dedup::{is_deduplicable, DedupLayer, DedupService};
+
+mod hooks;
+pub use hooks::{HookLayer, HookProvider, HookService, RequestHooks, TransactionHooks};
+
+mod journal;
+pub use journal::{
+    unresolved_transactions, FileJournal, JournalEntry, JournalEvent, JournalLayer,
+    JournalProvider, JournalSink, MemoryJournal,
+};
+
+mod validation;
+pub use validation::{
+    Divergence, DivergenceKind, ResponseSchema, ValidationLayer, ValidationService,
+};
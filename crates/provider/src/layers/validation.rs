@@ -0,0 +1,451 @@
+use alloy_json_rpc::{Id, RequestPacket, ResponsePacket, ResponsePayload};
+use alloy_primitives::{Bytes, B256, U128, U256, U64};
+use alloy_rpc_types_eth::{
+    Block, BlockTransactions, EIP1186AccountProofResponse, FeeHistory, Log, SyncStatus,
+    Transaction, TransactionReceipt,
+};
+use alloy_transport::{Transport, TransportError, TransportFut};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{value::RawValue, Value};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// A type a response can be validated against by a [`ValidationLayer`].
+///
+/// Besides the fields dropped or altered by a round trip through the type, the fields captured by
+/// the catch-all [`OtherFields`](alloy_serde::OtherFields) of the type are reported as unknown.
+pub trait ResponseSchema: DeserializeOwned + Serialize {
+    /// Returns the paths, relative to the value, of the fields that are unknown to the type but
+    /// were kept by it.
+    fn unknown_fields(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+impl<T: ResponseSchema> ResponseSchema for Option<T> {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.as_ref().map(T::unknown_fields).unwrap_or_default()
+    }
+}
+
+impl<T: ResponseSchema> ResponseSchema for Vec<T> {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.iter()
+            .enumerate()
+            .flat_map(|(i, item)| {
+                item.unknown_fields().into_iter().map(move |f| format!("[{i}]{f}"))
+            })
+            .collect()
+    }
+}
+
+impl ResponseSchema for Transaction {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.other.keys().map(|key| format!(".{key}")).collect()
+    }
+}
+
+impl ResponseSchema for Block {
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields: Vec<_> = self.other.keys().map(|key| format!(".{key}")).collect();
+        if let BlockTransactions::Full(transactions) = &self.transactions {
+            fields.extend(
+                transactions.unknown_fields().into_iter().map(|f| format!(".transactions{f}")),
+            );
+        }
+        fields
+    }
+}
+
+macro_rules! impl_response_schema {
+    ($($ty:ty),* $(,)?) => {
+        $(impl ResponseSchema for $ty {})*
+    };
+}
+
+impl_response_schema!(
+    bool,
+    U64,
+    U128,
+    U256,
+    B256,
+    Bytes,
+    Log,
+    TransactionReceipt,
+    FeeHistory,
+    EIP1186AccountProofResponse,
+    SyncStatus,
+);
+
+/// A divergence of a response from its schema, reported by a [`ValidationLayer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The method of the request.
+    pub method: String,
+    /// The path of the diverging value in the result, e.g. `$.transactions[0].hash`.
+    pub path: String,
+    /// The kind of the divergence.
+    pub kind: DivergenceKind,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} response at {}: {}", self.method, self.path, self.kind)
+    }
+}
+
+/// The kind of a [`Divergence`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The result could not be deserialized into the schema.
+    Deserialization(String),
+    /// A field unknown to the schema was kept in its catch-all fields.
+    UnknownField,
+    /// A field was dropped by the round trip through the schema.
+    DroppedField,
+    /// A non-null field was added by the round trip through the schema.
+    AddedField(Value),
+    /// A value was altered by the round trip through the schema.
+    Mismatch {
+        /// The value received from the node.
+        received: Value,
+        /// The value after the round trip.
+        reserialized: Value,
+    },
+}
+
+impl fmt::Display for DivergenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deserialization(err) => write!(f, "failed to deserialize: {err}"),
+            Self::UnknownField => f.write_str("unknown field"),
+            Self::DroppedField => f.write_str("field dropped by deserialization"),
+            Self::AddedField(value) => write!(f, "field added by serialization: {value}"),
+            Self::Mismatch { received, reserialized } => {
+                write!(f, "received {received}, reserialized as {reserialized}")
+            }
+        }
+    }
+}
+
+/// Validates a result, returning the paths and kinds of its divergences.
+type Validator = fn(&RawValue) -> Vec<(String, DivergenceKind)>;
+
+fn validate<T: ResponseSchema>(result: &RawValue) -> Vec<(String, DivergenceKind)> {
+    let typed = match serde_json::from_str::<T>(result.get()) {
+        Ok(typed) => typed,
+        Err(err) => {
+            return vec![("$".to_string(), DivergenceKind::Deserialization(err.to_string()))]
+        }
+    };
+    let mut divergences: Vec<_> = typed
+        .unknown_fields()
+        .into_iter()
+        .map(|path| (format!("${path}"), DivergenceKind::UnknownField))
+        .collect();
+    if let (Ok(received), Ok(reserialized)) =
+        (serde_json::from_str::<Value>(result.get()), serde_json::to_value(&typed))
+    {
+        diff("$".to_string(), &received, &reserialized, &mut divergences);
+    }
+    divergences
+}
+
+/// Compares a received value to its reserialization, pushing their divergences.
+fn diff(
+    path: String,
+    received: &Value,
+    reserialized: &Value,
+    out: &mut Vec<(String, DivergenceKind)>,
+) {
+    match (received, reserialized) {
+        (Value::Object(received), Value::Object(reserialized)) => {
+            for (key, value) in received {
+                match reserialized.get(key) {
+                    Some(other) => diff(format!("{path}.{key}"), value, other, out),
+                    None if value.is_null() => {}
+                    None => out.push((format!("{path}.{key}"), DivergenceKind::DroppedField)),
+                }
+            }
+            for (key, value) in reserialized {
+                if !received.contains_key(key) && !value.is_null() {
+                    out.push((format!("{path}.{key}"), DivergenceKind::AddedField(value.clone())));
+                }
+            }
+        }
+        (Value::Array(received), Value::Array(reserialized))
+            if received.len() == reserialized.len() =>
+        {
+            for (i, (value, other)) in received.iter().zip(reserialized).enumerate() {
+                diff(format!("{path}[{i}]"), value, other, out);
+            }
+        }
+        // Hex strings may differ in case, e.g. checksummed addresses.
+        (Value::String(a), Value::String(b))
+            if a.starts_with("0x") && b.starts_with("0x") && a.eq_ignore_ascii_case(b) => {}
+        (received, reserialized) if received == reserialized => {}
+        (received, reserialized) => out.push((
+            path,
+            DivergenceKind::Mismatch {
+                received: received.clone(),
+                reserialized: reserialized.clone(),
+            },
+        )),
+    }
+}
+
+/// A layer validating responses against typed schemas, for debugging.
+///
+/// Added to a [`ClientBuilder`], the layer deserializes the result of every successful response
+/// whose method has a schema into it, serializes it back, and reports the differences with the
+/// received result as [`Divergence`]s, along with the unknown fields kept by the schema. This
+/// catches nodes returning malformed or non-standard responses, and gaps in the deserialization of
+/// the types, before they surface as errors or silently dropped data.
+///
+/// Responses are passed through unchanged. Divergences are logged as warnings by default, or
+/// passed to the handler set with [`on_divergence`](Self::on_divergence). Since every response is
+/// deserialized twice, the layer is meant for development and staging environments.
+///
+/// [`new`](Self::new) has schemas for the common `eth` methods; others are added with
+/// [`with_schema`](Self::with_schema).
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_primitives::U64;
+/// use alloy_provider::{layers::ValidationLayer, ProviderBuilder};
+/// use alloy_rpc_client::ClientBuilder;
+///
+/// let validation = ValidationLayer::new()
+///     .with_schema::<U64>("net_peerCount")
+///     .on_divergence(|divergence| eprintln!("{divergence}"));
+/// let client = ClientBuilder::default().layer(validation).http("http://localhost:8545".parse()?);
+/// let provider = ProviderBuilder::new().on_client(client);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`ClientBuilder`]: alloy_rpc_client::ClientBuilder
+#[derive(Clone)]
+pub struct ValidationLayer {
+    schemas: Arc<HashMap<Cow<'static, str>, Validator>>,
+    handler: Arc<dyn Fn(&Divergence) + Send + Sync>,
+}
+
+impl fmt::Debug for ValidationLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationLayer")
+            .field("methods", &self.schemas.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ValidationLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValidationLayer {
+    /// Creates a new layer with schemas for the common `eth` methods, logging divergences.
+    pub fn new() -> Self {
+        Self::empty()
+            .with_schema::<U64>("eth_blockNumber")
+            .with_schema::<U64>("eth_chainId")
+            .with_schema::<U128>("eth_gasPrice")
+            .with_schema::<U128>("eth_maxPriorityFeePerGas")
+            .with_schema::<U256>("eth_getBalance")
+            .with_schema::<U64>("eth_getTransactionCount")
+            .with_schema::<B256>("eth_getStorageAt")
+            .with_schema::<Bytes>("eth_getCode")
+            .with_schema::<Bytes>("eth_call")
+            .with_schema::<U64>("eth_estimateGas")
+            .with_schema::<Option<Block>>("eth_getBlockByNumber")
+            .with_schema::<Option<Block>>("eth_getBlockByHash")
+            .with_schema::<Option<Transaction>>("eth_getTransactionByHash")
+            .with_schema::<Option<TransactionReceipt>>("eth_getTransactionReceipt")
+            .with_schema::<Option<Vec<TransactionReceipt>>>("eth_getBlockReceipts")
+            .with_schema::<Vec<Log>>("eth_getLogs")
+            .with_schema::<FeeHistory>("eth_feeHistory")
+            .with_schema::<EIP1186AccountProofResponse>("eth_getProof")
+            .with_schema::<SyncStatus>("eth_syncing")
+    }
+
+    /// Creates a new layer without schemas, logging divergences.
+    pub fn empty() -> Self {
+        Self {
+            schemas: Default::default(),
+            handler: Arc::new(
+                |divergence| tracing::warn!(%divergence, "response diverges from schema"),
+            ),
+        }
+    }
+
+    /// Validates the responses of `method` against `T`, replacing its previous schema.
+    pub fn with_schema<T: ResponseSchema>(mut self, method: impl Into<Cow<'static, str>>) -> Self {
+        Arc::make_mut(&mut self.schemas).insert(method.into(), validate::<T>);
+        self
+    }
+
+    /// Sets the handler of the divergences, instead of logging them.
+    pub fn on_divergence(mut self, handler: impl Fn(&Divergence) + Send + Sync + 'static) -> Self {
+        self.handler = Arc::new(handler);
+        self
+    }
+}
+
+impl<S> tower::Layer<S> for ValidationLayer {
+    type Service = ValidationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ValidationService { inner, layer: self.clone() }
+    }
+}
+
+/// A transport validating responses, created by a [`ValidationLayer`].
+#[derive(Clone, Debug)]
+pub struct ValidationService<S> {
+    inner: S,
+    layer: ValidationLayer,
+}
+
+impl<S> tower::Service<RequestPacket> for ValidationService<S>
+where
+    S: Transport + Clone,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let requests = match &request {
+            RequestPacket::Single(request) => std::slice::from_ref(request),
+            RequestPacket::Batch(requests) => requests.as_slice(),
+        };
+        let methods: HashMap<Id, (String, Validator)> = requests
+            .iter()
+            .filter_map(|request| {
+                let validator = *self.layer.schemas.get(request.method())?;
+                Some((request.id().clone(), (request.method().to_string(), validator)))
+            })
+            .collect();
+        let handler = self.layer.handler.clone();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await?;
+            let responses = match &response {
+                ResponsePacket::Single(response) => std::slice::from_ref(response),
+                ResponsePacket::Batch(responses) => responses.as_slice(),
+            };
+            for response in responses {
+                let (ResponsePayload::Success(result), Some((method, validator))) =
+                    (&response.payload, methods.get(&response.id))
+                else {
+                    continue;
+                };
+                for (path, kind) in validator(result) {
+                    handler(&Divergence { method: method.clone(), path, kind });
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Provider, ProviderBuilder};
+    use alloy_rpc_client::ClientBuilder;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    #[test]
+    fn diffs_round_trips() {
+        let result = |value: Value| RawValue::from_string(value.to_string()).unwrap();
+
+        let received = json!({"hash": "0xAB", "other": null});
+        assert!(validate::<Option<HashOnly>>(&result(received)).is_empty());
+
+        let divergences = validate::<U64>(&result(json!("0x01")));
+        assert_eq!(
+            divergences,
+            [(
+                "$".to_string(),
+                DivergenceKind::Mismatch { received: json!("0x01"), reserialized: json!("0x1") }
+            )]
+        );
+
+        let divergences = validate::<Option<HashOnly>>(&result(json!({"hash": "0x1", "extra": 1})));
+        assert_eq!(divergences, [("$.extra".to_string(), DivergenceKind::DroppedField)]);
+
+        let divergences = validate::<U64>(&result(json!("0xzz")));
+        assert!(matches!(divergences[..], [(_, DivergenceKind::Deserialization(_))]));
+    }
+
+    #[derive(Serialize, serde::Deserialize)]
+    struct HashOnly {
+        hash: String,
+    }
+
+    impl ResponseSchema for HashOnly {}
+
+    #[tokio::test]
+    async fn reports_divergences() {
+        let transport = MockTransport::new(|method, _params| {
+            let result = match method {
+                "eth_chainId" => json!("0x1"),
+                "eth_getTransactionByHash" => json!({
+                    "hash": B256::with_last_byte(1),
+                    "nonce": "0x0",
+                    "blockHash": null,
+                    "blockNumber": null,
+                    "transactionIndex": null,
+                    "from": "0x0000000000000000000000000000000000000001",
+                    "to": null,
+                    "value": "0x0",
+                    "gasPrice": "0x1",
+                    "gas": "0x5208",
+                    "input": "0x",
+                    "v": "0x1b",
+                    "r": "0x1",
+                    "s": "0x1",
+                    "type": "0x0",
+                    "l1Fee": "0x2",
+                }),
+                method => unreachable!("{method}"),
+            };
+            Ok(result)
+        });
+        let divergences = Arc::new(Mutex::new(Vec::new()));
+        let reported = divergences.clone();
+        let validation = ValidationLayer::new()
+            .on_divergence(move |divergence| reported.lock().unwrap().push(divergence.clone()));
+        let client = ClientBuilder::default().layer(validation).transport(transport, true);
+        let provider = ProviderBuilder::new().on_client(client);
+
+        assert_eq!(provider.get_chain_id().await.unwrap(), 1);
+        assert!(divergences.lock().unwrap().is_empty());
+
+        provider.get_transaction_by_hash(B256::with_last_byte(1)).await.unwrap().unwrap();
+        let divergences = divergences.lock().unwrap();
+        assert!(
+            divergences.contains(&Divergence {
+                method: "eth_getTransactionByHash".to_string(),
+                path: "$.l1Fee".to_string(),
+                kind: DivergenceKind::UnknownField,
+            }),
+            "{divergences:?}"
+        );
+    }
+}
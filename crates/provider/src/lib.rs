@@ -28,20 +28,47 @@ extern crate tracing;
 mod builder;
 pub use builder::{Identity, ProviderBuilder, ProviderLayer, Stack};
 
+pub mod broker;
+
 pub mod ext;
 
 pub mod fillers;
 pub mod layers;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock;
+
+mod canonical;
+pub use canonical::{CanonicalChainTracker, ChainEvent};
+
 mod chain;
 
+mod config;
+pub use config::{
+    ChainConfig, EndpointConfig, FillerConfig, LayerConfig, ProviderConfig, TransportKind,
+};
+
+mod finality;
+pub use finality::{FinalityEvent, FinalityTracker};
+
 mod heart;
 pub use heart::{PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig};
 
+mod log_index;
+#[cfg(all(feature = "log-index-sled", not(target_arch = "wasm32")))]
+pub use log_index::SledLogStore;
+pub use log_index::{
+    LogIndexCache, LogIndexCacheError, LogStore, MemoryLogStore, DEFAULT_LOG_INDEX_BATCH_SIZE,
+};
+
 mod provider;
 pub use provider::{
-    builder, EthCall, FilterPollerBuilder, Provider, RootProvider, RpcWithBlock, SendableTx,
-    WalletProvider,
+    builder, AccountDelta, AccountWatcher, BlockWithdrawals, BundleSimulation, BundleSimulator,
+    DynProvider, EthCall, ExecutionRequest, Executor, FeeHistoryStream, FilterGuard,
+    FilterPollerBuilder, GasQuote, GasTicker, PendingTransactionStream, Provider, RootProvider,
+    RpcExecutor, RpcWithBlock, SendableTx, StateSnapshot, StateSnapshotAt, StorageChange,
+    StorageWatcher, TimestampDirection, WaitForReceiptError, WalletProvider, WithdrawalsStream,
+    DEFAULT_GAS_TICKER_HYSTERESIS,
 };
 
 pub mod utils;
@@ -0,0 +1,511 @@
+//! A local index of the logs of a filter, answering historical queries without the node.
+
+use crate::Provider;
+use alloy_eips::{BlockNumHash, BlockNumberOrTag};
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types_eth::{Filter, Log};
+use alloy_transport::{Transport, TransportError};
+use async_trait::async_trait;
+use std::{collections::BTreeMap, fmt, marker::PhantomData, sync::Mutex};
+
+#[cfg(all(feature = "log-index-sled", not(target_arch = "wasm32")))]
+mod sled;
+#[cfg(all(feature = "log-index-sled", not(target_arch = "wasm32")))]
+pub use self::sled::SledLogStore;
+
+/// The default number of blocks whose logs are fetched with each `eth_getLogs` request.
+pub const DEFAULT_LOG_INDEX_BATCH_SIZE: u64 = 1000;
+
+/// An error of a [`LogIndexCache`] or of its [`LogStore`].
+#[derive(Debug)]
+pub enum LogIndexCacheError {
+    /// A request to the node failed.
+    Transport(TransportError),
+    /// The node does not have a block the index needs, e.g. because it is syncing.
+    MissingBlock(BlockNumber),
+    /// The queried blocks start before the first block of the index.
+    BeforeStart {
+        /// The first queried block.
+        from: BlockNumber,
+        /// The first block of the index.
+        start: BlockNumber,
+    },
+    /// The backend of the store failed.
+    Store(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl LogIndexCacheError {
+    /// Creates a new [`LogIndexCacheError::Store`] error.
+    pub fn store(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Store(Box::new(err))
+    }
+}
+
+impl From<TransportError> for LogIndexCacheError {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+impl fmt::Display for LogIndexCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(err) => err.fmt(f),
+            Self::MissingBlock(number) => write!(f, "the node does not have block {number}"),
+            Self::BeforeStart { from, start } => {
+                write!(f, "block {from} is before the first indexed block {start}")
+            }
+            Self::Store(err) => write!(f, "log store backend error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LogIndexCacheError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Transport(err) => Some(err),
+            Self::Store(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// The storage of a [`LogIndexCache`]: the logs of the indexed blocks, and the hashes of some of
+/// these blocks, the checkpoints, used to detect reorgs.
+///
+/// The logs are stored up to the last checkpoint, and each [`append`](Self::append) adds the
+/// logs of the blocks up to a new checkpoint. Implementations backed by a database, such as
+/// SQLite, must append and roll back atomically, so that the logs always match the
+/// checkpoints.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait LogStore: fmt::Debug + Send + Sync {
+    /// Returns the last checkpoint, up to which the logs are stored.
+    async fn last_checkpoint(&self) -> Result<Option<BlockNumHash>, LogIndexCacheError>;
+
+    /// Returns the last checkpoint before block `number`.
+    async fn checkpoint_before(
+        &self,
+        number: BlockNumber,
+    ) -> Result<Option<BlockNumHash>, LogIndexCacheError>;
+
+    /// Stores the logs of the blocks after the last checkpoint up to `checkpoint`, which becomes
+    /// the last checkpoint.
+    async fn append(
+        &self,
+        checkpoint: BlockNumHash,
+        logs: Vec<Log>,
+    ) -> Result<(), LogIndexCacheError>;
+
+    /// Removes the checkpoints and logs after block `number`, or all of them if `None`.
+    async fn rollback(&self, number: Option<BlockNumber>) -> Result<(), LogIndexCacheError>;
+
+    /// Returns the stored logs of the blocks `from..=to`, in order.
+    async fn logs(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<Log>, LogIndexCacheError>;
+}
+
+/// A [`LogStore`] in the memory of the process.
+///
+/// The index is lost when the process exits; see `SledLogStore`, behind the `log-index-sled`
+/// feature, for a store persisted on disk.
+#[derive(Debug, Default)]
+pub struct MemoryLogStore {
+    inner: Mutex<MemoryLogs>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryLogs {
+    checkpoints: BTreeMap<BlockNumber, B256>,
+    logs: BTreeMap<BlockNumber, Vec<Log>>,
+}
+
+impl MemoryLogStore {
+    /// Creates a new, empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl LogStore for MemoryLogStore {
+    async fn last_checkpoint(&self) -> Result<Option<BlockNumHash>, LogIndexCacheError> {
+        let inner = self.inner.lock().unwrap();
+        Ok(inner.checkpoints.last_key_value().map(|(&number, &hash)| BlockNumHash { number, hash }))
+    }
+
+    async fn checkpoint_before(
+        &self,
+        number: BlockNumber,
+    ) -> Result<Option<BlockNumHash>, LogIndexCacheError> {
+        let inner = self.inner.lock().unwrap();
+        let before = inner.checkpoints.range(..number).next_back();
+        Ok(before.map(|(&number, &hash)| BlockNumHash { number, hash }))
+    }
+
+    async fn append(
+        &self,
+        checkpoint: BlockNumHash,
+        logs: Vec<Log>,
+    ) -> Result<(), LogIndexCacheError> {
+        let mut inner = self.inner.lock().unwrap();
+        for log in logs {
+            let number = log.block_number.unwrap_or(checkpoint.number);
+            inner.logs.entry(number).or_default().push(log);
+        }
+        inner.checkpoints.insert(checkpoint.number, checkpoint.hash);
+        Ok(())
+    }
+
+    async fn rollback(&self, number: Option<BlockNumber>) -> Result<(), LogIndexCacheError> {
+        let mut inner = self.inner.lock().unwrap();
+        match number {
+            Some(number) => {
+                inner.checkpoints.split_off(&(number + 1));
+                inner.logs.split_off(&(number + 1));
+            }
+            None => *inner = MemoryLogs::default(),
+        }
+        Ok(())
+    }
+
+    async fn logs(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<Log>, LogIndexCacheError> {
+        if from > to {
+            return Ok(Vec::new());
+        }
+        let inner = self.inner.lock().unwrap();
+        Ok(inner.logs.range(from..=to).flat_map(|(_, logs)| logs.iter().cloned()).collect())
+    }
+}
+
+/// A local index of the logs matching a filter, kept in a [`LogStore`].
+///
+/// The index ingests the logs of the filter from its from block, in batches of
+/// [`DEFAULT_LOG_INDEX_BATCH_SIZE`] blocks, recording the hash of the last block of each batch as a
+/// checkpoint. Before ingesting new blocks, the last checkpoints are compared with the blocks
+/// of the node, and the logs after the last checkpoint still on the canonical chain are rolled
+/// back.
+///
+/// [`get_logs`](Self::get_logs) answers queries of already indexed blocks from the store, and
+/// only fetches the blocks after the last checkpoint from the node. Queries of indexed blocks
+/// are not checked against reorgs until the next [`sync`](Self::sync), which should be called
+/// regularly, e.g. on each new block.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_primitives::address;
+/// use alloy_provider::{LogIndexCache, MemoryLogStore};
+/// use alloy_rpc_types_eth::Filter;
+///
+/// let filter = Filter::new()
+///     .address(address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"))
+///     .event("Transfer(address,address,uint256)")
+///     .from_block(20_000_000);
+/// let index = LogIndexCache::new(provider, filter, MemoryLogStore::new());
+///
+/// // The first query fetches the logs from the node, the next ones are answered locally.
+/// let logs = index.get_logs(20_000_000, 20_100_000).await?;
+/// let again = index.get_logs(20_050_000, 20_100_000).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct LogIndexCache<P, T, N = Ethereum, S = MemoryLogStore> {
+    provider: P,
+    filter: Filter,
+    store: S,
+    start: BlockNumber,
+    batch_size: u64,
+    sync_lock: tokio::sync::Mutex<()>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P: fmt::Debug, T, N, S: fmt::Debug> fmt::Debug for LogIndexCache<P, T, N, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogIndexCache")
+            .field("provider", &self.provider)
+            .field("filter", &self.filter)
+            .field("store", &self.store)
+            .field("start", &self.start)
+            .field("batch_size", &self.batch_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N, S> LogIndexCache<P, T, N, S>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    S: LogStore,
+{
+    /// Creates an index of the logs matching `filter`, starting at its from block, or at the
+    /// genesis block if it has none. The other block options of the filter are ignored.
+    pub fn new(provider: P, filter: Filter, store: S) -> Self {
+        Self {
+            provider,
+            start: filter.get_from_block().unwrap_or_default(),
+            filter,
+            store,
+            batch_size: DEFAULT_LOG_INDEX_BATCH_SIZE,
+            sync_lock: Default::default(),
+            _pd: PhantomData,
+        }
+    }
+
+    /// Sets the number of blocks whose logs are fetched with each `eth_getLogs` request.
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Returns the store of the index.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the last indexed block.
+    pub async fn head(&self) -> Result<Option<BlockNumHash>, LogIndexCacheError> {
+        self.store.last_checkpoint().await
+    }
+
+    /// Rolls back reorged blocks, and indexes the blocks up to the latest block of the node.
+    pub async fn sync(&self) -> Result<Option<BlockNumHash>, LogIndexCacheError> {
+        let latest = self.provider.get_block_number().await?;
+        self.sync_to(latest).await
+    }
+
+    /// Rolls back reorged blocks, and indexes the blocks up to block `to`, returning the last
+    /// indexed block.
+    pub async fn sync_to(
+        &self,
+        to: BlockNumber,
+    ) -> Result<Option<BlockNumHash>, LogIndexCacheError> {
+        let _guard = self.sync_lock.lock().await;
+        let mut head = self.rollback_reorged().await?;
+        let mut next = head.map_or(self.start, |head| head.number + 1);
+        while next <= to {
+            let end = to.min(next.saturating_add(self.batch_size - 1));
+            let hash = self.block_hash(end).await?;
+            let filter = self.filter.clone().from_block(next).to_block(end);
+            let logs = self.provider.get_logs(&filter).await?;
+            // A reorg between the requests could mix the logs of one chain with the checkpoint
+            // of another, so the batch is retried if the last block changed.
+            if self.block_hash(end).await? != hash {
+                continue;
+            }
+            let checkpoint = BlockNumHash { number: end, hash };
+            self.store.append(checkpoint, logs).await?;
+            head = Some(checkpoint);
+            next = end + 1;
+        }
+        Ok(head)
+    }
+
+    /// Returns the logs of the blocks `from..=to` matching the filter of the index, syncing the
+    /// blocks after the last indexed block first.
+    pub async fn get_logs(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<Log>, LogIndexCacheError> {
+        if from < self.start {
+            return Err(LogIndexCacheError::BeforeStart { from, start: self.start });
+        }
+        if self.head().await?.map_or(true, |head| head.number < to) {
+            self.sync_to(to).await?;
+        }
+        self.store.logs(from, to).await
+    }
+
+    /// Walks back the checkpoints until one matches the node, rolls back the logs after it, and
+    /// returns it.
+    async fn rollback_reorged(&self) -> Result<Option<BlockNumHash>, LogIndexCacheError> {
+        let last = self.store.last_checkpoint().await?;
+        let mut checkpoint = last;
+        while let Some(candidate) = checkpoint {
+            match self.block_hash(candidate.number).await {
+                Ok(hash) if hash == candidate.hash => break,
+                Ok(_) | Err(LogIndexCacheError::MissingBlock(_)) => {
+                    checkpoint = self.store.checkpoint_before(candidate.number).await?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if checkpoint != last {
+            debug!(?last, ?checkpoint, "rolling back reorged logs");
+            self.store.rollback(checkpoint.map(|checkpoint| checkpoint.number)).await?;
+        }
+        Ok(checkpoint)
+    }
+
+    async fn block_hash(&self, number: BlockNumber) -> Result<B256, LogIndexCacheError> {
+        let block = self.provider.get_block_by_number(BlockNumberOrTag::Number(number), false);
+        block
+            .await?
+            .and_then(|block| block.header.hash)
+            .ok_or(LogIndexCacheError::MissingBlock(number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, RootProvider};
+    use alloy_primitives::Address;
+    use alloy_rpc_types_eth::{Block, Header};
+    use serde_json::Value;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// Returns the hash of block `number` of `fork`.
+    fn hash(number: u64, fork: u8) -> B256 {
+        let mut hash = B256::with_last_byte(fork);
+        hash[..8].copy_from_slice(&number.to_be_bytes());
+        hash
+    }
+
+    /// A node with one log per block, whose blocks after `fork_at` are on fork `fork`.
+    struct Node {
+        latest: u64,
+        fork_at: u64,
+        fork: u8,
+    }
+
+    impl Node {
+        fn hash(&self, number: u64) -> B256 {
+            hash(number, if number > self.fork_at { self.fork } else { 0 })
+        }
+
+        fn log(&self, number: u64) -> Log {
+            Log {
+                block_number: Some(number),
+                block_hash: Some(self.hash(number)),
+                inner: alloy_primitives::Log::empty(),
+                ..Default::default()
+            }
+        }
+    }
+
+    fn provider(node: Arc<Mutex<Node>>, get_logs: Arc<AtomicUsize>) -> RootProvider<MockTransport> {
+        let transport = MockTransport::new(move |method, params| {
+            let node = node.lock().unwrap();
+            let number = |value: &Value| {
+                u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+            };
+            let result = match method {
+                "eth_blockNumber" => serde_json::to_value(alloy_primitives::U64::from(node.latest)),
+                "eth_getBlockByNumber" => {
+                    let number = number(&params[0]);
+                    let header = Header {
+                        number: Some(number),
+                        hash: Some(node.hash(number)),
+                        ..Default::default()
+                    };
+                    serde_json::to_value((number <= node.latest).then(|| Block::<
+                        alloy_rpc_types_eth::Transaction,
+                    > {
+                        header,
+                        ..Default::default()
+                    }))
+                }
+                "eth_getLogs" => {
+                    get_logs.fetch_add(1, Ordering::SeqCst);
+                    let (from, to) =
+                        (number(&params[0]["fromBlock"]), number(&params[0]["toBlock"]));
+                    serde_json::to_value((from..=to).map(|n| node.log(n)).collect::<Vec<_>>())
+                }
+                method => unreachable!("{method}"),
+            };
+            Ok(result.unwrap())
+        });
+        transport.provider()
+    }
+
+    fn numbers(logs: &[Log]) -> Vec<u64> {
+        logs.iter().map(|log| log.block_number.unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn answers_indexed_blocks_locally() {
+        let node = Arc::new(Mutex::new(Node { latest: 25, fork_at: u64::MAX, fork: 0 }));
+        let get_logs = Arc::new(AtomicUsize::new(0));
+        let filter = Filter::new().address(Address::ZERO).from_block(10);
+        let index = LogIndexCache::new(
+            provider(node.clone(), get_logs.clone()),
+            filter,
+            MemoryLogStore::new(),
+        )
+        .with_batch_size(4);
+
+        assert!(matches!(
+            index.get_logs(5, 12).await,
+            Err(LogIndexCacheError::BeforeStart { from: 5, start: 10 })
+        ));
+        assert_eq!(numbers(&index.get_logs(10, 20).await.unwrap()), (10..=20).collect::<Vec<_>>());
+        assert_eq!(get_logs.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            index.head().await.unwrap(),
+            Some(BlockNumHash { number: 20, hash: hash(20, 0) })
+        );
+
+        // Indexed blocks are answered locally, only the tail is fetched.
+        assert_eq!(numbers(&index.get_logs(12, 15).await.unwrap()), [12, 13, 14, 15]);
+        assert_eq!(get_logs.load(Ordering::SeqCst), 3);
+        assert_eq!(numbers(&index.get_logs(18, 22).await.unwrap()), [18, 19, 20, 21, 22]);
+        assert_eq!(get_logs.load(Ordering::SeqCst), 4);
+
+        assert_eq!(index.sync().await.unwrap().unwrap().number, 25);
+        // The end of the next batch, 26..=29, is not mined yet.
+        assert!(matches!(index.sync_to(30).await, Err(LogIndexCacheError::MissingBlock(29))));
+    }
+
+    #[tokio::test]
+    async fn rolls_back_reorgs() {
+        let node = Arc::new(Mutex::new(Node { latest: 20, fork_at: u64::MAX, fork: 0 }));
+        let get_logs = Arc::new(AtomicUsize::new(0));
+        let index = LogIndexCache::new(
+            provider(node.clone(), get_logs),
+            Filter::new().from_block(0),
+            MemoryLogStore::new(),
+        )
+        .with_batch_size(5);
+        assert_eq!(index.sync().await.unwrap().unwrap().number, 20);
+        let logs = index.get_logs(0, 20).await.unwrap();
+        assert!(logs.iter().all(|log| log.block_hash == Some(hash(log.block_number.unwrap(), 0))));
+
+        // Blocks 13 and later are reorged: the checkpoints of blocks 14 and 19 no longer match,
+        // so the logs are rolled back to block 9 and fetched again.
+        *node.lock().unwrap() = Node { latest: 22, fork_at: 12, fork: 1 };
+        assert_eq!(
+            index.sync().await.unwrap(),
+            Some(BlockNumHash { number: 22, hash: hash(22, 1) })
+        );
+        let logs = index.get_logs(0, 22).await.unwrap();
+        assert_eq!(numbers(&logs), (0..=22).collect::<Vec<_>>());
+        for log in &logs {
+            let number = log.block_number.unwrap();
+            assert_eq!(log.block_hash, Some(hash(number, u8::from(number > 12))));
+        }
+
+        // The chain is shorter after a reorg: the checkpoint of block 22 is missing.
+        *node.lock().unwrap() = Node { latest: 16, fork_at: 12, fork: 2 };
+        assert_eq!(
+            index.sync().await.unwrap(),
+            Some(BlockNumHash { number: 16, hash: hash(16, 2) })
+        );
+        assert_eq!(numbers(&index.get_logs(0, 16).await.unwrap()), (0..=16).collect::<Vec<_>>());
+    }
+}
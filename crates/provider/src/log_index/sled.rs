@@ -0,0 +1,201 @@
+use super::{LogIndexCacheError, LogStore};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types_eth::Log;
+use async_trait::async_trait;
+use sled::{Batch, IVec, Tree};
+use std::path::Path;
+
+/// The key prefix of the checkpoints, followed by the block number.
+const CHECKPOINT: u8 = b'c';
+/// The key prefix of the logs, followed by the block number and the position of the log.
+const LOG: u8 = b'l';
+
+/// A [`LogStore`] persisted on disk in a [sled](https://docs.rs/sled) tree.
+///
+/// The checkpoints and the logs are keyed by block number in a single tree, so that appends and
+/// rollbacks are applied as one atomic batch. The logs are stored as JSON, and each append is
+/// flushed to disk before returning.
+#[derive(Clone, Debug)]
+pub struct SledLogStore {
+    tree: Tree,
+}
+
+impl SledLogStore {
+    /// The name of the tree opened by [`open`](Self::open).
+    pub const DEFAULT_TREE: &'static str = "alloy_log_index";
+
+    /// Opens the database at `path`, creating it if needed, and stores the index in its
+    /// [default tree](Self::DEFAULT_TREE).
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, LogIndexCacheError> {
+        let db = sled::open(path).map_err(LogIndexCacheError::store)?;
+        let tree = db.open_tree(Self::DEFAULT_TREE).map_err(LogIndexCacheError::store)?;
+        Ok(Self::new(tree))
+    }
+
+    /// Creates a store in `tree`, e.g. to keep the indexes of several filters in one database.
+    ///
+    /// The tree must only be used by this store.
+    pub const fn new(tree: Tree) -> Self {
+        Self { tree }
+    }
+
+    /// Returns the tree the index is stored in.
+    pub const fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    fn checkpoint(entry: sled::Result<(IVec, IVec)>) -> Result<BlockNumHash, LogIndexCacheError> {
+        let (key, value) = entry.map_err(LogIndexCacheError::store)?;
+        Ok(BlockNumHash { number: number(&key), hash: B256::from_slice(&value) })
+    }
+
+    /// Returns the keys of the entries with `prefix` after block `number`, or all of them.
+    fn keys_after(
+        &self,
+        prefix: u8,
+        number: Option<BlockNumber>,
+    ) -> Result<Vec<IVec>, LogIndexCacheError> {
+        let entries = match number.and_then(|number| number.checked_add(1)) {
+            Some(start) => self.tree.range(key(prefix, start, &[])..key(prefix + 1, 0, &[])),
+            None if number.is_some() => return Ok(Vec::new()),
+            None => self.tree.scan_prefix([prefix]),
+        };
+        entries.keys().collect::<Result<_, _>>().map_err(LogIndexCacheError::store)
+    }
+}
+
+#[async_trait]
+impl LogStore for SledLogStore {
+    async fn last_checkpoint(&self) -> Result<Option<BlockNumHash>, LogIndexCacheError> {
+        self.tree.scan_prefix([CHECKPOINT]).next_back().map(Self::checkpoint).transpose()
+    }
+
+    async fn checkpoint_before(
+        &self,
+        number: BlockNumber,
+    ) -> Result<Option<BlockNumHash>, LogIndexCacheError> {
+        let mut checkpoints =
+            self.tree.range(key(CHECKPOINT, 0, &[])..key(CHECKPOINT, number, &[]));
+        checkpoints.next_back().map(Self::checkpoint).transpose()
+    }
+
+    async fn append(
+        &self,
+        checkpoint: BlockNumHash,
+        logs: Vec<Log>,
+    ) -> Result<(), LogIndexCacheError> {
+        let mut batch = Batch::default();
+        // The logs of a block are all appended at once, so their position in the batch orders
+        // them within the block.
+        for (position, log) in (0u64..).zip(logs) {
+            let number = log.block_number.unwrap_or(checkpoint.number);
+            let value = serde_json::to_vec(&log).map_err(LogIndexCacheError::store)?;
+            batch.insert(key(LOG, number, &position.to_be_bytes()), value);
+        }
+        batch.insert(key(CHECKPOINT, checkpoint.number, &[]), checkpoint.hash.as_slice());
+        self.tree.apply_batch(batch).map_err(LogIndexCacheError::store)?;
+        self.tree.flush_async().await.map_err(LogIndexCacheError::store)?;
+        Ok(())
+    }
+
+    async fn rollback(&self, number: Option<BlockNumber>) -> Result<(), LogIndexCacheError> {
+        let mut batch = Batch::default();
+        for key in
+            self.keys_after(CHECKPOINT, number)?.into_iter().chain(self.keys_after(LOG, number)?)
+        {
+            batch.remove(key);
+        }
+        self.tree.apply_batch(batch).map_err(LogIndexCacheError::store)?;
+        self.tree.flush_async().await.map_err(LogIndexCacheError::store)?;
+        Ok(())
+    }
+
+    async fn logs(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<Log>, LogIndexCacheError> {
+        if from > to {
+            return Ok(Vec::new());
+        }
+        let end = to.checked_add(1).map_or_else(|| key(LOG + 1, 0, &[]), |end| key(LOG, end, &[]));
+        self.tree
+            .range(key(LOG, from, &[])..end)
+            .values()
+            .map(|value| {
+                let value = value.map_err(LogIndexCacheError::store)?;
+                serde_json::from_slice(&value).map_err(LogIndexCacheError::store)
+            })
+            .collect()
+    }
+}
+
+/// Returns the key of block `number` with `prefix`, followed by `suffix`.
+fn key(prefix: u8, number: BlockNumber, suffix: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(9 + suffix.len());
+    key.push(prefix);
+    key.extend_from_slice(&number.to_be_bytes());
+    key.extend_from_slice(suffix);
+    key
+}
+
+/// Returns the block number of `key`.
+fn number(key: &[u8]) -> BlockNumber {
+    BlockNumber::from_be_bytes(key[1..9].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(number: BlockNumber, index: u64) -> Log {
+        Log {
+            block_number: Some(number),
+            log_index: Some(index),
+            inner: alloy_primitives::Log::empty(),
+            ..Default::default()
+        }
+    }
+
+    fn checkpoint(number: BlockNumber) -> BlockNumHash {
+        BlockNumHash { number, hash: B256::with_last_byte(number as u8) }
+    }
+
+    fn positions(logs: &[Log]) -> Vec<(u64, u64)> {
+        logs.iter().map(|log| (log.block_number.unwrap(), log.log_index.unwrap())).collect()
+    }
+
+    #[tokio::test]
+    async fn persists_appends_and_rollbacks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SledLogStore::open(dir.path()).unwrap();
+        assert_eq!(store.last_checkpoint().await.unwrap(), None);
+
+        store.append(checkpoint(4), vec![log(2, 0), log(2, 1), log(4, 2)]).await.unwrap();
+        store.append(checkpoint(9), vec![log(5, 0), log(9, 1)]).await.unwrap();
+        store.append(checkpoint(14), vec![log(12, 0)]).await.unwrap();
+        assert_eq!(store.last_checkpoint().await.unwrap(), Some(checkpoint(14)));
+        assert_eq!(store.checkpoint_before(14).await.unwrap(), Some(checkpoint(9)));
+        assert_eq!(store.checkpoint_before(4).await.unwrap(), None);
+        assert_eq!(
+            positions(&store.logs(2, 9).await.unwrap()),
+            [(2, 0), (2, 1), (4, 2), (5, 0), (9, 1)]
+        );
+        assert!(store.logs(9, 2).await.unwrap().is_empty());
+
+        store.rollback(Some(9)).await.unwrap();
+        assert_eq!(store.last_checkpoint().await.unwrap(), Some(checkpoint(9)));
+        assert_eq!(positions(&store.logs(0, u64::MAX).await.unwrap()).len(), 5);
+
+        // The index survives reopening the database.
+        drop(store);
+        let store = SledLogStore::open(dir.path()).unwrap();
+        assert_eq!(store.last_checkpoint().await.unwrap(), Some(checkpoint(9)));
+        assert_eq!(positions(&store.logs(5, 20).await.unwrap()), [(5, 0), (9, 1)]);
+
+        store.rollback(None).await.unwrap();
+        assert_eq!(store.last_checkpoint().await.unwrap(), None);
+        assert!(store.logs(0, u64::MAX).await.unwrap().is_empty());
+    }
+}
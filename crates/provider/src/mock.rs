@@ -0,0 +1,674 @@
+//! An in-memory chain, for testing chain-following logic without a node.
+//!
+//! [`MockChainProvider`] answers the standard read methods from blocks, receipts and accounts kept
+//! in memory, and exposes helpers to push new blocks and simulate reorgs:
+//!
+//! ```
+//! # async fn example() -> alloy_transport::TransportResult<()> {
+//! use alloy_primitives::{Address, LogData, B256, U256};
+//! use alloy_provider::{mock::MockChainProvider, Provider};
+//! use alloy_rpc_types_eth::Filter;
+//!
+//! let provider = MockChainProvider::new();
+//! provider.set_balance(Address::ZERO, U256::from(100));
+//!
+//! let log = alloy_primitives::Log { address: Address::ZERO, data: LogData::empty() };
+//! provider.push_block(vec![alloy_provider::mock::receipt(B256::with_last_byte(1), vec![log])]);
+//! assert_eq!(provider.get_block_number().await?, 1);
+//!
+//! let logs = provider.get_logs(&Filter::new().from_block(0)).await?;
+//! assert_eq!(logs.len(), 1);
+//!
+//! // Replace block 1.
+//! provider.reorg(1);
+//! provider.push_block(vec![]);
+//! assert!(provider.get_logs(&Filter::new().from_block(0)).await?.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`MockTransport`] answers requests with a handler instead, for testing code against scripted
+//! responses of a node:
+//!
+//! ```
+//! # async fn example() -> alloy_transport::TransportResult<()> {
+//! use alloy_provider::{mock::MockTransport, Provider};
+//! use serde_json::json;
+//!
+//! let provider = MockTransport::new(|method, _params| match method {
+//!     "eth_blockNumber" => Ok(json!("0x2a")),
+//!     _ => Err(alloy_provider::mock::rpc_error(-32601, "method not found")),
+//! })
+//! .provider();
+//! assert_eq!(provider.get_block_number().await?, 42);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Provider, RootProvider};
+use alloy_consensus::{Receipt, ReceiptEnvelope, ReceiptWithBloom};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_json_rpc::{
+    ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest,
+};
+use alloy_primitives::{keccak256, Address, Bloom, Bytes, B256, U256, U64};
+use alloy_rpc_client::RpcClient;
+use alloy_rpc_types_eth::{
+    Block, BlockTransactions, Filter, FilterBlockOption, FilteredParams, Header, Log,
+    TransactionReceipt,
+};
+use alloy_transport::{TransportError, TransportFut};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{value::RawValue, Value};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    task,
+    time::Duration,
+};
+
+/// The default chain ID of a [`MockChainProvider`].
+pub const MOCK_CHAIN_ID: u64 = 31337;
+
+/// The interval between the timestamps of consecutive mock blocks.
+const BLOCK_TIME: u64 = 12;
+
+/// The state of an account of the mock chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockAccount {
+    /// The account balance.
+    pub balance: U256,
+    /// The account nonce.
+    pub nonce: u64,
+    /// The account code.
+    pub code: Bytes,
+}
+
+#[derive(Debug)]
+struct ChainState {
+    chain_id: u64,
+    /// The canonical blocks, indexed by number.
+    blocks: Vec<Block>,
+    /// The receipts of the canonical blocks, by transaction hash.
+    receipts: HashMap<B256, TransactionReceipt>,
+    accounts: HashMap<Address, MockAccount>,
+    /// The number of reorgs, mixed into block hashes so that replaced blocks get new hashes.
+    reorgs: u64,
+}
+
+impl ChainState {
+    fn new(chain_id: u64) -> Self {
+        let mut state = Self {
+            chain_id,
+            blocks: Vec::new(),
+            receipts: HashMap::new(),
+            accounts: HashMap::new(),
+            reorgs: 0,
+        };
+        state.push_block(Vec::new());
+        state
+    }
+
+    fn head(&self) -> &Block {
+        self.blocks.last().expect("the genesis block is never removed")
+    }
+
+    fn head_number(&self) -> u64 {
+        self.blocks.len() as u64 - 1
+    }
+
+    fn push_block(&mut self, mut receipts: Vec<TransactionReceipt>) -> Block {
+        let number = self.blocks.len() as u64;
+        let (parent_hash, timestamp) = self.blocks.last().map_or((B256::ZERO, 0), |parent| {
+            (parent.header.hash.unwrap(), parent.header.timestamp)
+        });
+
+        let mut preimage =
+            [parent_hash.as_slice(), &number.to_be_bytes(), &self.reorgs.to_be_bytes()].concat();
+        for receipt in &receipts {
+            preimage.extend_from_slice(receipt.transaction_hash.as_slice());
+        }
+        let hash = keccak256(preimage);
+        let timestamp = if number == 0 { 0 } else { timestamp + BLOCK_TIME };
+
+        let mut logs_bloom = Bloom::ZERO;
+        let mut gas_used = 0;
+        let mut log_index = 0;
+        for (index, receipt) in receipts.iter_mut().enumerate() {
+            receipt.block_hash = Some(hash);
+            receipt.block_number = Some(number);
+            receipt.transaction_index = Some(index as u64);
+            gas_used += receipt.gas_used;
+            if let Some(inner) = receipt_with_bloom_mut(&mut receipt.inner) {
+                logs_bloom.accrue_bloom(&inner.logs_bloom);
+                for log in &mut inner.receipt.logs {
+                    log.block_hash = Some(hash);
+                    log.block_number = Some(number);
+                    log.block_timestamp = Some(timestamp);
+                    log.transaction_hash = Some(receipt.transaction_hash);
+                    log.transaction_index = Some(index as u64);
+                    log.log_index = Some(log_index);
+                    log.removed = false;
+                    log_index += 1;
+                }
+            }
+        }
+
+        let block = Block {
+            header: Header {
+                hash: Some(hash),
+                parent_hash,
+                number: Some(number),
+                timestamp,
+                logs_bloom,
+                gas_used,
+                gas_limit: 30_000_000,
+                ..Default::default()
+            },
+            transactions: BlockTransactions::Hashes(
+                receipts.iter().map(|receipt| receipt.transaction_hash).collect(),
+            ),
+            ..Default::default()
+        };
+        for receipt in receipts {
+            self.receipts.insert(receipt.transaction_hash, receipt);
+        }
+        self.blocks.push(block.clone());
+        block
+    }
+
+    fn reorg(&mut self, depth: u64) -> Vec<Block> {
+        let depth = depth.min(self.head_number()) as usize;
+        let removed = self.blocks.split_off(self.blocks.len() - depth);
+        for block in &removed {
+            for hash in block.transactions.hashes() {
+                self.receipts.remove(hash);
+            }
+        }
+        self.reorgs += 1;
+        removed
+    }
+
+    fn resolve_tag(&self, tag: BlockNumberOrTag) -> Option<u64> {
+        match tag {
+            BlockNumberOrTag::Earliest => Some(0),
+            BlockNumberOrTag::Number(number) => (number <= self.head_number()).then_some(number),
+            _ => Some(self.head_number()),
+        }
+    }
+
+    fn block(&self, id: BlockId) -> Option<&Block> {
+        match id {
+            BlockId::Hash(hash) => {
+                self.blocks.iter().find(|block| block.header.hash == Some(hash.block_hash))
+            }
+            BlockId::Number(tag) => {
+                self.resolve_tag(tag).map(|number| &self.blocks[number as usize])
+            }
+        }
+    }
+
+    fn block_receipts(&self, block: &Block) -> Vec<TransactionReceipt> {
+        block.transactions.hashes().filter_map(|hash| self.receipts.get(hash).cloned()).collect()
+    }
+
+    fn logs(&self, filter: Filter) -> Result<Vec<Log>, ErrorPayload> {
+        let blocks: Vec<&Block> = match filter.block_option {
+            FilterBlockOption::AtBlockHash(hash) => {
+                vec![self.block(hash.into()).ok_or_else(|| invalid_params("unknown block"))?]
+            }
+            FilterBlockOption::Range { from_block, to_block } => {
+                let resolve = |tag: Option<BlockNumberOrTag>| match tag.unwrap_or_default() {
+                    BlockNumberOrTag::Number(number) => number,
+                    tag => self.resolve_tag(tag).unwrap_or_else(|| self.head_number()),
+                };
+                let (from, to) = (resolve(from_block), resolve(to_block));
+                // Like nodes do, a range starting past the head has no logs yet.
+                if from > self.head_number() {
+                    return Ok(Vec::new());
+                }
+                if from > to {
+                    return Err(invalid_params("invalid block range"));
+                }
+                self.blocks[from as usize..=to.min(self.head_number()) as usize].iter().collect()
+            }
+        };
+
+        let params = FilteredParams::new(Some(filter));
+        let mut logs = Vec::new();
+        for block in blocks {
+            for hash in block.transactions.hashes() {
+                let Some(receipt) = self.receipts.get(hash) else { continue };
+                logs.extend(receipt.inner.logs().iter().filter(|log| {
+                    params.filter_address(&log.address()) && params.filter_topics(log.topics())
+                }));
+            }
+        }
+        Ok(logs.into_iter().cloned().collect())
+    }
+
+    fn account(&self, address: &Address) -> MockAccount {
+        self.accounts.get(address).cloned().unwrap_or_default()
+    }
+
+    fn handle(&self, method: &str, params: Option<&RawValue>) -> Result<Value, ErrorPayload> {
+        let params: Vec<Value> = match params {
+            Some(params) => serde_json::from_str(params.get())
+                .map_err(|err| invalid_params(&err.to_string()))?,
+            None => Vec::new(),
+        };
+        match method {
+            "eth_chainId" => to_value(U64::from(self.chain_id)),
+            "eth_blockNumber" => to_value(U64::from(self.head_number())),
+            "eth_getBlockByNumber" => to_value(self.block(BlockId::Number(param(&params, 0)?))),
+            "eth_getBlockByHash" => to_value(self.block(param::<B256>(&params, 0)?.into())),
+            "eth_getBlockReceipts" => {
+                to_value(self.block(param(&params, 0)?).map(|block| self.block_receipts(block)))
+            }
+            "eth_getTransactionReceipt" => to_value(self.receipts.get(&param::<B256>(&params, 0)?)),
+            "eth_getLogs" => to_value(self.logs(param(&params, 0)?)?),
+            "eth_getBalance" | "eth_getTransactionCount" | "eth_getCode" => {
+                let account = self.account(&param(&params, 0)?);
+                let block = params.get(1).cloned().map(serde_json::from_value).transpose();
+                let block = block.map_err(|err| invalid_params(&err.to_string()))?;
+                if self.block(block.unwrap_or_default()).is_none() {
+                    return Err(rpc_error(-32000, "header not found"));
+                }
+                match method {
+                    "eth_getBalance" => to_value(account.balance),
+                    "eth_getTransactionCount" => to_value(U64::from(account.nonce)),
+                    _ => to_value(account.code),
+                }
+            }
+            _ => Err(rpc_error(
+                -32601,
+                &format!("the method {method} does not exist/is not available"),
+            )),
+        }
+    }
+}
+
+/// Returns the receipt with bloom inside of `envelope`.
+fn receipt_with_bloom_mut(
+    envelope: &mut ReceiptEnvelope<Log>,
+) -> Option<&mut ReceiptWithBloom<Log>> {
+    match envelope {
+        ReceiptEnvelope::Legacy(receipt)
+        | ReceiptEnvelope::Eip2930(receipt)
+        | ReceiptEnvelope::Eip1559(receipt)
+        | ReceiptEnvelope::Eip4844(receipt) => Some(receipt),
+        _ => None,
+    }
+}
+
+fn param<T: DeserializeOwned>(params: &[Value], index: usize) -> Result<T, ErrorPayload> {
+    let param = params.get(index).cloned().unwrap_or(Value::Null);
+    serde_json::from_value(param).map_err(|err| invalid_params(&err.to_string()))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, ErrorPayload> {
+    serde_json::to_value(value).map_err(|err| rpc_error(-32603, &err.to_string()))
+}
+
+fn invalid_params(message: &str) -> ErrorPayload {
+    rpc_error(-32602, &format!("invalid params: {message}"))
+}
+
+/// Creates an error response with the given code and message, e.g. `-32000` for the errors of
+/// execution clients.
+pub fn rpc_error(code: i64, message: &str) -> ErrorPayload {
+    ErrorPayload { code, message: message.into(), data: None }
+}
+
+/// Creates the response to `request` from the result of handling it.
+fn respond(request: &SerializedRequest, result: Result<Value, ErrorPayload>) -> Response {
+    let payload = match result {
+        Ok(value) => match serde_json::value::to_raw_value(&value) {
+            Ok(raw) => ResponsePayload::Success(raw),
+            Err(err) => ResponsePayload::Failure(rpc_error(-32603, &err.to_string())),
+        },
+        Err(err) => ResponsePayload::Failure(err),
+    };
+    Response { id: request.id().clone(), payload }
+}
+
+/// Answers the requests of `packet` with `respond`.
+fn respond_packet(
+    packet: RequestPacket,
+    respond: impl Fn(&SerializedRequest) -> Response,
+) -> ResponsePacket {
+    match packet {
+        RequestPacket::Single(request) => ResponsePacket::Single(respond(&request)),
+        RequestPacket::Batch(requests) => {
+            ResponsePacket::Batch(requests.iter().map(respond).collect())
+        }
+    }
+}
+
+/// Creates a successful EIP-1559 receipt of the transaction `transaction_hash`, emitting `logs`.
+///
+/// The block and index fields of the receipt and its logs are set by
+/// [`MockChainProvider::push_block`].
+pub fn receipt(transaction_hash: B256, logs: Vec<alloy_primitives::Log>) -> TransactionReceipt {
+    let mut logs_bloom = Bloom::ZERO;
+    let logs = logs
+        .into_iter()
+        .map(|inner| {
+            logs_bloom.accrue_log(&inner);
+            Log { inner, ..Default::default() }
+        })
+        .collect();
+    TransactionReceipt {
+        inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+            receipt: Receipt { status: true.into(), cumulative_gas_used: 21_000, logs },
+            logs_bloom,
+        }),
+        transaction_hash,
+        transaction_index: None,
+        block_hash: None,
+        block_number: None,
+        gas_used: 21_000,
+        effective_gas_price: 0,
+        blob_gas_used: None,
+        blob_gas_price: None,
+        from: Address::ZERO,
+        to: None,
+        contract_address: None,
+        #[allow(deprecated)]
+        state_root: None,
+    }
+}
+
+/// The transport of a [`MockChainProvider`], answering requests from the in-memory chain.
+///
+/// It can be used to build custom provider stacks on top of the mock chain, e.g. with
+/// [`ProviderBuilder::on_client`](crate::ProviderBuilder::on_client).
+#[derive(Clone, Debug)]
+pub struct MockChain {
+    state: Arc<RwLock<ChainState>>,
+}
+
+impl MockChain {
+    fn read(&self) -> RwLockReadGuard<'_, ChainState> {
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, ChainState> {
+        self.state.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn respond(&self, request: &SerializedRequest) -> Response {
+        respond(request, self.read().handle(request.method(), request.params()))
+    }
+}
+
+impl tower::Service<RequestPacket> for MockChain {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let response = respond_packet(request, |request| self.respond(request));
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+/// The handler of a [`MockTransport`].
+type Handler = dyn Fn(&str, Value) -> Result<Value, ErrorPayload> + Send + Sync;
+
+/// A transport answering requests with a handler, for testing code against scripted responses of
+/// a node.
+///
+/// The handler is called with the method and the params of each request, the params being `null`
+/// if the request has none, and returns the result of the request or an error response, see
+/// [`rpc_error`]. The requests of a batch are handled one by one.
+#[derive(Clone)]
+pub struct MockTransport {
+    handler: Arc<Handler>,
+    latency: Duration,
+}
+
+impl fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockTransport").field("latency", &self.latency).finish_non_exhaustive()
+    }
+}
+
+impl MockTransport {
+    /// Creates a new transport answering requests with `handler`.
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&str, Value) -> Result<Value, ErrorPayload> + Send + Sync + 'static,
+    {
+        Self { handler: Arc::new(handler), latency: Duration::ZERO }
+    }
+
+    /// Delays the responses by `latency`, e.g. to keep requests in flight concurrently.
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Returns a local client of the transport.
+    pub fn client(&self) -> RpcClient<Self> {
+        RpcClient::new(self.clone(), true)
+    }
+
+    /// Returns a provider of the transport.
+    pub fn provider(&self) -> RootProvider<Self> {
+        RootProvider::new(self.client())
+    }
+
+    fn respond(&self, request: &SerializedRequest) -> Response {
+        let params = request.params().map_or(Ok(Value::Null), |params| {
+            serde_json::from_str(params.get()).map_err(|err| invalid_params(&err.to_string()))
+        });
+        respond(request, params.and_then(|params| (self.handler)(request.method(), params)))
+    }
+}
+
+impl tower::Service<RequestPacket> for MockTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let response = respond_packet(request, |request| self.respond(request));
+        let latency = self.latency;
+        Box::pin(async move {
+            if !latency.is_zero() {
+                tokio::time::sleep(latency).await;
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// A provider backed by an in-memory chain.
+///
+/// It answers `eth_chainId`, `eth_blockNumber`, `eth_getBlockByNumber`, `eth_getBlockByHash`,
+/// `eth_getBlockReceipts`, `eth_getTransactionReceipt`, `eth_getLogs`, `eth_getBalance`,
+/// `eth_getTransactionCount` and `eth_getCode`; other methods return a "method not found" error.
+///
+/// The chain starts with an empty genesis block. Blocks only contain transaction hashes, and
+/// accounts have a single state, returned for every existing block.
+///
+/// Clones share the same chain.
+#[derive(Clone, Debug)]
+pub struct MockChainProvider {
+    root: RootProvider<MockChain>,
+    chain: MockChain,
+}
+
+impl Default for MockChainProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockChainProvider {
+    /// Creates a new mock chain with the [`MOCK_CHAIN_ID`] chain ID.
+    pub fn new() -> Self {
+        Self::with_chain_id(MOCK_CHAIN_ID)
+    }
+
+    /// Creates a new mock chain with the given chain ID.
+    pub fn with_chain_id(chain_id: u64) -> Self {
+        let chain = MockChain { state: Arc::new(RwLock::new(ChainState::new(chain_id))) };
+        let root = RootProvider::new(RpcClient::new(chain.clone(), true));
+        Self { root, chain }
+    }
+
+    /// Returns the transport answering requests from the mock chain.
+    pub const fn transport(&self) -> &MockChain {
+        &self.chain
+    }
+
+    /// Returns the latest block.
+    pub fn head(&self) -> Block {
+        self.chain.read().head().clone()
+    }
+
+    /// Appends a block containing the transactions of `receipts`, and returns it.
+    ///
+    /// The block and index fields of the receipts and their logs are set accordingly, see
+    /// [`receipt`] to create receipts.
+    pub fn push_block(&self, receipts: Vec<TransactionReceipt>) -> Block {
+        self.chain.write().push_block(receipts)
+    }
+
+    /// Appends `count` empty blocks, and returns the new latest block.
+    pub fn push_empty_blocks(&self, count: u64) -> Block {
+        let mut state = self.chain.write();
+        for _ in 0..count {
+            state.push_block(Vec::new());
+        }
+        state.head().clone()
+    }
+
+    /// Removes the `depth` latest blocks and their receipts, and returns them.
+    ///
+    /// Blocks pushed afterwards get different hashes than the removed blocks at the same height.
+    /// The genesis block is never removed.
+    pub fn reorg(&self, depth: u64) -> Vec<Block> {
+        self.chain.write().reorg(depth)
+    }
+
+    /// Returns the state of `address`.
+    pub fn account(&self, address: Address) -> MockAccount {
+        self.chain.read().account(&address)
+    }
+
+    /// Sets the state of `address`.
+    pub fn set_account(&self, address: Address, account: MockAccount) {
+        self.chain.write().accounts.insert(address, account);
+    }
+
+    /// Sets the balance of `address`.
+    pub fn set_balance(&self, address: Address, balance: U256) {
+        self.chain.write().accounts.entry(address).or_default().balance = balance;
+    }
+
+    /// Sets the nonce of `address`.
+    pub fn set_nonce(&self, address: Address, nonce: u64) {
+        self.chain.write().accounts.entry(address).or_default().nonce = nonce;
+    }
+
+    /// Sets the code of `address`.
+    pub fn set_code(&self, address: Address, code: Bytes) {
+        self.chain.write().accounts.entry(address).or_default().code = code;
+    }
+}
+
+impl Provider<MockChain> for MockChainProvider {
+    #[inline(always)]
+    fn root(&self) -> &RootProvider<MockChain> {
+        &self.root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::LogData;
+
+    fn log(address: Address, topic: B256) -> alloy_primitives::Log {
+        alloy_primitives::Log { address, data: LogData::new_unchecked(vec![topic], Bytes::new()) }
+    }
+
+    #[tokio::test]
+    async fn reads_blocks_and_accounts() {
+        let provider = MockChainProvider::with_chain_id(1);
+        assert_eq!(provider.get_chain_id().await.unwrap(), 1);
+        assert_eq!(provider.get_block_number().await.unwrap(), 0);
+
+        let address = Address::with_last_byte(1);
+        provider.set_balance(address, U256::from(10));
+        provider.set_nonce(address, 3);
+        assert_eq!(provider.get_balance(address).await.unwrap(), U256::from(10));
+        assert_eq!(provider.get_transaction_count(address).await.unwrap(), 3);
+        assert!(provider.get_code_at(address).await.unwrap().is_empty());
+
+        let pushed = provider.push_block(vec![receipt(B256::with_last_byte(1), vec![])]);
+        let block = provider.get_block_by_number(1.into(), false).await.unwrap().unwrap();
+        assert_eq!(block, pushed);
+        assert_eq!(block.header.parent_hash, provider.chain.read().blocks[0].header.hash.unwrap());
+        assert_eq!(block.header.timestamp, BLOCK_TIME);
+
+        let receipt =
+            provider.get_transaction_receipt(B256::with_last_byte(1)).await.unwrap().unwrap();
+        assert_eq!(receipt.block_hash, block.header.hash);
+        assert!(provider.get_block_by_number(2.into(), false).await.unwrap().is_none());
+
+        let err = provider.get_balance(address).number(2).await.unwrap_err();
+        assert_eq!(err.as_error_resp().unwrap().code, -32000);
+        let err = provider.get_gas_price().await.unwrap_err();
+        assert_eq!(err.as_error_resp().unwrap().code, -32601);
+    }
+
+    #[tokio::test]
+    async fn filters_logs_and_reorgs() {
+        let provider = MockChainProvider::new();
+        let (a, b) = (Address::with_last_byte(0xa), Address::with_last_byte(0xb));
+        let topic = B256::with_last_byte(0xf);
+        provider.push_block(vec![
+            receipt(B256::with_last_byte(1), vec![log(a, topic), log(b, topic)]),
+            receipt(B256::with_last_byte(2), vec![log(a, B256::ZERO)]),
+        ]);
+        let replaced =
+            provider.push_block(vec![receipt(B256::with_last_byte(3), vec![log(a, topic)])]);
+
+        let logs = provider.get_logs(&Filter::new().from_block(0).address(a)).await.unwrap();
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs.iter().map(|log| log.log_index.unwrap()).collect::<Vec<_>>(), [0, 2, 0]);
+
+        let filter = Filter::new().from_block(0).event_signature(topic);
+        assert_eq!(provider.get_logs(&filter).await.unwrap().len(), 3);
+        let filter = Filter::new().at_block_hash(replaced.header.hash.unwrap());
+        assert_eq!(provider.get_logs(&filter).await.unwrap().len(), 1);
+        let filter = Filter::new().from_block(1).to_block(10);
+        assert_eq!(provider.get_logs(&filter).await.unwrap().len(), 4);
+        assert!(provider.get_logs(&Filter::new().from_block(3)).await.unwrap().is_empty());
+
+        assert_eq!(provider.reorg(1), vec![replaced.clone()]);
+        let new = provider.push_block(vec![]);
+        assert_eq!(new.header.number, replaced.header.number);
+        assert_ne!(new.header.hash, replaced.header.hash);
+        assert!(provider.get_transaction_receipt(B256::with_last_byte(3)).await.unwrap().is_none());
+        assert_eq!(provider.get_logs(&Filter::new().from_block(0)).await.unwrap().len(), 3);
+
+        provider.reorg(u64::MAX);
+        assert_eq!(provider.get_block_number().await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,198 @@
+use super::storage_watch::heads;
+use crate::FilterPollerBuilder;
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_client::WeakClient;
+use alloy_rpc_types_eth::BlockId;
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use futures::{future, Stream, StreamExt};
+use std::time::Duration;
+
+/// A change of a field of an account, yielded by an [`AccountWatcher`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountDelta<V> {
+    /// The hash of the block the new value was read at.
+    pub block_hash: B256,
+    /// The previously yielded value, or the value at the latest block when the stream started.
+    pub old: V,
+    /// The new value.
+    pub new: V,
+}
+
+/// A builder of a stream of the changes of the balance or nonce of an account, returned by
+/// [`Provider::watch_balance`](crate::Provider::watch_balance) and
+/// [`Provider::watch_nonce`](crate::Provider::watch_nonce).
+///
+/// The field is read at the latest block, which sets the initial value without yielding it, then
+/// at the last new block of each poll of a block filter. A delta is yielded when the value differs
+/// from the last one read, so the blocks of a poll are coalesced: a reorg that replaces blocks
+/// without changing the value at the new head yields nothing, and one that does yields a single
+/// delta from the value before the reorg.
+///
+/// Errors are yielded without ending the stream, which retries at the next block.
+#[derive(Debug)]
+#[must_use = "this builder does nothing unless turned into a stream"]
+pub struct AccountWatcher<T, V> {
+    client: WeakClient<T>,
+    blocks: FilterPollerBuilder<T, B256>,
+    address: Address,
+    method: &'static str,
+    convert: fn(U256) -> V,
+}
+
+impl<T> AccountWatcher<T, U256>
+where
+    T: Transport + Clone,
+{
+    /// Creates a new watcher of the balance of `address`, reading it at the blocks of `blocks`.
+    pub(crate) const fn balance(
+        client: WeakClient<T>,
+        blocks: FilterPollerBuilder<T, B256>,
+        address: Address,
+    ) -> Self {
+        Self { client, blocks, address, method: "eth_getBalance", convert: |balance| balance }
+    }
+}
+
+impl<T> AccountWatcher<T, u64>
+where
+    T: Transport + Clone,
+{
+    /// Creates a new watcher of the nonce of `address`, reading it at the blocks of `blocks`.
+    pub(crate) fn nonce(
+        client: WeakClient<T>,
+        blocks: FilterPollerBuilder<T, B256>,
+        address: Address,
+    ) -> Self {
+        Self {
+            client,
+            blocks,
+            address,
+            method: "eth_getTransactionCount",
+            convert: |nonce| nonce.saturating_to(),
+        }
+    }
+}
+
+impl<T, V> AccountWatcher<T, V>
+where
+    T: Transport + Clone,
+    V: Copy + PartialEq + Unpin + 'static,
+{
+    /// Sets the interval between the polls of new blocks.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.blocks.set_poll_interval(poll_interval);
+        self
+    }
+
+    /// Returns the stream of the changes of the field.
+    pub fn into_stream(self) -> impl Stream<Item = TransportResult<AccountDelta<V>>> + Unpin {
+        let Self { client, blocks, address, method, convert } = self;
+        let values = heads(client.clone(), blocks).then(move |head| {
+            let client = client.clone();
+            async move {
+                let block_hash = head?;
+                let client = client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+                let value: U256 =
+                    client.request(method, (address, BlockId::hash(block_hash))).await?;
+                Ok((block_hash, convert(value)))
+            }
+        });
+
+        let deltas = values.scan(None, |last: &mut Option<V>, result| {
+            let delta = match result {
+                Ok((block_hash, new)) => match last.replace(new) {
+                    Some(old) if old != new => Some(Ok(AccountDelta { block_hash, old, new })),
+                    _ => None,
+                },
+                Err(err) => Some(Err(err)),
+            };
+            future::ready(Some(delta))
+        });
+        Box::pin(deltas.filter_map(future::ready))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Provider, RootProvider};
+    use alloy_primitives::U64;
+    use alloy_rpc_types_eth::{Block, Header};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    const ADDRESS: Address = Address::with_last_byte(0xaa);
+
+    /// A node at block 1 whose block 3 is replaced by blocks `0x13` and `0x14`, with the balance
+    /// and nonce of [`ADDRESS`] at each block.
+    fn provider() -> RootProvider<MockTransport> {
+        let polls = Arc::new(Mutex::new(vec![vec![5], vec![0x13, 0x14], vec![3], vec![2]]));
+        let transport = MockTransport::new(move |method, params| {
+            let account = |param: &Value| {
+                let hash = param.as_str().or_else(|| param["blockHash"].as_str()).unwrap();
+                match hash.parse::<B256>().unwrap()[31] {
+                    1 | 2 | 0x13 => (10u64, 0u64),
+                    3 | 0x14 => (15, 1),
+                    5 => (20, 2),
+                    block => unreachable!("{block}"),
+                }
+            };
+            let result = match method {
+                "eth_newBlockFilter" => json!("0x1"),
+                "eth_getFilterChanges" => {
+                    let hashes = polls.lock().unwrap().pop().unwrap_or_default();
+                    json!(hashes.into_iter().map(B256::with_last_byte).collect::<Vec<_>>())
+                }
+                "eth_getBlockByNumber" => {
+                    let header =
+                        Header { hash: Some(B256::with_last_byte(1)), ..Default::default() };
+                    json!(Block::<alloy_rpc_types_eth::Transaction> {
+                        header,
+                        ..Default::default()
+                    })
+                }
+                "eth_getBalance" => json!(U256::from(account(&params[1]).0)),
+                "eth_getTransactionCount" => json!(U64::from(account(&params[1]).1)),
+                method => unreachable!("{method}"),
+            };
+            Ok(result)
+        });
+        transport.provider()
+    }
+
+    fn delta<V>(block: u8, old: V, new: V) -> AccountDelta<V> {
+        AccountDelta { block_hash: B256::with_last_byte(block), old, new }
+    }
+
+    #[tokio::test]
+    async fn watches_balance() {
+        let provider = provider();
+        let watcher = provider.watch_balance(ADDRESS).await.unwrap();
+        let deltas: Vec<_> = watcher
+            .with_poll_interval(Duration::from_millis(10))
+            .into_stream()
+            .take(2)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        // The reorg of block 3 includes its transaction again in block 0x14.
+        assert_eq!(
+            deltas,
+            [delta(3, U256::from(10), U256::from(15)), delta(5, U256::from(15), U256::from(20))]
+        );
+    }
+
+    #[tokio::test]
+    async fn watches_nonce() {
+        let provider = provider();
+        let watcher = provider.watch_nonce(ADDRESS).await.unwrap();
+        let deltas: Vec<_> = watcher
+            .with_poll_interval(Duration::from_millis(10))
+            .into_stream()
+            .take(2)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(deltas, [delta(3, 0, 1), delta(5, 1, 2)]);
+    }
+}
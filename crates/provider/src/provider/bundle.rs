@@ -0,0 +1,249 @@
+use crate::Provider;
+use alloy_eips::BlockId;
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{Address, I256};
+use alloy_rpc_types_eth::{
+    simulate::{SimBlock, SimCallResult, SimulatePayload},
+    state::StateOverride,
+    BlockOverrides, Log, TransactionRequest,
+};
+use alloy_transport::{Transport, TransportResult};
+use std::{collections::BTreeMap, marker::PhantomData};
+
+/// The outcome of a bundle simulated by a [`BundleSimulator`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BundleSimulation {
+    /// The results of the transactions, in order.
+    pub calls: Vec<SimCallResult>,
+    /// The total gas used by the transactions.
+    pub gas_used: u128,
+    /// The net change of the ether balance of each account touched by a value transfer.
+    ///
+    /// This does not include the fees paid for the gas of the transactions.
+    pub balance_deltas: BTreeMap<Address, I256>,
+    /// The logs emitted by the transactions, in order, excluding the ether transfer logs.
+    pub logs: Vec<Log>,
+}
+
+impl BundleSimulation {
+    /// Accumulates the results of the transactions of a bundle.
+    pub fn from_calls(calls: Vec<SimCallResult>) -> Self {
+        let mut balance_deltas = BTreeMap::<Address, I256>::new();
+        for (from, to, value) in calls.iter().flat_map(SimCallResult::transfers) {
+            let value = I256::try_from(value).unwrap_or(I256::MAX);
+            *balance_deltas.entry(from).or_default() -= value;
+            *balance_deltas.entry(to).or_default() += value;
+        }
+        balance_deltas.retain(|_, delta| !delta.is_zero());
+
+        Self {
+            gas_used: calls.iter().map(|call| call.gas_used).sum(),
+            balance_deltas,
+            logs: calls.iter().flat_map(SimCallResult::contract_logs).cloned().collect(),
+            calls,
+        }
+    }
+
+    /// Returns `true` if all the transactions succeeded.
+    pub fn is_success(&self) -> bool {
+        self.calls.iter().all(|call| call.status)
+    }
+
+    /// Returns the index and result of the first failed transaction, if any.
+    pub fn first_failure(&self) -> Option<(usize, &SimCallResult)> {
+        self.calls.iter().enumerate().find(|(_, call)| !call.status)
+    }
+
+    /// Returns the net change of the ether balance of the given account.
+    pub fn balance_delta(&self, address: &Address) -> I256 {
+        self.balance_deltas.get(address).copied().unwrap_or_default()
+    }
+}
+
+/// Simulates a bundle of transactions, executed one after the other on top of a block, with
+/// `eth_simulateV1`.
+///
+/// The simulation accumulates the gas used, the ether balance changes and the logs of the
+/// transactions into a [`BundleSimulation`].
+///
+/// # Note
+///
+/// Not all client implementations support `eth_simulateV1`.
+#[derive(Debug)]
+#[must_use = "BundleSimulator does nothing unless simulated"]
+pub struct BundleSimulator<'a, P, T, N = Ethereum> {
+    provider: &'a P,
+    transactions: Vec<TransactionRequest>,
+    block: BlockId,
+    overrides: Option<StateOverride>,
+    block_overrides: Option<BlockOverrides>,
+    validation: bool,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<'a, P, T, N> BundleSimulator<'a, P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new simulator of an empty bundle, on top of the latest block.
+    pub fn new(provider: &'a P) -> Self {
+        Self {
+            provider,
+            transactions: Vec::new(),
+            block: BlockId::default(),
+            overrides: None,
+            block_overrides: None,
+            validation: false,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Appends a transaction to the bundle.
+    pub fn transaction(mut self, tx: TransactionRequest) -> Self {
+        self.transactions.push(tx);
+        self
+    }
+
+    /// Appends transactions to the bundle.
+    pub fn transactions(mut self, txs: impl IntoIterator<Item = TransactionRequest>) -> Self {
+        self.transactions.extend(txs);
+        self
+    }
+
+    /// Sets the block to simulate the bundle on top of.
+    pub const fn block(mut self, block: BlockId) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// Sets the state overrides to apply before executing the bundle.
+    pub fn overrides(mut self, overrides: StateOverride) -> Self {
+        self.overrides = Some(overrides);
+        self
+    }
+
+    /// Sets the overrides of the block environment the bundle is executed in.
+    pub fn block_overrides(mut self, block_overrides: BlockOverrides) -> Self {
+        self.block_overrides = Some(block_overrides);
+        self
+    }
+
+    /// Sets whether the transactions are validated, e.g. their nonce, balance and fees.
+    ///
+    /// Disabled by default.
+    pub const fn validation(mut self, validation: bool) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Simulates the bundle.
+    pub async fn simulate(&self) -> TransportResult<BundleSimulation> {
+        let payload = SimulatePayload {
+            block_state_calls: vec![SimBlock {
+                block_overrides: self.block_overrides.clone(),
+                state_overrides: self.overrides.clone(),
+                calls: self.transactions.clone(),
+            }],
+            trace_transfers: true,
+            validation: self.validation,
+            return_full_transactions: false,
+        };
+        let blocks = self.provider.simulate(&payload).block_id(self.block).await?;
+        let calls = blocks.into_iter().next().map(|block| block.calls).unwrap_or_default();
+        Ok(BundleSimulation::from_calls(calls))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use alloy_primitives::{Bytes, LogData, B256, U256};
+    use alloy_rpc_types_eth::{
+        simulate::{TRANSFER_LOG_ADDRESS, TRANSFER_LOG_TOPIC},
+        Block, Transaction,
+    };
+
+    use serde_json::json;
+
+    fn log(address: Address, topics: Vec<B256>, data: Bytes) -> Log {
+        Log {
+            inner: alloy_primitives::Log { address, data: LogData::new_unchecked(topics, data) },
+            ..Default::default()
+        }
+    }
+
+    fn transfer(from: u8, to: u8, value: u64) -> Log {
+        let topics = vec![
+            TRANSFER_LOG_TOPIC,
+            Address::with_last_byte(from).into_word(),
+            Address::with_last_byte(to).into_word(),
+        ];
+        log(TRANSFER_LOG_ADDRESS, topics, U256::from(value).to_be_bytes_vec().into())
+    }
+
+    #[tokio::test]
+    async fn simulates_bundle() {
+        let transport = MockTransport::new(|method, params| {
+            assert_eq!(method, "eth_simulateV1");
+            assert_eq!(params[0]["traceTransfers"], json!(true));
+            assert_eq!(params[0]["blockStateCalls"][0]["calls"].as_array().unwrap().len(), 2);
+            assert_eq!(params[1], json!("0x10"));
+
+            let calls = vec![
+                SimCallResult {
+                    logs: vec![
+                        transfer(1, 2, 100),
+                        log(
+                            Address::with_last_byte(2),
+                            vec![B256::with_last_byte(7)],
+                            Bytes::new(),
+                        ),
+                    ],
+                    gas_used: 30_000,
+                    status: true,
+                    ..Default::default()
+                },
+                SimCallResult {
+                    logs: vec![transfer(2, 3, 40), transfer(1, 1, 5)],
+                    gas_used: 21_000,
+                    status: true,
+                    ..Default::default()
+                },
+            ];
+            let mut block = serde_json::to_value(Block::<Transaction>::default()).unwrap();
+            block["calls"] = serde_json::to_value(calls).unwrap();
+            Ok(json!([block]))
+        });
+        let provider = transport.provider();
+
+        let simulation = BundleSimulator::new(&provider)
+            .transaction(TransactionRequest::default().to(Address::with_last_byte(2)))
+            .transaction(TransactionRequest::default().to(Address::with_last_byte(3)))
+            .block(BlockId::number(16))
+            .simulate()
+            .await
+            .unwrap();
+
+        assert!(simulation.is_success());
+        assert_eq!(simulation.first_failure(), None);
+        assert_eq!(simulation.gas_used, 51_000);
+        assert_eq!(simulation.logs.len(), 1);
+        assert_eq!(simulation.logs[0].address(), Address::with_last_byte(2));
+        assert_eq!(
+            simulation.balance_delta(&Address::with_last_byte(1)),
+            I256::try_from(-100).unwrap()
+        );
+        assert_eq!(
+            simulation.balance_delta(&Address::with_last_byte(2)),
+            I256::try_from(60).unwrap()
+        );
+        assert_eq!(
+            simulation.balance_delta(&Address::with_last_byte(3)),
+            I256::try_from(40).unwrap()
+        );
+        assert_eq!(simulation.balance_deltas.len(), 3);
+    }
+}
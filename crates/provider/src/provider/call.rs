@@ -1,31 +1,49 @@
+use crate::provider::{ExecutionRequest, Executor};
 use alloy_eips::BlockId;
 use alloy_json_rpc::RpcReturn;
 use alloy_network::Network;
+use alloy_primitives::Bytes;
 use alloy_rpc_client::{RpcCall, WeakClient};
-use alloy_rpc_types_eth::state::StateOverride;
-use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use alloy_rpc_types_eth::{state::StateOverride, BlockOverrides};
+use alloy_transport::{RpcFut, Transport, TransportErrorKind, TransportResult};
 use futures::FutureExt;
 use serde::ser::SerializeSeq;
-use std::{future::Future, marker::PhantomData, task::Poll};
+use std::{fmt, future::Future, marker::PhantomData, sync::Arc, task::Poll};
 
 type RunningFut<'req, 'state, T, N, Resp, Output, Map> =
     RpcCall<T, EthCallParams<'req, 'state, N>, Resp, Output, Map>;
 
+type ExecuteFn<N, Resp> = fn(Arc<dyn Executor<N>>, ExecutionRequest<N>) -> RpcFut<'static, Resp>;
+
 #[derive(Clone, Debug)]
 struct EthCallParams<'req, 'state, N: Network> {
     data: &'req N::TransactionRequest,
     block: Option<BlockId>,
     overrides: Option<&'state StateOverride>,
+    block_overrides: Option<&'state BlockOverrides>,
 }
 
 impl<N: Network> serde::Serialize for EthCallParams<'_, '_, N> {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let len = if self.overrides.is_some() { 3 } else { 2 };
+        let len = if self.block_overrides.is_some() {
+            4
+        } else if self.overrides.is_some() {
+            3
+        } else {
+            2
+        };
 
         let mut seq = serializer.serialize_seq(Some(len))?;
         seq.serialize_element(&self.data)?;
 
-        if let Some(overrides) = self.overrides {
+        if let Some(block_overrides) = self.block_overrides {
+            seq.serialize_element(&self.block.unwrap_or_default())?;
+            match self.overrides {
+                Some(overrides) => seq.serialize_element(overrides)?,
+                None => seq.serialize_element(&StateOverride::default())?,
+            }
+            seq.serialize_element(block_overrides)?;
+        } else if let Some(overrides) = self.overrides {
             seq.serialize_element(&self.block.unwrap_or_default())?;
             seq.serialize_element(overrides)?;
         } else if let Some(block) = self.block {
@@ -36,6 +54,25 @@ impl<N: Network> serde::Serialize for EthCallParams<'_, '_, N> {
     }
 }
 
+/// An [`Executor`] set on an [`EthCall`], with the function executing calls whose response is
+/// `Resp` with it.
+struct CallExecutor<N: Network, Resp> {
+    executor: Arc<dyn Executor<N>>,
+    execute: ExecuteFn<N, Resp>,
+}
+
+impl<N: Network, Resp> Clone for CallExecutor<N, Resp> {
+    fn clone(&self) -> Self {
+        Self { executor: self.executor.clone(), execute: self.execute }
+    }
+}
+
+impl<N: Network, Resp> fmt::Debug for CallExecutor<N, Resp> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.executor.fmt(f)
+    }
+}
+
 /// The [`EthCallFut`] future is the future type for an `eth_call` RPC request.
 #[derive(Clone, Debug)]
 #[doc(hidden)] // Not public API.
@@ -49,7 +86,6 @@ where
     Resp: RpcReturn,
     Map: Fn(Resp) -> Output;
 
-#[derive(Clone, Debug)]
 enum EthCallFutInner<'req, 'state, T, N, Resp, Output, Map>
 where
     T: Transport + Clone,
@@ -61,14 +97,73 @@ where
         client: WeakClient<T>,
         data: &'req N::TransactionRequest,
         overrides: Option<&'state StateOverride>,
+        block_overrides: Option<&'state BlockOverrides>,
         block: Option<BlockId>,
         method: &'static str,
+        executor: Option<CallExecutor<N, Resp>>,
         map: Map,
     },
     Running(RunningFut<'req, 'state, T, N, Resp, Output, Map>),
+    Executing {
+        fut: RpcFut<'static, Resp>,
+        map: Map,
+    },
     Polling,
 }
 
+impl<T, N, Resp, Output, Map> Clone for EthCallFutInner<'_, '_, T, N, Resp, Output, Map>
+where
+    T: Transport + Clone,
+    N: Network,
+    Resp: RpcReturn + Clone,
+    Output: Clone,
+    Map: Fn(Resp) -> Output + Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Self::Preparing {
+                client,
+                data,
+                overrides,
+                block_overrides,
+                block,
+                method,
+                executor,
+                map,
+            } => Self::Preparing {
+                client: client.clone(),
+                data,
+                overrides: *overrides,
+                block_overrides: *block_overrides,
+                block: *block,
+                method,
+                executor: executor.clone(),
+                map: map.clone(),
+            },
+            Self::Running(call) => Self::Running(call.clone()),
+            Self::Executing { .. } => panic!("cloned after dispatch"),
+            Self::Polling => Self::Polling,
+        }
+    }
+}
+
+impl<T, N, Resp, Output, Map> fmt::Debug for EthCallFutInner<'_, '_, T, N, Resp, Output, Map>
+where
+    T: Transport + Clone,
+    N: Network,
+    Resp: RpcReturn,
+    Map: Fn(Resp) -> Output,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Preparing { .. } => "Preparing",
+            Self::Running(..) => "Running",
+            Self::Executing { .. } => "Executing",
+            Self::Polling => "Polling",
+        })
+    }
+}
+
 impl<'req, 'state, T, N, Resp, Output, Map> EthCallFutInner<'req, 'state, T, N, Resp, Output, Map>
 where
     T: Transport + Clone,
@@ -87,19 +182,43 @@ where
         matches!(self, Self::Running(..))
     }
 
+    /// Returns `true` if the future is in the executing state.
+    const fn is_executing(&self) -> bool {
+        matches!(self, Self::Executing { .. })
+    }
+
     fn poll_preparing(&mut self, cx: &mut std::task::Context<'_>) -> Poll<TransportResult<Output>> {
-        let Self::Preparing { client, data, overrides, block, method, map } =
-            std::mem::replace(self, Self::Polling)
+        let Self::Preparing {
+            client,
+            data,
+            overrides,
+            block_overrides,
+            block,
+            method,
+            executor,
+            map,
+        } = std::mem::replace(self, Self::Polling)
         else {
             unreachable!("bad state")
         };
 
+        if let Some(CallExecutor { executor, execute }) = executor {
+            let request = ExecutionRequest {
+                tx: data.clone(),
+                block: block.unwrap_or_default(),
+                overrides: overrides.cloned(),
+                block_overrides: block_overrides.cloned(),
+            };
+            *self = Self::Executing { fut: execute(executor, request), map };
+            return self.poll_executing(cx);
+        }
+
         let client = match client.upgrade().ok_or_else(TransportErrorKind::backend_gone) {
             Ok(client) => client,
             Err(e) => return Poll::Ready(Err(e)),
         };
 
-        let params = EthCallParams { data, block, overrides };
+        let params = EthCallParams { data, block, overrides, block_overrides };
 
         let fut = client.request(method, params).map_resp(map);
 
@@ -112,6 +231,12 @@ where
 
         call.poll_unpin(cx)
     }
+
+    fn poll_executing(&mut self, cx: &mut std::task::Context<'_>) -> Poll<TransportResult<Output>> {
+        let Self::Executing { fut, map } = self else { unreachable!("bad state") };
+
+        fut.poll_unpin(cx).map_ok(map)
+    }
 }
 
 impl<'req, 'state, T, N, Resp, Output, Map> Future
@@ -134,6 +259,8 @@ where
             this.poll_preparing(cx)
         } else if this.is_running() {
             this.poll_running(cx)
+        } else if this.is_executing() {
+            this.poll_executing(cx)
         } else {
             panic!("unexpected state")
         }
@@ -157,8 +284,10 @@ where
 
     data: &'req N::TransactionRequest,
     overrides: Option<&'state StateOverride>,
+    block_overrides: Option<&'state BlockOverrides>,
     block: Option<BlockId>,
     method: &'static str,
+    executor: Option<CallExecutor<N, Resp>>,
     map: Map,
     _pd: PhantomData<fn() -> (Resp, Output)>,
 }
@@ -175,8 +304,10 @@ where
             client,
             data,
             overrides: None,
+            block_overrides: None,
             block: None,
             method: "eth_call",
+            executor: None,
             map: std::convert::identity,
             _pd: PhantomData,
         }
@@ -188,8 +319,10 @@ where
             client,
             data,
             overrides: None,
+            block_overrides: None,
             block: None,
             method: "eth_estimateGas",
+            executor: None,
             map: std::convert::identity,
             _pd: PhantomData,
         }
@@ -215,8 +348,10 @@ where
             client: self.client,
             data: self.data,
             overrides: self.overrides,
+            block_overrides: self.block_overrides,
             block: self.block,
             method: self.method,
+            executor: self.executor,
             map,
             _pd: PhantomData,
         }
@@ -228,6 +363,30 @@ where
         self
     }
 
+    /// Set the state overrides for this call, if any.
+    pub const fn overrides_opt(mut self, overrides: Option<&'state StateOverride>) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Set the block overrides for this call.
+    ///
+    /// Not all client implementations support block overrides, in particular for
+    /// `eth_estimateGas`.
+    pub const fn block_overrides(mut self, block_overrides: &'state BlockOverrides) -> Self {
+        self.block_overrides = Some(block_overrides);
+        self
+    }
+
+    /// Set the block overrides for this call, if any.
+    pub const fn block_overrides_opt(
+        mut self,
+        block_overrides: Option<&'state BlockOverrides>,
+    ) -> Self {
+        self.block_overrides = block_overrides;
+        self
+    }
+
     /// Set the block to use for this call.
     pub const fn block(mut self, block: BlockId) -> Self {
         self.block = Some(block);
@@ -235,6 +394,23 @@ where
     }
 }
 
+impl<'req, 'state, T, N, Output, Map> EthCall<'req, 'state, T, N, Bytes, Output, Map>
+where
+    T: Transport + Clone,
+    N: Network,
+    Map: Fn(Bytes) -> Output,
+{
+    /// Set the [`Executor`] executing this call, instead of the node through `eth_call`.
+    ///
+    /// The executor is given the transaction, block and overrides of this call.
+    pub fn executor(mut self, executor: Arc<dyn Executor<N>>) -> Self {
+        let execute: ExecuteFn<N, Bytes> =
+            |executor, request| Box::pin(async move { executor.execute(request).await });
+        self.executor = Some(CallExecutor { executor, execute });
+        self
+    }
+}
+
 impl<'req, 'state, T, N, Resp, Output, Map> std::future::IntoFuture
     for EthCall<'req, 'state, T, N, Resp, Output, Map>
 where
@@ -253,21 +429,25 @@ where
             client: self.client,
             data: self.data,
             overrides: self.overrides,
+            block_overrides: self.block_overrides,
             block: self.block,
             method: self.method,
+            executor: self.executor,
             map: self.map,
         })
     }
 }
 
 #[cfg(test)]
-
 mod test {
     use super::*;
+    use crate::{mock::MockTransport, Provider, RpcExecutor};
     use alloy_eips::BlockNumberOrTag;
     use alloy_network::{Ethereum, TransactionBuilder};
-    use alloy_primitives::{address, U256};
+    use alloy_primitives::{address, bytes, U256};
     use alloy_rpc_types_eth::{state::StateOverride, TransactionRequest};
+    use serde_json::json;
+    use std::future::IntoFuture;
 
     #[test]
     fn test_serialize_eth_call_params() {
@@ -288,7 +468,7 @@ mod test {
 
         // Expected: [data]
         let params: EthCallParams<'_, '_, Ethereum> =
-            EthCallParams { data: &data, block: None, overrides: None };
+            EthCallParams { data: &data, block: None, overrides: None, block_overrides: None };
 
         assert_eq!(params.data, &data);
         assert_eq!(params.block, None);
@@ -299,8 +479,12 @@ mod test {
         );
 
         // Expected: [data, block, overrides]
-        let params: EthCallParams<'_, '_, Ethereum> =
-            EthCallParams { data: &data, block: Some(block), overrides: Some(&overrides) };
+        let params: EthCallParams<'_, '_, Ethereum> = EthCallParams {
+            data: &data,
+            block: Some(block),
+            overrides: Some(&overrides),
+            block_overrides: None,
+        };
 
         assert_eq!(params.data, &data);
         assert_eq!(params.block, Some(block));
@@ -311,8 +495,12 @@ mod test {
         );
 
         // Expected: [data, (default), overrides]
-        let params: EthCallParams<'_, '_, Ethereum> =
-            EthCallParams { data: &data, block: None, overrides: Some(&overrides) };
+        let params: EthCallParams<'_, '_, Ethereum> = EthCallParams {
+            data: &data,
+            block: None,
+            overrides: Some(&overrides),
+            block_overrides: None,
+        };
 
         assert_eq!(params.data, &data);
         assert_eq!(params.block, None);
@@ -323,8 +511,12 @@ mod test {
         );
 
         // Expected: [data, block]
-        let params: EthCallParams<'_, '_, Ethereum> =
-            EthCallParams { data: &data, block: Some(block), overrides: None };
+        let params: EthCallParams<'_, '_, Ethereum> = EthCallParams {
+            data: &data,
+            block: Some(block),
+            overrides: None,
+            block_overrides: None,
+        };
 
         assert_eq!(params.data, &data);
         assert_eq!(params.block, Some(block));
@@ -333,5 +525,60 @@ mod test {
             serde_json::to_string(&params).unwrap(),
             r#"[{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","maxFeePerGas":"0x4a817c800","maxPriorityFeePerGas":"0x3b9aca00","gas":"0x5208","value":"0x64","nonce":"0x0","chainId":"0x1"},"0x1"]"#
         );
+
+        // Expected: [data, (default), (empty), block_overrides]
+        let block_overrides = BlockOverrides { number: Some(U256::from(2)), ..Default::default() };
+        let params: EthCallParams<'_, '_, Ethereum> = EthCallParams {
+            data: &data,
+            block: None,
+            overrides: None,
+            block_overrides: Some(&block_overrides),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&params).unwrap(),
+            r#"[{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","maxFeePerGas":"0x4a817c800","maxPriorityFeePerGas":"0x3b9aca00","gas":"0x5208","value":"0x64","nonce":"0x0","chainId":"0x1"},"latest",{},{"number":"0x2"}]"#
+        );
+    }
+
+    /// An executor returning the input of the transaction.
+    #[derive(Debug)]
+    struct EchoExecutor;
+
+    #[async_trait::async_trait]
+    impl Executor for EchoExecutor {
+        async fn execute(&self, request: ExecutionRequest) -> TransportResult<Bytes> {
+            assert_eq!(request.block, BlockId::number(1));
+            assert!(request.overrides.is_some());
+            Ok(request.tx.input.into_input().unwrap_or_default())
+        }
+    }
+
+    #[tokio::test]
+    async fn call_with_executor() {
+        let transport = MockTransport::new(|method, _params| {
+            assert_eq!(method, "eth_call");
+            Ok(json!(bytes!("beef")))
+        });
+        let provider = transport.provider();
+        let tx = TransactionRequest::default().input(bytes!("0102").into());
+
+        assert_eq!(provider.call(&tx).await.unwrap(), bytes!("beef"));
+
+        let executor = Arc::new(RpcExecutor::new(provider.weak_client()));
+        let fut = provider.call(&tx).executor(executor).into_future();
+        assert_eq!(fut.clone().await.unwrap(), bytes!("beef"));
+        assert_eq!(fut.await.unwrap(), bytes!("beef"));
+
+        let overrides = StateOverride::default();
+        let len = provider
+            .call(&tx)
+            .block(BlockId::number(1))
+            .overrides(&overrides)
+            .executor(Arc::new(EchoExecutor))
+            .map_resp(|output| output.len())
+            .await
+            .unwrap();
+        assert_eq!(len, 2);
     }
 }
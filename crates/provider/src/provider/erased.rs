@@ -0,0 +1,66 @@
+use crate::Provider;
+use alloy_network::Ethereum;
+use alloy_transport::BoxTransport;
+use std::sync::Arc;
+
+/// A type-erased [`Provider`] over a [`BoxTransport`], shared behind an [`Arc`].
+///
+/// Providers are generic over their transport, network, and the fillers and layers they are built
+/// with. Erasing all but the network lets applications store providers built in different ways in
+/// the same field, without making the types holding them generic.
+///
+/// Box the transport of a provider to erase it with [`Provider::erased`], e.g. by building it with
+/// [`ProviderBuilder::on_builtin`], or on a [boxed](alloy_rpc_client::RpcClient::boxed) client.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy_provider::{DynProvider, Provider, ProviderBuilder};
+/// use alloy_rpc_client::RpcClient;
+///
+/// struct App {
+///     provider: DynProvider,
+/// }
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider =
+///     ProviderBuilder::new().with_recommended_fillers().on_builtin("ws://localhost:8546").await?;
+/// let app = App { provider: provider.erased() };
+///
+/// let client = RpcClient::new_http("http://localhost:8545".parse()?).boxed();
+/// let app = App { provider: ProviderBuilder::new().on_client(client).erased() };
+/// let block_number = app.provider.get_block_number().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`ProviderBuilder::on_builtin`]: crate::ProviderBuilder::on_builtin
+pub type DynProvider<N = Ethereum> = Arc<dyn Provider<BoxTransport, N>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, ProviderBuilder, RootProvider};
+    use alloy_rpc_client::RpcClient;
+    use serde_json::json;
+
+    fn client() -> RpcClient<BoxTransport> {
+        let transport = MockTransport::new(|method, _params| {
+            assert_eq!(method, "eth_blockNumber");
+            Ok(json!("0x2a"))
+        });
+        transport.client().boxed()
+    }
+
+    #[tokio::test]
+    async fn erased_providers() {
+        let providers: Vec<DynProvider> = vec![
+            RootProvider::new(client()).erased(),
+            ProviderBuilder::new().with_recommended_fillers().on_client(client()).erased(),
+        ];
+        for provider in &providers {
+            assert_eq!(provider.get_block_number().await.unwrap(), 42);
+            assert_eq!(provider.clone().get_block_number().await.unwrap(), 42);
+        }
+    }
+}
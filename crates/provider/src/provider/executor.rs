@@ -0,0 +1,68 @@
+use crate::EthCall;
+use alloy_eips::BlockId;
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::Bytes;
+use alloy_rpc_client::WeakClient;
+use alloy_rpc_types_eth::{state::StateOverride, BlockOverrides};
+use alloy_transport::{Transport, TransportResult};
+use std::fmt::{self, Debug};
+
+/// A transaction to execute with an [`Executor`], along with the state to execute it at.
+#[derive(Clone, Debug)]
+pub struct ExecutionRequest<N: Network = Ethereum> {
+    /// The transaction to execute.
+    pub tx: N::TransactionRequest,
+    /// The block whose state the transaction is executed at.
+    pub block: BlockId,
+    /// The state overrides to apply before executing the transaction, if any.
+    pub overrides: Option<StateOverride>,
+    /// The block overrides to apply before executing the transaction, if any.
+    pub block_overrides: Option<BlockOverrides>,
+}
+
+/// Executes transactions without committing them, returning their output.
+///
+/// This is the operation behind [`EthCall`]. By default, calls are executed by the node through
+/// `eth_call`, as done by [`RpcExecutor`]. Another executor, e.g. a local EVM, can be plugged into
+/// a call with [`EthCall::executor`], to simulate transactions without a round-trip to the node.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait Executor<N: Network = Ethereum>: Debug + Send + Sync {
+    /// Executes the transaction at the requested state, returning its output.
+    async fn execute(&self, request: ExecutionRequest<N>) -> TransportResult<Bytes>;
+}
+
+/// An [`Executor`] delegating to the `eth_call` method of the node.
+#[derive(Clone)]
+pub struct RpcExecutor<T> {
+    client: WeakClient<T>,
+}
+
+impl<T> fmt::Debug for RpcExecutor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcExecutor").finish_non_exhaustive()
+    }
+}
+
+impl<T> RpcExecutor<T> {
+    /// Creates a new executor calling the node behind the given client.
+    pub const fn new(client: WeakClient<T>) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<T, N> Executor<N> for RpcExecutor<T>
+where
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn execute(&self, request: ExecutionRequest<N>) -> TransportResult<Bytes> {
+        EthCall::<T, N, Bytes>::new(self.client.clone(), &request.tx)
+            .block(request.block)
+            .overrides_opt(request.overrides.as_ref())
+            .block_overrides_opt(request.block_overrides.as_ref())
+            .await
+    }
+}
@@ -0,0 +1,153 @@
+use alloy_primitives::U64;
+use alloy_rpc_client::{PollerBuilder, WeakClient};
+use alloy_rpc_types_eth::{BlockNumberOrTag, FeeHistory};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use async_stream::stream;
+use futures::{stream::BoxStream, Stream, StreamExt};
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A stream of rolling fee histories, returned by
+/// [`Provider::fee_history_stream`](crate::Provider::fee_history_stream).
+///
+/// The stream yields the fee history of the last `window` blocks, then an updated one on each new
+/// head. Only the new blocks, and the previous head in case it was reorged, are requested with
+/// `eth_feeHistory`, and merged with [`FeeHistory::extend`]. Derived series, e.g.
+/// [`FeeHistory::base_fee_trend`] or [`FeeHistory::reward_percentile`], are computed on the yielded
+/// fee histories.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::StreamExt;
+///
+/// let mut stream = provider.fee_history_stream(20, &[25.0, 50.0, 75.0]).await?;
+/// while let Some(fees) = stream.next().await {
+///     let fees = fees?;
+///     println!("base fee trend: {:?}", fees.base_fee_trend());
+///     println!("median priority fees: {:?}", fees.reward_percentile(1));
+///     println!("blob gas used: {:?}", fees.average_blob_gas_used_ratio());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "streams do nothing unless polled"]
+pub struct FeeHistoryStream {
+    inner: BoxStream<'static, TransportResult<FeeHistory>>,
+}
+
+impl fmt::Debug for FeeHistoryStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeeHistoryStream").finish_non_exhaustive()
+    }
+}
+
+impl FeeHistoryStream {
+    /// Creates a new stream starting from the given fee history, polling new heads with `client`.
+    pub(crate) fn new<T: Transport + Clone>(
+        client: WeakClient<T>,
+        mut history: FeeHistory,
+        window: u64,
+        percentiles: Vec<f64>,
+    ) -> Self {
+        let heads = PollerBuilder::<T, (), U64>::new(client.clone(), "eth_blockNumber", ());
+        let inner = stream! {
+            yield Ok(history.clone());
+
+            let mut heads = heads.into_stream();
+            while let Some(head) = heads.next().await {
+                let head = head.to::<u64>();
+                let newest = history.newest_block();
+                if newest.is_some_and(|newest| head <= newest) {
+                    continue;
+                }
+
+                // Refetch the previous head, in case it was reorged.
+                let from = newest.unwrap_or(head).max(head.saturating_sub(window.saturating_sub(1)));
+                let Some(client) = client.upgrade() else {
+                    yield Err(TransportErrorKind::backend_gone());
+                    break;
+                };
+                let params = (U64::from(head - from + 1), BlockNumberOrTag::Number(head), &percentiles);
+                match client.request::<_, FeeHistory>("eth_feeHistory", params).await {
+                    Ok(newer) => {
+                        history.extend(newer, window as usize);
+                        yield Ok(history.clone());
+                    }
+                    Err(err) => yield Err(err),
+                }
+            }
+        };
+        Self { inner: inner.boxed() }
+    }
+}
+
+impl Stream for FeeHistoryStream {
+    type Item = TransportResult<FeeHistory>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock::MockTransport, Provider, RootProvider};
+    use futures::StreamExt;
+    use serde_json::json;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[tokio::test]
+    async fn rolls_fee_history() {
+        let head = Arc::new(Mutex::new(10u64));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let (node_head, recorded) = (head.clone(), requests.clone());
+        let transport = MockTransport::new(move |method, params| {
+            let head = *node_head.lock().unwrap();
+            let result = match method {
+                "eth_blockNumber" => json!(format!("{head:#x}")),
+                "eth_feeHistory" => {
+                    let count = u64::from_str_radix(&params[0].as_str().unwrap()[2..], 16).unwrap();
+                    let newest = match params[1].as_str().unwrap() {
+                        "latest" => head,
+                        newest => u64::from_str_radix(&newest[2..], 16).unwrap(),
+                    };
+                    recorded.lock().unwrap().push((count, newest));
+                    // The base fee of each block is its number, and its reward twice its number.
+                    let oldest = newest + 1 - count;
+                    json!({
+                        "oldestBlock": format!("{oldest:#x}"),
+                        "baseFeePerGas": (oldest..=newest + 1).map(|n| format!("{n:#x}")).collect::<Vec<_>>(),
+                        "gasUsedRatio": vec![0.5; count as usize],
+                        "reward": (oldest..=newest).map(|n| [format!("{:#x}", 2 * n)]).collect::<Vec<_>>(),
+                    })
+                }
+                method => unreachable!("{method}"),
+            };
+            Ok(result)
+        });
+        let client = transport.client().with_poll_interval(Duration::from_millis(10));
+        let provider = RootProvider::<_>::new(client);
+
+        let mut stream = provider.fee_history_stream(4, &[50.0]).await.unwrap();
+        let fees = stream.next().await.unwrap().unwrap();
+        assert_eq!(fees.oldest_block, 7);
+        assert_eq!(fees.base_fee_per_gas, [7, 8, 9, 10, 11]);
+
+        *head.lock().unwrap() = 12;
+        let fees = stream.next().await.unwrap().unwrap();
+        assert_eq!(fees.oldest_block, 9);
+        assert_eq!(fees.base_fee_per_gas, [9, 10, 11, 12, 13]);
+        assert_eq!(fees.reward_percentile(0), Some(vec![18, 20, 22, 24]));
+
+        // Only the new blocks, and the previous head, were requested.
+        assert_eq!(*requests.lock().unwrap(), [(4, 10), (3, 12)]);
+    }
+}
@@ -0,0 +1,164 @@
+use alloy_json_rpc::RpcReturn;
+use alloy_primitives::U256;
+use alloy_rpc_client::WeakClient;
+use alloy_rpc_types_eth::Log;
+use alloy_transport::{utils::Spawnable, Transport, TransportErrorKind, TransportResult};
+use std::fmt;
+
+/// A filter installed on the node, uninstalled with `eth_uninstallFilter` when dropped.
+///
+/// Node-side filters are only removed when they time out, so filters that are not uninstalled
+/// explicitly accumulate on the node in long-running services. The guard removes the filter in a
+/// background task when dropped, or with [`uninstall`](Self::uninstall) to wait for the result.
+///
+/// Returned by [`Provider::install_filter`](crate::Provider::install_filter), any filter ID can be
+/// guarded with [`new`](Self::new).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_rpc_types_eth::{Filter, Log};
+///
+/// let filter = provider.install_filter(&Filter::new().select(0u64..)).await?;
+/// let logs = filter.logs().await?;
+/// let new_logs: Vec<Log> = filter.changes().await?;
+/// // The filter is uninstalled here.
+/// drop(filter);
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "the filter is uninstalled when the guard is dropped"]
+pub struct FilterGuard<T: Transport + Clone> {
+    client: WeakClient<T>,
+    id: Option<U256>,
+}
+
+impl<T: Transport + Clone> fmt::Debug for FilterGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilterGuard").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+impl<T: Transport + Clone> FilterGuard<T> {
+    /// Guards the filter with the given ID, installed with the given client.
+    pub const fn new(client: WeakClient<T>, id: U256) -> Self {
+        Self { client, id: Some(id) }
+    }
+
+    /// Returns the ID of the filter.
+    pub fn id(&self) -> U256 {
+        self.id.expect("filter is installed until the guard is consumed")
+    }
+
+    /// Returns the values added since the last poll, with `eth_getFilterChanges`.
+    pub async fn changes<R: RpcReturn>(&self) -> TransportResult<Vec<R>> {
+        let client = self.client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        client.request("eth_getFilterChanges", (self.id(),)).await
+    }
+
+    /// Returns all the logs matching the filter, with `eth_getFilterLogs`.
+    pub async fn logs(&self) -> TransportResult<Vec<Log>> {
+        let client = self.client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        client.request("eth_getFilterLogs", (self.id(),)).await
+    }
+
+    /// Uninstalls the filter, returning whether the node found it.
+    pub async fn uninstall(mut self) -> TransportResult<bool> {
+        let id = self.id();
+        self.id = None;
+        let client = self.client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        client.request("eth_uninstallFilter", (id,)).await
+    }
+
+    /// Releases the filter without uninstalling it, returning its ID.
+    pub fn into_id(mut self) -> U256 {
+        let id = self.id();
+        self.id = None;
+        id
+    }
+}
+
+impl<T: Transport + Clone> Drop for FilterGuard<T> {
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else { return };
+        let Some(client) = self.client.upgrade() else { return };
+
+        // Spawning requires a runtime, which is gone if the guard is dropped on shutdown.
+        #[cfg(not(target_arch = "wasm32"))]
+        if tokio::runtime::Handle::try_current().is_err() {
+            debug!(%id, "no runtime to uninstall the filter");
+            return;
+        }
+
+        async move {
+            if let Err(err) = client.request::<_, bool>("eth_uninstallFilter", (id,)).await {
+                debug!(%id, %err, "failed to uninstall the filter");
+            }
+        }
+        .spawn_task();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock::MockTransport, Provider, RootProvider};
+    use alloy_primitives::U256;
+    use alloy_rpc_types_eth::{Filter, Log};
+    use serde_json::{json, Value};
+    use tokio::sync::mpsc;
+
+    fn provider() -> (RootProvider<MockTransport>, mpsc::UnboundedReceiver<(String, Value)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let transport = MockTransport::new(move |method, params| {
+            let result = match method {
+                "eth_newFilter" => json!("0x7"),
+                "eth_getFilterLogs" | "eth_getFilterChanges" => {
+                    json!([Log::<alloy_primitives::LogData>::default()])
+                }
+                "eth_uninstallFilter" => json!(true),
+                method => unreachable!("{method}"),
+            };
+            tx.send((method.to_string(), params)).unwrap();
+            Ok(result)
+        });
+        (transport.provider(), rx)
+    }
+
+    #[tokio::test]
+    async fn filter_lifecycle() {
+        let (provider, mut requests) = provider();
+
+        assert_eq!(provider.get_filter_logs(U256::from(7)).await.unwrap().len(), 1);
+        assert_eq!(requests.recv().await.unwrap(), ("eth_getFilterLogs".into(), json!(["0x7"])));
+        assert!(provider.uninstall_filter(U256::from(7)).await.unwrap());
+        assert_eq!(requests.recv().await.unwrap(), ("eth_uninstallFilter".into(), json!(["0x7"])));
+    }
+
+    #[tokio::test]
+    async fn guard_uninstalls_on_drop() {
+        let (provider, mut requests) = provider();
+
+        let filter = provider.install_filter(&Filter::new()).await.unwrap();
+        assert_eq!(requests.recv().await.unwrap().0, "eth_newFilter");
+        assert_eq!(filter.id(), U256::from(7));
+        assert_eq!(filter.logs().await.unwrap().len(), 1);
+        assert_eq!(requests.recv().await.unwrap().0, "eth_getFilterLogs");
+        assert_eq!(filter.changes::<Log>().await.unwrap().len(), 1);
+        assert_eq!(requests.recv().await.unwrap().0, "eth_getFilterChanges");
+
+        drop(filter);
+        assert_eq!(requests.recv().await.unwrap(), ("eth_uninstallFilter".into(), json!(["0x7"])));
+
+        let filter = provider.install_filter(&Filter::new()).await.unwrap();
+        requests.recv().await.unwrap();
+        assert!(filter.uninstall().await.unwrap());
+        assert_eq!(requests.recv().await.unwrap().0, "eth_uninstallFilter");
+
+        let filter = provider.install_filter(&Filter::new()).await.unwrap();
+        requests.recv().await.unwrap();
+        assert_eq!(filter.into_id(), U256::from(7));
+        tokio::task::yield_now().await;
+        assert!(requests.try_recv().is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use super::FeeHistoryStream;
+use crate::utils::{self, Eip1559Estimation, EstimatorFunction};
+use alloy_json_rpc::RpcError;
+use alloy_rpc_types_eth::FeeHistory;
+use alloy_transport::TransportResult;
+use futures::{ready, Stream, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The default minimum relative change of a fee for a [`GasTicker`] to yield a new quote.
+pub const DEFAULT_GAS_TICKER_HYSTERESIS: f64 = 0.1;
+
+/// Suggested fees for the next block, yielded by a [`GasTicker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasQuote {
+    /// The number of the newest block of the fee history the fees are suggested from.
+    pub block_number: u64,
+    /// The suggested max fee per gas.
+    pub max_fee_per_gas: u128,
+    /// The suggested max priority fee per gas.
+    pub max_priority_fee_per_gas: u128,
+    /// The suggested max fee per blob gas, or `None` if the next block is pre-EIP-4844.
+    pub max_fee_per_blob_gas: Option<u128>,
+}
+
+impl From<GasQuote> for Eip1559Estimation {
+    fn from(quote: GasQuote) -> Self {
+        Self {
+            max_fee_per_gas: quote.max_fee_per_gas,
+            max_priority_fee_per_gas: quote.max_priority_fee_per_gas,
+        }
+    }
+}
+
+/// A stream of suggested fees, returned by [`Provider::gas_ticker`](crate::Provider::gas_ticker).
+///
+/// On each new block of its [`FeeHistoryStream`], the ticker suggests the EIP-1559 fees with its
+/// [estimator](Self::with_estimator), from the base fee of the newest block and the rewards of the
+/// history, and a max fee per blob gas of twice the blob base fee of the next block.
+///
+/// The first quote is always yielded. A new quote is then only yielded when one of its fees moved
+/// by more than the [hysteresis](Self::with_hysteresis) relative to the last yielded quote, so that
+/// consumers don't reprice on every small fluctuation. A quote converts into an
+/// [`Eip1559Estimation`], and fees set on a transaction request are kept by the
+/// [`GasFiller`](crate::fillers::GasFiller).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::StreamExt;
+///
+/// let mut ticker = provider.gas_ticker().await?.with_hysteresis(0.05);
+/// while let Some(quote) = ticker.next().await {
+///     let quote = quote?;
+///     println!("block {}: max fee {}", quote.block_number, quote.max_fee_per_gas);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct GasTicker {
+    history: FeeHistoryStream,
+    estimator: EstimatorFunction,
+    hysteresis: f64,
+    last: Option<GasQuote>,
+}
+
+impl GasTicker {
+    /// Creates a new ticker suggesting fees from the fee histories of `history`.
+    ///
+    /// The rewards of the first requested percentile are passed to the estimator.
+    pub fn new(history: FeeHistoryStream) -> Self {
+        Self {
+            history,
+            estimator: utils::eip1559_default_estimator,
+            hysteresis: DEFAULT_GAS_TICKER_HYSTERESIS,
+            last: None,
+        }
+    }
+
+    /// Sets the estimator of the EIP-1559 fees.
+    pub const fn with_estimator(mut self, estimator: EstimatorFunction) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// Sets the minimum relative change of a fee to yield a new quote, e.g. `0.1` for 10%.
+    ///
+    /// With `0.0`, a quote is yielded whenever it differs from the last one.
+    pub const fn with_hysteresis(mut self, hysteresis: f64) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Returns the last yielded quote.
+    pub const fn last(&self) -> Option<GasQuote> {
+        self.last
+    }
+
+    fn quote(&self, history: &FeeHistory) -> TransportResult<GasQuote> {
+        let block_number = history.newest_block().ok_or(RpcError::NullResp)?;
+        let base_fee_per_gas = history
+            .latest_block_base_fee()
+            .filter(|fee| *fee != 0)
+            .ok_or(RpcError::UnsupportedFeature("eip1559"))?;
+        let estimate = (self.estimator)(base_fee_per_gas, history.reward.as_deref().unwrap_or(&[]));
+        Ok(GasQuote {
+            block_number,
+            max_fee_per_gas: estimate.max_fee_per_gas,
+            max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: history
+                .next_block_blob_base_fee()
+                .map(|fee| fee.saturating_mul(utils::EIP1559_BASE_FEE_MULTIPLIER)),
+        })
+    }
+
+    /// Returns whether `quote` moved enough from the last yielded quote to be yielded.
+    fn moved(&self, quote: &GasQuote) -> bool {
+        let Some(last) = self.last else { return true };
+        let moved = |old: u128, new: u128| old.abs_diff(new) as f64 > old as f64 * self.hysteresis;
+        moved(last.max_fee_per_gas, quote.max_fee_per_gas)
+            || moved(last.max_priority_fee_per_gas, quote.max_priority_fee_per_gas)
+            || match (last.max_fee_per_blob_gas, quote.max_fee_per_blob_gas) {
+                (Some(old), Some(new)) => moved(old, new),
+                (old, new) => old != new,
+            }
+    }
+}
+
+impl Stream for GasTicker {
+    type Item = TransportResult<GasQuote>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let Some(history) = ready!(self.history.poll_next_unpin(cx)) else {
+                return Poll::Ready(None);
+            };
+            match history.and_then(|history| self.quote(&history)) {
+                Ok(quote) if self.moved(&quote) => {
+                    self.last = Some(quote);
+                    return Poll::Ready(Some(Ok(quote)));
+                }
+                Ok(_) => {}
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Provider, RootProvider};
+    use serde_json::json;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// A node at block 10, whose head advances on each poll up to block 12.
+    fn node() -> RootProvider<MockTransport> {
+        let head = Arc::new(Mutex::new(10u64));
+        let transport = MockTransport::new(move |method, params| {
+            let result = match method {
+                "eth_blockNumber" => {
+                    let mut head = head.lock().unwrap();
+                    *head = (*head + 1).min(12);
+                    json!(format!("{:#x}", *head))
+                }
+                "eth_feeHistory" => {
+                    let count = u64::from_str_radix(&params[0].as_str().unwrap()[2..], 16).unwrap();
+                    let newest = match params[1].as_str().unwrap() {
+                        "latest" => 10,
+                        newest => u64::from_str_radix(&newest[2..], 16).unwrap(),
+                    };
+                    // The base fee rises by 5% at block 11, and by 20% at block 12.
+                    let base_fee = |n: u64| match n {
+                        ..=10 => 1000,
+                        11 => 1050,
+                        _ => 1200,
+                    };
+                    let oldest = newest + 1 - count;
+                    json!({
+                        "oldestBlock": format!("{oldest:#x}"),
+                        "baseFeePerGas": (oldest..=newest + 1).map(|n| format!("{:#x}", base_fee(n))).collect::<Vec<_>>(),
+                        "baseFeePerBlobGas": vec!["0x1"; count as usize + 1],
+                        "gasUsedRatio": vec![0.5; count as usize],
+                        "blobGasUsedRatio": vec![0.5; count as usize],
+                        "reward": vec![["0xa"]; count as usize],
+                    })
+                }
+                method => unreachable!("{method}"),
+            };
+            Ok(result)
+        });
+        let client = transport.client().with_poll_interval(Duration::from_millis(10));
+        RootProvider::new(client)
+    }
+
+    fn quote(block_number: u64, base_fee: u128) -> GasQuote {
+        GasQuote {
+            block_number,
+            max_fee_per_gas: 2 * base_fee + 10,
+            max_priority_fee_per_gas: 10,
+            max_fee_per_blob_gas: Some(2),
+        }
+    }
+
+    #[tokio::test]
+    async fn ticks_with_hysteresis() {
+        let provider = node();
+        let mut ticker = provider.gas_ticker().await.unwrap();
+        assert_eq!(ticker.next().await.unwrap().unwrap(), quote(10, 1000));
+        // The quote of block 11 moved by less than 10%.
+        assert_eq!(ticker.next().await.unwrap().unwrap(), quote(12, 1200));
+        assert_eq!(ticker.last(), Some(quote(12, 1200)));
+
+        let provider = node();
+        let ticker = provider.gas_ticker().await.unwrap().with_hysteresis(0.0);
+        let quotes: Vec<_> = ticker.take(3).map(Result::unwrap).collect().await;
+        assert_eq!(quotes, [quote(10, 1000), quote(11, 1050), quote(12, 1200)]);
+    }
+}
@@ -1,12 +1,50 @@
+mod account_watch;
+pub use account_watch::{AccountDelta, AccountWatcher};
+
+mod bundle;
+pub use bundle::{BundleSimulation, BundleSimulator};
+
 mod call;
 pub use call::EthCall;
 
+mod erased;
+pub use erased::DynProvider;
+
+mod executor;
+pub use executor::{ExecutionRequest, Executor, RpcExecutor};
+
+mod fee_history;
+pub use fee_history::FeeHistoryStream;
+
+mod filter;
+pub use filter::FilterGuard;
+
+mod gas_ticker;
+pub use gas_ticker::{GasQuote, GasTicker, DEFAULT_GAS_TICKER_HYSTERESIS};
+
+mod receipt;
+pub use receipt::WaitForReceiptError;
+
 mod root;
 pub use root::{builder, RootProvider};
 
 mod sendable;
 pub use sendable::SendableTx;
 
+mod sender_nonce;
+
+mod snapshot;
+pub use snapshot::{StateSnapshot, StateSnapshotAt};
+
+mod storage_watch;
+pub use storage_watch::{StorageChange, StorageWatcher};
+
+mod stream;
+pub use stream::PendingTransactionStream;
+
+mod timestamp;
+pub use timestamp::TimestampDirection;
+
 mod r#trait;
 pub use r#trait::{FilterPollerBuilder, Provider};
 
@@ -15,3 +53,6 @@ pub use wallet::WalletProvider;
 
 mod with_block;
 pub use with_block::RpcWithBlock;
+
+mod withdrawals;
+pub use withdrawals::{BlockWithdrawals, WithdrawalsStream};
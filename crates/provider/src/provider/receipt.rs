@@ -0,0 +1,167 @@
+use crate::Provider;
+use alloy_json_rpc::RpcError;
+use alloy_network::Network;
+use alloy_primitives::TxHash;
+use alloy_transport::{Transport, TransportError};
+use std::{fmt, time::Duration};
+
+/// The maximum multiple of the poll interval the delay between two receipt requests backs off to.
+const MAX_BACKOFF: u32 = 8;
+
+/// An error of [`Provider::wait_for_receipt`].
+#[derive(Debug)]
+pub enum WaitForReceiptError {
+    /// The transaction was not included before the timeout.
+    Timeout {
+        /// The hash of the transaction.
+        hash: TxHash,
+        /// How long the receipt was waited for.
+        timeout: Duration,
+    },
+    /// A receipt request failed with a non-transient error.
+    Transport(TransportError),
+}
+
+impl WaitForReceiptError {
+    /// Returns `true` if the transaction was not included before the timeout.
+    pub const fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout { .. })
+    }
+}
+
+impl fmt::Display for WaitForReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout { hash, timeout } => {
+                write!(f, "transaction {hash} was not included within {timeout:?}")
+            }
+            Self::Transport(err) => write!(f, "receipt request failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for WaitForReceiptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Timeout { .. } => None,
+            Self::Transport(err) => Some(err),
+        }
+    }
+}
+
+impl From<TransportError> for WaitForReceiptError {
+    fn from(err: TransportError) -> Self {
+        Self::Transport(err)
+    }
+}
+
+/// Polls the receipt of the transaction `hash` until it is available, backing off on missing
+/// receipts and transient errors.
+pub(crate) async fn wait_for_receipt<P, T, N>(
+    provider: &P,
+    hash: TxHash,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<N::ReceiptResponse, WaitForReceiptError>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    let poll = async {
+        let mut backoff = 1;
+        loop {
+            match provider.get_transaction_receipt(hash).await {
+                Ok(Some(receipt)) => return Ok(receipt),
+                // Behind a load balancer, the node answering may lag behind the one the
+                // transaction was sent to, so the receipt may only show up after a few nulls.
+                Ok(None) => {}
+                Err(err) if is_transient(&err) => {
+                    trace!(%hash, %err, "transient error fetching the receipt");
+                }
+                Err(err) => return Err(WaitForReceiptError::Transport(err)),
+            }
+            tokio::time::sleep(poll_interval * backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    };
+    tokio::time::timeout(timeout, poll)
+        .await
+        .unwrap_or(Err(WaitForReceiptError::Timeout { hash, timeout }))
+}
+
+/// Returns `true` if the receipt request may succeed when retried.
+fn is_transient(err: &TransportError) -> bool {
+    match err {
+        RpcError::NullResp => true,
+        RpcError::Transport(kind) => kind.is_retry_err(),
+        // Returned by geth while the transaction index is built, e.g. after a restart.
+        RpcError::ErrorResp(payload) => payload.message.contains("indexing is in progress"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{self, rpc_error, MockTransport},
+        Provider, RootProvider,
+    };
+    use alloy_primitives::B256;
+    use serde_json::{json, Value};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    /// A provider answering receipt requests with an indexing error, then nulls, then the receipt.
+    fn provider(receipt_after: usize) -> (RootProvider<MockTransport>, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let transport = MockTransport::new(move |method, _params| {
+            assert_eq!(method, "eth_getTransactionReceipt");
+            match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(rpc_error(-32000, "transaction indexing is in progress")),
+                n if n < receipt_after => Ok(Value::Null),
+                _ => Ok(json!(mock::receipt(B256::with_last_byte(1), vec![]))),
+            }
+        });
+        (transport.provider(), requests)
+    }
+
+    #[tokio::test]
+    async fn waits_through_transient_nulls() {
+        let (provider, requests) = provider(3);
+        let receipt = provider
+            .wait_for_receipt(
+                B256::with_last_byte(1),
+                Duration::from_millis(1),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(receipt.transaction_hash, B256::with_last_byte(1));
+        assert_eq!(requests.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        let (provider, _) = provider(usize::MAX);
+        let err = provider
+            .wait_for_receipt(
+                B256::with_last_byte(1),
+                Duration::from_millis(1),
+                Duration::from_millis(50),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+        assert_eq!(
+            err.to_string(),
+            format!("transaction {} was not included within 50ms", B256::with_last_byte(1))
+        );
+    }
+}
@@ -92,6 +92,22 @@ impl<T: Transport + Clone, N: Network> RootProvider<T, N> {
         self.pubsub_frontend()?.get_subscription(id).await.map(Subscription::from)
     }
 
+    /// Gets the subscription corresponding to the given RPC subscription ID, buffering up to
+    /// `capacity` notifications for slow receivers.
+    ///
+    /// See [`PubSubFrontend::get_subscription_with_capacity`] for details.
+    #[cfg(feature = "pubsub")]
+    pub async fn get_subscription_with_capacity<R: alloy_json_rpc::RpcReturn>(
+        &self,
+        id: alloy_primitives::U256,
+        capacity: usize,
+    ) -> alloy_transport::TransportResult<Subscription<R>> {
+        self.pubsub_frontend()?
+            .get_subscription_with_capacity(id, capacity)
+            .await
+            .map(Subscription::from)
+    }
+
     /// Unsubscribes from the subscription corresponding to the given RPC subscription ID.
     #[cfg(feature = "pubsub")]
     pub fn unsubscribe(&self, id: alloy_primitives::U256) -> alloy_transport::TransportResult<()> {
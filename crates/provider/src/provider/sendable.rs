@@ -3,11 +3,11 @@ use alloy_network::Network;
 /// A transaction that can be sent. This is either a builder or an envelope.
 ///
 /// This type is used to allow for fillers to convert a builder into an envelope
-/// without changing the user-facing API.
+/// without changing the user-facing API. It is passed to fillers, and to
+/// [`TransactionHooks`] with the filled transaction.
 ///
-/// Users should NOT use this type directly. It should only be used as an
-/// implementation detail of [`Provider::send_transaction_internal`].
-#[doc(hidden, alias = "SendableTransaction")]
+/// [`TransactionHooks`]: crate::layers::TransactionHooks
+#[doc(alias = "SendableTransaction")]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SendableTx<N: Network> {
     /// A transaction that is not yet signed.
@@ -0,0 +1,214 @@
+use crate::Provider;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Network, TransactionResponse};
+use alloy_primitives::{Address, BlockNumber, TxHash};
+use alloy_rpc_types_eth::BlockNumberOrTag;
+use alloy_transport::{Transport, TransportError, TransportResult};
+use serde::Deserialize;
+use std::marker::PhantomData;
+
+/// The JSON-RPC error code of calls to unknown methods.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The transactions of a block fetched with full transaction bodies.
+#[derive(Debug, Deserialize)]
+struct BlockTransactions<T> {
+    transactions: Vec<T>,
+}
+
+/// Search of the transaction sent by an account with a given nonce.
+pub(crate) struct SenderNonceSearch<'a, P: ?Sized, T, N> {
+    provider: &'a P,
+    sender: Address,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<'a, P, T, N> SenderNonceSearch<'a, P, T, N>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    pub(crate) const fn new(provider: &'a P, sender: Address) -> Self {
+        Self { provider, sender, _pd: PhantomData }
+    }
+
+    /// Returns the transaction with the given nonce, using `ots_getTransactionBySenderAndNonce`
+    /// if the node supports it and scanning blocks otherwise.
+    pub(crate) async fn find(&self, nonce: u64) -> TransportResult<Option<N::TransactionResponse>> {
+        match self.find_indexed(nonce).await {
+            Ok(Some(hash)) => self.provider.get_transaction_by_hash(hash).await,
+            Ok(None) => Ok(None),
+            Err(err) if is_method_not_found(&err) => {
+                debug!(sender = %self.sender, nonce, "ots_ namespace unavailable, scanning blocks");
+                self.scan(nonce).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn find_indexed(&self, nonce: u64) -> TransportResult<Option<TxHash>> {
+        self.provider
+            .client()
+            .request("ots_getTransactionBySenderAndNonce", (self.sender, nonce))
+            .await
+    }
+
+    async fn nonce_at(&self, block: BlockNumber) -> TransportResult<u64> {
+        self.provider.get_transaction_count(self.sender).number(block).await
+    }
+
+    /// Finds the transaction by binary searching the first block after which the account nonce
+    /// is greater than `nonce`, then looking up the sender's transactions in that block.
+    ///
+    /// This requires the historical state of the searched blocks.
+    pub(crate) async fn scan(&self, nonce: u64) -> TransportResult<Option<N::TransactionResponse>> {
+        let latest = self.provider.get_block_number().await?;
+        if self.nonce_at(latest).await? <= nonce {
+            return Ok(None);
+        }
+        let mut low_nonce = self.nonce_at(0).await?;
+        if low_nonce > nonce {
+            return Ok(None);
+        }
+
+        // Invariant: `nonce_at(low) <= nonce < nonce_at(high)`.
+        let (mut low, mut high) = (0, latest);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            let mid_nonce = self.nonce_at(mid).await?;
+            if mid_nonce > nonce {
+                high = mid;
+            } else {
+                (low, low_nonce) = (mid, mid_nonce);
+            }
+        }
+
+        // The sender's transactions are included in nonce order, starting from `low_nonce`.
+        let block: Option<BlockTransactions<N::TransactionResponse>> = self
+            .provider
+            .client()
+            .request("eth_getBlockByNumber", (BlockNumberOrTag::Number(high), true))
+            .await?;
+        let index = (nonce - low_nonce) as usize;
+        Ok(block.and_then(|block| {
+            block.transactions.into_iter().filter(|tx| tx.from() == self.sender).nth(index)
+        }))
+    }
+}
+
+const fn is_method_not_found(err: &TransportError) -> bool {
+    matches!(err, RpcError::ErrorResp(payload) if payload.code == METHOD_NOT_FOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{rpc_error, MockTransport};
+    use alloy_json_rpc::ErrorPayload;
+    use alloy_primitives::{B256, U64};
+    use alloy_rpc_types_eth::Transaction;
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    const SENDER: Address = Address::with_last_byte(1);
+
+    fn hash(number: u64, from: Address, nonce: u64) -> B256 {
+        let mut hash = B256::ZERO;
+        hash[29] = number as u8;
+        hash[30] = from[19];
+        hash[31] = nonce as u8;
+        hash
+    }
+
+    /// A chain of 100 blocks where `SENDER` sends its transactions with nonces 0 and 1 in block
+    /// 40, and the one with nonce 2 in block 70, each block also containing another transaction.
+    fn block_transactions(number: u64) -> Vec<Transaction> {
+        let tx = |from: Address, nonce| Transaction {
+            hash: hash(number, from, nonce),
+            from,
+            nonce,
+            block_number: Some(number),
+            ..Default::default()
+        };
+        let mut txs = vec![tx(Address::with_last_byte(2), 0)];
+        match number {
+            40 => txs.extend([tx(SENDER, 0), tx(SENDER, 1)]),
+            70 => txs.push(tx(SENDER, 2)),
+            _ => {}
+        }
+        txs
+    }
+
+    fn handle(method: &str, params: &Value, ots: bool) -> Result<Value, ErrorPayload> {
+        let number = |value: &Value| match value.as_str().unwrap() {
+            "latest" => 99,
+            hex => u64::from_str_radix(hex.trim_start_matches("0x"), 16).unwrap(),
+        };
+        Ok(match method {
+            "ots_getTransactionBySenderAndNonce" if ots => {
+                let nonce = params[1].as_u64().unwrap();
+                let block = [40, 40, 70].get(nonce as usize);
+                json!(block.map(|block| hash(*block, SENDER, nonce)))
+            }
+            "eth_blockNumber" => json!(U64::from(99)),
+            "eth_getTransactionCount" => {
+                let number = number(&params[1]);
+                json!(U64::from(if number >= 70 {
+                    3
+                } else if number >= 40 {
+                    2
+                } else {
+                    0
+                }))
+            }
+            "eth_getBlockByNumber" => {
+                json!({ "transactions": block_transactions(number(&params[0])) })
+            }
+            "eth_getTransactionByHash" => {
+                let hash: B256 = serde_json::from_value(params[0].clone()).unwrap();
+                json!(block_transactions(hash[29] as u64).into_iter().find(|tx| tx.hash == hash))
+            }
+            _ => {
+                return Err(rpc_error(
+                    METHOD_NOT_FOUND,
+                    &format!("the method {method} does not exist/is not available"),
+                ))
+            }
+        })
+    }
+
+    fn provider(ots: bool) -> (impl Provider<MockTransport>, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let log = calls.clone();
+        let transport = MockTransport::new(move |method, params| {
+            log.lock().unwrap().push(method.to_string());
+            handle(method, &params, ots)
+        });
+        (transport.provider(), calls)
+    }
+
+    async fn find(provider: &impl Provider<MockTransport>, nonce: u64) -> Option<(u64, u64)> {
+        let tx = provider.get_transaction_by_sender_and_nonce(SENDER, nonce).await.unwrap()?;
+        assert_eq!(tx.from, SENDER);
+        Some((tx.nonce, tx.block_number.unwrap()))
+    }
+
+    #[tokio::test]
+    async fn uses_otterscan_index() {
+        let (provider, calls) = provider(true);
+        assert_eq!(find(&provider, 1).await, Some((1, 40)));
+        assert_eq!(find(&provider, 3).await, None);
+        assert!(calls.lock().unwrap().iter().all(|method| method != "eth_getTransactionCount"));
+    }
+
+    #[tokio::test]
+    async fn scans_blocks_without_otterscan() {
+        let (provider, calls) = provider(false);
+        assert_eq!(find(&provider, 0).await, Some((0, 40)));
+        assert_eq!(find(&provider, 1).await, Some((1, 40)));
+        assert_eq!(find(&provider, 2).await, Some((2, 70)));
+        assert_eq!(find(&provider, 3).await, None);
+        assert!(calls.lock().unwrap().iter().any(|method| method == "eth_getTransactionCount"));
+    }
+}
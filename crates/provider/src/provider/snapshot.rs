@@ -0,0 +1,131 @@
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, Bytes, StorageValue, U256, U64};
+use alloy_rpc_client::{BatchRequest, ClientRef};
+use alloy_transport::{RpcFut, Transport, TransportResult};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    future::IntoFuture,
+};
+
+/// The state of a single account at a specific block, as returned by
+/// [`StateSnapshotAt`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateSnapshot {
+    /// The account address.
+    pub address: Address,
+    /// The block the state was read at.
+    pub block_id: BlockId,
+    /// The account balance.
+    pub balance: U256,
+    /// The account nonce.
+    pub nonce: u64,
+    /// The account bytecode.
+    pub code: Bytes,
+    /// The requested storage slots and their values.
+    pub storage: BTreeMap<U256, StorageValue>,
+}
+
+impl StateSnapshot {
+    /// Returns the value of the given storage slot, if it was part of the snapshot.
+    pub fn storage_at(&self, slot: U256) -> Option<StorageValue> {
+        self.storage.get(&slot).copied()
+    }
+
+    /// Returns `true` if the account has code deployed at the snapshot block.
+    pub fn has_code(&self) -> bool {
+        !self.code.is_empty()
+    }
+}
+
+/// A builder for reading the balance, nonce, code and storage of an account at
+/// a pinned block.
+///
+/// All reads are sent in a single JSON-RPC batch of `eth_getBalance`,
+/// `eth_getTransactionCount`, `eth_getCode` and `eth_getStorageAt` requests, all
+/// against the same [`BlockId`]. Pin the block by number or hash to get a
+/// consistent point-in-time view; block tags such as `latest` may resolve to
+/// different blocks for each request in the batch.
+///
+/// Created by [`Provider::get_state_snapshot`](crate::Provider::get_state_snapshot).
+#[derive(Debug)]
+#[must_use = "this builder does nothing unless you `.await` it"]
+pub struct StateSnapshotAt<'a, T> {
+    client: ClientRef<'a, T>,
+    address: Address,
+    block_id: BlockId,
+    slots: BTreeSet<U256>,
+}
+
+impl<'a, T> StateSnapshotAt<'a, T>
+where
+    T: Transport + Clone,
+{
+    /// Creates a new snapshot builder for `address` at `block_id`.
+    pub const fn new(client: ClientRef<'a, T>, address: Address, block_id: BlockId) -> Self {
+        Self { client, address, block_id, slots: BTreeSet::new() }
+    }
+
+    /// Sets the block to read the state at.
+    pub const fn block_id(mut self, block_id: BlockId) -> Self {
+        self.block_id = block_id;
+        self
+    }
+
+    /// Adds a storage slot to read.
+    pub fn slot(mut self, slot: U256) -> Self {
+        self.slots.insert(slot);
+        self
+    }
+
+    /// Adds multiple storage slots to read.
+    pub fn slots(mut self, slots: impl IntoIterator<Item = U256>) -> Self {
+        self.slots.extend(slots);
+        self
+    }
+
+    /// Sends the batch and returns the snapshot.
+    pub async fn send(self) -> TransportResult<StateSnapshot> {
+        let Self { client, address, block_id, slots } = self;
+
+        let mut batch = BatchRequest::new(client);
+        let balance = batch.add_call::<U256>("eth_getBalance", (address, block_id))?;
+        let nonce = batch.add_call::<U64>("eth_getTransactionCount", (address, block_id))?;
+        let code = batch.add_call::<Bytes>("eth_getCode", (address, block_id))?;
+        let storage = slots
+            .into_iter()
+            .map(|slot| {
+                batch
+                    .add_call::<StorageValue>("eth_getStorageAt", (address, slot, block_id))
+                    .map(|waiter| (slot, waiter))
+            })
+            .collect::<TransportResult<Vec<_>>>()?;
+
+        batch.send().await?;
+
+        let mut snapshot = StateSnapshot {
+            address,
+            block_id,
+            balance: balance.await?,
+            nonce: crate::utils::convert_u64(nonce.await?),
+            code: code.await?,
+            storage: BTreeMap::new(),
+        };
+        for (slot, value) in storage {
+            snapshot.storage.insert(slot, value.await?);
+        }
+
+        Ok(snapshot)
+    }
+}
+
+impl<'a, T> IntoFuture for StateSnapshotAt<'a, T>
+where
+    T: Transport + Clone,
+{
+    type Output = TransportResult<StateSnapshot>;
+    type IntoFuture = RpcFut<'a, StateSnapshot>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
@@ -0,0 +1,326 @@
+use crate::FilterPollerBuilder;
+use alloy_consensus::{
+    proofs::{verify_proof, Nibbles},
+    Account,
+};
+use alloy_json_rpc::RpcError;
+use alloy_primitives::{keccak256, Address, StorageValue, B256, U256};
+use alloy_rpc_client::WeakClient;
+use alloy_rpc_types_eth::{Block, BlockId, BlockNumberOrTag, EIP1186AccountProofResponse};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use futures::{future, Stream, StreamExt};
+use std::time::Duration;
+
+/// A change of the value of a storage slot, yielded by a [`StorageWatcher`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageChange {
+    /// The hash of the block the value was read at.
+    pub block_hash: B256,
+    /// The value of the slot.
+    pub value: StorageValue,
+    /// The previous value of the slot, `None` for the first value of the stream.
+    pub previous: Option<StorageValue>,
+}
+
+/// A builder of a stream of the changes of a storage slot, returned by
+/// [`Provider::watch_storage`](crate::Provider::watch_storage).
+///
+/// The slot is read at the latest block, then at the last new block of each poll of a block
+/// filter, with `eth_getStorageAt`. A value is yielded when it differs from the previous one, so
+/// changes reverted within a poll interval are missed.
+///
+/// With [proof verification](Self::with_proof_verification), the slot is read with
+/// `eth_getProof` instead, and the account and storage proofs are verified against the state
+/// root of the header of the block returned by the node.
+///
+/// Errors are yielded without ending the stream, which retries at the next block.
+#[derive(Debug)]
+#[must_use = "this builder does nothing unless turned into a stream"]
+pub struct StorageWatcher<T> {
+    client: WeakClient<T>,
+    blocks: FilterPollerBuilder<T, B256>,
+    address: Address,
+    slot: U256,
+    verify_proofs: bool,
+}
+
+impl<T> StorageWatcher<T>
+where
+    T: Transport + Clone,
+{
+    /// Creates a new watcher of `slot` of `address`, reading it at the blocks of `blocks`.
+    pub(crate) const fn new(
+        client: WeakClient<T>,
+        blocks: FilterPollerBuilder<T, B256>,
+        address: Address,
+        slot: U256,
+    ) -> Self {
+        Self { client, blocks, address, slot, verify_proofs: false }
+    }
+
+    /// Sets whether to read the slot with `eth_getProof` and verify its proofs.
+    pub const fn with_proof_verification(mut self, verify_proofs: bool) -> Self {
+        self.verify_proofs = verify_proofs;
+        self
+    }
+
+    /// Sets the interval between the polls of new blocks.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.blocks.set_poll_interval(poll_interval);
+        self
+    }
+
+    /// Returns the stream of the changes of the slot.
+    pub fn into_stream(self) -> impl Stream<Item = TransportResult<StorageChange>> + Unpin {
+        let Self { client, blocks, address, slot, verify_proofs } = self;
+        let values = heads(client.clone(), blocks).then(move |head| {
+            let client = client.clone();
+            async move {
+                let block_hash = head?;
+                let client = client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+                let block = BlockId::hash(block_hash);
+                let value = if verify_proofs {
+                    let header: Option<Block> =
+                        client.request("eth_getBlockByHash", (block_hash, false)).await?;
+                    let state_root = header.ok_or(RpcError::NullResp)?.header.state_root;
+                    let proof: EIP1186AccountProofResponse = client
+                        .request("eth_getProof", (address, [B256::from(slot)], block))
+                        .await?;
+                    verify_storage_proof(state_root, address, slot, &proof).map_err(|err| {
+                        TransportErrorKind::custom_str(&format!("invalid storage proof: {err}"))
+                    })?
+                } else {
+                    client.request("eth_getStorageAt", (address, slot, block)).await?
+                };
+                Ok((block_hash, value))
+            }
+        });
+
+        let changes = values.scan(None, |previous: &mut Option<StorageValue>, result| {
+            let change = match result {
+                Ok((block_hash, value)) if *previous != Some(value) => {
+                    Some(Ok(StorageChange { block_hash, value, previous: previous.replace(value) }))
+                }
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            };
+            future::ready(Some(change))
+        });
+        Box::pin(changes.filter_map(future::ready))
+    }
+}
+
+/// Returns the stream of the hashes of the latest block, then of the last new block of each poll
+/// of `blocks`.
+pub(super) fn heads<T: Transport + Clone>(
+    client: WeakClient<T>,
+    blocks: FilterPollerBuilder<T, B256>,
+) -> impl Stream<Item = TransportResult<B256>> {
+    let latest = async move {
+        let client = client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        let block: Option<Block> =
+            client.request("eth_getBlockByNumber", (BlockNumberOrTag::Latest, false)).await?;
+        block.and_then(|block| block.header.hash).ok_or(RpcError::NullResp)
+    };
+    futures::stream::once(latest).chain(
+        blocks.into_stream().filter_map(|hashes| future::ready(hashes.last().copied().map(Ok))),
+    )
+}
+
+/// Verifies the proofs of `slot` of `address` against a state root, returning its value.
+fn verify_storage_proof(
+    state_root: B256,
+    address: Address,
+    slot: U256,
+    proof: &EIP1186AccountProofResponse,
+) -> Result<StorageValue, String> {
+    if proof.address != address {
+        return Err(format!("proof of {} instead of {address}", proof.address));
+    }
+    let storage = proof
+        .storage_proof
+        .iter()
+        .find(|storage| U256::from_be_bytes(storage.key.0 .0) == slot)
+        .ok_or("missing storage proof")?;
+
+    let account = Account {
+        nonce: proof.nonce.to(),
+        balance: proof.balance,
+        storage_root: proof.storage_hash,
+        code_hash: proof.code_hash,
+    };
+    let address_key = Nibbles::unpack(keccak256(address));
+    let account = alloy_rlp::encode(account);
+    if let Err(err) =
+        verify_proof(state_root, address_key.clone(), Some(account), &proof.account_proof)
+    {
+        // An account that does not exist has no storage.
+        let exclusion = verify_proof(state_root, address_key, None, &proof.account_proof);
+        if storage.value.is_zero() && exclusion.is_ok() {
+            return Ok(U256::ZERO);
+        }
+        return Err(format!("account: {err}"));
+    }
+
+    // Zero values are not stored in the trie, so their proofs are proofs of exclusion.
+    let slot_key = Nibbles::unpack(keccak256(B256::from(slot)));
+    let value = (!storage.value.is_zero()).then(|| alloy_rlp::encode(storage.value));
+    verify_proof(proof.storage_hash, slot_key, value, &storage.proof)
+        .map(|()| storage.value)
+        .map_err(|err| format!("storage: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockTransport, Provider, RootProvider};
+    use alloy_consensus::EMPTY_ROOT_HASH;
+    use alloy_primitives::{Bytes, U64};
+    use alloy_rpc_types_eth::{EIP1186StorageProof, Header};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    const ADDRESS: Address = Address::with_last_byte(0xaa);
+
+    /// Returns the RLP encoding of the only node of a trie with a single leaf.
+    fn single_leaf(key: B256, value: &[u8]) -> Bytes {
+        let mut path = vec![0x20];
+        path.extend_from_slice(key.as_slice());
+        let mut payload = Vec::new();
+        alloy_rlp::Encodable::encode(&path.as_slice(), &mut payload);
+        alloy_rlp::Encodable::encode(&value, &mut payload);
+        let mut node = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: payload.len() }.encode(&mut node);
+        node.extend(payload);
+        node.into()
+    }
+
+    /// Returns a block whose state holds `value` at slot 1 of [`ADDRESS`], and its proof.
+    fn state(value: U256) -> (B256, EIP1186AccountProofResponse) {
+        let slot = B256::from(U256::from(1));
+        // Zero values are not stored, leaving the storage trie empty.
+        let storage_proof = if value.is_zero() {
+            vec![]
+        } else {
+            vec![single_leaf(keccak256(slot), &alloy_rlp::encode(value))]
+        };
+        let account = Account {
+            nonce: 1,
+            balance: U256::from(100),
+            storage_root: storage_proof.first().map_or(EMPTY_ROOT_HASH, keccak256),
+            code_hash: keccak256([]),
+        };
+        let account_node = single_leaf(keccak256(ADDRESS), &alloy_rlp::encode(account));
+        let proof = EIP1186AccountProofResponse {
+            address: ADDRESS,
+            balance: account.balance,
+            code_hash: account.code_hash,
+            nonce: U64::from(account.nonce),
+            storage_hash: account.storage_root,
+            account_proof: vec![account_node.clone()],
+            storage_proof: vec![EIP1186StorageProof {
+                key: slot.into(),
+                value,
+                proof: storage_proof,
+            }],
+        };
+        (keccak256(&account_node), proof)
+    }
+
+    /// A node at block 1, whose new blocks are returned by successive polls, with the value of
+    /// the slot at each block. The proof of block 2 has a tampered value.
+    fn provider() -> RootProvider<MockTransport> {
+        let values = [5u64, 5, 8, 7, 9];
+        let polls = Arc::new(Mutex::new(vec![vec![5], vec![3, 4], vec![], vec![2]]));
+        let transport = MockTransport::new(move |method, params| {
+            let block = |param: &Value| {
+                let hash = param.as_str().or_else(|| param["blockHash"].as_str()).unwrap();
+                hash.parse::<B256>().unwrap()[31] as usize
+            };
+            let value = |block: usize| U256::from(values[block - 1]);
+            let result = match method {
+                "eth_newBlockFilter" => json!("0x1"),
+                "eth_getFilterChanges" => {
+                    let hashes = polls.lock().unwrap().pop().unwrap_or_default();
+                    json!(hashes.into_iter().map(B256::with_last_byte).collect::<Vec<_>>())
+                }
+                "eth_getBlockByNumber" | "eth_getBlockByHash" => {
+                    let number = if method == "eth_getBlockByHash" { block(&params[0]) } else { 1 };
+                    let header = Header {
+                        hash: Some(B256::with_last_byte(number as u8)),
+                        state_root: state(value(number)).0,
+                        ..Default::default()
+                    };
+                    json!(Block::<alloy_rpc_types_eth::Transaction> {
+                        header,
+                        ..Default::default()
+                    })
+                }
+                "eth_getStorageAt" => json!(value(block(&params[2]))),
+                "eth_getProof" => {
+                    let number = block(&params[2]);
+                    let mut proof = state(value(number)).1;
+                    if number == 2 {
+                        proof.storage_proof[0].value += U256::from(1);
+                    }
+                    json!(proof)
+                }
+                method => unreachable!("{method}"),
+            };
+            Ok(result)
+        });
+        transport.provider()
+    }
+
+    fn change(block: u8, value: u64, previous: Option<u64>) -> StorageChange {
+        StorageChange {
+            block_hash: B256::with_last_byte(block),
+            value: U256::from(value),
+            previous: previous.map(U256::from),
+        }
+    }
+
+    #[tokio::test]
+    async fn watches_changes() {
+        let provider = provider();
+        let watcher = provider.watch_storage(ADDRESS, U256::from(1)).await.unwrap();
+        let changes: Vec<_> = watcher
+            .with_poll_interval(Duration::from_millis(10))
+            .into_stream()
+            .take(3)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        // Block 2 does not change the value, and block 3 is skipped by the poll of block 4.
+        assert_eq!(changes, [change(1, 5, None), change(4, 7, Some(5)), change(5, 9, Some(7))]);
+    }
+
+    #[tokio::test]
+    async fn verifies_proofs() {
+        let provider = provider();
+        let watcher = provider.watch_storage(ADDRESS, U256::from(1)).await.unwrap();
+        let mut stream = watcher
+            .with_poll_interval(Duration::from_millis(10))
+            .with_proof_verification(true)
+            .into_stream();
+        assert_eq!(stream.next().await.unwrap().unwrap(), change(1, 5, None));
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("invalid storage proof"), "{err}");
+        assert_eq!(stream.next().await.unwrap().unwrap(), change(4, 7, Some(5)));
+
+        let (state_root, proof) = state(U256::ZERO);
+        assert_eq!(
+            verify_storage_proof(state_root, ADDRESS, U256::from(1), &proof),
+            Ok(U256::ZERO)
+        );
+        assert!(verify_storage_proof(state_root, ADDRESS, U256::from(2), &proof).is_err());
+        assert!(verify_storage_proof(B256::ZERO, ADDRESS, U256::from(1), &proof).is_err());
+
+        // An account absent from an empty state has no storage.
+        let proof = EIP1186AccountProofResponse { account_proof: vec![], ..proof };
+        assert_eq!(
+            verify_storage_proof(EMPTY_ROOT_HASH, ADDRESS, U256::from(1), &proof),
+            Ok(U256::ZERO)
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use alloy_network::{Network, TransactionResponse};
+use alloy_primitives::Address;
+use alloy_rpc_client::WeakClient;
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use futures::{stream::BoxStream, Stream, StreamExt};
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// A stream of pending transactions, returned by
+/// [`Provider::pending_transactions`](crate::Provider::pending_transactions).
+///
+/// Transactions can be narrowed down with [`filter`](Self::filter), [`filter_to`](Self::filter_to)
+/// and [`filter_from`](Self::filter_from), then paired with their receipts as they get included
+/// with [`with_receipts`](Self::with_receipts).
+///
+/// Any stream of transactions can be used as the source with [`new`](Self::new), e.g. a
+/// `newPendingTransactions` subscription.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_primitives::address;
+/// use futures::StreamExt;
+///
+/// let router = address!("7a250d5630B4cF539739dF2C5dAcb4c659F2488D");
+/// let mut stream = provider.pending_transactions().await?.filter_to(router).with_receipts(16);
+/// while let Some(res) = stream.next().await {
+///     let (tx, receipt) = res?;
+///     println!("{tx:?} was included: {receipt:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "streams do nothing unless polled"]
+pub struct PendingTransactionStream<T, N: Network> {
+    client: WeakClient<T>,
+    transactions: BoxStream<'static, N::TransactionResponse>,
+    receipt_timeout: Option<Duration>,
+}
+
+impl<T, N: Network> fmt::Debug for PendingTransactionStream<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingTransactionStream")
+            .field("receipt_timeout", &self.receipt_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, N: Network> PendingTransactionStream<T, N> {
+    /// Creates a new stream of the given transactions, using `client` to fetch their receipts.
+    pub fn new(
+        client: WeakClient<T>,
+        transactions: impl Stream<Item = N::TransactionResponse> + Send + 'static,
+    ) -> Self {
+        Self { client, transactions: transactions.boxed(), receipt_timeout: None }
+    }
+
+    /// Only keeps the transactions matching `predicate`.
+    pub fn filter<F>(mut self, mut predicate: F) -> Self
+    where
+        F: FnMut(&N::TransactionResponse) -> bool + Send + 'static,
+    {
+        self.transactions =
+            self.transactions.filter(move |tx| futures::future::ready(predicate(tx))).boxed();
+        self
+    }
+
+    /// Only keeps the transactions sent to `address`.
+    pub fn filter_to(self, address: Address) -> Self {
+        self.filter(move |tx| tx.to() == Some(address))
+    }
+
+    /// Only keeps the transactions sent from `address`.
+    pub fn filter_from(self, address: Address) -> Self {
+        self.filter(move |tx| tx.from() == address)
+    }
+
+    /// Sets how long to wait for the receipt of a transaction in
+    /// [`with_receipts`](Self::with_receipts) before dropping it.
+    ///
+    /// Pending transactions may never be included, e.g. when they are replaced, so setting a
+    /// timeout prevents them from holding on to a slot forever. There is no timeout by default.
+    pub const fn receipt_timeout(mut self, timeout: Duration) -> Self {
+        self.receipt_timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the configured receipt timeout.
+    pub const fn get_receipt_timeout(&self) -> Option<Duration> {
+        self.receipt_timeout
+    }
+}
+
+impl<T, N> PendingTransactionStream<T, N>
+where
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Waits for the receipts of the transactions, yielding each transaction along with its
+    /// receipt as soon as it is included.
+    ///
+    /// Receipts are polled with `eth_getTransactionReceipt` at the client's poll interval, for
+    /// at most `concurrency` transactions at a time; the following transactions are only pulled
+    /// from the source once a slot is freed. Transactions are therefore yielded in inclusion
+    /// order rather than in the order they were seen.
+    ///
+    /// Transactions whose receipt did not arrive within the
+    /// [receipt timeout](Self::receipt_timeout) are dropped, and an error is yielded if a receipt
+    /// request fails. Once the client is dropped, no more transactions are pulled from the source
+    /// and the receipts still awaited resolve to an error.
+    ///
+    /// The returned stream does not borrow the provider, so it can be moved to another task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is 0.
+    pub fn with_receipts(
+        self,
+        concurrency: usize,
+    ) -> impl Stream<Item = TransportResult<(N::TransactionResponse, N::ReceiptResponse)>> + 'static
+    {
+        assert!(concurrency > 0, "concurrency must be greater than 0");
+        let Self { client, transactions, receipt_timeout } = self;
+        let alive = client.clone();
+        transactions
+            .take_while(move |_| futures::future::ready(alive.strong_count() > 0))
+            .map(move |tx| {
+                let receipt = wait_for_receipt::<T, N>(client.clone(), tx);
+                async move {
+                    match receipt_timeout {
+                        Some(timeout) => tokio::time::timeout(timeout, receipt).await.ok(),
+                        None => Some(receipt.await),
+                    }
+                }
+            })
+            .buffer_unordered(concurrency)
+            .filter_map(futures::future::ready)
+    }
+}
+
+impl<T, N: Network> Stream for PendingTransactionStream<T, N> {
+    type Item = N::TransactionResponse;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.transactions.poll_next_unpin(cx)
+    }
+}
+
+/// Polls the receipt of `tx` until it is available.
+async fn wait_for_receipt<T, N>(
+    client: WeakClient<T>,
+    tx: N::TransactionResponse,
+) -> TransportResult<(N::TransactionResponse, N::ReceiptResponse)>
+where
+    T: Transport + Clone,
+    N: Network,
+{
+    let hash = tx.tx_hash();
+    loop {
+        let client = client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+        let receipt: Option<N::ReceiptResponse> =
+            client.request("eth_getTransactionReceipt", (hash,)).await?;
+        if let Some(receipt) = receipt {
+            return Ok((tx, receipt));
+        }
+        let interval = client.poll_interval();
+        drop(client);
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock::MockChainProvider, Provider};
+    use alloy_network::Ethereum;
+    use alloy_primitives::B256;
+    use alloy_rpc_types_eth::Transaction;
+
+    fn tx(hash: u8, to: Address) -> Transaction {
+        Transaction { hash: B256::with_last_byte(hash), to: Some(to), ..Default::default() }
+    }
+
+    fn stream(
+        provider: &MockChainProvider,
+        txs: Vec<Transaction>,
+    ) -> PendingTransactionStream<crate::mock::MockChain, Ethereum> {
+        provider.client().set_poll_interval(Duration::from_millis(10));
+        PendingTransactionStream::new(provider.weak_client(), futures::stream::iter(txs))
+    }
+
+    #[tokio::test]
+    async fn filters_transactions() {
+        let provider = MockChainProvider::new();
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let txs = vec![tx(1, a), tx(2, b), tx(3, a)];
+
+        let hashes: Vec<_> =
+            stream(&provider, txs.clone()).filter_to(a).map(|tx| tx.hash).collect().await;
+        assert_eq!(hashes, [B256::with_last_byte(1), B256::with_last_byte(3)]);
+
+        let hashes: Vec<_> = stream(&provider, txs)
+            .filter_to(a)
+            .filter(|tx| tx.hash != B256::with_last_byte(1))
+            .map(|tx| tx.hash)
+            .collect()
+            .await;
+        assert_eq!(hashes, [B256::with_last_byte(3)]);
+    }
+
+    #[tokio::test]
+    async fn pairs_transactions_with_receipts() {
+        let provider = MockChainProvider::new();
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let txs = vec![tx(1, a), tx(2, b), tx(3, a), tx(4, a)];
+
+        // The third transaction is included after the first one.
+        provider.push_block(vec![crate::mock::receipt(B256::with_last_byte(1), Vec::new())]);
+        let chain = provider.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            chain.push_block(vec![crate::mock::receipt(B256::with_last_byte(3), Vec::new())]);
+        });
+
+        // The fourth transaction is never included.
+        let stream = stream(&provider, txs)
+            .filter_to(a)
+            .receipt_timeout(Duration::from_millis(200))
+            .with_receipts(3);
+        let included: Vec<_> = stream
+            .map(|res| {
+                let (tx, receipt) = res.unwrap();
+                assert_eq!(tx.hash, receipt.transaction_hash);
+                (tx.hash, receipt.block_number.unwrap())
+            })
+            .collect()
+            .await;
+        assert_eq!(included, [(B256::with_last_byte(1), 1), (B256::with_last_byte(3), 2)]);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::BlockNumber;
+use alloy_rpc_types_eth::{Block, BlockNumberOrTag};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use std::{collections::HashMap, marker::PhantomData};
+
+/// The number of blocks sampled to compute the average block time in
+/// [`Provider::estimate_block_at`].
+pub(crate) const BLOCK_TIME_SAMPLE: u64 = 1000;
+
+/// Which block to return when no block has the exact timestamp searched for by
+/// [`Provider::get_block_by_timestamp`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TimestampDirection {
+    /// The last block with a timestamp lower than or equal to the searched timestamp.
+    #[default]
+    Before,
+    /// The first block with a timestamp greater than or equal to the searched timestamp.
+    After,
+}
+
+/// Binary search of a block by timestamp, caching the probed blocks.
+pub(crate) struct TimestampSearch<'a, P: ?Sized, T, N> {
+    provider: &'a P,
+    blocks: HashMap<BlockNumber, Block>,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<'a, P, T, N> TimestampSearch<'a, P, T, N>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    pub(crate) fn new(provider: &'a P) -> Self {
+        Self { provider, blocks: HashMap::new(), _pd: PhantomData }
+    }
+
+    /// Returns the block with the given number, fetching it if it was not probed yet.
+    async fn block(&mut self, number: BlockNumberOrTag) -> TransportResult<&Block> {
+        let cached = number.as_number().filter(|number| self.blocks.contains_key(number));
+        let number = match cached {
+            Some(number) => number,
+            None => {
+                let block = self
+                    .provider
+                    .get_block_by_number(number, false)
+                    .await?
+                    .ok_or_else(|| TransportErrorKind::custom_str("block not found"))?;
+                let number = block
+                    .header
+                    .number
+                    .ok_or_else(|| TransportErrorKind::custom_str("block number is missing"))?;
+                self.blocks.entry(number).or_insert(block);
+                number
+            }
+        };
+        Ok(&self.blocks[&number])
+    }
+
+    async fn timestamp(&mut self, number: BlockNumber) -> TransportResult<u64> {
+        self.block(number.into()).await.map(|block| block.header.timestamp)
+    }
+
+    /// Returns the latest block number and its timestamp.
+    pub(crate) async fn latest(&mut self) -> TransportResult<(BlockNumber, u64)> {
+        let block = self.block(BlockNumberOrTag::Latest).await?;
+        Ok((block.header.number.unwrap_or_default(), block.header.timestamp))
+    }
+
+    /// Returns the number of the block matching `timestamp` in `direction`, if any.
+    pub(crate) async fn find(
+        &mut self,
+        timestamp: u64,
+        direction: TimestampDirection,
+    ) -> TransportResult<Option<BlockNumber>> {
+        let (latest, latest_timestamp) = self.latest().await?;
+        let genesis_timestamp = self.timestamp(0).await?;
+
+        match direction {
+            TimestampDirection::Before if timestamp < genesis_timestamp => return Ok(None),
+            TimestampDirection::Before if timestamp >= latest_timestamp => return Ok(Some(latest)),
+            TimestampDirection::After if timestamp > latest_timestamp => return Ok(None),
+            TimestampDirection::After if timestamp <= genesis_timestamp => return Ok(Some(0)),
+            _ => {}
+        }
+
+        // Invariant: `timestamp(low) < timestamp <= timestamp(high)`, or
+        // `timestamp(low) <= timestamp < timestamp(high)` when searching before.
+        let (mut low, mut high) = (0, latest);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            let mid_timestamp = self.timestamp(mid).await?;
+            let go_low = match direction {
+                TimestampDirection::Before => mid_timestamp > timestamp,
+                TimestampDirection::After => mid_timestamp >= timestamp,
+            };
+            if go_low {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+
+        Ok(Some(match direction {
+            TimestampDirection::Before => low,
+            TimestampDirection::After => high,
+        }))
+    }
+
+    /// Returns the block with the given number, from the cache if it was probed.
+    pub(crate) async fn get(&mut self, number: BlockNumber) -> TransportResult<Block> {
+        self.block(number.into()).await?;
+        Ok(self.blocks.remove(&number).expect("block was just cached"))
+    }
+
+    /// Estimates the number of the block at `timestamp`.
+    ///
+    /// Past timestamps are searched for, while future ones are extrapolated from the average
+    /// block time of the last [`BLOCK_TIME_SAMPLE`] blocks.
+    pub(crate) async fn estimate(&mut self, timestamp: u64) -> TransportResult<BlockNumber> {
+        let (latest, latest_timestamp) = self.latest().await?;
+        if timestamp <= latest_timestamp {
+            return Ok(self.find(timestamp, TimestampDirection::Before).await?.unwrap_or_default());
+        }
+
+        let sample_start = latest.saturating_sub(BLOCK_TIME_SAMPLE);
+        let sample_start_timestamp = self.timestamp(sample_start).await?;
+        let elapsed = latest_timestamp.saturating_sub(sample_start_timestamp);
+        if elapsed == 0 {
+            return Err(TransportErrorKind::custom_str(
+                "not enough blocks to estimate the block time",
+            ));
+        }
+
+        // blocks ahead = time ahead / average block time
+        let blocks_ahead = (timestamp - latest_timestamp) as u128 * (latest - sample_start) as u128
+            / elapsed as u128;
+        Ok(latest.saturating_add(blocks_ahead.try_into().unwrap_or(u64::MAX)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockChainProvider;
+
+    async fn find(
+        provider: &MockChainProvider,
+        timestamp: u64,
+        direction: TimestampDirection,
+    ) -> Option<u64> {
+        let block = provider.get_block_by_timestamp(timestamp, direction).await.unwrap();
+        block.map(|block| block.header.number.unwrap())
+    }
+
+    #[tokio::test]
+    async fn block_by_timestamp() {
+        let provider = MockChainProvider::new();
+        provider.push_empty_blocks(100);
+
+        // Exact matches.
+        assert_eq!(find(&provider, 0, TimestampDirection::Before).await, Some(0));
+        assert_eq!(find(&provider, 12 * 37, TimestampDirection::Before).await, Some(37));
+        assert_eq!(find(&provider, 12 * 37, TimestampDirection::After).await, Some(37));
+        assert_eq!(find(&provider, 12 * 100, TimestampDirection::After).await, Some(100));
+
+        // Between blocks.
+        assert_eq!(find(&provider, 12 * 37 + 5, TimestampDirection::Before).await, Some(37));
+        assert_eq!(find(&provider, 12 * 37 + 5, TimestampDirection::After).await, Some(38));
+        assert_eq!(find(&provider, 12 * 99 + 1, TimestampDirection::After).await, Some(100));
+
+        // Out of range.
+        assert_eq!(find(&provider, 12 * 100 + 1, TimestampDirection::Before).await, Some(100));
+        assert_eq!(find(&provider, 12 * 100 + 1, TimestampDirection::After).await, None);
+    }
+
+    #[tokio::test]
+    async fn estimates_block_at() {
+        let provider = MockChainProvider::new();
+        assert!(provider.estimate_block_at(100).await.is_err());
+
+        provider.push_empty_blocks(100);
+        assert_eq!(provider.estimate_block_at(12 * 50 + 3).await.unwrap(), 50);
+        assert_eq!(provider.estimate_block_at(12 * 100).await.unwrap(), 100);
+        assert_eq!(provider.estimate_block_at(12 * 150 + 3).await.unwrap(), 150);
+    }
+}
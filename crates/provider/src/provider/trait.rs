@@ -1,25 +1,34 @@
 //! Ethereum JSON-RPC provider.
 
+use super::{receipt, sender_nonce::SenderNonceSearch, timestamp::TimestampSearch};
 use crate::{
+    canonical::{ChainTracker, DEFAULT_MAX_DEPTH},
+    finality::FinalityPoller,
     utils::{self, Eip1559Estimation, EstimatorFunction},
-    EthCall, Identity, PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig,
-    ProviderBuilder, RootProvider, RpcWithBlock, SendableTx,
+    AccountWatcher, CanonicalChainTracker, DynProvider, EthCall, FeeHistoryStream, FilterGuard,
+    FinalityTracker, GasTicker, Identity, PendingTransaction, PendingTransactionBuilder,
+    PendingTransactionConfig, PendingTransactionStream, ProviderBuilder, RootProvider,
+    RpcWithBlock, SendableTx, StateSnapshotAt, StorageWatcher, TimestampDirection,
+    WaitForReceiptError, WithdrawalsStream,
 };
 use alloy_eips::eip2718::Encodable2718;
 use alloy_json_rpc::{RpcError, RpcParam, RpcReturn};
-use alloy_network::{Ethereum, Network};
+use alloy_network::{Ethereum, Network, SignedTransactionBundle};
 use alloy_primitives::{
-    hex, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, B256, U128,
+    hex, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, B256, B64, U128,
     U256, U64,
 };
 use alloy_rpc_client::{ClientRef, PollerBuilder, RpcCall, WeakClient};
 use alloy_rpc_types_eth::{
+    simulate::{SimulatePayload, SimulatedBlock},
     AccessListWithGasUsed, Block, BlockId, BlockNumberOrTag, BlockTransactionsKind,
-    EIP1186AccountProofResponse, FeeHistory, Filter, FilterChanges, Log, SyncStatus,
+    EIP1186AccountProofResponse, FeeHistory, Filter, FilterChanges, Log, SyncStatus, UncleIndex,
+    Work,
 };
 use alloy_transport::{BoxTransport, Transport, TransportErrorKind, TransportResult};
+use futures::StreamExt;
 use serde_json::value::RawValue;
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::RangeInclusive, sync::Arc, time::Duration};
 
 /// A task that polls the provider with `eth_getFilterChanges`, returning a list of `R`.
 ///
@@ -43,6 +52,10 @@ pub type FilterPollerBuilder<T, R> = PollerBuilder<T, (U256,), Vec<R>>;
 /// pending transactions. This is only available on `pubsub` clients, such as
 /// Websockets or IPC.
 ///
+/// Identical subscriptions share a single subscription on the node: subscribing
+/// to new block headers twice yields two independent receivers of the same
+/// node subscription, which is cancelled once both are unsubscribed.
+///
 /// For a polling alternatives available over HTTP, use the `watch_*` methods.
 /// However, be aware that polling increases RPC usage drastically.
 ///
@@ -78,6 +91,18 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         ProviderBuilder::default()
     }
 
+    /// Erases the type of the provider, returning a [`DynProvider`].
+    ///
+    /// This is only available for providers over a [`BoxTransport`], such as the ones built with
+    /// [`ProviderBuilder::on_builtin`](crate::ProviderBuilder::on_builtin).
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    fn erased(self) -> DynProvider<N>
+    where
+        Self: Provider<BoxTransport, N> + Sized + 'static,
+    {
+        Arc::new(self)
+    }
+
     /// Returns the RPC client used to send requests.
     ///
     /// NOTE: this method should not be overridden.
@@ -166,16 +191,49 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         RpcWithBlock::new(self.weak_client(), "eth_createAccessList", request)
     }
 
+    /// Simulates blocks of calls on top of the given block, returning the simulated blocks along
+    /// with the results of their calls.
+    ///
+    /// # Note
+    ///
+    /// Not all client implementations support `eth_simulateV1`.
+    fn simulate<'req>(
+        &self,
+        payload: &'req SimulatePayload,
+    ) -> RpcWithBlock<T, &'req SimulatePayload, Vec<SimulatedBlock>> {
+        RpcWithBlock::new(self.weak_client(), "eth_simulateV1", payload)
+    }
+
     /// This function returns an [`EthCall`] which can be used to get a gas estimate,
-    /// or to add [`StateOverride`] or a [`BlockId`]. If no overrides
+    /// or to add [`StateOverride`], [`BlockOverrides`] or a [`BlockId`]. If no overrides
     /// or block ID is provided, the gas estimate will be computed for the latest block
     /// with the current state.
     ///
     /// [`StateOverride`]: alloy_rpc_types_eth::state::StateOverride
+    /// [`BlockOverrides`]: alloy_rpc_types_eth::BlockOverrides
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use alloy_provider::Provider;
+    /// # use alloy_rpc_types_eth::state::{AccountOverride, StateOverride};
+    /// # use alloy_transport::BoxTransport;
+    /// # async fn example<P: Provider<BoxTransport>>(
+    /// #    provider: P,
+    /// #    tx: alloy_rpc_types_eth::transaction::TransactionRequest,
+    /// # ) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Estimate the gas of a transaction sent by an account that is not funded yet.
+    /// let funded =
+    ///     AccountOverride { balance: Some(alloy_primitives::U256::MAX), ..Default::default() };
+    /// let overrides = StateOverride::from([(tx.from.unwrap_or_default(), funded)]);
+    /// let gas = provider.estimate_gas(&tx).overrides(&overrides).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
     /// # Note
     ///
-    /// Not all client implementations support state overrides for eth_estimateGas.
+    /// Not all client implementations support state and block overrides for eth_estimateGas.
     fn estimate_gas<'req>(
         &self,
         tx: &'req N::TransactionRequest,
@@ -183,6 +241,48 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         EthCall::gas_estimate(self.weak_client(), tx).map_resp(crate::utils::convert_u128)
     }
 
+    /// Finds the minimum gas limit with which `tx` succeeds at the given block, by bisecting the
+    /// gas limit of `eth_call`s.
+    ///
+    /// `eth_estimateGas` may return a gas limit higher than needed, e.g. to account for the gas
+    /// refund cap or the 63/64 rule, or within the node's tolerance. This instead returns the
+    /// exact gas limit below which the call fails, at the cost of a logarithmic number of
+    /// `eth_call`s. The search starts from the gas limit of `tx`, or its `eth_estimateGas`
+    /// estimate, which must succeed. Calls are assumed to succeed above their minimum gas limit,
+    /// which may not hold for contracts whose behavior depends on the gas left.
+    async fn estimate_minimum_gas(
+        &self,
+        tx: &N::TransactionRequest,
+        block: BlockId,
+    ) -> TransportResult<u128> {
+        use alloy_network::TransactionBuilder;
+
+        /// The intrinsic gas of a transaction, below which calls always fail.
+        const MIN_GAS: u128 = 21_000;
+
+        let mut tx = tx.clone();
+        let mut high = match tx.gas_limit() {
+            Some(gas_limit) => gas_limit,
+            None => self.estimate_gas(&tx).block(block).await?,
+        }
+        .max(MIN_GAS);
+        tx.set_gas_limit(high);
+        self.call(&tx).block(block).await?;
+
+        // Invariant: the call fails with `low` gas, and succeeds with `high` gas.
+        let mut low = MIN_GAS - 1;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            tx.set_gas_limit(mid);
+            match self.call(&tx).block(block).await {
+                Ok(_) => high = mid,
+                Err(RpcError::ErrorResp(_)) => low = mid,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(high)
+    }
+
     /// Estimates the EIP1559 `maxFeePerGas` and `maxPriorityFeePerGas` fields.
     ///
     /// Receives an optional [EstimatorFunction] that can be used to modify
@@ -234,6 +334,44 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
             .await
     }
 
+    /// Returns a stream of the fee history of the last `window` blocks, updated on each new block.
+    ///
+    /// Only the new blocks are requested on each update, instead of the whole window. See
+    /// [`FeeHistoryStream`] for more details.
+    async fn fee_history_stream(
+        &self,
+        window: u64,
+        reward_percentiles: &[f64],
+    ) -> TransportResult<FeeHistoryStream> {
+        let history =
+            self.get_fee_history(window, BlockNumberOrTag::Latest, reward_percentiles).await?;
+        Ok(FeeHistoryStream::new(self.weak_client(), history, window, reward_percentiles.to_vec()))
+    }
+
+    /// Returns a stream of suggested fees for the next block, updated on each new block.
+    ///
+    /// The fees are estimated from the fee history of the last
+    /// [`EIP1559_FEE_ESTIMATION_PAST_BLOCKS`](utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS) blocks,
+    /// like [`estimate_eip1559_fees`](Self::estimate_eip1559_fees). See [`GasTicker`] for more
+    /// details.
+    async fn gas_ticker(&self) -> TransportResult<GasTicker> {
+        let history = self
+            .fee_history_stream(
+                utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+                &[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
+            )
+            .await?;
+        Ok(GasTicker::new(history))
+    }
+
+    /// Returns a stream of the withdrawals of the blocks of `range`, in order.
+    ///
+    /// Blocks are requested without their transactions. See [`WithdrawalsStream`] for more
+    /// details.
+    fn withdrawals_stream(&self, range: RangeInclusive<BlockNumber>) -> WithdrawalsStream {
+        WithdrawalsStream::new(self.weak_client(), range)
+    }
+
     /// Gets the current gas price in wei.
     fn get_gas_price(&self) -> RpcCall<T, (), U128, u128> {
         self.client().request("eth_gasPrice", ()).map_resp(crate::utils::convert_u128)
@@ -319,6 +457,44 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         Ok(block)
     }
 
+    /// Gets the block closest to `timestamp`, in the given [`TimestampDirection`].
+    ///
+    /// The block is searched for by bisecting block numbers between genesis and the latest
+    /// block, which takes a logarithmic number of `eth_getBlockByNumber` requests. Returns `None`
+    /// if no block matches, e.g. when searching for the first block after a future timestamp.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example<P: alloy_provider::Provider>(provider: P) -> alloy_transport::TransportResult<()> {
+    /// use alloy_provider::TimestampDirection;
+    ///
+    /// // The last block mined on or before 2024-01-01.
+    /// let block = provider.get_block_by_timestamp(1704067200, TimestampDirection::Before).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_block_by_timestamp(
+        &self,
+        timestamp: u64,
+        direction: TimestampDirection,
+    ) -> TransportResult<Option<Block>> {
+        let mut search = TimestampSearch::new(self);
+        match search.find(timestamp, direction).await? {
+            Some(number) => search.get(number).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Estimates the number of the block at `timestamp`.
+    ///
+    /// For past timestamps this is the last block mined on or before `timestamp`, see
+    /// [`get_block_by_timestamp`](Self::get_block_by_timestamp). Future block numbers are
+    /// extrapolated from the average block time of the last 1000 blocks.
+    async fn estimate_block_at(&self, timestamp: u64) -> TransportResult<BlockNumber> {
+        TimestampSearch::new(self).estimate(timestamp).await
+    }
+
     /// Gets the selected block [BlockNumberOrTag] receipts.
     async fn get_block_receipts(
         &self,
@@ -327,11 +503,78 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getBlockReceipts", (block,)).await
     }
 
+    /// Gets the number of transactions in the block specified by the tag [BlockId].
+    ///
+    /// Returns `None` if the block does not exist.
+    async fn get_block_transaction_count(&self, tag: BlockId) -> TransportResult<Option<u64>> {
+        match tag {
+            BlockId::Hash(hash) => self.get_block_transaction_count_by_hash(hash.block_hash).await,
+            BlockId::Number(number) => self.get_block_transaction_count_by_number(number).await,
+        }
+    }
+
+    /// Gets the number of transactions in the block with the given [BlockHash].
+    ///
+    /// Returns `None` if the block does not exist.
+    async fn get_block_transaction_count_by_hash(
+        &self,
+        hash: BlockHash,
+    ) -> TransportResult<Option<u64>> {
+        self.client()
+            .request("eth_getBlockTransactionCountByHash", (hash,))
+            .await
+            .map(|count: Option<U64>| count.map(|count| count.to::<u64>()))
+    }
+
+    /// Gets the number of transactions in the block with the given [BlockNumberOrTag].
+    ///
+    /// Returns `None` if the block does not exist.
+    async fn get_block_transaction_count_by_number(
+        &self,
+        number: BlockNumberOrTag,
+    ) -> TransportResult<Option<u64>> {
+        self.client()
+            .request("eth_getBlockTransactionCountByNumber", (number,))
+            .await
+            .map(|count: Option<U64>| count.map(|count| count.to::<u64>()))
+    }
+
     /// Gets the bytecode located at the corresponding [Address].
     fn get_code_at(&self, address: Address) -> RpcWithBlock<T, Address, Bytes> {
         RpcWithBlock::new(self.weak_client(), "eth_getCode", address)
     }
 
+    /// Spawns a task tracking the canonical chain, and returns a handle broadcasting its
+    /// [extensions and reorgs](crate::ChainEvent).
+    ///
+    /// New heads are received with an `eth_subscribe("newHeads")` subscription on `pubsub`
+    /// clients, and by polling the latest block otherwise. See [`CanonicalChainTracker`] for more
+    /// details.
+    async fn canonical_chain_tracker(&self) -> TransportResult<CanonicalChainTracker> {
+        #[cfg(feature = "pubsub")]
+        if self.root().pubsub_frontend().is_ok() {
+            let heads = self.subscribe_blocks().await?.into_stream();
+            return Ok(ChainTracker::spawn(self.weak_client(), heads, DEFAULT_MAX_DEPTH));
+        }
+
+        let heads = PollerBuilder::<T, _, Option<Block>>::new(
+            self.weak_client(),
+            "eth_getBlockByNumber",
+            (BlockNumberOrTag::Latest, false),
+        )
+        .into_stream()
+        .filter_map(futures::future::ready);
+        Ok(ChainTracker::spawn(self.weak_client(), heads, DEFAULT_MAX_DEPTH))
+    }
+
+    /// Spawns a task polling the `finalized` and `safe` blocks, and returns a handle
+    /// broadcasting their [advances](crate::FinalityEvent).
+    ///
+    /// See [`FinalityTracker`] for more details.
+    fn finality_tracker(&self) -> FinalityTracker<T> {
+        FinalityPoller::new(self.weak_client()).spawn()
+    }
+
     /// Watch for new blocks by polling the provider with
     /// [`eth_getFilterChanges`](Self::get_filter_changes).
     ///
@@ -386,6 +629,72 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         Ok(PollerBuilder::new(self.weak_client(), "eth_getFilterChanges", (id,)))
     }
 
+    /// Watches the value of the storage slot `slot` of `address`, read at each new block.
+    ///
+    /// Returns a builder of the stream of the changes of the value, whose first item is the
+    /// value at the latest block. See [`StorageWatcher`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::{address, U256};
+    /// use futures::StreamExt;
+    ///
+    /// let pool = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
+    /// let watcher = provider.watch_storage(pool, U256::ZERO).await?;
+    /// let mut changes = watcher.with_proof_verification(true).into_stream();
+    /// while let Some(change) = changes.next().await {
+    ///     let change = change?;
+    ///     println!("slot0 is {} at block {}", change.value, change.block_hash);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn watch_storage(
+        &self,
+        address: Address,
+        slot: U256,
+    ) -> TransportResult<StorageWatcher<T>> {
+        let blocks = self.watch_blocks().await?;
+        Ok(StorageWatcher::new(self.weak_client(), blocks, address, slot))
+    }
+
+    /// Watches the balance of `address`, read at each new block.
+    ///
+    /// Returns a builder of the stream of the changes of the balance. See [`AccountWatcher`] for
+    /// more details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::address;
+    /// use futures::StreamExt;
+    ///
+    /// let wallet = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+    /// let mut deltas = provider.watch_balance(wallet).await?.into_stream();
+    /// while let Some(delta) = deltas.next().await {
+    ///     let delta = delta?;
+    ///     println!("balance changed from {} to {} at block {}", delta.old, delta.new, delta.block_hash);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn watch_balance(&self, address: Address) -> TransportResult<AccountWatcher<T, U256>> {
+        let blocks = self.watch_blocks().await?;
+        Ok(AccountWatcher::balance(self.weak_client(), blocks, address))
+    }
+
+    /// Watches the nonce of `address`, read at each new block.
+    ///
+    /// Returns a builder of the stream of the changes of the nonce. See [`AccountWatcher`] for
+    /// more details.
+    async fn watch_nonce(&self, address: Address) -> TransportResult<AccountWatcher<T, u64>> {
+        let blocks = self.watch_blocks().await?;
+        Ok(AccountWatcher::nonce(self.weak_client(), blocks, address))
+    }
+
     /// Watch for new logs using the given filter by polling the provider with
     /// [`eth_getFilterChanges`](Self::get_filter_changes).
     ///
@@ -452,6 +761,41 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         Ok(PollerBuilder::new(self.weak_client(), "eth_getFilterChanges", (id,)))
     }
 
+    /// Returns a stream of the full pending transactions, polled with
+    /// [`watch_full_pending_transactions`](Self::watch_full_pending_transactions).
+    ///
+    /// The stream can be filtered and paired with the transaction receipts as they get included.
+    /// See [`PendingTransactionStream`] for more details.
+    ///
+    /// # Support
+    ///
+    /// This endpoint might not be supported by all clients.
+    ///
+    /// # Examples
+    ///
+    /// Get the next 5 transactions sent to an address, once they are included:
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::address;
+    /// use futures::StreamExt;
+    ///
+    /// let address = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+    /// let stream = provider.pending_transactions().await?.filter_to(address).with_receipts(8);
+    /// let mut stream = std::pin::pin!(stream.take(5));
+    /// while let Some((tx, receipt)) = stream.next().await.transpose()? {
+    ///    println!("transaction {tx:#?} included in block {:?}", receipt.block_number);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn pending_transactions(&self) -> TransportResult<PendingTransactionStream<T, N>> {
+        let poller = self.watch_full_pending_transactions().await?;
+        let transactions =
+            futures::StreamExt::flat_map(poller.into_stream(), futures::stream::iter);
+        Ok(PendingTransactionStream::new(self.weak_client(), transactions))
+    }
+
     /// Get a list of values that have been added since the last poll.
     ///
     /// The return value depends on what stream `id` corresponds to.
@@ -472,6 +816,20 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getFilterChanges", (id,)).await
     }
 
+    /// Retrieves all the logs matching the filter with the given ID, as
+    /// [`get_logs`](Self::get_logs) would.
+    async fn get_filter_logs(&self, id: U256) -> TransportResult<Vec<Log>> {
+        self.client().request("eth_getFilterLogs", (id,)).await
+    }
+
+    /// Uninstalls the filter with the given ID, returning whether the node found it.
+    ///
+    /// Filters should be uninstalled when no longer polled, see also
+    /// [`install_filter`](Self::install_filter).
+    async fn uninstall_filter(&self, id: U256) -> TransportResult<bool> {
+        self.client().request("eth_uninstallFilter", (id,)).await
+    }
+
     /// Watch for the confirmation of a single pending transaction with the given configuration.
     ///
     /// Note that this is handled internally rather than calling any specific RPC method, and as
@@ -500,6 +858,28 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         RpcWithBlock::new(self.weak_client(), "eth_getProof", (address, keys))
     }
 
+    /// Gets the balance, nonce, code and selected storage slots of [Address] at the given block,
+    /// in a single batch request.
+    ///
+    /// Storage slots are added with [`StateSnapshotAt::slot`] or [`StateSnapshotAt::slots`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::{address, U256};
+    ///
+    /// let addr = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+    /// let snapshot =
+    ///     provider.get_state_snapshot(addr, 19_000_000.into()).slots([U256::ZERO, U256::from(1)]).await?;
+    /// println!("balance: {}, nonce: {}", snapshot.balance, snapshot.nonce);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_state_snapshot(&self, address: Address, block: BlockId) -> StateSnapshotAt<'_, T> {
+        StateSnapshotAt::new(self.client(), address, block)
+    }
+
     /// Gets the specified storage value from [Address].
     fn get_storage_at(
         &self,
@@ -517,6 +897,38 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getTransactionByHash", (hash,)).await
     }
 
+    /// Gets the transaction sent by `sender` with the given `nonce`, if it was included in a
+    /// block.
+    ///
+    /// This uses `ots_getTransactionBySenderAndNonce` when the node supports the Otterscan
+    /// namespace. Otherwise, the block including the transaction is found by binary searching the
+    /// blocks between which the account nonce went past `nonce`, which requires the node to
+    /// serve historical state.
+    ///
+    /// Note that this is handled internally rather than calling a single RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::address;
+    ///
+    /// let sender = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+    /// match provider.get_transaction_by_sender_and_nonce(sender, 42).await? {
+    ///     Some(tx) => println!("nonce 42 was used by {tx:#?}"),
+    ///     None => println!("nonce 42 was not used yet"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn get_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> TransportResult<Option<N::TransactionResponse>> {
+        SenderNonceSearch::new(self, sender).find(nonce).await
+    }
+
     /// Gets the transaction count (AKA "nonce") of the corresponding address.
     #[doc(alias = "get_nonce")]
     #[doc(alias = "get_account_nonce")]
@@ -533,9 +945,36 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getTransactionReceipt", (hash,)).await
     }
 
-    /// Gets an uncle block through the tag [BlockId] and index [u64].
-    async fn get_uncle(&self, tag: BlockId, idx: u64) -> TransportResult<Option<Block>> {
-        let idx = U64::from(idx);
+    /// Waits for the receipt of a transaction, e.g. one submitted by another party.
+    ///
+    /// The receipt is requested every `poll_interval` until it is available. Missing receipts and
+    /// transient errors, such as rate limits or the node still indexing transactions, are retried
+    /// with an exponential backoff capped at 8 times `poll_interval`, so that nodes lagging behind
+    /// a load balancer do not fail the wait. Other errors are returned immediately.
+    ///
+    /// Returns [`WaitForReceiptError::Timeout`] if the transaction was not included within
+    /// `timeout`. Use [`PendingTransactionBuilder`] instead to wait for confirmations of a
+    /// transaction sent through this provider.
+    async fn wait_for_receipt(
+        &self,
+        hash: TxHash,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<N::ReceiptResponse, WaitForReceiptError> {
+        receipt::wait_for_receipt(self, hash, poll_interval, timeout).await
+    }
+
+    /// Gets an uncle block through the tag [BlockId] and index [UncleIndex].
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    async fn get_uncle(
+        &self,
+        tag: BlockId,
+        idx: impl Into<UncleIndex> + Send,
+    ) -> TransportResult<Option<Block>>
+    where
+        Self: Sized,
+    {
+        let idx = idx.into();
         match tag {
             BlockId::Hash(hash) => {
                 self.client()
@@ -590,6 +1029,15 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_newFilter", (filter,)).await
     }
 
+    /// Installs a filter for the logs matching the given filter, which is uninstalled when the
+    /// returned guard is dropped.
+    ///
+    /// See [`FilterGuard`] for more details.
+    async fn install_filter(&self, filter: &Filter) -> TransportResult<FilterGuard<T>> {
+        let id = self.new_filter(filter).await?;
+        Ok(FilterGuard::new(self.weak_client(), id))
+    }
+
     /// Notify the provider that we are interested in new pending transactions.
     ///
     /// If `full` is `true`, the stream will consist of full transaction bodies instead of just the
@@ -617,6 +1065,25 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         Ok(PendingTransactionBuilder::new(self.root(), tx_hash))
     }
 
+    /// Broadcasts a transaction signed on another machine, e.g. an air-gapped one, after checking
+    /// that it is for the chain of the provider.
+    ///
+    /// See [`UnsignedTransactionBundle`](alloy_network::UnsignedTransactionBundle) for the cold
+    /// signing workflow.
+    async fn send_transaction_bundle(
+        &self,
+        bundle: &SignedTransactionBundle,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        let chain_id = self.get_chain_id().await?;
+        if bundle.chain_id != chain_id {
+            return Err(TransportErrorKind::custom_str(&format!(
+                "transaction bundle is for chain {}, but the provider is on chain {chain_id}",
+                bundle.chain_id
+            )));
+        }
+        self.send_raw_transaction(&bundle.raw).await
+    }
+
     /// Broadcasts a transaction to the network.
     ///
     /// Returns a [`PendingTransactionBuilder`] which can be used to configure
@@ -840,6 +1307,47 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.root().get_subscription(id).await
     }
 
+    /// Subscribe to a [`SubscriptionKind`] with the given [`Params`], returning a stream of `R`.
+    ///
+    /// This supports the subscriptions not covered by the dedicated methods, such as
+    /// client-specific kinds, or client-specific options passed with [`Params::Other`]:
+    ///
+    /// [`SubscriptionKind`]: alloy_rpc_types_eth::pubsub::SubscriptionKind
+    /// [`Params`]: alloy_rpc_types_eth::pubsub::Params
+    /// [`Params::Other`]: alloy_rpc_types_eth::pubsub::Params::Other
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_rpc_types_eth::{
+    ///     pubsub::{Params, SubscriptionKind},
+    ///     Transaction,
+    /// };
+    /// use serde_json::json;
+    ///
+    /// let params = Params::Other(json!({ "addresses": [{ "to": "0x0000000000000000000000000000000000000001" }] }));
+    /// let sub = provider
+    ///     .subscribe_to::<Transaction>(SubscriptionKind::AlchemyMinedTransactions, params)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "pubsub")]
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    async fn subscribe_to<R>(
+        &self,
+        kind: alloy_rpc_types_eth::pubsub::SubscriptionKind,
+        params: alloy_rpc_types_eth::pubsub::Params,
+    ) -> TransportResult<alloy_pubsub::Subscription<R>>
+    where
+        R: RpcReturn,
+        Self: Sized,
+    {
+        match params {
+            alloy_rpc_types_eth::pubsub::Params::None => self.subscribe((kind,)).await,
+            params => self.subscribe((kind, params)).await,
+        }
+    }
+
     /// Cancels a subscription given the subscription ID.
     #[cfg(feature = "pubsub")]
     async fn unsubscribe(&self, id: U256) -> TransportResult<()> {
@@ -861,6 +1369,38 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("net_version", ()).map_resp(crate::utils::convert_u64)
     }
 
+    /// Gets the version of the `eth` wire protocol spoken by the node.
+    fn get_protocol_version(&self) -> RpcCall<T, (), U64, u64> {
+        self.client().request("eth_protocolVersion", ()).map_resp(crate::utils::convert_u64)
+    }
+
+    /// Gets the proof-of-work package to mine: the hash of the current block header, the seed
+    /// hash and the boundary condition to be met.
+    async fn get_work(&self) -> TransportResult<Work> {
+        self.client().request("eth_getWork", ()).await
+    }
+
+    /// Submits a proof-of-work solution, returning whether it was accepted.
+    async fn submit_work(
+        &self,
+        nonce: B64,
+        pow_hash: B256,
+        mix_digest: B256,
+    ) -> TransportResult<bool> {
+        self.client().request("eth_submitWork", (nonce, pow_hash, mix_digest)).await
+    }
+
+    /// Submits the hashrate of a remote miner, identified by a random `id`, returning whether it
+    /// was accepted.
+    async fn submit_hashrate(&self, hashrate: U256, id: B256) -> TransportResult<bool> {
+        self.client().request("eth_submitHashrate", (hashrate, id)).await
+    }
+
+    /// Gets the number of hashes per second the node is mining with.
+    fn get_hashrate(&self) -> RpcCall<T, (), U64, u64> {
+        self.client().request("eth_hashrate", ()).map_resp(crate::utils::convert_u64)
+    }
+
     /* ---------------------------------------- raw calls --------------------------------------- */
 
     /// Sends a raw JSON-RPC request.
@@ -1216,6 +1756,99 @@ mod tests {
         assert_eq!(block.header.number.unwrap(), num);
     }
 
+    #[tokio::test]
+    async fn gets_block_transaction_and_uncle_counts() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let block = provider.get_block_by_number(0.into(), false).await.unwrap().unwrap();
+        let hash = block.header.hash.unwrap();
+
+        let count = provider.get_block_transaction_count_by_number(0.into()).await.unwrap();
+        assert_eq!(count, Some(0));
+        let count = provider.get_block_transaction_count(hash.into()).await.unwrap();
+        assert_eq!(count, Some(0));
+
+        assert_eq!(provider.get_uncle_count(hash.into()).await.unwrap(), 0);
+        assert_eq!(provider.get_uncle(0.into(), 0).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn estimates_minimum_gas() {
+        use crate::mock::{rpc_error, MockTransport};
+        use serde_json::json;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let transport = MockTransport::new(move |method, params| {
+            assert_eq!(params[1], "0x7");
+            let gas =
+                params[0]["gas"].as_str().map(|gas| u128::from_str_radix(&gas[2..], 16).unwrap());
+            match (method, gas) {
+                ("eth_estimateGas", None) => Ok(json!("0x186a0")),
+                ("eth_call", Some(gas)) => {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    if gas >= 53_123 {
+                        Ok(json!("0x"))
+                    } else {
+                        Err(rpc_error(-32000, "out of gas"))
+                    }
+                }
+                (method, gas) => unreachable!("{method} {gas:?}"),
+            }
+        });
+        let provider = transport.provider();
+
+        let tx = TransactionRequest::default().to(Address::ZERO);
+        let gas = provider.estimate_minimum_gas(&tx, 7.into()).await.unwrap();
+        assert_eq!(gas, 53_123);
+        // One call at the estimate, then a bisection of the range above the intrinsic gas.
+        assert_eq!(calls.load(Ordering::Relaxed), 17);
+
+        let tx = tx.gas_limit(50_000);
+        assert!(provider.estimate_minimum_gas(&tx, 7.into()).await.is_err());
+
+        // Gas limits below the intrinsic gas are raised to it rather than bisected.
+        let provider = MockTransport::new(|_, _| Ok(json!("0x"))).provider();
+        let tx = TransactionRequest::default().to(Address::ZERO).gas_limit(20_000);
+        assert_eq!(provider.estimate_minimum_gas(&tx, 7.into()).await.unwrap(), 21_000);
+    }
+
+    #[tokio::test]
+    async fn gets_and_submits_work() {
+        use crate::mock::MockTransport;
+        use serde_json::json;
+
+        let transport = MockTransport::new(|method, params| {
+            let result = match method {
+                "eth_getWork" => json!([
+                    B256::with_last_byte(1),
+                    B256::with_last_byte(2),
+                    B256::with_last_byte(3),
+                    "0x10"
+                ]),
+                "eth_submitWork" => json!(params[0] == "0x0000000000000042"),
+                "eth_submitHashrate" => json!(params[0] == "0x500"),
+                "eth_hashrate" => json!("0x500"),
+                "eth_protocolVersion" => json!("0x44"),
+                method => panic!("unexpected method {method}"),
+            };
+            Ok(result)
+        });
+        let provider = transport.provider();
+
+        let work = provider.get_work().await.unwrap();
+        assert_eq!(work.target, B256::with_last_byte(3));
+        assert_eq!(work.number, Some(16));
+
+        let nonce = B64::from(0x42u64);
+        assert!(provider.submit_work(nonce, work.pow_hash, B256::ZERO).await.unwrap());
+        assert!(!provider.submit_work(B64::ZERO, work.pow_hash, B256::ZERO).await.unwrap());
+        assert!(provider.submit_hashrate(U256::from(0x500), B256::ZERO).await.unwrap());
+        assert_eq!(provider.get_hashrate().await.unwrap(), 0x500);
+        assert_eq!(provider.get_protocol_version().await.unwrap(), 0x44);
+    }
+
     #[tokio::test]
     async fn gets_client_version() {
         init_tracing();
@@ -1252,6 +1885,24 @@ mod tests {
         assert_eq!(storage, U256::ZERO);
     }
 
+    #[tokio::test]
+    async fn gets_state_snapshot() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let addr = Address::with_last_byte(16);
+        let snapshot = provider
+            .get_state_snapshot(addr, BlockId::number(0))
+            .slots([U256::ZERO, U256::from(1)])
+            .await
+            .unwrap();
+        assert_eq!(snapshot.address, addr);
+        assert_eq!(snapshot.balance, U256::ZERO);
+        assert_eq!(snapshot.nonce, 0);
+        assert!(!snapshot.has_code());
+        assert_eq!(snapshot.storage.len(), 2);
+        assert_eq!(snapshot.storage_at(U256::ZERO), Some(U256::ZERO));
+    }
+
     #[tokio::test]
     async fn gets_transaction_by_hash_not_found() {
         init_tracing();
@@ -1366,6 +2017,38 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn sends_transaction_bundle() {
+        use crate::mock::MockTransport;
+        use alloy_network::BUNDLE_VERSION;
+
+        // A transfer of 1 wei on chain 31337.
+        let raw = bytes!("f865808477359400825208940000000000000000000000000000000000000000018082f4f5a00505e227c1c636c76fac55795db1a40a4d24840d81b40d2fe0cc85767f6bd202a01e91b437099a8a90234ac5af3cb7ca4fb1432e133f75f9a91678eaf5f487c74b");
+        let hash = b256!("9dae5cf33694a02e8a7d5de3fe31e9d05ca0ba6e9180efac4ab20a06c9e598a3");
+        let transport = MockTransport::new(move |method, _params| {
+            let result = match method {
+                "eth_chainId" => serde_json::json!("0x7a69"),
+                "eth_sendRawTransaction" => serde_json::json!(hash),
+                method => unreachable!("{method}"),
+            };
+            Ok(result)
+        });
+        let provider = transport.provider();
+
+        let mut bundle = SignedTransactionBundle {
+            version: BUNDLE_VERSION,
+            chain_id: 31337,
+            from: address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+            hash,
+            raw,
+        };
+        let pending = provider.send_transaction_bundle(&bundle).await.unwrap();
+        assert_eq!(*pending.tx_hash(), hash);
+
+        bundle.chain_id = 1;
+        assert!(provider.send_transaction_bundle(&bundle).await.is_err());
+    }
+
     #[tokio::test]
     async fn connect_boxed() {
         init_tracing();
@@ -1426,4 +2109,23 @@ mod tests {
         let block = provider.get_block_by_number(0.into(), false).await.unwrap().unwrap();
         assert!(block.transactions.is_hashes());
     }
+
+    #[tokio::test]
+    async fn estimates_gas_with_state_override() {
+        use alloy_network::TransactionBuilder;
+        use alloy_rpc_types_eth::state::{AccountOverride, StateOverride};
+
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let unfunded = address!("000000000000000000000000000000000000dEaD");
+        let tx = TransactionRequest::default()
+            .with_from(unfunded)
+            .with_to(address!("0000000000000000000000000000000000000001"))
+            .with_value(U256::from(1_000_000));
+
+        let account = AccountOverride { balance: Some(U256::MAX), ..Default::default() };
+        let overrides = StateOverride::from([(unfunded, account)]);
+        let gas = provider.estimate_gas(&tx).overrides(&overrides).await.unwrap();
+        assert!(gas >= 21_000);
+    }
 }
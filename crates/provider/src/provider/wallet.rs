@@ -1,5 +1,5 @@
 use crate::{
-    fillers::{FillProvider, JoinFill, TxFiller, WalletFiller},
+    fillers::{FillProvider, JoinFill, PolicyFiller, TxFiller, WalletFiller},
     Provider,
 };
 use alloy_network::{Ethereum, Network, NetworkWallet};
@@ -53,6 +53,24 @@ where
     }
 }
 
+impl<F, N> WalletProvider<N> for PolicyFiller<F>
+where
+    F: WalletProvider<N>,
+    N: Network,
+{
+    type Wallet = F::Wallet;
+
+    #[inline(always)]
+    fn wallet(&self) -> &Self::Wallet {
+        self.inner().wallet()
+    }
+
+    #[inline(always)]
+    fn wallet_mut(&mut self) -> &mut Self::Wallet {
+        self.inner_mut().wallet_mut()
+    }
+}
+
 impl<L, R, N> WalletProvider<N> for JoinFill<L, R>
 where
     R: WalletProvider<N>,
@@ -0,0 +1,160 @@
+use alloy_json_rpc::RpcError;
+use alloy_primitives::B256;
+use alloy_rpc_client::WeakClient;
+use alloy_rpc_types_eth::{BlockNumberOrTag, Withdrawal};
+use alloy_transport::{Transport, TransportErrorKind, TransportResult};
+use futures::{stream::BoxStream, Stream, StreamExt};
+use serde::Deserialize;
+use std::{
+    fmt,
+    ops::RangeInclusive,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The number of blocks requested concurrently by a [`WithdrawalsStream`].
+const CONCURRENT_REQUESTS: usize = 8;
+
+/// The withdrawals of a block, yielded by a [`WithdrawalsStream`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct BlockWithdrawals {
+    /// The number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The withdrawals of the block, empty before Shanghai.
+    #[serde(default, deserialize_with = "deserialize_withdrawals")]
+    pub withdrawals: Vec<Withdrawal>,
+}
+
+fn deserialize_withdrawals<'de, D>(deserializer: D) -> Result<Vec<Withdrawal>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<Vec<Withdrawal>>::deserialize(deserializer).map(Option::unwrap_or_default)
+}
+
+/// A stream of the withdrawals of a range of blocks, returned by
+/// [`Provider::withdrawals_stream`](crate::Provider::withdrawals_stream).
+///
+/// The blocks are requested without their transactions, and only their number, hash and
+/// withdrawals are deserialized. Up to 8 blocks are requested concurrently, and yielded in order.
+/// The stream ends after the last block of the range, or after the first error.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_eips::eip4895::sum_by_address;
+/// use futures::StreamExt;
+///
+/// let mut stream = provider.withdrawals_stream(20_000_000..=20_000_100);
+/// while let Some(block) = stream.next().await {
+///     let block = block?;
+///     for (address, wei) in sum_by_address(&block.withdrawals) {
+///         println!("block {}: {address} received {wei} wei", block.number);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "streams do nothing unless polled"]
+pub struct WithdrawalsStream {
+    inner: BoxStream<'static, TransportResult<BlockWithdrawals>>,
+}
+
+impl fmt::Debug for WithdrawalsStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithdrawalsStream").finish_non_exhaustive()
+    }
+}
+
+impl WithdrawalsStream {
+    /// Creates a new stream of the withdrawals of the blocks of `range`, requested with `client`.
+    pub(crate) fn new<T: Transport + Clone>(
+        client: WeakClient<T>,
+        range: RangeInclusive<u64>,
+    ) -> Self {
+        let inner = futures::stream::iter(range)
+            .map(move |number| {
+                let client = client.clone();
+                async move {
+                    let client = client.upgrade().ok_or_else(TransportErrorKind::backend_gone)?;
+                    let params = (BlockNumberOrTag::Number(number), false);
+                    client
+                        .request::<_, Option<BlockWithdrawals>>("eth_getBlockByNumber", params)
+                        .await?
+                        .ok_or(RpcError::NullResp)
+                }
+            })
+            .buffered(CONCURRENT_REQUESTS)
+            .scan(false, |failed, result| {
+                // End the stream after the first error.
+                let item = (!*failed).then(|| {
+                    *failed = result.is_err();
+                    result
+                });
+                async move { item }
+            });
+        Self { inner: inner.boxed() }
+    }
+}
+
+impl Stream for WithdrawalsStream {
+    type Item = TransportResult<BlockWithdrawals>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock::MockTransport, Provider};
+    use alloy_eips::eip4895::total_amount_wei;
+    use alloy_primitives::U256;
+    use futures::StreamExt;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn streams_withdrawals() {
+        let transport = MockTransport::new(|method, params| {
+            assert_eq!(method, "eth_getBlockByNumber");
+            assert_eq!(params[1], false);
+            let number = u64::from_str_radix(&params[0].as_str().unwrap()[2..], 16).unwrap();
+            // Block 2 is pre-Shanghai, and block 4 does not exist.
+            let result = match number {
+                2 => json!({ "number": "0x2", "hash": format!("0x{number:064x}"), "miner": "0x0" }),
+                4 => Value::Null,
+                _ => json!({
+                    "number": format!("{number:#x}"),
+                    "hash": format!("0x{number:064x}"),
+                    "withdrawals": [{
+                        "index": format!("{number:#x}"),
+                        "validatorIndex": "0x1",
+                        "address": "0x0000000000000000000000000000000000000001",
+                        "amount": format!("{number:#x}"),
+                    }],
+                }),
+            };
+            Ok(result)
+        });
+        let provider = transport.provider();
+
+        let blocks = provider.withdrawals_stream(1..=6).collect::<Vec<_>>().await;
+        assert_eq!(blocks.len(), 4);
+        let blocks = blocks.into_iter().take(3).map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(blocks.iter().map(|block| block.number).collect::<Vec<_>>(), [1, 2, 3]);
+        assert!(blocks[1].withdrawals.is_empty());
+        assert_eq!(
+            total_amount_wei(blocks.iter().flat_map(|block| &block.withdrawals)),
+            U256::from(4_000_000_000u64)
+        );
+
+        let mut stream = provider.withdrawals_stream(3..=5);
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+    }
+}
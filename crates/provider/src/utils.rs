@@ -1,6 +1,14 @@
 //! Provider-related utilities.
 
-use alloy_primitives::{U128, U64};
+use crate::Provider;
+use alloy_eips::{
+    eip2935::{block_hash_slot, is_served, HISTORY_STORAGE_ADDRESS},
+    eip4788::{beacon_root_slot, timestamp_slot, BEACON_ROOTS_ADDRESS},
+    BlockId,
+};
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{BlockNumber, B256, U128, U256, U64};
+use alloy_transport::{Transport, TransportResult};
 
 /// The number of blocks from the past for which the fee rewards are fetched for fee estimation.
 pub const EIP1559_FEE_ESTIMATION_PAST_BLOCKS: u64 = 10;
@@ -55,6 +63,98 @@ pub fn eip1559_default_estimator(
     }
 }
 
+/// Returns the parent beacon block root of the block with the given timestamp, as stored in the
+/// [EIP-4788] beacon roots contract, at the latest block.
+///
+/// Returns `None` if no root is stored for the timestamp: either no block has this timestamp, or
+/// its root was overwritten in the ring buffer of the contract by a more recent block.
+///
+/// [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
+pub async fn get_parent_beacon_block_root<P, T, N>(
+    provider: &P,
+    timestamp: u64,
+) -> TransportResult<Option<B256>>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    // The contract rejects the zero timestamp, which would match any empty slot.
+    if timestamp == 0 {
+        return Ok(None);
+    }
+
+    // Both slots are read at the same block, so that they are not updated in between.
+    let block = BlockId::number(provider.get_block_number().await?);
+    let stored_timestamp = provider
+        .get_storage_at(BEACON_ROOTS_ADDRESS, timestamp_slot(timestamp))
+        .block_id(block)
+        .await?;
+    if stored_timestamp != U256::from(timestamp) {
+        return Ok(None);
+    }
+    let root = provider
+        .get_storage_at(BEACON_ROOTS_ADDRESS, beacon_root_slot(timestamp))
+        .block_id(block)
+        .await?;
+    Ok(Some(root.into()))
+}
+
+/// Returns the hash of the block with the given number, as stored in the [EIP-2935] history
+/// storage contract, at the latest block.
+///
+/// Unlike the `BLOCKHASH` opcode, which only serves the last 256 blocks, the contract serves the
+/// hashes of the last [`HISTORY_SERVE_WINDOW`] blocks.
+///
+/// Returns `None` if the block is not within the window of the latest block, or if its hash was
+/// not stored, e.g. because it was produced before the contract was activated.
+///
+/// [EIP-2935]: https://eips.ethereum.org/EIPS/eip-2935
+/// [`HISTORY_SERVE_WINDOW`]: alloy_eips::eip2935::HISTORY_SERVE_WINDOW
+pub async fn get_historical_block_hash<P, T, N>(
+    provider: &P,
+    number: BlockNumber,
+) -> TransportResult<Option<B256>>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    let latest = provider.get_block_number().await?;
+    if !is_served(latest, number) {
+        return Ok(None);
+    }
+    let hash = provider
+        .get_storage_at(HISTORY_STORAGE_ADDRESS, block_hash_slot(number))
+        .block_id(BlockId::number(latest))
+        .await?;
+    Ok(Some(hash.into()).filter(|hash: &B256| !hash.is_zero()))
+}
+
+/// Returns the hash of the block with the given number by calling the [EIP-2935] history storage
+/// contract at the given block.
+///
+/// Returns `None` if the contract does not serve the hash, i.e. returns the zero hash. The call
+/// fails if the block number is not lower than the number of the block the call is executed at.
+///
+/// [EIP-2935]: https://eips.ethereum.org/EIPS/eip-2935
+pub async fn call_historical_block_hash<P, T, N>(
+    provider: &P,
+    number: BlockNumber,
+    block: BlockId,
+) -> TransportResult<Option<B256>>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    let tx = N::TransactionRequest::default()
+        .with_to(HISTORY_STORAGE_ADDRESS)
+        .with_input(U256::from(number).to_be_bytes::<32>());
+    let output = provider.call(&tx).block(block).await?;
+    Ok(Some(B256::left_padding_from(&output)).filter(|hash| !hash.is_zero()))
+}
+
 /// Convert `U128` to `u128`.
 pub(crate) fn convert_u128(r: U128) -> u128 {
     r.to::<u128>()
@@ -67,6 +167,8 @@ pub(crate) fn convert_u64(r: U64) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock::MockTransport;
+    use serde_json::json;
     use std::vec;
 
     #[test]
@@ -122,4 +224,70 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn gets_parent_beacon_block_root() {
+        let root = B256::repeat_byte(0xbe);
+        let transport = MockTransport::new(move |method, params| {
+            let result = match method {
+                "eth_blockNumber" => json!("0x64"),
+                "eth_getStorageAt" => {
+                    assert_eq!(params[0], json!(BEACON_ROOTS_ADDRESS));
+                    assert_eq!(params[2], json!("0x64"));
+                    let slot: U256 = serde_json::from_value(params[1].clone()).unwrap();
+                    if slot == timestamp_slot(1000) {
+                        json!(U256::from(1000))
+                    } else if slot == beacon_root_slot(1000) {
+                        json!(root)
+                    } else {
+                        json!(B256::ZERO)
+                    }
+                }
+                method => panic!("unexpected method {method}"),
+            };
+            Ok(result)
+        });
+        let provider = transport.provider();
+
+        assert_eq!(get_parent_beacon_block_root(&provider, 1000).await.unwrap(), Some(root));
+        // Overwritten by the block with the timestamp 1000 in the ring buffer.
+        assert_eq!(get_parent_beacon_block_root(&provider, 1000 + 8191).await.unwrap(), None);
+        assert_eq!(get_parent_beacon_block_root(&provider, 1001).await.unwrap(), None);
+        assert_eq!(get_parent_beacon_block_root(&provider, 0).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn gets_historical_block_hash() {
+        let hash = B256::repeat_byte(0x35);
+        let transport = MockTransport::new(move |method, params| {
+            let result = match method {
+                "eth_blockNumber" => json!("0x2710"),
+                "eth_getStorageAt" => {
+                    assert_eq!(params[0], json!(HISTORY_STORAGE_ADDRESS));
+                    assert_eq!(params[2], json!("0x2710"));
+                    let slot: U256 = serde_json::from_value(params[1].clone()).unwrap();
+                    json!(if slot == block_hash_slot(5000) { hash } else { B256::ZERO })
+                }
+                "eth_call" => {
+                    assert_eq!(params[0]["to"], json!(HISTORY_STORAGE_ADDRESS));
+                    assert_eq!(params[1], json!("latest"));
+                    let input: U256 = serde_json::from_value(params[0]["input"].clone()).unwrap();
+                    json!(if input == U256::from(5000) { hash } else { B256::ZERO })
+                }
+                method => panic!("unexpected method {method}"),
+            };
+            Ok(result)
+        });
+        let provider = transport.provider();
+
+        assert_eq!(get_historical_block_hash(&provider, 5000).await.unwrap(), Some(hash));
+        assert_eq!(get_historical_block_hash(&provider, 5001).await.unwrap(), None);
+        // Out of the window of the latest block, while sharing the slot of the block 5000.
+        assert_eq!(get_historical_block_hash(&provider, 5000 + 8192).await.unwrap(), None);
+        assert_eq!(get_historical_block_hash(&provider, 1000).await.unwrap(), None);
+
+        let latest = BlockId::latest();
+        assert_eq!(call_historical_block_hash(&provider, 5000, latest).await.unwrap(), Some(hash));
+        assert_eq!(call_historical_block_hash(&provider, 5001, latest).await.unwrap(), None);
+    }
 }
@@ -1,14 +1,16 @@
 use crate::{ix::PubSubInstruction, managers::InFlight, RawSubscription};
-use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
+use alloy_json_rpc::{
+    IdMode, RequestPacket, Response, ResponsePacket, SerializedRequest, ServerNotification,
+};
 use alloy_primitives::U256;
 use alloy_transport::{TransportError, TransportErrorKind, TransportFut, TransportResult};
 use futures::{future::try_join_all, FutureExt, TryFutureExt};
 use std::{
     future::Future,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
 
 /// A `PubSubFrontend` is [`Transport`] composed of a channel to a running
 /// PubSub service.
@@ -20,31 +22,82 @@ pub struct PubSubFrontend {
     /// The number of items to buffer in new subscription channels. Defaults to
     /// 16. See [`tokio::sync::broadcast::channel`] for a description.
     channel_size: AtomicUsize,
+    /// Whether response IDs are matched leniently. Defaults to `false`.
+    lenient_ids: AtomicBool,
+    /// The sender of server notifications other than subscription
+    /// notifications.
+    notifications: broadcast::Sender<ServerNotification>,
 }
 
 impl Clone for PubSubFrontend {
     fn clone(&self) -> Self {
         let channel_size = self.channel_size.load(Ordering::Relaxed);
-        Self { tx: self.tx.clone(), channel_size: AtomicUsize::new(channel_size) }
+        let lenient_ids = self.lenient_ids.load(Ordering::Relaxed);
+        Self {
+            tx: self.tx.clone(),
+            channel_size: AtomicUsize::new(channel_size),
+            lenient_ids: AtomicBool::new(lenient_ids),
+            notifications: self.notifications.clone(),
+        }
     }
 }
 
 impl PubSubFrontend {
     /// Create a new frontend.
-    pub(crate) const fn new(tx: mpsc::UnboundedSender<PubSubInstruction>) -> Self {
-        Self { tx, channel_size: AtomicUsize::new(16) }
+    pub(crate) const fn new(
+        tx: mpsc::UnboundedSender<PubSubInstruction>,
+        notifications: broadcast::Sender<ServerNotification>,
+    ) -> Self {
+        Self {
+            tx,
+            channel_size: AtomicUsize::new(16),
+            lenient_ids: AtomicBool::new(false),
+            notifications,
+        }
+    }
+
+    /// Get a receiver of the notifications sent by the server with a method
+    /// other than `eth_subscription`, such as client-specific events.
+    ///
+    /// Only notifications received after this call are delivered.
+    pub fn server_notifications(&self) -> broadcast::Receiver<ServerNotification> {
+        self.notifications.subscribe()
     }
 
     /// Get the subscription ID for a local ID.
     pub fn get_subscription(
         &self,
         id: U256,
+    ) -> impl Future<Output = TransportResult<RawSubscription>> + Send + 'static {
+        self.get_sub(id, None)
+    }
+
+    /// Get the subscription ID for a local ID, buffering up to `capacity`
+    /// notifications for slow receivers instead of the configured
+    /// [channel size](Self::channel_size).
+    ///
+    /// The capacity only applies if the subscription has no other receivers
+    /// yet, e.g. right after subscribing. Otherwise the receiver shares the
+    /// existing channel.
+    pub fn get_subscription_with_capacity(
+        &self,
+        id: U256,
+        capacity: usize,
+    ) -> impl Future<Output = TransportResult<RawSubscription>> + Send + 'static {
+        debug_assert_ne!(capacity, 0, "channel size must be non-zero");
+        self.get_sub(id, Some(capacity))
+    }
+
+    fn get_sub(
+        &self,
+        id: U256,
+        channel_size: Option<usize>,
     ) -> impl Future<Output = TransportResult<RawSubscription>> + Send + 'static {
         let backend_tx = self.tx.clone();
         async move {
             let (tx, rx) = oneshot::channel();
             backend_tx
-                .send(PubSubInstruction::GetSub(id, tx))
+                .send(PubSubInstruction::GetSub(id, channel_size, tx))
                 .map_err(|_| TransportErrorKind::backend_gone())?;
             rx.await.map_err(|_| TransportErrorKind::backend_gone())
         }
@@ -64,9 +117,10 @@ impl PubSubFrontend {
     ) -> impl Future<Output = TransportResult<Response>> + Send + 'static {
         let tx = self.tx.clone();
         let channel_size = self.channel_size.load(Ordering::Relaxed);
+        let id_mode = self.id_mode();
 
         async move {
-            let (in_flight, rx) = InFlight::new(req, channel_size);
+            let (in_flight, rx) = InFlight::new(req, channel_size, id_mode);
             tx.send(PubSubInstruction::Request(in_flight))
                 .map_err(|_| TransportErrorKind::backend_gone())?;
             rx.await.map_err(|_| TransportErrorKind::backend_gone())?
@@ -101,6 +155,22 @@ impl PubSubFrontend {
         debug_assert_ne!(channel_size, 0, "channel size must be non-zero");
         self.channel_size.store(channel_size, Ordering::Relaxed);
     }
+
+    /// Get how the IDs of responses are matched with the IDs of requests.
+    /// Defaults to [`IdMode::Strict`].
+    pub fn id_mode(&self) -> IdMode {
+        if self.lenient_ids.load(Ordering::Relaxed) {
+            IdMode::Lenient
+        } else {
+            IdMode::Strict
+        }
+    }
+
+    /// Set how the IDs of responses are matched with the IDs of requests.
+    /// Responses are always passed on with the ID of their request.
+    pub fn set_id_mode(&self, id_mode: IdMode) {
+        self.lenient_ids.store(id_mode.is_lenient(), Ordering::Relaxed);
+    }
 }
 
 impl tower::Service<RequestPacket> for PubSubFrontend {
@@ -7,8 +7,9 @@ use tokio::sync::oneshot;
 pub(crate) enum PubSubInstruction {
     /// Send a request.
     Request(InFlight),
-    /// Get the subscription ID for a local ID.
-    GetSub(U256, oneshot::Sender<RawSubscription>),
+    /// Get the subscription ID for a local ID, optionally with a channel of
+    /// the given capacity.
+    GetSub(U256, Option<usize>, oneshot::Sender<RawSubscription>),
     /// Unsubscribe from a subscription.
     Unsubscribe(U256),
 }
@@ -17,7 +18,7 @@ impl fmt::Debug for PubSubInstruction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Request(arg0) => f.debug_tuple("Request").field(arg0).finish(),
-            Self::GetSub(arg0, _) => f.debug_tuple("GetSub").field(arg0).finish(),
+            Self::GetSub(arg0, arg1, _) => f.debug_tuple("GetSub").field(arg0).field(arg1).finish(),
             Self::Unsubscribe(arg0) => f.debug_tuple("Unsubscribe").field(arg0).finish(),
         }
     }
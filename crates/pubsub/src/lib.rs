@@ -25,4 +25,4 @@ mod managers;
 mod service;
 
 mod sub;
-pub use sub::{RawSubscription, Subscription, SubscriptionItem};
+pub use sub::{LagPolicy, RawSubscription, Subscription, SubscriptionItem};
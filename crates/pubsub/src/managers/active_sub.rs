@@ -14,6 +14,8 @@ pub(crate) struct ActiveSubscription {
     pub(crate) request: SerializedRequest,
     /// The channel via which notifications are broadcast.
     pub(crate) tx: broadcast::Sender<Box<RawValue>>,
+    /// The capacity of the channel.
+    pub(crate) channel_size: usize,
 }
 
 // NB: We implement this to prevent any incorrect future implementations.
@@ -50,6 +52,7 @@ impl fmt::Debug for ActiveSubscription {
             .field("local_id", &self.local_id)
             .field("request", &self.request)
             .field("subscribers", &self.tx.receiver_count())
+            .field("channel_size", &self.channel_size)
             .finish()
     }
 }
@@ -59,7 +62,21 @@ impl ActiveSubscription {
     pub(crate) fn new(request: SerializedRequest, channel_size: usize) -> Self {
         let local_id = request.params_hash();
         let (tx, _rx) = broadcast::channel(channel_size);
-        Self { request, local_id, tx }
+        Self { request, local_id, tx, channel_size }
+    }
+
+    /// Replace the channel with one of the given capacity, if there are no
+    /// receivers yet. Returns `true` if the channel has the given capacity.
+    pub(crate) fn set_channel_size(&mut self, channel_size: usize) -> bool {
+        if channel_size == self.channel_size {
+            return true;
+        }
+        if self.tx.receiver_count() > 0 {
+            return false;
+        }
+        (self.tx, _) = broadcast::channel(channel_size);
+        self.channel_size = channel_size;
+        true
     }
 
     /// Serialize the request as a boxed [`RawValue`].
@@ -71,7 +88,11 @@ impl ActiveSubscription {
 
     /// Get a subscription.
     pub(crate) fn subscribe(&self) -> RawSubscription {
-        RawSubscription { rx: self.tx.subscribe(), local_id: self.local_id }
+        RawSubscription {
+            rx: self.tx.subscribe(),
+            local_id: self.local_id,
+            lag_policy: Default::default(),
+        }
     }
 
     /// Notify the subscription channel of a new value, if any receiver exists.
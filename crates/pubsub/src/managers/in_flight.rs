@@ -1,4 +1,4 @@
-use alloy_json_rpc::{Response, ResponsePayload, SerializedRequest};
+use alloy_json_rpc::{IdMode, Response, ResponsePayload, SerializedRequest};
 use alloy_primitives::U256;
 use alloy_transport::{TransportError, TransportResult};
 use std::fmt;
@@ -15,6 +15,9 @@ pub(crate) struct InFlight {
     /// The number of items to buffer in the subscription channel.
     pub(crate) channel_size: usize,
 
+    /// How the ID of the response is matched with the ID of the request.
+    pub(crate) id_mode: IdMode,
+
     /// The channel to send the response on.
     pub(crate) tx: oneshot::Sender<TransportResult<Response>>,
 }
@@ -24,6 +27,7 @@ impl fmt::Debug for InFlight {
         f.debug_struct("InFlight")
             .field("request", &self.request)
             .field("channel_size", &self.channel_size)
+            .field("id_mode", &self.id_mode)
             .field("tx_is_closed", &self.tx.is_closed())
             .finish()
     }
@@ -34,10 +38,11 @@ impl InFlight {
     pub(crate) fn new(
         request: SerializedRequest,
         channel_size: usize,
+        id_mode: IdMode,
     ) -> (Self, oneshot::Receiver<TransportResult<Response>>) {
         let (tx, rx) = oneshot::channel();
 
-        (Self { request, channel_size, tx }, rx)
+        (Self { request, channel_size, id_mode, tx }, rx)
     }
 
     /// Check if the request is a subscription.
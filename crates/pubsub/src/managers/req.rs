@@ -30,10 +30,52 @@ impl RequestManager {
     /// If the request created a new subscription, this function returns the
     /// subscription ID and the in-flight request for conversion to an
     /// `ActiveSubscription`.
-    pub(crate) fn handle_response(&mut self, resp: Response) -> Option<(U256, InFlight)> {
-        if let Some(in_flight) = self.reqs.remove(&resp.id) {
-            return in_flight.fulfill(resp);
+    pub(crate) fn handle_response(&mut self, mut resp: Response) -> Option<(U256, InFlight)> {
+        let in_flight = match self.reqs.remove(&resp.id) {
+            Some(in_flight) => in_flight,
+            None => {
+                let id = resp.id.alternate()?;
+                if !self.reqs.get(&id)?.id_mode.is_lenient() {
+                    return None;
+                }
+                // Answer with the ID of the request.
+                resp.id = id;
+                self.reqs.remove(&resp.id)?
+            }
+        };
+        in_flight.fulfill(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{IdMode, Request, ResponsePayload};
+    use serde_json::value::RawValue;
+
+    fn response(id: Id) -> Response {
+        Response {
+            id,
+            payload: ResponsePayload::Success(RawValue::from_string("1".into()).unwrap()),
         }
-        None
+    }
+
+    #[test]
+    fn matches_string_ids_leniently() {
+        let mut manager = RequestManager::default();
+        let mut insert = |id, id_mode| {
+            let request = Request::new("eth_blockNumber", Id::Number(id), ()).serialize().unwrap();
+            let (in_flight, rx) = InFlight::new(request, 0, id_mode);
+            manager.insert(in_flight);
+            rx
+        };
+        let mut strict = insert(1, IdMode::Strict);
+        let mut lenient = insert(2, IdMode::Lenient);
+
+        assert!(manager.handle_response(response(Id::String("1".into()))).is_none());
+        assert!(manager.handle_response(response(Id::String("2".into()))).is_none());
+        assert!(strict.try_recv().is_err());
+        assert_eq!(lenient.try_recv().unwrap().unwrap().id, Id::Number(2));
+        assert_eq!(manager.len(), 1);
     }
 }
@@ -2,6 +2,7 @@ use crate::{managers::ActiveSubscription, RawSubscription};
 use alloy_json_rpc::{EthNotification, SerializedRequest};
 use alloy_primitives::{B256, U256};
 use bimap::BiBTreeMap;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Default)]
 pub(crate) struct SubscriptionManager {
@@ -9,6 +10,8 @@ pub(crate) struct SubscriptionManager {
     local_to_sub: BiBTreeMap<B256, ActiveSubscription>,
     /// Tracks the CURRENT server id for a subscription.
     local_to_server: BiBTreeMap<B256, U256>,
+    /// The number of consumers sharing each subscription.
+    consumers: BTreeMap<B256, usize>,
 }
 
 impl SubscriptionManager {
@@ -35,11 +38,13 @@ impl SubscriptionManager {
         let local_id = active.local_id;
         self.local_to_server.insert(local_id, server_id);
         self.local_to_sub.insert(local_id, active);
+        self.consumers.insert(local_id, 1);
 
         sub
     }
 
-    /// Insert or update the server_id for a subscription.
+    /// Insert or update the server_id for a subscription. A new subscription
+    /// has a single consumer.
     pub(crate) fn upsert(
         &mut self,
         request: SerializedRequest,
@@ -58,6 +63,33 @@ impl SubscriptionManager {
         }
     }
 
+    /// Returns `true` if a subscription with the given local_id is active.
+    pub(crate) fn contains(&self, local_id: B256) -> bool {
+        self.local_to_sub.contains_left(&local_id)
+    }
+
+    /// Add a consumer to a subscription.
+    pub(crate) fn add_consumer(&mut self, local_id: B256) {
+        *self.consumers.entry(local_id).or_default() += 1;
+    }
+
+    /// Remove a consumer from a subscription, returning `true` if no consumer
+    /// is left.
+    pub(crate) fn remove_consumer(&mut self, local_id: B256) -> bool {
+        match self.consumers.get_mut(&local_id) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Get the current server_id of a subscription, if it has one.
+    pub(crate) fn server_id_for(&self, local_id: B256) -> Option<U256> {
+        self.local_to_server.get_by_left(&local_id).copied()
+    }
+
     /// De-alias an alias, getting the original ID.
     pub(crate) fn local_id_for(&self, server_id: U256) -> Option<B256> {
         self.local_to_server.get_by_right(&server_id).copied()
@@ -77,6 +109,7 @@ impl SubscriptionManager {
     pub(crate) fn remove_sub(&mut self, local_id: B256) {
         let _ = self.local_to_sub.remove_by_left(&local_id);
         let _ = self.local_to_server.remove_by_left(&local_id);
+        let _ = self.consumers.remove(&local_id);
     }
 
     /// Notify the subscription channel of a new value, if the sub is known,
@@ -95,4 +128,57 @@ impl SubscriptionManager {
     pub(crate) fn get_subscription(&self, local_id: B256) -> Option<RawSubscription> {
         self.local_to_sub.get_by_left(&local_id).map(ActiveSubscription::subscribe)
     }
+
+    /// Get a receiver for a subscription, with a channel of the given
+    /// capacity if it has no other receivers yet.
+    pub(crate) fn get_subscription_with_capacity(
+        &mut self,
+        local_id: B256,
+        channel_size: usize,
+    ) -> Option<RawSubscription> {
+        let (_, mut sub) = self.local_to_sub.remove_by_left(&local_id)?;
+        if !sub.set_channel_size(channel_size) {
+            debug!(
+                %local_id,
+                channel_size = sub.channel_size,
+                "subscription channel already in use, keeping its capacity"
+            );
+        }
+        let rx = sub.subscribe();
+        self.local_to_sub.insert(local_id, sub);
+        Some(rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request};
+    use serde_json::value::RawValue;
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    #[test]
+    fn per_subscription_capacity() {
+        let mut subs = SubscriptionManager::default();
+        let request =
+            Request::new("eth_subscribe", Id::Number(1), ("newHeads",)).serialize().unwrap();
+        let local_id = request.params_hash();
+        let server_id = U256::from(1);
+        drop(subs.upsert(request, server_id, 16));
+
+        let notify = |subs: &mut SubscriptionManager, i: u64| {
+            let result = RawValue::from_string(i.to_string()).unwrap();
+            subs.notify(EthNotification { subscription: server_id, result });
+        };
+
+        let mut rx = subs.get_subscription_with_capacity(local_id, 2).unwrap();
+        // The channel is in use, so its capacity is kept.
+        let mut other = subs.get_subscription_with_capacity(local_id, 64).unwrap();
+        for i in 0..3 {
+            notify(&mut subs, i);
+        }
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Lagged(1))));
+        assert_eq!(rx.try_recv().unwrap().get(), "1");
+        assert!(matches!(other.try_recv(), Err(TryRecvError::Lagged(1))));
+    }
 }
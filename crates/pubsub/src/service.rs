@@ -4,14 +4,19 @@ use crate::{
     managers::{InFlight, RequestManager, SubscriptionManager},
     PubSubConnect, PubSubFrontend, RawSubscription,
 };
-use alloy_json_rpc::{Id, PubSubItem, Request, Response, ResponsePayload};
-use alloy_primitives::U256;
+use alloy_json_rpc::{
+    Id, IdMode, PubSubItem, Request, Response, ResponsePayload, ServerNotification,
+};
+use alloy_primitives::{B256, U256};
 use alloy_transport::{
     utils::{to_json_raw_value, Spawnable},
     TransportErrorKind, TransportResult,
 };
 use serde_json::value::RawValue;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+/// The number of server notifications buffered for each receiver.
+const SERVER_NOTIFICATIONS_CHANNEL_SIZE: usize = 16;
 
 /// The service contains the backend handle, a subscription manager, and the
 /// configuration details required to reconnect.
@@ -31,6 +36,10 @@ pub(crate) struct PubSubService<T> {
 
     /// The request manager.
     pub(crate) in_flights: RequestManager,
+
+    /// The sender of server notifications other than subscription
+    /// notifications.
+    pub(crate) notifications: broadcast::Sender<ServerNotification>,
 }
 
 impl<T: PubSubConnect> PubSubService<T> {
@@ -39,15 +48,17 @@ impl<T: PubSubConnect> PubSubService<T> {
         let handle = connector.connect().await?;
 
         let (tx, reqs) = mpsc::unbounded_channel();
+        let (notifications, _) = broadcast::channel(SERVER_NOTIFICATIONS_CHANNEL_SIZE);
         let this = Self {
             handle,
             connector,
             reqs,
             subs: SubscriptionManager::default(),
             in_flights: Default::default(),
+            notifications: notifications.clone(),
         };
         this.spawn();
-        Ok(PubSubFrontend::new(tx))
+        Ok(PubSubFrontend::new(tx, notifications))
     }
 
     /// Reconnect by dropping the backend and creating a new one.
@@ -92,7 +103,7 @@ impl<T: PubSubConnect> PubSubService<T> {
             let req = sub.request().to_owned();
             // 0 is a dummy value, we don't care about the channel size here,
             // as none of these will result in channel creation.
-            let (in_flight, _) = InFlight::new(req.clone(), 0);
+            let (in_flight, _) = InFlight::new(req.clone(), 0, IdMode::Strict);
             self.in_flights.insert(in_flight);
 
             let msg = req.into_serialized();
@@ -108,7 +119,20 @@ impl<T: PubSubConnect> PubSubService<T> {
     }
 
     /// Service a request.
+    ///
+    /// Subscriptions with the same params as an active subscription are not
+    /// sent to the server. They share the active subscription instead, each
+    /// consumer getting its own receiver.
     fn service_request(&mut self, in_flight: InFlight) -> TransportResult<()> {
+        if in_flight.is_subscription() {
+            let local_id = in_flight.request.params_hash();
+            if self.subs.contains(local_id) {
+                trace!(%local_id, "sharing active subscription");
+                self.subs.add_consumer(local_id);
+                return Self::respond_with_local_id(in_flight, local_id);
+            }
+        }
+
         let brv = in_flight.request();
 
         self.dispatch_request(brv.serialized().to_owned())?;
@@ -123,19 +147,41 @@ impl<T: PubSubConnect> PubSubService<T> {
     /// the subscription does not exist, the waiter is sent nothing, and the
     /// `tx` is dropped. This notifies the waiter that the subscription does
     /// not exist.
-    fn service_get_sub(&mut self, local_id: U256, tx: oneshot::Sender<RawSubscription>) {
-        if let Some(rx) = self.subs.get_subscription(local_id.into()) {
+    fn service_get_sub(
+        &mut self,
+        local_id: U256,
+        channel_size: Option<usize>,
+        tx: oneshot::Sender<RawSubscription>,
+    ) {
+        let rx = match channel_size {
+            Some(channel_size) => {
+                self.subs.get_subscription_with_capacity(local_id.into(), channel_size)
+            }
+            None => self.subs.get_subscription(local_id.into()),
+        };
+        if let Some(rx) = rx {
             let _ = tx.send(rx);
         }
     }
 
     /// Service an unsubscribe instruction.
+    ///
+    /// The server subscription is only cancelled once every consumer sharing
+    /// it has unsubscribed.
     fn service_unsubscribe(&mut self, local_id: U256) -> TransportResult<()> {
-        let req = Request::new("eth_unsubscribe", Id::None, [local_id]);
-        let brv = req.serialize().expect("no ser error").take_request();
+        let local_id = local_id.into();
+        if !self.subs.remove_consumer(local_id) {
+            trace!(%local_id, "subscription still has consumers");
+            return Ok(());
+        }
 
-        self.dispatch_request(brv)?;
-        self.subs.remove_sub(local_id.into());
+        // The subscription has no server_id while reconnecting.
+        if let Some(server_id) = self.subs.server_id_for(local_id) {
+            let req = Request::new("eth_unsubscribe", Id::None, [server_id]);
+            let brv = req.serialize().expect("no ser error").take_request();
+            self.dispatch_request(brv)?;
+        }
+        self.subs.remove_sub(local_id);
         Ok(())
     }
 
@@ -144,8 +190,8 @@ impl<T: PubSubConnect> PubSubService<T> {
         trace!(?ix, "servicing instruction");
         match ix {
             PubSubInstruction::Request(in_flight) => self.service_request(in_flight),
-            PubSubInstruction::GetSub(alias, tx) => {
-                self.service_get_sub(alias, tx);
+            PubSubInstruction::GetSub(alias, channel_size, tx) => {
+                self.service_get_sub(alias, channel_size, tx);
                 Ok(())
             }
             PubSubInstruction::Unsubscribe(alias) => self.service_unsubscribe(alias),
@@ -163,18 +209,43 @@ impl<T: PubSubConnect> PubSubService<T> {
                 self.subs.notify(notification);
                 Ok(())
             }
+            PubSubItem::ServerNotification(notification) => {
+                trace!(method = %notification.method, "received server notification");
+                // We don't care if there are no receivers.
+                let _ = self.notifications.send(notification);
+                Ok(())
+            }
         }
     }
 
     /// Rewrite the subscription id and insert into the subscriptions manager
     fn handle_sub_response(&mut self, in_flight: InFlight, server_id: U256) -> TransportResult<()> {
-        let request = in_flight.request;
-        let id = request.id().clone();
+        let local_id = in_flight.request.params_hash();
+
+        // Identical subscriptions sent before the first response arrived
+        // result in duplicate server subscriptions. Keep the current one.
+        if let Some(current) = self.subs.server_id_for(local_id) {
+            if current != server_id {
+                debug!(%local_id, %server_id, "dropping duplicate server subscription");
+                let req = Request::new("eth_unsubscribe", Id::None, [server_id]);
+                self.dispatch_request(req.serialize().expect("no ser error").take_request())?;
+                self.subs.add_consumer(local_id);
+                return Self::respond_with_local_id(in_flight, local_id);
+            }
+        }
 
-        self.subs.upsert(request, server_id, in_flight.channel_size);
+        // Responses to the subscriptions re-issued on reconnection update the
+        // server_id of an active subscription, without adding a consumer.
+        self.subs.upsert(in_flight.request.clone(), server_id, in_flight.channel_size);
 
         // lie to the client about the sub id.
-        let local_id = self.subs.local_id_for(server_id).unwrap();
+        Self::respond_with_local_id(in_flight, local_id)
+    }
+
+    /// Respond to a subscription request with the local id of the
+    /// subscription.
+    fn respond_with_local_id(in_flight: InFlight, local_id: B256) -> TransportResult<()> {
+        let id = in_flight.request.id().clone();
         // Serialized B256 is always a valid serialized U256 too.
         let ser_alias = to_json_raw_value(&local_id)?;
 
@@ -233,3 +304,114 @@ impl<T: PubSubConnect> PubSubService<T> {
         fut.spawn_task();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConnectionInterface;
+    use alloy_json_rpc::EthNotification;
+    use serde_json::{json, Value};
+
+    /// Connects to a backend driven by the test.
+    struct MockConnect(mpsc::UnboundedSender<ConnectionInterface>);
+
+    impl PubSubConnect for MockConnect {
+        fn is_local(&self) -> bool {
+            true
+        }
+
+        async fn connect(&self) -> TransportResult<ConnectionHandle> {
+            let (handle, interface) = ConnectionHandle::new();
+            self.0.send(interface).unwrap();
+            Ok(handle)
+        }
+    }
+
+    async fn recv(interface: &mut ConnectionInterface) -> Value {
+        serde_json::from_str(interface.recv_from_frontend().await.unwrap().get()).unwrap()
+    }
+
+    fn respond(interface: &ConnectionInterface, request: &Value, server_id: u64) {
+        let id = serde_json::from_value(request["id"].clone()).unwrap();
+        let payload = ResponsePayload::Success(to_json_raw_value(&U256::from(server_id)).unwrap());
+        interface.send_to_frontend(PubSubItem::Response(Response { id, payload })).unwrap();
+    }
+
+    #[tokio::test]
+    async fn shared_subscriptions() {
+        let (tx, mut interfaces) = mpsc::unbounded_channel();
+        let frontend = MockConnect(tx).into_service().await.unwrap();
+        let mut interface = interfaces.recv().await.unwrap();
+
+        let subscribe = |id: u64| {
+            let req = Request::new("eth_subscribe", Id::Number(id), ("newHeads",));
+            let resp = frontend.send(req.serialize().unwrap());
+            async move {
+                let resp = resp.await.unwrap();
+                serde_json::from_str::<U256>(resp.payload.as_success().unwrap().get()).unwrap()
+            }
+        };
+
+        // Identical subscriptions issued before the server responds are both
+        // sent, and the duplicate server subscription is dropped.
+        let first = tokio::spawn(subscribe(1));
+        let second = tokio::spawn(subscribe(2));
+        let (a, b) = (recv(&mut interface).await, recv(&mut interface).await);
+        respond(&interface, &a, 1);
+        respond(&interface, &b, 2);
+        let local_id = first.await.unwrap();
+        assert_eq!(second.await.unwrap(), local_id);
+        let unsubscribe = recv(&mut interface).await;
+        assert_eq!(unsubscribe["method"], "eth_unsubscribe");
+        assert_eq!(unsubscribe["params"], json!(["0x2"]));
+
+        // Once active, identical subscriptions are not sent to the server.
+        assert_eq!(subscribe(3).await, local_id);
+        let mut heads = frontend.get_subscription(local_id).await.unwrap();
+        let mut other = frontend.get_subscription(local_id).await.unwrap();
+        let result = to_json_raw_value(&7).unwrap();
+        let notification = EthNotification { subscription: U256::from(1), result };
+        interface.send_to_frontend(PubSubItem::Notification(notification)).unwrap();
+        assert_eq!(heads.recv().await.unwrap().get(), "7");
+        assert_eq!(other.recv().await.unwrap().get(), "7");
+
+        // A different subscription is sent.
+        let logs = Request::new("eth_subscribe", Id::Number(4), ("logs",));
+        drop(tokio::spawn(frontend.send(logs.serialize().unwrap())));
+        assert_eq!(recv(&mut interface).await["params"], json!(["logs"]));
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_shared_subscription() {
+        let (tx, mut interfaces) = mpsc::unbounded_channel();
+        let frontend = MockConnect(tx).into_service().await.unwrap();
+        let mut interface = interfaces.recv().await.unwrap();
+
+        let req = Request::new("eth_subscribe", Id::Number(1), ("newHeads",));
+        let first = tokio::spawn(frontend.send(req.serialize().unwrap()));
+        let request = recv(&mut interface).await;
+        respond(&interface, &request, 1);
+        let resp = first.await.unwrap().unwrap();
+        let local_id: U256 =
+            serde_json::from_str(resp.payload.as_success().unwrap().get()).unwrap();
+        let req = Request::new("eth_subscribe", Id::Number(2), ("newHeads",));
+        frontend.send(req.serialize().unwrap()).await.unwrap();
+
+        let mut first = frontend.get_subscription(local_id).await.unwrap();
+        let mut second = frontend.get_subscription(local_id).await.unwrap();
+        // The subscription outlives the first consumer unsubscribing.
+        frontend.unsubscribe(local_id).unwrap();
+        let result = to_json_raw_value(&7).unwrap();
+        let notification = EthNotification { subscription: U256::from(1), result };
+        interface.send_to_frontend(PubSubItem::Notification(notification)).unwrap();
+        assert_eq!(first.recv().await.unwrap().get(), "7");
+        assert_eq!(second.recv().await.unwrap().get(), "7");
+
+        // The last consumer unsubscribing cancels the server subscription.
+        frontend.unsubscribe(local_id).unwrap();
+        let unsubscribe = recv(&mut interface).await;
+        assert_eq!(unsubscribe["method"], "eth_unsubscribe");
+        assert_eq!(unsubscribe["params"], json!(["0x1"]));
+        assert!(second.recv().await.is_err());
+    }
+}
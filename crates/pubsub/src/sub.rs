@@ -6,6 +6,24 @@ use std::{pin::Pin, task};
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 
+/// What subscription streams do when their receiver falls behind.
+///
+/// Subscription channels hold a bounded number of notifications. When a
+/// receiver falls behind by more than the channel capacity, the oldest
+/// notifications are dropped for it. A larger capacity, see
+/// [`get_subscription_with_capacity`], trades memory for fewer gaps.
+///
+/// [`get_subscription_with_capacity`]: crate::PubSubFrontend::get_subscription_with_capacity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LagPolicy {
+    /// Skip the dropped notifications, only logging the gap.
+    #[default]
+    DropOldest,
+    /// Signal the gap: [`SubAnyStream`] yields a [`SubscriptionItem::Lagged`]
+    /// item, while [`SubscriptionStream`] and [`SubResultStream`] end.
+    Error,
+}
+
 /// A Subscription is a feed of notifications from the server, identified by a
 /// local ID.
 ///
@@ -17,6 +35,8 @@ pub struct RawSubscription {
     pub(crate) rx: broadcast::Receiver<Box<RawValue>>,
     /// The local ID of the subscription.
     pub(crate) local_id: B256,
+    /// What streams do when the receiver falls behind.
+    pub(crate) lag_policy: LagPolicy,
 }
 
 impl RawSubscription {
@@ -25,6 +45,19 @@ impl RawSubscription {
         &self.local_id
     }
 
+    /// Get what streams created from this subscription do when the receiver
+    /// falls behind.
+    pub const fn lag_policy(&self) -> LagPolicy {
+        self.lag_policy
+    }
+
+    /// Set what streams created from this subscription do when the receiver
+    /// falls behind. Defaults to [`LagPolicy::DropOldest`].
+    pub const fn with_lag_policy(mut self, lag_policy: LagPolicy) -> Self {
+        self.lag_policy = lag_policy;
+        self
+    }
+
     /// Wrapper for [`blocking_recv`]. Block the current thread until a message
     /// is available.
     ///
@@ -57,7 +90,7 @@ impl RawSubscription {
     ///
     /// [`resubscribe`]: broadcast::Receiver::resubscribe
     pub fn resubscribe(&self) -> Self {
-        Self { rx: self.rx.resubscribe(), local_id: self.local_id }
+        Self { rx: self.rx.resubscribe(), local_id: self.local_id, lag_policy: self.lag_policy }
     }
 
     /// Wrapper for [`same_channel`]. Returns `true` if the two subscriptions
@@ -90,6 +123,10 @@ pub enum SubscriptionItem<T> {
     Item(T),
     /// Some other value.
     Other(Box<RawValue>),
+    /// The receiver fell behind, and this many notifications were dropped.
+    ///
+    /// Only yielded by [`SubAnyStream`] with [`LagPolicy::Error`].
+    Lagged(u64),
 }
 
 impl<T: DeserializeOwned> From<Box<RawValue>> for SubscriptionItem<T> {
@@ -148,6 +185,19 @@ impl<T> Subscription<T> {
         &mut self.inner
     }
 
+    /// Get what streams created from this subscription do when the receiver
+    /// falls behind.
+    pub const fn lag_policy(&self) -> LagPolicy {
+        self.inner.lag_policy
+    }
+
+    /// Set what streams created from this subscription do when the receiver
+    /// falls behind. Defaults to [`LagPolicy::DropOldest`].
+    pub const fn with_lag_policy(mut self, lag_policy: LagPolicy) -> Self {
+        self.inner.lag_policy = lag_policy;
+        self
+    }
+
     /// Returns `true` if the broadcast channel is empty (i.e. there are
     /// currently no notifications to receive).
     pub fn is_empty(&self) -> bool {
@@ -217,28 +267,36 @@ impl<T: DeserializeOwned> Subscription<T> {
 
     /// Convert the subscription into a stream.
     ///
-    /// Errors are logged and ignored.
+    /// Errors are logged and ignored. Gaps are handled according to the
+    /// [`LagPolicy`].
     pub fn into_stream(self) -> SubscriptionStream<T> {
         SubscriptionStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            lag_policy: self.inner.lag_policy,
+            inner: Some(self.inner.into_stream()),
             _pd: std::marker::PhantomData,
         }
     }
 
     /// Convert the subscription into a stream that returns deserialization results.
+    ///
+    /// Gaps are handled according to the [`LagPolicy`].
     pub fn into_result_stream(self) -> SubResultStream<T> {
         SubResultStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            lag_policy: self.inner.lag_policy,
+            inner: Some(self.inner.into_stream()),
             _pd: std::marker::PhantomData,
         }
     }
 
     /// Convert the subscription into a stream that may yield unexpected types.
+    ///
+    /// Gaps are handled according to the [`LagPolicy`].
     pub fn into_any_stream(self) -> SubAnyStream<T> {
         SubAnyStream {
             id: self.inner.local_id,
+            lag_policy: self.inner.lag_policy,
             inner: self.inner.into_stream(),
             _pd: std::marker::PhantomData,
         }
@@ -252,7 +310,7 @@ impl<T: DeserializeOwned> Subscription<T> {
         loop {
             match self.blocking_recv_any()? {
                 SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+                SubscriptionItem::Other(_) | SubscriptionItem::Lagged(_) => continue,
             }
         }
     }
@@ -265,7 +323,7 @@ impl<T: DeserializeOwned> Subscription<T> {
         loop {
             match self.recv_any().await? {
                 SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+                SubscriptionItem::Other(_) | SubscriptionItem::Lagged(_) => continue,
             }
         }
     }
@@ -278,7 +336,7 @@ impl<T: DeserializeOwned> Subscription<T> {
         loop {
             match self.try_recv_any()? {
                 SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+                SubscriptionItem::Other(_) | SubscriptionItem::Lagged(_) => continue,
             }
         }
     }
@@ -319,6 +377,7 @@ impl<T: DeserializeOwned> Subscription<T> {
 #[derive(Debug)]
 pub struct SubAnyStream<T> {
     id: B256,
+    lag_policy: LagPolicy,
     inner: BroadcastStream<Box<RawValue>>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
@@ -340,24 +399,59 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
         loop {
             match ready!(self.inner.poll_next_unpin(cx)) {
                 Some(Ok(value)) => return task::Poll::Ready(Some(value.into())),
-                Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
-                    continue;
-                }
+                Some(Err(BroadcastStreamRecvError::Lagged(n))) => match self.lag_policy {
+                    LagPolicy::DropOldest => {
+                        debug!(skipped = n, %self.id, "stream lagged");
+                        continue;
+                    }
+                    LagPolicy::Error => {
+                        return task::Poll::Ready(Some(SubscriptionItem::Lagged(n)));
+                    }
+                },
                 None => return task::Poll::Ready(None),
             }
         }
     }
 }
 
+/// Polls the next notification of a subscription stream, handling gaps
+/// according to `lag_policy`. Ends the stream on a gap with
+/// [`LagPolicy::Error`].
+fn poll_next_notification(
+    inner: &mut Option<BroadcastStream<Box<RawValue>>>,
+    id: &B256,
+    lag_policy: LagPolicy,
+    cx: &mut task::Context<'_>,
+) -> task::Poll<Option<Box<RawValue>>> {
+    let Some(stream) = inner else { return task::Poll::Ready(None) };
+    loop {
+        match ready!(stream.poll_next_unpin(cx)) {
+            Some(Ok(value)) => return task::Poll::Ready(Some(value)),
+            Some(Err(BroadcastStreamRecvError::Lagged(n))) => match lag_policy {
+                LagPolicy::DropOldest => {
+                    debug!(skipped = n, %id, "stream lagged");
+                    continue;
+                }
+                LagPolicy::Error => {
+                    error!(skipped = n, %id, "stream lagged, ending it");
+                    *inner = None;
+                    return task::Poll::Ready(None);
+                }
+            },
+            None => return task::Poll::Ready(None),
+        }
+    }
+}
+
 /// A stream of notifications from the server, identified by a local ID. This/
 /// stream will yield only the expected type, discarding any notifications of
 /// unexpected types.
 #[derive(Debug)]
 pub struct SubscriptionStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    lag_policy: LagPolicy,
+    /// `None` once the stream ended because of a gap.
+    inner: Option<BroadcastStream<Box<RawValue>>>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -375,22 +469,20 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
         mut self: Pin<&mut Self>,
         cx: &mut task::Context<'_>,
     ) -> task::Poll<Option<Self::Item>> {
+        let this = &mut *self;
         loop {
-            match ready!(self.inner.poll_next_unpin(cx)) {
-                Some(Ok(value)) => match serde_json::from_str(value.get()) {
-                    Ok(item) => return task::Poll::Ready(Some(item)),
-                    Err(err) => {
-                        debug!(value = ?value.get(), %err, %self.id, "failed deserializing subscription item");
-                        error!(%err, %self.id, "failed deserializing subscription item");
-                        continue;
-                    }
-                },
-                Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
+            let Some(value) =
+                ready!(poll_next_notification(&mut this.inner, &this.id, this.lag_policy, cx))
+            else {
+                return task::Poll::Ready(None);
+            };
+            match serde_json::from_str(value.get()) {
+                Ok(item) => return task::Poll::Ready(Some(item)),
+                Err(err) => {
+                    debug!(value = ?value.get(), %err, %this.id, "failed deserializing subscription item");
+                    error!(%err, %this.id, "failed deserializing subscription item");
                     continue;
                 }
-                None => return task::Poll::Ready(None),
             }
         }
     }
@@ -403,7 +495,9 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
 #[derive(Debug)]
 pub struct SubResultStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    lag_policy: LagPolicy,
+    /// `None` once the stream ended because of a gap.
+    inner: Option<BroadcastStream<Box<RawValue>>>,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -421,18 +515,41 @@ impl<T: DeserializeOwned> Stream for SubResultStream<T> {
         mut self: Pin<&mut Self>,
         cx: &mut task::Context<'_>,
     ) -> task::Poll<Option<Self::Item>> {
-        loop {
-            match ready!(self.inner.poll_next_unpin(cx)) {
-                Some(Ok(value)) => {
-                    return task::Poll::Ready(Some(serde_json::from_str(value.get())))
-                }
-                Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
-                    continue;
-                }
-                None => return task::Poll::Ready(None),
-            }
+        let this = &mut *self;
+        let value = ready!(poll_next_notification(&mut this.inner, &this.id, this.lag_policy, cx));
+        task::Poll::Ready(value.map(|value| serde_json::from_str(value.get())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a subscription that lagged behind by 2 notifications, of 4 sent.
+    fn lagged(lag_policy: LagPolicy) -> Subscription<u64> {
+        let (tx, rx) = broadcast::channel(2);
+        for i in 0..4 {
+            tx.send(RawValue::from_string(i.to_string()).unwrap()).unwrap();
         }
+        Subscription::from(RawSubscription { rx, local_id: B256::ZERO, lag_policy })
+    }
+
+    #[tokio::test]
+    async fn lag_policies() {
+        let items = lagged(LagPolicy::DropOldest).into_stream().collect::<Vec<_>>().await;
+        assert_eq!(items, [2, 3]);
+
+        let items = lagged(LagPolicy::Error).into_any_stream().collect::<Vec<_>>().await;
+        assert!(matches!(
+            items[..],
+            [SubscriptionItem::Lagged(2), SubscriptionItem::Item(2), SubscriptionItem::Item(3)]
+        ));
+
+        let mut stream = lagged(LagPolicy::Error).into_stream();
+        assert_eq!(stream.next().await, None);
+        assert_eq!(stream.next().await, None);
+
+        let sub = lagged(LagPolicy::DropOldest).with_lag_policy(LagPolicy::Error);
+        assert!(sub.into_result_stream().next().await.is_none());
     }
 }
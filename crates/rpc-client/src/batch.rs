@@ -1,10 +1,10 @@
 use crate::{client::RpcClientInner, ClientRef};
 use alloy_json_rpc::{
-    transform_response, try_deserialize_ok, Id, Request, RequestPacket, ResponsePacket, RpcParam,
-    RpcReturn, SerializedRequest,
+    transform_response, try_deserialize_ok, Id, IdMode, Request, RequestPacket, ResponsePacket,
+    ResponsePayload, RpcError, RpcParam, RpcReturn, SerializedRequest,
 };
 use alloy_transport::{Transport, TransportError, TransportErrorKind, TransportResult};
-use futures::channel::oneshot;
+use futures::{channel::oneshot, future::JoinAll};
 use serde_json::value::RawValue;
 use std::{
     borrow::Cow,
@@ -20,6 +20,35 @@ pub(crate) type ChannelMap = HashMap<Id, Channel>;
 
 /// A batch JSON-RPC request, used to bundle requests into a single transport
 /// call.
+///
+/// Each call added to the batch returns a [`Waiter`] resolving to its own typed
+/// response, so that calls with different response types can be mixed:
+///
+/// ```no_run
+/// # async fn example(client: alloy_rpc_client::ReqwestClient) -> alloy_transport::TransportResult<()> {
+/// use alloy_primitives::{Address, U256, U64};
+///
+/// let mut batch = client.new_batch();
+/// let block_number = batch.add_call::<U64>("eth_blockNumber", ())?;
+/// let balance = batch.add_call::<U256>("eth_getBalance", (Address::ZERO, "latest"))?;
+/// batch.send().await?;
+///
+/// // Requests may fail separately.
+/// let block_number = block_number.await?;
+/// let balance = balance.await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Requests are sent in the order they were added, and responses are matched
+/// to their waiters by ID, so servers may answer them in any order. Servers
+/// that echo IDs as strings or reuse IDs are supported in
+/// [`IdMode::Lenient`], see [`RpcClientInner::with_id_mode`]. An error
+/// response or a response that fails to deserialize only fails its own waiter,
+/// while errors affecting the whole batch, such as transport errors, are
+/// delivered to every waiter.
+///
+/// Large batches can be split with [`with_max_size`](Self::with_max_size).
 #[derive(Debug)]
 #[must_use = "A BatchRequest does nothing unless sent via `send_batch` and `.await`"]
 pub struct BatchRequest<'a, T> {
@@ -27,10 +56,16 @@ pub struct BatchRequest<'a, T> {
     transport: ClientRef<'a, T>,
 
     /// The requests to be sent.
-    requests: RequestPacket,
+    requests: Vec<SerializedRequest>,
 
     /// The channels to send the responses through.
     channels: ChannelMap,
+
+    /// The batches flushed after reaching `max_size` requests.
+    flushed: Vec<(Vec<SerializedRequest>, ChannelMap)>,
+
+    /// The maximum number of requests per batch.
+    max_size: Option<usize>,
 }
 
 /// Awaits a single response for a request that has been included in a batch.
@@ -61,17 +96,36 @@ where
     }
 }
 
+/// A future sending a [`BatchRequest`], which resolves once every response has
+/// been delivered to its [`Waiter`].
+///
+/// If the batch was split into several batches, they are sent concurrently, and
+/// the future resolves to the first error encountered, if any.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct BatchFuture<Conn: Transport + Clone> {
+    packets: JoinAll<PacketFuture<Conn>>,
+}
+
+impl<Conn: Transport + Clone> std::fmt::Debug for BatchFuture<Conn> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchFuture").finish_non_exhaustive()
+    }
+}
+
 #[pin_project::pin_project(project = CallStateProj)]
 #[derive(Debug)]
-pub enum BatchFuture<Conn: Transport> {
+enum PacketFuture<Conn: Transport> {
     Prepared {
         transport: Conn,
         requests: RequestPacket,
         channels: ChannelMap,
+        id_mode: IdMode,
     },
-    SerError(Option<TransportError>),
     AwaitingResponse {
         channels: ChannelMap,
+        id_mode: IdMode,
+        /// The request IDs in order, to match responses by position.
+        ids: Vec<Id>,
         #[pin]
         fut: Conn::Future,
     },
@@ -83,19 +137,67 @@ impl<'a, T> BatchRequest<'a, T> {
     pub fn new(transport: &'a RpcClientInner<T>) -> Self {
         Self {
             transport,
-            requests: RequestPacket::Batch(Vec::with_capacity(10)),
+            requests: Vec::with_capacity(10),
             channels: HashMap::with_capacity(10),
+            flushed: Vec::new(),
+            max_size: None,
         }
     }
 
+    /// Sets the maximum number of requests per batch.
+    ///
+    /// Once the batch holds `max_size` requests, they are flushed into their
+    /// own batch and subsequent calls start a new one. All batches are sent
+    /// concurrently by [`send`](Self::send).
+    ///
+    /// # Panics
+    ///
+    /// If `max_size` is zero.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        assert!(max_size > 0, "batch max size must be greater than zero");
+        self.max_size = Some(max_size);
+        // Split the requests added so far.
+        let requests = std::mem::take(&mut self.requests);
+        let mut channels = std::mem::take(&mut self.channels);
+        for request in requests {
+            let tx = channels.remove(request.id()).expect("every request has a channel");
+            self.push_with_channel(request, tx);
+        }
+        self
+    }
+
+    /// Returns the number of requests in the batch.
+    pub fn len(&self) -> usize {
+        self.requests.len() + self.flushed.iter().map(|(requests, _)| requests.len()).sum::<usize>()
+    }
+
+    /// Returns `true` if no requests were added to the batch.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Moves the pending requests into their own batch.
+    fn flush(&mut self) {
+        let requests = std::mem::take(&mut self.requests);
+        let channels = std::mem::take(&mut self.channels);
+        self.flushed.push((requests, channels));
+    }
+
     fn push_raw(
         &mut self,
         request: SerializedRequest,
     ) -> oneshot::Receiver<TransportResult<Box<RawValue>>> {
         let (tx, rx) = oneshot::channel();
+        self.push_with_channel(request, tx);
+        rx
+    }
+
+    fn push_with_channel(&mut self, request: SerializedRequest, tx: Channel) {
         self.channels.insert(request.id().clone(), tx);
         self.requests.push(request);
-        rx
+        if self.max_size.is_some_and(|max_size| self.requests.len() >= max_size) {
+            self.flush();
+        }
     }
 
     fn push<Params: RpcParam, Resp: RpcReturn>(
@@ -111,27 +213,34 @@ impl<'a, Conn> BatchRequest<'a, Conn>
 where
     Conn: Transport + Clone,
 {
-    /// Add a call to the batch.
+    /// Add a call to the batch, returning a [`Waiter`] for its response of type
+    /// `Resp`.
     ///
     /// ### Errors
     ///
-    /// If the request cannot be serialized, this will return an error.
-    pub fn add_call<Params: RpcParam, Resp: RpcReturn>(
+    /// If the request cannot be serialized, this will return an error. The
+    /// request is not added to the batch in this case.
+    pub fn add_call<Resp: RpcReturn>(
         &mut self,
         method: impl Into<Cow<'static, str>>,
-        params: &Params,
+        params: impl RpcParam,
     ) -> TransportResult<Waiter<Resp>> {
-        let request = self.transport.make_request(method, Cow::Borrowed(params));
+        let request = self.transport.make_request(method, params);
         self.push(request)
     }
 
     /// Send the batch future via its connection.
-    pub fn send(self) -> BatchFuture<Conn> {
-        BatchFuture::Prepared {
-            transport: self.transport.transport.clone(),
-            requests: self.requests,
-            channels: self.channels,
+    pub fn send(mut self) -> BatchFuture<Conn> {
+        if !self.requests.is_empty() {
+            self.flush();
         }
+        let transport = &self.transport.transport;
+        let id_mode = self.transport.id_mode;
+        let packets = self.flushed.into_iter().map(|(requests, channels)| {
+            let requests = RequestPacket::Batch(requests);
+            PacketFuture::Prepared { transport: transport.clone(), requests, channels, id_mode }
+        });
+        BatchFuture { packets: packets.collect() }
     }
 }
 
@@ -147,7 +256,56 @@ where
     }
 }
 
-impl<T> BatchFuture<T>
+impl<T> Future for BatchFuture<T>
+where
+    T: Transport + Clone,
+{
+    type Output = TransportResult<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let results = ready!(Pin::new(&mut self.packets).poll(cx));
+        Poll::Ready(results.into_iter().collect())
+    }
+}
+
+/// Removes the channel of the request answered by the response with the given
+/// ID.
+fn take_channel(channels: &mut ChannelMap, id: &Id, id_mode: IdMode) -> Option<Channel> {
+    channels.remove(id).or_else(|| channels.remove(&id_mode.fallback(id)?))
+}
+
+/// Fails every request of a batch with `err`.
+fn fail_all(channels: &mut ChannelMap, err: &TransportError) {
+    for (_, tx) in channels.drain() {
+        let _ = tx.send(Err(copy_error(err)));
+    }
+}
+
+/// Copies `err`, keeping its kind. Sources that cannot be cloned are replaced by errors with the
+/// same message.
+fn copy_error(err: &TransportError) -> TransportError {
+    match err {
+        RpcError::ErrorResp(payload) => RpcError::ErrorResp(payload.clone()),
+        RpcError::NullResp => RpcError::NullResp,
+        RpcError::UnsupportedFeature(feature) => RpcError::UnsupportedFeature(feature),
+        RpcError::LocalUsageError(err) => RpcError::local_usage_str(&err.to_string()),
+        RpcError::SerError(err) => RpcError::SerError(serde::ser::Error::custom(err)),
+        RpcError::DeserError { err, text } => {
+            RpcError::DeserError { err: serde::de::Error::custom(err), text: text.clone() }
+        }
+        RpcError::Transport(kind) => RpcError::Transport(match kind {
+            TransportErrorKind::MissingBatchResponse(id) => {
+                TransportErrorKind::MissingBatchResponse(id.clone())
+            }
+            TransportErrorKind::BackendGone => TransportErrorKind::BackendGone,
+            TransportErrorKind::PubsubUnavailable => TransportErrorKind::PubsubUnavailable,
+            TransportErrorKind::HttpError(err) => TransportErrorKind::HttpError(err.clone()),
+            kind => TransportErrorKind::Custom(kind.to_string().into()),
+        }),
+    }
+}
+
+impl<T> PacketFuture<T>
 where
     T: Transport + Clone,
 {
@@ -155,12 +313,14 @@ where
         mut self: Pin<&mut Self>,
         cx: &mut task::Context<'_>,
     ) -> Poll<<Self as Future>::Output> {
-        let CallStateProj::Prepared { transport, requests, channels } = self.as_mut().project()
+        let CallStateProj::Prepared { transport, requests, channels, id_mode } =
+            self.as_mut().project()
         else {
             unreachable!("Called poll_prepared in incorrect state")
         };
 
         if let Err(e) = task::ready!(transport.poll_ready(cx)) {
+            fail_all(channels, &e);
             self.set(Self::Complete);
             return Poll::Ready(Err(e));
         }
@@ -169,9 +329,16 @@ where
         // with 0-capacity collections.
         let channels = std::mem::replace(channels, HashMap::with_capacity(0));
         let req = std::mem::replace(requests, RequestPacket::Batch(Vec::with_capacity(0)));
+        let ids = match &req {
+            RequestPacket::Batch(reqs) if id_mode.is_lenient() => {
+                reqs.iter().map(|req| req.id().clone()).collect()
+            }
+            _ => Vec::new(),
+        };
 
+        let id_mode = *id_mode;
         let fut = transport.call(req);
-        self.set(Self::AwaitingResponse { channels, fut });
+        self.set(Self::AwaitingResponse { channels, id_mode, ids, fut });
         cx.waker().wake_by_ref();
         Poll::Pending
     }
@@ -180,7 +347,9 @@ where
         mut self: Pin<&mut Self>,
         cx: &mut task::Context<'_>,
     ) -> Poll<<Self as Future>::Output> {
-        let CallStateProj::AwaitingResponse { channels, fut } = self.as_mut().project() else {
+        let CallStateProj::AwaitingResponse { channels, id_mode, ids, fut } =
+            self.as_mut().project()
+        else {
             unreachable!("Called poll_awaiting_response in incorrect state")
         };
 
@@ -188,6 +357,7 @@ where
         let responses = match ready!(fut.poll(cx)) {
             Ok(responses) => responses,
             Err(e) => {
+                fail_all(channels, &e);
                 self.set(Self::Complete);
                 return Poll::Ready(Err(e));
             }
@@ -195,14 +365,33 @@ where
 
         // Send all responses via channels
         match responses {
-            ResponsePacket::Single(single) => {
-                if let Some(tx) = channels.remove(&single.id) {
+            ResponsePacket::Single(single) => match take_channel(channels, &single.id, *id_mode) {
+                Some(tx) => {
                     let _ = tx.send(transform_response(single));
                 }
-            }
+                // An error response for the whole batch, e.g. if the server
+                // does not support batching.
+                None => {
+                    if let ResponsePayload::Failure(err) = single.payload {
+                        fail_all(channels, &RpcError::ErrorResp(err));
+                    }
+                }
+            },
             ResponsePacket::Batch(responses) => {
-                for response in responses.into_iter() {
-                    if let Some(tx) = channels.remove(&response.id) {
+                let mut unmatched = Vec::new();
+                for (position, response) in responses.into_iter().enumerate() {
+                    match take_channel(channels, &response.id, *id_mode) {
+                        Some(tx) => {
+                            let _ = tx.send(transform_response(response));
+                        }
+                        None => unmatched.push((position, response)),
+                    }
+                }
+
+                // In lenient mode, responses with unknown or reused IDs are
+                // matched with the request at the same position.
+                for (position, response) in unmatched {
+                    if let Some(tx) = ids.get(position).and_then(|id| channels.remove(id)) {
                         let _ = tx.send(transform_response(response));
                     }
                 }
@@ -218,23 +407,9 @@ where
         self.set(Self::Complete);
         Poll::Ready(Ok(()))
     }
-
-    fn poll_ser_error(
-        mut self: Pin<&mut Self>,
-        _cx: &mut task::Context<'_>,
-    ) -> Poll<<Self as Future>::Output> {
-        let e = if let CallStateProj::SerError(e) = self.as_mut().project() {
-            e.take().expect("no error")
-        } else {
-            unreachable!("Called poll_ser_error in incorrect state")
-        };
-
-        self.set(Self::Complete);
-        Poll::Ready(Err(e))
-    }
 }
 
-impl<T> Future for BatchFuture<T>
+impl<T> Future for PacketFuture<T>
 where
     T: Transport + Clone,
 {
@@ -249,10 +424,148 @@ where
             return self.poll_awaiting_response(cx);
         }
 
-        if matches!(*self.as_mut(), Self::SerError(_)) {
-            return self.poll_ser_error(cx);
+        panic!("Called poll on CallState in invalid state")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RpcClient;
+    use alloy_json_rpc::{ErrorPayload, Response};
+    use alloy_primitives::{U256, U64};
+    use alloy_transport::{BoxTransport, TransportFut};
+    use std::sync::{Arc, Mutex};
+
+    /// A transport answering batches in reverse order, with an error for `eth_fail`, and
+    /// recording the methods of each batch.
+    fn transport(batches: Arc<Mutex<Vec<Vec<String>>>>) -> BoxTransport {
+        BoxTransport::new(tower::service_fn(move |request: RequestPacket| {
+            let batches = batches.clone();
+            Box::pin(async move {
+                let RequestPacket::Batch(requests) = request else { unreachable!() };
+                batches.lock().unwrap().push(requests.iter().map(|r| r.method().into()).collect());
+                let responses = requests.iter().rev().map(|request| {
+                    let payload = match request.method() {
+                        "eth_fail" => ResponsePayload::Failure(ErrorPayload {
+                            code: -32000,
+                            message: "failed".into(),
+                            data: None,
+                        }),
+                        "eth_blockNumber" => ResponsePayload::Success(
+                            RawValue::from_string("\"0x10\"".into()).unwrap(),
+                        ),
+                        _ => ResponsePayload::Success(
+                            RawValue::from_string("\"0xde0b6b3a7640000\"".into()).unwrap(),
+                        ),
+                    };
+                    Response { id: request.id().clone(), payload }
+                });
+                Ok(ResponsePacket::Batch(responses.collect()))
+            }) as TransportFut<'static>
+        }))
+    }
+
+    #[tokio::test]
+    async fn typed_responses() {
+        let batches = Arc::default();
+        let client = RpcClient::new(transport(Arc::clone(&batches)), true);
+
+        let mut batch = BatchRequest::new(&client);
+        let block_number = batch.add_call::<U64>("eth_blockNumber", ()).unwrap();
+        let failed = batch.add_call::<U64>("eth_fail", ()).unwrap();
+        let balance = batch.add_call::<U256>("eth_getBalance", ("0x00", "latest")).unwrap();
+        // Deserialization errors are isolated as well.
+        let invalid = batch.add_call::<bool>("eth_getBalance", ("0x00", "latest")).unwrap();
+        assert_eq!(batch.len(), 4);
+        batch.send().await.unwrap();
+
+        assert_eq!(
+            batches.lock().unwrap()[0],
+            ["eth_blockNumber", "eth_fail", "eth_getBalance", "eth_getBalance"]
+        );
+        assert_eq!(block_number.await.unwrap(), U64::from(16));
+        assert_eq!(failed.await.unwrap_err().as_error_resp().unwrap().code, -32000);
+        assert_eq!(balance.await.unwrap(), U256::from(10).pow(U256::from(18)));
+        assert!(invalid.await.unwrap_err().is_deser_error());
+    }
+
+    #[tokio::test]
+    async fn flushes_at_max_size() {
+        let batches = Arc::default();
+        let client = RpcClient::new(transport(Arc::clone(&batches)), true);
+
+        let mut batch = BatchRequest::new(&client);
+        let first = batch.add_call::<U64>("eth_blockNumber", ()).unwrap();
+        let mut batch = batch.with_max_size(2);
+        let waiters = (0..4)
+            .map(|_| batch.add_call::<U64>("eth_blockNumber", ()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(batch.len(), 5);
+        batch.send().await.unwrap();
+
+        let sizes = batches.lock().unwrap().iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(sizes, [2, 2, 1]);
+        assert_eq!(first.await.unwrap(), U64::from(16));
+        for waiter in waiters {
+            assert_eq!(waiter.await.unwrap(), U64::from(16));
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_errors_reach_every_waiter() {
+        let transport = BoxTransport::new(tower::service_fn(|_: RequestPacket| {
+            Box::pin(async { Err(TransportErrorKind::backend_gone()) }) as TransportFut<'static>
+        }));
+        let client = RpcClient::new(transport, true);
+
+        let mut batch = BatchRequest::new(&client);
+        let a = batch.add_call::<U64>("eth_blockNumber", ()).unwrap();
+        let b = batch.add_call::<U64>("eth_chainId", ()).unwrap();
+        assert!(batch.send().await.is_err());
+        for waiter in [a, b] {
+            let err = waiter.await.unwrap_err();
+            assert!(matches!(err, RpcError::Transport(TransportErrorKind::BackendGone)), "{err}");
         }
 
-        panic!("Called poll on CallState in invalid state")
+        // Empty batches are not sent.
+        assert!(BatchRequest::new(&client).send().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn lenient_ids() {
+        // Echoes the first ID as a string, and reuses it for the second response.
+        let transport = BoxTransport::new(tower::service_fn(|request: RequestPacket| {
+            Box::pin(async move {
+                let RequestPacket::Batch(requests) = request else { unreachable!() };
+                let id = Id::String(requests[0].id().to_string());
+                let responses = requests.iter().enumerate().map(|(i, _)| {
+                    let result = RawValue::from_string(format!("\"{i:#x}\"")).unwrap();
+                    Response { id: id.clone(), payload: ResponsePayload::Success(result) }
+                });
+                Ok(ResponsePacket::Batch(responses.collect()))
+            }) as TransportFut<'static>
+        }));
+
+        let client = RpcClient::new(transport.clone(), true);
+        let mut batch = BatchRequest::new(&client);
+        let a = batch.add_call::<U64>("eth_blockNumber", ()).unwrap();
+        let b = batch.add_call::<U64>("eth_blockNumber", ()).unwrap();
+        batch.send().await.unwrap();
+        assert!(matches!(
+            a.await,
+            Err(RpcError::Transport(TransportErrorKind::MissingBatchResponse(_)))
+        ));
+        assert!(b.await.is_err());
+
+        let client = RpcClient::from_inner(
+            RpcClientInner::new(transport, true).with_id_mode(IdMode::Lenient),
+        );
+        let mut batch = BatchRequest::new(&client);
+        let a = batch.add_call::<U64>("eth_blockNumber", ()).unwrap();
+        let b = batch.add_call::<U64>("eth_blockNumber", ()).unwrap();
+        batch.send().await.unwrap();
+        assert_eq!(a.await.unwrap(), U64::from(0));
+        assert_eq!(b.await.unwrap(), U64::from(1));
     }
 }
@@ -67,14 +67,47 @@ impl<L> ClientBuilder<L> {
         L: Layer<alloy_transport_http::Http<alloy_transport_http::HyperClient>>,
         L::Service: Transport,
     {
-        let executor = hyper_util::rt::TokioExecutor::new();
-        let client = hyper_util::client::legacy::Client::builder(executor).build_http();
-        let transport = alloy_transport_http::Http::with_client(client, url);
+        self.hyper_http_with_builder(url, &alloy_transport_http::HyperClientBuilder::new())
+    }
+
+    /// Convenience function to create a new [`RpcClient`] with a `hyper` HTTP transport, whose
+    /// client is built by the given builder, e.g. to use HTTP/2 or configure its connection pool.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
+    pub fn hyper_http_with_builder(
+        self,
+        url: url::Url,
+        builder: &alloy_transport_http::HyperClientBuilder,
+    ) -> RpcClient<L::Service>
+    where
+        L: Layer<alloy_transport_http::Http<alloy_transport_http::HyperClient>>,
+        L::Service: Transport,
+    {
+        let transport = alloy_transport_http::Http::with_client(builder.build(), url);
         let is_local = transport.guess_local();
 
         self.transport(transport, is_local)
     }
 
+    /// Convenience function to create a new [`RpcClient`] with a `hyper` HTTP transport supporting
+    /// HTTPS, whose client is built by the given builder with the given TLS options, e.g. a client
+    /// certificate for a gateway enforcing mutual TLS.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "hyper-rustls"))]
+    pub fn hyper_https_with_builder(
+        self,
+        url: url::Url,
+        builder: &alloy_transport_http::HyperClientBuilder,
+        tls: &alloy_transport::TlsConfig,
+    ) -> TransportResult<RpcClient<L::Service>>
+    where
+        L: Layer<alloy_transport_http::HyperTlsTransport>,
+        L::Service: Transport,
+    {
+        let transport = alloy_transport_http::Http::with_client(builder.build_tls(tls)?, url);
+        let is_local = transport.guess_local();
+
+        Ok(self.transport(transport, is_local))
+    }
+
     /// Connect a pubsub transport, producing an [`RpcClient`] with the provided
     /// connection.
     #[cfg(feature = "pubsub")]
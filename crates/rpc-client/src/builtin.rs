@@ -13,6 +13,9 @@ pub enum BuiltInConnectionString {
     /// HTTP transport.
     #[cfg(any(feature = "reqwest", feature = "hyper"))]
     Http(url::Url),
+    /// HTTP transport over a Unix domain socket, from an `http+unix://` URL.
+    #[cfg(all(unix, feature = "hyper"))]
+    HttpUnix(url::Url),
     /// WebSocket transport.
     #[cfg(feature = "ws")]
     Ws(url::Url, Option<alloy_transport::Authorization>),
@@ -26,6 +29,8 @@ impl BoxTransportConnect for BuiltInConnectionString {
         match self {
             #[cfg(any(feature = "reqwest", feature = "hyper"))]
             Self::Http(url) => alloy_transport::utils::guess_local_url(url),
+            #[cfg(all(unix, feature = "hyper"))]
+            Self::HttpUnix(_) => true,
             #[cfg(feature = "ws")]
             Self::Ws(url, _) => alloy_transport::utils::guess_local_url(url),
             #[cfg(feature = "ipc")]
@@ -75,6 +80,10 @@ impl BuiltInConnectionString {
                 "hyper not supported by BuiltinConnectionString. Please instantiate a hyper client manually",
             )),
 
+            #[cfg(all(unix, feature = "hyper"))]
+            Self::HttpUnix(url) => alloy_transport_http::Http::new_unix(url)
+                .map(alloy_transport::Transport::boxed),
+
             #[cfg(all(not(target_arch = "wasm32"), feature = "ws"))]
             Self::Ws(url, Some(auth)) => {
                 alloy_transport_ws::WsConnect::with_auth(url.clone(), Some(auth.clone()))
@@ -103,6 +112,9 @@ impl BuiltInConnectionString {
     }
 
     /// Tries to parse the given string as an HTTP URL.
+    ///
+    /// With the `hyper` feature, `http+unix://` URLs of endpoints served over a Unix domain socket
+    /// are also accepted, see [`Http::new_unix`](alloy_transport_http::Http::new_unix).
     #[cfg(any(feature = "reqwest", feature = "hyper"))]
     pub fn try_as_http(s: &str) -> Result<Self, TransportError> {
         let url = if s.starts_with("localhost:") || s.parse::<std::net::SocketAddr>().is_ok() {
@@ -114,6 +126,13 @@ impl BuiltInConnectionString {
         .map_err(TransportErrorKind::custom)?;
 
         let scheme = url.scheme();
+        #[cfg(all(unix, feature = "hyper"))]
+        if scheme == alloy_transport_http::HTTP_UNIX_SCHEME {
+            alloy_transport_http::split_unix_url(&url).ok_or_else(|| {
+                TransportErrorKind::custom_str(&format!("invalid `{scheme}` URL: {url}"))
+            })?;
+            return Ok(Self::HttpUnix(url));
+        }
         if scheme != "http" && scheme != "https" {
             let msg = format!("invalid URL scheme: {scheme}; expected `http` or `https`");
             return Err(TransportErrorKind::custom_str(&msg));
@@ -222,6 +241,18 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(all(unix, feature = "hyper"))]
+    fn test_parsing_http_unix() {
+        assert_eq!(
+            BuiltInConnectionString::from_str("http+unix://%2Fvar%2Frun%2Fnode.sock").unwrap(),
+            BuiltInConnectionString::HttpUnix(
+                "http+unix://%2Fvar%2Frun%2Fnode.sock".parse::<Url>().unwrap()
+            )
+        );
+        assert!(BuiltInConnectionString::try_as_http("http+unix://%2").is_err());
+    }
+
     #[test]
     #[cfg(feature = "ws")]
     fn test_parsing_ws() {
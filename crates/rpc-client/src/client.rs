@@ -1,5 +1,5 @@
 use crate::{poller::PollerBuilder, BatchRequest, ClientBuilder, RpcCall};
-use alloy_json_rpc::{Id, Request, RpcParam, RpcReturn};
+use alloy_json_rpc::{Id, IdMode, Request, RpcParam, RpcReturn};
 use alloy_transport::{BoxTransport, Transport};
 use alloy_transport_http::Http;
 use std::{
@@ -119,7 +119,8 @@ impl<T: Transport + Clone> RpcClient<T> {
         let inner = match Arc::try_unwrap(self.0) {
             Ok(inner) => inner,
             Err(inner) => RpcClientInner::new(inner.transport.clone(), inner.is_local)
-                .with_id(inner.id.load(Ordering::Relaxed)),
+                .with_id(inner.id.load(Ordering::Relaxed))
+                .with_id_mode(inner.id_mode),
         };
         RpcClient::from_inner(inner.boxed())
     }
@@ -163,6 +164,8 @@ pub struct RpcClientInner<T> {
     pub(crate) id: AtomicU64,
     /// The poll interval for the client in milliseconds.
     pub(crate) poll_interval: AtomicU64,
+    /// How response IDs are matched with request IDs in batches.
+    pub(crate) id_mode: IdMode,
 }
 
 impl<T> RpcClientInner<T> {
@@ -177,6 +180,7 @@ impl<T> RpcClientInner<T> {
             is_local,
             id: AtomicU64::new(0),
             poll_interval: if is_local { AtomicU64::new(250) } else { AtomicU64::new(7000) },
+            id_mode: IdMode::Strict,
         }
     }
 
@@ -186,6 +190,21 @@ impl<T> RpcClientInner<T> {
         Self { id: AtomicU64::new(id), ..self }
     }
 
+    /// Sets how response IDs are matched with request IDs in batches.
+    ///
+    /// Use [`IdMode::Lenient`] for servers that echo IDs as strings or reuse
+    /// IDs within a batch.
+    #[inline]
+    pub fn with_id_mode(self, id_mode: IdMode) -> Self {
+        Self { id_mode, ..self }
+    }
+
+    /// Returns how response IDs are matched with request IDs in batches.
+    #[inline]
+    pub const fn id_mode(&self) -> IdMode {
+        self.id_mode
+    }
+
     /// Returns the default poll interval (milliseconds) for the client.
     pub fn poll_interval(&self) -> Duration {
         Duration::from_millis(self.poll_interval.load(Ordering::Relaxed))
@@ -294,6 +313,7 @@ impl<T: Transport + Clone> RpcClientInner<T> {
             is_local: self.is_local,
             id: self.id,
             poll_interval: self.poll_interval,
+            id_mode: self.id_mode,
         }
     }
 }
@@ -342,6 +362,15 @@ mod pubsub_impl {
         pub fn set_channel_size(&self, size: usize) {
             self.transport.set_channel_size(size)
         }
+
+        /// Get a receiver of the notifications sent by the server with a
+        /// method other than `eth_subscription`. See
+        /// [`PubSubFrontend::server_notifications`].
+        pub fn server_notifications(
+            &self,
+        ) -> tokio::sync::broadcast::Receiver<alloy_json_rpc::ServerNotification> {
+            self.transport.server_notifications()
+        }
     }
 }
 
@@ -7,7 +7,7 @@ use alloy_transport_ws::WsConnect;
 async fn it_makes_a_request() {
     let anvil = Anvil::new().spawn();
     let url = anvil.ws_endpoint();
-    let connector = WsConnect { url: url.parse().unwrap(), auth: None };
+    let connector = WsConnect::new(url);
     let client = ClientBuilder::default().pubsub(connector).await.unwrap();
     let req: RpcCall<_, (), U64> = client.request("eth_blockNumber", ());
     let timeout = tokio::time::timeout(std::time::Duration::from_secs(2), req);
@@ -0,0 +1,168 @@
+//! Types for the Alchemy token APIs: <https://docs.alchemy.com/reference/token-api-quickstart>
+
+use alloy_primitives::{Address, U256};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The tokens to return the balances of with `alchemy_getTokenBalances`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TokenBalancesSpec {
+    /// All the ERC-20 tokens the address ever held.
+    #[default]
+    Erc20,
+    /// The top 100 tokens by 24 hour volume.
+    DefaultTokens,
+    /// The given token contracts.
+    Contracts(Vec<Address>),
+}
+
+impl Serialize for TokenBalancesSpec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Erc20 => serializer.serialize_str("erc20"),
+            Self::DefaultTokens => serializer.serialize_str("DEFAULT_TOKENS"),
+            Self::Contracts(contracts) => contracts.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenBalancesSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Spec {
+            Name(String),
+            Contracts(Vec<Address>),
+        }
+
+        match Spec::deserialize(deserializer)? {
+            Spec::Name(name) if name == "erc20" => Ok(Self::Erc20),
+            Spec::Name(name) if name == "DEFAULT_TOKENS" => Ok(Self::DefaultTokens),
+            Spec::Name(name) => {
+                Err(D::Error::custom(format!("unknown token balances spec {name}")))
+            }
+            Spec::Contracts(contracts) => Ok(Self::Contracts(contracts)),
+        }
+    }
+}
+
+impl From<Vec<Address>> for TokenBalancesSpec {
+    fn from(contracts: Vec<Address>) -> Self {
+        Self::Contracts(contracts)
+    }
+}
+
+/// The response of `alchemy_getTokenBalances`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalances {
+    /// The address the balances are of.
+    pub address: Address,
+    /// The balances of the tokens.
+    pub token_balances: Vec<TokenBalance>,
+    /// The key of the next page, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_key: Option<String>,
+}
+
+impl TokenBalances {
+    /// Returns the non-zero balances, skipping the tokens whose balance could not be fetched.
+    pub fn non_zero(&self) -> impl Iterator<Item = (Address, U256)> + '_ {
+        self.token_balances.iter().filter_map(|balance| {
+            balance
+                .token_balance
+                .filter(|value| !value.is_zero())
+                .map(|value| (balance.contract_address, value))
+        })
+    }
+}
+
+/// The balance of a token, as returned by `alchemy_getTokenBalances`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalance {
+    /// The address of the token contract.
+    pub contract_address: Address,
+    /// The balance, in the smallest unit of the token, if it could be fetched.
+    pub token_balance: Option<U256>,
+    /// The reason the balance could not be fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The metadata of a token, as returned by `alchemy_getTokenMetadata`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    /// The name of the token.
+    pub name: Option<String>,
+    /// The symbol of the token.
+    pub symbol: Option<String>,
+    /// The decimals of the token.
+    pub decimals: Option<u8>,
+    /// The URL of the logo of the token.
+    pub logo: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn serde_token_balances_spec() {
+        assert_eq!(serde_json::to_string(&TokenBalancesSpec::Erc20).unwrap(), r#""erc20""#);
+        assert_eq!(
+            serde_json::to_string(&TokenBalancesSpec::DefaultTokens).unwrap(),
+            r#""DEFAULT_TOKENS""#
+        );
+        let contracts = TokenBalancesSpec::from(vec![Address::with_last_byte(1)]);
+        let json = serde_json::to_string(&contracts).unwrap();
+        assert_eq!(json, r#"["0x0000000000000000000000000000000000000001"]"#);
+        assert_eq!(serde_json::from_str::<TokenBalancesSpec>(&json).unwrap(), contracts);
+        assert_eq!(
+            serde_json::from_str::<TokenBalancesSpec>(r#""DEFAULT_TOKENS""#).unwrap(),
+            TokenBalancesSpec::DefaultTokens
+        );
+        assert!(serde_json::from_str::<TokenBalancesSpec>(r#""erc721""#).is_err());
+    }
+
+    // <https://docs.alchemy.com/reference/alchemy-gettokenbalances>
+    #[test]
+    fn deserialize_token_balances() {
+        let s = r#"{
+            "address": "0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5",
+            "tokenBalances": [
+                {
+                    "contractAddress": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                    "tokenBalance": "0x0000000000000000000000000000000000000000000000000000000002faf080",
+                    "error": null
+                },
+                {
+                    "contractAddress": "0xdac17f958d2ee523a2206206994597c13d831ec7",
+                    "tokenBalance": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                    "error": null
+                },
+                {
+                    "contractAddress": "0x6b175474e89094c44da98b954eedeac495271d0f",
+                    "tokenBalance": null,
+                    "error": "execution reverted"
+                }
+            ]
+        }"#;
+        let balances: TokenBalances = serde_json::from_str(s).unwrap();
+        assert_eq!(balances.token_balances.len(), 3);
+        assert_eq!(balances.token_balances[2].error.as_deref(), Some("execution reverted"));
+        assert_eq!(
+            balances.non_zero().collect::<Vec<_>>(),
+            [(address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"), U256::from(50_000_000))]
+        );
+    }
+
+    #[test]
+    fn deserialize_token_metadata() {
+        let s = r#"{"decimals":6,"logo":null,"name":"USD Coin","symbol":"USDC"}"#;
+        let metadata: TokenMetadata = serde_json::from_str(s).unwrap();
+        assert_eq!(metadata.symbol.as_deref(), Some("USDC"));
+        assert_eq!(metadata.decimals, Some(6));
+        assert_eq!(metadata.logo, None);
+    }
+}
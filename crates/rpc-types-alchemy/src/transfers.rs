@@ -0,0 +1,323 @@
+//! Types for `alchemy_getAssetTransfers`: <https://docs.alchemy.com/reference/alchemy-getassettransfers>
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The category of an asset transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetTransferCategory {
+    /// A top level transfer of ether.
+    External,
+    /// An internal transfer of ether, made by a contract.
+    Internal,
+    /// An ERC-20 token transfer.
+    Erc20,
+    /// An ERC-721 token transfer.
+    Erc721,
+    /// An ERC-1155 token transfer.
+    Erc1155,
+    /// A transfer of a special NFT, e.g. CryptoPunks, that predates the NFT standards.
+    SpecialNft,
+}
+
+impl AssetTransferCategory {
+    /// All the asset transfer categories.
+    pub const ALL: [Self; 6] = [
+        Self::External,
+        Self::Internal,
+        Self::Erc20,
+        Self::Erc721,
+        Self::Erc1155,
+        Self::SpecialNft,
+    ];
+}
+
+/// The order in which asset transfers are returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssetTransferOrder {
+    /// From the oldest to the newest transfer.
+    #[default]
+    #[serde(rename = "asc")]
+    Ascending,
+    /// From the newest to the oldest transfer.
+    #[serde(rename = "desc")]
+    Descending,
+}
+
+/// The parameters of `alchemy_getAssetTransfers`.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_primitives::address;
+/// use alloy_rpc_types_alchemy::{AssetTransferCategory, AssetTransfersRequest};
+///
+/// let request = AssetTransfersRequest::new([AssetTransferCategory::Erc20])
+///     .from_block(18_000_000)
+///     .to_address(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+///     .with_metadata();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransfersRequest {
+    /// The first block to include, the genesis block by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockNumberOrTag>,
+    /// The last block to include, the latest block by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockNumberOrTag>,
+    /// Only include the transfers sent by this address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<Address>,
+    /// Only include the transfers received by this address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<Address>,
+    /// Only include the token transfers of these contracts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contract_addresses: Vec<Address>,
+    /// The categories of transfers to include.
+    pub category: Vec<AssetTransferCategory>,
+    /// The order of the transfers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<AssetTransferOrder>,
+    /// Whether to include the block timestamp of each transfer.
+    #[serde(default)]
+    pub with_metadata: bool,
+    /// Whether to exclude the transfers of no value, true by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_zero_value: Option<bool>,
+    /// The maximum number of transfers returned, 1000 by default.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub max_count: Option<u64>,
+    /// The key of the page to return, from the previous [`AssetTransfersResponse`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_key: Option<String>,
+}
+
+impl AssetTransfersRequest {
+    /// Creates a new request for the transfers of the given categories.
+    pub fn new(categories: impl IntoIterator<Item = AssetTransferCategory>) -> Self {
+        Self { category: categories.into_iter().collect(), ..Default::default() }
+    }
+
+    /// Sets the first block to include.
+    pub fn from_block(mut self, block: impl Into<BlockNumberOrTag>) -> Self {
+        self.from_block = Some(block.into());
+        self
+    }
+
+    /// Sets the last block to include.
+    pub fn to_block(mut self, block: impl Into<BlockNumberOrTag>) -> Self {
+        self.to_block = Some(block.into());
+        self
+    }
+
+    /// Only includes the transfers sent by `address`.
+    pub const fn from_address(mut self, address: Address) -> Self {
+        self.from_address = Some(address);
+        self
+    }
+
+    /// Only includes the transfers received by `address`.
+    pub const fn to_address(mut self, address: Address) -> Self {
+        self.to_address = Some(address);
+        self
+    }
+
+    /// Only includes the token transfers of `contract`.
+    pub fn contract_address(mut self, contract: Address) -> Self {
+        self.contract_addresses.push(contract);
+        self
+    }
+
+    /// Sets the order of the transfers.
+    pub const fn order(mut self, order: AssetTransferOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Includes the block timestamp of each transfer.
+    pub const fn with_metadata(mut self) -> Self {
+        self.with_metadata = true;
+        self
+    }
+
+    /// Sets whether to exclude the transfers of no value.
+    pub const fn exclude_zero_value(mut self, exclude: bool) -> Self {
+        self.exclude_zero_value = Some(exclude);
+        self
+    }
+
+    /// Sets the maximum number of transfers returned.
+    pub const fn max_count(mut self, max_count: u64) -> Self {
+        self.max_count = Some(max_count);
+        self
+    }
+
+    /// Sets the key of the page to return.
+    pub fn page_key(mut self, page_key: impl Into<String>) -> Self {
+        self.page_key = Some(page_key.into());
+        self
+    }
+}
+
+/// The response of `alchemy_getAssetTransfers`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransfersResponse {
+    /// The transfers of the page.
+    pub transfers: Vec<AssetTransfer>,
+    /// The key of the next page, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_key: Option<String>,
+}
+
+/// An asset transfer, as returned by `alchemy_getAssetTransfers`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransfer {
+    /// The category of the transfer.
+    pub category: AssetTransferCategory,
+    /// The number of the block of the transfer.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_num: u64,
+    /// The sender of the asset.
+    pub from: Address,
+    /// The recipient of the asset, if any.
+    pub to: Option<Address>,
+    /// The amount transferred, in units of the asset, e.g. ether and not wei.
+    ///
+    /// This is lossy, [`RawContract::value`] holds the exact amount.
+    pub value: Option<f64>,
+    /// The id of the ERC-721 token transferred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub erc721_token_id: Option<U256>,
+    /// The ids and amounts of the ERC-1155 tokens transferred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub erc1155_metadata: Option<Vec<Erc1155Transfer>>,
+    /// The id of the NFT transferred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<U256>,
+    /// The symbol of the asset, if known.
+    pub asset: Option<String>,
+    /// The unique id of the transfer.
+    pub unique_id: String,
+    /// The hash of the transaction of the transfer.
+    pub hash: B256,
+    /// The raw transfer details.
+    pub raw_contract: RawContract,
+    /// The metadata of the transfer, if requested with
+    /// [`with_metadata`](AssetTransfersRequest::with_metadata).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<AssetTransferMetadata>,
+}
+
+/// An ERC-1155 token transferred in an [`AssetTransfer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Erc1155Transfer {
+    /// The id of the token.
+    pub token_id: U256,
+    /// The amount transferred.
+    pub value: U256,
+}
+
+/// The raw details of an [`AssetTransfer`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawContract {
+    /// The exact amount transferred, in the smallest unit of the asset.
+    pub value: Option<U256>,
+    /// The address of the token contract, `None` for ether transfers.
+    pub address: Option<Address>,
+    /// The decimals of the token, if known.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub decimal: Option<u8>,
+}
+
+/// The metadata of an [`AssetTransfer`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransferMetadata {
+    /// The timestamp of the block of the transfer, in ISO 8601 format.
+    pub block_timestamp: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn serialize_request() {
+        let request = AssetTransfersRequest::new([AssetTransferCategory::External])
+            .from_block(0x10)
+            .to_address(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+            .order(AssetTransferOrder::Descending)
+            .max_count(100)
+            .page_key("abc");
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"fromBlock":"0x10","toAddress":"0xd8da6bf26964af9d7eed9e03e53415d37aa96045","category":["external"],"order":"desc","withMetadata":false,"maxCount":"0x64","pageKey":"abc"}"#
+        );
+    }
+
+    // <https://docs.alchemy.com/reference/alchemy-getassettransfers>
+    #[test]
+    fn deserialize_response() {
+        let s = r#"{
+            "transfers": [
+                {
+                    "blockNum": "0xd2113f",
+                    "uniqueId": "0x5b3d8c0e4f1de7e4bd9bbbd5e9a0b53fad8a2b6a4b2d9a6d1e0c9b6c7c8e2f5a:log:155",
+                    "hash": "0x5b3d8c0e4f1de7e4bd9bbbd5e9a0b53fad8a2b6a4b2d9a6d1e0c9b6c7c8e2f5a",
+                    "from": "0xef4396d9ff8107086d215a1c9f8866c54795d7c7",
+                    "to": "0x5c43b1ed97e52d009611d89b74fa829fe4ac56b1",
+                    "value": 1500.5,
+                    "erc721TokenId": null,
+                    "erc1155Metadata": null,
+                    "tokenId": null,
+                    "asset": "USDC",
+                    "category": "erc20",
+                    "rawContract": {
+                        "value": "0x596fd020",
+                        "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                        "decimal": "0x6"
+                    },
+                    "metadata": { "blockTimestamp": "2021-09-29T18:34:07.000Z" }
+                },
+                {
+                    "blockNum": "0xd2114a",
+                    "uniqueId": "0x7c3d8c0e4f1de7e4bd9bbbd5e9a0b53fad8a2b6a4b2d9a6d1e0c9b6c7c8e2f5a:external",
+                    "hash": "0x7c3d8c0e4f1de7e4bd9bbbd5e9a0b53fad8a2b6a4b2d9a6d1e0c9b6c7c8e2f5a",
+                    "from": "0xef4396d9ff8107086d215a1c9f8866c54795d7c7",
+                    "to": null,
+                    "value": null,
+                    "asset": "ETH",
+                    "category": "specialnft",
+                    "rawContract": { "value": null, "address": null, "decimal": null }
+                }
+            ],
+            "pageKey": "1f8d2ab0-8ee7-4a3c-8d4c-0b5e2c4a4a8c"
+        }"#;
+        let response: AssetTransfersResponse = serde_json::from_str(s).unwrap();
+        assert_eq!(response.page_key.as_deref(), Some("1f8d2ab0-8ee7-4a3c-8d4c-0b5e2c4a4a8c"));
+
+        let transfer = &response.transfers[0];
+        assert_eq!(transfer.category, AssetTransferCategory::Erc20);
+        assert_eq!(transfer.block_num, 0xd2113f);
+        assert_eq!(transfer.value, Some(1500.5));
+        assert_eq!(transfer.raw_contract.value, Some(U256::from(1_500_500_000u64)));
+        assert_eq!(transfer.raw_contract.decimal, Some(6));
+        assert_eq!(transfer.metadata.as_ref().unwrap().block_timestamp, "2021-09-29T18:34:07.000Z");
+
+        let transfer = &response.transfers[1];
+        assert_eq!(transfer.category, AssetTransferCategory::SpecialNft);
+        assert_eq!(transfer.to, None);
+        assert_eq!(transfer.raw_contract, RawContract::default());
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(serde_json::from_str::<AssetTransfersResponse>(&json).unwrap(), response);
+    }
+}
@@ -0,0 +1,158 @@
+//! Types for the `clique` namespace: <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-clique>
+
+use alloy_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The state of the clique authorization voting at a given block, as returned by
+/// `clique_getSnapshot`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CliqueSnapshot {
+    /// Block number where the snapshot was created.
+    pub number: u64,
+    /// Block hash where the snapshot was created.
+    pub hash: B256,
+    /// Set of authorized signers at this moment.
+    #[serde(with = "signer_set")]
+    pub signers: BTreeSet<Address>,
+    /// Set of recent signers, keyed by the block they signed, used for spam protection.
+    pub recents: BTreeMap<u64, Address>,
+    /// List of votes cast in chronological order.
+    pub votes: Vec<CliqueVote>,
+    /// Current vote tally, keyed by the voted address, to avoid recalculating.
+    pub tally: BTreeMap<Address, CliqueTally>,
+}
+
+impl CliqueSnapshot {
+    /// Returns whether the given address is an authorized signer in this snapshot.
+    pub fn is_signer(&self, address: &Address) -> bool {
+        self.signers.contains(address)
+    }
+
+    /// Returns whether the given address signed one of the recent blocks, and therefore cannot
+    /// seal the next block.
+    pub fn is_recent_signer(&self, address: &Address) -> bool {
+        self.recents.values().any(|signer| signer == address)
+    }
+}
+
+/// A single vote that an authorized signer made to modify the list of authorizations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CliqueVote {
+    /// Authorized signer that cast this vote.
+    pub signer: Address,
+    /// Block number the vote was cast in, used to expire old votes.
+    pub block: u64,
+    /// Account being voted on to change its authorization.
+    pub address: Address,
+    /// Whether to authorize or deauthorize the voted account.
+    pub authorize: bool,
+}
+
+/// A simple vote tally to keep the current score of votes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CliqueTally {
+    /// Whether the vote is about authorizing or kicking someone.
+    pub authorize: bool,
+    /// Number of votes until now wanting to pass the proposal.
+    pub votes: u64,
+}
+
+/// The sealing activity of the signers over the recent blocks, as returned by `clique_status`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliqueStatus {
+    /// Percentage of the recent blocks that were sealed by an in-turn signer.
+    pub inturn_percent: f64,
+    /// Number of recent blocks sealed by each signer.
+    pub sealer_activity: BTreeMap<Address, u64>,
+    /// Number of recent blocks the status was computed over.
+    pub num_blocks: u64,
+}
+
+/// (De)serializes a set of signers as a map from each signer to an empty object, as done by geth.
+mod signer_set {
+    use alloy_primitives::Address;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[derive(Serialize)]
+    struct Empty {}
+
+    pub(super) fn serialize<S: Serializer>(
+        signers: &BTreeSet<Address>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(signers.iter().map(|signer| (signer, Empty {})))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeSet<Address>, D::Error> {
+        let signers = BTreeMap::<Address, serde::de::IgnoredAny>::deserialize(deserializer)?;
+        Ok(signers.into_keys().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn serde_clique_snapshot() {
+        let s = r#"{
+            "number": 6,
+            "hash": "0x4b8d4b1f8c0a5e29bd4e9d02e3f6b3f2f4bb9d9dfea1d1d9a0c6d0f7b37f3c6a",
+            "signers": {
+                "0x7ffc57839b00206d1ad20c69a1981b489f772031": {},
+                "0xb279182d99e65703f0076e4812653aab85fca0f0": {}
+            },
+            "recents": {
+                "5": "0x7ffc57839b00206d1ad20c69a1981b489f772031",
+                "6": "0xb279182d99e65703f0076e4812653aab85fca0f0"
+            },
+            "votes": [
+                {
+                    "signer": "0x7ffc57839b00206d1ad20c69a1981b489f772031",
+                    "block": 4,
+                    "address": "0x0d4b0a2a3b3f9d2f6a4d1d6e8d5e2b4c1a3e5f70",
+                    "authorize": true
+                }
+            ],
+            "tally": {
+                "0x0d4b0a2a3b3f9d2f6a4d1d6e8d5e2b4c1a3e5f70": { "authorize": true, "votes": 1 }
+            }
+        }"#;
+        let snapshot: CliqueSnapshot = serde_json::from_str(s).unwrap();
+        let signer = address!("7ffc57839b00206d1ad20c69a1981b489f772031");
+        let candidate = address!("0d4b0a2a3b3f9d2f6a4d1d6e8d5e2b4c1a3e5f70");
+        assert_eq!(snapshot.number, 6);
+        assert_eq!(snapshot.signers.len(), 2);
+        assert!(snapshot.is_signer(&signer));
+        assert!(!snapshot.is_signer(&candidate));
+        assert!(snapshot.is_recent_signer(&signer));
+        assert_eq!(snapshot.recents[&5], signer);
+        assert_eq!(
+            snapshot.votes,
+            vec![CliqueVote { signer, block: 4, address: candidate, authorize: true }]
+        );
+        assert_eq!(snapshot.tally[&candidate], CliqueTally { authorize: true, votes: 1 });
+
+        let serialized = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(serialized, serde_json::from_str::<serde_json::Value>(s).unwrap());
+    }
+
+    #[test]
+    fn serde_clique_status() {
+        let s = r#"{"inturnPercent":75.5,"sealerActivity":{"0x7ffc57839b00206d1ad20c69a1981b489f772031":3},"numBlocks":4}"#;
+        let status: CliqueStatus = serde_json::from_str(s).unwrap();
+        assert_eq!(status.inturn_percent, 75.5);
+        assert_eq!(
+            status.sealer_activity[&address!("7ffc57839b00206d1ad20c69a1981b489f772031")],
+            3
+        );
+        assert_eq!(status.num_blocks, 4);
+        assert_eq!(serde_json::to_string(&status).unwrap(), s);
+    }
+}
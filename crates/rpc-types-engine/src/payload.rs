@@ -1,6 +1,15 @@
 //! Payload types.
-use alloy_consensus::{Blob, Bytes48};
-use alloy_eips::{eip6110::DepositRequest, eip7002::WithdrawalRequest};
+use crate::MaybeCancunPayloadFields;
+use alloy_consensus::{
+    constants::MAXIMUM_EXTRA_DATA_SIZE,
+    proofs::{calculate_requests_root, calculate_transactions_root, calculate_withdrawals_root},
+    Blob, Block, BlockBody, Bytes48, Header, Request, TxEnvelope, EMPTY_OMMER_ROOT_HASH,
+};
+use alloy_eips::{
+    eip2718::{Decodable2718, Eip2718Error, Encodable2718},
+    eip6110::DepositRequest,
+    eip7002::WithdrawalRequest,
+};
 use alloy_primitives::{Address, Bloom, Bytes, B256, B64, U256};
 use alloy_rpc_types_eth::{transaction::BlobTransactionSidecar, Withdrawal};
 use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
@@ -759,6 +768,223 @@ impl<'de> Deserialize<'de> for ExecutionPayload {
     }
 }
 
+impl ExecutionPayloadV1 {
+    /// Converts a block into a payload, computing its block hash.
+    ///
+    /// The blob sidecars of the transactions must be split from them, as done by
+    /// [`PayloadBuilder`](alloy_consensus::PayloadBuilder).
+    pub fn from_block_slow(block: &Block) -> Self {
+        let header = &block.header;
+        Self {
+            parent_hash: header.parent_hash,
+            fee_recipient: header.beneficiary,
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            logs_bloom: header.logs_bloom,
+            prev_randao: header.mix_hash,
+            block_number: header.number,
+            gas_limit: header.gas_limit as u64,
+            gas_used: header.gas_used as u64,
+            timestamp: header.timestamp,
+            extra_data: header.extra_data.clone(),
+            base_fee_per_gas: U256::from(header.base_fee_per_gas.unwrap_or_default()),
+            block_hash: header.hash_slow(),
+            transactions: block
+                .body
+                .transactions
+                .iter()
+                .map(|tx| tx.encoded_2718().into())
+                .collect(),
+        }
+    }
+
+    /// Converts the payload into a block, without checking its block hash.
+    pub fn try_into_block(self) -> Result<Block, PayloadError> {
+        if self.extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(PayloadError::ExtraData(self.extra_data));
+        }
+        let base_fee_per_gas = u128::try_from(self.base_fee_per_gas)
+            .map_err(|_| PayloadError::BaseFee(self.base_fee_per_gas))?;
+        let transactions = self
+            .transactions
+            .iter()
+            .map(|tx| {
+                TxEnvelope::decode_2718(&mut tx.as_ref()).map_err(|err| match err {
+                    Eip2718Error::RlpError(err) => err,
+                    _ => alloy_rlp::Error::Custom("unexpected transaction type"),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let header = Header {
+            parent_hash: self.parent_hash,
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            beneficiary: self.fee_recipient,
+            state_root: self.state_root,
+            transactions_root: calculate_transactions_root(&transactions),
+            receipts_root: self.receipts_root,
+            logs_bloom: self.logs_bloom,
+            number: self.block_number,
+            gas_limit: self.gas_limit.into(),
+            gas_used: self.gas_used.into(),
+            timestamp: self.timestamp,
+            mix_hash: self.prev_randao,
+            base_fee_per_gas: Some(base_fee_per_gas),
+            extra_data: self.extra_data,
+            ..Default::default()
+        };
+        Ok(Block::new(header, BlockBody { transactions, ..Default::default() }))
+    }
+}
+
+impl ExecutionPayloadV2 {
+    /// Converts a block into a payload, computing its block hash.
+    ///
+    /// See [`ExecutionPayloadV1::from_block_slow`].
+    pub fn from_block_slow(block: &Block) -> Self {
+        Self {
+            payload_inner: ExecutionPayloadV1::from_block_slow(block),
+            withdrawals: block.body.withdrawals.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Converts the payload into a block, without checking its block hash.
+    pub fn try_into_block(self) -> Result<Block, PayloadError> {
+        let mut block = self.payload_inner.try_into_block()?;
+        block.header.withdrawals_root = Some(calculate_withdrawals_root(&self.withdrawals));
+        block.body.withdrawals = Some(self.withdrawals);
+        Ok(block)
+    }
+}
+
+impl ExecutionPayloadV3 {
+    /// Converts a block into a payload, computing its block hash.
+    ///
+    /// See [`ExecutionPayloadV1::from_block_slow`].
+    pub fn from_block_slow(block: &Block) -> Self {
+        Self {
+            payload_inner: ExecutionPayloadV2::from_block_slow(block),
+            blob_gas_used: block.header.blob_gas_used.unwrap_or_default() as u64,
+            excess_blob_gas: block.header.excess_blob_gas.unwrap_or_default() as u64,
+        }
+    }
+
+    /// Converts the payload into a block, without checking its block hash.
+    ///
+    /// The parent beacon block root is not part of the payload, and is left unset.
+    pub fn try_into_block(self) -> Result<Block, PayloadError> {
+        let mut block = self.payload_inner.try_into_block()?;
+        block.header.blob_gas_used = Some(self.blob_gas_used.into());
+        block.header.excess_blob_gas = Some(self.excess_blob_gas.into());
+        Ok(block)
+    }
+}
+
+impl ExecutionPayloadV4 {
+    /// Converts a block into a payload, computing its block hash.
+    ///
+    /// Requests of other types than deposit and withdrawal requests are not part of the payload.
+    /// See also [`ExecutionPayloadV1::from_block_slow`].
+    pub fn from_block_slow(block: &Block) -> Self {
+        let requests = block.body.requests.as_deref().unwrap_or_default();
+        Self {
+            payload_inner: ExecutionPayloadV3::from_block_slow(block),
+            deposit_requests: requests
+                .iter()
+                .filter_map(|request| match request {
+                    Request::DepositRequest(request) => Some(*request),
+                    _ => None,
+                })
+                .collect(),
+            withdrawal_requests: requests
+                .iter()
+                .filter_map(|request| match request {
+                    Request::WithdrawalRequest(request) => Some(*request),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Converts the payload into a block, without checking its block hash.
+    ///
+    /// The parent beacon block root is not part of the payload, and is left unset.
+    pub fn try_into_block(self) -> Result<Block, PayloadError> {
+        let mut block = self.payload_inner.try_into_block()?;
+        let requests: Vec<_> = self
+            .deposit_requests
+            .into_iter()
+            .map(Request::DepositRequest)
+            .chain(self.withdrawal_requests.into_iter().map(Request::WithdrawalRequest))
+            .collect();
+        block.header.requests_root = Some(calculate_requests_root(&requests));
+        block.body.requests = Some(requests);
+        Ok(block)
+    }
+}
+
+impl ExecutionPayload {
+    /// Converts a block into a payload of the latest version its header has the fields of: V4 with
+    /// a requests root, V3 with blob gas fields, V2 with a withdrawals root, and V1 otherwise.
+    ///
+    /// See also [`ExecutionPayloadV1::from_block_slow`].
+    pub fn from_block_slow(block: &Block) -> Self {
+        let header = &block.header;
+        if header.requests_root.is_some() {
+            ExecutionPayloadV4::from_block_slow(block).into()
+        } else if header.blob_gas_used.is_some() || header.excess_blob_gas.is_some() {
+            ExecutionPayloadV3::from_block_slow(block).into()
+        } else if header.withdrawals_root.is_some() {
+            ExecutionPayloadV2::from_block_slow(block).into()
+        } else {
+            ExecutionPayloadV1::from_block_slow(block).into()
+        }
+    }
+
+    /// Converts the payload into a block, without checking its block hash.
+    pub fn try_into_block(self) -> Result<Block, PayloadError> {
+        match self {
+            Self::V1(payload) => payload.try_into_block(),
+            Self::V2(payload) => payload.try_into_block(),
+            Self::V3(payload) => payload.try_into_block(),
+            Self::V4(payload) => payload.try_into_block(),
+        }
+    }
+
+    /// Converts the payload into a block with the fields sent alongside it to
+    /// `engine_newPayloadV3` and later, checking its block hash and blob versioned hashes.
+    pub fn try_into_block_with_cancun_fields(
+        self,
+        cancun_fields: &MaybeCancunPayloadFields,
+    ) -> Result<Block, PayloadError> {
+        let block_hash = self.block_hash();
+        let post_cancun = self.as_v3().is_some();
+        let mut block = self.try_into_block()?;
+
+        match (post_cancun, cancun_fields.as_ref()) {
+            (true, Some(fields)) => {
+                block.header.parent_beacon_block_root = Some(fields.parent_beacon_block_root);
+                let versioned_hashes = block.body.transactions.iter().flat_map(|tx| match tx {
+                    TxEnvelope::Eip4844(tx) => tx.tx().tx().blob_versioned_hashes.as_slice(),
+                    _ => &[],
+                });
+                if !versioned_hashes.eq(&fields.versioned_hashes) {
+                    return Err(PayloadError::InvalidVersionedHashes);
+                }
+            }
+            (true, None) => return Err(PayloadError::PostCancunWithoutCancunFields),
+            (false, Some(_)) => return Err(PayloadError::PreCancunWithCancunFields),
+            (false, None) => {}
+        }
+
+        let execution = block.hash_slow();
+        if execution != block_hash {
+            return Err(PayloadError::BlockHash { execution, consensus: block_hash });
+        }
+        Ok(block)
+    }
+}
+
 /// Error that can occur when handling payloads.
 #[derive(Debug, thiserror::Error)]
 pub enum PayloadError {
@@ -848,6 +1074,21 @@ pub struct ExecutionPayloadBodyV1 {
     pub withdrawals: Option<Vec<Withdrawal>>,
 }
 
+impl ExecutionPayloadBodyV1 {
+    /// Returns the body of the given block.
+    pub fn from_block(block: &Block) -> Self {
+        Self {
+            transactions: block
+                .body
+                .transactions
+                .iter()
+                .map(|tx| tx.encoded_2718().into())
+                .collect(),
+            withdrawals: block.body.withdrawals.clone(),
+        }
+    }
+}
+
 /// This structure contains the attributes required to initiate a payload build process in the
 /// context of an `engine_forkchoiceUpdated` call.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -1050,6 +1291,7 @@ pub enum PayloadValidationError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::CancunPayloadFields;
 
     #[test]
     fn serde_payload_status() {
@@ -1147,7 +1389,70 @@ mod tests {
         assert_eq!(serde_json::to_string(&payload).unwrap(), s);
 
         let any_payload: ExecutionPayload = serde_json::from_str(s).unwrap();
-        assert_eq!(any_payload, payload.into());
+        assert_eq!(any_payload, payload.clone().into());
+
+        let block = any_payload
+            .try_into_block_with_cancun_fields(&MaybeCancunPayloadFields::none())
+            .unwrap();
+        assert_eq!(block.hash_slow(), payload.block_hash);
+        assert_eq!(
+            block.header.transactions_root,
+            calculate_transactions_root(&block.body.transactions)
+        );
+        assert_eq!(ExecutionPayloadV1::from_block_slow(&block), payload);
+    }
+
+    #[test]
+    fn payload_block_roundtrip_cancun() {
+        let template = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            withdrawals_root: Some(Default::default()),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::with_last_byte(1)),
+            ..Default::default()
+        };
+        let withdrawal = Withdrawal {
+            index: 1,
+            validator_index: 2,
+            address: Address::with_last_byte(3),
+            amount: 4,
+        };
+        let built =
+            alloy_consensus::PayloadBuilder::new(template).withdrawals(vec![withdrawal]).build();
+
+        let payload = ExecutionPayload::from_block_slow(&built.block);
+        assert_eq!(payload.block_hash(), built.hash_slow());
+        assert_eq!(payload.as_v2().unwrap().withdrawals, [withdrawal]);
+        assert!(payload.as_v3().is_some());
+
+        let fields = |parent_beacon_block_root, versioned_hashes| {
+            MaybeCancunPayloadFields::from(CancunPayloadFields {
+                parent_beacon_block_root,
+                versioned_hashes,
+            })
+        };
+        let block = payload
+            .clone()
+            .try_into_block_with_cancun_fields(&fields(B256::with_last_byte(1), vec![]))
+            .unwrap();
+        assert_eq!(block, built.block);
+
+        let err = payload
+            .clone()
+            .try_into_block_with_cancun_fields(&fields(B256::with_last_byte(2), vec![]))
+            .unwrap_err();
+        assert!(err.is_block_hash_mismatch());
+        let err = payload
+            .clone()
+            .try_into_block_with_cancun_fields(&fields(B256::with_last_byte(1), vec![B256::ZERO]))
+            .unwrap_err();
+        assert!(err.is_invalid_versioned_hashes());
+        assert!(matches!(
+            payload.try_into_block_with_cancun_fields(&MaybeCancunPayloadFields::none()),
+            Err(PayloadError::PostCancunWithoutCancunFields)
+        ));
     }
 
     #[test]
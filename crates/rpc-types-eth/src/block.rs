@@ -1,6 +1,7 @@
 //! Block RPC types.
 
-use crate::{Transaction, Withdrawal};
+use crate::{other_fields_heap_size, InMemorySize, Transaction, Withdrawal};
+use alloy_eips::eip4844::BlobParams;
 use alloy_primitives::{Address, BlockHash, Bloom, Bytes, B256, B64, U256, U64};
 use alloy_serde::OtherFields;
 use serde::{ser::Error, Deserialize, Serialize, Serializer};
@@ -20,6 +21,7 @@ pub struct Block<T = Transaction> {
     pub header: Header,
     /// Uncles' hashes.
     #[serde(default)]
+    #[cfg_attr(feature = "lenient", serde(deserialize_with = "alloy_serde::null_as_default"))]
     pub uncles: Vec<B256>,
     /// Block Transactions. In the case of an uncle block, this field is not included in RPC
     /// responses, and when deserialized, it will be set to [BlockTransactions::Uncle].
@@ -29,7 +31,7 @@ pub struct Block<T = Transaction> {
     )]
     pub transactions: BlockTransactions<T>,
     /// Integer the size of this block in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
     pub size: Option<U256>,
     /// Withdrawals in the block.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -47,6 +49,10 @@ impl Block {
 }
 
 /// Block header representation.
+///
+/// With the `lenient` feature, the hashes, `miner`, `logsBloom`, `difficulty` and `extraData`
+/// fields default to zero when they are missing or `null`, as returned by some non-ethereum
+/// networks.
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -54,21 +60,54 @@ pub struct Header {
     /// Hash of the block
     pub hash: Option<BlockHash>,
     /// Hash of the parent
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub parent_hash: B256,
     /// Hash of the uncles
     #[serde(rename = "sha3Uncles")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub uncles_hash: B256,
     /// Alias of `author`
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub miner: Address,
     /// State root hash
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub state_root: B256,
     /// Transactions root hash
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub transactions_root: B256,
     /// Transactions receipts root hash
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub receipts_root: B256,
     /// Logs bloom
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub logs_bloom: Bloom,
     /// Difficulty
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
+    #[cfg_attr(not(feature = "lenient"), serde(with = "alloy_serde::quantity"))]
     pub difficulty: U256,
     /// Block number
     #[serde(default, with = "alloy_serde::quantity::opt")]
@@ -83,9 +122,13 @@ pub struct Header {
     #[serde(default, with = "alloy_serde::quantity")]
     pub timestamp: u64,
     /// Total difficulty
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
     pub total_difficulty: Option<U256>,
     /// Extra data
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub extra_data: Bytes,
     /// Mix Hash
     ///
@@ -119,7 +162,7 @@ pub struct Header {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub parent_beacon_block_root: Option<B256>,
     /// EIP-7685 requests root.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "requestsHash")]
     pub requests_root: Option<B256>,
 }
 
@@ -131,6 +174,13 @@ impl Header {
         self.excess_blob_gas.map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for _this_ block with the given blob parameters of its fork.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None
+    pub fn blob_fee_with_params(&self, params: BlobParams) -> Option<u128> {
+        self.excess_blob_gas.map(|excess| params.calc_blob_fee(excess))
+    }
+
     /// Returns the blob fee for the next block according to the EIP-4844 spec.
     ///
     /// Returns `None` if `excess_blob_gas` is None.
@@ -140,6 +190,16 @@ impl Header {
         self.next_block_excess_blob_gas().map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for the next block with the given blob parameters of its fork.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None.
+    ///
+    /// See also [Self::next_block_excess_blob_gas_with_params]
+    pub fn next_block_blob_fee_with_params(&self, params: BlobParams) -> Option<u128> {
+        self.next_block_excess_blob_gas_with_params(params)
+            .map(|excess| params.calc_blob_fee(excess))
+    }
+
     /// Calculate excess blob gas for the next block according to the EIP-4844
     /// spec.
     ///
@@ -147,6 +207,13 @@ impl Header {
     pub fn next_block_excess_blob_gas(&self) -> Option<u128> {
         Some(calc_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
+
+    /// Calculate excess blob gas for the next block with the given blob parameters of its fork.
+    ///
+    /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
+    pub fn next_block_excess_blob_gas_with_params(&self, params: BlobParams) -> Option<u128> {
+        Some(params.next_block_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+    }
 }
 
 /// Block Transactions depending on the boolean attribute of `eth_getBlockBy*`,
@@ -546,18 +613,28 @@ pub struct BlockOverrides {
     /// For `eth_callMany` this will be the block number of the first simulated block. Each
     /// following block increments its block number by 1
     // Note: geth uses `number`, erigon uses `blockNumber`
-    #[serde(default, skip_serializing_if = "Option::is_none", alias = "blockNumber")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        alias = "blockNumber",
+        with = "alloy_serde::quantity::opt"
+    )]
     pub number: Option<U256>,
     /// Overrides the difficulty of the block.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
     pub difficulty: Option<U256>,
     /// Overrides the timestamp of the block.
     // Note: geth uses `time`, erigon uses `timestamp`
-    #[serde(default, skip_serializing_if = "Option::is_none", alias = "timestamp")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        alias = "timestamp",
+        with = "alloy_serde::quantity::opt"
+    )]
     pub time: Option<U64>,
     /// Overrides the gas limit of the block.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub gas_limit: Option<u128>,
     /// Overrides the coinbase address of the block.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub coinbase: Option<Address>,
@@ -565,14 +642,43 @@ pub struct BlockOverrides {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub random: Option<B256>,
     /// Overrides the basefee of the block.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub base_fee: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub base_fee: Option<u128>,
     /// A dictionary that maps blockNumber to a user-defined hash. It could be queried from the
     /// solidity opcode BLOCKHASH.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub block_hash: Option<BTreeMap<u64, B256>>,
 }
 
+impl InMemorySize for Header {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.extra_data.heap_size()
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for BlockTransactions<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::Full(txs) => txs.heap_size(),
+            Self::Hashes(hashes) => hashes.heap_size(),
+            Self::Uncle => 0,
+        }
+    }
+}
+
+impl<T: InMemorySize> InMemorySize for Block<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.header.heap_size()
+            + self.uncles.heap_size()
+            + self.transactions.heap_size()
+            + self.withdrawals.heap_size()
+            + other_fields_heap_size(&self.other)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use arbitrary::Arbitrary;
@@ -888,4 +994,75 @@ mod tests {
         assert!(block.transactions.is_empty());
         assert!(block.transactions.as_transactions().is_some());
     }
+
+    #[test]
+    fn serde_nonstandard_block_fields() {
+        let s = r#"{
+    "hash": "0xb25d0e54ca0104e3ebfb5a1dcdf9528140854d609886a300946fd6750dcb19f4",
+    "parentHash": "0x9400ec9ef59689c157ac89eeed906f15ddd768f94e1575e0e27d37c241439a5d",
+    "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+    "miner": "0x829bd824b016326a401d083b33d092293333a830",
+    "stateRoot": "0x546e330050c66d02923e7f1f3e925efaf64e4384eeecf2288f40088714a77a84",
+    "transactionsRoot": "0xd5eb3ad6d7c7a4798cc5fb14a6820073f44a941107c5d79dac60bd16325631fe",
+    "receiptsRoot": "0xb21c41cbb3439c5af25304e1405524c885e733b16203221900cb7f4b387b62f0",
+    "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+    "difficulty": "0x0",
+    "number": "0xa9a230",
+    "gasLimit": "0xbe5a66",
+    "gasUsed": "0xbe0fcc",
+    "timestamp": "0x5f93b749",
+    "extraData": "0x",
+    "baseFeePerGas": null,
+    "l1BlockNumber": "0x1212e4b",
+    "uncles": [],
+    "transactions": []
+}"#;
+
+        let block = serde_json::from_str::<Block>(s).unwrap();
+        assert_eq!(block.header.mix_hash, None);
+        assert_eq!(block.header.base_fee_per_gas, None);
+        assert_eq!(
+            block.other.get_deserialized::<U64>("l1BlockNumber").unwrap().unwrap(),
+            U64::from(0x1212e4b)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lenient")]
+    fn serde_lenient_block() {
+        let s = r#"{
+    "hash": "0xb25d0e54ca0104e3ebfb5a1dcdf9528140854d609886a300946fd6750dcb19f4",
+    "parentHash": "0x9400ec9ef59689c157ac89eeed906f15ddd768f94e1575e0e27d37c241439a5d",
+    "miner": null,
+    "stateRoot": "0x546e330050c66d02923e7f1f3e925efaf64e4384eeecf2288f40088714a77a84",
+    "transactionsRoot": "0xd5eb3ad6d7c7a4798cc5fb14a6820073f44a941107c5d79dac60bd16325631fe",
+    "receiptsRoot": "0xb21c41cbb3439c5af25304e1405524c885e733b16203221900cb7f4b387b62f0",
+    "number": "0xa9a230",
+    "gasLimit": "0xbe5a66",
+    "gasUsed": "0xbe0fcc",
+    "timestamp": "0x5f93b749",
+    "extraData": "0x",
+    "uncles": null,
+    "transactions": [{
+        "hash": "0x21f6554c28453a01e7276c1db2fc1695bb512b170818bfa98fa8136433100616",
+        "nonce": "0x1",
+        "from": "0x9a53bfba35269414f3b2d20b52ca01b15932c7b2",
+        "to": null,
+        "value": null,
+        "gas": "0x5208",
+        "data": "0x1234",
+        "feeCurrency": null
+    }]
+}"#;
+
+        let block = serde_json::from_str::<Block>(s).unwrap();
+        assert_eq!(block.header.miner, Address::ZERO);
+        assert_eq!(block.header.uncles_hash, B256::ZERO);
+        assert_eq!(block.header.logs_bloom, Bloom::ZERO);
+        assert!(block.uncles.is_empty());
+        let tx = &block.transactions.as_transactions().unwrap()[0];
+        assert_eq!(tx.value, U256::ZERO);
+        assert_eq!(tx.input, Bytes::from_static(&[0x12, 0x34]));
+        assert!(tx.other.contains_key("feeCurrency"));
+    }
 }
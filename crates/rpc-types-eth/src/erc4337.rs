@@ -0,0 +1,426 @@
+//! Types for [ERC-4337] account abstraction: user operations, and their sponsorship by paymasters.
+//!
+//! [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+
+use alloy_primitives::{Address, Bytes, U256};
+use serde::{Deserialize, Serialize};
+
+/// A user operation, for the v0.6 entry point.
+///
+/// The paymaster is packed with its data in [`paymaster_and_data`](Self::paymaster_and_data).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// The account sending the operation.
+    pub sender: Address,
+    /// The anti-replay nonce, with its key in the upper 192 bits.
+    pub nonce: U256,
+    /// The factory address and data deploying the account, if it does not exist yet.
+    pub init_code: Bytes,
+    /// The data passed to the account for execution.
+    pub call_data: Bytes,
+    /// The gas limit of the execution.
+    pub call_gas_limit: U256,
+    /// The gas limit of the verification.
+    pub verification_gas_limit: U256,
+    /// The gas paid to the bundler for the overhead of the operation.
+    pub pre_verification_gas: U256,
+    /// The maximum fee per gas, as in [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559).
+    pub max_fee_per_gas: U256,
+    /// The maximum priority fee per gas, as in [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559).
+    pub max_priority_fee_per_gas: U256,
+    /// The paymaster address followed by its data, or empty if the account pays for itself.
+    pub paymaster_and_data: Bytes,
+    /// The signature of the operation, checked by the account.
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// Returns the paymaster of the operation, if any.
+    pub fn paymaster(&self) -> Option<Address> {
+        self.paymaster_and_data.get(..20).map(Address::from_slice)
+    }
+
+    /// Sets the paymaster address followed by its data.
+    pub fn with_paymaster_and_data(mut self, paymaster_and_data: Bytes) -> Self {
+        self.paymaster_and_data = paymaster_and_data;
+        self
+    }
+
+    /// Applies the result of a paymaster sponsoring the operation: its paymaster data, and the gas
+    /// limits it estimated, if any.
+    pub fn apply_sponsorship(&mut self, sponsorship: &PaymasterSponsorship) {
+        if let Some(paymaster_and_data) = sponsorship.paymaster_and_data() {
+            self.paymaster_and_data = paymaster_and_data;
+        }
+        sponsorship.apply_gas(
+            &mut self.call_gas_limit,
+            &mut self.verification_gas_limit,
+            &mut self.pre_verification_gas,
+        );
+    }
+}
+
+/// A user operation, for the v0.7 entry point.
+///
+/// This is the form user operations are sent to bundlers in, the entry point packing the factory,
+/// gas, and paymaster fields together on chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackedUserOperation {
+    /// The account sending the operation.
+    pub sender: Address,
+    /// The anti-replay nonce, with its key in the upper 192 bits.
+    pub nonce: U256,
+    /// The factory deploying the account, if it does not exist yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    /// The data passed to the factory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    /// The data passed to the account for execution.
+    pub call_data: Bytes,
+    /// The gas limit of the execution.
+    pub call_gas_limit: U256,
+    /// The gas limit of the verification.
+    pub verification_gas_limit: U256,
+    /// The gas paid to the bundler for the overhead of the operation.
+    pub pre_verification_gas: U256,
+    /// The maximum fee per gas, as in [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559).
+    pub max_fee_per_gas: U256,
+    /// The maximum priority fee per gas, as in [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559).
+    pub max_priority_fee_per_gas: U256,
+    /// The paymaster paying for the operation, if the account does not pay for itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    /// The gas limit of the paymaster verification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    /// The gas limit of the paymaster post-operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    /// The data passed to the paymaster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    /// The signature of the operation, checked by the account.
+    pub signature: Bytes,
+}
+
+impl PackedUserOperation {
+    /// Sets the paymaster, its gas limits, and its data.
+    pub fn with_paymaster(
+        mut self,
+        paymaster: Address,
+        verification_gas_limit: U256,
+        post_op_gas_limit: U256,
+        data: Bytes,
+    ) -> Self {
+        self.paymaster = Some(paymaster);
+        self.paymaster_verification_gas_limit = Some(verification_gas_limit);
+        self.paymaster_post_op_gas_limit = Some(post_op_gas_limit);
+        self.paymaster_data = Some(data);
+        self
+    }
+
+    /// Returns the factory and its data, packed as the `initCode` of the entry point.
+    pub fn init_code(&self) -> Bytes {
+        let Some(factory) = self.factory else { return Bytes::new() };
+        let data = self.factory_data.as_ref().map_or(&[][..], |data| &data[..]);
+        [factory.as_slice(), data].concat().into()
+    }
+
+    /// Returns the paymaster, its gas limits, and its data, packed as the `paymasterAndData` of
+    /// the entry point, or empty if there is no paymaster.
+    ///
+    /// Gas limits are truncated to their lower 128 bits, as packed by the entry point.
+    pub fn paymaster_and_data(&self) -> Bytes {
+        let Some(paymaster) = self.paymaster else { return Bytes::new() };
+        let gas = |limit: Option<U256>| limit.unwrap_or_default().to_be_bytes::<32>();
+        let verification_gas_limit = gas(self.paymaster_verification_gas_limit);
+        let post_op_gas_limit = gas(self.paymaster_post_op_gas_limit);
+        [
+            paymaster.as_slice(),
+            &verification_gas_limit[16..],
+            &post_op_gas_limit[16..],
+            self.paymaster_data.as_ref().map_or(&[][..], |data| &data[..]),
+        ]
+        .concat()
+        .into()
+    }
+
+    /// Applies the result of a paymaster sponsoring the operation: its paymaster fields, and the
+    /// gas limits it estimated, if any.
+    pub fn apply_sponsorship(&mut self, sponsorship: &PaymasterSponsorship) {
+        if let Some(paymaster) = sponsorship.paymaster {
+            self.paymaster = Some(paymaster);
+            self.paymaster_data = Some(sponsorship.paymaster_data.clone().unwrap_or_default());
+        }
+        if let Some(limit) = sponsorship.paymaster_verification_gas_limit {
+            self.paymaster_verification_gas_limit = Some(limit);
+        }
+        if let Some(limit) = sponsorship.paymaster_post_op_gas_limit {
+            self.paymaster_post_op_gas_limit = Some(limit);
+        }
+        sponsorship.apply_gas(
+            &mut self.call_gas_limit,
+            &mut self.verification_gas_limit,
+            &mut self.pre_verification_gas,
+        );
+    }
+}
+
+/// A user operation sent to a bundler or a paymaster, for either entry point version.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SendUserOperation {
+    /// A user operation for the v0.6 entry point.
+    EntryPointV06(UserOperation),
+    /// A user operation for the v0.7 entry point.
+    EntryPointV07(PackedUserOperation),
+}
+
+impl SendUserOperation {
+    /// Returns the account sending the operation.
+    pub const fn sender(&self) -> Address {
+        match self {
+            Self::EntryPointV06(op) => op.sender,
+            Self::EntryPointV07(op) => op.sender,
+        }
+    }
+
+    /// Returns the nonce of the operation.
+    pub const fn nonce(&self) -> U256 {
+        match self {
+            Self::EntryPointV06(op) => op.nonce,
+            Self::EntryPointV07(op) => op.nonce,
+        }
+    }
+
+    /// Sets the nonce of the operation.
+    pub fn set_nonce(&mut self, nonce: U256) {
+        match self {
+            Self::EntryPointV06(op) => op.nonce = nonce,
+            Self::EntryPointV07(op) => op.nonce = nonce,
+        }
+    }
+
+    /// Returns the maximum fee per gas of the operation.
+    pub const fn max_fee_per_gas(&self) -> U256 {
+        match self {
+            Self::EntryPointV06(op) => op.max_fee_per_gas,
+            Self::EntryPointV07(op) => op.max_fee_per_gas,
+        }
+    }
+
+    /// Sets the maximum fee, and maximum priority fee, per gas of the operation.
+    pub fn set_fees(&mut self, max_fee_per_gas: U256, max_priority_fee_per_gas: U256) {
+        let (max_fee, max_priority_fee) = match self {
+            Self::EntryPointV06(op) => (&mut op.max_fee_per_gas, &mut op.max_priority_fee_per_gas),
+            Self::EntryPointV07(op) => (&mut op.max_fee_per_gas, &mut op.max_priority_fee_per_gas),
+        };
+        *max_fee = max_fee_per_gas;
+        *max_priority_fee = max_priority_fee_per_gas;
+    }
+
+    /// Applies the result of a paymaster sponsoring the operation.
+    pub fn apply_sponsorship(&mut self, sponsorship: &PaymasterSponsorship) {
+        match self {
+            Self::EntryPointV06(op) => op.apply_sponsorship(sponsorship),
+            Self::EntryPointV07(op) => op.apply_sponsorship(sponsorship),
+        }
+    }
+
+    /// Applies the gas limits estimated by a bundler.
+    pub fn apply_gas_estimation(&mut self, gas: &UserOperationGasEstimation) {
+        match self {
+            Self::EntryPointV06(op) => {
+                op.call_gas_limit = gas.call_gas_limit;
+                op.verification_gas_limit = gas.verification_gas_limit;
+                op.pre_verification_gas = gas.pre_verification_gas;
+            }
+            Self::EntryPointV07(op) => {
+                op.call_gas_limit = gas.call_gas_limit;
+                op.verification_gas_limit = gas.verification_gas_limit;
+                op.pre_verification_gas = gas.pre_verification_gas;
+                if op.paymaster.is_some() {
+                    if let Some(limit) = gas.paymaster_verification_gas_limit {
+                        op.paymaster_verification_gas_limit = Some(limit);
+                    }
+                    if let Some(limit) = gas.paymaster_post_op_gas_limit {
+                        op.paymaster_post_op_gas_limit = Some(limit);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<UserOperation> for SendUserOperation {
+    fn from(op: UserOperation) -> Self {
+        Self::EntryPointV06(op)
+    }
+}
+
+impl From<PackedUserOperation> for SendUserOperation {
+    fn from(op: PackedUserOperation) -> Self {
+        Self::EntryPointV07(op)
+    }
+}
+
+/// The gas limits of a user operation, as estimated by `eth_estimateUserOperationGas`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGasEstimation {
+    /// The gas paid to the bundler for the overhead of the operation.
+    pub pre_verification_gas: U256,
+    /// The gas limit of the verification.
+    pub verification_gas_limit: U256,
+    /// The gas limit of the execution.
+    pub call_gas_limit: U256,
+    /// The gas limit of the paymaster verification, for the v0.7 entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    /// The gas limit of the paymaster post-operation, for the v0.7 entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+}
+
+/// The result of a paymaster sponsoring a user operation, as returned by
+/// `pm_sponsorUserOperation`, `pm_getPaymasterStubData`, and `pm_getPaymasterData`.
+///
+/// Paymasters return `paymasterAndData` for the v0.6 entry point, and the split paymaster fields
+/// for the v0.7 entry point. Some of them also estimate the gas limits of the operation, which
+/// must then be used as is, since the paymaster signature covers them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymasterSponsorship {
+    /// The paymaster address followed by its data, for the v0.6 entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_and_data: Option<Bytes>,
+    /// The paymaster, for the v0.7 entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    /// The data passed to the paymaster, for the v0.7 entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    /// The gas limit of the paymaster verification, for the v0.7 entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    /// The gas limit of the paymaster post-operation, for the v0.7 entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    /// The gas limit of the execution, if estimated by the paymaster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_gas_limit: Option<U256>,
+    /// The gas limit of the verification, if estimated by the paymaster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_gas_limit: Option<U256>,
+    /// The pre-verification gas, if estimated by the paymaster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_verification_gas: Option<U256>,
+}
+
+impl PaymasterSponsorship {
+    /// Returns the paymaster address followed by its data, as used by the v0.6 entry point.
+    ///
+    /// Falls back to packing the paymaster and its data when only the split fields are returned.
+    pub fn paymaster_and_data(&self) -> Option<Bytes> {
+        self.paymaster_and_data.clone().or_else(|| {
+            let paymaster = self.paymaster?;
+            let data = self.paymaster_data.as_ref().map_or(&[][..], |data| &data[..]);
+            Some([paymaster.as_slice(), data].concat().into())
+        })
+    }
+
+    fn apply_gas(
+        &self,
+        call_gas_limit: &mut U256,
+        verification_gas_limit: &mut U256,
+        pre_verification_gas: &mut U256,
+    ) {
+        if let Some(limit) = self.call_gas_limit {
+            *call_gas_limit = limit;
+        }
+        if let Some(limit) = self.verification_gas_limit {
+            *verification_gas_limit = limit;
+        }
+        if let Some(gas) = self.pre_verification_gas {
+            *pre_verification_gas = gas;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+
+    #[test]
+    fn sponsor_v06() {
+        let sponsorship: PaymasterSponsorship = serde_json::from_str(
+            r#"{
+                "paymasterAndData": "0x0000000000000000000000000000000000000001abcd",
+                "preVerificationGas": "0xc350",
+                "verificationGasLimit": "0x186a0",
+                "callGasLimit": "0x5208"
+            }"#,
+        )
+        .unwrap();
+
+        let mut op = SendUserOperation::from(UserOperation {
+            call_gas_limit: U256::from(1),
+            max_fee_per_gas: U256::from(100),
+            ..Default::default()
+        });
+        op.apply_sponsorship(&sponsorship);
+        let SendUserOperation::EntryPointV06(op) = op else { unreachable!() };
+        assert_eq!(op.paymaster(), Some(Address::with_last_byte(1)));
+        assert_eq!(op.paymaster_and_data, bytes!("0000000000000000000000000000000000000001abcd"));
+        assert_eq!(op.call_gas_limit, U256::from(21000));
+        assert_eq!(op.verification_gas_limit, U256::from(100000));
+        assert_eq!(op.pre_verification_gas, U256::from(50000));
+        assert_eq!(op.max_fee_per_gas, U256::from(100));
+    }
+
+    #[test]
+    fn sponsor_v07() {
+        let sponsorship: PaymasterSponsorship = serde_json::from_str(
+            r#"{
+                "paymaster": "0x0000000000000000000000000000000000000002",
+                "paymasterData": "0xabcd",
+                "paymasterVerificationGasLimit": "0x1",
+                "paymasterPostOpGasLimit": "0x2"
+            }"#,
+        )
+        .unwrap();
+
+        let mut op = PackedUserOperation {
+            factory: Some(address!("0000000000000000000000000000000000000003")),
+            factory_data: Some(bytes!("ef")),
+            call_gas_limit: U256::from(1),
+            ..Default::default()
+        };
+        op.apply_sponsorship(&sponsorship);
+        assert_eq!(op.call_gas_limit, U256::from(1));
+        assert_eq!(op.init_code(), bytes!("0000000000000000000000000000000000000003ef"));
+        assert_eq!(
+            op.paymaster_and_data(),
+            bytes!(
+                "0000000000000000000000000000000000000002"
+                "00000000000000000000000000000001"
+                "00000000000000000000000000000002"
+                "abcd"
+            )
+        );
+
+        let json = serde_json::to_value(SendUserOperation::from(op.clone())).unwrap();
+        assert_eq!(json["paymaster"], "0x0000000000000000000000000000000000000002");
+        assert_eq!(json["paymasterPostOpGasLimit"], "0x2");
+        assert_eq!(serde_json::from_value::<SendUserOperation>(json).unwrap(), op.into());
+
+        assert_eq!(
+            sponsorship.paymaster_and_data(),
+            Some(bytes!("0000000000000000000000000000000000000002abcd"))
+        );
+    }
+}
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 #[doc(alias = "TransactionGasAndReward")]
 pub struct TxGasAndReward {
     /// Gas used by the transaction
-    pub gas_used: u64,
+    pub gas_used: u128,
     /// The effective gas tip by the transaction
     pub reward: u128,
 }
@@ -100,6 +100,94 @@ impl FeeHistory {
             // Skip zero values that are returned for pre-EIP-4844 blocks.
             .filter(|fee| *fee != 0)
     }
+
+    /// Returns the number of blocks in the fee history.
+    pub fn block_count(&self) -> usize {
+        self.gas_used_ratio.len()
+    }
+
+    /// Returns the number of the newest block in the fee history, if it is not empty.
+    pub fn newest_block(&self) -> Option<u64> {
+        (self.block_count() as u64).checked_sub(1).map(|offset| self.oldest_block + offset)
+    }
+
+    /// Returns the relative change of the base fee from the oldest block to the next block, e.g.
+    /// `0.125` if it increased by 12.5%.
+    ///
+    /// Returns `None` if the fee history is empty, or the oldest block has no base fee.
+    pub fn base_fee_trend(&self) -> Option<f64> {
+        let first = *self.base_fee_per_gas.first().filter(|fee| **fee != 0)?;
+        let next = self.next_block_base_fee()?;
+        Some((next as f64 - first as f64) / first as f64)
+    }
+
+    /// Returns the priority fees of the given reward percentile, by block, i.e. the curve of the
+    /// percentile at `index` in the requested percentiles.
+    ///
+    /// Returns `None` if rewards were not requested, or the index is out of bounds.
+    pub fn reward_percentile(&self, index: usize) -> Option<Vec<u128>> {
+        self.reward.as_ref()?.iter().map(|rewards| rewards.get(index).copied()).collect()
+    }
+
+    /// Returns the average gas used ratio of the blocks, or `None` if the fee history is empty.
+    pub fn average_gas_used_ratio(&self) -> Option<f64> {
+        average(&self.gas_used_ratio)
+    }
+
+    /// Returns the average blob gas used ratio of the blocks, or `None` if the fee history has no
+    /// blob gas data.
+    pub fn average_blob_gas_used_ratio(&self) -> Option<f64> {
+        average(&self.blob_gas_used_ratio)
+    }
+
+    /// Extends the fee history with a newer one, then drops the oldest blocks beyond `window`.
+    ///
+    /// Blocks of `newer` replace the blocks with the same numbers, e.g. to refresh blocks that
+    /// were reorged. If `newer` does not overlap or directly follow this fee history, it replaces
+    /// it entirely. Both must have been requested with the same reward percentiles.
+    pub fn extend(&mut self, newer: Self, window: usize) {
+        let len = self.block_count() as u64;
+        if newer.oldest_block < self.oldest_block || newer.oldest_block > self.oldest_block + len {
+            *self = newer;
+        } else {
+            let keep = (newer.oldest_block - self.oldest_block) as usize;
+            splice(&mut self.base_fee_per_gas, keep, newer.base_fee_per_gas);
+            splice(&mut self.gas_used_ratio, keep, newer.gas_used_ratio);
+            splice(&mut self.base_fee_per_blob_gas, keep, newer.base_fee_per_blob_gas);
+            splice(&mut self.blob_gas_used_ratio, keep, newer.blob_gas_used_ratio);
+            self.reward = match (self.reward.take(), newer.reward) {
+                (Some(mut reward), Some(newer)) => {
+                    splice(&mut reward, keep, newer);
+                    Some(reward)
+                }
+                (_, newer) => newer,
+            };
+        }
+
+        let excess = self.block_count().saturating_sub(window);
+        if excess > 0 {
+            for fees in [&mut self.base_fee_per_gas, &mut self.base_fee_per_blob_gas] {
+                fees.drain(..excess.min(fees.len()));
+            }
+            for ratios in [&mut self.gas_used_ratio, &mut self.blob_gas_used_ratio] {
+                ratios.drain(..excess.min(ratios.len()));
+            }
+            if let Some(reward) = &mut self.reward {
+                reward.drain(..excess.min(reward.len()));
+            }
+            self.oldest_block += excess as u64;
+        }
+    }
+}
+
+/// Replaces the elements of `values` from `index` with `newer`.
+fn splice<T>(values: &mut Vec<T>, index: usize, newer: Vec<T>) {
+    values.truncate(index);
+    values.extend(newer);
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
 }
 
 #[cfg(test)]
@@ -130,6 +218,37 @@ mod tests {
         let _actual = serde_json::from_str::<FeeHistory>(json).unwrap();
     }
 
+    #[test]
+    fn extend_fee_history() {
+        let history = |oldest_block: u64, base_fees: &[u128], rewards: &[u128]| FeeHistory {
+            base_fee_per_gas: base_fees.to_vec(),
+            gas_used_ratio: vec![0.5; rewards.len()],
+            base_fee_per_blob_gas: vec![1; base_fees.len()],
+            blob_gas_used_ratio: vec![0.25; rewards.len()],
+            oldest_block,
+            reward: Some(rewards.iter().map(|reward| vec![*reward, reward * 2]).collect()),
+        };
+
+        let mut fees = history(10, &[100, 110, 120], &[1, 2]);
+        assert_eq!(fees.newest_block(), Some(11));
+        assert_eq!(fees.base_fee_trend(), Some(0.2));
+        assert_eq!(fees.reward_percentile(1), Some(vec![2, 4]));
+        assert_eq!(fees.reward_percentile(2), None);
+        assert_eq!(fees.average_blob_gas_used_ratio(), Some(0.25));
+
+        // Block 11 is refreshed, and block 12 appended.
+        fees.extend(history(11, &[115, 125, 130], &[3, 4]), 3);
+        assert_eq!(fees, history(10, &[100, 115, 125, 130], &[1, 3, 4]));
+
+        // Blocks beyond the window are dropped.
+        fees.extend(history(13, &[140, 150], &[5]), 3);
+        assert_eq!(fees, history(11, &[115, 125, 140, 150], &[3, 4, 5]));
+
+        // Histories with a gap replace the previous one.
+        fees.extend(history(20, &[200, 210], &[6]), 3);
+        assert_eq!(fees, history(20, &[200, 210], &[6]));
+    }
+
     #[test]
     fn test_fee_history_serde_3() {
         let json = r#"{"oldestBlock":"0xdee807","baseFeePerGas":["0x4ccf46253","0x4457de658","0x4531c5aee","0x3cfa33972","0x3d33403eb","0x399457884","0x40bdf9772","0x48d55e7c4","0x51e9ebf14","0x55f460bf9","0x4e31607e4"],"gasUsedRatio":[0.05909575012589385,0.5498182666666667,0.0249864,0.5146185,0.2633512,0.997582061117319,0.999914966153302,0.9986873805040722,0.6973219148223686,0.13879896448917434],"baseFeePerBlobGas":["0x0","0x0","0x0","0x0","0x0","0x0","0x0","0x0","0x0","0x0","0x0"],"blobGasUsedRatio":[0,0,0,0,0,0,0,0,0,0]}"#;
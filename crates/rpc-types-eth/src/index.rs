@@ -3,91 +3,152 @@ use serde::{
     de::{Error, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::fmt;
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
 
-/// A hex encoded or decimal index that's intended to be used as a rust index, hence it's
-/// deserialized into a `usize`.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
-pub struct Index(pub usize);
+/// Implements hex-quantity serde, formatting and arithmetic for an index newtype.
+macro_rules! impl_index {
+    ($name:ident($int:ty)) => {
+        impl From<$int> for $name {
+            fn from(idx: $int) -> Self {
+                Self(idx)
+            }
+        }
 
-impl From<Index> for usize {
-    fn from(idx: Index) -> Self {
-        idx.0
-    }
-}
+        impl From<$name> for $int {
+            fn from(idx: $name) -> Self {
+                idx.0
+            }
+        }
 
-impl From<Index> for U256 {
-    fn from(idx: Index) -> Self {
-        Self::from(idx.0)
-    }
-}
+        impl From<$name> for U256 {
+            fn from(idx: $name) -> Self {
+                Self::from(idx.0)
+            }
+        }
 
-impl From<usize> for Index {
-    fn from(idx: usize) -> Self {
-        Self(idx)
-    }
-}
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
 
-impl Serialize for Index {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_str(&format!("0x{:x}", self.0))
-    }
-}
+        impl Add<$int> for $name {
+            type Output = Self;
+
+            fn add(self, rhs: $int) -> Self {
+                Self(self.0 + rhs)
+            }
+        }
+
+        impl AddAssign<$int> for $name {
+            fn add_assign(&mut self, rhs: $int) {
+                self.0 += rhs;
+            }
+        }
 
-impl<'a> Deserialize<'a> for Index {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'a>,
-    {
-        struct IndexVisitor;
+        impl Sub<$int> for $name {
+            type Output = Self;
 
-        impl<'a> Visitor<'a> for IndexVisitor {
-            type Value = Index;
+            fn sub(self, rhs: $int) -> Self {
+                Self(self.0 - rhs)
+            }
+        }
 
-            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(formatter, "hex-encoded or decimal index")
+        impl SubAssign<$int> for $name {
+            fn sub_assign(&mut self, rhs: $int) {
+                self.0 -= rhs;
             }
+        }
 
-            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             where
-                E: Error,
+                S: Serializer,
             {
-                Ok(Index(value as usize))
+                serializer.serialize_str(&format!("0x{:x}", self.0))
             }
+        }
 
-            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        impl<'a> Deserialize<'a> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
             where
-                E: Error,
+                D: Deserializer<'a>,
             {
-                value.strip_prefix("0x").map_or_else(
-                    || {
-                        value.parse::<usize>().map(Index).map_err(|e| {
+                struct IndexVisitor;
+
+                impl<'a> Visitor<'a> for IndexVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        write!(formatter, "hex-encoded or decimal index")
+                    }
+
+                    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        <$int>::try_from(value).map($name).map_err(|e| {
                             Error::custom(format!("Failed to parse numeric index: {e}"))
                         })
-                    },
-                    |val| {
-                        usize::from_str_radix(val, 16).map(Index).map_err(|e| {
-                            Error::custom(format!("Failed to parse hex encoded index value: {e}"))
-                        })
-                    },
-                )
-            }
-
-            fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
-            where
-                E: Error,
-            {
-                self.visit_str(value.as_ref())
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        value.strip_prefix("0x").map_or_else(
+                            || {
+                                value.parse::<$int>().map($name).map_err(|e| {
+                                    Error::custom(format!("Failed to parse numeric index: {e}"))
+                                })
+                            },
+                            |val| {
+                                <$int>::from_str_radix(val, 16).map($name).map_err(|e| {
+                                    Error::custom(format!(
+                                        "Failed to parse hex encoded index value: {e}"
+                                    ))
+                                })
+                            },
+                        )
+                    }
+
+                    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        self.visit_str(value.as_ref())
+                    }
+                }
+
+                deserializer.deserialize_any(IndexVisitor)
             }
         }
-
-        deserializer.deserialize_any(IndexVisitor)
-    }
+    };
 }
 
+/// A hex encoded or decimal index that's intended to be used as a rust index, hence it's
+/// deserialized into a `usize`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Index(pub usize);
+
+impl_index!(Index(usize));
+
+/// The position of an uncle in the ommers list of a block, e.g. the index parameter of
+/// `eth_getUncleByBlockHashAndIndex`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UncleIndex(pub u64);
+
+impl_index!(UncleIndex(u64));
+
+/// The position of a log in a block, as returned in the `logIndex` field of logs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LogIndex(pub u64);
+
+impl_index!(LogIndex(u64));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +212,17 @@ mod tests {
             serde_json::from_value(json_data).expect("Failed to deserialize large index");
         assert_eq!(index, Index::from(u64::MAX as usize));
     }
+
+    #[test]
+    fn test_named_indices() {
+        let index: UncleIndex = serde_json::from_value(json!("0x1")).unwrap();
+        assert_eq!(index, UncleIndex(1));
+        assert_eq!(serde_json::to_value(index + 9).unwrap(), json!("0xa"));
+
+        let mut index = LogIndex::from(3);
+        index -= 1;
+        assert!(index < LogIndex(3));
+        assert_eq!(u64::from(index), 2);
+        assert_eq!(serde_json::from_value::<LogIndex>(json!(2)).unwrap(), index);
+    }
 }
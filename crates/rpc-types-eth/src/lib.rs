@@ -6,6 +6,10 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+// Required by the `Arbitrary` derives of `alloy-primitives`, enabled by the `proptest` feature.
+#[cfg(all(feature = "proptest", not(feature = "arbitrary")))]
+use arbitrary as _;
+
 pub use alloy_eips::eip4895::Withdrawal;
 
 mod account;
@@ -17,6 +21,8 @@ pub use block::*;
 mod call;
 pub use call::{Bundle, EthCallResponse, StateContext};
 
+pub mod erc4337;
+
 pub mod error;
 
 mod fee;
@@ -26,7 +32,7 @@ mod filter;
 pub use filter::*;
 
 mod index;
-pub use index::Index;
+pub use index::{Index, LogIndex, UncleIndex};
 
 mod log;
 pub use log::*;
@@ -36,11 +42,19 @@ pub mod pubsub;
 mod raw_log;
 pub use raw_log::{logs_bloom, Log as RawLog};
 
+pub mod simulate;
+
+mod size;
+pub use size::{other_fields_heap_size, InMemorySize};
+
 pub mod state;
 
 mod syncing;
 pub use syncing::*;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
 pub mod transaction;
 pub use transaction::*;
 
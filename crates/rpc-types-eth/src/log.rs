@@ -1,14 +1,13 @@
 #![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
 
-use alloy_primitives::{Address, BlockHash, LogData, TxHash, B256};
+use crate::{InMemorySize, RawLog};
+use alloy_primitives::{Address, BlockHash, Bytes, LogData, TxHash, B256};
 use serde::{Deserialize, Serialize};
 
 /// Ethereum Log emitted by a transaction
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[cfg_attr(
-    any(test, feature = "arbitrary"),
-    derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
-)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[serde(rename_all = "camelCase")]
 pub struct Log<T = LogData> {
     #[serde(flatten)]
@@ -49,6 +48,41 @@ impl<T> Log<T> {
     pub const fn data(&self) -> &T {
         &self.inner.data
     }
+
+    /// Returns whether the log was removed from the canonical chain by a reorg.
+    pub const fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    /// Returns whether the log was emitted by a pending transaction, i.e. whether it is missing
+    /// the block context.
+    pub const fn is_pending(&self) -> bool {
+        self.block_hash.is_none()
+    }
+
+    /// Consumes the log, returning the consensus log without the block context.
+    pub fn into_inner(self) -> alloy_primitives::Log<T> {
+        self.inner
+    }
+
+    /// Consumes the log, returning its data.
+    pub fn into_data(self) -> T {
+        self.inner.data
+    }
+
+    /// Creates a new `Log` with the given inner log and the metadata of this one.
+    const fn with_inner<U>(&self, inner: alloy_primitives::Log<U>) -> Log<U> {
+        Log {
+            inner,
+            block_hash: self.block_hash,
+            block_number: self.block_number,
+            block_timestamp: self.block_timestamp,
+            transaction_hash: self.transaction_hash,
+            transaction_index: self.transaction_index,
+            log_index: self.log_index,
+            removed: self.removed,
+        }
+    }
 }
 
 impl Log<LogData> {
@@ -70,20 +104,66 @@ impl Log<LogData> {
         self.inner.data.topics_mut()
     }
 
+    /// Consumes the log, returning its address, topics and data.
+    pub fn into_parts(self) -> (Address, Vec<B256>, Bytes) {
+        let (topics, data) = self.inner.data.split();
+        (self.inner.address, topics, data)
+    }
+
     /// Decode the log data into a typed log.
     pub fn log_decode<T: alloy_sol_types::SolEvent>(&self) -> alloy_sol_types::Result<Log<T>> {
         let decoded = T::decode_log(&self.inner, false)?;
-        Ok(Log {
-            inner: decoded,
-            block_hash: self.block_hash,
-            block_number: self.block_number,
-            block_timestamp: self.block_timestamp,
-            transaction_hash: self.transaction_hash,
-            transaction_index: self.transaction_index,
-            log_index: self.log_index,
-            removed: self.removed,
-        })
+        Ok(self.with_inner(decoded))
     }
+
+    /// Decode the log data with the first of the given ABIs that emits it.
+    ///
+    /// Returns `None` if none of the ABIs can decode the log.
+    #[cfg(feature = "dyn-abi")]
+    pub fn log_decode_dyn(&self, abis: &[&dyn DynAbi]) -> Option<Log<DecodedLog>> {
+        let decoded = abis.iter().find_map(|abi| abi.try_decode_log(&self.inner.data))?;
+        Some(self.with_inner(alloy_primitives::Log { address: self.address(), data: decoded }))
+    }
+}
+
+/// An ABI that can decode event logs at runtime, used to decode logs of several contracts or
+/// events at once with [`Log::log_decode_dyn`] or
+/// [`TransactionReceipt::decode_all`](crate::TransactionReceipt::decode_all).
+///
+/// This is implemented for a single [`Event`](alloy_json_abi::Event) and for a whole
+/// [`JsonAbi`](alloy_json_abi::JsonAbi).
+#[cfg(feature = "dyn-abi")]
+pub trait DynAbi {
+    /// Decodes `log`, returning `None` if it was not emitted by an event of this ABI.
+    fn try_decode_log(&self, log: &LogData) -> Option<DecodedLog>;
+}
+
+#[cfg(feature = "dyn-abi")]
+impl DynAbi for alloy_json_abi::Event {
+    fn try_decode_log(&self, log: &LogData) -> Option<DecodedLog> {
+        if !self.anonymous && log.topics().first() != Some(&self.selector()) {
+            return None;
+        }
+        let event = alloy_dyn_abi::EventExt::decode_log(self, log, true).ok()?;
+        Some(DecodedLog { name: self.name.clone(), event })
+    }
+}
+
+#[cfg(feature = "dyn-abi")]
+impl DynAbi for alloy_json_abi::JsonAbi {
+    fn try_decode_log(&self, log: &LogData) -> Option<DecodedLog> {
+        self.events().find_map(|event| event.try_decode_log(log))
+    }
+}
+
+/// A log decoded with a [`DynAbi`].
+#[cfg(feature = "dyn-abi")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedLog {
+    /// The name of the event that emitted the log.
+    pub name: String,
+    /// The decoded event values.
+    pub event: alloy_dyn_abi::DecodedEvent,
 }
 
 impl<T> alloy_rlp::Encodable for Log<T>
@@ -125,6 +205,64 @@ where
     }
 }
 
+impl<T> From<alloy_primitives::Log<T>> for Log<T> {
+    /// Creates a log without block context.
+    fn from(inner: alloy_primitives::Log<T>) -> Self {
+        Self {
+            inner,
+            block_hash: None,
+            block_number: None,
+            block_timestamp: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            removed: false,
+        }
+    }
+}
+
+impl<T> From<Log<T>> for alloy_primitives::Log<T> {
+    fn from(log: Log<T>) -> Self {
+        log.inner
+    }
+}
+
+impl From<alloy_primitives::Log> for RawLog {
+    fn from(log: alloy_primitives::Log) -> Self {
+        let (topics, data) = log.data.split();
+        Self { address: log.address, topics, data }
+    }
+}
+
+impl From<Log> for RawLog {
+    fn from(log: Log) -> Self {
+        log.inner.into()
+    }
+}
+
+impl TryFrom<RawLog> for alloy_primitives::Log {
+    type Error = TooManyTopicsError;
+
+    fn try_from(log: RawLog) -> Result<Self, Self::Error> {
+        let count = log.topics.len();
+        Self::new(log.address, log.topics, log.data).ok_or(TooManyTopicsError(count))
+    }
+}
+
+impl TryFrom<RawLog> for Log {
+    type Error = TooManyTopicsError;
+
+    /// Creates a log without block context.
+    fn try_from(log: RawLog) -> Result<Self, Self::Error> {
+        alloy_primitives::Log::try_from(log).map(Into::into)
+    }
+}
+
+/// The error returned when converting a [`RawLog`] with more than 4 topics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("log has {0} topics, at most 4 are allowed")]
+pub struct TooManyTopicsError(pub usize);
+
 impl<T> AsRef<alloy_primitives::Log<T>> for Log<T> {
     fn as_ref(&self) -> &alloy_primitives::Log<T> {
         &self.inner
@@ -149,6 +287,13 @@ impl<T> AsMut<T> for Log<T> {
     }
 }
 
+impl<T: InMemorySize> InMemorySize for Log<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.inner.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloy_primitives::{Address, Bytes};
@@ -202,4 +347,27 @@ mod tests {
         let deserialized: Log = serde_json::from_str(&serialized).unwrap();
         assert_eq!(log, deserialized);
     }
+
+    #[test]
+    fn converts_logs() {
+        let raw = RawLog {
+            address: Address::with_last_byte(1),
+            topics: vec![B256::with_last_byte(2), B256::with_last_byte(3)],
+            data: Bytes::from_static(&[4]),
+        };
+
+        let log = Log::try_from(raw.clone()).unwrap();
+        assert!(log.is_pending());
+        assert!(!log.is_removed());
+        assert_eq!(log.topics(), raw.topics);
+        assert_eq!(RawLog::from(log.clone()), raw);
+
+        let primitive: alloy_primitives::Log = log.clone().into();
+        assert_eq!(Log::from(primitive.clone()), log);
+        assert_eq!(RawLog::from(primitive), raw);
+        assert_eq!(log.into_parts(), (raw.address, raw.topics, raw.data));
+
+        let raw = RawLog { topics: vec![B256::ZERO; 5], ..Default::default() };
+        assert_eq!(Log::try_from(raw), Err(TooManyTopicsError(5)));
+    }
 }
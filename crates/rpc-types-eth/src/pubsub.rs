@@ -64,6 +64,7 @@ impl Serialize for SubscriptionResult {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub enum SubscriptionKind {
     /// New block headers subscription.
     ///
@@ -93,10 +94,39 @@ pub enum SubscriptionKind {
     /// indicating that the synchronization has started (true), finished (false) or an object with
     /// various progress indicators.
     Syncing,
+    /// Alchemy mined transactions subscription.
+    ///
+    /// Returns the transactions that are mined as part of the canonical chain, optionally
+    /// narrowed down by the addresses they are sent from or to with [`Params::Other`]. Only
+    /// supported by Alchemy endpoints.
+    #[serde(rename = "alchemy_minedTransactions")]
+    AlchemyMinedTransactions,
+    /// Alchemy pending transactions subscription.
+    ///
+    /// Returns the hash or full tx for the transactions that are added to the pending state,
+    /// optionally narrowed down by the addresses they are sent from or to with
+    /// [`Params::Other`]. Only supported by Alchemy endpoints.
+    #[serde(rename = "alchemy_pendingTransactions")]
+    AlchemyPendingTransactions,
+}
+
+impl SubscriptionKind {
+    /// Returns the name of the subscription kind, as sent to `eth_subscribe`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::NewHeads => "newHeads",
+            Self::Logs => "logs",
+            Self::NewPendingTransactions => "newPendingTransactions",
+            Self::Syncing => "syncing",
+            Self::AlchemyMinedTransactions => "alchemy_minedTransactions",
+            Self::AlchemyPendingTransactions => "alchemy_pendingTransactions",
+        }
+    }
 }
 
 /// Any additional parameters for a subscription.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Params {
     /// No parameters passed.
     #[default]
@@ -105,6 +135,9 @@ pub enum Params {
     Logs(Box<Filter>),
     /// Boolean parameter for new pending transactions.
     Bool(bool),
+    /// Raw parameters, for client-specific subscriptions, e.g. the options of Alchemy's
+    /// [`SubscriptionKind::AlchemyMinedTransactions`], or log filters with extra options.
+    Other(serde_json::Value),
 }
 
 impl Params {
@@ -119,6 +152,31 @@ impl Params {
     pub const fn is_logs(&self) -> bool {
         matches!(self, Self::Logs(_))
     }
+
+    /// Returns true if it's a raw parameter.
+    #[inline]
+    pub const fn is_other(&self) -> bool {
+        matches!(self, Self::Other(_))
+    }
+
+    /// Returns the parameter requesting full transactions instead of hashes, for
+    /// [`SubscriptionKind::NewPendingTransactions`].
+    #[inline]
+    pub const fn full_transactions() -> Self {
+        Self::Bool(true)
+    }
+}
+
+impl From<Filter> for Params {
+    fn from(filter: Filter) -> Self {
+        Self::Logs(Box::new(filter))
+    }
+}
+
+impl From<bool> for Params {
+    fn from(full: bool) -> Self {
+        Self::Bool(full)
+    }
 }
 
 impl Serialize for Params {
@@ -130,6 +188,7 @@ impl Serialize for Params {
             Self::None => (&[] as &[serde_json::Value]).serialize(serializer),
             Self::Logs(logs) => logs.serialize(serializer),
             Self::Bool(full) => full.serialize(serializer),
+            Self::Other(params) => params.serialize(serializer),
         }
     }
 }
@@ -149,9 +208,19 @@ impl<'a> Deserialize<'a> for Params {
             return Ok(Self::Bool(val));
         }
 
+        let Some(object) = v.as_object() else {
+            return Err(D::Error::custom(format!("Invalid Pub-Sub parameters: {v}")));
+        };
+
+        // Objects with fields that log filters do not have are kept as is, e.g. client-specific
+        // options, but invalid log filters are rejected.
+        const FILTER_FIELDS: [&str; 5] = ["fromBlock", "toBlock", "blockHash", "address", "topics"];
+        if object.keys().any(|key| !FILTER_FIELDS.contains(&key.as_str())) {
+            return Ok(Self::Other(v));
+        }
         serde_json::from_value(v)
-            .map(|f| Self::Logs(Box::new(f)))
-            .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {e}")))
+            .map(|filter| Self::Logs(Box::new(filter)))
+            .map_err(|err| D::Error::custom(format!("Invalid Pub-Sub log filter: {err}")))
     }
 }
 
@@ -166,4 +235,37 @@ mod tests {
         let s: Params = serde_json::from_str("null").unwrap();
         assert_eq!(s, Params::None);
     }
+
+    #[test]
+    fn client_specific_params_serde() {
+        let filter: Params = serde_json::from_str(r#"{"address":[],"topics":[]}"#).unwrap();
+        assert!(filter.is_logs());
+
+        let raw = r#"{"addresses":[{"to":"0x0000000000000000000000000000000000000001"}],"hashesOnly":true}"#;
+        let params: Params = serde_json::from_str(raw).unwrap();
+        assert_eq!(params, Params::Other(serde_json::from_str(raw).unwrap()));
+        assert_eq!(serde_json::to_string(&params).unwrap(), raw);
+
+        assert!(serde_json::from_str::<Params>("1").is_err());
+        assert!(serde_json::from_str::<Params>(r#"{"address":"0x01"}"#).is_err());
+        assert!(
+            serde_json::from_str::<Params>(r#"{"fromBlock":"0x1","blockHash":"0x01"}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn subscription_kind_serde() {
+        for kind in [
+            SubscriptionKind::NewHeads,
+            SubscriptionKind::Logs,
+            SubscriptionKind::NewPendingTransactions,
+            SubscriptionKind::Syncing,
+            SubscriptionKind::AlchemyMinedTransactions,
+            SubscriptionKind::AlchemyPendingTransactions,
+        ] {
+            let json = serde_json::to_string(&kind).unwrap();
+            assert_eq!(json, format!("\"{}\"", kind.as_str()));
+            assert_eq!(serde_json::from_str::<SubscriptionKind>(&json).unwrap(), kind);
+        }
+    }
 }
@@ -0,0 +1,164 @@
+//! Types for the `eth_simulateV1` method.
+
+use crate::{state::StateOverride, Block, BlockOverrides, Log, TransactionRequest};
+use alloy_primitives::{address, b256, Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The address emitting the logs of the ether transfers when `traceTransfers` is enabled.
+pub const TRANSFER_LOG_ADDRESS: Address = address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+
+/// The topic of the ether transfer logs, i.e. the signature of the ERC-20 `Transfer` event.
+pub const TRANSFER_LOG_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// A block of calls to simulate, along with the overrides to apply before executing them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBlock {
+    /// The overrides of the block environment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// The state overrides applied before executing the calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls to execute, in order.
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// The payload of an `eth_simulateV1` request.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, in order, each one on top of the previous one.
+    #[serde(default)]
+    pub block_state_calls: Vec<SimBlock>,
+    /// Whether to emit a log for each ether transfer, from [`TRANSFER_LOG_ADDRESS`].
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether to validate the calls as transactions, e.g. their nonce and balance.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether to return the full transactions of the blocks instead of their hashes.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+/// The error of a failed simulated call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateError {
+    /// The error code.
+    pub code: i64,
+    /// The error message.
+    pub message: String,
+    /// The revert data, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+/// The result of a simulated call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimCallResult {
+    /// The data returned by the call.
+    pub return_data: Bytes,
+    /// The logs emitted by the call.
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    /// The gas used by the call.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u128,
+    /// Whether the call succeeded.
+    #[serde(with = "alloy_serde::quantity")]
+    pub status: bool,
+    /// The error of the call, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+impl SimCallResult {
+    /// Returns the ether transfers of the call, as `(from, to, value)`, if `traceTransfers` was
+    /// enabled.
+    pub fn transfers(&self) -> impl Iterator<Item = (Address, Address, U256)> + '_ {
+        self.logs.iter().filter_map(|log| {
+            let [topic, from, to] = log.topics() else { return None };
+            if log.address() != TRANSFER_LOG_ADDRESS || *topic != TRANSFER_LOG_TOPIC {
+                return None;
+            }
+            let value = U256::try_from_be_slice(&log.data().data)?;
+            Some((Address::from_word(*from), Address::from_word(*to), value))
+        })
+    }
+
+    /// Returns the logs emitted by the contracts called, i.e. without the logs of the ether
+    /// transfers.
+    pub fn contract_logs(&self) -> impl Iterator<Item = &Log> + '_ {
+        self.logs.iter().filter(|log| log.address() != TRANSFER_LOG_ADDRESS)
+    }
+}
+
+/// A simulated block, along with the results of its calls.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedBlock<B = Block> {
+    /// The simulated block.
+    #[serde(flatten)]
+    pub inner: B,
+    /// The results of the calls of the block, in order.
+    pub calls: Vec<SimCallResult>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_simulate_payload() {
+        let s = r#"{"blockStateCalls":[{"blockOverrides":{"number":"0x2"},"calls":[{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x64"}]}],"traceTransfers":true,"validation":false,"returnFullTransactions":false}"#;
+        let payload: SimulatePayload = serde_json::from_str(s).unwrap();
+        assert_eq!(payload.block_state_calls.len(), 1);
+        assert_eq!(payload.block_state_calls[0].calls[0].value, Some(U256::from(100)));
+        assert!(payload.trace_transfers);
+        assert_eq!(serde_json::to_string(&payload).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_sim_call_result() {
+        let s = r#"{
+            "returnData": "0x",
+            "logs": [
+                {
+                    "address": "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee",
+                    "topics": [
+                        "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                        "0x0000000000000000000000000000000000000000000000000000000000000001",
+                        "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    ],
+                    "data": "0x0000000000000000000000000000000000000000000000000000000000000064",
+                    "blockNumber": "0x2",
+                    "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                    "transactionIndex": "0x0",
+                    "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                    "logIndex": "0x0",
+                    "removed": false
+                }
+            ],
+            "gasUsed": "0x5208",
+            "status": "0x1"
+        }"#;
+        let result: SimCallResult = serde_json::from_str(s).unwrap();
+        assert!(result.status);
+        assert_eq!(result.gas_used, 21_000);
+        assert_eq!(result.error, None);
+        assert_eq!(
+            result.transfers().collect::<Vec<_>>(),
+            vec![(Address::with_last_byte(1), Address::with_last_byte(2), U256::from(100))]
+        );
+        assert_eq!(result.contract_logs().count(), 0);
+
+        let s = r#"{"returnData":"0x","logs":[],"gasUsed":"0x6a4b","status":"0x0","error":{"code":3,"message":"execution reverted","data":"0x08c379a0"}}"#;
+        let result: SimCallResult = serde_json::from_str(s).unwrap();
+        assert!(!result.status);
+        assert_eq!(result.error.as_ref().unwrap().code, 3);
+        assert_eq!(serde_json::to_string(&result).unwrap(), s);
+    }
+}
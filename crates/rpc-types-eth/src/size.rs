@@ -0,0 +1,31 @@
+//! In-memory size accounting of the RPC types.
+
+use alloy_serde::OtherFields;
+use serde_json::Value;
+use std::mem;
+
+pub use alloy_eips::InMemorySize;
+
+/// Returns the number of bytes allocated on the heap by the given [`OtherFields`].
+///
+/// [`OtherFields`] is defined outside of this crate, so it cannot implement [`InMemorySize`]
+/// itself. The nodes of the underlying map are approximated by the size of their entries.
+pub fn other_fields_heap_size(fields: &OtherFields) -> usize {
+    fields.iter().map(|(key, value)| entry_heap_size(key, value)).sum()
+}
+
+fn entry_heap_size(key: &String, value: &Value) -> usize {
+    mem::size_of::<(String, Value)>() + key.capacity() + json_heap_size(value)
+}
+
+fn json_heap_size(value: &Value) -> usize {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => 0,
+        Value::String(s) => s.capacity(),
+        Value::Array(values) => {
+            values.capacity() * mem::size_of::<Value>()
+                + values.iter().map(json_heap_size).sum::<usize>()
+        }
+        Value::Object(map) => map.iter().map(|(key, value)| entry_heap_size(key, value)).sum(),
+    }
+}
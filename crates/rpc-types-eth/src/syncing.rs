@@ -1,9 +1,10 @@
 use alloy_primitives::{B512, U256, U64};
+use alloy_serde::OtherFields;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
 
 /// Syncing info
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncInfo {
     /// Starting block
@@ -16,6 +17,119 @@ pub struct SyncInfo {
     pub warp_chunks_amount: Option<U256>,
     /// Warp sync snapshot chunks processed.
     pub warp_chunks_processed: Option<U256>,
+    /// Client-specific sync progress.
+    #[serde(flatten)]
+    pub details: SyncDetails,
+}
+
+/// Client-specific sync progress, returned by `eth_syncing` next to the fields of [`SyncInfo`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SyncDetails {
+    /// Snap sync progress of geth.
+    Geth(Box<GethSyncDetails>),
+    /// Pipeline stage checkpoints of reth.
+    Reth(RethSyncDetails),
+    /// World state download progress of Besu.
+    Besu(BesuSyncDetails),
+    /// Sync mode of Nethermind.
+    Nethermind(NethermindSyncDetails),
+    /// Fields of other clients, or of unknown versions of the clients above.
+    Other(OtherFields),
+}
+
+impl Default for SyncDetails {
+    fn default() -> Self {
+        Self::Other(OtherFields::default())
+    }
+}
+
+/// Snap sync progress of geth.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GethSyncDetails {
+    /// Number of accounts downloaded.
+    #[serde(with = "alloy_serde::quantity")]
+    pub synced_accounts: u64,
+    /// Number of account trie bytes persisted to disk.
+    #[serde(with = "alloy_serde::quantity")]
+    pub synced_account_bytes: u64,
+    /// Number of bytecodes downloaded.
+    #[serde(with = "alloy_serde::quantity")]
+    pub synced_bytecodes: u64,
+    /// Number of bytecode bytes downloaded.
+    #[serde(with = "alloy_serde::quantity")]
+    pub synced_bytecode_bytes: u64,
+    /// Number of storage slots downloaded.
+    #[serde(with = "alloy_serde::quantity")]
+    pub synced_storage: u64,
+    /// Number of storage trie bytes persisted to disk.
+    #[serde(with = "alloy_serde::quantity")]
+    pub synced_storage_bytes: u64,
+    /// Number of state trie nodes downloaded.
+    #[serde(with = "alloy_serde::quantity")]
+    pub healed_trienodes: u64,
+    /// Number of state trie bytes persisted to disk.
+    #[serde(with = "alloy_serde::quantity")]
+    pub healed_trienode_bytes: u64,
+    /// Number of bytecodes downloaded while healing.
+    #[serde(with = "alloy_serde::quantity")]
+    pub healed_bytecodes: u64,
+    /// Number of bytecode bytes downloaded while healing.
+    #[serde(with = "alloy_serde::quantity")]
+    pub healed_bytecode_bytes: u64,
+    /// Number of state trie nodes pending.
+    #[serde(with = "alloy_serde::quantity")]
+    pub healing_trienodes: u64,
+    /// Number of bytecodes pending.
+    #[serde(with = "alloy_serde::quantity")]
+    pub healing_bytecode: u64,
+    /// Number of blocks whose transactions are indexed.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub tx_index_finished_blocks: Option<u64>,
+    /// Number of blocks whose transactions are not indexed yet.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub tx_index_remaining_blocks: Option<u64>,
+}
+
+/// Pipeline stage checkpoints of reth.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RethSyncDetails {
+    /// The checkpoints of the sync stages.
+    pub stages: Vec<SyncStage>,
+}
+
+/// The checkpoint of a sync stage.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncStage {
+    /// Name of the stage.
+    pub name: String,
+    /// The last block processed by the stage.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block: u64,
+}
+
+/// World state download progress of Besu.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BesuSyncDetails {
+    /// Number of state entries downloaded.
+    #[serde(with = "alloy_serde::quantity")]
+    pub pulled_states: u64,
+    /// Number of state entries known to be missing.
+    #[serde(with = "alloy_serde::quantity")]
+    pub known_states: u64,
+}
+
+/// Sync mode of Nethermind.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NethermindSyncDetails {
+    /// Whether the node is syncing.
+    pub is_syncing: bool,
+    /// The active sync modes, e.g. `"SnapSync, FastHeaders"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_mode: Option<String>,
 }
 
 /// Peers info
@@ -99,7 +213,8 @@ pub struct PipProtocolInfo {
 }
 
 /// Sync status
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
 pub enum SyncStatus {
     /// Info when syncing
     Info(SyncInfo),
@@ -117,7 +232,7 @@ impl<'de> Deserialize<'de> for SyncStatus {
         enum Syncing {
             /// When client is synced to the highest block, eth_syncing with return "false"
             None(bool),
-            IsSyncing(SyncInfo),
+            IsSyncing(Box<SyncInfo>),
         }
 
         match Syncing::deserialize(deserializer)? {
@@ -125,7 +240,7 @@ impl<'de> Deserialize<'de> for SyncStatus {
             Syncing::None(true) => Err(serde::de::Error::custom(
                 "eth_syncing returned `true` that is undefined value.",
             )),
-            Syncing::IsSyncing(sync) => Ok(Self::Info(sync)),
+            Syncing::IsSyncing(sync) => Ok(Self::Info(*sync)),
         }
     }
 }
@@ -160,3 +275,69 @@ pub struct ChainStatus {
     /// Describes the gap in the blockchain, if there is one: (first, last)
     pub block_gap: Option<(U256, U256)>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sync_info(json: &str) -> SyncInfo {
+        match serde_json::from_str(json).unwrap() {
+            SyncStatus::Info(info) => info,
+            SyncStatus::None => panic!("expected sync info"),
+        }
+    }
+
+    #[test]
+    fn deserialize_client_sync_details() {
+        let geth = sync_info(
+            r#"{"startingBlock":"0x0","currentBlock":"0x12a3","highestBlock":"0x13d65d6","syncedAccounts":"0x1c5b","syncedAccountBytes":"0x2c6f2e","syncedBytecodes":"0x36","syncedBytecodeBytes":"0x3f8a1","syncedStorage":"0x2d3","syncedStorageBytes":"0x18f4b","healedTrienodes":"0x0","healedTrienodeBytes":"0x0","healedBytecodes":"0x0","healedBytecodeBytes":"0x0","healingTrienodes":"0x0","healingBytecode":"0x0","txIndexFinishedBlocks":"0x0","txIndexRemainingBlocks":"0x1"}"#,
+        );
+        assert_eq!(geth.current_block, U256::from(0x12a3));
+        assert!(
+            matches!(geth.details, SyncDetails::Geth(ref details) if details.synced_accounts == 0x1c5b)
+        );
+
+        let reth = sync_info(
+            r#"{"startingBlock":"0x0","currentBlock":"0x0","highestBlock":"0x0","warpChunksAmount":null,"warpChunksProcessed":null,"stages":[{"name":"Headers","block":"0x12d8a10"},{"name":"Bodies","block":"0x0"}]}"#,
+        );
+        let SyncDetails::Reth(details) = &reth.details else { panic!("{:?}", reth.details) };
+        assert_eq!(details.stages[0], SyncStage { name: "Headers".into(), block: 0x12d8a10 });
+
+        let besu = sync_info(
+            r#"{"startingBlock":"0x0","currentBlock":"0x1518","highestBlock":"0x9567a3","pulledStates":"0x203ca","knownStates":"0x200636"}"#,
+        );
+        assert_eq!(
+            besu.details,
+            SyncDetails::Besu(BesuSyncDetails { pulled_states: 0x203ca, known_states: 0x200636 })
+        );
+
+        let nethermind = sync_info(
+            r#"{"isSyncing":true,"startingBlock":"0x0","currentBlock":"0x0","highestBlock":"0x12d8a10","syncMode":"SnapSync, FastHeaders"}"#,
+        );
+        assert!(
+            matches!(nethermind.details, SyncDetails::Nethermind(ref details) if details.is_syncing)
+        );
+
+        let other = sync_info(
+            r#"{"startingBlock":"0x0","currentBlock":"0x1","highestBlock":"0x2","stage":"bodies"}"#,
+        );
+        let SyncDetails::Other(fields) = &other.details else { panic!("{:?}", other.details) };
+        assert_eq!(fields.get("stage").unwrap(), "bodies");
+    }
+
+    #[test]
+    fn serialize_sync_info() {
+        let info = SyncInfo {
+            current_block: U256::from(1),
+            details: SyncDetails::Besu(BesuSyncDetails { pulled_states: 2, known_states: 3 }),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&SyncStatus::Info(info.clone())).unwrap();
+        assert_eq!(
+            json,
+            r#"{"startingBlock":"0x0","currentBlock":"0x1","highestBlock":"0x0","warpChunksAmount":null,"warpChunksProcessed":null,"pulledStates":"0x2","knownStates":"0x3"}"#
+        );
+        assert_eq!(serde_json::from_str::<SyncStatus>(&json).unwrap(), SyncStatus::Info(info));
+        assert_eq!(serde_json::to_string(&SyncStatus::None).unwrap(), "false");
+    }
+}
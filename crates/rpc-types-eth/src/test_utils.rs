@@ -0,0 +1,218 @@
+//! Fixture-based conformance checks for the serde implementations of RPC types.
+//!
+//! Every check deserializes a JSON sample, serializes it back and compares both documents, so
+//! that fields which are dropped, renamed or re-encoded along the way are reported. A missing
+//! field and a `null` field are considered equal, as are hex strings that only differ in case.
+//!
+//! Samples can be collected in a fixture directory, laid out as `<client>/<kind>/<name>.json`
+//! where `kind` is one of `block`, `transaction` or `receipt`, and checked at once with
+//! [`check_fixture_dir`]. The samples of this crate live in its `test_data/fixtures` directory.
+
+use crate::{AnyTransactionReceipt, Block, Transaction};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// An error returned when a JSON sample does not round-trip.
+#[derive(Debug, thiserror::Error)]
+pub enum RoundtripError {
+    /// The sample could not be deserialized.
+    #[error("failed to deserialize sample: {0}")]
+    Deserialize(#[source] serde_json::Error),
+    /// The deserialized value could not be serialized.
+    #[error("failed to serialize sample: {0}")]
+    Serialize(#[source] serde_json::Error),
+    /// The serialized value differs from the sample.
+    #[error("`{path}` changed during round-trip: expected {expected}, got {actual}")]
+    Mismatch {
+        /// The JSON path of the differing value.
+        path: String,
+        /// The value in the sample.
+        expected: Value,
+        /// The value after the round-trip.
+        actual: Value,
+    },
+}
+
+/// An error returned by [`check_fixture_dir`].
+#[derive(Debug, thiserror::Error)]
+pub enum FixtureError {
+    /// The fixture directory could not be read.
+    #[error("failed to read {}: {source}", path.display())]
+    Io {
+        /// The path that could not be read.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        source: io::Error,
+    },
+    /// The fixture is not in a `block`, `transaction` or `receipt` directory.
+    #[error("unknown fixture kind `{kind}` for {}", path.display())]
+    UnknownKind {
+        /// The path of the fixture.
+        path: PathBuf,
+        /// The name of the directory of the fixture.
+        kind: String,
+    },
+    /// The fixture does not round-trip.
+    #[error("{}: {source}", path.display())]
+    Roundtrip {
+        /// The path of the fixture.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        source: RoundtripError,
+    },
+}
+
+/// Checks that the JSON sample round-trips losslessly through `T`, and returns the deserialized
+/// value.
+pub fn check_roundtrip<T>(json: &str) -> Result<T, RoundtripError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let expected: Value = serde_json::from_str(json).map_err(RoundtripError::Deserialize)?;
+    let value: T = serde_json::from_value(expected.clone()).map_err(RoundtripError::Deserialize)?;
+    let actual = serde_json::to_value(&value).map_err(RoundtripError::Serialize)?;
+    compare(&mut String::from("$"), &expected, &actual)?;
+    Ok(value)
+}
+
+/// Checks that the JSON sample of a block round-trips losslessly.
+pub fn check_block(json: &str) -> Result<Block, RoundtripError> {
+    check_roundtrip(json)
+}
+
+/// Checks that the JSON sample of a transaction round-trips losslessly.
+pub fn check_transaction(json: &str) -> Result<Transaction, RoundtripError> {
+    check_roundtrip(json)
+}
+
+/// Checks that the JSON sample of a receipt round-trips losslessly.
+pub fn check_receipt(json: &str) -> Result<AnyTransactionReceipt, RoundtripError> {
+    check_roundtrip(json)
+}
+
+/// Checks all the fixtures of the given directory, and returns the number of checked fixtures.
+///
+/// Fixtures are expected at `<dir>/<client>/<kind>/<name>.json`, and checked according to their
+/// `kind` directory.
+pub fn check_fixture_dir(dir: impl AsRef<Path>) -> Result<usize, FixtureError> {
+    let mut count = 0;
+    for client in read_dir(dir.as_ref())? {
+        for kind_dir in read_dir(&client)? {
+            let kind = kind_dir.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            for path in read_dir(&kind_dir)? {
+                if path.extension().map_or(true, |ext| ext != "json") {
+                    continue;
+                }
+                let json = fs::read_to_string(&path)
+                    .map_err(|source| FixtureError::Io { path: path.clone(), source })?;
+                let result = match kind.as_str() {
+                    "block" => check_block(&json).map(drop),
+                    "transaction" => check_transaction(&json).map(drop),
+                    "receipt" => check_receipt(&json).map(drop),
+                    _ => return Err(FixtureError::UnknownKind { path, kind }),
+                };
+                result.map_err(|source| FixtureError::Roundtrip { path, source })?;
+                count += 1;
+            }
+        }
+    }
+    Ok(count)
+}
+
+/// Returns the sorted entries of a directory.
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, FixtureError> {
+    let io_err = |source| FixtureError::Io { path: dir.to_path_buf(), source };
+    let mut paths = fs::read_dir(dir)
+        .map_err(io_err)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_err)?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// Returns the value of a field, or `null` if it is missing.
+fn field<'a>(map: &'a Map<String, Value>, key: &str) -> &'a Value {
+    static NULL: Value = Value::Null;
+    map.get(key).unwrap_or(&NULL)
+}
+
+/// Compares a sample with its round-tripped value, recording the JSON path of the comparison.
+fn compare(path: &mut String, expected: &Value, actual: &Value) -> Result<(), RoundtripError> {
+    let mismatch = |path: &str| RoundtripError::Mismatch {
+        path: path.to_string(),
+        expected: expected.clone(),
+        actual: actual.clone(),
+    };
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for key in
+                expected.keys().chain(actual.keys().filter(|key| !expected.contains_key(*key)))
+            {
+                let len = path.len();
+                path.push('.');
+                path.push_str(key);
+                compare(path, field(expected, key), field(actual, key))?;
+                path.truncate(len);
+            }
+            Ok(())
+        }
+        (Value::Array(expected_items), Value::Array(actual_items)) => {
+            if expected_items.len() != actual_items.len() {
+                return Err(mismatch(path));
+            }
+            for (i, (expected, actual)) in expected_items.iter().zip(actual_items).enumerate() {
+                let len = path.len();
+                path.push_str(&format!("[{i}]"));
+                compare(path, expected, actual)?;
+                path.truncate(len);
+            }
+            Ok(())
+        }
+        (Value::String(expected), Value::String(actual))
+            if expected.starts_with("0x") && expected.eq_ignore_ascii_case(actual) =>
+        {
+            Ok(())
+        }
+        _ if expected == actual => Ok(()),
+        _ => Err(mismatch(path)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data/fixtures");
+        let count = check_fixture_dir(dir).unwrap_or_else(|err| panic!("{err}"));
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn reports_lost_fields() {
+        let json = r#"{"hash":"0x5e77a04531c7c107af1882d76cbff9486d0a9aa53701c30888509d4f5f2b003a","nonce":"0x0","blockHash":null,"blockNumber":null,"transactionIndex":null,"from":"0x0000000000000000000000000000000000000000","to":null,"value":"0x0","gas":"0x5208","input":"0x","r":"0x1","s":"0x1","v":"0x1b","gasPrice":"0x1"}"#;
+        check_transaction(json).unwrap();
+
+        let json = json.replace(r#""v":"0x1b""#, r#""v":"0x01b""#);
+        let err = check_transaction(&json).unwrap_err();
+        assert!(matches!(err, RoundtripError::Mismatch { ref path, .. } if path == "$.v"), "{err}");
+    }
+
+    #[test]
+    fn null_and_missing_fields_are_equal() {
+        let expected = serde_json::json!({ "a": null, "b": ["0xAB"] });
+        let actual = serde_json::json!({ "b": ["0xab"], "c": null });
+        compare(&mut String::new(), &expected, &actual).unwrap();
+
+        let actual = serde_json::json!({ "b": ["0xab"], "c": 1 });
+        assert!(compare(&mut String::new(), &expected, &actual).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Misc Arbitrum-specific types.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_serde::OtherFields;
+use serde::{Deserialize, Serialize};
+
+/// Arbitrum transaction types, in addition to the Ethereum ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum ArbitrumTxType {
+    /// A deposit of ETH from L1.
+    Deposit = 0x64,
+    /// An L1 to L2 message without a signature, sent from an L1 contract.
+    Unsigned = 0x65,
+    /// An L1 to L2 message from a contract, with a sender aliased address.
+    Contract = 0x66,
+    /// The redeem of a retryable ticket.
+    Retry = 0x68,
+    /// The submission of a retryable ticket.
+    SubmitRetryable = 0x69,
+    /// An ArbOS internal transaction.
+    Internal = 0x6a,
+    /// A legacy Arbitrum Classic transaction.
+    Legacy = 0x78,
+}
+
+impl ArbitrumTxType {
+    /// Returns whether this is a retryable ticket submission or redeem.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::Retry | Self::SubmitRetryable)
+    }
+
+    /// Returns whether this transaction is created by the chain rather than signed by a user.
+    pub const fn is_system(&self) -> bool {
+        !matches!(self, Self::Legacy)
+    }
+}
+
+impl From<ArbitrumTxType> for u8 {
+    fn from(value: ArbitrumTxType) -> Self {
+        value as Self
+    }
+}
+
+impl TryFrom<u8> for ArbitrumTxType {
+    type Error = u8;
+
+    /// Returns the value back if it is not an Arbitrum transaction type.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0x64 => Self::Deposit,
+            0x65 => Self::Unsigned,
+            0x66 => Self::Contract,
+            0x68 => Self::Retry,
+            0x69 => Self::SubmitRetryable,
+            0x6a => Self::Internal,
+            0x78 => Self::Legacy,
+            _ => return Err(value),
+        })
+    }
+}
+
+/// Arbitrum specific transaction fields, set on retryable ticket transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[doc(alias = "ArbitrumTxFields")]
+pub struct ArbitrumTransactionFields {
+    /// The L1 request ID of a retryable ticket submission.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<B256>,
+    /// The ID of the ticket redeemed by a retry transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket_id: Option<B256>,
+    /// The address refunded with the unused gas of a retry transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_to: Option<Address>,
+    /// The maximum refund of a retry transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_refund: Option<U256>,
+    /// The refund of the submission fee of a retry transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submission_fee_refund: Option<U256>,
+    /// The L1 base fee at the time of a retryable ticket submission.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_base_fee: Option<U256>,
+    /// The ETH deposited with a retryable ticket submission.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_value: Option<U256>,
+    /// The destination of the call made when the ticket is redeemed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_to: Option<Address>,
+    /// The value sent with the call made when the ticket is redeemed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_value: Option<U256>,
+    /// The data of the call made when the ticket is redeemed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_data: Option<Bytes>,
+    /// The address credited with the call value if the ticket expires or is cancelled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beneficiary: Option<Address>,
+    /// The maximum submission fee of a retryable ticket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_submission_fee: Option<U256>,
+}
+
+/// Additional fields for Arbitrum transaction receipts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[doc(alias = "ArbitrumTxReceiptFields")]
+pub struct ArbitrumTransactionReceiptFields {
+    /// The part of the gas used that pays for posting the transaction data to L1.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub gas_used_for_l1: Option<u128>,
+    /// The L1 block number seen by the L2 block of the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub l1_block_number: Option<u64>,
+}
+
+impl From<ArbitrumTransactionFields> for OtherFields {
+    fn from(value: ArbitrumTransactionFields) -> Self {
+        Self::from_serialized(&value).expect("fields serialize to a JSON object")
+    }
+}
+
+impl From<ArbitrumTransactionReceiptFields> for OtherFields {
+    fn from(value: ArbitrumTransactionReceiptFields) -> Self {
+        Self::from_serialized(&value).expect("fields serialize to a JSON object")
+    }
+}
+
+/// The L1 component of the gas estimate of a transaction, returned by
+/// `NodeInterface.gasEstimateL1Component`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArbitrumL1GasEstimate {
+    /// The gas needed to pay for posting the transaction data to L1.
+    pub gas_estimate_for_l1: u64,
+    /// The L2 base fee.
+    pub base_fee: U256,
+    /// The estimated L1 base fee.
+    pub l1_base_fee_estimate: U256,
+}
+
+/// The components of the gas estimate of a transaction, returned by
+/// `NodeInterface.gasEstimateComponents`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArbitrumGasComponents {
+    /// The total gas estimate, including the L1 component.
+    pub gas_estimate: u64,
+    /// The gas needed to pay for posting the transaction data to L1.
+    pub gas_estimate_for_l1: u64,
+    /// The L2 base fee.
+    pub base_fee: U256,
+    /// The estimated L1 base fee.
+    pub l1_base_fee_estimate: U256,
+}
+
+impl ArbitrumGasComponents {
+    /// Returns the gas spent on L2 execution.
+    pub const fn gas_estimate_for_l2(&self) -> u64 {
+        self.gas_estimate.saturating_sub(self.gas_estimate_for_l1)
+    }
+}
+
+/// The current gas prices in wei, returned by `ArbGasInfo.getPricesInWei`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArbitrumPricesInWei {
+    /// The price per L2 transaction.
+    pub per_l2_tx: U256,
+    /// The price per L1 calldata byte.
+    pub per_l1_calldata_byte: U256,
+    /// The price per storage allocation.
+    pub per_storage_allocation: U256,
+    /// The base price per unit of ArbGas.
+    pub per_arb_gas_base: U256,
+    /// The congestion price per unit of ArbGas.
+    pub per_arb_gas_congestion: U256,
+    /// The total price per unit of ArbGas.
+    pub per_arb_gas_total: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransactionReceipt;
+    use alloy_serde::WithOtherFields;
+
+    #[test]
+    fn tx_types() {
+        for ty in 0..=u8::MAX {
+            if let Ok(arb_ty) = ArbitrumTxType::try_from(ty) {
+                assert_eq!(u8::from(arb_ty), ty);
+            }
+        }
+        assert_eq!(ArbitrumTxType::try_from(0x02), Err(0x02));
+        assert!(ArbitrumTxType::SubmitRetryable.is_retryable());
+        assert!(!ArbitrumTxType::Deposit.is_retryable());
+    }
+
+    #[test]
+    fn deserialize_receipt_fields() {
+        let receipt = r#"{"blockHash":"0x7c6e8d2b0b4ea27b7df7ac0d8fc1b2bb64e6fda2e4e77b0a4bb2236d6a5d2d54","blockNumber":"0xdde8b5e","contractAddress":null,"cumulativeGasUsed":"0x0","effectiveGasPrice":"0x989680","from":"0x5e1497dd1f08c87b2d8fe23e9aab6c1de833d927","gasUsed":"0x1a23c","gasUsedForL1":"0x82d5","l1BlockNumber":"0x12a7a44","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","status":"0x1","to":"0x1b02da8cb0d097eb8d57a175b88c7d8b47997506","transactionHash":"0x2e2f5b1cb1a0d0ed2f3d2c8e4b5a0b0f3c0d1e2f3a4b5c6d7e8f90a1b2c3d4e5","transactionIndex":"0x1","type":"0x2"}"#;
+        let receipt: WithOtherFields<TransactionReceipt> = serde_json::from_str(receipt).unwrap();
+        let fields: ArbitrumTransactionReceiptFields = receipt.other.deserialize_as().unwrap();
+        assert_eq!(fields.gas_used_for_l1, Some(0x82d5));
+        assert_eq!(fields.l1_block_number, Some(0x12a7a44));
+        assert_eq!(OtherFields::from(fields), receipt.other);
+    }
+
+    #[test]
+    fn deserialize_retryable_fields() {
+        let tx = serde_json::json!({
+            "ticketId": "0x4c4a2c3f1e0e3a0d9a0d5a4b3c2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d",
+            "refundTo": "0x5e1497dd1f08c87b2d8fe23e9aab6c1de833d927",
+            "maxRefund": "0x1d1a94a2000",
+            "submissionFeeRefund": "0x0"
+        });
+        let fields: ArbitrumTransactionFields = serde_json::from_value(tx.clone()).unwrap();
+        assert_eq!(fields.max_refund, Some(U256::from(0x1d1a94a2000u64)));
+        assert_eq!(fields.submission_fee_refund, Some(U256::ZERO));
+        assert_eq!(serde_json::to_value(fields).unwrap(), tx);
+    }
+}
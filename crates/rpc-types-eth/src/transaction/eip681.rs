@@ -0,0 +1,463 @@
+//! [EIP-681] payment request URIs, such as `ethereum:0xfb69…@1?value=1e18`.
+//!
+//! [EIP-681]: https://eips.ethereum.org/EIPS/eip-681
+
+use crate::TransactionRequest;
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{keccak256, Address, Bytes, ChainId, TxKind, U256};
+use std::{fmt, str::FromStr};
+
+/// The scheme of the URIs.
+const SCHEME: &str = "ethereum:";
+
+/// The prefix of payment URIs, the only prefix defined by EIP-681, which is optional.
+const PAY_PREFIX: &str = "pay-";
+
+/// An error parsing an [EIP-681] URI or building its transaction.
+///
+/// [EIP-681]: https://eips.ethereum.org/EIPS/eip-681
+#[derive(Debug, thiserror::Error)]
+pub enum Eip681Error {
+    /// The URI does not start with `ethereum:`.
+    #[error("URI does not start with `ethereum:`")]
+    InvalidScheme,
+    /// The target is neither an address nor a name.
+    #[error("invalid target address {0:?}")]
+    InvalidTarget(String),
+    /// The target is a name, which must be resolved to an address first.
+    #[error("target {0:?} must be resolved to an address")]
+    UnresolvedName(String),
+    /// The chain ID is not a number.
+    #[error("invalid chain ID {0:?}")]
+    InvalidChainId(String),
+    /// A parameter is not a `key=value` pair.
+    #[error("invalid parameter {0:?}")]
+    InvalidParameter(String),
+    /// A number is malformed, negative or fractional where an integer is expected, or too large.
+    #[error("invalid number {0:?}")]
+    InvalidNumber(String),
+    /// The URI has function arguments but no function.
+    #[error("function arguments without a function")]
+    ArgumentsWithoutFunction,
+    /// A function argument does not match its type.
+    #[error(transparent)]
+    Abi(#[from] alloy_dyn_abi::Error),
+}
+
+/// The target of a [`PaymentRequest`]: an address, or a name such as an ENS name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PaymentTarget {
+    /// An address.
+    Address(Address),
+    /// A name, to resolve to an address.
+    Name(String),
+}
+
+impl fmt::Display for PaymentTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address(address) => address.fmt(f),
+            Self::Name(name) => f.write_str(name),
+        }
+    }
+}
+
+/// A payment request [EIP-681] URI: a transfer of ether, or a call of a function of a contract
+/// such as an ERC-20 `transfer`.
+///
+/// The request is parsed with [`FromStr`], written with [`Display`](fmt::Display), and turned
+/// into a transaction with [`transaction_request`](Self::transaction_request). The optional
+/// `pay-` prefix is accepted, and omitted when written.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_primitives::{address, U256};
+/// use alloy_rpc_types_eth::transaction::eip681::PaymentRequest;
+///
+/// // A transfer of 1 USDC, with 6 decimals, on Ethereum mainnet.
+/// let uri = "ethereum:0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48@1/transfer?address=0x8e23Ee67d1332aD560396262C48ffbB01f93d052&uint256=1e6";
+/// let request: PaymentRequest = uri.parse()?;
+/// assert_eq!(request.chain_id, Some(1));
+/// assert_eq!(request.to_string(), uri);
+///
+/// let tx = request.transaction_request()?;
+/// assert_eq!(tx.to, Some(address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").into()));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [EIP-681]: https://eips.ethereum.org/EIPS/eip-681
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentRequest {
+    /// The recipient of the ether, or the contract of the function.
+    pub target: PaymentTarget,
+    /// The chain of the transaction, the current chain of the wallet if `None`.
+    pub chain_id: Option<ChainId>,
+    /// The name of the function to call, `None` for a transfer of ether.
+    pub function: Option<String>,
+    /// The arguments of the function, as their Solidity types and values.
+    pub arguments: Vec<(String, String)>,
+    /// The amount of wei to send.
+    pub value: Option<U256>,
+    /// The gas limit of the transaction.
+    pub gas_limit: Option<u128>,
+    /// The gas price of the transaction.
+    pub gas_price: Option<u128>,
+}
+
+impl PaymentRequest {
+    /// Creates a request to pay `value` wei to `to`.
+    pub const fn transfer(to: Address, value: U256) -> Self {
+        Self {
+            target: PaymentTarget::Address(to),
+            chain_id: None,
+            function: None,
+            arguments: Vec::new(),
+            value: Some(value),
+            gas_limit: None,
+            gas_price: None,
+        }
+    }
+
+    /// Creates a request to call `function` of the contract `to` with the given arguments, as
+    /// their Solidity types and values.
+    pub fn call<T, V>(
+        to: Address,
+        function: impl Into<String>,
+        arguments: impl IntoIterator<Item = (T, V)>,
+    ) -> Self
+    where
+        T: Into<String>,
+        V: Into<String>,
+    {
+        Self {
+            target: PaymentTarget::Address(to),
+            chain_id: None,
+            function: Some(function.into()),
+            arguments: arguments.into_iter().map(|(ty, value)| (ty.into(), value.into())).collect(),
+            value: None,
+            gas_limit: None,
+            gas_price: None,
+        }
+    }
+
+    /// Sets the chain of the transaction.
+    pub const fn with_chain_id(mut self, chain_id: ChainId) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Returns the calldata of the function call, or `None` for a transfer of ether.
+    ///
+    /// The arguments are ABI-encoded after the selector of the function, computed from its name
+    /// and the types of the arguments. Integer arguments may use the scientific notation of
+    /// EIP-681, such as `2.5e18`.
+    pub fn calldata(&self) -> Result<Option<Vec<u8>>, Eip681Error> {
+        let Some(function) = &self.function else { return Ok(None) };
+        let mut types = Vec::with_capacity(self.arguments.len());
+        let mut values = Vec::with_capacity(self.arguments.len());
+        for (ty, value) in &self.arguments {
+            let ty = DynSolType::parse(ty)?;
+            let value = match ty {
+                DynSolType::Int(_) | DynSolType::Uint(_) => {
+                    let (negative, n) = parse_number(value)?;
+                    ty.coerce_str(&format!("{}{n}", if negative { "-" } else { "" }))?
+                }
+                _ => ty.coerce_str(value)?,
+            };
+            types.push(ty.sol_type_name());
+            values.push(value);
+        }
+        let signature = format!("{function}({})", types.join(","));
+        let mut calldata = keccak256(signature)[..4].to_vec();
+        calldata.extend(DynSolValue::Tuple(values).abi_encode_params());
+        Ok(Some(calldata))
+    }
+
+    /// Returns the transaction of the request, without sender, nonce nor fees other than the gas
+    /// price.
+    ///
+    /// Fails if the target is a [name](PaymentTarget::Name), which must be resolved to an
+    /// address first.
+    pub fn transaction_request(&self) -> Result<TransactionRequest, Eip681Error> {
+        let to = match &self.target {
+            PaymentTarget::Address(address) => *address,
+            PaymentTarget::Name(name) => return Err(Eip681Error::UnresolvedName(name.clone())),
+        };
+        Ok(TransactionRequest {
+            to: Some(TxKind::Call(to)),
+            value: self.value,
+            gas: self.gas_limit,
+            gas_price: self.gas_price,
+            chain_id: self.chain_id,
+            input: self.calldata()?.map(|data| Bytes::from(data).into()).unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = Eip681Error;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let rest = uri.strip_prefix(SCHEME).ok_or(Eip681Error::InvalidScheme)?;
+        let rest = rest.strip_prefix(PAY_PREFIX).unwrap_or(rest);
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (target, function) = match path.split_once('/') {
+            Some((target, function)) => (target, Some(percent_decode(function)?)),
+            None => (path, None),
+        };
+        let (target, chain_id) = match target.split_once('@') {
+            Some((target, chain_id)) => (
+                target,
+                Some(
+                    chain_id
+                        .parse()
+                        .map_err(|_| Eip681Error::InvalidChainId(chain_id.to_string()))?,
+                ),
+            ),
+            None => (target, None),
+        };
+        let target = if target.starts_with("0x") {
+            target
+                .parse()
+                .map(PaymentTarget::Address)
+                .map_err(|_| Eip681Error::InvalidTarget(target.to_string()))?
+        } else if !target.is_empty() {
+            PaymentTarget::Name(percent_decode(target)?)
+        } else {
+            return Err(Eip681Error::InvalidTarget(String::new()));
+        };
+
+        let mut request = Self {
+            target,
+            chain_id,
+            function,
+            arguments: Vec::new(),
+            value: None,
+            gas_limit: None,
+            gas_price: None,
+        };
+        for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+            let (key, value) = parameter
+                .split_once('=')
+                .ok_or_else(|| Eip681Error::InvalidParameter(parameter.to_string()))?;
+            let value = percent_decode(value)?;
+            match key {
+                "value" => request.value = Some(parse_unsigned(&value)?),
+                "gas" | "gasLimit" => request.gas_limit = Some(parse_u128(&value)?),
+                "gasPrice" => request.gas_price = Some(parse_u128(&value)?),
+                ty => request.arguments.push((percent_decode(ty)?, value)),
+            }
+        }
+        if request.function.is_none() && !request.arguments.is_empty() {
+            return Err(Eip681Error::ArgumentsWithoutFunction);
+        }
+        Ok(request)
+    }
+}
+
+impl fmt::Display for PaymentRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{SCHEME}{}", PercentEncoded(&self.target.to_string()))?;
+        if let Some(chain_id) = self.chain_id {
+            write!(f, "@{chain_id}")?;
+        }
+        if let Some(function) = &self.function {
+            write!(f, "/{}", PercentEncoded(function))?;
+        }
+        let arguments = self.arguments.iter().map(|(ty, value)| (ty.as_str(), value.to_string()));
+        let fields = [
+            ("value", self.value),
+            ("gasLimit", self.gas_limit.map(U256::from)),
+            ("gasPrice", self.gas_price.map(U256::from)),
+        ];
+        let fields = fields.into_iter().filter_map(|(key, n)| Some((key, n?.to_string())));
+        for (i, (key, value)) in arguments.chain(fields).enumerate() {
+            let separator = if i == 0 { '?' } else { '&' };
+            write!(f, "{separator}{}={}", PercentEncoded(key), PercentEncoded(&value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a number of EIP-681, such as `-2.5e3`, which must be an integer, returning whether it
+/// is negative and its absolute value.
+fn parse_number(s: &str) -> Result<(bool, U256), Eip681Error> {
+    let invalid = || Eip681Error::InvalidNumber(s.to_string());
+    let (negative, unsigned) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<u8>().map_err(|_| invalid())?),
+        None => (unsigned, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if integer.is_empty() && fraction.is_empty()
+        || !is_digits(integer)
+        || !is_digits(fraction)
+        || mantissa.ends_with('.')
+    {
+        return Err(invalid());
+    }
+
+    // Shift the fractional digits into the integer, which must leave only zeros behind.
+    let shift = (exponent as usize).min(fraction.len());
+    let (shifted, rest) = fraction.split_at(shift);
+    if rest.bytes().any(|b| b != b'0') {
+        return Err(invalid());
+    }
+    let digits = format!("{integer}{shifted}");
+    let digits = if digits.is_empty() { "0" } else { &digits };
+    let scale = U256::from(10).checked_pow(U256::from(exponent as usize - shift));
+    let n = U256::from_str_radix(digits, 10)
+        .ok()
+        .zip(scale)
+        .and_then(|(n, scale)| n.checked_mul(scale))
+        .ok_or_else(invalid)?;
+    Ok((negative && !n.is_zero(), n))
+}
+
+/// Parses a non-negative number of EIP-681.
+fn parse_unsigned(s: &str) -> Result<U256, Eip681Error> {
+    match parse_number(s)? {
+        (false, n) => Ok(n),
+        (true, _) => Err(Eip681Error::InvalidNumber(s.to_string())),
+    }
+}
+
+/// Parses a non-negative number of EIP-681 which fits in a `u128`.
+fn parse_u128(s: &str) -> Result<u128, Eip681Error> {
+    parse_unsigned(s)?.try_into().map_err(|_| Eip681Error::InvalidNumber(s.to_string()))
+}
+
+/// Decodes the `%XX` escapes of a component of the URI.
+fn percent_decode(s: &str) -> Result<String, Eip681Error> {
+    let invalid = || Eip681Error::InvalidParameter(s.to_string());
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut input = s.bytes();
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            let hex = [input.next().ok_or_else(invalid)?, input.next().ok_or_else(invalid)?];
+            let hex = std::str::from_utf8(&hex).map_err(|_| invalid())?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Writes a component of the URI, escaping the characters with a meaning in the URI.
+struct PercentEncoded<'a>(&'a str);
+
+impl fmt::Display for PercentEncoded<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.bytes() {
+            if byte.is_ascii_alphanumeric() || b"-._~()[],".contains(&byte) {
+                write!(f, "{}", byte as char)?;
+            } else {
+                write!(f, "%{byte:02X}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex};
+
+    #[test]
+    fn numbers() {
+        let parse = |s| parse_number(s).map(|(negative, n)| (negative, n.to::<u128>()));
+        assert_eq!(parse("0").unwrap(), (false, 0));
+        assert_eq!(parse("2.014e18").unwrap(), (false, 2_014_000_000_000_000_000));
+        assert_eq!(parse("1E6").unwrap(), (false, 1_000_000));
+        assert!(parse("-25e-0").is_err());
+        assert_eq!(parse("-2.50e1").unwrap(), (true, 25));
+        assert_eq!(parse("+.5e1").unwrap(), (false, 5));
+        for invalid in ["", "-", "1.", "1.5", "1.25e1", "0x10", "1e", "1e100", "e5"] {
+            assert!(parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn ether_transfer() {
+        let uri = "ethereum:pay-0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359@5?value=2.014e18&gas=21000&gasPrice=5e10";
+        let request: PaymentRequest = uri.parse().unwrap();
+        let to = address!("fB6916095ca1df60bB79Ce92cE3Ea74c37c5d359");
+        assert_eq!(
+            request,
+            PaymentRequest {
+                chain_id: Some(5),
+                gas_limit: Some(21_000),
+                gas_price: Some(50_000_000_000),
+                ..PaymentRequest::transfer(to, U256::from(2_014_000_000_000_000_000u128))
+            }
+        );
+        assert_eq!(
+            request.to_string(),
+            "ethereum:0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359@5?value=2014000000000000000&gasLimit=21000&gasPrice=50000000000"
+        );
+        assert_eq!(request.to_string().parse::<PaymentRequest>().unwrap(), request);
+
+        let tx = request.transaction_request().unwrap();
+        assert_eq!(tx.to, Some(TxKind::Call(to)));
+        assert_eq!(tx.value, Some(U256::from(2_014_000_000_000_000_000u128)));
+        assert_eq!(tx.chain_id, Some(5));
+        assert_eq!(tx.input.input(), None);
+    }
+
+    #[test]
+    fn function_call() {
+        let token = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let recipient = "0x8e23Ee67d1332aD560396262C48ffbB01f93d052";
+        let request =
+            PaymentRequest::call(token, "transfer", [("address", recipient), ("uint256", "1.5e6")])
+                .with_chain_id(1);
+        let uri = request.to_string();
+        assert_eq!(uri, format!("ethereum:{token}@1/transfer?address={recipient}&uint256=1.5e6"));
+        assert_eq!(uri.parse::<PaymentRequest>().unwrap(), request);
+
+        let calldata = request.calldata().unwrap().unwrap();
+        assert_eq!(
+            hex::encode(calldata),
+            "a9059cbb0000000000000000000000008e23ee67d1332ad560396262c48ffbb01f93d052000000000000000000000000000000000000000000000000000000000016e360"
+        );
+    }
+
+    #[test]
+    fn names_and_strings() {
+        let request: PaymentRequest =
+            "ethereum:alice.eth/setName?string=hello%20world%26more".parse().unwrap();
+        assert_eq!(request.target, PaymentTarget::Name("alice.eth".into()));
+        assert_eq!(request.arguments, [("string".to_string(), "hello world&more".to_string())]);
+        assert_eq!(request.to_string(), "ethereum:alice.eth/setName?string=hello%20world%26more");
+        assert!(matches!(request.transaction_request(), Err(Eip681Error::UnresolvedName(_))));
+    }
+
+    #[test]
+    fn invalid_uris() {
+        for uri in [
+            "bitcoin:0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "ethereum:0x1234",
+            "ethereum:",
+            "ethereum:0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359@x",
+            "ethereum:0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359?value",
+            "ethereum:0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359?value=-1",
+            "ethereum:0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359?uint256=1",
+            "ethereum:0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359?value=%2",
+        ] {
+            assert!(uri.parse::<PaymentRequest>().is_err(), "{uri}");
+        }
+
+        let request: PaymentRequest =
+            "ethereum:0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359/f?uint8=256".parse().unwrap();
+        assert!(request.calldata().is_err());
+    }
+}
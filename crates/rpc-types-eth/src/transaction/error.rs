@@ -19,6 +19,12 @@ pub enum ConversionError {
     /// Invalid signature
     #[error("invalid signature")]
     InvalidSignature,
+    /// Invalid `v` value in signature for the transaction type.
+    #[error("invalid `v` value in signature for the transaction type: {0}")]
+    InvalidV(alloy_primitives::U256),
+    /// The y parity of the signature does not match its `v` value.
+    #[error("y parity does not match the `v` value of the signature")]
+    YParityMismatch,
     /// Missing `chainId` field for EIP-1559 transaction.
     #[error("missing `chainId` field for EIP-155 transaction")]
     MissingChainId,
@@ -1,5 +1,6 @@
 //! RPC types for transactions
 
+use crate::{other_fields_heap_size, InMemorySize};
 use alloy_consensus::{
     SignableTransaction, Signed, TxEip1559, TxEip2930, TxEip4844, TxEip4844Variant, TxEnvelope,
     TxLegacy, TxType,
@@ -11,12 +12,20 @@ use serde::{Deserialize, Serialize};
 pub use alloy_consensus::BlobTransactionSidecar;
 pub use alloy_eips::eip2930::{AccessList, AccessListItem, AccessListWithGasUsed};
 
+pub mod arbitrum;
+pub use arbitrum::ArbitrumTransactionReceiptFields;
+
 mod common;
 pub use common::TransactionInfo;
 
 mod error;
 pub use error::ConversionError;
 
+#[cfg(feature = "dyn-abi")]
+pub mod eip681;
+#[cfg(feature = "dyn-abi")]
+pub use eip681::{Eip681Error, PaymentRequest, PaymentTarget};
+
 pub mod optimism;
 pub use optimism::OptimismTransactionReceiptFields;
 
@@ -32,9 +41,14 @@ pub use signature::{Parity, Signature};
 pub use alloy_consensus::{AnyReceiptEnvelope, Receipt, ReceiptEnvelope, ReceiptWithBloom};
 
 /// Transaction object used in RPC
+///
+/// With the `lenient` feature, `value` and `input` default to zero when they are missing or
+/// `null`, and `input` is read from `data` when it is absent, as returned by some non-ethereum
+/// networks.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "lenient", serde(remote = "Self"))]
 #[doc(alias = "Tx")]
 pub struct Transaction {
     /// Hash
@@ -56,6 +70,11 @@ pub struct Transaction {
     /// Recipient
     pub to: Option<Address>,
     /// Transferred value
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
+    #[cfg_attr(not(feature = "lenient"), serde(with = "alloy_serde::quantity"))]
     pub value: U256,
     /// Gas Price
     #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
@@ -73,6 +92,10 @@ pub struct Transaction {
     #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
     pub max_fee_per_blob_gas: Option<u128>,
     /// Data
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "alloy_serde::null_as_default")
+    )]
     pub input: Bytes,
     /// All _flattened_ fields of the transaction signature.
     ///
@@ -111,6 +134,27 @@ pub struct Transaction {
     pub other: OtherFields,
 }
 
+#[cfg(feature = "lenient")]
+impl Serialize for Transaction {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "lenient")]
+impl<'de> Deserialize<'de> for Transaction {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields = serde_json::Map::deserialize(deserializer)?;
+        // An `alias` would reject objects with both keys, so only fall back to `data` here.
+        if !fields.contains_key("input") {
+            if let Some(data) = fields.remove("data") {
+                fields.insert("input".to_string(), data);
+            }
+        }
+        Self::deserialize(serde_json::Value::Object(fields)).map_err(serde::de::Error::custom)
+    }
+}
+
 impl Transaction {
     /// Returns true if the transaction is a legacy or 2930 transaction.
     pub const fn is_legacy_gas(&self) -> bool {
@@ -269,6 +313,16 @@ impl TryFrom<Transaction> for TxEnvelope {
     }
 }
 
+impl InMemorySize for Transaction {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.input.heap_size()
+            + self.blob_versioned_hashes.heap_size()
+            + self.access_list.heap_size()
+            + other_fields_heap_size(&self.other)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,6 +375,24 @@ mod tests {
         assert_eq!(transaction, deserialized);
     }
 
+    #[test]
+    #[cfg(feature = "lenient")]
+    fn serde_lenient_input_and_data() {
+        let mut transaction = Transaction::default();
+        let mut fields = serde_json::to_value(&transaction).unwrap();
+        fields.as_object_mut().unwrap().remove("input");
+        fields["data"] = "0x1234".into();
+        let deserialized: Transaction = serde_json::from_value(fields.clone()).unwrap();
+        assert_eq!(deserialized.input, Bytes::from_static(&[0x12, 0x34]));
+
+        // `input` takes precedence, and `data` is kept with the other fields.
+        fields["input"] = "0x56".into();
+        let deserialized: Transaction = serde_json::from_value(fields).unwrap();
+        transaction.input = Bytes::from_static(&[0x56]);
+        transaction.other.insert("data".to_string(), "0x1234".into());
+        assert_eq!(deserialized, transaction);
+    }
+
     #[test]
     fn serde_transaction_with_parity_bit() {
         let transaction = Transaction {
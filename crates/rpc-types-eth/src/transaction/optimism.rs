@@ -48,13 +48,13 @@ pub struct OptimismTransactionReceiptFields {
 
 impl From<OptimismTransactionFields> for OtherFields {
     fn from(value: OptimismTransactionFields) -> Self {
-        serde_json::to_value(value).unwrap().try_into().unwrap()
+        Self::from_serialized(&value).expect("fields serialize to a JSON object")
     }
 }
 
 impl From<OptimismTransactionReceiptFields> for OtherFields {
     fn from(value: OptimismTransactionReceiptFields) -> Self {
-        serde_json::to_value(value).unwrap().try_into().unwrap()
+        Self::from_serialized(&value).expect("fields serialize to a JSON object")
     }
 }
 
@@ -1,9 +1,12 @@
 #![allow(unknown_lints, non_local_definitions)] // TODO: remove when proptest-derive updates
 
-use crate::Log;
-use alloy_consensus::{AnyReceiptEnvelope, ReceiptEnvelope, TxType};
-use alloy_primitives::{Address, BlockHash, TxHash, B256};
+#[cfg(feature = "dyn-abi")]
+use crate::{DecodedLog, DynAbi};
+use crate::{InMemorySize, Log};
+use alloy_consensus::{AnyReceiptEnvelope, Eip658Value, ReceiptEnvelope, TxReceipt, TxType};
+use alloy_primitives::{Address, BlockHash, TxHash, B256, U256};
 use alloy_serde::WithOtherFields;
+use alloy_sol_types::SolEvent;
 use serde::{Deserialize, Serialize};
 
 /// Transaction receipt
@@ -11,10 +14,8 @@ use serde::{Deserialize, Serialize};
 /// This type is generic over an inner [`ReceiptEnvelope`] which contains
 /// consensus data and metadata.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[cfg_attr(
-    any(test, feature = "arbitrary"),
-    derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
-)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "proptest"), derive(proptest_derive::Arbitrary))]
 #[serde(rename_all = "camelCase")]
 #[doc(alias = "TxReceipt")]
 pub struct TransactionReceipt<T = ReceiptEnvelope<Log>> {
@@ -58,11 +59,37 @@ pub struct TransactionReceipt<T = ReceiptEnvelope<Log>> {
     pub contract_address: Option<Address>,
     /// The post-transaction stateroot (pre Byzantium)
     ///
-    /// EIP98 makes this optional field, if it's missing then skip serializing it
-    #[serde(skip_serializing_if = "Option::is_none", rename = "root")]
+    /// This is a copy of the post state of the inner receipt, which it is serialized with.
+    #[deprecated(note = "use `TransactionReceipt::status_or_post_state` instead")]
+    #[serde(flatten, with = "post_state_root")]
     pub state_root: Option<B256>,
 }
 
+/// (De)serializes the deprecated [`TransactionReceipt::state_root`] copy of the `root` field,
+/// which is left to the inner receipt.
+mod post_state_root {
+    use alloy_primitives::B256;
+    use serde::{ser::SerializeMap, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        _: &Option<B256>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_map(Some(0))?.end()
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<B256>, D::Error> {
+        #[derive(Deserialize)]
+        struct Root {
+            #[serde(default)]
+            root: Option<B256>,
+        }
+        Root::deserialize(deserializer).map(|Root { root }| root)
+    }
+}
+
 impl AsRef<ReceiptEnvelope<Log>> for TransactionReceipt {
     fn as_ref(&self) -> &ReceiptEnvelope<Log> {
         &self.inner
@@ -100,7 +127,19 @@ impl TransactionReceipt {
 }
 
 impl<T> TransactionReceipt<T> {
+    /// Returns the fee paid by the transaction in wei, which is
+    /// `gas_used * effective_gas_price`, plus `blob_gas_used * blob_gas_price` for blob
+    /// transactions.
+    pub fn fee_paid(&self) -> U256 {
+        let blob_fee = match (self.blob_gas_used, self.blob_gas_price) {
+            (Some(gas), Some(price)) => U256::from(gas) * U256::from(price),
+            _ => U256::ZERO,
+        };
+        U256::from(self.gas_used) * U256::from(self.effective_gas_price) + blob_fee
+    }
+
     /// Maps the inner receipt value of this receipt.
+    #[allow(deprecated)]
     pub fn map_inner<U, F>(self, f: F) -> TransactionReceipt<U>
     where
         F: FnOnce(T) -> U,
@@ -123,15 +162,62 @@ impl<T> TransactionReceipt<T> {
     }
 }
 
+impl<T: TxReceipt<Log>> TransactionReceipt<T> {
+    /// Returns the status code, or the post-transaction state root of pre-[EIP-658] receipts.
+    ///
+    /// The state root is read from the `root` field of the receipt.
+    ///
+    /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+    pub fn status_or_post_state(&self) -> Eip658Value {
+        *self.inner.status_or_post_state()
+    }
+
+    /// Returns the post-transaction state root of pre-[EIP-658] receipts.
+    ///
+    /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+    #[doc(alias = "root")]
+    pub fn state_root(&self) -> Option<B256> {
+        self.status_or_post_state().as_post_state()
+    }
+
+    /// Returns the logs emitted as the event `E`, decoded, along with their metadata.
+    ///
+    /// Logs of other events are skipped.
+    pub fn decoded_logs<E: SolEvent>(&self) -> Vec<Log<E>> {
+        self.inner
+            .logs()
+            .iter()
+            .filter(|log| E::ANONYMOUS || log.topic0() == Some(&E::SIGNATURE_HASH))
+            .filter_map(|log| log.log_decode().ok())
+            .collect()
+    }
+
+    /// Returns the logs emitted by an event of any of the given ABIs, decoded with the first ABI
+    /// that emits them, along with their metadata.
+    ///
+    /// Logs that no ABI can decode are skipped.
+    #[cfg(feature = "dyn-abi")]
+    pub fn decode_all(&self, abis: &[&dyn DynAbi]) -> Vec<Log<DecodedLog>> {
+        self.inner.logs().iter().filter_map(|log| log.log_decode_dyn(abis)).collect()
+    }
+}
+
 /// Alias for a catch-all receipt type.
 #[doc(alias = "AnyTxReceipt")]
 pub type AnyTransactionReceipt = WithOtherFields<TransactionReceipt<AnyReceiptEnvelope<Log>>>;
 
+impl<T: InMemorySize> InMemorySize for TransactionReceipt<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.inner.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::TransactionReceipt;
-    use alloy_consensus::{Eip658Value, Receipt, ReceiptWithBloom};
+    use alloy_consensus::{Receipt, ReceiptWithBloom};
     use alloy_primitives::{address, b256, bloom, Bloom};
     use arbitrary::Arbitrary;
     use rand::Rng;
@@ -170,6 +256,8 @@ mod test {
             })
         ));
 
+        assert_eq!(receipt.fee_paid(), U256::from(0xbde1u128 * 0xfb0f6e8c9));
+
         let log = receipt.inner.as_receipt().unwrap().logs.first().unwrap();
         assert_eq!(log.address(), address!("dac17f958d2ee523a2206206994597c13d831ec7"));
         assert_eq!(log.log_index, Some(0x118));
@@ -321,4 +409,115 @@ mod test {
         assert_eq!(other.gas_used_for_l1, "0x2c906");
         assert_eq!(other.l1_block_number, "0x1323b96");
     }
+
+    #[test]
+    fn deserialize_pre_byzantium_receipt() {
+        let json_str = r#"{"blockHash":"0x4e3a3754410177e6937ef1f84bba68ea139e8d1a2258c5f85db9f1cd715a1bdd","blockNumber":"0xb443","contractAddress":null,"cumulativeGasUsed":"0x5208","effectiveGasPrice":"0x2d79883d2000","from":"0xa1e4380a3b1f749673e270229993ee55f35663b4","gasUsed":"0x5208","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","root":"0x96a8e009d2b88b1483e6941e6812e32263b05683fac202abc622a3e31aed1957","to":"0x5df9b87991262f6ba471f09758cde1c0fc1de734","transactionHash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060","transactionIndex":"0x0","type":"0x0"}"#;
+
+        let root = b256!("96a8e009d2b88b1483e6941e6812e32263b05683fac202abc622a3e31aed1957");
+        let receipt: TransactionReceipt = serde_json::from_str(json_str).unwrap();
+        assert_eq!(receipt.status_or_post_state(), Eip658Value::PostState(root));
+        assert_eq!(receipt.state_root(), Some(root));
+        #[allow(deprecated)]
+        let state_root = receipt.state_root;
+        assert_eq!(state_root, Some(root));
+        assert!(receipt.status());
+        assert_eq!(
+            serde_json::to_value(&receipt).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json_str).unwrap()
+        );
+
+        let receipt: AnyTransactionReceipt = serde_json::from_str(json_str).unwrap();
+        assert_eq!(receipt.state_root(), Some(root));
+        assert!(receipt.other.is_empty());
+    }
+
+    alloy_sol_types::sol! {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+        event Approval(address indexed owner, address indexed spender, uint256 value);
+    }
+
+    fn receipt_with_logs() -> TransactionReceipt {
+        let token = address!("dac17f958d2ee523a2206206994597c13d831ec7");
+        let (from, to) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let events = [
+            Transfer { from, to, value: U256::from(1) }.encode_log_data(),
+            Approval { owner: from, spender: to, value: U256::from(2) }.encode_log_data(),
+            Transfer { from: to, to: from, value: U256::from(3) }.encode_log_data(),
+        ];
+        let logs = events
+            .into_iter()
+            .enumerate()
+            .map(|(i, data)| Log {
+                inner: alloy_primitives::Log { address: token, data },
+                block_number: Some(100),
+                log_index: Some(10 + i as u64),
+                ..Default::default()
+            })
+            .collect();
+
+        TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+                receipt: Receipt { status: true.into(), cumulative_gas_used: 0, logs },
+                logs_bloom: Bloom::ZERO,
+            }),
+            transaction_hash: B256::ZERO,
+            transaction_index: None,
+            block_hash: None,
+            block_number: Some(100),
+            gas_used: 0,
+            effective_gas_price: 0,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from,
+            to: Some(token),
+            contract_address: None,
+            #[allow(deprecated)]
+            state_root: None,
+        }
+    }
+
+    #[test]
+    fn decodes_typed_logs() {
+        let receipt = receipt_with_logs();
+
+        let transfers = receipt.decoded_logs::<Transfer>();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].inner.value, U256::from(1));
+        assert_eq!(transfers[0].log_index, Some(10));
+        assert_eq!(transfers[1].inner.from, Address::with_last_byte(2));
+        assert_eq!(transfers[1].log_index, Some(12));
+        assert_eq!(transfers[1].block_number, Some(100));
+
+        let approvals = receipt.decoded_logs::<Approval>();
+        assert_eq!(approvals.len(), 1);
+        assert_eq!(approvals[0].inner.spender, Address::with_last_byte(2));
+        assert_eq!(approvals[0].log_index, Some(11));
+    }
+
+    #[test]
+    #[cfg(feature = "dyn-abi")]
+    fn decodes_dynamic_logs() {
+        use alloy_dyn_abi::DynSolValue;
+
+        let receipt = receipt_with_logs();
+        let transfer = alloy_json_abi::Event::parse(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        )
+        .unwrap();
+        let abi = alloy_json_abi::JsonAbi::parse([
+            "event Approval(address indexed owner, address indexed spender, uint256 value)",
+        ])
+        .unwrap();
+
+        let logs = receipt.decode_all(&[&transfer]);
+        assert_eq!(logs.len(), 2);
+        assert!(logs.iter().all(|log| log.inner.name == "Transfer"));
+
+        let logs = receipt.decode_all(&[&abi, &transfer]);
+        let names: Vec<_> = logs.iter().map(|log| log.inner.name.as_str()).collect();
+        assert_eq!(names, ["Transfer", "Approval", "Transfer"]);
+        assert_eq!(logs[1].log_index, Some(11));
+        assert_eq!(logs[1].inner.event.body, [DynSolValue::Uint(U256::from(2), 256)]);
+    }
 }
@@ -36,7 +36,7 @@ pub struct TransactionRequest {
     #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
     pub gas: Option<u128>,
     /// The value transferred in the transaction, in wei.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
     pub value: Option<U256>,
     /// Transaction data.
     #[serde(default, flatten)]
@@ -1,7 +1,13 @@
 //! Signature related RPC values
-use alloy_primitives::U256;
+use super::ConversionError;
+use alloy_consensus::{constants::SECP256K1N_HALF, TxType};
+use alloy_primitives::{uint, U256};
 use serde::{Deserialize, Serialize};
 
+/// The order of the secp256k1 curve.
+const SECP256K1N: U256 =
+    uint!(0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141_U256);
+
 /// Container type for all signature fields in RPC
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -62,6 +68,76 @@ where
     }
 }
 
+impl Signature {
+    /// Returns true if the `s` value is at most [`SECP256K1N_HALF`], as required for the
+    /// signatures of transactions since Homestead ([EIP-2]).
+    ///
+    /// [`SECP256K1N_HALF`]: alloy_consensus::constants::SECP256K1N_HALF
+    /// [EIP-2]: https://eips.ethereum.org/EIPS/eip-2
+    pub fn is_low_s(&self) -> bool {
+        self.s <= SECP256K1N_HALF
+    }
+
+    /// Returns the equivalent signature with a low `s` value, which is the signature itself if `s`
+    /// is already low.
+    ///
+    /// A high `s` value is replaced by its complement, and the parity in `v` and `y_parity` is
+    /// inverted accordingly, preserving the legacy or [EIP-155] encoding of `v`.
+    ///
+    /// Returns [`ConversionError::InvalidV`] if `v` is not a valid legacy, [EIP-155] or typed
+    /// transaction value. Use [`Signature::validate_for`] to also check `v` against the type of
+    /// the transaction.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub fn normalize_s(&self) -> Result<Self, ConversionError> {
+        let parity = self.v_parity().ok_or(ConversionError::InvalidV(self.v))?;
+        if self.is_low_s() {
+            return Ok(*self);
+        }
+        let v = if parity { self.v - U256::from(1) } else { self.v + U256::from(1) };
+        Ok(Self {
+            r: self.r,
+            s: SECP256K1N - self.s,
+            v,
+            y_parity: self.y_parity.map(|parity| Parity(!parity.0)),
+        })
+    }
+
+    /// Checks that `v` and `y_parity` are valid for the given transaction type.
+    ///
+    /// Legacy transactions must have a `v` of 27 or 28, or an [EIP-155] value of at least 35,
+    /// while typed transactions must have a `v` of 0 or 1. If set, `y_parity` must match the
+    /// parity encoded in `v`.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub fn validate_for(&self, tx_type: TxType) -> Result<(), ConversionError> {
+        let valid_v = match tx_type {
+            TxType::Legacy => self.v >= U256::from(27),
+            _ => self.v <= U256::from(1),
+        };
+        let parity =
+            self.v_parity().filter(|_| valid_v).ok_or(ConversionError::InvalidV(self.v))?;
+        if self.y_parity.is_some_and(|y_parity| y_parity.0 != parity) {
+            return Err(ConversionError::YParityMismatch);
+        }
+        Ok(())
+    }
+
+    /// Returns the parity encoded in `v`, or `None` if `v` is not a valid legacy, [EIP-155] or
+    /// typed transaction value.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    fn v_parity(&self) -> Option<bool> {
+        let v = u64::try_from(self.v).ok()?;
+        match v {
+            0 | 1 => Some(v == 1),
+            27 | 28 => Some(v == 28),
+            35.. => Some((v - 35) % 2 == 1),
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<Signature> for alloy_primitives::Signature {
     type Error = alloy_primitives::SignatureError;
 
@@ -206,4 +282,64 @@ mod tests {
         let parity: Result<Parity, _> = serde_json::from_str(raw_parity);
         assert!(parity.is_err());
     }
+
+    #[test]
+    fn normalize_high_s() {
+        let signature = Signature {
+            r: U256::from(1),
+            s: SECP256K1N - U256::from(2),
+            v: U256::from(37),
+            y_parity: None,
+        };
+        assert!(!signature.is_low_s());
+
+        let normalized = signature.normalize_s().unwrap();
+        assert!(normalized.is_low_s());
+        assert_eq!(normalized.s, U256::from(2));
+        assert_eq!(normalized.v, U256::from(38));
+        assert_eq!(normalized.normalize_s().unwrap(), normalized);
+
+        let typed = Signature { v: U256::from(1), y_parity: Some(Parity(true)), ..signature };
+        let normalized = typed.normalize_s().unwrap();
+        assert_eq!((normalized.v, normalized.y_parity), (U256::ZERO, Some(Parity(false))));
+    }
+
+    #[test]
+    fn normalize_rejects_invalid_v() {
+        let high = Signature {
+            r: U256::from(1),
+            s: SECP256K1N - U256::from(2),
+            v: U256::from(29),
+            y_parity: None,
+        };
+        assert!(matches!(high.normalize_s(), Err(ConversionError::InvalidV(v)) if v == high.v));
+
+        // An invalid `v` is reported even if `s` is already low.
+        let low = Signature { s: U256::from(2), ..high };
+        assert!(matches!(low.normalize_s(), Err(ConversionError::InvalidV(_))));
+    }
+
+    #[test]
+    fn validate_v_for_tx_type() {
+        let signature = |v: u64, y_parity: Option<bool>| Signature {
+            v: U256::from(v),
+            y_parity: y_parity.map(Parity),
+            ..Default::default()
+        };
+
+        assert!(signature(27, None).validate_for(TxType::Legacy).is_ok());
+        assert!(signature(28, Some(true)).validate_for(TxType::Legacy).is_ok());
+        assert!(signature(38, None).validate_for(TxType::Legacy).is_ok());
+        assert!(signature(1, None).validate_for(TxType::Legacy).is_err());
+        assert!(signature(29, None).validate_for(TxType::Legacy).is_err());
+        assert!(signature(34, None).validate_for(TxType::Legacy).is_err());
+
+        assert!(signature(1, Some(true)).validate_for(TxType::Eip1559).is_ok());
+        assert!(signature(0, None).validate_for(TxType::Eip4844).is_ok());
+        assert!(signature(27, None).validate_for(TxType::Eip2930).is_err());
+        assert!(matches!(
+            signature(0, Some(true)).validate_for(TxType::Eip1559),
+            Err(ConversionError::YParityMismatch)
+        ));
+    }
 }
@@ -1,4 +1,4 @@
-use alloy_primitives::{B256, U256};
+use alloy_primitives::{B256, U256, U64};
 use serde::{
     de::{Error, SeqAccess, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
@@ -59,7 +59,7 @@ impl<'a> Deserialize<'a> for Work {
                 let target = seq
                     .next_element::<B256>()?
                     .ok_or_else(|| A::Error::custom("missing target"))?;
-                let number = seq.next_element::<u64>()?;
+                let number = seq.next_element::<U64>()?.map(|number| number.to());
                 Ok(Work { pow_hash, seed_hash, target, number })
             }
         }
@@ -67,3 +67,25 @@ impl<'a> Deserialize<'a> for Work {
         deserializer.deserialize_any(WorkVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_work() {
+        let work = Work {
+            pow_hash: B256::with_last_byte(1),
+            seed_hash: B256::with_last_byte(2),
+            target: B256::with_last_byte(3),
+            number: Some(16),
+        };
+        let json = serde_json::to_value(work).unwrap();
+        assert_eq!(json[3], "0x10");
+        assert_eq!(serde_json::from_value::<Work>(json.clone()).unwrap(), work);
+
+        let mut json = json;
+        json[3] = 16.into();
+        assert_eq!(serde_json::from_value::<Work>(json).unwrap(), work);
+    }
+}
@@ -23,7 +23,7 @@ pub struct EthCallBundle {
     pub timestamp: Option<u64>,
     /// gas limit of the block to use for this simulation
     #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<u64>,
+    pub gas_limit: Option<u128>,
     /// difficulty of the block to use for this simulation
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub difficulty: Option<U256>,
@@ -57,7 +57,7 @@ pub struct EthCallBundleResponse {
     pub state_block_number: u64,
     /// The total gas used by all transactions in the bundle
     #[serde(with = "alloy_serde::quantity")]
-    pub total_gas_used: u64,
+    pub total_gas_used: u128,
 }
 
 /// Result of a single transaction in a bundle for `eth_callBundle`
@@ -80,7 +80,7 @@ pub struct EthCallBundleTransactionResult {
     pub gas_price: U256,
     /// The amount of gas used by the transaction
     #[serde(with = "alloy_serde::quantity")]
-    pub gas_used: u64,
+    pub gas_used: u128,
     /// The address to which the transaction is sent (optional)
     pub to_address: Option<Address>,
     /// The transaction hash
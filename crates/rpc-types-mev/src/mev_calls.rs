@@ -116,10 +116,10 @@ pub struct SimBundleOverrides {
     pub timestamp: Option<u64>,
     /// Gas limit used for simulation, defaults to parentBlock.gasLimit
     #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<u64>,
+    pub gas_limit: Option<u128>,
     /// Base fee used for simulation, defaults to parentBlock.baseFeePerGas
     #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
-    pub base_fee: Option<u64>,
+    pub base_fee: Option<u128>,
     /// Timeout in seconds, defaults to 5
     #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
@@ -148,7 +148,7 @@ pub struct SimBundleResponse {
     pub refundable_value: u64,
     /// The gas used by the simulated block.
     #[serde(with = "alloy_serde::quantity")]
-    pub gas_used: u64,
+    pub gas_used: u128,
     /// Logs returned by `mev_simBundle`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub logs: Option<Vec<SimBundleLogs>>,
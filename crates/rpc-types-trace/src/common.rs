@@ -72,6 +72,81 @@ impl<Ok, Err> TraceResult<Ok, Err> {
     pub const fn new_error(error: Err, tx_hash: Option<TxHash>) -> Self {
         Self::Error { error, tx_hash }
     }
+
+    /// Converts the trace result into a [`Result`] of the trace or the tracer error.
+    pub fn into_result(self) -> Result<Ok, Err> {
+        match self {
+            Self::Success { result, .. } => Ok(result),
+            Self::Error { error, .. } => Err(error),
+        }
+    }
+}
+
+/// An error joining the trace results of a block to its transactions, see [`join_tx_hashes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum TraceJoinError {
+    /// The number of trace results is not the number of transactions.
+    #[error("expected {expected} trace results, got {got}")]
+    CountMismatch {
+        /// The number of transactions.
+        expected: usize,
+        /// The number of trace results.
+        got: usize,
+    },
+    /// A trace result carries the hash of another transaction than the one at its index.
+    #[error("trace result {index} is for transaction {got}, expected {expected}")]
+    HashMismatch {
+        /// The index of the trace result.
+        index: usize,
+        /// The hash of the transaction at the index.
+        expected: TxHash,
+        /// The hash carried by the trace result.
+        got: TxHash,
+    },
+}
+
+/// Joins the trace results of a block, as returned by `debug_traceBlockByNumber` and
+/// `debug_traceBlockByHash`, to the hashes of the transactions of the block, by index.
+///
+/// Returns the hash of each transaction along with its trace result, which is either its trace
+/// or the error of the tracer for this transaction. Results which carry a transaction hash are
+/// checked against the hash of the transaction at their index.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_primitives::TxHash;
+/// use alloy_rpc_types_trace::{common::join_tx_hashes, geth::TraceResult};
+///
+/// let results = vec![TraceResult::new_error("execution timeout".to_string(), None)];
+/// let traces = join_tx_hashes(results, [TxHash::ZERO]).unwrap();
+/// let (tx_hash, result) = traces.into_iter().next().unwrap();
+/// assert_eq!(tx_hash, TxHash::ZERO);
+/// assert_eq!(result.into_result().unwrap_err(), "execution timeout");
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn join_tx_hashes<Ok, Err>(
+    results: Vec<TraceResult<Ok, Err>>,
+    tx_hashes: impl IntoIterator<Item = TxHash>,
+) -> Result<Vec<(TxHash, TraceResult<Ok, Err>)>, TraceJoinError> {
+    let tx_hashes = tx_hashes.into_iter().collect::<Vec<_>>();
+    if results.len() != tx_hashes.len() {
+        return Err(TraceJoinError::CountMismatch {
+            expected: tx_hashes.len(),
+            got: results.len(),
+        });
+    }
+    tx_hashes
+        .into_iter()
+        .zip(results)
+        .enumerate()
+        .map(|(index, (expected, result))| match result.tx_hash() {
+            Some(got) if got != expected => {
+                Err(TraceJoinError::HashMismatch { index, expected, got })
+            }
+            _ => Ok((expected, result)),
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -110,4 +185,29 @@ mod tests {
         assert_eq!(error_result.success(), None);
         assert_eq!(error_result.error(), Some(&ErrResult { code: 404 }));
     }
+
+    #[test]
+    fn join_results_to_tx_hashes() {
+        let hashes = [TxHash::with_last_byte(1), TxHash::with_last_byte(2)];
+        let ok = || OkResult { message: "ok".to_string() };
+        let results: Vec<TraceResult<OkResult, ErrResult>> = vec![
+            TraceResult::new_success(ok(), None),
+            TraceResult::new_error(ErrResult { code: 1 }, Some(hashes[1])),
+        ];
+
+        let joined = join_tx_hashes(results.clone(), hashes).unwrap();
+        assert_eq!(joined, [(hashes[0], results[0].clone()), (hashes[1], results[1].clone())]);
+        assert_eq!(
+            joined.into_iter().map(|(_, result)| result.into_result()).collect::<Vec<_>>(),
+            [Ok(ok()), Err(ErrResult { code: 1 })]
+        );
+        assert_eq!(
+            join_tx_hashes(results.clone(), [hashes[0]]),
+            Err(TraceJoinError::CountMismatch { expected: 1, got: 2 })
+        );
+        assert_eq!(
+            join_tx_hashes(results, [hashes[0], hashes[0]]),
+            Err(TraceJoinError::HashMismatch { index: 1, expected: hashes[0], got: hashes[1] })
+        );
+    }
 }
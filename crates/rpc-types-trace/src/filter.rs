@@ -1,7 +1,10 @@
 //! `trace_filter` types and support
 use alloy_primitives::Address;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    ops::{Bound, RangeBounds},
+};
 
 /// Trace filter.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,6 +35,11 @@ pub struct TraceFilter {
 // === impl TraceFilter ===
 
 impl TraceFilter {
+    /// Returns a [`TraceFilterBuilder`].
+    pub fn builder() -> TraceFilterBuilder {
+        TraceFilterBuilder::default()
+    }
+
     /// Sets the `from_block` field of the struct
     pub const fn from_block(mut self, block: u64) -> Self {
         self.from_block = Some(block);
@@ -82,6 +90,113 @@ impl TraceFilter {
     }
 }
 
+/// Builder for a [`TraceFilter`].
+///
+/// Addresses are added one at a time or in batches, ignoring duplicates, and the block range can
+/// be given as a Rust range.
+///
+/// ```
+/// use alloy_primitives::Address;
+/// use alloy_rpc_types_trace::filter::TraceFilter;
+///
+/// let filter = TraceFilter::builder()
+///     .from_address(Address::ZERO)
+///     .to_addresses([Address::ZERO, Address::repeat_byte(1)])
+///     .block_range(100..200)
+///     .count(10)
+///     .build();
+/// assert_eq!((filter.from_block, filter.to_block), (Some(100), Some(199)));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct TraceFilterBuilder {
+    filter: TraceFilter,
+}
+
+impl TraceFilterBuilder {
+    /// Adds an address to the `from_address` set.
+    pub fn from_address(mut self, address: Address) -> Self {
+        if !self.filter.from_address.contains(&address) {
+            self.filter.from_address.push(address);
+        }
+        self
+    }
+
+    /// Adds addresses to the `from_address` set.
+    pub fn from_addresses(self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        addresses.into_iter().fold(self, Self::from_address)
+    }
+
+    /// Adds an address to the `to_address` set.
+    pub fn to_address(mut self, address: Address) -> Self {
+        if !self.filter.to_address.contains(&address) {
+            self.filter.to_address.push(address);
+        }
+        self
+    }
+
+    /// Adds addresses to the `to_address` set.
+    pub fn to_addresses(self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        addresses.into_iter().fold(self, Self::to_address)
+    }
+
+    /// Sets how the `from_address` and `to_address` sets are combined.
+    pub const fn mode(mut self, mode: TraceFilterMode) -> Self {
+        self.filter.mode = mode;
+        self
+    }
+
+    /// Sets the first block to trace.
+    pub const fn from_block(mut self, block: u64) -> Self {
+        self.filter.from_block = Some(block);
+        self
+    }
+
+    /// Sets the last block to trace.
+    pub const fn to_block(mut self, block: u64) -> Self {
+        self.filter.to_block = Some(block);
+        self
+    }
+
+    /// Sets the range of blocks to trace.
+    pub fn block_range(mut self, range: impl RangeBounds<u64>) -> Self {
+        self.filter.from_block = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => Some(start.saturating_add(1)),
+            Bound::Unbounded => None,
+        };
+        self.filter.to_block = match range.end_bound() {
+            Bound::Included(&end) => Some(end),
+            Bound::Excluded(&end) => Some(end.saturating_sub(1)),
+            Bound::Unbounded => None,
+        };
+        self
+    }
+
+    /// Sets the number of matching traces to skip.
+    pub const fn after(mut self, after: u64) -> Self {
+        self.filter.after = Some(after);
+        self
+    }
+
+    /// Sets the maximum number of traces to return.
+    pub const fn count(mut self, count: u64) -> Self {
+        self.filter.count = Some(count);
+        self
+    }
+
+    /// Returns the built [`TraceFilter`].
+    pub fn build(self) -> TraceFilter {
+        self.filter
+    }
+}
+
+impl From<TraceFilterBuilder> for TraceFilter {
+    fn from(builder: TraceFilterBuilder) -> Self {
+        builder.build()
+    }
+}
+
 /// How to apply `from_address` and `to_address` filters.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -135,6 +250,34 @@ mod tests {
         assert_eq!(filter.to_block, Some(5));
     }
 
+    #[test]
+    fn test_filter_builder() {
+        let a = Address::with_last_byte(1);
+        let b = Address::with_last_byte(2);
+        let filter = TraceFilter::builder()
+            .from_addresses([a, b, a])
+            .to_address(b)
+            .block_range(..=5)
+            .after(10)
+            .count(20)
+            .build();
+        assert_eq!(filter.from_address, [a, b]);
+        assert_eq!(filter.to_address, [b]);
+        assert_eq!((filter.from_block, filter.to_block), (None, Some(5)));
+        assert_eq!(
+            serde_json::to_value(&filter).unwrap(),
+            json!({
+                "fromBlock": null,
+                "toBlock": "0x5",
+                "fromAddress": [a, b],
+                "toAddress": [b],
+                "mode": "union",
+                "after": 10,
+                "count": 20
+            })
+        );
+    }
+
     #[test]
     fn test_filter_matcher_addresses_unspecified() {
         let test_addr_d8 = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
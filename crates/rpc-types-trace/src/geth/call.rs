@@ -1,5 +1,6 @@
 //! Geth call tracer types.
 
+use alloy_eips::InMemorySize;
 use alloy_primitives::{Address, Bytes, B256, U256};
 use serde::{Deserialize, Serialize};
 
@@ -11,11 +12,11 @@ pub struct CallFrame {
     /// The address of that initiated the call.
     pub from: Address,
     /// How much gas was left before the call.
-    #[serde(default)]
-    pub gas: U256,
+    #[serde(default, with = "alloy_serde::quantity")]
+    pub gas: u128,
     /// How much gas was used by the call.
-    #[serde(default, rename = "gasUsed")]
-    pub gas_used: U256,
+    #[serde(default, rename = "gasUsed", with = "alloy_serde::quantity")]
+    pub gas_used: u128,
     /// The address of the contract that was called.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub to: Option<Address>,
@@ -85,10 +86,30 @@ impl CallConfig {
     }
 }
 
+impl InMemorySize for CallFrame {
+    fn heap_size(&self) -> usize {
+        self.input.heap_size()
+            + self.output.heap_size()
+            + self.error.heap_size()
+            + self.revert_reason.heap_size()
+            + self.calls.heap_size()
+            + self.logs.heap_size()
+            + self.typ.heap_size()
+    }
+}
+
+impl InMemorySize for CallLogFrame {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.topics.heap_size() + self.data.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::geth::*;
+    use alloy_rpc_types_eth::test_utils::check_roundtrip;
 
     // See <https://github.com/ethereum/go-ethereum/tree/master/eth/tracers/internal/tracetest/testdata>
     const DEFAULT: &str = include_str!("../../test_data/call_tracer/default.json");
@@ -120,4 +141,12 @@ mod tests {
         let _trace: CallFrame = serde_json::from_str(ONLY_TOP_CALL).unwrap();
         let _trace: CallFrame = serde_json::from_str(WITH_LOG).unwrap();
     }
+
+    #[test]
+    fn roundtrip_call_trace() {
+        // The legacy tracer omits the gas of selfdestructs, which is restored as zero.
+        for trace in [DEFAULT, ONLY_TOP_CALL, WITH_LOG] {
+            check_roundtrip::<CallFrame>(trace).unwrap();
+        }
+    }
 }
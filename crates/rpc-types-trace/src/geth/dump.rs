@@ -0,0 +1,204 @@
+//! Geth world state dump types, returned by `debug_dumpBlock` and `debug_accountRange`.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// The world state at a block, as returned by `debug_dumpBlock`.
+///
+/// <https://github.com/ethereum/go-ethereum/blob/v1.14.5/core/state/dump.go#L67-L71>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDump {
+    /// The state root.
+    pub root: B256,
+    /// The accounts, keyed by their address, or by `pre(<hash>)` if the node does not know the
+    /// preimage of their hashed address.
+    pub accounts: BTreeMap<String, DumpAccount>,
+}
+
+impl StateDump {
+    /// Returns the account with the given address, if any.
+    pub fn account(&self, address: Address) -> Option<&DumpAccount> {
+        self.accounts.values().find(|account| account.address == Some(address))
+    }
+}
+
+/// A page of the world state at a block, as returned by `debug_accountRange`.
+///
+/// <https://github.com/ethereum/go-ethereum/blob/v1.14.5/core/state/dump.go#L113-L118>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRangeDump {
+    /// The state root.
+    pub root: B256,
+    /// The accounts of the page, keyed as in [`StateDump::accounts`].
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// The hashed address to start the next page from, or `None` if this is the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "base64_bytes")]
+    pub next: Option<Bytes>,
+}
+
+/// An account of a world state dump.
+///
+/// <https://github.com/ethereum/go-ethereum/blob/v1.14.5/core/state/dump.go#L54-L65>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// The balance of the account, in decimal.
+    #[serde(with = "decimal")]
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The storage root of the account.
+    pub root: B256,
+    /// The code hash of the account.
+    pub code_hash: B256,
+    /// The code of the account, unless omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The storage of the account, unless omitted.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty", with = "storage")]
+    pub storage: BTreeMap<B256, U256>,
+    /// The address of the account, if the node knows the preimage of its hashed address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The hashed address of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "key")]
+    pub address_hash: Option<B256>,
+}
+
+/// Balances are dumped as decimal strings.
+mod decimal {
+    use super::*;
+    use serde::de::Error;
+
+    pub(super) fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(D::Error::custom)
+    }
+}
+
+/// Storage values are dumped as the hex of their minimal big-endian bytes, without a `0x` prefix.
+mod storage {
+    use super::*;
+    use serde::de::Error;
+
+    pub(super) fn serialize<S: Serializer>(
+        storage: &BTreeMap<B256, U256>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(storage.iter().map(|(slot, value)| (slot, format!("{value:x}"))))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<B256, U256>, D::Error> {
+        BTreeMap::<B256, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(slot, value)| {
+                let value = value.strip_prefix("0x").unwrap_or(&value);
+                Ok((slot, U256::from_str_radix(value, 16).map_err(D::Error::custom)?))
+            })
+            .collect()
+    }
+}
+
+/// Go encodes byte slices as base64 strings.
+mod base64_bytes {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::de::Error;
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Option<Bytes>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_str(&STANDARD.encode(value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Bytes>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| STANDARD.decode(value).map(Into::into).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, bytes};
+
+    #[test]
+    fn deserialize_state_dump() {
+        let s = r#"{
+            "root": "0xa8a41b1dfaa79ab3cbb2b8fdcb34eac9e5d5d1f5d1e2bd4a8cbb3d7e4a0d3a6c",
+            "accounts": {
+                "0x000000000000000000000000000000000000dEaD": {
+                    "balance": "1000000000000000000",
+                    "nonce": 1,
+                    "root": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+                    "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+                    "address": "0x000000000000000000000000000000000000dead",
+                    "key": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                },
+                "pre(0x0000000000000000000000000000000000000000000000000000000000000002)": {
+                    "balance": "0",
+                    "nonce": 0,
+                    "root": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+                    "codeHash": "0x0000000000000000000000000000000000000000000000000000000000000003",
+                    "code": "0x6080",
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000": "2a"
+                    },
+                    "key": "0x0000000000000000000000000000000000000000000000000000000000000002"
+                }
+            }
+        }"#;
+        let dump: StateDump = serde_json::from_str(s).unwrap();
+
+        let dead = dump.account(address!("000000000000000000000000000000000000dEaD")).unwrap();
+        assert_eq!(dead.balance, U256::from(10).pow(U256::from(18)));
+        assert_eq!(dead.nonce, 1);
+        assert_eq!(dead.code, None);
+
+        let contract = &dump.accounts
+            ["pre(0x0000000000000000000000000000000000000000000000000000000000000002)"];
+        assert_eq!(contract.address, None);
+        assert_eq!(contract.code, Some(bytes!("6080")));
+        assert_eq!(contract.storage[&B256::ZERO], U256::from(42));
+
+        let json = serde_json::to_value(&dump).unwrap();
+        assert_eq!(serde_json::from_value::<StateDump>(json).unwrap(), dump);
+    }
+
+    #[test]
+    fn deserialize_account_range() {
+        let s = r#"{
+            "root": "0xa8a41b1dfaa79ab3cbb2b8fdcb34eac9e5d5d1f5d1e2bd4a8cbb3d7e4a0d3a6c",
+            "accounts": {},
+            "next": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQ="
+        }"#;
+        let range: AccountRangeDump = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            range.next,
+            Some(b256!("0000000000000000000000000000000000000000000000000000000000000004").into())
+        );
+        let json = serde_json::to_value(&range).unwrap();
+        assert_eq!(json["next"], "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQ=");
+
+        let range: AccountRangeDump =
+            serde_json::from_str(r#"{"root":"0x0000000000000000000000000000000000000000000000000000000000000000","accounts":{}}"#)
+                .unwrap();
+        assert_eq!(range.next, None);
+    }
+}
@@ -9,6 +9,7 @@ use std::{collections::BTreeMap, time::Duration};
 // re-exports
 pub use self::{
     call::{CallConfig, CallFrame, CallLogFrame},
+    dump::{AccountRangeDump, DumpAccount, StateDump},
     four_byte::FourByteFrame,
     noop::NoopFrame,
     pre_state::{
@@ -18,6 +19,7 @@ pub use self::{
 };
 
 pub mod call;
+pub mod dump;
 pub mod four_byte;
 pub mod mux;
 pub mod noop;
@@ -1,6 +1,7 @@
 //! Pre-state Geth tracer types.
 
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
+use alloy_rpc_types_eth::state::{AccountOverride, StateOverride};
 use serde::{Deserialize, Serialize};
 use std::collections::{btree_map, BTreeMap};
 
@@ -102,6 +103,82 @@ impl DiffMode {
             state.storage.retain(|_, value| *value != B256::ZERO);
         }
     }
+
+    /// Applies the changes of this diff onto the given [StateOverride], so that it describes the
+    /// state after the traced transaction was executed.
+    ///
+    /// Changed balances, nonces, code and storage slots of the post state are overridden. Storage
+    /// slots that are only present in the pre state of an account were cleared, and are
+    /// overridden with zero. Accounts that are only present in the pre state were selfdestructed,
+    /// and are overridden with an empty account.
+    ///
+    /// Storage slots are written to the `state` of an [AccountOverride] if it already overrides
+    /// the whole storage, and to its `state_diff` otherwise.
+    pub fn apply_to(&self, overrides: &mut StateOverride) {
+        for (address, post) in &self.post {
+            let account = overrides.entry(*address).or_default();
+            if let Some(balance) = post.balance {
+                account.balance = Some(balance);
+            }
+            if let Some(nonce) = post.nonce {
+                account.nonce = Some(U64::from(nonce));
+            }
+            if let Some(code) = &post.code {
+                account.code = Some(code.clone());
+            }
+
+            let cleared = self.pre.get(address).into_iter().flat_map(|pre| {
+                pre.storage.keys().filter(|slot| !post.storage.contains_key(*slot))
+            });
+            let mut changes = cleared
+                .map(|slot| (*slot, B256::ZERO))
+                .chain(post.storage.iter().map(|(slot, value)| (*slot, *value)))
+                .peekable();
+            if changes.peek().is_some() {
+                let storage = match &mut account.state {
+                    Some(state) => state,
+                    None => account.state_diff.get_or_insert_with(Default::default),
+                };
+                storage.extend(changes);
+            }
+        }
+
+        for address in self.pre.keys().filter(|address| !self.post.contains_key(*address)) {
+            overrides.insert(
+                *address,
+                AccountOverride {
+                    balance: Some(U256::ZERO),
+                    nonce: Some(U64::ZERO),
+                    code: Some(Bytes::new()),
+                    state: Some(Default::default()),
+                    state_diff: None,
+                },
+            );
+        }
+    }
+
+    /// Returns the [StateOverride] describing the state after the traced transaction was
+    /// executed.
+    ///
+    /// See [DiffMode::apply_to].
+    pub fn to_state_override(&self) -> StateOverride {
+        let mut overrides = StateOverride::default();
+        self.apply_to(&mut overrides);
+        overrides
+    }
+
+    /// Composes the given diffs, in execution order, into a single [StateOverride] describing the
+    /// state after all traced transactions were executed.
+    ///
+    /// This can be used to chain simulations, by passing the result as the state override of the
+    /// next call.
+    pub fn compose<'a>(diffs: impl IntoIterator<Item = &'a Self>) -> StateOverride {
+        let mut overrides = StateOverride::default();
+        for diff in diffs {
+            diff.apply_to(&mut overrides);
+        }
+        overrides
+    }
 }
 
 /// Helper type for [DiffMode] to represent a specific set
@@ -231,6 +308,7 @@ impl PreStateConfig {
 mod tests {
     use super::*;
     use crate::geth::*;
+    use alloy_primitives::address;
 
     // See <https://github.com/ethereum/go-ethereum/tree/master/eth/tracers/internal/tracetest/testdata>
     const DEFAULT: &str = include_str!("../../test_data/pre_state_tracer/default.json");
@@ -350,4 +428,68 @@ mod tests {
         assert!(diff_changed.post.is_empty());
         assert!(diff_changed.pre.is_empty());
     }
+
+    #[test]
+    fn test_diff_to_state_override() {
+        let diff: DiffMode = serde_json::from_str(DIFF_MODE).unwrap();
+        let overrides = diff.to_state_override();
+        assert_eq!(overrides.len(), 4);
+
+        let sender = &overrides[&address!("b436ba50d378d4bbc8660d312a13df6af6e89dfb")];
+        assert_eq!(sender.balance, Some(U256::from(0x1780d7725724a9044b75u128)));
+        assert_eq!(sender.nonce, Some(U64::from(29073)));
+        assert_eq!(sender.code, None);
+        assert_eq!(sender.state_diff, None);
+
+        let contract = &overrides[&address!("3b873a919aa0512d5a0f09e6dcceaa4a6727fafe")];
+        assert_eq!(contract.code, None);
+        assert_eq!(contract.state, None);
+        let state_diff = contract.state_diff.as_ref().unwrap();
+        assert_eq!(state_diff.len(), 1);
+        assert_eq!(state_diff[&B256::with_last_byte(3)], B256::from(U256::from(0x5a37b95eu64)));
+    }
+
+    #[test]
+    fn test_compose_diffs() {
+        let contract = Address::with_last_byte(1);
+        let account = |storage: &[(u8, u8)]| AccountState {
+            storage: storage
+                .iter()
+                .map(|(slot, value)| (B256::with_last_byte(*slot), B256::with_last_byte(*value)))
+                .collect(),
+            ..Default::default()
+        };
+
+        // The first transaction writes slot 1 and clears slot 2.
+        let first = DiffMode {
+            pre: BTreeMap::from([(contract, account(&[(1, 1), (2, 2)]))]),
+            post: BTreeMap::from([(contract, account(&[(1, 3)]))]),
+        };
+        let overrides = DiffMode::compose([&first]);
+        let state_diff = overrides[&contract].state_diff.as_ref().unwrap();
+        assert_eq!(state_diff[&B256::with_last_byte(1)], B256::with_last_byte(3));
+        assert_eq!(state_diff[&B256::with_last_byte(2)], B256::ZERO);
+
+        // The second transaction selfdestructs the contract, and the third one recreates it.
+        let second = DiffMode {
+            pre: BTreeMap::from([(contract, account(&[(1, 3)]))]),
+            post: BTreeMap::new(),
+        };
+        let third = DiffMode {
+            pre: BTreeMap::new(),
+            post: BTreeMap::from([(
+                contract,
+                AccountState { nonce: Some(1), ..account(&[(4, 4)]) },
+            )]),
+        };
+        let overrides = DiffMode::compose([&first, &second, &third]);
+        let recreated = &overrides[&contract];
+        assert_eq!(recreated.balance, Some(U256::ZERO));
+        assert_eq!(recreated.nonce, Some(U64::from(1)));
+        assert_eq!(recreated.code, Some(Bytes::new()));
+        assert_eq!(recreated.state_diff, None);
+        let state = recreated.state.as_ref().unwrap();
+        assert_eq!(state.len(), 1);
+        assert_eq!(state[&B256::with_last_byte(4)], B256::with_last_byte(4));
+    }
 }
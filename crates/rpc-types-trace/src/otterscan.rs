@@ -128,7 +128,7 @@ pub struct OtsReceipt {
     pub status: bool,
     /// The cumulative gas used.
     #[serde(with = "alloy_serde::quantity")]
-    pub cumulative_gas_used: u64,
+    pub cumulative_gas_used: u128,
     /// The logs sent from contracts.
     ///
     /// Note: this is set to null.
@@ -2,6 +2,7 @@
 //!
 //! See <https://openethereum.github.io/JSONRPC-trace-module>
 
+use alloy_eips::InMemorySize;
 use alloy_primitives::{Address, BlockHash, Bytes, TxHash, B256, U256, U64};
 use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use std::{
@@ -47,7 +48,7 @@ impl TraceResults {
     /// The root trace's gasUsed should mirror the actual gas used by the transaction.
     ///
     /// This allows setting it manually by consuming the execution result's gas for example.
-    pub fn set_root_trace_gas_used(&mut self, gas_used: u64) {
+    pub fn set_root_trace_gas_used(&mut self, gas_used: u128) {
         if let Some(r) = self.trace.first_mut().and_then(|t| t.result.as_mut()) {
             r.set_gas_used(gas_used)
         }
@@ -288,7 +289,8 @@ pub struct CallAction {
     /// The type of the call.
     pub call_type: CallType,
     /// The gas available for executing the call.
-    pub gas: U64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u128,
     /// The input data provided to the call.
     pub input: Bytes,
     /// Address of the destination/target account.
@@ -304,7 +306,8 @@ pub struct CreateAction {
     /// The address of the creator.
     pub from: Address,
     /// The gas available for the creation init code.
-    pub gas: U64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u128,
     /// The init code.
     pub init: Bytes,
     /// The value with which the new account is endowed.
@@ -350,7 +353,8 @@ pub struct SelfdestructAction {
 #[serde(rename_all = "camelCase")]
 pub struct CallOutput {
     /// Gas used by the call.
-    pub gas_used: U64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u128,
     /// The output data of the call.
     pub output: Bytes,
 }
@@ -364,7 +368,8 @@ pub struct CreateOutput {
     /// Contract code.
     pub code: Bytes,
     /// Gas used by the call.
-    pub gas_used: U64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u128,
 }
 
 /// Represents the output of a trace.
@@ -381,7 +386,7 @@ pub enum TraceOutput {
 
 impl TraceOutput {
     /// Returns the gas used by this trace.
-    pub const fn gas_used(&self) -> U64 {
+    pub const fn gas_used(&self) -> u128 {
         match self {
             Self::Call(call) => call.gas_used,
             Self::Create(create) => create.gas_used,
@@ -389,10 +394,10 @@ impl TraceOutput {
     }
 
     /// Sets the gas used by this trace.
-    pub fn set_gas_used(&mut self, gas_used: u64) {
+    pub fn set_gas_used(&mut self, gas_used: u128) {
         match self {
-            Self::Call(call) => call.gas_used = U64::from(gas_used),
-            Self::Create(create) => create.gas_used = U64::from(gas_used),
+            Self::Call(call) => call.gas_used = gas_used,
+            Self::Create(create) => create.gas_used = gas_used,
         }
     }
 }
@@ -569,6 +574,44 @@ pub struct StorageDelta {
     pub val: U256,
 }
 
+impl InMemorySize for Action {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::Call(call) => call.input.heap_size(),
+            Self::Create(create) => create.init.heap_size(),
+            Self::Selfdestruct(_) | Self::Reward(_) => 0,
+        }
+    }
+}
+
+impl InMemorySize for TraceOutput {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::Call(call) => call.output.heap_size(),
+            Self::Create(create) => create.code.heap_size(),
+        }
+    }
+}
+
+impl InMemorySize for TransactionTrace {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.action.heap_size()
+            + self.error.heap_size()
+            + self.result.heap_size()
+            + self.trace_address.heap_size()
+    }
+}
+
+impl InMemorySize for LocalizedTransactionTrace {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.trace.heap_size()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -646,13 +689,13 @@ mod tests {
                         action: Action::Call(CallAction {
                             from: "0x4f4495243837681061c4743b74b3eedf548d56a5".parse::<Address>().unwrap(),
                             call_type: CallType::DelegateCall,
-                            gas: U64::from(3148955),
+                            gas: 3148955,
                             input: Bytes::from_str("0x585a9fd40000000000000000000000000000000000000000000000000000000000000040a47c5ad9a4af285720eae6cc174a9c75c5bbaf973b00f1a0c191327445b6581000000000000000000000000000000000000000000000000000000000000001e000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000140000000000000000000000000ce16f69375520ab01377ce7b88f5ba8c48f8d666f61490331372e432315cd97447e3bc452d6c73a6e0536260a88ddab46f85c88d00000000000000000000000000000000000000000000000000000000000001a0000000000000000000000000000000000000000000000000000000000aab8cf0fbfb038751339cb61161fa11789b41a78f1b7b0e12cf8e467d403590b7a5f26f0000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000646616e746f6d0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a3078636531364636393337353532306162303133373763653742383866354241384334384638443636360000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000045553444300000000000000000000000000000000000000000000000000000000").unwrap(),
                             to:  "0x99b5fa03a5ea4315725c43346e55a6a6fbd94098".parse::<Address>().unwrap(),
                             value: U256::from(0),
                         }),
                         error: None,
-                        result: Some(TraceOutput::Call(CallOutput { gas_used: U64::from(32364), output: Bytes::new() })),
+                        result: Some(TraceOutput::Call(CallOutput { gas_used: 32364, output: Bytes::new() })),
                         subtraces: 0,
                         trace_address: vec![0, 10, 0],
                     },
@@ -692,12 +735,12 @@ mod tests {
                     trace: TransactionTrace {
                         action: Action::Create(CreateAction{
                             from: "0x4f4495243837681061c4743b74b3eedf548d56a5".parse::<Address>().unwrap(),
-                            gas: U64::from(3438907),
+                            gas: 3438907,
                             init: Bytes::from_str("0x6080604052600160005534801561001557600080fd5b50610324806100256000396000f3fe608060405234801561001057600080fd5b50600436106100355760003560e01c8062f55d9d1461003a5780631cff79cd1461004f575b600080fd5b61004d6100483660046101da565b610079565b005b61006261005d3660046101fc565b6100bb565b60405161007092919061027f565b60405180910390f35b6002600054141561009d5760405163caa30f5560e01b815260040160405180910390fd5b600260005573ffffffffffffffffffffffffffffffffffffffff8116ff5b60006060600260005414156100e35760405163caa30f5560e01b815260040160405180910390fd5b600260005573ffffffffffffffffffffffffffffffffffffffff85163b610136576040517f6f7c43f100000000000000000000000000000000000000000000000000000000815260040160405180910390fd5b8473ffffffffffffffffffffffffffffffffffffffff16848460405161015d9291906102de565b6000604051808303816000865af19150503d806000811461019a576040519150601f19603f3d011682016040523d82523d6000602084013e61019f565b606091505b50600160005590969095509350505050565b803573ffffffffffffffffffffffffffffffffffffffff811681146101d557600080fd5b919050565b6000602082840312156101ec57600080fd5b6101f5826101b1565b9392505050565b60008060006040848603121561021157600080fd5b61021a846101b1565b9250602084013567ffffffffffffffff8082111561023757600080fd5b818601915086601f83011261024b57600080fd5b81358181111561025a57600080fd5b87602082850101111561026c57600080fd5b6020830194508093505050509250925092565b821515815260006020604081840152835180604085015260005b818110156102b557858101830151858201606001528201610299565b818111156102c7576000606083870101525b50601f01601f191692909201606001949350505050565b818382376000910190815291905056fea264697066735822122032cb5e746816b7fac95205c068b30da37bd40119a57265be331c162cae74712464736f6c63430008090033").unwrap(),
                             value: U256::from(0),
                         }),
                         error: None,
-                        result: Some(TraceOutput::Create(CreateOutput { gas_used: U64::from(183114), address: "0x7eb6c6c1db08c0b9459a68cfdcedab64f319c138".parse::<Address>().unwrap(), code: Bytes::from_str("0x608060405234801561001057600080fd5b50600436106100355760003560e01c8062f55d9d1461003a5780631cff79cd1461004f575b600080fd5b61004d6100483660046101da565b610079565b005b61006261005d3660046101fc565b6100bb565b60405161007092919061027f565b60405180910390f35b6002600054141561009d5760405163caa30f5560e01b815260040160405180910390fd5b600260005573ffffffffffffffffffffffffffffffffffffffff8116ff5b60006060600260005414156100e35760405163caa30f5560e01b815260040160405180910390fd5b600260005573ffffffffffffffffffffffffffffffffffffffff85163b610136576040517f6f7c43f100000000000000000000000000000000000000000000000000000000815260040160405180910390fd5b8473ffffffffffffffffffffffffffffffffffffffff16848460405161015d9291906102de565b6000604051808303816000865af19150503d806000811461019a576040519150601f19603f3d011682016040523d82523d6000602084013e61019f565b606091505b50600160005590969095509350505050565b803573ffffffffffffffffffffffffffffffffffffffff811681146101d557600080fd5b919050565b6000602082840312156101ec57600080fd5b6101f5826101b1565b9392505050565b60008060006040848603121561021157600080fd5b61021a846101b1565b9250602084013567ffffffffffffffff8082111561023757600080fd5b818601915086601f83011261024b57600080fd5b81358181111561025a57600080fd5b87602082850101111561026c57600080fd5b6020830194508093505050509250925092565b821515815260006020604081840152835180604085015260005b818110156102b557858101830151858201606001528201610299565b818111156102c7576000606083870101525b50601f01601f191692909201606001949350505050565b818382376000910190815291905056fea264697066735822122032cb5e746816b7fac95205c068b30da37bd40119a57265be331c162cae74712464736f6c63430008090033").unwrap() })),
+                        result: Some(TraceOutput::Create(CreateOutput { gas_used: 183114, address: "0x7eb6c6c1db08c0b9459a68cfdcedab64f319c138".parse::<Address>().unwrap(), code: Bytes::from_str("0x608060405234801561001057600080fd5b50600436106100355760003560e01c8062f55d9d1461003a5780631cff79cd1461004f575b600080fd5b61004d6100483660046101da565b610079565b005b61006261005d3660046101fc565b6100bb565b60405161007092919061027f565b60405180910390f35b6002600054141561009d5760405163caa30f5560e01b815260040160405180910390fd5b600260005573ffffffffffffffffffffffffffffffffffffffff8116ff5b60006060600260005414156100e35760405163caa30f5560e01b815260040160405180910390fd5b600260005573ffffffffffffffffffffffffffffffffffffffff85163b610136576040517f6f7c43f100000000000000000000000000000000000000000000000000000000815260040160405180910390fd5b8473ffffffffffffffffffffffffffffffffffffffff16848460405161015d9291906102de565b6000604051808303816000865af19150503d806000811461019a576040519150601f19603f3d011682016040523d82523d6000602084013e61019f565b606091505b50600160005590969095509350505050565b803573ffffffffffffffffffffffffffffffffffffffff811681146101d557600080fd5b919050565b6000602082840312156101ec57600080fd5b6101f5826101b1565b9392505050565b60008060006040848603121561021157600080fd5b61021a846101b1565b9250602084013567ffffffffffffffff8082111561023757600080fd5b818601915086601f83011261024b57600080fd5b81358181111561025a57600080fd5b87602082850101111561026c57600080fd5b6020830194508093505050509250925092565b821515815260006020604081840152835180604085015260005b818110156102b557858101830151858201606001528201610299565b818111156102c7576000606083870101525b50601f01601f191692909201606001949350505050565b818382376000910190815291905056fea264697066735822122032cb5e746816b7fac95205c068b30da37bd40119a57265be331c162cae74712464736f6c63430008090033").unwrap() })),
                         subtraces: 0,
                         trace_address: vec![0, 7, 0, 0],
                     },
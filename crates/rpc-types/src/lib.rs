@@ -11,12 +11,18 @@ pub use alloy_serde as serde_helpers;
 #[cfg(feature = "admin")]
 pub use alloy_rpc_types_admin as admin;
 
+#[cfg(feature = "alchemy")]
+pub use alloy_rpc_types_alchemy as alchemy;
+
 #[cfg(feature = "anvil")]
 pub use alloy_rpc_types_anvil as anvil;
 
 #[cfg(feature = "beacon")]
 pub use alloy_rpc_types_beacon as beacon;
 
+#[cfg(feature = "clique")]
+pub use alloy_rpc_types_clique as clique;
+
 #[cfg(feature = "engine")]
 pub use alloy_rpc_types_engine as engine;
 
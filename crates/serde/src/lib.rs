@@ -36,7 +36,7 @@ pub mod ttd;
 pub use ttd::*;
 
 mod other;
-pub use other::{OtherFields, WithOtherFields};
+pub use other::{OtherFields, OtherFieldsError, WithOtherFields};
 
 /// Serialize a byte vec as a hex string _without_ the "0x" prefix.
 ///
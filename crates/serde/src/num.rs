@@ -3,14 +3,14 @@
 /// serde functions for handling `u8` via [U8](alloy_primitives::U8)
 pub mod u8_via_ruint {
     use alloy_primitives::U8;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     /// Deserializes an `u8` from [U8] accepting a hex quantity string with optional 0x prefix
     pub fn deserialize<'de, D>(deserializer: D) -> Result<u8, D::Error>
     where
         D: Deserializer<'de>,
     {
-        U8::deserialize(deserializer).map(|val| val.to())
+        crate::quantity::deserialize(deserializer)
     }
 
     /// Serializes u64 as hex string
@@ -22,7 +22,7 @@ pub mod u8_via_ruint {
 /// serde functions for handling `Option<u8>` via [U8](alloy_primitives::U8)
 pub mod u8_opt_via_ruint {
     use alloy_primitives::U8;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     /// Serializes u64 as hex string
     pub fn serialize<S: Serializer>(value: &Option<u8>, s: S) -> Result<S::Ok, S::Error> {
@@ -37,21 +37,21 @@ pub mod u8_opt_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        Ok(U8::deserialize(deserializer).map_or(None, |v| Some(u8::from_be_bytes(v.to_be_bytes()))))
+        Ok(crate::quantity::deserialize(deserializer).ok())
     }
 }
 
 /// serde functions for handling `u64` via [U64](alloy_primitives::U64)
 pub mod u64_via_ruint {
     use alloy_primitives::U64;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     /// Deserializes an `u64` from [U64] accepting a hex quantity string with optional 0x prefix
     pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
     where
         D: Deserializer<'de>,
     {
-        U64::deserialize(deserializer).map(|val| val.to())
+        crate::quantity::deserialize(deserializer)
     }
 
     /// Serializes u64 as hex string
@@ -63,7 +63,7 @@ pub mod u64_via_ruint {
 /// serde functions for handling `Option<u64>` via [U64](alloy_primitives::U64)
 pub mod u64_opt_via_ruint {
     use alloy_primitives::U64;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     /// Serializes u64 as hex string
     pub fn serialize<S: Serializer>(value: &Option<u64>, s: S) -> Result<S::Ok, S::Error> {
@@ -78,15 +78,14 @@ pub mod u64_opt_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        Ok(U64::deserialize(deserializer)
-            .map_or(None, |v| Some(u64::from_be_bytes(v.to_be_bytes()))))
+        Ok(crate::quantity::deserialize(deserializer).ok())
     }
 }
 
 /// serde functions for handling primitive `u128` via [U128](alloy_primitives::U128)
 pub mod u128_via_ruint {
     use alloy_primitives::U128;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     /// Deserializes an `u128` accepting a hex quantity string with optional 0x prefix or
     /// a number
@@ -94,7 +93,7 @@ pub mod u128_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        U128::deserialize(deserializer).map(|val| val.to())
+        crate::quantity::deserialize(deserializer)
     }
 
     /// Serializes u128 as hex string
@@ -106,7 +105,7 @@ pub mod u128_via_ruint {
 /// serde functions for handling primitive optional `u128` via [U128](alloy_primitives::U128)
 pub mod u128_opt_via_ruint {
     use alloy_primitives::U128;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     /// Deserializes an `Option<u128>` accepting a hex quantity string with optional 0x prefix or
     /// a number
@@ -114,8 +113,7 @@ pub mod u128_opt_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        Option::<U128>::deserialize(deserializer)?
-            .map_or_else(|| Ok(None), |val| Ok(Some(val.to())))
+        crate::quantity::opt::deserialize(deserializer)
     }
 
     /// Serializes `Option<u128>` as hex string
@@ -132,7 +130,7 @@ pub mod u128_vec_via_ruint {
     #[cfg(not(feature = "std"))]
     use alloc::vec::Vec;
     use alloy_primitives::U128;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     /// Deserializes an `u128` accepting a hex quantity string with optional 0x prefix or
     /// a number
@@ -140,8 +138,7 @@ pub mod u128_vec_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        let vec = Vec::<U128>::deserialize(deserializer)?;
-        Ok(vec.into_iter().map(|val| val.to()).collect())
+        crate::quantity::vec::deserialize(deserializer)
     }
 
     /// Serializes u128 as hex string
@@ -154,7 +151,7 @@ pub mod u128_vec_via_ruint {
 /// serde functions for handling `Vec<Vec<u128>>` via [U128](alloy_primitives::U128)
 pub mod u128_vec_vec_opt_via_ruint {
     use alloy_primitives::U128;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     #[cfg(not(feature = "std"))]
     use alloc::vec::Vec;
@@ -165,14 +162,7 @@ pub mod u128_vec_vec_opt_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        Option::<Vec<Vec<U128>>>::deserialize(deserializer)?.map_or_else(
-            || Ok(None),
-            |vec| {
-                Ok(Some(
-                    vec.into_iter().map(|v| v.into_iter().map(|val| val.to()).collect()).collect(),
-                ))
-            },
-        )
+        crate::quantity::u128_vec_vec_opt::deserialize(deserializer)
     }
 
     /// Serializes u128 as hex string
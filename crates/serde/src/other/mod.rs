@@ -11,11 +11,62 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 
 #[cfg(not(feature = "std"))]
-use alloc::string::String;
+use alloc::string::{String, ToString};
 
 #[cfg(any(test, feature = "arbitrary"))]
 mod arbitrary_;
 
+/// An error returned when accessing the fields of [`OtherFields`] as typed values.
+#[derive(Debug)]
+pub enum OtherFieldsError {
+    /// The field is missing.
+    Missing(String),
+    /// The field could not be deserialized.
+    Deserialize {
+        /// The key of the field.
+        key: String,
+        /// The deserialization error.
+        source: serde_json::Error,
+    },
+    /// The value could not be serialized.
+    Serialize {
+        /// The key of the field, if a single field was serialized.
+        key: Option<String>,
+        /// The serialization error.
+        source: serde_json::Error,
+    },
+    /// The value did not serialize to a JSON object, so it cannot be converted to fields.
+    NotAnObject,
+}
+
+impl fmt::Display for OtherFieldsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(key) => write!(f, "missing field `{key}`"),
+            Self::Deserialize { key, source } => {
+                write!(f, "failed to deserialize field `{key}`: {source}")
+            }
+            Self::Serialize { key: Some(key), source } => {
+                write!(f, "failed to serialize field `{key}`: {source}")
+            }
+            Self::Serialize { key: None, source } => {
+                write!(f, "failed to serialize fields: {source}")
+            }
+            Self::NotAnObject => f.write_str("fields must serialize to a JSON object"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OtherFieldsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deserialize { source, .. } | Self::Serialize { source, .. } => Some(source),
+            Self::Missing(_) | Self::NotAnObject => None,
+        }
+    }
+}
+
 /// Generic type for capturing additional fields when deserializing structs.
 ///
 /// For example, the [optimism `eth_getTransactionByHash` request][optimism] returns additional
@@ -26,6 +77,30 @@ mod arbitrary_;
 ///
 /// This type must be used with [`#[serde(flatten)]`][flatten].
 ///
+/// # Examples
+///
+/// The fields known to extend a type can be declared as a struct deriving [`Serialize`] and
+/// [`Deserialize`]:
+///
+/// ```
+/// use alloy_serde::OtherFields;
+///
+/// #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct L2Fields {
+///     #[serde(with = "alloy_serde::quantity")]
+///     l1_block_number: u64,
+/// }
+///
+/// let mut other = OtherFields::from_serialized(&L2Fields { l1_block_number: 1 })?;
+/// other.insert_serialized("sequencer", "0x01")?;
+///
+/// assert_eq!(other.deserialize_as::<L2Fields>()?, L2Fields { l1_block_number: 1 });
+/// assert_eq!(other.get_required::<String>("sequencer")?, "0x01");
+/// assert!(other.get_required::<String>("missing").is_err());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+///
 /// [optimism]: https://docs.alchemy.com/alchemy/apis/optimism/eth-gettransactionbyhash
 /// [flatten]: https://serde.rs/field-attrs.html#flatten
 #[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,6 +115,51 @@ impl OtherFields {
         Self { inner }
     }
 
+    /// Creates the fields from a value serializing to a JSON object, such as a struct deriving
+    /// [`Serialize`].
+    pub fn from_serialized<T: Serialize>(value: &T) -> Result<Self, OtherFieldsError> {
+        let mut fields = Self::default();
+        fields.extend_serialized(value)?;
+        Ok(fields)
+    }
+
+    /// Inserts the fields of a value serializing to a JSON object, such as a struct deriving
+    /// [`Serialize`], replacing the existing fields with the same keys.
+    pub fn extend_serialized<T: Serialize>(&mut self, value: &T) -> Result<(), OtherFieldsError> {
+        match serde_json::to_value(value)
+            .map_err(|source| OtherFieldsError::Serialize { key: None, source })?
+        {
+            Value::Object(map) => {
+                self.inner.extend(map);
+                Ok(())
+            }
+            _ => Err(OtherFieldsError::NotAnObject),
+        }
+    }
+
+    /// Inserts the serialized value of the field, returning the previous value, if any.
+    pub fn insert_serialized<V: Serialize>(
+        &mut self,
+        key: impl Into<String>,
+        value: V,
+    ) -> Result<Option<Value>, OtherFieldsError> {
+        let key = key.into();
+        match serde_json::to_value(value) {
+            Ok(value) => Ok(self.inner.insert(key, value)),
+            Err(source) => Err(OtherFieldsError::Serialize { key: Some(key), source }),
+        }
+    }
+
+    /// Inserts the serialized value of the field, and returns the fields.
+    pub fn with_serialized<V: Serialize>(
+        mut self,
+        key: impl Into<String>,
+        value: V,
+    ) -> Result<Self, OtherFieldsError> {
+        self.insert_serialized(key, value)?;
+        Ok(self)
+    }
+
     /// Deserialized this type into another container type.
     pub fn deserialize_as<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
         let map = self.inner.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
@@ -69,6 +189,33 @@ impl OtherFields {
         self.get_with(key, serde_json::from_value)
     }
 
+    /// Returns the deserialized value of the field, or an error if it is missing or cannot be
+    /// deserialized.
+    pub fn get_required<V: DeserializeOwned>(
+        &self,
+        key: impl AsRef<str>,
+    ) -> Result<V, OtherFieldsError> {
+        let key = key.as_ref();
+        let value =
+            self.inner.get(key).ok_or_else(|| OtherFieldsError::Missing(key.to_string()))?;
+        V::deserialize(value)
+            .map_err(|source| OtherFieldsError::Deserialize { key: key.to_string(), source })
+    }
+
+    /// Returns an iterator over the fields, with their values deserialized as `V`.
+    ///
+    /// Each field is deserialized independently, so an error on one field does not end the
+    /// iteration.
+    pub fn iter_deserialized<V: DeserializeOwned>(
+        &self,
+    ) -> impl Iterator<Item = (&str, Result<V, OtherFieldsError>)> + '_ {
+        self.inner.iter().map(|(key, value)| {
+            let value = V::deserialize(value)
+                .map_err(|source| OtherFieldsError::Deserialize { key: key.clone(), source });
+            (key.as_str(), value)
+        })
+    }
+
     /// Removes the deserialized value of the field, if it exists
     ///
     /// **Note:** this will also remove the value if deserializing it resulted in an error
@@ -188,6 +335,20 @@ impl<T> WithOtherFields<T> {
     pub fn new(inner: T) -> Self {
         Self { inner, other: Default::default() }
     }
+
+    /// Creates a new [`WithOtherFields`] instance, with the fields of the given extension.
+    ///
+    /// See [`OtherFields::from_serialized`].
+    pub fn with_extension<E: Serialize>(inner: T, extension: &E) -> Result<Self, OtherFieldsError> {
+        Ok(Self { inner, other: OtherFields::from_serialized(extension)? })
+    }
+
+    /// Deserializes the other fields as the given extension.
+    ///
+    /// See [`OtherFields::deserialize_as`].
+    pub fn extension<E: DeserializeOwned>(&self) -> serde_json::Result<E> {
+        self.other.deserialize_as()
+    }
 }
 
 impl<T> Deref for WithOtherFields<T> {
@@ -270,4 +431,42 @@ mod tests {
             OtherFields::new(BTreeMap::from_iter([("b".to_string(), serde_json::json!(2))]))
         );
     }
+
+    #[test]
+    fn typed_fields() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Extension {
+            a: u64,
+            b: String,
+        }
+
+        let other = OtherFields::from_serialized(&Extension { a: 1, b: "b".to_string() })
+            .unwrap()
+            .with_serialized("c", 3u64)
+            .unwrap();
+        assert_eq!(other.get_required::<u64>("a").unwrap(), 1);
+        assert_eq!(other.get_required::<u64>("c").unwrap(), 3);
+        assert!(matches!(
+            other.get_required::<u64>("d"),
+            Err(OtherFieldsError::Missing(key)) if key == "d"
+        ));
+        assert!(matches!(
+            other.get_required::<u64>("b"),
+            Err(OtherFieldsError::Deserialize { key, .. }) if key == "b"
+        ));
+        assert!(matches!(OtherFields::from_serialized(&1u64), Err(OtherFieldsError::NotAnObject)));
+
+        let numbers: Vec<_> = other
+            .iter_deserialized::<u64>()
+            .filter_map(|(key, value)| Some((key, value.ok()?)))
+            .collect();
+        assert_eq!(numbers, [("a", 1), ("c", 3)]);
+
+        let with_other =
+            WithOtherFields::with_extension((), &Extension { a: 1, b: "b".into() }).unwrap();
+        assert_eq!(
+            with_other.extension::<Extension>().unwrap(),
+            Extension { a: 1, b: "b".to_string() }
+        );
+    }
 }
@@ -7,9 +7,138 @@
 //! This is only valid for human-readable [`serde`] implementations.
 //! For non-human-readable implementations, the format is unspecified.
 //! Currently, it uses a fixed-width big-endian byte-array.
+//!
+//! By default, quantities are deserialized leniently: anything the underlying integer types
+//! accept is allowed, such as leading zeros or decimal strings. The [`QuantityMode::Strict`] mode,
+//! set for the deserializations run by [`with_mode`], enforces the format above instead, for all
+//! the quantity helpers of this crate. This lets spec-compliance tooling validate the responses
+//! of a provider.
+//!
+//! The helpers handle both primitive integers and the [`Uint`](alloy_primitives::Uint) aliases,
+//! such as [`U64`](alloy_primitives::U64) and [`U256`](alloy_primitives::U256).
 
+use core::{fmt, marker::PhantomData, str::FromStr};
 use private::ConvertRuint;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{
+    de::{self, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// How strictly "quantity" hex strings are deserialized.
+///
+/// See [`quantity`](self) for more information.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum QuantityMode {
+    /// Accepts anything the underlying integer types accept, e.g. `0x01`, `0x`, `0xA` or `1`.
+    #[default]
+    Lenient,
+    /// Only accepts `0x`-prefixed lowercase hex strings without leading zeros, e.g. `0x0` or
+    /// `0x1a`.
+    Strict,
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static MODE: core::cell::Cell<QuantityMode> = const { core::cell::Cell::new(QuantityMode::Lenient) };
+}
+
+/// Returns the [`QuantityMode`] used to deserialize quantities on the current thread, set by
+/// [`with_mode`].
+pub fn mode() -> QuantityMode {
+    #[cfg(feature = "std")]
+    return MODE.with(core::cell::Cell::get);
+    #[cfg(not(feature = "std"))]
+    QuantityMode::Lenient
+}
+
+/// Runs `f`, deserializing quantities with the given [`QuantityMode`] in it, by all the helpers
+/// of this crate.
+///
+/// The mode only applies to the current thread until `f` returns, and only affects
+/// human-readable formats.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_primitives::U256;
+/// use alloy_serde::quantity::{self, QuantityMode};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Block {
+///     #[serde(with = "alloy_serde::quantity")]
+///     number: u64,
+///     #[serde(with = "alloy_serde::quantity")]
+///     difficulty: U256,
+/// }
+///
+/// let json = r#"{"number":"0x01","difficulty":"0x0"}"#;
+/// assert!(serde_json::from_str::<Block>(json).is_ok());
+///
+/// quantity::with_mode(QuantityMode::Strict, || {
+///     assert!(serde_json::from_str::<Block>(json).is_err());
+///     let json = r#"{"number":"0x1","difficulty":"0x00"}"#;
+///     assert!(serde_json::from_str::<Block>(json).is_err());
+/// });
+/// assert!(serde_json::from_str::<Block>(json).is_ok());
+/// ```
+#[cfg(feature = "std")]
+pub fn with_mode<T>(mode: QuantityMode, f: impl FnOnce() -> T) -> T {
+    /// Restores the previous mode, even if `f` panics.
+    struct Restore(QuantityMode);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            MODE.with(|mode| mode.set(self.0));
+        }
+    }
+
+    let _restore = Restore(MODE.with(|current| current.replace(mode)));
+    f()
+}
+
+/// A "quantity", deserialized according to the current [`QuantityMode`].
+pub(crate) struct Quantity<R>(pub(crate) R);
+
+impl<'de, R> Deserialize<'de> for Quantity<R>
+where
+    R: Deserialize<'de> + FromStr,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if mode() == QuantityMode::Strict && deserializer.is_human_readable() {
+            deserializer.deserialize_str(StrictVisitor(PhantomData)).map(Self)
+        } else {
+            R::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+/// Deserializes a quantity in the [`QuantityMode::Strict`] mode.
+struct StrictVisitor<R>(PhantomData<R>);
+
+impl<R: FromStr> Visitor<'_> for StrictVisitor<R> {
+    type Value = R;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a hex quantity string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        check_strict(value).map_err(|reason| E::invalid_value(Unexpected::Str(value), &reason))?;
+        value.parse().map_err(|_| E::invalid_value(Unexpected::Str(value), &"a quantity in range"))
+    }
+}
+
+/// Checks that the given string is a quantity in the [`QuantityMode::Strict`] mode, returning
+/// what was expected otherwise.
+fn check_strict(value: &str) -> Result<(), &'static str> {
+    let digits = value.strip_prefix("0x").ok_or("a 0x-prefixed quantity")?;
+    match digits.as_bytes() {
+        [] => Err("a quantity with at least one digit"),
+        [b'0', _, ..] => Err("a quantity without leading zeros"),
+        digits if digits.iter().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) => Ok(()),
+        _ => Err("a quantity of lowercase hex digits"),
+    }
+}
 
 /// Serializes a primitive number as a "quantity" hex string.
 pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
@@ -26,14 +155,14 @@ where
     T: ConvertRuint,
     D: Deserializer<'de>,
 {
-    T::Ruint::deserialize(deserializer).map(T::from_ruint)
+    Quantity::<T::Ruint>::deserialize(deserializer).map(|quantity| T::from_ruint(quantity.0))
 }
 
 /// Serde functions for encoding optional primitive numbers using the Ethereum "quantity" format.
 ///
 /// See [`quantity`](self) for more information.
 pub mod opt {
-    use super::private::ConvertRuint;
+    use super::{private::ConvertRuint, Quantity};
     use serde::{Deserialize, Deserializer, Serializer};
 
     /// Serializes an optional primitive number as a "quantity" hex string.
@@ -54,7 +183,8 @@ pub mod opt {
         T: ConvertRuint,
         D: Deserializer<'de>,
     {
-        Ok(Option::<T::Ruint>::deserialize(deserializer)?.map(T::from_ruint))
+        let value = Option::<Quantity<T::Ruint>>::deserialize(deserializer)?;
+        Ok(value.map(|quantity| T::from_ruint(quantity.0)))
     }
 }
 
@@ -62,7 +192,7 @@ pub mod opt {
 ///
 /// See [`quantity`](self) for more information.
 pub mod vec {
-    use super::private::ConvertRuint;
+    use super::{private::ConvertRuint, Quantity};
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
     #[cfg(not(feature = "std"))]
@@ -84,8 +214,8 @@ pub mod vec {
         T: ConvertRuint,
         D: Deserializer<'de>,
     {
-        let vec = Vec::<T::Ruint>::deserialize(deserializer)?;
-        Ok(vec.into_iter().map(T::from_ruint).collect())
+        let vec = Vec::<Quantity<T::Ruint>>::deserialize(deserializer)?;
+        Ok(vec.into_iter().map(|quantity| T::from_ruint(quantity.0)).collect())
     }
 }
 
@@ -98,6 +228,7 @@ mod private {
         type Ruint: Copy
             + serde::Serialize
             + serde::de::DeserializeOwned
+            + core::str::FromStr
             + TryFrom<Self>
             + TryInto<Self>;
 
@@ -129,11 +260,18 @@ mod private {
         u32  = alloy_primitives::U32,
         u64  = alloy_primitives::U64,
         u128 = alloy_primitives::U128,
+        alloy_primitives::U8   = alloy_primitives::U8,
+        alloy_primitives::U16  = alloy_primitives::U16,
+        alloy_primitives::U32  = alloy_primitives::U32,
+        alloy_primitives::U64  = alloy_primitives::U64,
+        alloy_primitives::U128 = alloy_primitives::U128,
+        alloy_primitives::U256 = alloy_primitives::U256,
     }
 }
 
 /// serde functions for handling `Vec<Vec<u128>>` via [U128](alloy_primitives::U128)
 pub mod u128_vec_vec_opt {
+    use super::Quantity;
     use alloy_primitives::U128;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -146,11 +284,13 @@ pub mod u128_vec_vec_opt {
     where
         D: Deserializer<'de>,
     {
-        Option::<Vec<Vec<U128>>>::deserialize(deserializer)?.map_or_else(
+        Option::<Vec<Vec<Quantity<U128>>>>::deserialize(deserializer)?.map_or_else(
             || Ok(None),
             |vec| {
                 Ok(Some(
-                    vec.into_iter().map(|v| v.into_iter().map(|val| val.to()).collect()).collect(),
+                    vec.into_iter()
+                        .map(|v| v.into_iter().map(|val| val.0.to()).collect())
+                        .collect(),
                 ))
             },
         )
@@ -176,7 +316,10 @@ pub mod u128_vec_vec_opt {
 
 #[cfg(test)]
 mod tests {
-    use serde::{Deserialize, Serialize};
+    use super::{check_strict, StrictVisitor};
+    use alloy_primitives::U64;
+    use core::marker::PhantomData;
+    use serde::{Deserialize, Deserializer, Serialize};
 
     #[cfg(not(feature = "std"))]
     use alloc::{string::ToString, vec, vec::Vec};
@@ -284,4 +427,57 @@ mod tests {
         let deserialized: Value = serde_json::from_str(&s).unwrap();
         assert_eq!(val, deserialized);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn scoped_mode() {
+        use super::{mode, with_mode, QuantityMode};
+        use alloy_primitives::U256;
+
+        #[derive(Debug, PartialEq, Eq, Deserialize)]
+        struct Value {
+            #[serde(with = "super")]
+            inner: U256,
+            #[serde(with = "super::opt")]
+            opt: Option<U64>,
+        }
+
+        let lenient = r#"{"inner":"0x01","opt":"0x02"}"#;
+        let value = Value { inner: U256::from(1), opt: Some(U64::from(2)) };
+        assert_eq!(serde_json::from_str::<Value>(lenient).unwrap(), value);
+
+        with_mode(QuantityMode::Strict, || {
+            assert_eq!(mode(), QuantityMode::Strict);
+            assert!(serde_json::from_str::<Value>(lenient).is_err());
+            assert!(serde_json::from_str::<Value>(r#"{"inner":"0x1","opt":"0x02"}"#).is_err());
+            let strict = r#"{"inner":"0x1","opt":"0x2"}"#;
+            assert_eq!(serde_json::from_str::<Value>(strict).unwrap(), value);
+
+            // Other threads are not affected.
+            std::thread::spawn(move || assert_eq!(mode(), QuantityMode::Lenient)).join().unwrap();
+        });
+        assert_eq!(mode(), QuantityMode::Lenient);
+        assert_eq!(serde_json::from_str::<Value>(lenient).unwrap(), value);
+    }
+
+    #[test]
+    fn strict_quantity() {
+        for valid in ["0x0", "0x1", "0x1a", "0xffffffffffffffff"] {
+            assert_eq!(check_strict(valid), Ok(()), "{valid}");
+        }
+        for invalid in ["0x", "0x00", "0x01", "0xA", "0X1", "1", "", "0x-1", "0x1_0"] {
+            assert!(check_strict(invalid).is_err(), "{invalid}");
+        }
+
+        let strict = |s: &str| {
+            serde_json::Deserializer::from_str(s)
+                .deserialize_str(StrictVisitor::<U64>(PhantomData))
+                .map_err(|err| err.to_string())
+        };
+        assert_eq!(strict(r#""0x3e8""#), Ok(U64::from(1000)));
+        assert!(strict(r#""0x03e8""#).unwrap_err().contains("without leading zeros"));
+        assert!(strict(r#""0x""#).unwrap_err().contains("at least one digit"));
+        assert!(strict(r#""0x10000000000000000""#).unwrap_err().contains("in range"));
+        assert!(strict("1000").is_err());
+    }
 }
@@ -0,0 +1,794 @@
+//! Signing with private keys stored in the credential store of the operating system.
+
+use crate::{LocalSignerError, PrivateKeySigner};
+use alloy_consensus::SignableTransaction;
+use alloy_network::TxSigner;
+use alloy_primitives::{hex, Address, ChainId, Signature, B256};
+use alloy_signer::{sign_transaction_with_chain_id, Result, Signer, SignerSync};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// The default time after which an unlocked [`KeychainSigner`] locks itself again.
+pub const DEFAULT_UNLOCK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Error thrown by a [`CredentialStore`] or a [`KeychainSigner`].
+#[derive(Debug, Error)]
+pub enum KeychainError {
+    /// The credential store holds no key for the service and account.
+    #[error("no key for account {account:?} of service {service:?} in the credential store")]
+    NotFound {
+        /// The service of the key.
+        service: String,
+        /// The account of the key.
+        account: String,
+    },
+    /// The [`UnlockPrompt`] of the signer denied the unlock.
+    #[error("unlock of the key of account {0:?} was denied")]
+    UnlockDenied(String),
+    /// The credential store holds an invalid key.
+    #[error("invalid key in the credential store: {0}")]
+    InvalidKey(#[from] LocalSignerError),
+    /// The credential store of the operating system failed.
+    #[error("credential store error: {0}")]
+    Store(String),
+    /// There is no supported credential store on this platform.
+    #[error("no credential store on this platform")]
+    Unsupported,
+    /// The signer cannot be configured because it was cloned.
+    #[error("the signer was cloned")]
+    Cloned,
+}
+
+/// A store of secrets, indexed by service and account, such as the credential store of the
+/// operating system.
+pub trait CredentialStore: Send + Sync {
+    /// Returns the secret of the account of the service, or `None` if there is none.
+    fn get(&self, service: &str, account: &str)
+        -> Result<Option<Zeroizing<String>>, KeychainError>;
+
+    /// Sets the secret of the account of the service, replacing any previous secret.
+    fn set(&self, service: &str, account: &str, secret: &str) -> Result<(), KeychainError>;
+
+    /// Deletes the secret of the account of the service, if any.
+    fn delete(&self, service: &str, account: &str) -> Result<(), KeychainError>;
+}
+
+impl<S: CredentialStore + ?Sized> CredentialStore for Arc<S> {
+    fn get(
+        &self,
+        service: &str,
+        account: &str,
+    ) -> Result<Option<Zeroizing<String>>, KeychainError> {
+        (**self).get(service, account)
+    }
+
+    fn set(&self, service: &str, account: &str, secret: &str) -> Result<(), KeychainError> {
+        (**self).set(service, account, secret)
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<(), KeychainError> {
+        (**self).delete(service, account)
+    }
+}
+
+/// The credential store of the operating system:
+/// - the login Keychain on macOS,
+/// - the Credential Manager on Windows,
+/// - the Secret Service on Linux and other Unix platforms, through the `secret-tool` command of
+///   libsecret.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OsCredentialStore;
+
+/// The status of a Keychain query which found no item.
+#[cfg(target_os = "macos")]
+const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+#[cfg(target_os = "macos")]
+impl CredentialStore for OsCredentialStore {
+    fn get(
+        &self,
+        service: &str,
+        account: &str,
+    ) -> Result<Option<Zeroizing<String>>, KeychainError> {
+        match security_framework::passwords::get_generic_password(service, account) {
+            Ok(secret) => String::from_utf8(secret)
+                .map(|secret| Some(Zeroizing::new(secret)))
+                .map_err(|_| KeychainError::Store("secret is not valid UTF-8".into())),
+            Err(err) if err.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+            Err(err) => Err(KeychainError::Store(err.to_string())),
+        }
+    }
+
+    fn set(&self, service: &str, account: &str, secret: &str) -> Result<(), KeychainError> {
+        security_framework::passwords::set_generic_password(service, account, secret.as_bytes())
+            .map_err(|err| KeychainError::Store(err.to_string()))
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<(), KeychainError> {
+        match security_framework::passwords::delete_generic_password(service, account) {
+            Err(err) if err.code() != ERR_SEC_ITEM_NOT_FOUND => {
+                Err(KeychainError::Store(err.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use super::{KeychainError, Zeroizing};
+    use windows_sys::Win32::{
+        Foundation::{GetLastError, ERROR_NOT_FOUND},
+        Security::Credentials::{
+            CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE,
+            CRED_TYPE_GENERIC,
+        },
+    };
+
+    /// Returns the null-terminated UTF-16 name of the credential of the account of the service.
+    fn target_name(service: &str, account: &str) -> Vec<u16> {
+        format!("{account}.{service}").encode_utf16().chain(Some(0)).collect()
+    }
+
+    fn last_error() -> KeychainError {
+        KeychainError::Store(std::io::Error::last_os_error().to_string())
+    }
+
+    pub(super) fn get(
+        service: &str,
+        account: &str,
+    ) -> Result<Option<Zeroizing<String>>, KeychainError> {
+        let target = target_name(service, account);
+        let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+        // SAFETY: `target` is null-terminated, and `credential` is freed below.
+        if unsafe { CredReadW(target.as_ptr(), CRED_TYPE_GENERIC, 0, &mut credential) } == 0 {
+            // SAFETY: reads the error of the call above.
+            return match unsafe { GetLastError() } {
+                ERROR_NOT_FOUND => Ok(None),
+                _ => Err(last_error()),
+            };
+        }
+        // SAFETY: the blob of a credential read successfully is valid for its size.
+        let secret = unsafe {
+            let credential = &*credential;
+            std::slice::from_raw_parts(
+                credential.CredentialBlob,
+                credential.CredentialBlobSize as usize,
+            )
+        };
+        let secret = String::from_utf8(secret.to_vec())
+            .map(Zeroizing::new)
+            .map_err(|_| KeychainError::Store("secret is not valid UTF-8".into()));
+        // SAFETY: `credential` was allocated by `CredReadW`, and `secret` does not borrow it.
+        unsafe { CredFree(credential.cast()) };
+        secret.map(Some)
+    }
+
+    pub(super) fn set(service: &str, account: &str, secret: &str) -> Result<(), KeychainError> {
+        let mut target = target_name(service, account);
+        let mut user: Vec<u16> = account.encode_utf16().chain(Some(0)).collect();
+        let mut blob = Zeroizing::new(secret.as_bytes().to_vec());
+        // SAFETY: all the fields of `CREDENTIALW` are integers or pointers, for which zero is
+        // valid.
+        let mut credential: CREDENTIALW = unsafe { std::mem::zeroed() };
+        credential.Type = CRED_TYPE_GENERIC;
+        credential.TargetName = target.as_mut_ptr();
+        credential.UserName = user.as_mut_ptr();
+        credential.CredentialBlob = blob.as_mut_ptr();
+        credential.CredentialBlobSize = blob.len() as u32;
+        credential.Persist = CRED_PERSIST_LOCAL_MACHINE;
+        // SAFETY: the pointers of `credential` outlive the call.
+        if unsafe { CredWriteW(&credential, 0) } == 0 {
+            return Err(last_error());
+        }
+        Ok(())
+    }
+
+    pub(super) fn delete(service: &str, account: &str) -> Result<(), KeychainError> {
+        let target = target_name(service, account);
+        // SAFETY: `target` is null-terminated.
+        if unsafe { CredDeleteW(target.as_ptr(), CRED_TYPE_GENERIC, 0) } == 0 {
+            // SAFETY: reads the error of the call above.
+            if unsafe { GetLastError() } != ERROR_NOT_FOUND {
+                return Err(last_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+impl CredentialStore for OsCredentialStore {
+    fn get(
+        &self,
+        service: &str,
+        account: &str,
+    ) -> Result<Option<Zeroizing<String>>, KeychainError> {
+        windows::get(service, account)
+    }
+
+    fn set(&self, service: &str, account: &str, secret: &str) -> Result<(), KeychainError> {
+        windows::set(service, account, secret)
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<(), KeychainError> {
+        windows::delete(service, account)
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl OsCredentialStore {
+    /// Runs `secret-tool` with the given arguments, writing `input` to its standard input, and
+    /// returns its standard output if it succeeded.
+    fn secret_tool(args: &[&str], input: Option<&str>) -> Result<Option<Vec<u8>>, KeychainError> {
+        use std::{
+            io::Write,
+            process::{Command, Stdio},
+        };
+
+        let mut child = Command::new("secret-tool")
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| KeychainError::Store(format!("failed to run secret-tool: {err}")))?;
+        if let Some(input) = input {
+            let mut stdin = child.stdin.take().expect("stdin is piped");
+            stdin
+                .write_all(input.as_bytes())
+                .map_err(|err| KeychainError::Store(err.to_string()))?;
+        }
+        let output =
+            child.wait_with_output().map_err(|err| KeychainError::Store(err.to_string()))?;
+        if !output.status.success() {
+            // `secret-tool lookup` fails without any message if there is no secret.
+            if output.stderr.is_empty() {
+                return Ok(None);
+            }
+            return Err(KeychainError::Store(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        Ok(Some(output.stdout))
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl CredentialStore for OsCredentialStore {
+    fn get(
+        &self,
+        service: &str,
+        account: &str,
+    ) -> Result<Option<Zeroizing<String>>, KeychainError> {
+        let Some(secret) =
+            Self::secret_tool(&["lookup", "service", service, "account", account], None)?
+        else {
+            return Ok(None);
+        };
+        let secret = Zeroizing::new(secret);
+        std::str::from_utf8(&secret)
+            .map(|secret| Some(Zeroizing::new(secret.trim_end().to_string())))
+            .map_err(|_| KeychainError::Store("secret is not valid UTF-8".into()))
+    }
+
+    fn set(&self, service: &str, account: &str, secret: &str) -> Result<(), KeychainError> {
+        let label = format!("{service} key of {account}");
+        let args = ["store", "--label", &label, "service", service, "account", account];
+        match Self::secret_tool(&args, Some(secret))? {
+            Some(_) => Ok(()),
+            None => Err(KeychainError::Store("secret-tool failed to store the secret".into())),
+        }
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<(), KeychainError> {
+        Self::secret_tool(&["clear", "service", service, "account", account], None).map(drop)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+impl CredentialStore for OsCredentialStore {
+    fn get(&self, _: &str, _: &str) -> Result<Option<Zeroizing<String>>, KeychainError> {
+        Err(KeychainError::Unsupported)
+    }
+
+    fn set(&self, _: &str, _: &str, _: &str) -> Result<(), KeychainError> {
+        Err(KeychainError::Unsupported)
+    }
+
+    fn delete(&self, _: &str, _: &str) -> Result<(), KeychainError> {
+        Err(KeychainError::Unsupported)
+    }
+}
+
+/// A [`CredentialStore`] in memory, which loses its secrets when dropped.
+#[derive(Default)]
+pub struct MemoryCredentialStore {
+    secrets: Mutex<HashMap<(String, String), Zeroizing<String>>>,
+}
+
+impl fmt::Debug for MemoryCredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryCredentialStore").finish_non_exhaustive()
+    }
+}
+
+impl CredentialStore for MemoryCredentialStore {
+    fn get(
+        &self,
+        service: &str,
+        account: &str,
+    ) -> Result<Option<Zeroizing<String>>, KeychainError> {
+        let secrets = self.secrets.lock().unwrap();
+        Ok(secrets.get(&(service.to_string(), account.to_string())).cloned())
+    }
+
+    fn set(&self, service: &str, account: &str, secret: &str) -> Result<(), KeychainError> {
+        let mut secrets = self.secrets.lock().unwrap();
+        secrets.insert((service.to_string(), account.to_string()), Zeroizing::new(secret.into()));
+        Ok(())
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<(), KeychainError> {
+        self.secrets.lock().unwrap().remove(&(service.to_string(), account.to_string()));
+        Ok(())
+    }
+}
+
+/// A request to unlock the key of a [`KeychainSigner`], passed to its [`UnlockPrompt`].
+#[derive(Clone, Copy, Debug)]
+pub struct UnlockRequest<'a> {
+    /// The service of the key.
+    pub service: &'a str,
+    /// The account of the key.
+    pub account: &'a str,
+    /// The address of the key.
+    pub address: Address,
+}
+
+/// A hook asking the user to unlock the key of a [`KeychainSigner`], for example with a
+/// passphrase or a biometric prompt.
+///
+/// It is implemented for closures taking an [`UnlockRequest`].
+pub trait UnlockPrompt: Send + Sync {
+    /// Asks the user to unlock the key, returning whether the user did.
+    fn unlock(&self, request: &UnlockRequest<'_>) -> bool;
+}
+
+impl<F> UnlockPrompt for F
+where
+    F: Fn(&UnlockRequest<'_>) -> bool + Send + Sync,
+{
+    fn unlock(&self, request: &UnlockRequest<'_>) -> bool {
+        self(request)
+    }
+}
+
+/// The state shared by the clones of a [`KeychainSigner`].
+struct Keychain<S> {
+    store: S,
+    service: String,
+    account: String,
+    prompt: Option<Arc<dyn UnlockPrompt>>,
+    unlock_timeout: Duration,
+    /// The unlocked key, and when it was unlocked.
+    unlocked: Mutex<Option<(PrivateKeySigner, Instant)>>,
+    /// Held while unlocking, so that concurrent signatures ask the prompt once.
+    unlocking: Mutex<()>,
+}
+
+/// A signer with a private key stored in a [`CredentialStore`], by default the credential store
+/// of the operating system, rather than in a key file.
+///
+/// The signer starts locked: the first signature asks the [`UnlockPrompt`], if any, before
+/// reading the key from the store. The key is then kept in memory until the signer locks itself
+/// after the [unlock timeout](Self::with_unlock_timeout), or is [locked](Self::lock). Clones of
+/// the signer share their unlocked key.
+///
+/// Asynchronous signatures ask the prompt and read the store on a blocking thread of the Tokio
+/// runtime, if any.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use alloy_signer::Signer;
+/// use alloy_signer_local::{KeychainSigner, OsCredentialStore, UnlockRequest};
+/// use std::time::Duration;
+///
+/// let signer = KeychainSigner::open(OsCredentialStore, "my-wallet", "default")?
+///     .with_unlock_timeout(Duration::from_secs(60))?
+///     .with_prompt(|request: &UnlockRequest<'_>| {
+///         println!("unlock {} to sign", request.address);
+///         // Ask for a passphrase or a fingerprint here.
+///         true
+///     })?;
+/// let signature = signer.sign_message(b"hello").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct KeychainSigner<S = OsCredentialStore> {
+    keychain: Arc<Keychain<S>>,
+    address: Address,
+    chain_id: Option<ChainId>,
+}
+
+impl<S> Clone for KeychainSigner<S> {
+    fn clone(&self) -> Self {
+        Self { keychain: self.keychain.clone(), address: self.address, chain_id: self.chain_id }
+    }
+}
+
+impl<S> fmt::Debug for KeychainSigner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeychainSigner")
+            .field("service", &self.keychain.service)
+            .field("account", &self.keychain.account)
+            .field("address", &self.address)
+            .field("chain_id", &self.chain_id)
+            .field("unlocked", &self.is_unlocked())
+            .finish()
+    }
+}
+
+impl<S: CredentialStore> KeychainSigner<S> {
+    /// Opens the signer with the key of the account of the service in the store.
+    ///
+    /// The key is read to check it and learn its address, but not kept: the signer starts
+    /// locked.
+    pub fn open(
+        store: S,
+        service: impl Into<String>,
+        account: impl Into<String>,
+    ) -> Result<Self, KeychainError> {
+        let (service, account) = (service.into(), account.into());
+        let address = read_key(&store, &service, &account)?.address();
+        Ok(Self::new(store, service, account, address))
+    }
+
+    /// Stores the key of the given signer as the key of the account of the service in the store,
+    /// replacing any previous key, and opens the signer with it. The signer starts locked.
+    pub fn import(
+        store: S,
+        service: impl Into<String>,
+        account: impl Into<String>,
+        signer: &PrivateKeySigner,
+    ) -> Result<Self, KeychainError> {
+        let (service, account) = (service.into(), account.into());
+        let secret = Zeroizing::new(hex::encode(signer.to_bytes()));
+        store.set(&service, &account, &secret)?;
+        Ok(Self::new(store, service, account, signer.address()))
+    }
+
+    /// Generates a random key, stored as the key of the account of the service in the store, and
+    /// opens the signer with it. The signer starts locked.
+    pub fn generate(
+        store: S,
+        service: impl Into<String>,
+        account: impl Into<String>,
+    ) -> Result<Self, KeychainError> {
+        Self::import(store, service, account, &PrivateKeySigner::random())
+    }
+
+    fn new(store: S, service: String, account: String, address: Address) -> Self {
+        let keychain = Keychain {
+            store,
+            service,
+            account,
+            prompt: None,
+            unlock_timeout: DEFAULT_UNLOCK_TIMEOUT,
+            unlocked: Mutex::new(None),
+            unlocking: Mutex::new(()),
+        };
+        Self { keychain: Arc::new(keychain), address, chain_id: None }
+    }
+
+    /// Sets the prompt asked before the key is read from the store, whenever the signer is
+    /// locked.
+    ///
+    /// Returns [`KeychainError::Cloned`] if the signer was cloned.
+    pub fn with_prompt(
+        mut self,
+        prompt: impl UnlockPrompt + 'static,
+    ) -> Result<Self, KeychainError> {
+        self.keychain_mut()?.prompt = Some(Arc::new(prompt));
+        Ok(self)
+    }
+
+    /// Sets the time after which the signer locks itself, [`DEFAULT_UNLOCK_TIMEOUT`] by default.
+    ///
+    /// Returns [`KeychainError::Cloned`] if the signer was cloned.
+    pub fn with_unlock_timeout(mut self, timeout: Duration) -> Result<Self, KeychainError> {
+        self.keychain_mut()?.unlock_timeout = timeout;
+        Ok(self)
+    }
+
+    fn keychain_mut(&mut self) -> Result<&mut Keychain<S>, KeychainError> {
+        Arc::get_mut(&mut self.keychain).ok_or(KeychainError::Cloned)
+    }
+
+    /// Deletes the key of the signer from the store, and locks the signer.
+    pub fn delete(self) -> Result<(), KeychainError> {
+        self.lock();
+        self.keychain.store.delete(&self.keychain.service, &self.keychain.account)
+    }
+
+    /// Unlocks the signer, asking the prompt and reading the key from the store if it is locked.
+    ///
+    /// This blocks on the prompt and the store, and should not be called on an asynchronous
+    /// executor.
+    pub fn unlock(&self) -> Result<(), KeychainError> {
+        self.key().map(drop)
+    }
+
+    /// Returns the key of the signer, unlocking it on a blocking thread of the Tokio runtime if it
+    /// is locked.
+    async fn key_async(&self) -> Result<PrivateKeySigner, KeychainError>
+    where
+        S: 'static,
+    {
+        if let Some(signer) = self.unlocked_key() {
+            return Ok(signer);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let this = self.clone();
+            return handle
+                .spawn_blocking(move || this.key())
+                .await
+                .map_err(|err| KeychainError::Store(format!("unlock task failed: {err}")))?;
+        }
+        self.key()
+    }
+
+    /// Returns the key of the signer, asking the prompt and reading the key from the store if it
+    /// is locked.
+    fn key(&self) -> Result<PrivateKeySigner, KeychainError> {
+        let keychain = &*self.keychain;
+        let _unlocking = keychain.unlocking.lock().unwrap();
+        if let Some(signer) = self.unlocked_key() {
+            return Ok(signer);
+        }
+
+        let request = UnlockRequest {
+            service: &keychain.service,
+            account: &keychain.account,
+            address: self.address,
+        };
+        if keychain.prompt.as_ref().is_some_and(|prompt| !prompt.unlock(&request)) {
+            return Err(KeychainError::UnlockDenied(keychain.account.clone()));
+        }
+        let signer = read_key(&keychain.store, &keychain.service, &keychain.account)?;
+        if signer.address() != self.address {
+            return Err(KeychainError::Store(format!(
+                "the key of account {:?} was replaced",
+                keychain.account
+            )));
+        }
+        *keychain.unlocked.lock().unwrap() = Some((signer.clone(), Instant::now()));
+        Ok(signer)
+    }
+}
+
+impl<S> KeychainSigner<S> {
+    /// Returns the key of the signer if it is unlocked, dropping it if the signer timed out.
+    fn unlocked_key(&self) -> Option<PrivateKeySigner> {
+        let mut unlocked = self.keychain.unlocked.lock().unwrap();
+        match &*unlocked {
+            Some((signer, at)) if at.elapsed() < self.keychain.unlock_timeout => {
+                Some(signer.clone())
+            }
+            _ => {
+                *unlocked = None;
+                None
+            }
+        }
+    }
+
+    /// Locks the signer, dropping its key from memory.
+    pub fn lock(&self) {
+        *self.keychain.unlocked.lock().unwrap() = None;
+    }
+
+    /// Returns whether the signer holds its key in memory, and can sign without the prompt.
+    pub fn is_unlocked(&self) -> bool {
+        self.keychain
+            .unlocked
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|(_, at)| at.elapsed() < self.keychain.unlock_timeout)
+    }
+
+    /// Returns the address of the key of the signer.
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the service of the key of the signer.
+    pub fn service(&self) -> &str {
+        &self.keychain.service
+    }
+
+    /// Returns the account of the key of the signer.
+    pub fn account(&self) -> &str {
+        &self.keychain.account
+    }
+}
+
+/// Reads the key of the account of the service from the store.
+fn read_key<S: CredentialStore>(
+    store: &S,
+    service: &str,
+    account: &str,
+) -> Result<PrivateKeySigner, KeychainError> {
+    let secret = store.get(service, account)?.ok_or_else(|| KeychainError::NotFound {
+        service: service.to_string(),
+        account: account.to_string(),
+    })?;
+    let bytes = Zeroizing::new(hex::decode(secret.as_str()).map_err(LocalSignerError::from)?);
+    Ok(PrivateKeySigner::from_slice(&bytes).map_err(LocalSignerError::from)?)
+}
+
+impl<S: CredentialStore> SignerSync for KeychainSigner<S> {
+    #[inline]
+    fn sign_hash_sync(&self, hash: &B256) -> Result<Signature> {
+        let signer = self.key().map_err(alloy_signer::Error::other)?;
+        signer.sign_hash_sync(hash)
+    }
+
+    #[inline]
+    fn chain_id_sync(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: CredentialStore + 'static> Signer for KeychainSigner<S> {
+    #[inline]
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature> {
+        let signer = self.key_async().await.map_err(alloy_signer::Error::other)?;
+        signer.sign_hash_sync(hash)
+    }
+
+    #[inline]
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    #[inline]
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    #[inline]
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.chain_id = chain_id;
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: CredentialStore + 'static> TxSigner<Signature> for KeychainSigner<S> {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    #[doc(alias = "sign_tx")]
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> Result<Signature> {
+        let signer = self.key_async().await.map_err(alloy_signer::Error::other)?;
+        sign_transaction_with_chain_id!(self, tx, signer.sign_hash_sync(&tx.signature_hash()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const SERVICE: &str = "alloy-test";
+
+    #[test]
+    fn import_and_open() {
+        let store = Arc::new(MemoryCredentialStore::default());
+        let key = PrivateKeySigner::random();
+        let signer = KeychainSigner::import(store.clone(), SERVICE, "alice", &key).unwrap();
+        assert_eq!(signer.address(), key.address());
+        assert!(!signer.is_unlocked());
+
+        let signer = KeychainSigner::open(store.clone(), SERVICE, "alice").unwrap();
+        assert_eq!(signer.address(), key.address());
+        let signature = signer.sign_message_sync(b"hello").unwrap();
+        assert_eq!(signature.recover_address_from_msg(b"hello").unwrap(), key.address());
+
+        signer.delete().unwrap();
+        assert!(matches!(
+            KeychainSigner::open(store, SERVICE, "alice"),
+            Err(KeychainError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn prompts_once_locked() {
+        let prompts = Arc::new(AtomicUsize::new(0));
+        let allow = Arc::new(Mutex::new(true));
+        let (counter, allowed) = (prompts.clone(), allow.clone());
+        let signer = KeychainSigner::generate(MemoryCredentialStore::default(), SERVICE, "bob")
+            .unwrap()
+            .with_prompt(move |request: &UnlockRequest<'_>| {
+                assert_eq!(request.account, "bob");
+                counter.fetch_add(1, Ordering::SeqCst);
+                *allowed.lock().unwrap()
+            })
+            .unwrap();
+        assert!(!signer.is_unlocked());
+
+        signer.sign_hash_sync(&B256::ZERO).unwrap();
+        signer.sign_hash_sync(&B256::ZERO).unwrap();
+        assert!(signer.is_unlocked());
+        assert_eq!(prompts.load(Ordering::SeqCst), 1);
+
+        signer.lock();
+        assert!(!signer.is_unlocked());
+        signer.sign_hash_sync(&B256::ZERO).unwrap();
+        assert_eq!(prompts.load(Ordering::SeqCst), 2);
+
+        signer.lock();
+        *allow.lock().unwrap() = false;
+        assert!(matches!(signer.unlock(), Err(KeychainError::UnlockDenied(_))));
+        assert!(signer.sign_hash_sync(&B256::ZERO).is_err());
+    }
+
+    #[test]
+    fn unlock_timeout() {
+        let signer = KeychainSigner::generate(MemoryCredentialStore::default(), SERVICE, "carol")
+            .unwrap()
+            .with_unlock_timeout(Duration::ZERO)
+            .unwrap();
+        signer.unlock().unwrap();
+        assert!(!signer.is_unlocked());
+
+        let signer = signer.with_prompt(|_: &UnlockRequest<'_>| false).unwrap();
+        assert!(signer.sign_hash_sync(&B256::ZERO).is_err());
+    }
+
+    #[test]
+    fn configure_cloned() {
+        let signer =
+            KeychainSigner::generate(MemoryCredentialStore::default(), SERVICE, "erin").unwrap();
+        let _clone = signer.clone();
+        assert!(matches!(signer.with_unlock_timeout(Duration::ZERO), Err(KeychainError::Cloned)));
+    }
+
+    #[tokio::test]
+    async fn unlocks_on_blocking_thread() {
+        let executor = std::thread::current().id();
+        let signer = KeychainSigner::generate(MemoryCredentialStore::default(), SERVICE, "frank")
+            .unwrap()
+            .with_prompt(move |_: &UnlockRequest<'_>| std::thread::current().id() != executor)
+            .unwrap();
+        let signature = signer.sign_message(b"hello").await.unwrap();
+        assert_eq!(signature.recover_address_from_msg(b"hello").unwrap(), signer.address());
+    }
+
+    #[test]
+    fn invalid_key() {
+        let store = MemoryCredentialStore::default();
+        store.set(SERVICE, "dave", "not a key").unwrap();
+        assert!(matches!(
+            KeychainSigner::open(store, SERVICE, "dave"),
+            Err(KeychainError::InvalidKey(_))
+        ));
+    }
+}
@@ -17,6 +17,14 @@ use std::fmt;
 mod error;
 pub use error::LocalSignerError;
 
+#[cfg(feature = "keychain")]
+mod keychain;
+#[cfg(feature = "keychain")]
+pub use keychain::{
+    CredentialStore, KeychainError, KeychainSigner, MemoryCredentialStore, OsCredentialStore,
+    UnlockPrompt, UnlockRequest, DEFAULT_UNLOCK_TIMEOUT,
+};
+
 #[cfg(feature = "mnemonic")]
 mod mnemonic;
 #[cfg(feature = "mnemonic")]
@@ -0,0 +1,72 @@
+//! [EIP-191] personal message utilities.
+//!
+//! Messages are prefixed with `"\x19Ethereum Signed Message:\n" + len(message)` before hashing, as
+//! done by `personal_sign` and `eth_sign`. Use
+//! [`Signer::sign_message`](crate::Signer::sign_message) to sign them.
+//!
+//! [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+
+use crate::Result;
+use alloy_primitives::{hex, Address, Bytes, Signature, B256};
+
+pub use alloy_primitives::{
+    eip191_hash_message as hash_message, utils::eip191_message as prefix_message,
+};
+
+/// Recovers the address that signed the EIP-191 prefixed `message`.
+#[inline]
+pub fn recover_message_signer(message: impl AsRef<[u8]>, signature: &Signature) -> Result<Address> {
+    Ok(signature.recover_address_from_msg(message)?)
+}
+
+/// Recovers the address that signed an already computed EIP-191 message hash.
+#[inline]
+pub fn recover_hash_signer(hash: &B256, signature: &Signature) -> Result<Address> {
+    Ok(signature.recover_address_from_prehash(hash)?)
+}
+
+/// Returns `true` if `signature` is a valid signature of the EIP-191 prefixed `message` by
+/// `address`.
+///
+/// This only verifies ECDSA signatures of externally owned accounts.
+#[inline]
+pub fn verify_message(message: impl AsRef<[u8]>, signature: &Signature, address: Address) -> bool {
+    recover_message_signer(message, signature).is_ok_and(|signer| signer == address)
+}
+
+/// Decodes the message parameter of a `personal_sign` or `eth_sign` request.
+///
+/// Wallets accept both `0x`-prefixed hex data and plain UTF-8 text; hex strings are decoded to
+/// their bytes, anything else is taken as is.
+pub fn decode_message_param(param: &str) -> Bytes {
+    param
+        .strip_prefix("0x")
+        .and_then(|data| hex::decode(data).ok())
+        .map_or_else(|| Bytes::copy_from_slice(param.as_bytes()), Bytes::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    // Signature of "hello world" from the `Signature::recover_address_from_msg` tests.
+    #[test]
+    fn recover_and_verify() {
+        let signature: Signature = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c".parse().unwrap();
+        let signer = address!("2c7536E3605D9C16a7a3D7b1898e529396a65c23");
+        let message = "Some data";
+
+        assert_eq!(recover_message_signer(message, &signature).unwrap(), signer);
+        assert_eq!(recover_hash_signer(&hash_message(message), &signature).unwrap(), signer);
+        assert!(verify_message(message, &signature, signer));
+        assert!(!verify_message("Other data", &signature, signer));
+    }
+
+    #[test]
+    fn message_params() {
+        assert_eq!(decode_message_param("0x68656c6c6f"), Bytes::from_static(b"hello"));
+        assert_eq!(decode_message_param("hello"), Bytes::from_static(b"hello"));
+        assert_eq!(decode_message_param("0xnothex"), Bytes::from_static(b"0xnothex"));
+    }
+}
@@ -14,6 +14,13 @@ pub use signer::{Signer, SignerSync};
 
 pub mod utils;
 
+pub mod eip191;
+
+pub mod siwe;
+
+#[cfg(feature = "eip712")]
+pub mod requests;
+
 pub use alloy_primitives::Signature;
 pub use k256;
 
@@ -0,0 +1,241 @@
+//! Typed JSON-RPC request and response shapes of wallet signing methods, for proxying
+//! `personal_sign`, `eth_sign` and `eth_signTypedData_v4` requests to a [`Signer`].
+
+use crate::{eip191, Result, Signer};
+use alloy_dyn_abi::eip712::TypedData;
+use alloy_primitives::{hex, Address, Bytes, Signature, B256};
+use serde::{de, ser::SerializeTuple, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The parameters of a `personal_sign` request: `[message, address]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PersonalSignRequest {
+    /// The message to sign, before EIP-191 prefixing.
+    pub message: Bytes,
+    /// The address of the account to sign with.
+    pub address: Address,
+}
+
+impl PersonalSignRequest {
+    /// Returns the EIP-191 hash of the message.
+    pub fn signing_hash(&self) -> B256 {
+        eip191::hash_message(&self.message)
+    }
+
+    /// Signs the message with `signer`.
+    pub async fn sign<S: Signer + Send + Sync>(&self, signer: &S) -> Result<SignatureResponse> {
+        signer.sign_message(&self.message).await.map(SignatureResponse)
+    }
+
+    /// Recovers the address that produced `signature` for this request.
+    pub fn recover(&self, signature: &Signature) -> Result<Address> {
+        eip191::recover_message_signer(&self.message, signature)
+    }
+}
+
+impl Serialize for PersonalSignRequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.message, self.address).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PersonalSignRequest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (message, address) = <(String, Address)>::deserialize(deserializer)?;
+        Ok(Self { message: eip191::decode_message_param(&message), address })
+    }
+}
+
+/// The parameters of an `eth_sign` request: `[address, message]`.
+///
+/// Note the reversed order compared to [`PersonalSignRequest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthSignRequest {
+    /// The address of the account to sign with.
+    pub address: Address,
+    /// The message to sign, before EIP-191 prefixing.
+    pub message: Bytes,
+}
+
+impl EthSignRequest {
+    /// Returns the EIP-191 hash of the message.
+    pub fn signing_hash(&self) -> B256 {
+        eip191::hash_message(&self.message)
+    }
+
+    /// Signs the message with `signer`.
+    pub async fn sign<S: Signer + Send + Sync>(&self, signer: &S) -> Result<SignatureResponse> {
+        signer.sign_message(&self.message).await.map(SignatureResponse)
+    }
+}
+
+impl Serialize for EthSignRequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.address, &self.message).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EthSignRequest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (address, message) = <(Address, String)>::deserialize(deserializer)?;
+        Ok(Self { address, message: eip191::decode_message_param(&message) })
+    }
+}
+
+/// The parameters of an `eth_signTypedData_v4` request: `[address, typedData]`.
+///
+/// The typed data is serialized as a JSON string, as expected by most wallets, and deserialized
+/// from either a JSON string or object.
+#[derive(Clone, Debug)]
+pub struct SignTypedDataRequest {
+    /// The address of the account to sign with.
+    pub address: Address,
+    /// The typed data to sign.
+    pub typed_data: TypedData,
+}
+
+impl SignTypedDataRequest {
+    /// Returns the EIP-712 signing hash of the typed data.
+    pub fn signing_hash(&self) -> Result<B256> {
+        Ok(self.typed_data.eip712_signing_hash()?)
+    }
+
+    /// Signs the typed data with `signer`.
+    pub async fn sign<S: Signer + Send + Sync>(&self, signer: &S) -> Result<SignatureResponse> {
+        signer.sign_dynamic_typed_data(&self.typed_data).await.map(SignatureResponse)
+    }
+
+    /// Recovers the address that produced `signature` for this request.
+    pub fn recover(&self, signature: &Signature) -> Result<Address> {
+        Ok(signature.recover_address_from_prehash(&self.signing_hash()?)?)
+    }
+}
+
+impl Serialize for SignTypedDataRequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let typed_data =
+            serde_json::to_string(&self.typed_data).map_err(serde::ser::Error::custom)?;
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.address)?;
+        tuple.serialize_element(&typed_data)?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SignTypedDataRequest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (address, typed_data) = <(Address, TypedData)>::deserialize(deserializer)?;
+        Ok(Self { address, typed_data })
+    }
+}
+
+/// The response of a wallet signing method: the 65 bytes `r || s || v` signature as a hex
+/// string, with `v` being 27 or 28.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignatureResponse(pub Signature);
+
+impl From<Signature> for SignatureResponse {
+    fn from(signature: Signature) -> Self {
+        Self(signature)
+    }
+}
+
+impl From<SignatureResponse> for Signature {
+    fn from(response: SignatureResponse) -> Self {
+        response.0
+    }
+}
+
+impl Serialize for SignatureResponse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = self.0.as_bytes();
+        bytes[64] = 27 + self.0.v().y_parity_byte();
+        serializer.serialize_str(&hex::encode_prefixed(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for SignatureResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Bytes::deserialize(deserializer)?;
+        Signature::try_from(&bytes[..]).map(Self).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::secret_key_to_address;
+    use alloy_primitives::ChainId;
+    use k256::ecdsa::SigningKey;
+    use serde_json::json;
+
+    struct TestSigner(SigningKey);
+
+    impl TestSigner {
+        fn new() -> Self {
+            Self(SigningKey::from_slice(&[0x42; 32]).unwrap())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Signer for TestSigner {
+        async fn sign_hash(&self, hash: &B256) -> Result<Signature> {
+            Ok(self.0.sign_prehash_recoverable(hash.as_slice())?.into())
+        }
+
+        fn address(&self) -> Address {
+            secret_key_to_address(&self.0)
+        }
+
+        fn chain_id(&self) -> Option<ChainId> {
+            None
+        }
+
+        fn set_chain_id(&mut self, _chain_id: Option<ChainId>) {}
+    }
+
+    #[tokio::test]
+    async fn personal_sign_roundtrip() {
+        let signer = TestSigner::new();
+        let params = json!(["0x68656c6c6f", signer.address()]);
+        let request: PersonalSignRequest = serde_json::from_value(params).unwrap();
+        assert_eq!(request.message, Bytes::from_static(b"hello"));
+
+        let response = request.sign(&signer).await.unwrap();
+        let json = serde_json::to_value(response).unwrap();
+        assert_eq!(json.as_str().unwrap().len(), 2 + 130);
+
+        let response: SignatureResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(request.recover(&response.0).unwrap(), signer.address());
+
+        let request: EthSignRequest =
+            serde_json::from_value(json!([signer.address(), "hello"])).unwrap();
+        assert_eq!(request.signing_hash(), eip191::hash_message("hello"));
+    }
+
+    #[tokio::test]
+    async fn sign_typed_data_v4() {
+        let signer = TestSigner::new();
+        let typed_data = json!({
+            "types": {
+                "EIP712Domain": [{ "name": "name", "type": "string" }],
+                "Mail": [{ "name": "contents", "type": "string" }]
+            },
+            "primaryType": "Mail",
+            "domain": { "name": "Ether Mail" },
+            "message": { "contents": "Hello, Bob!" }
+        });
+
+        // Both the JSON string and object forms are accepted.
+        let from_string: SignTypedDataRequest =
+            serde_json::from_value(json!([signer.address(), typed_data.to_string()])).unwrap();
+        let request: SignTypedDataRequest =
+            serde_json::from_value(json!([signer.address(), typed_data])).unwrap();
+        assert_eq!(from_string.signing_hash().unwrap(), request.signing_hash().unwrap());
+
+        let serialized = serde_json::to_value(&request).unwrap();
+        assert!(serialized[1].is_string());
+
+        let response = request.sign(&signer).await.unwrap();
+        assert_eq!(request.recover(&response.0).unwrap(), signer.address());
+    }
+}
@@ -0,0 +1,795 @@
+//! [EIP-4361] Sign-In with Ethereum messages.
+//!
+//! A [`SiweMessage`] is written with [`Display`](fmt::Display) and parsed with [`FromStr`]
+//! following the ABNF of the EIP, signed as an [EIP-191] personal message, and verified against
+//! the expected domain, nonce and time with [`SiweMessage::verify`].
+//!
+//! Only signatures of externally owned accounts are verified here; smart contract wallets are
+//! verified with EIP-1271 by `alloy_contract::eip1271::verify_siwe_message`.
+//!
+//! [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+//! [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+
+use crate::{eip191, Result, Signer, SignerSync};
+use alloy_primitives::{Address, ChainId, Signature, B256};
+use std::{
+    fmt,
+    iter::Peekable,
+    str::{FromStr, Split},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The suffix of the first line of a message, after the scheme and domain.
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+/// The version of the messages, the only one defined by EIP-4361.
+const VERSION: &str = "1";
+
+const URI_TAG: &str = "URI: ";
+const VERSION_TAG: &str = "Version: ";
+const CHAIN_ID_TAG: &str = "Chain ID: ";
+const NONCE_TAG: &str = "Nonce: ";
+const ISSUED_AT_TAG: &str = "Issued At: ";
+const EXPIRATION_TIME_TAG: &str = "Expiration Time: ";
+const NOT_BEFORE_TAG: &str = "Not Before: ";
+const REQUEST_ID_TAG: &str = "Request ID: ";
+const RESOURCES_TAG: &str = "Resources:";
+const RESOURCE_PREFIX: &str = "- ";
+
+/// An error parsing or verifying a [`SiweMessage`].
+#[derive(Debug, thiserror::Error)]
+pub enum SiweError {
+    /// A line is missing or is not the expected one.
+    #[error("expected {expected}, got {got:?}")]
+    UnexpectedLine {
+        /// The expected line.
+        expected: &'static str,
+        /// The line found, `None` at the end of the message.
+        got: Option<String>,
+    },
+    /// A field does not match its grammar.
+    #[error("invalid {field}: {value:?}")]
+    InvalidField {
+        /// The name of the field.
+        field: &'static str,
+        /// The invalid value.
+        value: String,
+    },
+    /// The version is not `1`.
+    #[error("unsupported version {0:?}")]
+    UnsupportedVersion(String),
+    /// The domain is not the expected one.
+    #[error("domain {got:?} does not match the expected domain {expected:?}")]
+    DomainMismatch {
+        /// The expected domain.
+        expected: String,
+        /// The domain of the message.
+        got: String,
+    },
+    /// The nonce is not the expected one.
+    #[error("nonce {got:?} does not match the expected nonce {expected:?}")]
+    NonceMismatch {
+        /// The expected nonce.
+        expected: String,
+        /// The nonce of the message.
+        got: String,
+    },
+    /// The chain ID is not the expected one.
+    #[error("chain ID {got} does not match the expected chain ID {expected}")]
+    ChainIdMismatch {
+        /// The expected chain ID.
+        expected: ChainId,
+        /// The chain ID of the message.
+        got: ChainId,
+    },
+    /// The message expired.
+    #[error("message expired at {0}")]
+    Expired(Timestamp),
+    /// The message is not valid yet.
+    #[error("message is not valid before {0}")]
+    NotYetValid(Timestamp),
+    /// The signature was not made by the address of the message.
+    #[error("signature is by {recovered}, not {expected}")]
+    SignerMismatch {
+        /// The address of the message.
+        expected: Address,
+        /// The address recovered from the signature.
+        recovered: Address,
+    },
+    /// The signer could not be recovered from the signature.
+    #[error(transparent)]
+    Signature(#[from] alloy_primitives::SignatureError),
+}
+
+/// An [RFC 3339] date-time of a [`SiweMessage`], such as `2021-09-30T16:25:24Z`.
+///
+/// The timestamp keeps its text, so that a parsed message is written back unchanged.
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Timestamp {
+    text: String,
+    unix: i64,
+}
+
+impl Timestamp {
+    /// Returns the current time.
+    pub fn now() -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        Self::from_unix(now.as_secs()).expect("the current year has 4 digits")
+    }
+
+    /// Returns the UTC date-time of the number of seconds since the Unix epoch, or `None` after
+    /// the year 9999.
+    pub fn from_unix(secs: u64) -> Option<Self> {
+        let unix = i64::try_from(secs).ok()?;
+        let (days, secs) = (unix.div_euclid(86_400), unix.rem_euclid(86_400));
+        let (year, month, day) = civil_from_days(days);
+        if year > 9999 {
+            return None;
+        }
+        let (hour, minute, second) = (secs / 3600, secs / 60 % 60, secs % 60);
+        let text = format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z");
+        Some(Self { text, unix })
+    }
+
+    /// Returns the number of seconds since the Unix epoch, ignoring fractions of seconds.
+    pub const fn unix_timestamp(&self) -> i64 {
+        self.unix
+    }
+
+    /// Returns the timestamp as written in the message.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = SiweError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_rfc3339(s)
+            .map(|unix| Self { text: s.to_string(), unix })
+            .ok_or_else(|| SiweError::InvalidField { field: "timestamp", value: s.to_string() })
+    }
+}
+
+/// A Sign-In with Ethereum message of [EIP-4361].
+///
+/// # Examples
+///
+/// ```
+/// use alloy_signer::siwe::{SiweMessage, Timestamp, VerificationOpts};
+/// use alloy_signer_local::PrivateKeySigner;
+///
+/// let signer = PrivateKeySigner::random();
+/// let message = SiweMessage::new(
+///     "example.com",
+///     signer.address(),
+///     "https://example.com/login",
+///     1,
+///     "32891756",
+///     Timestamp::now(),
+/// )
+/// .with_statement("I accept the Terms of Service: https://example.com/tos");
+///
+/// // The wallet parses and signs the text of the message.
+/// let text = message.to_string();
+/// let signature = text.parse::<SiweMessage>()?.sign_sync(&signer)?;
+///
+/// // The backend verifies it against the domain and nonce of the session.
+/// let opts = VerificationOpts {
+///     domain: Some("example.com".into()),
+///     nonce: Some("32891756".into()),
+///     ..Default::default()
+/// };
+/// text.parse::<SiweMessage>()?.verify(&signature, &opts)?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SiweMessage {
+    /// The URI scheme of the origin of the request, `https` if `None`.
+    pub scheme: Option<String>,
+    /// The RFC 3986 authority requesting the signing, such as `example.com:8080`.
+    pub domain: String,
+    /// The address signing in, written with its EIP-55 checksum.
+    pub address: Address,
+    /// A human-readable assertion, on a single line, that the user signs.
+    pub statement: Option<String>,
+    /// The RFC 3986 URI of the subject of the signing.
+    pub uri: String,
+    /// The chain of the address, whose contract wallets verify the signature.
+    pub chain_id: ChainId,
+    /// A random string of at least 8 alphanumeric characters, preventing replay attacks.
+    pub nonce: String,
+    /// The time the message was created.
+    pub issued_at: Timestamp,
+    /// The time after which the message is no longer valid.
+    pub expiration_time: Option<Timestamp>,
+    /// The time before which the message is not valid yet.
+    pub not_before: Option<Timestamp>,
+    /// A system-specific identifier of the request.
+    pub request_id: Option<String>,
+    /// URIs of resources the user wishes to have resolved as part of the authentication.
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Creates a message with the required fields.
+    pub fn new(
+        domain: impl Into<String>,
+        address: Address,
+        uri: impl Into<String>,
+        chain_id: ChainId,
+        nonce: impl Into<String>,
+        issued_at: Timestamp,
+    ) -> Self {
+        Self {
+            scheme: None,
+            domain: domain.into(),
+            address,
+            statement: None,
+            uri: uri.into(),
+            chain_id,
+            nonce: nonce.into(),
+            issued_at,
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    /// Sets the statement.
+    pub fn with_statement(mut self, statement: impl Into<String>) -> Self {
+        self.statement = Some(statement.into());
+        self
+    }
+
+    /// Sets the expiration time.
+    pub fn with_expiration_time(mut self, expiration_time: Timestamp) -> Self {
+        self.expiration_time = Some(expiration_time);
+        self
+    }
+
+    /// Sets the time before which the message is not valid.
+    pub fn with_not_before(mut self, not_before: Timestamp) -> Self {
+        self.not_before = Some(not_before);
+        self
+    }
+
+    /// Sets the request ID.
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Sets the resources.
+    pub fn with_resources<T: Into<String>>(
+        mut self,
+        resources: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.resources = resources.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns the EIP-191 hash of the message, which is signed.
+    pub fn hash(&self) -> B256 {
+        eip191::hash_message(self.to_string())
+    }
+
+    /// Signs the message with [`Signer::sign_message`].
+    pub async fn sign<S: Signer + Sync + ?Sized>(&self, signer: &S) -> Result<Signature> {
+        signer.sign_message(self.to_string().as_bytes()).await
+    }
+
+    /// Signs the message with [`SignerSync::sign_message_sync`].
+    pub fn sign_sync<S: SignerSync + ?Sized>(&self, signer: &S) -> Result<Signature> {
+        signer.sign_message_sync(self.to_string().as_bytes())
+    }
+
+    /// Checks the domain, nonce and chain ID of the message against the expected ones, and that
+    /// the message is valid at the time of the options, the current time by default.
+    ///
+    /// The signature is not checked, see [`verify`](Self::verify).
+    pub fn validate(&self, opts: &VerificationOpts) -> Result<(), SiweError> {
+        if let Some(domain) = opts.domain.as_ref().filter(|domain| **domain != self.domain) {
+            return Err(SiweError::DomainMismatch {
+                expected: domain.clone(),
+                got: self.domain.clone(),
+            });
+        }
+        if let Some(nonce) = opts.nonce.as_ref().filter(|nonce| **nonce != self.nonce) {
+            return Err(SiweError::NonceMismatch {
+                expected: nonce.clone(),
+                got: self.nonce.clone(),
+            });
+        }
+        if let Some(chain_id) = opts.chain_id.filter(|chain_id| *chain_id != self.chain_id) {
+            return Err(SiweError::ChainIdMismatch { expected: chain_id, got: self.chain_id });
+        }
+
+        let now = opts
+            .timestamp
+            .as_ref()
+            .map_or_else(|| Timestamp::now().unix_timestamp(), Timestamp::unix_timestamp);
+        if let Some(expiration_time) = &self.expiration_time {
+            if now >= expiration_time.unix_timestamp() {
+                return Err(SiweError::Expired(expiration_time.clone()));
+            }
+        }
+        if let Some(not_before) = &self.not_before {
+            if now < not_before.unix_timestamp() {
+                return Err(SiweError::NotYetValid(not_before.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// [Validates](Self::validate) the message, and checks that `signature` is an ECDSA signature
+    /// of the message by its address.
+    ///
+    /// Smart contract wallets cannot be verified this way, see the [module](self) documentation.
+    pub fn verify(&self, signature: &Signature, opts: &VerificationOpts) -> Result<(), SiweError> {
+        self.validate(opts)?;
+        let recovered = signature.recover_address_from_prehash(&self.hash())?;
+        if recovered != self.address {
+            return Err(SiweError::SignerMismatch { expected: self.address, recovered });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        writeln!(f, "{}{PREAMBLE}", self.domain)?;
+        writeln!(f, "{}", self.address.to_checksum(None))?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{URI_TAG}{}", self.uri)?;
+        writeln!(f, "{VERSION_TAG}{VERSION}")?;
+        writeln!(f, "{CHAIN_ID_TAG}{}", self.chain_id)?;
+        writeln!(f, "{NONCE_TAG}{}", self.nonce)?;
+        write!(f, "{ISSUED_AT_TAG}{}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\n{EXPIRATION_TIME_TAG}{expiration_time}")?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\n{NOT_BEFORE_TAG}{not_before}")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\n{REQUEST_ID_TAG}{request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\n{RESOURCES_TAG}")?;
+            for resource in &self.resources {
+                write!(f, "\n{RESOURCE_PREFIX}{resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = SiweError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.split('\n').peekable();
+
+        let line = next_line(&mut lines, "the preamble")?;
+        let origin =
+            line.strip_suffix(PREAMBLE).ok_or_else(|| unexpected_line("the preamble", line))?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(check(scheme, "scheme", is_scheme)?), domain),
+            None => (None, origin),
+        };
+        let domain = check(domain, "domain", is_authority)?;
+
+        let line = next_line(&mut lines, "the address")?;
+        let address = Address::parse_checksummed(line, None)
+            .map_err(|_| SiweError::InvalidField { field: "address", value: line.to_string() })?;
+
+        empty_line(&mut lines)?;
+        let statement = match next_line(&mut lines, "an empty line or the statement")? {
+            "" => None,
+            statement => {
+                empty_line(&mut lines)?;
+                Some(statement.to_string())
+            }
+        };
+
+        let uri = check(tagged_line(&mut lines, URI_TAG)?, "URI", is_uri)?;
+        let version = tagged_line(&mut lines, VERSION_TAG)?;
+        if version != VERSION {
+            return Err(SiweError::UnsupportedVersion(version.to_string()));
+        }
+        let chain_id = tagged_line(&mut lines, CHAIN_ID_TAG)?;
+        let chain_id = chain_id
+            .parse()
+            .ok()
+            .filter(|_| chain_id.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or_else(|| SiweError::InvalidField {
+                field: "chain ID",
+                value: chain_id.to_string(),
+            })?;
+        let nonce = check(tagged_line(&mut lines, NONCE_TAG)?, "nonce", |nonce| {
+            nonce.len() >= 8 && nonce.bytes().all(|b| b.is_ascii_alphanumeric())
+        })?;
+        let issued_at = tagged_line(&mut lines, ISSUED_AT_TAG)?.parse()?;
+
+        let expiration_time =
+            optional_line(&mut lines, EXPIRATION_TIME_TAG).map(str::parse).transpose()?;
+        let not_before = optional_line(&mut lines, NOT_BEFORE_TAG).map(str::parse).transpose()?;
+        let request_id = optional_line(&mut lines, REQUEST_ID_TAG)
+            .map(|request_id| check(request_id, "request ID", |id| id.chars().all(is_pchar)))
+            .transpose()?;
+        let mut resources = Vec::new();
+        if lines.next_if_eq(&RESOURCES_TAG).is_some() {
+            while let Some(resource) = optional_line(&mut lines, RESOURCE_PREFIX) {
+                resources.push(check(resource, "resource", is_uri)?);
+            }
+        }
+        if let Some(line) = lines.next() {
+            return Err(unexpected_line("the end of the message", line));
+        }
+
+        Ok(Self {
+            scheme,
+            domain,
+            address,
+            statement,
+            uri,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+/// Options of [`SiweMessage::validate`] and [`SiweMessage::verify`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationOpts {
+    /// The expected domain, usually the domain of the backend.
+    pub domain: Option<String>,
+    /// The expected nonce, usually the nonce issued for the session.
+    pub nonce: Option<String>,
+    /// The expected chain ID.
+    pub chain_id: Option<ChainId>,
+    /// The time at which the message must be valid, the current time if `None`.
+    pub timestamp: Option<Timestamp>,
+}
+
+/// The lines of a message being parsed.
+type Lines<'a> = Peekable<Split<'a, char>>;
+
+fn unexpected_line(expected: &'static str, line: &str) -> SiweError {
+    SiweError::UnexpectedLine { expected, got: Some(line.to_string()) }
+}
+
+/// Returns the next line, which must exist.
+fn next_line<'a>(lines: &mut Lines<'a>, expected: &'static str) -> Result<&'a str, SiweError> {
+    lines.next().ok_or(SiweError::UnexpectedLine { expected, got: None })
+}
+
+/// Consumes the next line, which must be empty.
+fn empty_line(lines: &mut Lines<'_>) -> Result<(), SiweError> {
+    match next_line(lines, "an empty line")? {
+        "" => Ok(()),
+        line => Err(unexpected_line("an empty line", line)),
+    }
+}
+
+/// Returns the value of the next line, which must start with `tag`.
+fn tagged_line<'a>(lines: &mut Lines<'a>, tag: &'static str) -> Result<&'a str, SiweError> {
+    let line = next_line(lines, tag)?;
+    line.strip_prefix(tag).ok_or_else(|| unexpected_line(tag, line))
+}
+
+/// Returns the value of the next line and consumes it, if it starts with `tag`.
+fn optional_line<'a>(lines: &mut Lines<'a>, tag: &str) -> Option<&'a str> {
+    let value = lines.peek()?.strip_prefix(tag)?;
+    lines.next();
+    Some(value)
+}
+
+/// Returns the value if it matches the grammar of the field.
+fn check(
+    value: &str,
+    field: &'static str,
+    is_valid: impl Fn(&str) -> bool,
+) -> Result<String, SiweError> {
+    if value.is_empty() || !is_valid(value) {
+        return Err(SiweError::InvalidField { field, value: value.to_string() });
+    }
+    Ok(value.to_string())
+}
+
+/// `ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )` of RFC 3986.
+fn is_scheme(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic())
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+}
+
+/// `unreserved / pct-encoded / sub-delims / ":" / "@"` of RFC 3986.
+fn is_pchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~%!$&'()*+,;=:@".contains(c)
+}
+
+/// An authority of RFC 3986: `[ userinfo "@" ] host [ ":" port ]`, including IP literals.
+fn is_authority(s: &str) -> bool {
+    s.chars().all(|c| is_pchar(c) || c == '[' || c == ']')
+}
+
+/// A URI of RFC 3986: a scheme, a colon, and characters allowed in URIs.
+fn is_uri(s: &str) -> bool {
+    s.split_once(':').is_some_and(|(scheme, rest)| {
+        is_scheme(scheme) && rest.chars().all(|c| is_pchar(c) || "/?#[]".contains(c))
+    })
+}
+
+/// Parses an RFC 3339 date-time into seconds since the Unix epoch.
+fn parse_rfc3339(s: &str) -> Option<i64> {
+    let bytes = s.as_bytes();
+    let number = |start: usize, len: usize| {
+        let digits = s.get(start..start + len)?;
+        digits.bytes().all(|b| b.is_ascii_digit()).then(|| digits.parse::<i64>().ok())?
+    };
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+    let (year, month, day) = (number(0, 4)?, number(5, 2)?, number(8, 2)?);
+    let (hour, minute, second) = (number(11, 2)?, number(14, 2)?, number(17, 2)?);
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    let mut rest = &s[19..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        rest = &fraction[digits..];
+    }
+    let offset = match rest.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let (hours, minutes) = (number(s.len() - 5, 2)?, number(s.len() - 2, 2)?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+    Some(days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+const fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since the Unix epoch of a date of the proleptic Gregorian calendar.
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of [`days_from_civil`].
+const fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    // The example of EIP-4361.
+    const MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    #[test]
+    fn timestamps() {
+        let timestamp: Timestamp = "2021-09-30T16:25:24Z".parse().unwrap();
+        assert_eq!(timestamp.unix_timestamp(), 1_633_019_124);
+        assert_eq!(Timestamp::from_unix(1_633_019_124).unwrap(), timestamp);
+        assert_eq!(Timestamp::from_unix(0).unwrap().as_str(), "1970-01-01T00:00:00Z");
+        assert_eq!(Timestamp::from_unix(951_782_400).unwrap().as_str(), "2000-02-29T00:00:00Z");
+
+        let offset: Timestamp = "2021-09-30T18:25:24.123+02:00".parse().unwrap();
+        assert_eq!(offset.unix_timestamp(), timestamp.unix_timestamp());
+        assert_eq!(offset.to_string(), "2021-09-30T18:25:24.123+02:00");
+
+        for invalid in [
+            "2021-09-30",
+            "2021-09-30 16:25:24Z",
+            "2021-02-29T16:25:24Z",
+            "2021-09-30T24:00:00Z",
+            "2021-09-30T16:25:24",
+            "2021-09-30T16:25:24.Z",
+            "2021-09-30T16:25:24+0200",
+        ] {
+            assert!(invalid.parse::<Timestamp>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn eip_example() {
+        let message: SiweMessage = MESSAGE.parse().unwrap();
+        assert_eq!(
+            message,
+            SiweMessage::new(
+                "service.org",
+                address!("e5A12547fe4E872D192E3eCecb76F2Ce1aeA4946"),
+                "https://service.org/login",
+                1,
+                "32891756",
+                "2021-09-30T16:25:24Z".parse().unwrap(),
+            )
+            .with_statement("I accept the ServiceOrg Terms of Service: https://service.org/tos")
+            .with_resources([
+                "ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/",
+                "https://example.com/my-web2-claim.json",
+            ])
+        );
+        assert_eq!(message.to_string(), MESSAGE);
+    }
+
+    #[test]
+    fn optional_fields() {
+        let message = SiweMessage {
+            scheme: Some("https".into()),
+            ..SiweMessage::new(
+                "localhost:4361",
+                address!("e5A12547fe4E872D192E3eCecb76F2Ce1aeA4946"),
+                "urn:uuid:a6d0bd52-0ea2-4f06-9e07-6a5c3a4b2ac8",
+                5,
+                "abcdefgh12",
+                "2021-09-30T16:25:24Z".parse().unwrap(),
+            )
+        }
+        .with_expiration_time("2021-10-01T00:00:00Z".parse().unwrap())
+        .with_not_before("2021-09-30T17:00:00Z".parse().unwrap())
+        .with_request_id("some-id");
+        let text = message.to_string();
+        assert!(text.starts_with("https://localhost:4361 wants you"));
+        assert!(text.contains("0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946\n\n\nURI: "));
+        assert!(text.ends_with("Not Before: 2021-09-30T17:00:00Z\nRequest ID: some-id"));
+        assert_eq!(text.parse::<SiweMessage>().unwrap(), message);
+
+        let at = |timestamp: &str| VerificationOpts {
+            timestamp: Some(timestamp.parse().unwrap()),
+            ..Default::default()
+        };
+        assert!(message.validate(&at("2021-09-30T18:00:00Z")).is_ok());
+        assert!(matches!(
+            message.validate(&at("2021-09-30T16:30:00Z")),
+            Err(SiweError::NotYetValid(_))
+        ));
+        assert!(matches!(
+            message.validate(&at("2021-10-01T00:00:00Z")),
+            Err(SiweError::Expired(_))
+        ));
+        let opts = VerificationOpts { chain_id: Some(1), ..at("2021-09-30T18:00:00Z") };
+        assert!(matches!(
+            message.validate(&opts),
+            Err(SiweError::ChainIdMismatch { expected: 1, got: 5 })
+        ));
+    }
+
+    #[test]
+    fn invalid_messages() {
+        let replace = |from: &str, to: &str| MESSAGE.replacen(from, to, 1);
+        for invalid in [
+            String::new(),
+            replace("service.org wants", "service.org/path wants"),
+            replace(
+                "0xe5A12547fe4E872D192E3eCecb76F2Ce1aeA4946",
+                "0xe5a12547fe4e872d192e3ececb76f2ce1aea4946",
+            ),
+            replace("Service: https://service.org/tos\n", "Service: https://service.org/tos"),
+            replace("URI: https://service.org/login", "URI: /login"),
+            replace("Version: 1", "Version: 2"),
+            replace("Chain ID: 1", "Chain ID: +1"),
+            replace("Nonce: 32891756", "Nonce: 1234"),
+            replace("Issued At: 2021-09-30T16:25:24Z", "Issued At: yesterday"),
+            format!("{MESSAGE}\nRequest ID: id"),
+            format!("{MESSAGE}\n"),
+        ] {
+            assert!(invalid.parse::<SiweMessage>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn verify_signature() {
+        let key = k256::ecdsa::SigningKey::from_slice(&[1; 32]).unwrap();
+        let message = SiweMessage {
+            address: Address::from_private_key(&key),
+            ..MESSAGE.parse::<SiweMessage>().unwrap()
+        };
+        let (signature, recid) = key.sign_prehash_recoverable(message.hash().as_slice()).unwrap();
+        let signature = Signature::from((signature, recid));
+        let opts = VerificationOpts {
+            domain: Some("service.org".into()),
+            nonce: Some("32891756".into()),
+            timestamp: Some("2021-09-30T16:30:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        message.verify(&signature, &opts).unwrap();
+        assert!(matches!(
+            message.verify(
+                &signature,
+                &VerificationOpts { nonce: Some("other123".into()), ..opts.clone() }
+            ),
+            Err(SiweError::NonceMismatch { .. })
+        ));
+
+        let other = SiweMessage { address: Address::with_last_byte(1), ..message };
+        assert!(matches!(other.verify(&signature, &opts), Err(SiweError::SignerMismatch { .. })));
+    }
+}
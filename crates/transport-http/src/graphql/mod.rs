@@ -0,0 +1,258 @@
+//! A client for the [EIP-1767] GraphQL endpoint of Ethereum nodes.
+//!
+//! GraphQL lets a single request select nested data, e.g. the blocks of a range with the
+//! receipts and logs of all their transactions, which takes one JSON-RPC request per block and
+//! transaction otherwise.
+//!
+//! [EIP-1767]: https://eips.ethereum.org/EIPS/eip-1767
+
+use alloy_json_rpc::RpcError;
+use alloy_transport::{TransportErrorKind, TransportResult};
+use reqwest::Client;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use tracing::{debug, trace};
+use url::Url;
+
+mod query;
+pub use query::{BlockFields, BlockQuery, BlocksQuery, LogFilter};
+
+mod types;
+pub use types::{Block, BlockRef, Log, LogTransaction, Transaction};
+
+/// A GraphQL request: a query document and the values of its variables.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlRequest {
+    /// The query document.
+    pub query: String,
+    /// The values of the variables of the query.
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub variables: Map<String, Value>,
+    /// The operation to execute, if the document holds several.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+}
+
+impl GraphQlRequest {
+    /// Creates a new request for the given query document.
+    pub fn new(query: impl Into<String>) -> Self {
+        Self { query: query.into(), ..Default::default() }
+    }
+
+    /// Sets the value of a variable of the query.
+    pub fn variable(mut self, name: impl Into<String>, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).expect("variables serialize to JSON");
+        self.variables.insert(name.into(), value);
+        self
+    }
+}
+
+/// A GraphQL response, holding the data of the query and the errors raised executing it.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct GraphQlResponse<T> {
+    /// The data of the query, `None` if it could not be executed.
+    pub data: Option<T>,
+    /// The errors raised executing the query.
+    #[serde(default)]
+    pub errors: Vec<GraphQlError>,
+}
+
+/// An error raised executing a GraphQL query.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphQlError {
+    /// The description of the error.
+    pub message: String,
+    /// The path of the field the error was raised for, if any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<Value>,
+}
+
+impl fmt::Display for GraphQlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+        if !self.path.is_empty() {
+            let path = self.path.iter().map(|segment| match segment {
+                Value::String(field) => field.clone(),
+                segment => segment.to_string(),
+            });
+            write!(f, " at {}", path.collect::<Vec<_>>().join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/// The errors of a GraphQL response, returned as a [`TransportErrorKind::Custom`] error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphQlErrors(pub Vec<GraphQlError>);
+
+impl fmt::Display for GraphQlErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("graphql query failed: ")?;
+        for (i, err) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            err.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GraphQlErrors {}
+
+/// A client for the GraphQL endpoint of a node, usually served at `/graphql`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy_transport_http::graphql::{BlocksQuery, GraphQlClient};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = GraphQlClient::new("http://localhost:8545/graphql".parse()?);
+/// // Fetches 100 blocks with the receipts and logs of their transactions in one request.
+/// let blocks = client.blocks(BlocksQuery::new(1_000, 1_099).with_receipts().with_logs()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GraphQlClient {
+    client: Client,
+    url: Url,
+}
+
+impl GraphQlClient {
+    /// Creates a new client for the GraphQL endpoint at `url`.
+    pub fn new(url: Url) -> Self {
+        Self { client: Client::new(), url }
+    }
+
+    /// Creates a new client for the GraphQL endpoint at `url`, with a custom [`reqwest`] client.
+    pub const fn with_client(client: Client, url: Url) -> Self {
+        Self { client, url }
+    }
+
+    /// Get a reference to the URL of the endpoint.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Executes a query, deserializing its data as `T`.
+    ///
+    /// Responses with errors are rejected with [`GraphQlErrors`], even when they hold partial
+    /// data.
+    pub async fn query<T: DeserializeOwned>(&self, request: &GraphQlRequest) -> TransportResult<T> {
+        let resp = self
+            .client
+            .post(self.url.clone())
+            .json(request)
+            .send()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        let status = resp.status();
+        debug!(%status, "received graphql response from server");
+
+        let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+        trace!(body = %String::from_utf8_lossy(&body), "graphql response body");
+
+        // Query errors are returned with a 400 status and a body describing them.
+        if !status.is_success() && status != reqwest::StatusCode::BAD_REQUEST {
+            return Err(TransportErrorKind::http_error(
+                status.as_u16(),
+                String::from_utf8_lossy(&body).into_owned(),
+            ));
+        }
+        parse_response(&body)
+    }
+
+    /// Returns the block selected by the query, `None` if it does not exist.
+    pub async fn block(&self, query: BlockQuery) -> TransportResult<Option<Block>> {
+        #[derive(Deserialize)]
+        struct Data {
+            block: Option<Block>,
+        }
+        self.query::<Data>(&query.into_request()).await.map(|data| data.block)
+    }
+
+    /// Returns the blocks of the range selected by the query.
+    pub async fn blocks(&self, query: BlocksQuery) -> TransportResult<Vec<Block>> {
+        #[derive(Deserialize)]
+        struct Data {
+            blocks: Vec<Block>,
+        }
+        self.query::<Data>(&query.into_request()).await.map(|data| data.blocks)
+    }
+
+    /// Returns the logs matching the filter, with the transaction and block of each.
+    pub async fn logs(&self, filter: LogFilter) -> TransportResult<Vec<Log>> {
+        #[derive(Deserialize)]
+        struct Data {
+            logs: Vec<Log>,
+        }
+        self.query::<Data>(&filter.into_request()).await.map(|data| data.logs)
+    }
+}
+
+/// Parses the body of a GraphQL response.
+fn parse_response<T: DeserializeOwned>(body: &[u8]) -> TransportResult<T> {
+    let response: GraphQlResponse<T> = serde_json::from_slice(body)
+        .map_err(|err| RpcError::deser_err(err, String::from_utf8_lossy(body)))?;
+    if !response.errors.is_empty() {
+        return Err(TransportErrorKind::custom(GraphQlErrors(response.errors)));
+    }
+    response.data.ok_or(RpcError::NullResp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Data {
+        block: Option<Block>,
+    }
+
+    #[test]
+    fn parses_data() {
+        let body = br#"{"data":{"block":null}}"#;
+        assert!(parse_response::<Data>(body).unwrap().block.is_none());
+    }
+
+    #[test]
+    fn rejects_errors() {
+        let body = br#"{
+            "errors": [
+                { "message": "invalid block number", "path": ["block", "number"] },
+                { "message": "timeout" }
+            ],
+            "data": { "block": null }
+        }"#;
+        let err = parse_response::<Data>(body).unwrap_err();
+        let RpcError::Transport(TransportErrorKind::Custom(errors)) = &err else { unreachable!() };
+        assert_eq!(errors.downcast_ref::<GraphQlErrors>().unwrap().0.len(), 2);
+        assert_eq!(
+            err.to_string(),
+            "graphql query failed: invalid block number at block.number; timeout"
+        );
+    }
+
+    #[test]
+    fn rejects_missing_data() {
+        assert!(matches!(parse_response::<Data>(br#"{"data":null}"#), Err(RpcError::NullResp)));
+    }
+
+    #[test]
+    fn serializes_request() {
+        let request = GraphQlRequest::new("{ syncing { currentBlock } }");
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"query":"{ syncing { currentBlock } }"}"#
+        );
+        let request = request.variable("number", "0x1");
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"query":"{ syncing { currentBlock } }","variables":{"number":"0x1"}}"#
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Typed builders of queries for the node GraphQL schema.
+
+use super::GraphQlRequest;
+use alloy_primitives::{Address, B256};
+use serde::Serialize;
+
+/// The fields selected on a block.
+const BLOCK_FIELDS: &str = "number hash parent { hash } timestamp gasLimit gasUsed baseFeePerGas \
+                            miner { address } logsBloom transactionCount";
+
+/// The fields selected on a transaction.
+const TRANSACTION_FIELDS: &str = "hash index nonce type from { address } to { address } value \
+                                  gas gasPrice maxFeePerGas maxPriorityFeePerGas inputData";
+
+/// The receipt fields selected on a transaction.
+const RECEIPT_FIELDS: &str =
+    "status gasUsed cumulativeGasUsed effectiveGasPrice createdContract { address }";
+
+/// The fields selected on a log.
+const LOG_FIELDS: &str = "index account { address } topics data";
+
+/// The nested objects selected on the blocks of a query.
+///
+/// Selecting more only grows the response: the node resolves the nested objects itself, in the
+/// same round trip.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlockFields {
+    /// Selects the transactions of the blocks.
+    pub transactions: bool,
+    /// Selects the receipt fields of the transactions, implies `transactions`.
+    pub receipts: bool,
+    /// Selects the logs of the transactions, implies `transactions`.
+    pub logs: bool,
+}
+
+impl BlockFields {
+    /// Selects no nested object.
+    const NONE: Self = Self { transactions: false, receipts: false, logs: false };
+
+    /// Returns the selection set of a block.
+    fn selection(&self) -> String {
+        let mut selection = format!("{{ {BLOCK_FIELDS}");
+        if self.transactions || self.receipts || self.logs {
+            selection.push_str(" transactions { ");
+            selection.push_str(TRANSACTION_FIELDS);
+            if self.receipts {
+                selection.push(' ');
+                selection.push_str(RECEIPT_FIELDS);
+            }
+            if self.logs {
+                selection.push_str(" logs { ");
+                selection.push_str(LOG_FIELDS);
+                selection.push_str(" }");
+            }
+            selection.push_str(" }");
+        }
+        selection.push_str(" }");
+        selection
+    }
+}
+
+/// A query of a single block, by number or hash, or the latest one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlockQuery {
+    number: Option<u64>,
+    hash: Option<B256>,
+    fields: BlockFields,
+}
+
+impl BlockQuery {
+    /// Queries the latest block.
+    pub const fn latest() -> Self {
+        Self { number: None, hash: None, fields: BlockFields::NONE }
+    }
+
+    /// Queries the block with the given number.
+    pub const fn number(number: u64) -> Self {
+        Self { number: Some(number), ..Self::latest() }
+    }
+
+    /// Queries the block with the given hash.
+    pub const fn hash(hash: B256) -> Self {
+        Self { hash: Some(hash), ..Self::latest() }
+    }
+
+    /// Selects the transactions of the block.
+    pub const fn with_transactions(mut self) -> Self {
+        self.fields.transactions = true;
+        self
+    }
+
+    /// Selects the transactions of the block with their receipt fields.
+    pub const fn with_receipts(mut self) -> Self {
+        self.fields.receipts = true;
+        self
+    }
+
+    /// Selects the transactions of the block with their logs.
+    pub const fn with_logs(mut self) -> Self {
+        self.fields.logs = true;
+        self
+    }
+
+    /// Returns the nested objects selected on the block.
+    pub const fn fields(&self) -> BlockFields {
+        self.fields
+    }
+
+    /// Builds the request of the query.
+    pub fn into_request(self) -> GraphQlRequest {
+        let query = format!(
+            "query($number: Long, $hash: Bytes32) {{ block(number: $number, hash: $hash) {} }}",
+            self.fields.selection()
+        );
+        let mut request = GraphQlRequest::new(query);
+        if let Some(number) = self.number {
+            request = request.variable("number", Long(number));
+        }
+        if let Some(hash) = self.hash {
+            request = request.variable("hash", hash);
+        }
+        request
+    }
+}
+
+/// A query of a range of blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlocksQuery {
+    from: u64,
+    to: Option<u64>,
+    fields: BlockFields,
+}
+
+impl BlocksQuery {
+    /// Queries the blocks from `from` to `to`, both included.
+    pub const fn new(from: u64, to: u64) -> Self {
+        Self { from, to: Some(to), fields: BlockFields::NONE }
+    }
+
+    /// Queries the blocks from `from` to the latest one.
+    pub const fn from(from: u64) -> Self {
+        Self { from, to: None, fields: BlockFields::NONE }
+    }
+
+    /// Selects the transactions of the blocks.
+    pub const fn with_transactions(mut self) -> Self {
+        self.fields.transactions = true;
+        self
+    }
+
+    /// Selects the transactions of the blocks with their receipt fields.
+    pub const fn with_receipts(mut self) -> Self {
+        self.fields.receipts = true;
+        self
+    }
+
+    /// Selects the transactions of the blocks with their logs.
+    pub const fn with_logs(mut self) -> Self {
+        self.fields.logs = true;
+        self
+    }
+
+    /// Returns the nested objects selected on the blocks.
+    pub const fn fields(&self) -> BlockFields {
+        self.fields
+    }
+
+    /// Builds the request of the query.
+    pub fn into_request(self) -> GraphQlRequest {
+        let query = format!(
+            "query($from: Long!, $to: Long) {{ blocks(from: $from, to: $to) {} }}",
+            self.fields.selection()
+        );
+        let request = GraphQlRequest::new(query).variable("from", Long(self.from));
+        match self.to {
+            Some(to) => request.variable("to", Long(to)),
+            None => request,
+        }
+    }
+}
+
+/// A query of the logs matching a filter, selecting the transaction and block of each.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from_block: Option<Long>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to_block: Option<Long>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    addresses: Vec<Address>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    topics: Vec<Vec<B256>>,
+}
+
+impl LogFilter {
+    /// Creates a new filter matching all the logs of the latest block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the first block to include.
+    pub const fn from_block(mut self, block: u64) -> Self {
+        self.from_block = Some(Long(block));
+        self
+    }
+
+    /// Sets the last block to include.
+    pub const fn to_block(mut self, block: u64) -> Self {
+        self.to_block = Some(Long(block));
+        self
+    }
+
+    /// Only matches the logs emitted by `address`, in addition to the other addresses set.
+    pub fn address(mut self, address: Address) -> Self {
+        self.addresses.push(address);
+        self
+    }
+
+    /// Only matches the logs whose topic at `index` is one of `topics`.
+    ///
+    /// An empty list of topics matches any topic.
+    pub fn topic(mut self, index: usize, topics: impl IntoIterator<Item = B256>) -> Self {
+        if self.topics.len() <= index {
+            self.topics.resize(index + 1, Vec::new());
+        }
+        self.topics[index] = topics.into_iter().collect();
+        self
+    }
+
+    /// Builds the request of the query.
+    pub fn into_request(self) -> GraphQlRequest {
+        let query = format!(
+            "query($filter: FilterCriteria!) {{ logs(filter: $filter) {{ {LOG_FIELDS} \
+             transaction {{ hash index block {{ number hash }} }} }} }}"
+        );
+        GraphQlRequest::new(query).variable("filter", self)
+    }
+}
+
+/// A `Long` scalar, serialized as a hex string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+struct Long(#[serde(with = "alloy_serde::quantity")] u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn block_query() {
+        let request = BlockQuery::latest().into_request();
+        assert_eq!(
+            request.query,
+            format!(
+                "query($number: Long, $hash: Bytes32) {{ block(number: $number, hash: $hash) {{ \
+                 {BLOCK_FIELDS} }} }}"
+            )
+        );
+        assert!(request.variables.is_empty());
+
+        let request = BlockQuery::number(16).into_request();
+        assert_eq!(serde_json::Value::Object(request.variables), json!({ "number": "0x10" }));
+
+        let hash = B256::with_last_byte(1);
+        let request = BlockQuery::hash(hash).into_request();
+        assert_eq!(serde_json::Value::Object(request.variables), json!({ "hash": hash }));
+    }
+
+    #[test]
+    fn blocks_query_selects_nested_objects() {
+        let query = BlocksQuery::new(1, 2).with_transactions();
+        assert_eq!(
+            query.fields().selection(),
+            format!("{{ {BLOCK_FIELDS} transactions {{ {TRANSACTION_FIELDS} }} }}")
+        );
+
+        let request = query.with_receipts().with_logs().into_request();
+        assert!(request.query.starts_with(
+            "query($from: Long!, $to: Long) { blocks(from: $from, to: $to) { number hash"
+        ));
+        assert!(request.query.ends_with(&format!(
+            "transactions {{ {TRANSACTION_FIELDS} {RECEIPT_FIELDS} logs {{ {LOG_FIELDS} }} }} }} }}"
+        )));
+        assert_eq!(
+            serde_json::Value::Object(request.variables),
+            json!({ "from": "0x1", "to": "0x2" })
+        );
+
+        let request = BlocksQuery::from(1).with_logs().into_request();
+        assert_eq!(serde_json::Value::Object(request.variables), json!({ "from": "0x1" }));
+    }
+
+    #[test]
+    fn log_filter() {
+        let topic = B256::with_last_byte(2);
+        let request = LogFilter::new()
+            .from_block(10)
+            .to_block(20)
+            .address(Address::with_last_byte(1))
+            .topic(1, [topic])
+            .into_request();
+        assert!(request
+            .query
+            .starts_with("query($filter: FilterCriteria!) { logs(filter: $filter)"));
+        assert_eq!(
+            serde_json::Value::Object(request.variables),
+            json!({ "filter": {
+                "fromBlock": "0xa",
+                "toBlock": "0x14",
+                "addresses": [Address::with_last_byte(1)],
+                "topics": [[], [topic]],
+            } })
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! The objects of the node GraphQL schema, as selected by the queries of this module.
+
+use alloy_primitives::{Address, Bloom, Bytes, B256, U256};
+use serde::{Deserialize, Deserializer};
+
+/// A block, as selected by [`BlockQuery`](super::BlockQuery) and
+/// [`BlocksQuery`](super::BlocksQuery).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Block {
+    /// The number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The hash of the parent block, `None` for the genesis block.
+    #[serde(rename = "parent", deserialize_with = "parent_hash")]
+    pub parent_hash: Option<B256>,
+    /// The timestamp of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub timestamp: u64,
+    /// The gas limit of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// The gas used by the transactions of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The base fee of the block, after London.
+    pub base_fee_per_gas: Option<U256>,
+    /// The beneficiary of the block.
+    #[serde(deserialize_with = "account")]
+    pub miner: Address,
+    /// The bloom filter of the logs of the block.
+    pub logs_bloom: Bloom,
+    /// The number of transactions of the block.
+    #[serde(with = "alloy_serde::quantity::opt")]
+    pub transaction_count: Option<u64>,
+    /// The transactions of the block, if selected.
+    #[serde(default)]
+    pub transactions: Option<Vec<Transaction>>,
+}
+
+/// A transaction of a [`Block`].
+///
+/// The receipt fields are only set if selected, with
+/// [`BlockFields::receipts`](super::BlockFields::receipts).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The index of the transaction in its block.
+    #[serde(with = "alloy_serde::quantity::opt")]
+    pub index: Option<u64>,
+    /// The nonce of the sender of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// The [EIP-2718] type of the transaction.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    #[serde(rename = "type", default, with = "alloy_serde::quantity::opt")]
+    pub ty: Option<u64>,
+    /// The sender of the transaction.
+    #[serde(deserialize_with = "account")]
+    pub from: Address,
+    /// The recipient of the transaction, `None` for contract creations.
+    #[serde(deserialize_with = "opt_account")]
+    pub to: Option<Address>,
+    /// The value transferred, in wei.
+    pub value: U256,
+    /// The gas limit of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    /// The gas price of the transaction, the effective one for [EIP-1559] transactions.
+    ///
+    /// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+    pub gas_price: U256,
+    /// The maximum fee per gas of [EIP-1559] transactions.
+    ///
+    /// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+    #[serde(default)]
+    pub max_fee_per_gas: Option<U256>,
+    /// The maximum priority fee per gas of [EIP-1559] transactions.
+    ///
+    /// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// The input data of the transaction.
+    pub input_data: Bytes,
+    /// The status of the receipt, `1` for success and `0` for failure.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub status: Option<u64>,
+    /// The gas used by the transaction.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub gas_used: Option<u64>,
+    /// The gas used by the transaction and the ones before it in the block.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub cumulative_gas_used: Option<u64>,
+    /// The gas price paid by the transaction.
+    #[serde(default)]
+    pub effective_gas_price: Option<U256>,
+    /// The contract created by the transaction, if any.
+    #[serde(default, deserialize_with = "opt_account")]
+    pub created_contract: Option<Address>,
+    /// The logs emitted by the transaction, if selected.
+    #[serde(default)]
+    pub logs: Option<Vec<Log>>,
+}
+
+/// A log, as selected by [`LogFilter`](super::LogFilter) or nested in a [`Transaction`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Log {
+    /// The index of the log in its block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub index: u64,
+    /// The address of the contract that emitted the log.
+    #[serde(rename = "account", deserialize_with = "account")]
+    pub address: Address,
+    /// The topics of the log.
+    pub topics: Vec<B256>,
+    /// The data of the log.
+    pub data: Bytes,
+    /// The transaction that emitted the log, only selected by [`LogFilter`](super::LogFilter).
+    #[serde(default)]
+    pub transaction: Option<LogTransaction>,
+}
+
+/// The transaction that emitted a [`Log`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct LogTransaction {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The index of the transaction in its block.
+    #[serde(with = "alloy_serde::quantity::opt")]
+    pub index: Option<u64>,
+    /// The block of the transaction.
+    pub block: Option<BlockRef>,
+}
+
+/// The number and hash of a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub struct BlockRef {
+    /// The number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: B256,
+}
+
+/// An `Account` object, of which only the address is selected.
+#[derive(Deserialize)]
+struct Account {
+    address: Address,
+}
+
+fn account<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+    Account::deserialize(deserializer).map(|account| account.address)
+}
+
+fn opt_account<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Address>, D::Error> {
+    Option::<Account>::deserialize(deserializer).map(|account| account.map(|a| a.address))
+}
+
+fn parent_hash<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<B256>, D::Error> {
+    #[derive(Deserialize)]
+    struct Parent {
+        hash: B256,
+    }
+    Option::<Parent>::deserialize(deserializer).map(|parent| parent.map(|p| p.hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn deserialize_block() {
+        let s = r#"{
+            "number": "0x10",
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000010",
+            "parent": { "hash": "0x000000000000000000000000000000000000000000000000000000000000000f" },
+            "timestamp": "0x65f1a9b0",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x5208",
+            "baseFeePerGas": "0x7",
+            "miner": { "address": "0x0000000000000000000000000000000000000001" },
+            "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "transactionCount": "0x1",
+            "transactions": [{
+                "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "index": "0x0",
+                "nonce": "0x2",
+                "type": "0x2",
+                "from": { "address": "0x0000000000000000000000000000000000000002" },
+                "to": null,
+                "value": "0x0",
+                "gas": "0x5208",
+                "gasPrice": "0x8",
+                "maxFeePerGas": "0xa",
+                "maxPriorityFeePerGas": "0x1",
+                "inputData": "0x",
+                "status": "0x1",
+                "gasUsed": "0x5208",
+                "cumulativeGasUsed": "0x5208",
+                "effectiveGasPrice": "0x8",
+                "createdContract": { "address": "0x0000000000000000000000000000000000000003" },
+                "logs": [{
+                    "index": "0x0",
+                    "account": { "address": "0x0000000000000000000000000000000000000003" },
+                    "topics": ["0x0000000000000000000000000000000000000000000000000000000000000004"],
+                    "data": "0x01"
+                }]
+            }]
+        }"#;
+        let block: Block = serde_json::from_str(s).unwrap();
+        assert_eq!(block.number, 16);
+        assert_eq!(
+            block.parent_hash,
+            Some(b256!("000000000000000000000000000000000000000000000000000000000000000f"))
+        );
+        assert_eq!(block.miner, Address::with_last_byte(1));
+        assert_eq!(block.base_fee_per_gas, Some(U256::from(7)));
+
+        let tx = &block.transactions.unwrap()[0];
+        assert_eq!(tx.ty, Some(2));
+        assert_eq!(tx.to, None);
+        assert_eq!(tx.status, Some(1));
+        assert_eq!(tx.created_contract, Some(address!("0000000000000000000000000000000000000003")));
+        let logs = tx.logs.as_ref().unwrap();
+        assert_eq!(logs[0].address, Address::with_last_byte(3));
+        assert_eq!(logs[0].transaction, None);
+    }
+
+    #[test]
+    fn deserialize_log_with_transaction() {
+        let s = r#"{
+            "index": 3,
+            "account": { "address": "0x0000000000000000000000000000000000000001" },
+            "topics": [],
+            "data": "0x",
+            "transaction": {
+                "hash": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                "index": 1,
+                "block": {
+                    "number": 16,
+                    "hash": "0x0000000000000000000000000000000000000000000000000000000000000010"
+                }
+            }
+        }"#;
+        // Older nodes return `Long` scalars as numbers.
+        let log: Log = serde_json::from_str(s).unwrap();
+        assert_eq!(log.index, 3);
+        let tx = log.transaction.unwrap();
+        assert_eq!(tx.index, Some(1));
+        assert_eq!(tx.block.unwrap().number, 16);
+    }
+}
@@ -8,8 +8,14 @@ use hyper::{
     body::{Buf, Bytes},
     header,
 };
-use hyper_util::client::legacy::{connect::Connect, Client};
-use std::task;
+use hyper_util::{
+    client::legacy::{
+        connect::{Connect, HttpConnector},
+        Client,
+    },
+    rt::{TokioExecutor, TokioTimer},
+};
+use std::{task, time::Duration};
 use tower::Service;
 use tracing::{debug, debug_span, trace, Instrument};
 
@@ -25,6 +31,16 @@ pub type HyperTransport = Http<HyperClient>;
 /// Connection details for a [`HyperTransport`].
 pub type HyperConnect = HttpConnect<HyperTransport>;
 
+/// A [`hyper`] HTTP client supporting HTTPS with [`rustls`], built by
+/// [`HyperClientBuilder::build_tls`].
+#[cfg(feature = "hyper-rustls")]
+pub type HyperTlsClient =
+    Client<hyper_rustls::HttpsConnector<HttpConnector>, http_body_util::Full<::hyper::body::Bytes>>;
+
+/// An [`Http`] transport using [`hyper`] with HTTPS support.
+#[cfg(feature = "hyper-rustls")]
+pub type HyperTlsTransport = Http<HyperTlsClient>;
+
 impl TransportConnect for HyperConnect {
     type Transport = HyperTransport;
 
@@ -35,14 +51,203 @@ impl TransportConnect for HyperConnect {
     fn get_transport<'a: 'b, 'b>(
         &'a self,
     ) -> alloy_transport::Pbf<'b, Self::Transport, TransportError> {
-        let executor = hyper_util::rt::TokioExecutor::new();
-
-        let client = hyper_util::client::legacy::Client::builder(executor).build_http();
+        let client = HyperClientBuilder::new().build();
 
         Box::pin(async move { Ok(Http::with_client(client, self.url.clone())) })
     }
 }
 
+/// The default timeout of idle pooled connections of [`hyper_util`].
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// A builder of a [`HyperClient`], configuring its connections.
+///
+/// Options that are not set keep the defaults of [`hyper_util`]. With many concurrent requests to
+/// the same endpoint, raising the number of pooled connections, or multiplexing requests over
+/// HTTP/2 connections, avoids opening a new connection for each request.
+///
+/// # Examples
+///
+/// ```
+/// use alloy_transport_http::{Http, HyperClientBuilder};
+/// use std::time::Duration;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = HyperClientBuilder::new()
+///     .http2_prior_knowledge()
+///     .http2_keep_alive_interval(Duration::from_secs(20))
+///     .pool_idle_timeout(Duration::from_secs(60))
+///     .build();
+/// let transport = Http::with_client(client, "http://localhost:8545".parse()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HyperClientBuilder {
+    http2_prior_knowledge: bool,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+    tcp_nodelay: bool,
+    connect_timeout: Option<Duration>,
+    happy_eyeballs_timeout: Option<Option<Duration>>,
+    http2_keep_alive_interval: Option<Duration>,
+    http2_keep_alive_timeout: Option<Duration>,
+    http2_keep_alive_while_idle: bool,
+}
+
+impl HyperClientBuilder {
+    /// Creates a new builder with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only uses HTTP/2, without negotiating it, for endpoints known to support it.
+    ///
+    /// Requests are then multiplexed over a single connection to the endpoint. Since there is no
+    /// TLS support, this is HTTP/2 over cleartext, e.g. to a node or a proxy on a private network.
+    pub const fn http2_prior_knowledge(mut self) -> Self {
+        self.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept in the pool for each host.
+    pub const fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Sets how long idle connections are kept in the pool.
+    pub const fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the interval of the TCP keep-alive probes of the connections.
+    ///
+    /// Defaults to the [pool idle timeout](Self::pool_idle_timeout).
+    pub const fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Sets whether to disable Nagle's algorithm on the connections.
+    pub const fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.tcp_nodelay = nodelay;
+        self
+    }
+
+    /// Sets the timeout to establish a connection.
+    pub const fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long to wait for a connection to an address of the endpoint before also
+    /// connecting to its next address, alternating between IPv6 and IPv4 as described in
+    /// [RFC 6555](https://tools.ietf.org/html/rfc6555), or `None` to try the addresses one after
+    /// another.
+    pub const fn happy_eyeballs_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.happy_eyeballs_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the interval of the HTTP/2 pings keeping the connections alive.
+    pub const fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Sets how long to wait for the acknowledgement of an HTTP/2 keep-alive ping before closing
+    /// the connection.
+    pub const fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.http2_keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether to send HTTP/2 keep-alive pings on connections without in-flight requests.
+    pub const fn http2_keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.http2_keep_alive_while_idle = enabled;
+        self
+    }
+
+    /// Returns the builder of a client with the options that don't depend on the connector.
+    pub(crate) fn client_builder(&self) -> hyper_util::client::legacy::Builder {
+        let mut builder = Client::builder(TokioExecutor::new());
+        builder.timer(TokioTimer::new()).pool_timer(TokioTimer::new());
+        if self.http2_prior_knowledge {
+            builder.http2_only(true);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder.http2_keep_alive_timeout(timeout);
+        }
+        builder.http2_keep_alive_while_idle(self.http2_keep_alive_while_idle);
+        builder
+    }
+
+    /// Builds the client, which must be used within a Tokio runtime.
+    pub fn build(&self) -> HyperClient {
+        self.client_builder().build(self.http_connector())
+    }
+
+    /// Builds a client supporting HTTPS with the given TLS options, e.g. to authenticate with a
+    /// client certificate to a gateway enforcing mutual TLS. The client must be used within a
+    /// Tokio runtime.
+    ///
+    /// HTTP/2 is negotiated with the servers, and is the only protocol offered with
+    /// [`http2_prior_knowledge`](Self::http2_prior_knowledge). URLs with the `http` scheme are
+    /// still requested without TLS.
+    #[cfg(feature = "hyper-rustls")]
+    pub fn build_tls(
+        &self,
+        tls: &alloy_transport::TlsConfig,
+    ) -> alloy_transport::TransportResult<HyperTlsClient> {
+        let mut http = self.http_connector();
+        http.enforce_http(false);
+
+        let mut builder = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config(tls.rustls_client_config()?)
+            .https_or_http();
+        if let Some(name) = tls.server_name() {
+            let name = rustls::pki_types::ServerName::try_from(name.to_owned())
+                .map_err(TransportErrorKind::custom)?;
+            builder =
+                builder.with_server_name_resolver(hyper_rustls::FixedServerNameResolver::new(name));
+        }
+        let connector = if self.http2_prior_knowledge {
+            builder.enable_http2().wrap_connector(http)
+        } else {
+            builder.enable_all_versions().wrap_connector(http)
+        };
+        Ok(self.client_builder().build(connector))
+    }
+
+    /// Returns the connector of a client, configured with the TCP options.
+    pub(crate) fn http_connector(&self) -> HttpConnector {
+        let mut connector = HttpConnector::new();
+        // Same default as `Builder::build_http`, when connections are pooled.
+        let pooled = self.pool_max_idle_per_host != Some(0);
+        connector.set_keepalive(self.tcp_keepalive.or_else(|| {
+            pooled.then(|| self.pool_idle_timeout.unwrap_or(DEFAULT_POOL_IDLE_TIMEOUT))
+        }));
+        connector.set_nodelay(self.tcp_nodelay);
+        connector.set_connect_timeout(self.connect_timeout);
+        if let Some(timeout) = self.happy_eyeballs_timeout {
+            connector.set_happy_eyeballs_timeout(timeout);
+        }
+        connector
+    }
+}
+
 impl<C, B> Http<Client<C, Full<B>>>
 where
     C: Connect + Clone + Send + Sync + 'static,
@@ -146,3 +351,122 @@ where
         self.request_hyper(req)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request, ResponsePayload};
+    use hyper::{server::conn::http2, service::service_fn, Response, Version};
+    use hyper_util::rt::TokioIo;
+    use std::convert::Infallible;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn http2_prior_knowledge() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        // Serves a single connection, which must be HTTP/2.
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let service = service_fn(|request: hyper::Request<hyper::body::Incoming>| async move {
+                assert_eq!(request.version(), Version::HTTP_2);
+                let body = r#"{"jsonrpc":"2.0","id":0,"result":"0x1"}"#;
+                Ok::<_, Infallible>(Response::new(Full::new(Bytes::from_static(body.as_bytes()))))
+            });
+            http2::Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+                .unwrap();
+        });
+
+        let client = HyperClientBuilder::new()
+            .http2_prior_knowledge()
+            .http2_keep_alive_interval(Duration::from_secs(1))
+            .pool_max_idle_per_host(1)
+            .build();
+        let mut transport = Http::with_client(client, url);
+        for _ in 0..3 {
+            let request = Request::new("eth_chainId", Id::Number(0), ()).serialize().unwrap();
+            let ResponsePacket::Single(response) =
+                transport.call(RequestPacket::Single(request)).await.unwrap()
+            else {
+                unreachable!()
+            };
+            assert!(matches!(response.payload, ResponsePayload::Success(_)));
+        }
+    }
+
+    #[cfg(feature = "hyper-rustls")]
+    #[tokio::test]
+    async fn client_certificate() {
+        use alloy_transport::TlsConfig;
+        use hyper::server::conn::http1;
+        use rustls::{
+            pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+            server::WebPkiClientVerifier,
+            RootCertStore, ServerConfig,
+        };
+        use std::sync::Arc;
+
+        const CA: &[u8] = include_bytes!("../../transport/testdata/tls/ca.pem");
+        const SERVER: &[u8] = include_bytes!("../../transport/testdata/tls/server.pem");
+        const SERVER_KEY: &[u8] = include_bytes!("../../transport/testdata/tls/server.key");
+        const CLIENT: &[u8] = include_bytes!("../../transport/testdata/tls/client.pem");
+        const CLIENT_KEY: &[u8] = include_bytes!("../../transport/testdata/tls/client.key");
+
+        // Serves connections authenticated as `node.test` and requiring a client certificate.
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut roots = RootCertStore::empty();
+        roots.add(CertificateDer::from_pem_slice(CA).unwrap()).unwrap();
+        let verifier = WebPkiClientVerifier::builder_with_provider(roots.into(), provider.clone())
+            .build()
+            .unwrap();
+        let config = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(
+                vec![CertificateDer::from_pem_slice(SERVER).unwrap()],
+                PrivateKeyDer::from_pem_slice(SERVER_KEY).unwrap(),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: url::Url = format!("https://{}", listener.local_addr().unwrap()).parse().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let Ok(stream) = acceptor.accept(stream).await else { continue };
+                assert_eq!(stream.get_ref().1.server_name(), Some("node.test"));
+                let service = service_fn(|_| async move {
+                    let body = r#"{"jsonrpc":"2.0","id":0,"result":"0x1"}"#;
+                    Ok::<_, Infallible>(Response::new(Full::new(Bytes::from_static(
+                        body.as_bytes(),
+                    ))))
+                });
+                tokio::spawn(http1::Builder::new().serve_connection(TokioIo::new(stream), service));
+            }
+        });
+
+        let tls = TlsConfig::new()
+            .with_root_certificate(CA)
+            .without_builtin_roots()
+            .with_server_name("node.test");
+        let request = || {
+            RequestPacket::Single(
+                Request::new("eth_chainId", Id::Number(0), ()).serialize().unwrap(),
+            )
+        };
+
+        let client = HyperClientBuilder::new()
+            .build_tls(&tls.clone().with_client_identity(CLIENT, CLIENT_KEY))
+            .unwrap();
+        let mut transport = Http::with_client(client, url.clone());
+        transport.call(request()).await.unwrap();
+
+        // The server requires a client certificate.
+        let mut transport =
+            Http::with_client(HyperClientBuilder::new().build_tls(&tls).unwrap(), url);
+        transport.call(request()).await.unwrap_err();
+    }
+}
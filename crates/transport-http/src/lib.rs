@@ -16,12 +16,21 @@ pub use reqwest_transport::*;
 #[cfg(feature = "reqwest")]
 pub use reqwest;
 
+#[cfg(feature = "graphql")]
+pub mod graphql;
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
 mod hyper_transport;
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
 #[doc(inline)]
 pub use hyper_transport::*;
 
+#[cfg(all(unix, feature = "hyper"))]
+mod unix;
+#[cfg(all(unix, feature = "hyper"))]
+#[doc(inline)]
+pub use unix::*;
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
 pub use hyper;
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
@@ -0,0 +1,189 @@
+use crate::{Http, HttpConnect, HyperClientBuilder};
+use alloy_transport::{TransportConnect, TransportError, TransportErrorKind};
+use http_body_util::Full;
+use hyper::{body::Bytes, Uri};
+use hyper_util::{client::legacy::Client, rt::TokioIo};
+use std::{
+    ffi::OsStr,
+    future::Future,
+    io,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task,
+};
+use tokio::net::UnixStream;
+use tower::Service;
+use url::Url;
+
+/// The scheme of the URLs of HTTP endpoints served over a Unix domain socket.
+pub const HTTP_UNIX_SCHEME: &str = "http+unix";
+
+/// A [`hyper`] HTTP client connecting to a Unix domain socket.
+pub type HyperUnixClient = Client<UnixConnector, Full<Bytes>>;
+
+/// An [`Http`] transport using [`hyper`] over a Unix domain socket.
+pub type HyperUnixTransport = Http<HyperUnixClient>;
+
+/// Connection details for a [`HyperUnixTransport`], from an `http+unix://` URL.
+pub type HyperUnixConnect = HttpConnect<HyperUnixTransport>;
+
+/// A [`hyper_util`] connector opening its connections to a Unix domain socket, whatever the host
+/// of the requests.
+#[derive(Clone, Debug)]
+pub struct UnixConnector {
+    path: Arc<Path>,
+}
+
+impl UnixConnector {
+    /// Creates a new connector to the socket at `path`.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().into() }
+    }
+
+    /// Returns the path of the socket.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Service<Uri> for UnixConnector {
+    type Response = TokioIo<UnixStream>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let path = self.path.clone();
+        Box::pin(async move { UnixStream::connect(path).await.map(TokioIo::new) })
+    }
+}
+
+impl HyperClientBuilder {
+    /// Builds a client connecting to the Unix domain socket at `path`, which must be used within
+    /// a Tokio runtime.
+    ///
+    /// The TCP options of the builder are ignored.
+    pub fn build_unix(&self, path: impl AsRef<Path>) -> HyperUnixClient {
+        self.client_builder().build(UnixConnector::new(path))
+    }
+}
+
+impl HyperUnixTransport {
+    /// Creates a new transport to the endpoint of an `http+unix://` URL.
+    ///
+    /// The host of the URL is the percent-encoded path of the socket, and its path and query are
+    /// those of the requests, e.g. `http+unix://%2Fvar%2Frun%2Fnode.sock/rpc` sends the requests to
+    /// `/rpc` over the socket at `/var/run/node.sock`. The requests are sent with a `localhost`
+    /// host, which nodes checking virtual hosts accept by default.
+    pub fn new_unix(url: &Url) -> Result<Self, TransportError> {
+        let (path, url) = split_unix_url(url).ok_or_else(|| {
+            TransportErrorKind::custom_str(&format!("invalid `{HTTP_UNIX_SCHEME}` URL: {url}"))
+        })?;
+        Ok(Self::with_client(HyperClientBuilder::new().build_unix(path), url))
+    }
+}
+
+impl TransportConnect for HyperUnixConnect {
+    type Transport = HyperUnixTransport;
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn get_transport<'a: 'b, 'b>(
+        &'a self,
+    ) -> alloy_transport::Pbf<'b, Self::Transport, TransportError> {
+        Box::pin(async move { Http::new_unix(&self.url) })
+    }
+}
+
+/// Splits an `http+unix://` URL into the path of its socket and the `http://localhost` URL of the
+/// requests, or returns `None` if it is not such a URL.
+///
+/// See [`Http::new_unix`] for the format of the URL.
+pub fn split_unix_url(url: &Url) -> Option<(PathBuf, Url)> {
+    if url.scheme() != HTTP_UNIX_SCHEME {
+        return None;
+    }
+    let path = percent_decode(url.host_str().filter(|host| !host.is_empty())?)?;
+    let path = PathBuf::from(OsStr::from_bytes(&path));
+    let request = Url::parse(&format!("http://localhost{}", &url[url::Position::BeforePath..]));
+    Some((path, request.ok()?))
+}
+
+/// Decodes the percent-encoded bytes of `s`, or returns `None` if an escape is invalid.
+fn percent_decode(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = s.bytes();
+    let mut decoded = Vec::with_capacity(s.len());
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            decoded.push(byte);
+            continue;
+        }
+        let hex = [bytes.next()?, bytes.next()?];
+        decoded.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+    }
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request, RequestPacket, ResponsePacket, ResponsePayload};
+    use hyper::{server::conn::http1, service::service_fn, Response};
+    use std::convert::Infallible;
+    use tokio::net::UnixListener;
+
+    #[test]
+    fn splits_urls() {
+        let url = "http+unix://%2Fvar%2Frun%2Fnode.sock/rpc?key=1".parse().unwrap();
+        assert_eq!(
+            split_unix_url(&url).unwrap(),
+            ("/var/run/node.sock".into(), "http://localhost/rpc?key=1".parse().unwrap())
+        );
+        let url = "http+unix://node.sock".parse().unwrap();
+        assert_eq!(
+            split_unix_url(&url).unwrap(),
+            ("node.sock".into(), "http://localhost/".parse().unwrap())
+        );
+        assert!(split_unix_url(&"http+unix://%2".parse().unwrap()).is_none());
+        assert!(split_unix_url(&"http://localhost:8545".parse().unwrap()).is_none());
+    }
+
+    #[tokio::test]
+    async fn requests_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let service = service_fn(|request: hyper::Request<hyper::body::Incoming>| async move {
+                assert_eq!(request.uri().path(), "/rpc");
+                assert_eq!(request.headers()[hyper::header::HOST], "localhost");
+                let body = r#"{"jsonrpc":"2.0","id":0,"result":"0x1"}"#;
+                Ok::<_, Infallible>(Response::new(Full::new(Bytes::from_static(body.as_bytes()))))
+            });
+            http1::Builder::new().serve_connection(TokioIo::new(stream), service).await.unwrap();
+        });
+
+        let encoded = path.to_str().unwrap().replace('/', "%2F");
+        let connect: HyperUnixConnect =
+            format!("{HTTP_UNIX_SCHEME}://{encoded}/rpc").parse().unwrap();
+        assert!(connect.is_local());
+        let mut transport = connect.get_transport().await.unwrap();
+        for _ in 0..2 {
+            let request = Request::new("eth_chainId", Id::Number(0), ()).serialize().unwrap();
+            let ResponsePacket::Single(response) =
+                transport.call(RequestPacket::Single(request)).await.unwrap()
+            else {
+                unreachable!()
+            };
+            assert!(matches!(response.payload, ResponsePayload::Success(_)));
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use crate::WsBackend;
 use alloy_pubsub::PubSubConnect;
-use alloy_transport::{utils::Spawnable, Authorization, TransportErrorKind, TransportResult};
+use alloy_transport::{
+    utils::Spawnable, Authorization, TlsConfig, TransportErrorKind, TransportResult,
+};
 use futures::{SinkExt, StreamExt};
+use rustls::pki_types::ServerName;
 use serde_json::value::RawValue;
-use std::time::Duration;
-use tokio::time::sleep;
+use std::{sync::Arc, time::Duration};
+use tokio::{net::TcpStream, time::sleep};
+use tokio_rustls::TlsConnector;
 use tokio_tungstenite::{
-    tungstenite::{self, client::IntoClientRequest, Message},
+    tungstenite::{self, client::IntoClientRequest, handshake::client::Request, Message},
     MaybeTlsStream, WebSocketStream,
 };
 
@@ -21,6 +25,8 @@ pub struct WsConnect {
     pub url: String,
     /// The authorization header to use.
     pub auth: Option<Authorization>,
+    /// The TLS options of `wss` connections, or `None` for the defaults.
+    tls: Option<TlsConfig>,
 }
 
 impl WsConnect {
@@ -32,7 +38,19 @@ impl WsConnect {
     /// Creates a new websocket connection configuration with an authorization
     /// header.
     pub fn with_auth<S: Into<String>>(url: S, auth: Option<Authorization>) -> Self {
-        Self { url: url.into(), auth }
+        Self { url: url.into(), auth, tls: None }
+    }
+
+    /// Sets the TLS options of `wss` connections, e.g. a client certificate and the root
+    /// certificate of a private CA.
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Returns the TLS options of `wss` connections, if set with [`with_tls`](Self::with_tls).
+    pub const fn tls(&self) -> Option<&TlsConfig> {
+        self.tls.as_ref()
     }
 }
 
@@ -58,8 +76,10 @@ impl PubSubConnect for WsConnect {
     async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
         let request = self.clone().into_client_request();
         let req = request.map_err(TransportErrorKind::custom)?;
-        let (socket, _) =
-            tokio_tungstenite::connect_async(req).await.map_err(TransportErrorKind::custom)?;
+        let socket = match &self.tls {
+            Some(tls) if req.uri().scheme_str() == Some("wss") => connect_tls(req, tls).await?,
+            _ => tokio_tungstenite::connect_async(req).await.map_err(TransportErrorKind::custom)?.0,
+        };
 
         let (handle, interface) = alloy_pubsub::ConnectionHandle::new();
         let backend = WsBackend { socket, interface };
@@ -70,6 +90,26 @@ impl PubSubConnect for WsConnect {
     }
 }
 
+/// Connects over TLS with the given options, sending the server name of the options if any.
+async fn connect_tls(req: Request, tls: &TlsConfig) -> TransportResult<TungsteniteStream> {
+    let uri = req.uri();
+    let host = uri.host().ok_or_else(|| TransportErrorKind::custom_str("URL has no host"))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = uri.port_u16().unwrap_or(443);
+    let server_name = ServerName::try_from(tls.server_name().unwrap_or(host).to_owned())
+        .map_err(TransportErrorKind::custom)?;
+    let connector = TlsConnector::from(Arc::new(tls.rustls_client_config()?));
+
+    let stream = TcpStream::connect((host, port)).await.map_err(TransportErrorKind::custom)?;
+    stream.set_nodelay(true).map_err(TransportErrorKind::custom)?;
+    let stream =
+        connector.connect(server_name, stream).await.map_err(TransportErrorKind::custom)?;
+    let (socket, _) = tokio_tungstenite::client_async(req, MaybeTlsStream::Rustls(stream))
+        .await
+        .map_err(TransportErrorKind::custom)?;
+    Ok(socket)
+}
+
 impl WsBackend<TungsteniteStream> {
     /// Handle a message from the server.
     #[allow(clippy::result_unit_err)]
@@ -175,3 +215,79 @@ impl WsBackend<TungsteniteStream> {
         fut.spawn_task()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustls::{
+        pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+        server::WebPkiClientVerifier,
+        RootCertStore, ServerConfig,
+    };
+    use tokio::net::TcpListener;
+    use tokio_rustls::TlsAcceptor;
+
+    const CA: &[u8] = include_bytes!("../../transport/testdata/tls/ca.pem");
+    const SERVER: &[u8] = include_bytes!("../../transport/testdata/tls/server.pem");
+    const SERVER_KEY: &[u8] = include_bytes!("../../transport/testdata/tls/server.key");
+    const CLIENT: &[u8] = include_bytes!("../../transport/testdata/tls/client.pem");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../transport/testdata/tls/client.key");
+
+    /// Serves websocket connections authenticated as `node.test` and requiring a client
+    /// certificate, returning the URL of the server.
+    async fn serve() -> String {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut roots = RootCertStore::empty();
+        roots.add(CertificateDer::from_pem_slice(CA).unwrap()).unwrap();
+        let verifier = WebPkiClientVerifier::builder_with_provider(roots.into(), provider.clone())
+            .build()
+            .unwrap();
+        let config = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(
+                vec![CertificateDer::from_pem_slice(SERVER).unwrap()],
+                PrivateKeyDer::from_pem_slice(SERVER_KEY).unwrap(),
+            )
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("wss://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let stream = acceptor.accept(stream).await.ok()?;
+                    assert_eq!(stream.get_ref().1.server_name(), Some("node.test"));
+                    let mut socket = tokio_tungstenite::accept_async(stream).await.ok()?;
+                    while socket.next().await.is_some() {}
+                    Some(())
+                });
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn connects_with_client_certificate() {
+        let url = serve().await;
+        let tls = TlsConfig::new()
+            .with_root_certificate(CA)
+            .without_builtin_roots()
+            .with_server_name("node.test");
+
+        let connect = WsConnect::new(url.clone())
+            .with_tls(tls.clone().with_client_identity(CLIENT, CLIENT_KEY));
+        connect.connect().await.unwrap();
+
+        // The server requires a client certificate.
+        assert!(WsConnect::new(url.clone()).with_tls(tls).connect().await.is_err());
+        // The certificate of the server is not valid for its IP address.
+        let tls =
+            TlsConfig::new().with_root_certificate(CA).with_client_identity(CLIENT, CLIENT_KEY);
+        assert!(WsConnect::new(url).with_tls(tls).connect().await.is_err());
+    }
+}
@@ -94,7 +94,7 @@ impl TransportErrorKind {
 }
 
 /// Type for holding HTTP errors such as 429 rate limit error.
-#[derive(Debug, thiserror::Error)]
+#[derive(Clone, Debug, thiserror::Error)]
 #[error("HTTP error {status} with body: {body}")]
 pub struct HttpError {
     pub status: u16,
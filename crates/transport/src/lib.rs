@@ -15,6 +15,9 @@ pub use connect::{BoxTransportConnect, TransportConnect};
 mod common;
 pub use common::Authorization;
 
+mod tls;
+pub use tls::TlsConfig;
+
 mod error;
 #[doc(hidden)]
 pub use error::TransportErrorKind;
@@ -0,0 +1,181 @@
+use std::fmt;
+
+/// TLS options of the connections of HTTP and WebSocket transports, e.g. to connect to RPC
+/// gateways enforcing mutual TLS.
+///
+/// Certificates and keys are PEM-encoded. By default, servers are authenticated with the built-in
+/// Mozilla root certificates, against the host of the URL, and no client certificate is sent.
+///
+/// The options are applied with `rustls` by the WebSocket transport, and by the `hyper` HTTP
+/// transport with its `hyper-rustls` feature. `reqwest` clients are configured with their own
+/// builder.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy_transport::TlsConfig;
+///
+/// # fn example() -> std::io::Result<()> {
+/// let tls = TlsConfig::new()
+///     .with_client_identity(std::fs::read("client.pem")?, std::fs::read("client.key")?)
+///     .with_root_certificate(std::fs::read("gateway-ca.pem")?)
+///     .with_server_name("rpc.internal");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+    root_certificates: Vec<Vec<u8>>,
+    disable_builtin_roots: bool,
+    server_name: Option<String>,
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("client_identity", &self.identity.is_some())
+            .field("root_certificates", &self.root_certificates.len())
+            .field("disable_builtin_roots", &self.disable_builtin_roots)
+            .field("server_name", &self.server_name)
+            .finish()
+    }
+}
+
+impl TlsConfig {
+    /// Creates a new configuration with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the certificate chain, leaf first, and the private key presented to servers
+    /// requesting client authentication.
+    pub fn with_client_identity(
+        mut self,
+        certificate_chain: impl Into<Vec<u8>>,
+        private_key: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.identity = Some((certificate_chain.into(), private_key.into()));
+        self
+    }
+
+    /// Adds the root certificates of a PEM bundle to the trusted ones, e.g. the certificate of
+    /// the private CA of a gateway.
+    pub fn with_root_certificate(mut self, certificates: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(certificates.into());
+        self
+    }
+
+    /// Only trusts the [added](Self::with_root_certificate) root certificates, not the built-in
+    /// ones.
+    pub const fn without_builtin_roots(mut self) -> Self {
+        self.disable_builtin_roots = true;
+        self
+    }
+
+    /// Sets the server name sent in the SNI extension, and verified against the certificate of
+    /// the server, instead of the host of the URL.
+    ///
+    /// This allows connecting to a gateway by IP address, or through a tunnel, while
+    /// authenticating it with its name.
+    pub fn with_server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.server_name = Some(server_name.into());
+        self
+    }
+
+    /// Returns the overridden server name, if any.
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    /// Returns whether a client certificate is configured.
+    pub const fn has_client_identity(&self) -> bool {
+        self.identity.is_some()
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "rustls"))]
+mod rustls_config {
+    use super::TlsConfig;
+    use crate::{TransportErrorKind, TransportResult};
+    use rustls::{
+        pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+        ClientConfig, RootCertStore,
+    };
+    use std::sync::Arc;
+
+    /// Parses the certificates of a PEM bundle, requiring at least one.
+    fn certificates(pem: &[u8]) -> TransportResult<Vec<CertificateDer<'static>>> {
+        let certificates = CertificateDer::pem_slice_iter(pem)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(TransportErrorKind::custom)?;
+        if certificates.is_empty() {
+            return Err(TransportErrorKind::custom_str("no certificate found in PEM"));
+        }
+        Ok(certificates)
+    }
+
+    impl TlsConfig {
+        /// Builds the [`rustls`] client configuration, using the [`ring`] crypto provider.
+        ///
+        /// The server name is not part of it, and must be passed when connecting.
+        ///
+        /// [`ring`]: rustls::crypto::ring
+        pub fn rustls_client_config(&self) -> TransportResult<ClientConfig> {
+            let mut roots = RootCertStore::empty();
+            if !self.disable_builtin_roots {
+                roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+            }
+            for pem in &self.root_certificates {
+                for certificate in certificates(pem)? {
+                    roots.add(certificate).map_err(TransportErrorKind::custom)?;
+                }
+            }
+
+            let provider = Arc::new(rustls::crypto::ring::default_provider());
+            let builder = ClientConfig::builder_with_provider(provider)
+                .with_safe_default_protocol_versions()
+                .map_err(TransportErrorKind::custom)?
+                .with_root_certificates(roots);
+            match &self.identity {
+                Some((chain, key)) => {
+                    let key =
+                        PrivateKeyDer::from_pem_slice(key).map_err(TransportErrorKind::custom)?;
+                    builder
+                        .with_client_auth_cert(certificates(chain)?, key)
+                        .map_err(TransportErrorKind::custom)
+                }
+                None => Ok(builder.with_no_client_auth()),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const CA: &[u8] = include_bytes!("../testdata/tls/ca.pem");
+        const CLIENT: &[u8] = include_bytes!("../testdata/tls/client.pem");
+        const CLIENT_KEY: &[u8] = include_bytes!("../testdata/tls/client.key");
+
+        #[test]
+        fn builds_rustls_config() {
+            let config = TlsConfig::new().rustls_client_config().unwrap();
+            assert!(!config.client_auth_cert_resolver.has_certs());
+
+            let config = TlsConfig::new()
+                .with_client_identity(CLIENT, CLIENT_KEY)
+                .with_root_certificate(CA)
+                .without_builtin_roots()
+                .rustls_client_config()
+                .unwrap();
+            assert!(config.client_auth_cert_resolver.has_certs());
+
+            let invalid = TlsConfig::new().with_root_certificate("not a certificate");
+            assert!(invalid.rustls_client_config().is_err());
+            // The key doesn't match the certificate.
+            let mismatched = TlsConfig::new().with_client_identity(CA, CLIENT_KEY);
+            assert!(mismatched.rustls_client_config().is_err());
+        }
+    }
+}
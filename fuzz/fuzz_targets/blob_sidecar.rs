@@ -0,0 +1,7 @@
+#![no_main]
+
+use alloy_consensus::BlobTransactionSidecar;
+use alloy_fuzz::rlp_roundtrip;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| rlp_roundtrip::<BlobTransactionSidecar>(data));
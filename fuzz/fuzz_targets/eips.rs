@@ -0,0 +1,22 @@
+//! Decodes the RLP types of `alloy-eips`, the first byte of the input selecting the type.
+
+#![no_main]
+
+use alloy_eips::{
+    eip1898::BlockHashOrNumber, eip2930::AccessList, eip4895::Withdrawal, eip6110::DepositRequest,
+    eip7002::WithdrawalRequest, eip7251::ConsolidationRequest,
+};
+use alloy_fuzz::rlp_roundtrip;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((ty, data)) = data.split_first() else { return };
+    match ty % 6 {
+        0 => rlp_roundtrip::<AccessList>(data),
+        1 => rlp_roundtrip::<Withdrawal>(data),
+        2 => rlp_roundtrip::<DepositRequest>(data),
+        3 => rlp_roundtrip::<WithdrawalRequest>(data),
+        4 => rlp_roundtrip::<ConsolidationRequest>(data),
+        _ => rlp_roundtrip::<BlockHashOrNumber>(data),
+    }
+});
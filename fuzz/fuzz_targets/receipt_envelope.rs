@@ -0,0 +1,11 @@
+#![no_main]
+
+use alloy_consensus::{ReceiptEnvelope, ReceiptWithBloom};
+use alloy_fuzz::{eip2718_roundtrip, rlp_roundtrip};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    eip2718_roundtrip::<ReceiptEnvelope>(data);
+    rlp_roundtrip::<ReceiptEnvelope>(data);
+    rlp_roundtrip::<ReceiptWithBloom>(data);
+});
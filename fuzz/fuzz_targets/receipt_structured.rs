@@ -0,0 +1,12 @@
+//! Encodes arbitrary receipts, checking that the decoders accept everything the encoders produce.
+
+#![no_main]
+
+use alloy_consensus::ReceiptEnvelope;
+use alloy_fuzz::{check_eip2718_encoding, check_rlp_encoding};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|receipt: ReceiptEnvelope| {
+    check_eip2718_encoding(&receipt);
+    check_rlp_encoding(&receipt);
+});
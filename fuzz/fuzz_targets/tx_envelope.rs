@@ -0,0 +1,10 @@
+#![no_main]
+
+use alloy_consensus::TxEnvelope;
+use alloy_fuzz::{eip2718_roundtrip, rlp_roundtrip};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    eip2718_roundtrip::<TxEnvelope>(data);
+    rlp_roundtrip::<TxEnvelope>(data);
+});
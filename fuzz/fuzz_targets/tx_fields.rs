@@ -0,0 +1,17 @@
+//! Decodes the unsigned fields of a transaction, the first byte of the input selecting its type.
+
+#![no_main]
+
+use alloy_consensus::{TxEip1559, TxEip2930, TxEip4844, TxLegacy};
+use alloy_fuzz::rlp_roundtrip;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((ty, data)) = data.split_first() else { return };
+    match ty % 4 {
+        0 => rlp_roundtrip::<TxLegacy>(data),
+        1 => rlp_roundtrip::<TxEip2930>(data),
+        2 => rlp_roundtrip::<TxEip1559>(data),
+        _ => rlp_roundtrip::<TxEip4844>(data),
+    }
+});
@@ -0,0 +1,81 @@
+//! Differential checks shared by the fuzz targets.
+//!
+//! Decoders must never panic, whatever their input. Every value they accept must re-encode to
+//! exactly the length it reports, and decode back to itself from that encoding.
+
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_rlp::{Decodable, Encodable};
+use core::fmt::Debug;
+
+/// Checks the RLP decoder of `T` against its encoder.
+///
+/// Inputs rejected by the decoder are ignored, as long as the decoder does not panic.
+pub fn rlp_roundtrip<T>(mut data: &[u8])
+where
+    T: Decodable + Encodable + PartialEq + Debug,
+{
+    let Ok(value) = T::decode(&mut data) else { return };
+    check_rlp_encoding(&value);
+}
+
+/// Checks that encoding `value` and decoding the result yields `value` again.
+pub fn check_rlp_encoding<T>(value: &T)
+where
+    T: Decodable + Encodable + PartialEq + Debug,
+{
+    let encoded = alloy_rlp::encode(value);
+    assert_eq!(encoded.len(), value.length(), "encoded length mismatch for {value:?}");
+
+    let mut buf = &encoded[..];
+    let decoded = T::decode(&mut buf).expect("failed to decode an encoded value");
+    assert!(buf.is_empty(), "decoding left {} trailing bytes", buf.len());
+    assert_eq!(&decoded, value);
+    assert_eq!(alloy_rlp::encode(&decoded), encoded, "encoding is not stable");
+}
+
+/// Checks the [EIP-2718] and network decoders of `T` against their encoders.
+///
+/// The input is decoded both as an [EIP-2718] envelope and in its network form, as found in a
+/// block body or a `PooledTransactions` message. Inputs rejected by the decoders are ignored, as
+/// long as they do not panic.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub fn eip2718_roundtrip<T>(data: &[u8])
+where
+    T: Decodable2718 + Encodable2718 + PartialEq + Debug,
+{
+    if let Ok(value) = T::decode_2718(&mut &data[..]) {
+        check_eip2718_encoding(&value);
+    }
+    if let Ok(value) = T::network_decode(&mut &data[..]) {
+        check_eip2718_encoding(&value);
+    }
+    if let Ok(values) = T::network_decode_list(&mut &data[..]) {
+        values.iter().flatten().for_each(check_eip2718_encoding);
+    }
+}
+
+/// Checks that encoding `value` as an [EIP-2718] envelope, and in its network form, and decoding
+/// the results yields `value` again.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub fn check_eip2718_encoding<T>(value: &T)
+where
+    T: Decodable2718 + Encodable2718 + PartialEq + Debug,
+{
+    let encoded = value.encoded_2718();
+    assert_eq!(encoded.len(), value.encode_2718_len(), "encoded length mismatch for {value:?}");
+
+    let mut buf = &encoded[..];
+    let decoded = T::decode_2718(&mut buf).expect("failed to decode an encoded envelope");
+    assert!(buf.is_empty(), "decoding left {} trailing bytes", buf.len());
+    assert_eq!(&decoded, value);
+    assert_eq!(decoded.encoded_2718(), encoded, "encoding is not stable");
+
+    let mut network = Vec::new();
+    value.network_encode(&mut network);
+    let mut buf = &network[..];
+    let decoded = T::network_decode(&mut buf).expect("failed to decode a network encoding");
+    assert!(buf.is_empty(), "network decoding left {} trailing bytes", buf.len());
+    assert_eq!(&decoded, value);
+}
@@ -6,6 +6,7 @@ no_std_packages=(
     alloy-genesis
     alloy-serde
     alloy-consensus
+    "alloy-consensus --features serde,serde-bincode-compat"
 )
 
 for package in "${no_std_packages[@]}"; do